@@ -13,7 +13,7 @@ use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 use std::sync::Arc;
 
 use crate::db::DatabaseService;
-use crate::engine::{DeckAtomics, LevelAtomics, LinkedStemAtomics, SlicerAtomics};
+use crate::engine::{DeckAtomics, LevelAtomics, LinkedStemAtomics, MasterClockAtomics, SamplerAtomics, SlicerAtomics};
 use crate::loader::LinkedStemResultReceiver;
 use crate::types::NUM_DECKS;
 
@@ -64,6 +64,10 @@ pub struct AudioSystemResult {
     pub clip_indicator: Arc<AtomicBool>,
     /// Lock-free peak level atomics for UI metering
     pub level_atomics: Arc<LevelAtomics>,
+    /// Sampler slot state (loaded/playing) for pads and LEDs
+    pub sampler_atomics: Arc<SamplerAtomics>,
+    /// Master transport clock for MIDI clock output
+    pub master_clock: Arc<MasterClockAtomics>,
     /// Sample rate of the audio system
     pub sample_rate: u32,
     /// Actual buffer size in frames
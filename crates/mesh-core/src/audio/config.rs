@@ -5,6 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::engine::{line_input_slot, LineInput};
+use crate::types::NUM_DECKS;
+
 /// Maximum buffer size to pre-allocate (covers typical configurations)
 /// Common values: 64, 128, 256, 512, 1024, 2048, 4096 frames
 pub const MAX_BUFFER_SIZE: usize = 8192;
@@ -151,6 +154,25 @@ pub struct AudioConfig {
 
     /// Preferred sample rate (None = use device default, typically 44100 or 48000)
     pub sample_rate: Option<u32>,
+
+    /// Timecode inputs to open (0 = none)
+    /// Input N feeds deck N's timecode decoder (DVS).
+    #[serde(default)]
+    pub input_count: usize,
+
+    /// Open a stereo input for the mixer's mic channel
+    #[serde(default)]
+    pub mic_input: bool,
+
+    /// Open a stereo input for the mixer's aux line channel
+    #[serde(default)]
+    pub aux_input: bool,
+
+    /// Input device for the stereo inputs (None = use system default)
+    /// Used by CPAL backend; channel pairs follow `input_slots()`:
+    /// channels 1-2 are the first input, 3-4 the second, etc.
+    #[serde(default)]
+    pub input_device: Option<DeviceId>,
 }
 
 impl Default for AudioConfig {
@@ -164,6 +186,10 @@ impl Default for AudioConfig {
             cue_pair_index: None,
             buffer_size: BufferSize::default(),
             sample_rate: None,
+            input_count: 0,
+            mic_input: false,
+            aux_input: false,
+            input_device: None,
         }
     }
 }
@@ -228,6 +254,33 @@ impl AudioConfig {
         self
     }
 
+    /// Open `count` stereo inputs (e.g. one per DVS deck)
+    pub fn with_inputs(mut self, count: usize) -> Self {
+        self.input_count = count;
+        self
+    }
+
+    /// Open stereo inputs for the mixer's mic and/or aux channel
+    pub fn with_line_inputs(mut self, mic: bool, aux: bool) -> Self {
+        self.mic_input = mic;
+        self.aux_input = aux;
+        self
+    }
+
+    /// Engine input index of each stereo input to open, in device order
+    ///
+    /// Timecode inputs come first, then mic and aux.
+    pub fn input_slots(&self) -> Vec<usize> {
+        let mut slots: Vec<usize> = (0..self.input_count.min(NUM_DECKS)).collect();
+        if self.mic_input {
+            slots.push(line_input_slot(LineInput::Mic));
+        }
+        if self.aux_input {
+            slots.push(line_input_slot(LineInput::Aux));
+        }
+        slots
+    }
+
     /// Enable low-latency mode
     pub fn with_low_latency(mut self) -> Self {
         self.buffer_size = BufferSize::LowLatency;
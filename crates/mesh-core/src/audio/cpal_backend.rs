@@ -43,6 +43,12 @@
 //!                    │  (independent thread) │      never blocks on master
 //!                    └───────────────────────┘
 //! ```
+//!
+//! ## Inputs (DVS, mic, aux)
+//! When timecode or line inputs are configured, an input stream captures
+//! channel pairs of the input device into a second ring buffer, pair N
+//! feeding the engine input `input_slots()[N]`. The master stream drains it
+//! into the engine's input buffers before processing, in either output mode.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -51,10 +57,10 @@ use cpal::traits::{DeviceTrait, StreamTrait};
 use cpal::{BufferSize as CpalBufferSize, SampleFormat, Stream, StreamConfig};
 
 use super::config::{AudioConfig, BufferSize, OutputMode, DEFAULT_BUFFER_SIZE, MAX_BUFFER_SIZE};
-use super::device::{find_device_by_id, get_cpal_default_device};
+use super::device::{find_device_by_id, find_input_device, get_cpal_default_device};
 use super::error::{AudioError, AudioResult};
 use crate::db::DatabaseService;
-use crate::engine::{command_channel, AudioEngine, EngineCommand};
+use crate::engine::{command_channel, AudioEngine, EngineCommand, NUM_INPUTS};
 use crate::types::{StereoBuffer, StereoSample};
 
 use super::backend::{AudioHandle, AudioSystemResult, CommandSender, StereoPair};
@@ -67,6 +73,8 @@ pub struct CpalAudioHandle {
     master_stream: Stream,
     /// Cue output stream (only present in MasterAndCue mode)
     cue_stream: Option<Stream>,
+    /// Input stream (only present when inputs are configured)
+    input_stream: Option<Stream>,
     /// Sample rate of the audio system
     sample_rate: u32,
     /// Actual buffer size in frames (as negotiated with the device)
@@ -99,6 +107,11 @@ impl CpalAudioHandle {
                 log::warn!("Failed to pause cue stream: {}", e);
             }
         }
+        if let Some(ref input) = self.input_stream {
+            if let Err(e) = input.pause() {
+                log::warn!("Failed to pause input stream: {}", e);
+            }
+        }
     }
 
     /// Resume the audio stream
@@ -111,6 +124,11 @@ impl CpalAudioHandle {
                 log::warn!("Failed to resume cue stream: {}", e);
             }
         }
+        if let Some(ref input) = self.input_stream {
+            if let Err(e) = input.play() {
+                log::warn!("Failed to resume input stream: {}", e);
+            }
+        }
     }
 }
 
@@ -180,6 +198,8 @@ fn start_master_only(
     let linked_stem_receiver = engine.linked_stem_result_receiver();
     let clip_indicator = engine.clip_indicator();
     let level_atomics = engine.level_atomics();
+    let sampler_atomics = engine.sampler_atomics();
+    let master_clock = engine.master_clock();
     let output_latency_samples = engine.output_latency_samples();
     let internal_latency_samples = engine.internal_latency_samples();
 
@@ -189,8 +209,11 @@ fn start_master_only(
     // Create direct command channel for timing-critical MIDI commands
     let (direct_tx, direct_rx) = rtrb::RingBuffer::<EngineCommand>::new(64);
 
+    // Open the stereo inputs (optional: decks just see no timecode without them)
+    let (input_stream, input_rx) = start_inputs(config, sample_rate, buffer_size);
+
     // Create the audio callback state (lock-free triple buffer approach)
-    let callback_state = AudioCallbackState::new(engine, command_rx, direct_rx, OutputMode::MasterOnly);
+    let callback_state = AudioCallbackState::new(engine, command_rx, direct_rx, input_rx, OutputMode::MasterOnly);
     let callback_state = Arc::new(std::sync::Mutex::new(callback_state));
 
     // Build the stream (pass output latency atomic for real-time measurement)
@@ -207,6 +230,7 @@ fn start_master_only(
     let handle = CpalAudioHandle {
         master_stream: stream,
         cue_stream: None,
+        input_stream,
         sample_rate,
         buffer_size,
     };
@@ -221,6 +245,8 @@ fn start_master_only(
         linked_stem_receiver,
         clip_indicator,
         level_atomics,
+        sampler_atomics,
+        master_clock,
         sample_rate,
         buffer_size,
         latency_ms,
@@ -311,6 +337,8 @@ fn start_master_and_cue(
     let linked_stem_receiver = engine.linked_stem_result_receiver();
     let clip_indicator = engine.clip_indicator();
     let level_atomics = engine.level_atomics();
+    let sampler_atomics = engine.sampler_atomics();
+    let master_clock = engine.master_clock();
     let output_latency_samples = engine.output_latency_samples();
     let internal_latency_samples = engine.internal_latency_samples();
 
@@ -330,8 +358,11 @@ fn start_master_and_cue(
         cue_buffer_capacity
     );
 
+    // Open the stereo inputs (optional: decks just see no timecode without them)
+    let (input_stream, input_rx) = start_inputs(config, sample_rate, buffer_size);
+
     // Create callback state for master stream (owns the engine)
-    let callback_state = AudioCallbackState::new(engine, command_rx, direct_rx, OutputMode::MasterAndCue);
+    let callback_state = AudioCallbackState::new(engine, command_rx, direct_rx, input_rx, OutputMode::MasterAndCue);
     let callback_state = Arc::new(std::sync::Mutex::new(callback_state));
 
     // Build master stream with cue producer (pass output latency atomic for measurement)
@@ -360,6 +391,7 @@ fn start_master_and_cue(
     let handle = CpalAudioHandle {
         master_stream,
         cue_stream: Some(cue_stream),
+        input_stream,
         sample_rate,
         buffer_size,
     };
@@ -374,6 +406,8 @@ fn start_master_and_cue(
         linked_stem_receiver,
         clip_indicator,
         level_atomics,
+        sampler_atomics,
+        master_clock,
         sample_rate,
         buffer_size,
         latency_ms,
@@ -398,6 +432,8 @@ struct AudioCallbackState {
     master_buffer: StereoBuffer,
     /// Pre-allocated cue buffer
     cue_buffer: StereoBuffer,
+    /// Captured input frames, indexed by engine input
+    input_rx: Option<rtrb::Consumer<InputFrame>>,
 }
 
 impl AudioCallbackState {
@@ -405,6 +441,7 @@ impl AudioCallbackState {
         engine: AudioEngine,
         command_rx: rtrb::Consumer<EngineCommand>,
         direct_command_rx: rtrb::Consumer<EngineCommand>,
+        input_rx: Option<rtrb::Consumer<InputFrame>>,
         _output_mode: OutputMode,
     ) -> Self {
         Self {
@@ -413,6 +450,7 @@ impl AudioCallbackState {
             direct_command_rx,
             master_buffer: StereoBuffer::silence(MAX_BUFFER_SIZE),
             cue_buffer: StereoBuffer::silence(MAX_BUFFER_SIZE),
+            input_rx,
         }
     }
 
@@ -427,6 +465,23 @@ impl AudioCallbackState {
         // Process direct MIDI commands (timing-critical, bypasses iced tick)
         self.engine.process_commands(&mut self.direct_command_rx);
 
+        // Hand captured input to the engine (silence where the input stream lags)
+        if let Some(input_rx) = &mut self.input_rx {
+            // The input device runs on its own clock: drop a backlog rather
+            // than let the timecode latency grow
+            let backlog = input_rx.slots().saturating_sub(n_frames * 2);
+            for _ in 0..backlog {
+                let _ = input_rx.pop();
+            }
+            let inputs = self.engine.inputs_mut(n_frames);
+            for i in 0..n_frames {
+                let Ok(frame) = input_rx.pop() else { break };
+                for (buffer, sample) in inputs.iter_mut().zip(frame) {
+                    buffer.as_mut_slice()[i] = sample;
+                }
+            }
+        }
+
         // Process audio through the engine
         self.engine
             .process(&mut self.master_buffer, &mut self.cue_buffer);
@@ -458,6 +513,107 @@ impl AudioCallbackState {
     }
 }
 
+/// One captured frame: a stereo sample per engine input (silence if unused)
+type InputFrame = [StereoSample; NUM_INPUTS];
+
+/// Open and start the configured stereo inputs
+///
+/// Inputs are optional, so failures are logged rather than returned: the
+/// decks then simply see no timecode and the line channels stay silent.
+/// The input device must run at the output sample rate (no resampling on
+/// this path).
+fn start_inputs(
+    config: &AudioConfig,
+    sample_rate: u32,
+    buffer_size: u32,
+) -> (Option<Stream>, Option<rtrb::Consumer<InputFrame>>) {
+    let mut slots = config.input_slots();
+    if slots.is_empty() {
+        return (None, None);
+    }
+
+    let device = match find_input_device(config.input_device.as_ref()) {
+        Ok(device) => device,
+        Err(e) => {
+            log::warn!("No input device, inputs disabled: {}", e);
+            return (None, None);
+        }
+    };
+    let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+
+    // Widest f32 config at the output rate
+    let supported = device
+        .supported_input_configs()
+        .map(|configs| {
+            configs
+                .filter(|c| c.sample_format() == SampleFormat::F32 && c.channels() >= 2)
+                .filter(|c| sample_rate >= c.min_sample_rate().0 && sample_rate <= c.max_sample_rate().0)
+                .max_by_key(|c| c.channels())
+        })
+        .ok()
+        .flatten();
+    let Some(supported) = supported else {
+        log::warn!(
+            "Input device {} has no stereo f32 config at {}Hz, inputs disabled",
+            device_name,
+            sample_rate
+        );
+        return (None, None);
+    };
+
+    let channels = supported.channels() as usize;
+    if slots.len() > channels / 2 {
+        log::warn!(
+            "Input device {} has {} stereo pairs, {} inputs requested",
+            device_name,
+            channels / 2,
+            slots.len()
+        );
+        slots.truncate(channels / 2);
+    }
+    let count = slots.len();
+    let stream_config = StreamConfig {
+        channels: supported.channels(),
+        sample_rate: cpal::SampleRate(sample_rate),
+        buffer_size: buffer_size_to_cpal(buffer_size),
+    };
+
+    let (mut producer, consumer) = rtrb::RingBuffer::<InputFrame>::new(buffer_size as usize * 4);
+    let stream = device.build_input_stream(
+        &stream_config,
+        move |data: &[f32], _info: &cpal::InputCallbackInfo| {
+            for frame in data.chunks(channels) {
+                let mut captured = [StereoSample::silence(); NUM_INPUTS];
+                for (pair, &slot) in slots.iter().enumerate() {
+                    captured[slot] = StereoSample::new(frame[pair * 2], frame[pair * 2 + 1]);
+                }
+                if producer.push(captured).is_err() {
+                    break; // Master stream stalled - drop rather than block
+                }
+            }
+        },
+        move |err| {
+            log::error!("Input audio stream error: {}", err);
+        },
+        None,
+    );
+
+    let stream = match stream {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::warn!("Failed to build input stream, inputs disabled: {}", e);
+            return (None, None);
+        }
+    };
+    if let Err(e) = stream.play() {
+        log::warn!("Failed to start input stream, inputs disabled: {}", e);
+        return (None, None);
+    }
+
+    log::info!("Input device: {} ({} stereo inputs)", device_name, count);
+    (Some(stream), Some(consumer))
+}
+
 /// Convert our BufferSize to CPAL's BufferSize
 fn buffer_size_to_cpal(frames: u32) -> CpalBufferSize {
     CpalBufferSize::Fixed(frames)
@@ -215,6 +215,32 @@ pub fn get_cpal_default_device() -> AudioResult<cpal::Device> {
         .ok_or_else(|| AudioError::NoDefaultDevice("No default output device".to_string()))
 }
 
+/// Find a CPAL input device by ID, or the default input device for None
+pub fn find_input_device(id: Option<&DeviceId>) -> AudioResult<cpal::Device> {
+    let Some(id) = id else {
+        return get_default_host()
+            .default_input_device()
+            .ok_or_else(|| AudioError::NoDefaultDevice("No default input device".to_string()));
+    };
+
+    let hosts: Vec<Host> = match id.host.as_deref().and_then(get_host_by_name) {
+        Some(host) => vec![host],
+        None => cpal::available_hosts()
+            .into_iter()
+            .filter_map(|host_id| cpal::host_from_id(host_id).ok())
+            .collect(),
+    };
+    for host in hosts {
+        if let Ok(mut devices) = host.input_devices() {
+            if let Some(device) = devices.find(|d| d.name().ok().as_ref() == Some(&id.name)) {
+                return Ok(device);
+            }
+        }
+    }
+
+    Err(AudioError::DeviceNotFound(id.name.clone()))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Simplified Device Types for UI (Settings Dropdowns)
 // ═══════════════════════════════════════════════════════════════════════════
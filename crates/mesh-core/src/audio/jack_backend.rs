@@ -7,6 +7,8 @@
 //!
 //! - **Port enumeration**: See all JACK ports (e.g., "system:playback_1-2")
 //! - **Flexible routing**: Route master and cue to different port pairs
+//! - **Inputs**: Optional `in_N_left`/`in_N_right` capture ports (DVS timecode)
+//!   and `mic_*`/`aux_*` ports for the mixer's line inputs
 //! - **Lock-free design**: Same architecture as CPAL backend
 //! - **Pro-audio support**: Works with PipeWire's JACK compatibility layer
 //!
@@ -29,13 +31,13 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use jack::{AudioOut, Client, ClientOptions, Control, Port, ProcessScope};
+use jack::{AudioIn, AudioOut, Client, ClientOptions, Control, Port, ProcessScope};
 
 use super::backend::{AudioHandle, AudioSystemResult, CommandSender, StereoPair};
 use super::config::AudioConfig;
 use super::error::{AudioError, AudioResult};
 use crate::db::DatabaseService;
-use crate::engine::{command_channel, AudioEngine, EngineCommand};
+use crate::engine::{command_channel, line_input_slot, AudioEngine, EngineCommand, LineInput};
 use crate::types::StereoBuffer;
 
 /// Maximum buffer size to pre-allocate (covers all JACK configurations)
@@ -47,6 +49,18 @@ const MASTER_RIGHT: &str = "master_right";
 const CUE_LEFT: &str = "cue_left";
 const CUE_RIGHT: &str = "cue_right";
 
+/// JACK input port names for an engine input
+///
+/// Timecode inputs are `in_N_*` (N = deck number), line inputs `mic_*` and
+/// `aux_*`.
+fn input_port_names(slot: usize) -> (String, String) {
+    let name = match LineInput::ALL.into_iter().find(|&input| line_input_slot(input) == slot) {
+        Some(input) => input.label().to_lowercase(),
+        None => format!("in_{}", slot + 1),
+    };
+    (format!("{}_left", name), format!("{}_right", name))
+}
+
 /// JACK-specific audio handle
 ///
 /// Keeps the JACK client active. Drop this to disconnect from JACK.
@@ -92,6 +106,8 @@ struct JackProcessor {
     master_right: Port<AudioOut>,
     cue_left: Port<AudioOut>,
     cue_right: Port<AudioOut>,
+    /// Stereo input ports and the engine input each pair feeds
+    inputs: Vec<(usize, Port<AudioIn>, Port<AudioIn>)>,
     /// The audio engine (OWNED, not shared)
     engine: AudioEngine,
     /// Command receiver (consumer side of lock-free queue)
@@ -129,6 +145,20 @@ impl jack::ProcessHandler for JackProcessor {
         // Process direct MIDI commands (timing-critical, bypasses iced tick)
         self.engine.process_commands(&mut self.direct_command_rx);
 
+        // Hand input ports to the engine
+        if !self.inputs.is_empty() {
+            let buffers = self.engine.inputs_mut(n_frames);
+            for (slot, left, right) in &self.inputs {
+                let buffer = &mut buffers[*slot];
+                let left = left.as_slice(ps);
+                let right = right.as_slice(ps);
+                for (i, sample) in buffer.as_mut_slice().iter_mut().enumerate() {
+                    sample.left = left[i];
+                    sample.right = right[i];
+                }
+            }
+        }
+
         // Process audio through the engine
         self.engine
             .process(&mut self.master_buffer, &mut self.cue_buffer);
@@ -222,6 +252,20 @@ pub fn start_audio_system(
         .register_port(CUE_RIGHT, AudioOut::default())
         .map_err(|e| AudioError::ConfigError(format!("Failed to register port: {}", e)))?;
 
+    // Register input ports
+    let input_slots = config.input_slots();
+    let mut inputs = Vec::new();
+    for &slot in &input_slots {
+        let (left_name, right_name) = input_port_names(slot);
+        let left = client
+            .register_port(&left_name, AudioIn::default())
+            .map_err(|e| AudioError::ConfigError(format!("Failed to register port: {}", e)))?;
+        let right = client
+            .register_port(&right_name, AudioIn::default())
+            .map_err(|e| AudioError::ConfigError(format!("Failed to register port: {}", e)))?;
+        inputs.push((slot, left, right));
+    }
+
     // Create engine with JACK's sample rate
     let engine = AudioEngine::new_with_sample_rate(sample_rate, db_service);
     let deck_atomics = engine.deck_atomics();
@@ -230,6 +274,8 @@ pub fn start_audio_system(
     let linked_stem_receiver = engine.linked_stem_result_receiver();
     let clip_indicator = engine.clip_indicator();
     let level_atomics = engine.level_atomics();
+    let sampler_atomics = engine.sampler_atomics();
+    let master_clock = engine.master_clock();
     let output_latency_samples = engine.output_latency_samples();
     let internal_latency_samples = engine.internal_latency_samples();
 
@@ -245,6 +291,7 @@ pub fn start_audio_system(
         master_right,
         cue_left,
         cue_right,
+        inputs,
         engine,
         command_rx,
         direct_command_rx: direct_rx,
@@ -277,6 +324,9 @@ pub fn start_audio_system(
             log::warn!("Auto-connect failed: {}", e);
         }
     }
+    if !input_slots.is_empty() {
+        connect_inputs(&actual_client_name, &input_slots);
+    }
 
     let latency_ms = (buffer_size as f32 / sample_rate as f32) * 1000.0;
 
@@ -296,6 +346,8 @@ pub fn start_audio_system(
         linked_stem_receiver,
         clip_indicator,
         level_atomics,
+        sampler_atomics,
+        master_clock,
         sample_rate,
         buffer_size,
         latency_ms,
@@ -450,6 +502,46 @@ pub fn connect_ports(
     Ok(())
 }
 
+/// Sort key that puts `capture_10` after `capture_9`: the port name without
+/// its trailing number, then the number
+fn port_order(name: &str) -> (&str, u32) {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    (prefix, name[prefix.len()..].parse().unwrap_or(0))
+}
+
+/// Connect input ports to the system capture ports, one pair per input
+///
+/// The first input gets capture_1/2, the second capture_3/4, and so on, in
+/// `input_slots()` order. Inputs without a capture pair stay unconnected and
+/// can be patched by hand.
+fn connect_inputs(client_name: &str, slots: &[usize]) {
+    let client = match Client::new(
+        &format!("{}_connect_in", client_name),
+        ClientOptions::NO_START_SERVER,
+    ) {
+        Ok((client, _)) => client,
+        Err(e) => {
+            log::warn!("Could not connect inputs: {}", e);
+            return;
+        }
+    };
+
+    let mut capture = client.ports(Some(".*:capture_.*"), None, jack::PortFlags::IS_OUTPUT);
+    capture.sort_by(|a, b| port_order(a).cmp(&port_order(b)));
+    for (&slot, pair) in slots.iter().zip(capture.chunks_exact(2)) {
+        let (left, right) = input_port_names(slot);
+        let left_port = format!("{}:{}", client_name, left);
+        let right_port = format!("{}:{}", client_name, right);
+        if let Err(e) = client.connect_ports_by_name(&pair[0], &left_port) {
+            log::warn!("Could not connect {}: {}", left_port, e);
+        }
+        if let Err(e) = client.connect_ports_by_name(&pair[1], &right_port) {
+            log::warn!("Could not connect {}: {}", right_port, e);
+        }
+        log::info!("Connected {} and {} to {} / {}", pair[0], pair[1], left, right);
+    }
+}
+
 /// Reconnect audio outputs to different stereo pairs (hot-swap)
 ///
 /// Disconnects existing connections first, then connects to new pairs.
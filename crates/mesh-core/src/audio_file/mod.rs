@@ -136,6 +136,117 @@ pub struct SavedLoop {
     pub color: Option<String>,
 }
 
+/// A tempo-change marker in a variable-tempo beat grid
+///
+/// Each anchor starts a constant-tempo segment on a beat. The segment runs
+/// until the next anchor (or the end of the track).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TempoAnchor {
+    /// Sample position of the beat where this segment starts
+    pub sample_position: u64,
+    /// Tempo of the segment
+    pub bpm: f64,
+}
+
+impl TempoAnchor {
+    /// Create a new tempo anchor
+    pub fn new(sample_position: u64, bpm: f64) -> Self {
+        Self { sample_position, bpm }
+    }
+}
+
+/// Structural role of a phrase in the song
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionKind {
+    /// Opening phrases before the first drop (usually DJ-friendly, sparse)
+    Intro,
+    /// Tension rising into a drop
+    Build,
+    /// Full-energy section (drums and bass in)
+    Drop,
+    /// Low-energy section between drops (bass or drums out)
+    Breakdown,
+    /// Closing phrases after the last drop
+    Outro,
+}
+
+impl SectionKind {
+    pub const ALL: [SectionKind; 5] = [
+        SectionKind::Intro,
+        SectionKind::Build,
+        SectionKind::Drop,
+        SectionKind::Breakdown,
+        SectionKind::Outro,
+    ];
+
+    /// Stable identifier used for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SectionKind::Intro => "intro",
+            SectionKind::Build => "build",
+            SectionKind::Drop => "drop",
+            SectionKind::Breakdown => "breakdown",
+            SectionKind::Outro => "outro",
+        }
+    }
+
+    /// Parse a stored identifier (see [`SectionKind::as_str`])
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SectionKind::Intro => "Intro",
+            SectionKind::Build => "Build",
+            SectionKind::Drop => "Drop",
+            SectionKind::Breakdown => "Breakdown",
+            SectionKind::Outro => "Outro",
+        }
+    }
+
+    /// Default display color as hex string (same format as cue colors)
+    pub fn color(&self) -> &'static str {
+        match self {
+            SectionKind::Intro => "#458588",
+            SectionKind::Build => "#d79921",
+            SectionKind::Drop => "#cc241d",
+            SectionKind::Breakdown => "#689d6a",
+            SectionKind::Outro => "#b16286",
+        }
+    }
+}
+
+/// A phrase-aligned song section (intro, build, drop, ...)
+///
+/// Sections start on a downbeat and span a whole number of bars. They are
+/// detected during analysis and never overlap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhraseSection {
+    /// First sample of the section (a downbeat)
+    pub start_sample: u64,
+    /// First sample after the section
+    pub end_sample: u64,
+    /// Structural role
+    pub kind: SectionKind,
+    /// Length in bars (typically 8, 16 or 32)
+    pub bars: u32,
+}
+
+/// A stretch of the track in one musical key
+///
+/// Produced by windowed key detection; consecutive segments always differ in
+/// key. A track that never modulates has a single segment.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KeySegment {
+    /// First sample of the segment
+    pub start_sample: u64,
+    /// First sample after the segment
+    pub end_sample: u64,
+    /// Key in the same notation as `TrackMetadata::key` (e.g. "Am", "F#")
+    pub key: String,
+}
+
 /// Beat grid information
 #[derive(Debug, Clone)]
 pub struct BeatGrid {
@@ -143,6 +254,12 @@ pub struct BeatGrid {
     pub beats: Vec<u64>,
     /// First beat sample position (for regeneration from BPM)
     pub first_beat_sample: Option<u64>,
+    /// Tempo-change anchors, sorted by position
+    ///
+    /// Empty for constant-tempo grids (regenerated from `first_beat_sample`
+    /// and the track BPM). When present, `beats` is generated segment by
+    /// segment from these anchors.
+    pub anchors: Vec<TempoAnchor>,
 }
 
 impl BeatGrid {
@@ -151,6 +268,7 @@ impl BeatGrid {
         Self {
             beats: Vec::new(),
             first_beat_sample: None,
+            anchors: Vec::new(),
         }
     }
 
@@ -164,6 +282,7 @@ impl BeatGrid {
         Self {
             beats,
             first_beat_sample,
+            anchors: Vec::new(),
         }
     }
 
@@ -206,7 +325,101 @@ impl BeatGrid {
         Self {
             beats,
             first_beat_sample: Some(first_beat_sample),
+            anchors: Vec::new(),
+        }
+    }
+
+    /// Build a variable-tempo beat grid from tempo anchors
+    ///
+    /// Uses the default SAMPLE_RATE (48kHz). For other sample rates,
+    /// use `from_anchors_with_rate()` instead.
+    pub fn from_anchors(anchors: Vec<TempoAnchor>, duration_samples: u64) -> Self {
+        use crate::types::SAMPLE_RATE;
+        Self::from_anchors_with_rate(anchors, duration_samples, SAMPLE_RATE)
+    }
+
+    /// Build a variable-tempo beat grid from tempo anchors with a specific sample rate
+    ///
+    /// Each anchor starts a new segment at its own BPM; beats are laid out from
+    /// the anchor until the next anchor, which always lands on a beat itself.
+    /// Anchors with a non-positive BPM or past the end of the track are dropped.
+    pub fn from_anchors_with_rate(mut anchors: Vec<TempoAnchor>, duration_samples: u64, sample_rate: u32) -> Self {
+        anchors.retain(|a| a.bpm > 0.0 && a.sample_position < duration_samples);
+        anchors.sort_by_key(|a| a.sample_position);
+        anchors.dedup_by_key(|a| a.sample_position);
+
+        let Some(first) = anchors.first().copied() else {
+            return Self::new();
+        };
+
+        let mut beats = Vec::new();
+        for (i, anchor) in anchors.iter().enumerate() {
+            let samples_per_beat = sample_rate as f64 * 60.0 / anchor.bpm;
+            match anchors.get(i + 1) {
+                Some(next) => {
+                    // Same f64 accumulation as regenerate_with_rate; the next
+                    // anchor's own beat closes this segment
+                    let mut k = 0usize;
+                    loop {
+                        let pos = (anchor.sample_position as f64 + k as f64 * samples_per_beat).round() as u64;
+                        if pos >= next.sample_position {
+                            break;
+                        }
+                        beats.push(pos);
+                        k += 1;
+                    }
+                }
+                None => {
+                    let tail = Self::regenerate_with_rate(anchor.sample_position, anchor.bpm, duration_samples, sample_rate);
+                    beats.extend(tail.beats);
+                }
+            }
         }
+
+        Self {
+            beats,
+            first_beat_sample: Some(first.sample_position),
+            anchors,
+        }
+    }
+
+    /// Whether this grid has more than one tempo segment
+    pub fn is_variable_tempo(&self) -> bool {
+        self.anchors.len() > 1
+    }
+
+    /// Tempo of the segment containing a sample position
+    ///
+    /// Returns None for constant-tempo grids (use the track BPM). Positions
+    /// before the first anchor use the first segment's tempo.
+    pub fn bpm_at_sample(&self, sample: u64) -> Option<f64> {
+        if self.anchors.is_empty() {
+            return None;
+        }
+        let idx = self.anchors.partition_point(|a| a.sample_position <= sample);
+        Some(self.anchors[idx.saturating_sub(1)].bpm)
+    }
+
+    /// Index of the tempo segment containing a sample position
+    pub fn segment_at_sample(&self, sample: u64) -> Option<usize> {
+        if self.anchors.is_empty() {
+            return None;
+        }
+        let idx = self.anchors.partition_point(|a| a.sample_position <= sample);
+        Some(idx.saturating_sub(1))
+    }
+
+    /// Length in samples of the beat containing a sample position
+    ///
+    /// Measured from the grid itself, so it follows tempo changes. Returns
+    /// None when the grid has fewer than two beats.
+    pub fn beat_length_at(&self, sample: u64) -> Option<f64> {
+        if self.beats.len() < 2 {
+            return None;
+        }
+        let right = self.beats.partition_point(|&b| b <= sample);
+        let idx = right.saturating_sub(1).min(self.beats.len() - 2);
+        Some((self.beats[idx + 1] - self.beats[idx]) as f64)
     }
 
     /// Scale all sample positions by a ratio (for sample rate conversion)
@@ -222,6 +435,10 @@ impl BeatGrid {
         if let Some(ref mut fbs) = self.first_beat_sample {
             *fbs = ((*fbs as f64) * ratio).round() as u64;
         }
+        // Scale tempo anchors (BPM is rate-independent)
+        for anchor in &mut self.anchors {
+            anchor.sample_position = ((anchor.sample_position as f64) * ratio).round() as u64;
+        }
     }
 
     /// Get the beat index for a sample position (which beat are we on/past)
@@ -269,6 +486,10 @@ pub struct TrackMetadata {
     /// the drop markers are aligned so that structural elements
     /// (e.g., the drop) play at the same time.
     pub drop_marker: Option<u64>,
+    /// Detected song structure, in track order (empty if not analysed)
+    pub phrases: Vec<PhraseSection>,
+    /// Key timeline, in track order (empty if not analysed)
+    pub key_timeline: Vec<KeySegment>,
     /// Stem link references (prepared links stored in mslk chunk)
     ///
     /// These are pre-configured links to stems from other tracks.
@@ -277,6 +498,18 @@ pub struct TrackMetadata {
 }
 
 impl TrackMetadata {
+    /// Key at the start of the track: the first key segment, falling back
+    /// to the global key when there is no timeline
+    pub fn intro_key(&self) -> Option<&str> {
+        self.key_timeline.first().map(|s| s.key.as_str()).or(self.key.as_deref())
+    }
+
+    /// Key at the end of the track: the last key segment, falling back to
+    /// the global key when there is no timeline
+    pub fn outro_key(&self) -> Option<&str> {
+        self.key_timeline.last().map(|s| s.key.as_str()).or(self.key.as_deref())
+    }
+
     /// Scale all sample-based positions for sample rate conversion
     ///
     /// When audio is resampled from `source_rate` to `target_rate`, all sample
@@ -329,6 +562,18 @@ impl TrackMetadata {
             log::debug!("[METADATA] Drop marker scaled: {} -> {}", old_dm, *dm);
         }
 
+        // Scale phrase sections
+        for phrase in &mut self.phrases {
+            phrase.start_sample = ((phrase.start_sample as f64) * ratio).round() as u64;
+            phrase.end_sample = ((phrase.end_sample as f64) * ratio).round() as u64;
+        }
+
+        // Scale key segments
+        for segment in &mut self.key_timeline {
+            segment.start_sample = ((segment.start_sample as f64) * ratio).round() as u64;
+            segment.end_sample = ((segment.end_sample as f64) * ratio).round() as u64;
+        }
+
         // Scale stem link references (source drop markers)
         for stem_link in &mut self.stem_links {
             stem_link.source_drop_marker =
@@ -341,6 +586,14 @@ impl TrackMetadata {
     /// This is useful after resampling when you want to recalculate beat positions
     /// using the correct samples-per-beat for the new sample rate.
     pub fn regenerate_beat_grid(&mut self, duration_samples: u64, sample_rate: u32) {
+        if !self.beat_grid.anchors.is_empty() {
+            self.beat_grid = BeatGrid::from_anchors_with_rate(
+                self.beat_grid.anchors.clone(),
+                duration_samples,
+                sample_rate,
+            );
+            return;
+        }
         if let Some(bpm) = self.bpm {
             if let Some(first_beat) = self.beat_grid.first_beat_sample {
                 self.beat_grid = BeatGrid::regenerate_with_rate(
@@ -409,11 +662,51 @@ impl From<crate::db::SavedLoop> for SavedLoop {
     }
 }
 
+impl From<crate::db::TempoAnchor> for TempoAnchor {
+    fn from(db_anchor: crate::db::TempoAnchor) -> Self {
+        Self {
+            sample_position: db_anchor.sample_position as u64,
+            bpm: db_anchor.bpm,
+        }
+    }
+}
+
+impl TryFrom<crate::db::PhraseSection> for PhraseSection {
+    type Error = String;
+
+    fn try_from(db_section: crate::db::PhraseSection) -> Result<Self, Self::Error> {
+        let kind = SectionKind::parse(&db_section.kind)
+            .ok_or_else(|| format!("Unknown section kind: {}", db_section.kind))?;
+        Ok(Self {
+            start_sample: db_section.start_sample as u64,
+            end_sample: db_section.end_sample as u64,
+            kind,
+            bars: db_section.bars,
+        })
+    }
+}
+
+impl From<crate::db::KeySegment> for KeySegment {
+    fn from(db_segment: crate::db::KeySegment) -> Self {
+        Self {
+            start_sample: db_segment.start_sample as u64,
+            end_sample: db_segment.end_sample as u64,
+            key: db_segment.key,
+        }
+    }
+}
+
 impl From<crate::db::Track> for TrackMetadata {
     fn from(track: crate::db::Track) -> Self {
-        // Regenerate beat grid from first_beat_sample and BPM
-        let beat_grid = if let Some(bpm) = track.bpm {
-            let duration_samples = (track.duration_seconds * crate::types::SAMPLE_RATE as f64) as u64;
+        let duration_samples = (track.duration_seconds * crate::types::SAMPLE_RATE as f64) as u64;
+        // Variable-tempo grids come from their anchors; otherwise regenerate
+        // from first_beat_sample and BPM
+        let beat_grid = if !track.tempo_anchors.is_empty() {
+            BeatGrid::from_anchors(
+                track.tempo_anchors.into_iter().map(Into::into).collect(),
+                duration_samples,
+            )
+        } else if let Some(bpm) = track.bpm {
             BeatGrid::regenerate(track.first_beat_sample as u64, bpm, duration_samples)
         } else {
             BeatGrid::default()
@@ -431,6 +724,12 @@ impl From<crate::db::Track> for TrackMetadata {
             cue_points: track.cue_points.into_iter().map(Into::into).collect(),
             saved_loops: track.saved_loops.into_iter().map(Into::into).collect(),
             drop_marker: track.drop_marker.map(|d| d as u64),
+            phrases: track
+                .phrase_sections
+                .into_iter()
+                .filter_map(|s| s.try_into().ok())
+                .collect(),
+            key_timeline: track.key_segments.into_iter().map(Into::into).collect(),
             // Stem links are NOT included here because StemLink has track_id references
             // that require database lookups. The engine handles stem link loading separately.
             stem_links: Vec::new(),
@@ -741,7 +1040,7 @@ impl Drop for StemBuffers {
 }
 
 /// Resample a stereo buffer using FFT-based resampling
-fn resample_stereo_buffer(
+pub(crate) fn resample_stereo_buffer(
     input: &StereoBuffer,
     output: &mut StereoBuffer,
     source_rate: u32,
@@ -1747,6 +2046,13 @@ impl LoadedTrack {
         self.metadata.bpm.unwrap_or(120.0)
     }
 
+    /// Get the BPM at a sample position
+    ///
+    /// Follows tempo changes in variable-tempo grids; falls back to `bpm()`.
+    pub fn bpm_at(&self, sample: u64) -> f64 {
+        self.metadata.beat_grid.bpm_at_sample(sample).unwrap_or_else(|| self.bpm())
+    }
+
     /// Get the musical key (or "?" if unknown)
     pub fn key(&self) -> &str {
         self.metadata.key.as_deref().unwrap_or("?")
@@ -1807,6 +2113,13 @@ impl LoadedTrack {
         SAMPLE_RATE as f64 * 60.0 / bpm
     }
 
+    /// Calculate samples per beat at a sample position
+    ///
+    /// Follows tempo changes in variable-tempo grids; falls back to `samples_per_beat()`.
+    pub fn samples_per_beat_at(&self, sample: u64) -> f64 {
+        SAMPLE_RATE as f64 * 60.0 / self.bpm_at(sample)
+    }
+
     /// Get estimated memory usage in bytes
     pub fn memory_usage(&self) -> usize {
         // 4 stems * 2 channels * 4 bytes per sample * num samples
@@ -1868,6 +2181,43 @@ mod tests {
         assert_eq!(grid.beat_at_sample(30000), Some(1));
     }
 
+    #[test]
+    fn test_variable_tempo_grid() {
+        // 120 BPM (24000 samples/beat) for 4 beats, then 150 BPM (19200)
+        let grid = BeatGrid::from_anchors_with_rate(
+            vec![TempoAnchor::new(96_000, 150.0), TempoAnchor::new(0, 120.0)],
+            192_000,
+            48_000,
+        );
+        assert!(grid.is_variable_tempo());
+        assert_eq!(grid.first_beat_sample, Some(0));
+        assert_eq!(&grid.beats[..6], &[0, 24_000, 48_000, 72_000, 96_000, 115_200]);
+        assert_eq!(grid.bpm_at_sample(50_000), Some(120.0));
+        assert_eq!(grid.bpm_at_sample(100_000), Some(150.0));
+        assert_eq!(grid.beat_length_at(50_000), Some(24_000.0));
+        assert_eq!(grid.beat_length_at(100_000), Some(19_200.0));
+        assert_eq!(grid.segment_at_sample(96_000), Some(1));
+    }
+
+    #[test]
+    fn test_variable_tempo_grid_scaling() {
+        let mut grid = BeatGrid::from_anchors_with_rate(
+            vec![TempoAnchor::new(0, 120.0), TempoAnchor::new(44_100, 128.0)],
+            441_000,
+            44_100,
+        );
+        grid.scale_positions(48_000.0 / 44_100.0);
+        assert_eq!(grid.anchors[1].sample_position, 48_000);
+        assert_eq!(grid.anchors[1].bpm, 128.0);
+    }
+
+    #[test]
+    fn test_constant_grid_has_no_anchor_tempo() {
+        let grid = BeatGrid::regenerate_with_rate(0, 120.0, 96_000, 48_000);
+        assert!(!grid.is_variable_tempo());
+        assert_eq!(grid.bpm_at_sample(10), None);
+    }
+
     #[test]
     fn test_stem_buffers() {
         let mut stems = StemBuffers::with_length(100);
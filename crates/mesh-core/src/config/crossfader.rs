@@ -0,0 +1,230 @@
+//! Crossfader configuration
+//!
+//! Controls how the mixer crossfader blends the two sides of the mix:
+//! which decks are assigned to side A / side B / thru, the gain curve,
+//! and whether the fader direction is reversed (hamster mode).
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::NUM_DECKS;
+
+/// Width of the fade region at each end of the fader for the scratch curve
+/// (fraction of total travel). Outside this region both sides are at full gain.
+const SCRATCH_CUT_WIDTH: f32 = 0.04;
+
+/// Which side of the crossfader a deck is routed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossfaderAssign {
+    /// Left side — full gain with the fader on the left, cut on the right
+    A,
+    /// Bypass the crossfader entirely (only the channel fader applies)
+    #[default]
+    Thru,
+    /// Right side — full gain with the fader on the right, cut on the left
+    B,
+}
+
+impl CrossfaderAssign {
+    pub const ALL: [CrossfaderAssign; 3] = [
+        CrossfaderAssign::A,
+        CrossfaderAssign::Thru,
+        CrossfaderAssign::B,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CrossfaderAssign::A => "A",
+            CrossfaderAssign::Thru => "THRU",
+            CrossfaderAssign::B => "B",
+        }
+    }
+
+    /// Next assignment in the A → THRU → B → A cycle (for a single assign button)
+    pub fn cycle(&self) -> Self {
+        match self {
+            CrossfaderAssign::A => CrossfaderAssign::Thru,
+            CrossfaderAssign::Thru => CrossfaderAssign::B,
+            CrossfaderAssign::B => CrossfaderAssign::A,
+        }
+    }
+}
+
+/// Crossfader gain curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossfaderCurve {
+    /// Equal-power blend (sin/cos) — no level dip in the middle, for long blends
+    #[default]
+    ConstantPower,
+    /// Straight linear blend — 6 dB dip at the center
+    Linear,
+    /// Scratch cut — both sides at full gain, cutting within the last few
+    /// percent of travel at either end
+    Scratch,
+}
+
+impl CrossfaderCurve {
+    pub const ALL: [CrossfaderCurve; 3] = [
+        CrossfaderCurve::ConstantPower,
+        CrossfaderCurve::Linear,
+        CrossfaderCurve::Scratch,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CrossfaderCurve::ConstantPower => "Smooth",
+            CrossfaderCurve::Linear => "Linear",
+            CrossfaderCurve::Scratch => "Scratch",
+        }
+    }
+
+    /// Compute `(gain_a, gain_b)` for a normalized fader position
+    ///
+    /// `x` is 0.0 (full left / side A) to 1.0 (full right / side B).
+    pub fn gains(&self, x: f32) -> (f32, f32) {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            CrossfaderCurve::ConstantPower => {
+                let angle = x * std::f32::consts::FRAC_PI_2;
+                (angle.cos(), angle.sin())
+            }
+            CrossfaderCurve::Linear => (1.0 - x, x),
+            CrossfaderCurve::Scratch => (
+                ((1.0 - x) / SCRATCH_CUT_WIDTH).min(1.0),
+                (x / SCRATCH_CUT_WIDTH).min(1.0),
+            ),
+        }
+    }
+}
+
+/// Crossfader configuration (persisted in the player config)
+///
+/// The fader position itself is not persisted — it follows the physical
+/// control or the on-screen slider.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossfaderConfig {
+    /// Gain curve shape
+    pub curve: CrossfaderCurve,
+    /// Hamster mode: reverse the fader direction (left = side B)
+    pub reverse: bool,
+    /// Per-deck side assignment
+    /// Default: all decks on THRU so the crossfader has no effect until assigned.
+    pub assignments: [CrossfaderAssign; NUM_DECKS],
+}
+
+impl Default for CrossfaderConfig {
+    fn default() -> Self {
+        Self {
+            curve: CrossfaderCurve::default(),
+            reverse: false,
+            assignments: [CrossfaderAssign::Thru; NUM_DECKS],
+        }
+    }
+}
+
+impl CrossfaderConfig {
+    /// Gain for a deck assignment at a fader position (-1.0 = A, 1.0 = B)
+    ///
+    /// Applies hamster reversal before evaluating the curve. THRU always
+    /// returns unity gain.
+    pub fn gain_for(&self, assign: CrossfaderAssign, position: f32) -> f32 {
+        if assign == CrossfaderAssign::Thru {
+            return 1.0;
+        }
+        let position = if self.reverse { -position } else { position };
+        let x = (position.clamp(-1.0, 1.0) + 1.0) * 0.5;
+        let (gain_a, gain_b) = self.curve.gains(x);
+        match assign {
+            CrossfaderAssign::A => gain_a,
+            CrossfaderAssign::B => gain_b,
+            CrossfaderAssign::Thru => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thru_is_unity() {
+        let config = CrossfaderConfig::default();
+        for pos in [-1.0, -0.3, 0.0, 0.7, 1.0] {
+            assert_eq!(config.gain_for(CrossfaderAssign::Thru, pos), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_constant_power_center() {
+        let config = CrossfaderConfig::default();
+        let a = config.gain_for(CrossfaderAssign::A, 0.0);
+        let b = config.gain_for(CrossfaderAssign::B, 0.0);
+        // Equal power: a² + b² = 1 at every position
+        assert!((a * a + b * b - 1.0).abs() < 1e-5);
+        assert!((a - b).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_endpoints_cut_opposite_side() {
+        for curve in CrossfaderCurve::ALL {
+            let config = CrossfaderConfig { curve, ..Default::default() };
+            assert!(config.gain_for(CrossfaderAssign::A, 1.0) < 1e-5, "{:?}", curve);
+            assert!(config.gain_for(CrossfaderAssign::B, -1.0) < 1e-5, "{:?}", curve);
+            assert!((config.gain_for(CrossfaderAssign::A, -1.0) - 1.0).abs() < 1e-5);
+            assert!((config.gain_for(CrossfaderAssign::B, 1.0) - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_linear_center_dip() {
+        let config = CrossfaderConfig { curve: CrossfaderCurve::Linear, ..Default::default() };
+        assert!((config.gain_for(CrossfaderAssign::A, 0.0) - 0.5).abs() < 1e-5);
+        assert!((config.gain_for(CrossfaderAssign::B, 0.0) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_scratch_curve_full_gain_until_edge() {
+        let config = CrossfaderConfig { curve: CrossfaderCurve::Scratch, ..Default::default() };
+        // Most of the travel keeps both sides fully open
+        assert_eq!(config.gain_for(CrossfaderAssign::A, 0.8), 1.0);
+        assert_eq!(config.gain_for(CrossfaderAssign::B, -0.8), 1.0);
+        // Within the cut region the far side is attenuated
+        assert!(config.gain_for(CrossfaderAssign::A, 0.98) < 1.0);
+    }
+
+    #[test]
+    fn test_reverse_swaps_sides() {
+        let normal = CrossfaderConfig::default();
+        let hamster = CrossfaderConfig { reverse: true, ..Default::default() };
+        let pos = -0.6;
+        assert!(
+            (normal.gain_for(CrossfaderAssign::A, pos) - hamster.gain_for(CrossfaderAssign::B, pos)).abs() < 1e-5
+        );
+    }
+
+    #[test]
+    fn test_assign_cycle() {
+        assert_eq!(CrossfaderAssign::A.cycle(), CrossfaderAssign::Thru);
+        assert_eq!(CrossfaderAssign::Thru.cycle(), CrossfaderAssign::B);
+        assert_eq!(CrossfaderAssign::B.cycle(), CrossfaderAssign::A);
+    }
+
+    #[test]
+    fn test_yaml_roundtrip() {
+        let config = CrossfaderConfig {
+            curve: CrossfaderCurve::Scratch,
+            reverse: true,
+            assignments: [
+                CrossfaderAssign::A,
+                CrossfaderAssign::B,
+                CrossfaderAssign::Thru,
+                CrossfaderAssign::A,
+            ],
+        };
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let parsed: CrossfaderConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, config);
+    }
+}
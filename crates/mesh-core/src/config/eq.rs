@@ -0,0 +1,151 @@
+//! Channel EQ configuration
+//!
+//! Selects how the mixer's channel EQ knobs shape the sound: gentle
+//! shelving/peaking curves, or full-kill isolators built from
+//! Linkwitz-Riley crossovers (3 or 4 bands). The model can differ per deck.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::NUM_DECKS;
+
+/// Lowest allowed crossover frequency (Hz)
+const MIN_CROSSOVER_HZ: f32 = 40.0;
+/// Highest allowed crossover frequency (Hz)
+const MAX_CROSSOVER_HZ: f32 = 16000.0;
+/// Minimum spacing between neighbouring crossovers (ratio, ~1/3 octave)
+const MIN_CROSSOVER_RATIO: f32 = 1.25;
+
+/// Channel EQ model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EqModel {
+    /// Shelving low/high and peaking mid (DJM-style). Fixed curves at
+    /// 100 Hz / 1 kHz / 10 kHz; the kill position is a deep cut, not silence.
+    #[default]
+    Classic,
+    /// 3-band Linkwitz-Riley isolator — each band fully silent at kill
+    Isolator,
+    /// 4-band Linkwitz-Riley isolator (Xone-style) with a split mid range
+    FourBand,
+}
+
+impl EqModel {
+    pub const ALL: [EqModel; 3] = [EqModel::Classic, EqModel::Isolator, EqModel::FourBand];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EqModel::Classic => "Classic",
+            EqModel::Isolator => "Isolator",
+            EqModel::FourBand => "4-Band",
+        }
+    }
+
+    /// Number of EQ knobs the model uses (3 or 4)
+    pub fn band_count(&self) -> usize {
+        match self {
+            EqModel::Classic | EqModel::Isolator => 3,
+            EqModel::FourBand => 4,
+        }
+    }
+}
+
+/// Crossover points shared by the isolator models (Hz)
+///
+/// The 3-band isolator splits at `low_hz` and `high_hz`; the 4-band model
+/// additionally splits its mid range at `mid_hz`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EqCrossovers {
+    /// Low / mid split
+    pub low_hz: f32,
+    /// Low-mid / high-mid split (4-band only)
+    pub mid_hz: f32,
+    /// Mid / high split
+    pub high_hz: f32,
+}
+
+impl Default for EqCrossovers {
+    fn default() -> Self {
+        Self {
+            low_hz: 200.0,
+            mid_hz: 1000.0,
+            high_hz: 2500.0,
+        }
+    }
+}
+
+impl EqCrossovers {
+    /// Clamp into the audible range and keep the points ordered and spaced
+    pub fn validated(&self) -> Self {
+        let low_hz = self
+            .low_hz
+            .clamp(MIN_CROSSOVER_HZ, MAX_CROSSOVER_HZ / MIN_CROSSOVER_RATIO.powi(2));
+        let mid_hz = self
+            .mid_hz
+            .clamp(low_hz * MIN_CROSSOVER_RATIO, MAX_CROSSOVER_HZ / MIN_CROSSOVER_RATIO);
+        let high_hz = self.high_hz.clamp(mid_hz * MIN_CROSSOVER_RATIO, MAX_CROSSOVER_HZ);
+        Self { low_hz, mid_hz, high_hz }
+    }
+}
+
+/// Channel EQ configuration (persisted in the player config)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EqConfig {
+    /// Model used by every deck without an override
+    pub model: EqModel,
+    /// Per-deck model override (`null` = use `model`)
+    pub decks: [Option<EqModel>; NUM_DECKS],
+    /// Isolator crossover frequencies
+    pub crossovers: EqCrossovers,
+}
+
+impl Default for EqConfig {
+    fn default() -> Self {
+        Self {
+            model: EqModel::default(),
+            decks: [None; NUM_DECKS],
+            crossovers: EqCrossovers::default(),
+        }
+    }
+}
+
+impl EqConfig {
+    /// Effective model for a deck
+    pub fn model_for(&self, deck: usize) -> EqModel {
+        self.decks.get(deck).copied().flatten().unwrap_or(self.model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deck_override() {
+        let mut config = EqConfig { model: EqModel::Isolator, ..Default::default() };
+        config.decks[2] = Some(EqModel::FourBand);
+        assert_eq!(config.model_for(0), EqModel::Isolator);
+        assert_eq!(config.model_for(2), EqModel::FourBand);
+        assert_eq!(config.model_for(99), EqModel::Isolator);
+    }
+
+    #[test]
+    fn test_crossovers_validated_ordered() {
+        let crossovers = EqCrossovers { low_hz: 3000.0, mid_hz: 500.0, high_hz: 50000.0 }.validated();
+        assert!(crossovers.low_hz < crossovers.mid_hz);
+        assert!(crossovers.mid_hz < crossovers.high_hz);
+        assert!(crossovers.high_hz <= MAX_CROSSOVER_HZ);
+        assert_eq!(EqCrossovers::default().validated(), EqCrossovers::default());
+    }
+
+    #[test]
+    fn test_yaml_roundtrip() {
+        let mut config = EqConfig { model: EqModel::Isolator, ..Default::default() };
+        config.decks[1] = Some(EqModel::Classic);
+        config.crossovers.low_hz = 180.0;
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let parsed: EqConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, config);
+    }
+}
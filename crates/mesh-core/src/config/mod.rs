@@ -6,6 +6,9 @@
 //! - Generic YAML config loading/saving
 //! - Collection path utilities
 //! - Loudness normalization configuration
+//! - Crossfader curve and deck assignment
+//! - Channel EQ model and isolator crossovers
+//! - Set recording format, file splitting and stem tracks
 //!
 //! # Usage
 //!
@@ -19,10 +22,16 @@
 //! save_config(&config, &config_path)?;
 //! ```
 
+mod crossfader;
+mod eq;
 mod io;
 mod loudness;
 mod paths;
+mod recording;
 
+pub use crossfader::{CrossfaderAssign, CrossfaderConfig, CrossfaderCurve};
+pub use eq::{EqConfig, EqCrossovers, EqModel};
 pub use io::{load_config, save_config};
 pub use loudness::LoudnessConfig;
 pub use paths::{default_collection_path, default_config_path};
+pub use recording::{RecordingConfig, RecordingFormat, RecordingSplit, RecordingStems};
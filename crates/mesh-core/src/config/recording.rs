@@ -0,0 +1,212 @@
+//! Set recording configuration
+//!
+//! Controls the file format written by the set recorder and whether long
+//! recordings are split into multiple parts (e.g. to stay under the 4 GB
+//! file size limit of FAT32 USB sticks and of the WAV format itself).
+//! Optionally the summed per-stem buses are recorded alongside the master.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest part size used by [`RecordingSplit::Size4Gb`].
+///
+/// Kept safely below 4 GiB so a part (including its header) always fits on
+/// FAT32 and inside the 32-bit RIFF size fields.
+const FAT32_SAFE_PART_BYTES: u64 = 4_000_000_000;
+
+/// Audio file format for set recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    /// 16-bit integer PCM WAV (CD quality, widest compatibility)
+    #[default]
+    Wav16,
+    /// 24-bit integer PCM WAV
+    Wav24,
+    /// 32-bit float WAV (no clipping of the master bus above 0 dBFS)
+    WavFloat32,
+    /// 16-bit FLAC (lossless, roughly half the size of WAV)
+    Flac16,
+    /// 24-bit FLAC
+    Flac24,
+}
+
+impl RecordingFormat {
+    pub const ALL: [RecordingFormat; 5] = [
+        RecordingFormat::Wav16,
+        RecordingFormat::Wav24,
+        RecordingFormat::WavFloat32,
+        RecordingFormat::Flac16,
+        RecordingFormat::Flac24,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RecordingFormat::Wav16 => "WAV 16",
+            RecordingFormat::Wav24 => "WAV 24",
+            RecordingFormat::WavFloat32 => "WAV 32f",
+            RecordingFormat::Flac16 => "FLAC 16",
+            RecordingFormat::Flac24 => "FLAC 24",
+        }
+    }
+
+    /// File extension (without the dot)
+    pub fn extension(&self) -> &'static str {
+        if self.is_flac() { "flac" } else { "wav" }
+    }
+
+    /// Whether this is a FLAC (compressed) format
+    pub fn is_flac(&self) -> bool {
+        matches!(self, RecordingFormat::Flac16 | RecordingFormat::Flac24)
+    }
+
+    /// Bits per sample written to the file
+    pub fn bits_per_sample(&self) -> u16 {
+        match self {
+            RecordingFormat::Wav16 | RecordingFormat::Flac16 => 16,
+            RecordingFormat::Wav24 | RecordingFormat::Flac24 => 24,
+            RecordingFormat::WavFloat32 => 32,
+        }
+    }
+}
+
+/// When to split a long recording into a new file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingSplit {
+    /// Never split (WAV recordings stop growing at the 4 GB format limit)
+    Off,
+    /// Start a new part before the file reaches 4 GB (FAT32 safe)
+    #[default]
+    Size4Gb,
+    /// Start a new part every 30 minutes
+    Minutes30,
+    /// Start a new part every 60 minutes
+    Minutes60,
+}
+
+impl RecordingSplit {
+    pub const ALL: [RecordingSplit; 4] = [
+        RecordingSplit::Off,
+        RecordingSplit::Size4Gb,
+        RecordingSplit::Minutes30,
+        RecordingSplit::Minutes60,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RecordingSplit::Off => "Off",
+            RecordingSplit::Size4Gb => "4 GB",
+            RecordingSplit::Minutes30 => "30 min",
+            RecordingSplit::Minutes60 => "60 min",
+        }
+    }
+
+    /// Maximum size of a single part in bytes (None = no size limit)
+    pub fn max_part_bytes(&self) -> Option<u64> {
+        match self {
+            RecordingSplit::Size4Gb => Some(FAT32_SAFE_PART_BYTES),
+            _ => None,
+        }
+    }
+
+    /// Maximum length of a single part in stereo frames (None = no duration limit)
+    pub fn max_part_frames(&self, sample_rate: u32) -> Option<u64> {
+        let minutes = match self {
+            RecordingSplit::Minutes30 => 30,
+            RecordingSplit::Minutes60 => 60,
+            _ => return None,
+        };
+        Some(minutes * 60 * sample_rate as u64)
+    }
+}
+
+/// Multitrack recording of the per-stem buses (vocals, drums, bass, other
+/// summed across all decks) alongside the master
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingStems {
+    /// Record the master only
+    #[default]
+    Off,
+    /// One 8-channel file in mesh's stem layout (re-importable in mesh-cue)
+    Multichannel,
+    /// One stereo file per stem (`_(Vocals)`, `_(Drums)`, … naming)
+    SeparateFiles,
+}
+
+impl RecordingStems {
+    pub const ALL: [RecordingStems; 3] = [
+        RecordingStems::Off,
+        RecordingStems::Multichannel,
+        RecordingStems::SeparateFiles,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RecordingStems::Off => "Off",
+            RecordingStems::Multichannel => "8-Channel",
+            RecordingStems::SeparateFiles => "Per Stem",
+        }
+    }
+}
+
+/// Set recording configuration (persisted in the player config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// File format for new recordings
+    pub format: RecordingFormat,
+    /// Part splitting policy
+    pub split: RecordingSplit,
+    /// Write the tracklist into the recording as chapter markers
+    /// (WAV cue points / FLAC `CHAPTERxxx` comments) when it stops
+    pub embed_chapters: bool,
+    /// Also record the per-stem buses (costs 16 extra time stretchers
+    /// on the audio thread while recording)
+    pub stems: RecordingStems,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            format: RecordingFormat::default(),
+            split: RecordingSplit::default(),
+            embed_chapters: true,
+            stems: RecordingStems::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_extension() {
+        assert_eq!(RecordingFormat::Wav24.extension(), "wav");
+        assert_eq!(RecordingFormat::WavFloat32.extension(), "wav");
+        assert_eq!(RecordingFormat::Flac16.extension(), "flac");
+        assert_eq!(RecordingFormat::Flac24.bits_per_sample(), 24);
+    }
+
+    #[test]
+    fn test_split_limits() {
+        assert_eq!(RecordingSplit::Off.max_part_bytes(), None);
+        assert_eq!(RecordingSplit::Off.max_part_frames(48000), None);
+        assert!(RecordingSplit::Size4Gb.max_part_bytes().unwrap() < u32::MAX as u64);
+        assert_eq!(RecordingSplit::Minutes30.max_part_frames(48000), Some(30 * 60 * 48000));
+    }
+
+    #[test]
+    fn test_yaml_roundtrip() {
+        let config = RecordingConfig {
+            format: RecordingFormat::Flac24,
+            split: RecordingSplit::Minutes60,
+            embed_chapters: false,
+            stems: RecordingStems::SeparateFiles,
+        };
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let parsed: RecordingConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, config);
+    }
+}
@@ -10,7 +10,7 @@
 //!
 //! This reduces the number of database operations from 18+ per track to ~5.
 
-use super::{CuePoint, DbError, MeshDb, SavedLoop, StemLink};
+use super::{CuePoint, DbError, KeySegment, MeshDb, PhraseSection, SavedLoop, StemLink, TempoAnchor};
 use cozo::DataValue;
 use std::collections::BTreeMap;
 
@@ -114,6 +114,123 @@ impl BatchQuery {
         Ok(())
     }
 
+    /// Batch insert tempo anchors for a track
+    ///
+    /// Inserts all tempo anchors of a variable-tempo grid in a single CozoDB query.
+    pub fn batch_insert_tempo_anchors(
+        db: &MeshDb,
+        track_id: i64,
+        anchors: &[TempoAnchor],
+    ) -> Result<(), DbError> {
+        if anchors.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<DataValue> = anchors
+            .iter()
+            .map(|a| {
+                DataValue::List(vec![
+                    DataValue::from(track_id),
+                    DataValue::from(a.index as i64),
+                    DataValue::from(a.sample_position),
+                    DataValue::from(a.bpm),
+                ])
+            })
+            .collect();
+
+        let mut params = BTreeMap::new();
+        params.insert("rows".to_string(), DataValue::List(rows));
+
+        db.run_script(
+            r#"
+            ?[track_id, index, sample_position, bpm] <- $rows
+            :put tempo_anchors {track_id, index => sample_position, bpm}
+        "#,
+            params,
+        )?;
+
+        Ok(())
+    }
+
+    /// Batch insert phrase sections for a track
+    ///
+    /// Inserts the whole detected song structure in a single CozoDB query.
+    pub fn batch_insert_phrase_sections(
+        db: &MeshDb,
+        track_id: i64,
+        sections: &[PhraseSection],
+    ) -> Result<(), DbError> {
+        if sections.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<DataValue> = sections
+            .iter()
+            .map(|s| {
+                DataValue::List(vec![
+                    DataValue::from(track_id),
+                    DataValue::from(s.index as i64),
+                    DataValue::from(s.start_sample),
+                    DataValue::from(s.end_sample),
+                    DataValue::from(s.kind.as_str()),
+                    DataValue::from(s.bars as i64),
+                ])
+            })
+            .collect();
+
+        let mut params = BTreeMap::new();
+        params.insert("rows".to_string(), DataValue::List(rows));
+
+        db.run_script(
+            r#"
+            ?[track_id, index, start_sample, end_sample, kind, bars] <- $rows
+            :put phrase_sections {track_id, index => start_sample, end_sample, kind, bars}
+        "#,
+            params,
+        )?;
+
+        Ok(())
+    }
+
+    /// Batch insert key segments for a track
+    ///
+    /// Inserts the whole key timeline in a single CozoDB query.
+    pub fn batch_insert_key_segments(
+        db: &MeshDb,
+        track_id: i64,
+        segments: &[KeySegment],
+    ) -> Result<(), DbError> {
+        if segments.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<DataValue> = segments
+            .iter()
+            .map(|s| {
+                DataValue::List(vec![
+                    DataValue::from(track_id),
+                    DataValue::from(s.index as i64),
+                    DataValue::from(s.start_sample),
+                    DataValue::from(s.end_sample),
+                    DataValue::from(s.key.as_str()),
+                ])
+            })
+            .collect();
+
+        let mut params = BTreeMap::new();
+        params.insert("rows".to_string(), DataValue::List(rows));
+
+        db.run_script(
+            r#"
+            ?[track_id, index, start_sample, end_sample, key] <- $rows
+            :put key_segments {track_id, index => start_sample, end_sample, key}
+        "#,
+            params,
+        )?;
+
+        Ok(())
+    }
+
     /// Batch insert stem links for a track
     ///
     /// Inserts all stem links in a single CozoDB query.
@@ -197,9 +314,9 @@ impl BatchQuery {
         Ok(())
     }
 
-    /// Delete all metadata for a track (cue_points, saved_loops, stem_links)
+    /// Delete all metadata for a track (cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links, ...)
     ///
-    /// Removes all associated metadata in 3 queries (one per relation).
+    /// Removes all associated metadata with one query per relation.
     /// This is called before batch inserting new metadata to avoid duplicates.
     pub fn batch_delete_track_metadata(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
         let mut params = BTreeMap::new();
@@ -223,6 +340,33 @@ impl BatchQuery {
             params.clone(),
         )?;
 
+        // Delete tempo anchors
+        db.run_script(
+            r#"
+            ?[track_id, index] := *tempo_anchors{track_id, index}, track_id = $track_id
+            :rm tempo_anchors {track_id, index}
+        "#,
+            params.clone(),
+        )?;
+
+        // Delete phrase sections
+        db.run_script(
+            r#"
+            ?[track_id, index] := *phrase_sections{track_id, index}, track_id = $track_id
+            :rm phrase_sections {track_id, index}
+        "#,
+            params.clone(),
+        )?;
+
+        // Delete key segments
+        db.run_script(
+            r#"
+            ?[track_id, index] := *key_segments{track_id, index}, track_id = $track_id
+            :rm key_segments {track_id, index}
+        "#,
+            params.clone(),
+        )?;
+
         // Delete stem links
         db.run_script(
             r#"
@@ -291,6 +435,24 @@ impl BatchQuery {
             r#"
             ?[track_id] := *intensity_score{track_id}, track_id = $track_id
             :rm intensity_score {track_id}
+        "#,
+            params.clone(),
+        )?;
+
+        // Delete embedded tag metadata (album, genre, artwork path, ...)
+        db.run_script(
+            r#"
+            ?[track_id] := *tag_metadata{track_id}, track_id = $track_id
+            :rm tag_metadata {track_id}
+        "#,
+            params.clone(),
+        )?;
+
+        // Delete rating / colour label
+        db.run_script(
+            r#"
+            ?[track_id] := *track_ratings{track_id}, track_id = $track_id
+            :rm track_ratings {track_id}
         "#,
             params,
         )?;
@@ -402,8 +564,25 @@ mod tests {
             SavedLoop { track_id: 1, index: 0, start_sample: 100000, end_sample: 200000, label: None, color: None },
         ];
 
+        let anchors = vec![
+            TempoAnchor { track_id: 1, index: 0, sample_position: 0, bpm: 120.0 },
+            TempoAnchor { track_id: 1, index: 1, sample_position: 960000, bpm: 124.0 },
+        ];
+
+        let sections = vec![
+            PhraseSection { track_id: 1, index: 0, start_sample: 0, end_sample: 1536000, kind: "intro".to_string(), bars: 16 },
+            PhraseSection { track_id: 1, index: 1, start_sample: 1536000, end_sample: 3072000, kind: "drop".to_string(), bars: 16 },
+        ];
+
         BatchQuery::batch_insert_cue_points(&db, 1, &cues).unwrap();
         BatchQuery::batch_insert_saved_loops(&db, 1, &loops).unwrap();
+        BatchQuery::batch_insert_tempo_anchors(&db, 1, &anchors).unwrap();
+        BatchQuery::batch_insert_phrase_sections(&db, 1, &sections).unwrap();
+        BatchQuery::batch_insert_key_segments(
+            &db,
+            1,
+            &[KeySegment { track_id: 1, index: 0, start_sample: 0, end_sample: 3072000, key: "Am".to_string() }],
+        ).unwrap();
 
         // Verify they exist
         let cue_count = db.run_query(
@@ -427,6 +606,24 @@ mod tests {
             BTreeMap::new(),
         ).unwrap();
         assert_eq!(loop_count_after.rows[0][0], DataValue::from(0i64));
+
+        let anchor_count_after = db.run_query(
+            "?[count(index)] := *tempo_anchors{track_id, index}, track_id = 1",
+            BTreeMap::new(),
+        ).unwrap();
+        assert_eq!(anchor_count_after.rows[0][0], DataValue::from(0i64));
+
+        let section_count_after = db.run_query(
+            "?[count(index)] := *phrase_sections{track_id, index}, track_id = 1",
+            BTreeMap::new(),
+        ).unwrap();
+        assert_eq!(section_count_after.rows[0][0], DataValue::from(0i64));
+
+        let key_count_after = db.run_query(
+            "?[count(index)] := *key_segments{track_id, index}, track_id = 1",
+            BTreeMap::new(),
+        ).unwrap();
+        assert_eq!(key_count_after.rows[0][0], DataValue::from(0i64));
     }
 
     #[test]
@@ -436,6 +633,9 @@ mod tests {
         // These should succeed without errors even with empty slices
         BatchQuery::batch_insert_cue_points(&db, 1, &[]).unwrap();
         BatchQuery::batch_insert_saved_loops(&db, 1, &[]).unwrap();
+        BatchQuery::batch_insert_tempo_anchors(&db, 1, &[]).unwrap();
+        BatchQuery::batch_insert_phrase_sections(&db, 1, &[]).unwrap();
+        BatchQuery::batch_insert_key_segments(&db, 1, &[]).unwrap();
         BatchQuery::batch_insert_stem_links(&db, 1, &[]).unwrap();
     }
 }
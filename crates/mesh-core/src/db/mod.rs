@@ -25,10 +25,10 @@ mod service;
 pub(crate) use schema::TrackRow;
 
 // Public schema types (used across crates)
-pub use schema::{Playlist, CuePoint, SavedLoop, StemLink, SimilarTo, HarmonicMatch, HarmonicMatchType, MlAnalysisData, SessionRecord, TrackPlayRecord, TrackPlayUpdate};
+pub use schema::{Playlist, CuePoint, SavedLoop, TempoAnchor, PhraseSection, KeySegment, StemLink, SimilarTo, HarmonicMatch, HarmonicMatchType, MlAnalysisData, TagMetadata, TrackRating, SessionRecord, TrackPlayRecord, TrackPlayStats, TrackPlayUpdate};
 
 // Internal query module (pub(crate) - implementation detail)
-pub(crate) use queries::{TrackQuery, PlaylistQuery, SimilarityQuery, CuePointQuery, SavedLoopQuery, StemLinkQuery};
+pub(crate) use queries::{TrackQuery, PlaylistQuery, SmartPlaylistQuery, SimilarityQuery, CuePointQuery, SavedLoopQuery, TempoAnchorQuery, PhraseSectionQuery, KeySegmentQuery, StemLinkQuery, TagMetadataQuery, TrackRatingQuery};
 
 // Internal batch module (used directly by service.rs for efficient bulk inserts)
 
@@ -2,8 +2,10 @@
 //!
 //! This module provides typed query APIs that generate CozoScript internally.
 
-use super::schema::{TrackRow, Playlist, CuePoint, SavedLoop, StemLink, TrackPlayRecord, TrackPlayUpdate};
+use super::schema::{TrackRow, Playlist, CuePoint, SavedLoop, TempoAnchor, PhraseSection, KeySegment, StemLink, TagMetadata, TrackRating, TrackPlayRecord, TrackPlayUpdate};
 use super::{MeshDb, DbError};
+use crate::playlist::{SmartContext, SmartPlaylistRules};
+use crate::search::SearchQuery;
 use cozo::{DataValue, NamedRows, Vector};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
@@ -126,30 +128,45 @@ impl TrackQuery {
         Ok(rows_to_tracks(&result))
     }
 
-    /// Search tracks by title or artist
+    /// Search tracks with the browser search syntax
+    ///
+    /// Plain words match title or artist; `field:value` terms filter on
+    /// analysis, tags and play history (see [`crate::search`]).
     pub fn search(db: &MeshDb, query: &str, limit: usize) -> Result<Vec<TrackRow>, DbError> {
-        let mut params = BTreeMap::new();
-        let query_lower = query.to_lowercase();
-        params.insert("query".to_string(), DataValue::Str(query_lower.into()));
-        params.insert("limit".to_string(), DataValue::from(limit as i64));
+        Self::search_parsed(db, &SearchQuery::parse(query), limit)
+    }
 
-        // Substring match on title or artist. `coalesce(artist, "")` guards the
-        // nullable artist column so lowercase()/str_includes() never evaluate
-        // against null (Cozo's `||` does not short-circuit).
-        let result = db.run_query(r#"
+    /// Run an already parsed search
+    pub fn search_parsed(db: &MeshDb, query: &SearchQuery, limit: usize) -> Result<Vec<TrackRow>, DbError> {
+        let ctx = smart_context(db, query.uses_keys(), query.uses_session())?;
+        let (mut script, mut params) = query.compile(&ctx);
+        params.insert("limit".to_string(), DataValue::from(limit as i64));
+        script.push_str(r#"
             ?[id, path, folder_path, title, original_name, artist, bpm, original_bpm, key,
               duration_seconds, lufs, integrated_lufs, drop_marker, first_beat_sample, file_mtime, file_size, waveform_path] :=
+                matched[id],
                 *tracks{id, path, folder_path, title, original_name, artist, bpm, original_bpm, key,
-                        duration_seconds, lufs, integrated_lufs, drop_marker, first_beat_sample, file_mtime, file_size, waveform_path},
-                (str_includes(lowercase(title), $query) ||
-                 str_includes(lowercase(coalesce(artist, "")), $query))
+                        duration_seconds, lufs, integrated_lufs, drop_marker, first_beat_sample, file_mtime, file_size, waveform_path}
             :limit $limit
             :order title
-        "#, params)?;
+        "#);
 
+        let result = db.run_query(&script, params)?;
         Ok(rows_to_tracks(&result))
     }
 
+    /// Paths of every track matching a parsed search
+    pub fn search_paths(db: &MeshDb, query: &SearchQuery) -> Result<HashSet<String>, DbError> {
+        let ctx = smart_context(db, query.uses_keys(), query.uses_session())?;
+        let (mut script, params) = query.compile(&ctx);
+        script.push_str("?[path] := matched[id], *tracks{id, path}");
+
+        let result = db.run_query(&script, params)?;
+        Ok(result.rows.iter()
+            .filter_map(|row| row.first().and_then(|v| v.get_str()).map(|s| s.to_string()))
+            .collect())
+    }
+
     /// Insert or update a track
     pub fn upsert(db: &MeshDb, track: &TrackRow) -> Result<(), DbError> {
         let mut params = BTreeMap::new();
@@ -448,7 +465,13 @@ impl PlaylistQuery {
     }
 
     /// Get tracks in a playlist
+    ///
+    /// Smart playlists are evaluated from their rules on every call.
     pub fn get_tracks(db: &MeshDb, playlist_id: i64) -> Result<Vec<TrackRow>, DbError> {
+        if let Some(rules) = SmartPlaylistQuery::get_rules(db, playlist_id)? {
+            return SmartPlaylistQuery::evaluate(db, &rules);
+        }
+
         let mut params = BTreeMap::new();
         params.insert("playlist_id".to_string(), DataValue::from(playlist_id));
 
@@ -589,6 +612,8 @@ impl PlaylistQuery {
         let mut params = BTreeMap::new();
         params.insert("id".to_string(), DataValue::from(id));
 
+        SmartPlaylistQuery::delete(db, id)?;
+
         // First remove all track associations
         db.run_script(
             r#"
@@ -724,10 +749,115 @@ impl PlaylistQuery {
     }
 }
 
+// ============================================================================
+// Smart Playlist Queries
+// ============================================================================
+
+/// Query builder for smart playlist rules and their evaluation
+pub struct SmartPlaylistQuery;
+
+impl SmartPlaylistQuery {
+    /// Get the rules of a playlist, or None if it is a manual playlist
+    pub fn get_rules(db: &MeshDb, playlist_id: i64) -> Result<Option<SmartPlaylistRules>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("playlist_id".to_string(), DataValue::from(playlist_id));
+
+        let result = db.run_query(r#"
+            ?[rules_json] := *smart_playlists{playlist_id, rules_json}, playlist_id = $playlist_id
+        "#, params)?;
+
+        let Some(json) = result.rows.first().and_then(|row| row.first()).and_then(|v| v.get_str()) else {
+            return Ok(None);
+        };
+        serde_json::from_str(json)
+            .map(Some)
+            .map_err(|e| DbError::Serialization(e.to_string()))
+    }
+
+    /// Store (or replace) the rules of a playlist
+    pub fn set_rules(db: &MeshDb, playlist_id: i64, rules: &SmartPlaylistRules) -> Result<(), DbError> {
+        let json = serde_json::to_string(rules).map_err(|e| DbError::Serialization(e.to_string()))?;
+        let mut params = BTreeMap::new();
+        params.insert("playlist_id".to_string(), DataValue::from(playlist_id));
+        params.insert("rules_json".to_string(), DataValue::Str(json.into()));
+
+        db.run_script(r#"
+            ?[playlist_id, rules_json] <- [[$playlist_id, $rules_json]]
+            :put smart_playlists {playlist_id => rules_json}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Remove the rules of a playlist
+    pub fn delete(db: &MeshDb, playlist_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("playlist_id".to_string(), DataValue::from(playlist_id));
+
+        db.run_script(r#"
+            ?[playlist_id] := *smart_playlists{playlist_id}, playlist_id = $playlist_id
+            :rm smart_playlists {playlist_id}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// IDs of all smart playlists
+    pub fn get_all_ids(db: &MeshDb) -> Result<HashSet<i64>, DbError> {
+        let result = db.run_query("?[playlist_id] := *smart_playlists{playlist_id}", BTreeMap::new())?;
+
+        Ok(result.rows.iter()
+            .filter_map(|row| row.first().and_then(|v| v.get_int()))
+            .collect())
+    }
+
+    /// Evaluate a rule set against the library
+    pub fn evaluate(db: &MeshDb, rules: &SmartPlaylistRules) -> Result<Vec<TrackRow>, DbError> {
+        let ctx = smart_context(db, rules.uses_keys(), rules.uses_session())?;
+        let (mut script, params) = rules.compile(&ctx);
+        script.push_str(r#"
+            ?[id, path, folder_path, title, original_name, artist, bpm, original_bpm, key,
+              duration_seconds, lufs, integrated_lufs, drop_marker, first_beat_sample, file_mtime, file_size, waveform_path] :=
+                matched[id],
+                *tracks{id, path, folder_path, title, original_name, artist, bpm, original_bpm, key,
+                        duration_seconds, lufs, integrated_lufs, drop_marker, first_beat_sample, file_mtime, file_size, waveform_path}
+        "#);
+        script.push_str(&rules.order_and_limit());
+
+        let result = db.run_query(&script, params)?;
+        Ok(rows_to_tracks(&result))
+    }
+}
+
 // ============================================================================
 // Similarity Queries
 // ============================================================================
 
+/// Library facts for compiling smart playlist rules or search queries
+///
+/// The key and session lookups are only run when a rule needs them.
+fn smart_context(db: &MeshDb, keys: bool, session: bool) -> Result<SmartContext, DbError> {
+    let mut ctx = SmartContext {
+        now_secs: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0),
+        ..Default::default()
+    };
+    if keys {
+        let result = db.run_query("?[key] := *tracks{key}, !is_null(key)", BTreeMap::new())?;
+        ctx.library_keys = result.rows.iter()
+            .filter_map(|row| row.first().and_then(|v| v.get_str()).map(|s| s.to_string()))
+            .collect();
+    }
+    if session {
+        // Session ids are start timestamps, so the latest one is current
+        let result = db.run_query("?[max(id)] := *sessions{id}", BTreeMap::new())?;
+        ctx.session_id = result.rows.first().and_then(|row| row.first()).and_then(|v| v.get_int());
+    }
+    Ok(ctx)
+}
+
 /// Query builder for similarity search
 pub struct SimilarityQuery;
 
@@ -1455,6 +1585,272 @@ impl SavedLoopQuery {
     }
 }
 
+// ============================================================================
+// Tempo Anchor Queries
+// ============================================================================
+
+/// Query builder for tempo anchors (variable-tempo beat grids)
+pub struct TempoAnchorQuery;
+
+impl TempoAnchorQuery {
+    /// Get all tempo anchors for a track, in grid order
+    pub fn get_for_track(db: &MeshDb, track_id: i64) -> Result<Vec<TempoAnchor>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        let result = db.run_query(r#"
+            ?[track_id, index, sample_position, bpm] :=
+                *tempo_anchors{track_id, index, sample_position, bpm},
+                track_id = $track_id
+            :order index
+        "#, params)?;
+
+        Ok(rows_to_tempo_anchors(&result))
+    }
+
+    /// Insert or update a single tempo anchor
+    pub fn upsert(db: &MeshDb, anchor: &TempoAnchor) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(anchor.track_id));
+        params.insert("index".to_string(), DataValue::from(anchor.index as i64));
+        params.insert("sample_position".to_string(), DataValue::from(anchor.sample_position));
+        params.insert("bpm".to_string(), DataValue::from(anchor.bpm));
+
+        db.run_script(r#"
+            ?[track_id, index, sample_position, bpm] <- [[$track_id, $index, $sample_position, $bpm]]
+            :put tempo_anchors {track_id, index => sample_position, bpm}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Get all tempo anchors for all tracks (bulk query for sync)
+    pub fn get_all(db: &MeshDb) -> Result<HashMap<i64, Vec<TempoAnchor>>, DbError> {
+        let result = db.run_query(r#"
+            ?[track_id, index, sample_position, bpm] :=
+                *tempo_anchors{track_id, index, sample_position, bpm}
+            :order track_id, index
+        "#, BTreeMap::new())?;
+
+        let mut map: HashMap<i64, Vec<TempoAnchor>> = HashMap::new();
+        for anchor in rows_to_tempo_anchors(&result) {
+            map.entry(anchor.track_id).or_default().push(anchor);
+        }
+        Ok(map)
+    }
+
+    /// Delete all tempo anchors for a track
+    pub fn delete_all_for_track(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        db.run_script(r#"
+            ?[track_id, index] := *tempo_anchors{track_id, index}, track_id = $track_id
+            :rm tempo_anchors {track_id, index}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Replace all tempo anchors for a track (delete existing, insert new)
+    pub fn replace_all(db: &MeshDb, track_id: i64, anchors: &[TempoAnchor]) -> Result<(), DbError> {
+        Self::delete_all_for_track(db, track_id)?;
+
+        for anchor in anchors {
+            Self::upsert(db, anchor)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Phrase Section Queries
+// ============================================================================
+
+/// Query builder for phrase sections (song structure)
+pub struct PhraseSectionQuery;
+
+impl PhraseSectionQuery {
+    /// Get all phrase sections for a track, in track order
+    pub fn get_for_track(db: &MeshDb, track_id: i64) -> Result<Vec<PhraseSection>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        let result = db.run_query(r#"
+            ?[track_id, index, start_sample, end_sample, kind, bars] :=
+                *phrase_sections{track_id, index, start_sample, end_sample, kind, bars},
+                track_id = $track_id
+            :order index
+        "#, params)?;
+
+        Ok(rows_to_phrase_sections(&result))
+    }
+
+    /// Insert or update a single phrase section
+    pub fn upsert(db: &MeshDb, section: &PhraseSection) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(section.track_id));
+        params.insert("index".to_string(), DataValue::from(section.index as i64));
+        params.insert("start_sample".to_string(), DataValue::from(section.start_sample));
+        params.insert("end_sample".to_string(), DataValue::from(section.end_sample));
+        params.insert("kind".to_string(), DataValue::from(section.kind.as_str()));
+        params.insert("bars".to_string(), DataValue::from(section.bars as i64));
+
+        db.run_script(r#"
+            ?[track_id, index, start_sample, end_sample, kind, bars] <-
+                [[$track_id, $index, $start_sample, $end_sample, $kind, $bars]]
+            :put phrase_sections {track_id, index => start_sample, end_sample, kind, bars}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Get all phrase sections for all tracks (bulk query for sync)
+    pub fn get_all(db: &MeshDb) -> Result<HashMap<i64, Vec<PhraseSection>>, DbError> {
+        let result = db.run_query(r#"
+            ?[track_id, index, start_sample, end_sample, kind, bars] :=
+                *phrase_sections{track_id, index, start_sample, end_sample, kind, bars}
+            :order track_id, index
+        "#, BTreeMap::new())?;
+
+        let mut map: HashMap<i64, Vec<PhraseSection>> = HashMap::new();
+        for section in rows_to_phrase_sections(&result) {
+            map.entry(section.track_id).or_default().push(section);
+        }
+        Ok(map)
+    }
+
+    /// Delete all phrase sections for a track
+    pub fn delete_all_for_track(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        db.run_script(r#"
+            ?[track_id, index] := *phrase_sections{track_id, index}, track_id = $track_id
+            :rm phrase_sections {track_id, index}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Replace all phrase sections for a track (delete existing, insert new)
+    pub fn replace_all(db: &MeshDb, track_id: i64, sections: &[PhraseSection]) -> Result<(), DbError> {
+        Self::delete_all_for_track(db, track_id)?;
+
+        for section in sections {
+            Self::upsert(db, section)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Key Segment Queries
+// ============================================================================
+
+/// Query builder for key segments (key timeline)
+pub struct KeySegmentQuery;
+
+impl KeySegmentQuery {
+    /// Get all key segments for a track, in track order
+    pub fn get_for_track(db: &MeshDb, track_id: i64) -> Result<Vec<KeySegment>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        let result = db.run_query(r#"
+            ?[track_id, index, start_sample, end_sample, key] :=
+                *key_segments{track_id, index, start_sample, end_sample, key},
+                track_id = $track_id
+            :order index
+        "#, params)?;
+
+        Ok(rows_to_key_segments(&result))
+    }
+
+    /// Insert or update a single key segment
+    pub fn upsert(db: &MeshDb, segment: &KeySegment) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(segment.track_id));
+        params.insert("index".to_string(), DataValue::from(segment.index as i64));
+        params.insert("start_sample".to_string(), DataValue::from(segment.start_sample));
+        params.insert("end_sample".to_string(), DataValue::from(segment.end_sample));
+        params.insert("key".to_string(), DataValue::from(segment.key.as_str()));
+
+        db.run_script(r#"
+            ?[track_id, index, start_sample, end_sample, key] <-
+                [[$track_id, $index, $start_sample, $end_sample, $key]]
+            :put key_segments {track_id, index => start_sample, end_sample, key}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Get all key segments for all tracks (bulk query for sync)
+    pub fn get_all(db: &MeshDb) -> Result<HashMap<i64, Vec<KeySegment>>, DbError> {
+        let result = db.run_query(r#"
+            ?[track_id, index, start_sample, end_sample, key] :=
+                *key_segments{track_id, index, start_sample, end_sample, key}
+            :order track_id, index
+        "#, BTreeMap::new())?;
+
+        let mut map: HashMap<i64, Vec<KeySegment>> = HashMap::new();
+        for segment in rows_to_key_segments(&result) {
+            map.entry(segment.track_id).or_default().push(segment);
+        }
+        Ok(map)
+    }
+
+    /// Batch-fetch the key each track starts in (its first segment)
+    ///
+    /// Tracks without a key timeline are absent from the map.
+    pub fn batch_get_intro_keys(db: &MeshDb, track_ids: &[i64]) -> Result<HashMap<i64, String>, DbError> {
+        if track_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids_list: Vec<DataValue> = track_ids.iter().map(|&id| DataValue::from(id)).collect();
+        let mut params = BTreeMap::new();
+        params.insert("ids".to_string(), DataValue::List(ids_list));
+
+        let result = db.run_query(r#"
+            ?[track_id, key] :=
+                track_id in $ids,
+                *key_segments{track_id, index, key},
+                index = 0
+        "#, params)?;
+
+        Ok(result.rows.iter().filter_map(|row| {
+            Some((row.get(0)?.get_int()?, row.get(1)?.get_str()?.to_string()))
+        }).collect())
+    }
+
+    /// Delete all key segments for a track
+    pub fn delete_all_for_track(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        db.run_script(r#"
+            ?[track_id, index] := *key_segments{track_id, index}, track_id = $track_id
+            :rm key_segments {track_id, index}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Replace all key segments for a track (delete existing, insert new)
+    pub fn replace_all(db: &MeshDb, track_id: i64, segments: &[KeySegment]) -> Result<(), DbError> {
+        Self::delete_all_for_track(db, track_id)?;
+
+        for segment in segments {
+            Self::upsert(db, segment)?;
+        }
+
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Stem Link Queries
 // ============================================================================
@@ -1550,6 +1946,185 @@ impl StemLinkQuery {
     }
 }
 
+// ============================================================================
+// Tag Metadata Queries
+// ============================================================================
+
+/// Query builder for embedded tag metadata (album, genre, year, ...)
+pub struct TagMetadataQuery;
+
+impl TagMetadataQuery {
+    /// Insert or replace the metadata row for a track
+    pub fn upsert(db: &MeshDb, meta: &TagMetadata) -> Result<(), DbError> {
+        let opt_str = |v: &Option<String>| v.as_ref().map(|s| DataValue::Str(s.clone().into())).unwrap_or(DataValue::Null);
+
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(meta.track_id));
+        params.insert("album".to_string(), opt_str(&meta.album));
+        params.insert("genre".to_string(), opt_str(&meta.genre));
+        params.insert("year".to_string(), meta.year.map(|y| DataValue::from(y as i64)).unwrap_or(DataValue::Null));
+        params.insert("label".to_string(), opt_str(&meta.label));
+        params.insert("comment".to_string(), opt_str(&meta.comment));
+        params.insert("artwork_path".to_string(), opt_str(&meta.artwork_path));
+        params.insert("tag_bpm".to_string(), meta.tag_bpm.map(DataValue::from).unwrap_or(DataValue::Null));
+        params.insert("tag_key".to_string(), opt_str(&meta.tag_key));
+
+        db.run_script(r#"
+            ?[track_id, album, genre, year, label, comment, artwork_path, tag_bpm, tag_key] <-
+                [[$track_id, $album, $genre, $year, $label, $comment, $artwork_path, $tag_bpm, $tag_key]]
+            :put tag_metadata {track_id => album, genre, year, label, comment, artwork_path, tag_bpm, tag_key}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Get the metadata row for a track (None if the track has none)
+    pub fn get(db: &MeshDb, track_id: i64) -> Result<Option<TagMetadata>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        let result = db.run_query(r#"
+            ?[track_id, album, genre, year, label, comment, artwork_path, tag_bpm, tag_key] :=
+                *tag_metadata{track_id, album, genre, year, label, comment, artwork_path, tag_bpm, tag_key},
+                track_id = $track_id
+        "#, params)?;
+
+        Ok(rows_to_tag_metadata(&result).into_iter().next())
+    }
+
+    /// Batch-fetch metadata for multiple tracks (avoids N+1 when listing folders)
+    pub fn batch_get(db: &MeshDb, track_ids: &[i64]) -> Result<HashMap<i64, TagMetadata>, DbError> {
+        if track_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids_list: Vec<DataValue> = track_ids.iter().map(|&id| DataValue::from(id)).collect();
+        let mut params = BTreeMap::new();
+        params.insert("ids".to_string(), DataValue::List(ids_list));
+
+        let result = db.run_query(r#"
+            ?[track_id, album, genre, year, label, comment, artwork_path, tag_bpm, tag_key] :=
+                *tag_metadata{track_id, album, genre, year, label, comment, artwork_path, tag_bpm, tag_key},
+                is_in(track_id, $ids)
+        "#, params)?;
+
+        Ok(rows_to_tag_metadata(&result)
+            .into_iter()
+            .map(|meta| (meta.track_id, meta))
+            .collect())
+    }
+
+    /// Get IDs of all tracks with a metadata row (USB sync presence check)
+    pub fn get_track_ids(db: &MeshDb) -> Result<Vec<i64>, DbError> {
+        let result = db.run_query(r#"
+            ?[track_id] := *tag_metadata{track_id}
+        "#, BTreeMap::new())?;
+        Ok(result.rows.iter()
+            .filter_map(|row| row.first().and_then(|v| v.get_int()))
+            .collect())
+    }
+
+    /// Delete the metadata row for a track
+    pub fn delete(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        db.run_script(r#"
+            ?[track_id] := *tag_metadata{track_id}, track_id = $track_id
+            :rm tag_metadata {track_id}
+        "#, params)?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Track Rating Queries
+// ============================================================================
+
+/// Query builder for star ratings and colour labels
+pub struct TrackRatingQuery;
+
+impl TrackRatingQuery {
+    /// Insert or replace the rating row for a track
+    pub fn upsert(db: &MeshDb, rating: &TrackRating) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(rating.track_id));
+        params.insert("rating".to_string(), DataValue::from(rating.rating as i64));
+        params.insert("color".to_string(), rating.color.as_ref()
+            .map(|c| DataValue::Str(c.clone().into()))
+            .unwrap_or(DataValue::Null));
+
+        db.run_script(r#"
+            ?[track_id, rating, color] <- [[$track_id, $rating, $color]]
+            :put track_ratings {track_id => rating, color}
+        "#, params)?;
+
+        Ok(())
+    }
+
+    /// Get the rating row for a track (None if unrated)
+    pub fn get(db: &MeshDb, track_id: i64) -> Result<Option<TrackRating>, DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        let result = db.run_query(r#"
+            ?[track_id, rating, color] :=
+                *track_ratings{track_id, rating, color},
+                track_id = $track_id
+        "#, params)?;
+
+        Ok(rows_to_track_ratings(&result).into_iter().next())
+    }
+
+    /// Batch-fetch ratings for multiple tracks
+    pub fn batch_get(db: &MeshDb, track_ids: &[i64]) -> Result<HashMap<i64, TrackRating>, DbError> {
+        if track_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids_list: Vec<DataValue> = track_ids.iter().map(|&id| DataValue::from(id)).collect();
+        let mut params = BTreeMap::new();
+        params.insert("ids".to_string(), DataValue::List(ids_list));
+
+        let result = db.run_query(r#"
+            ?[track_id, rating, color] :=
+                *track_ratings{track_id, rating, color},
+                is_in(track_id, $ids)
+        "#, params)?;
+
+        Ok(rows_to_track_ratings(&result)
+            .into_iter()
+            .map(|rating| (rating.track_id, rating))
+            .collect())
+    }
+
+    /// Get every rating row, keyed by track ID (bulk fetch for USB sync)
+    pub fn get_all(db: &MeshDb) -> Result<HashMap<i64, TrackRating>, DbError> {
+        let result = db.run_query(r#"
+            ?[track_id, rating, color] := *track_ratings{track_id, rating, color}
+        "#, BTreeMap::new())?;
+
+        Ok(rows_to_track_ratings(&result)
+            .into_iter()
+            .map(|rating| (rating.track_id, rating))
+            .collect())
+    }
+
+    /// Delete the rating row for a track
+    pub fn delete(db: &MeshDb, track_id: i64) -> Result<(), DbError> {
+        let mut params = BTreeMap::new();
+        params.insert("track_id".to_string(), DataValue::from(track_id));
+
+        db.run_script(r#"
+            ?[track_id] := *track_ratings{track_id}, track_id = $track_id
+            :rm track_ratings {track_id}
+        "#, params)?;
+
+        Ok(())
+    }
+}
+
 // ============================================================================
 // History Queries
 // ============================================================================
@@ -1868,6 +2443,27 @@ impl HistoryQuery {
         Ok(map)
     }
 
+    /// Get every started play as (session_id, loaded_at, track_path, play_started_at)
+    ///
+    /// Loads that were never played are skipped. Used for play-count and
+    /// last-played columns.
+    pub fn get_play_events(db: &MeshDb) -> Result<Vec<(i64, i64, String, i64)>, DbError> {
+        let result = db.run_query(r#"
+            ?[session_id, loaded_at, track_path, play_started_at] :=
+                *track_plays{session_id, loaded_at, track_path, play_started_at},
+                !is_null(play_started_at)
+        "#, BTreeMap::new())?;
+
+        Ok(result.rows.iter()
+            .filter_map(|row| Some((
+                row.first()?.get_int()?,
+                row.get(1)?.get_int()?,
+                row.get(2)?.get_str()?.to_string(),
+                row.get(3)?.get_int()?,
+            )))
+            .collect())
+    }
+
     /// Get all track paths played in a session (for suggestion filtering and browser dimming)
     pub fn get_session_played_paths(db: &MeshDb, session_id: i64) -> Result<HashSet<String>, DbError> {
         let mut params = BTreeMap::new();
@@ -1914,6 +2510,69 @@ fn rows_to_saved_loops(result: &NamedRows) -> Vec<SavedLoop> {
     }).collect()
 }
 
+fn rows_to_tempo_anchors(result: &NamedRows) -> Vec<TempoAnchor> {
+    result.rows.iter().filter_map(|row| {
+        Some(TempoAnchor {
+            track_id: row.get(0)?.get_int()?,
+            index: row.get(1)?.get_int()? as u32,
+            sample_position: row.get(2)?.get_int()?,
+            bpm: row.get(3)?.get_float()?,
+        })
+    }).collect()
+}
+
+fn rows_to_tag_metadata(result: &NamedRows) -> Vec<TagMetadata> {
+    result.rows.iter().filter_map(|row| {
+        let opt_str = |i: usize| row.get(i).and_then(|v| v.get_str()).map(|s| s.to_string());
+        Some(TagMetadata {
+            track_id: row.get(0)?.get_int()?,
+            album: opt_str(1),
+            genre: opt_str(2),
+            year: row.get(3).and_then(|v| v.get_int()).map(|y| y as i32),
+            label: opt_str(4),
+            comment: opt_str(5),
+            artwork_path: opt_str(6),
+            tag_bpm: row.get(7).and_then(|v| v.get_float()),
+            tag_key: opt_str(8),
+        })
+    }).collect()
+}
+
+fn rows_to_track_ratings(result: &NamedRows) -> Vec<TrackRating> {
+    result.rows.iter().filter_map(|row| {
+        Some(TrackRating {
+            track_id: row.get(0)?.get_int()?,
+            rating: row.get(1)?.get_int()?.clamp(0, 5) as u8,
+            color: row.get(2).and_then(|v| v.get_str()).map(|s| s.to_string()),
+        })
+    }).collect()
+}
+
+fn rows_to_phrase_sections(result: &NamedRows) -> Vec<PhraseSection> {
+    result.rows.iter().filter_map(|row| {
+        Some(PhraseSection {
+            track_id: row.get(0)?.get_int()?,
+            index: row.get(1)?.get_int()? as u32,
+            start_sample: row.get(2)?.get_int()?,
+            end_sample: row.get(3)?.get_int()?,
+            kind: row.get(4)?.get_str()?.to_string(),
+            bars: row.get(5)?.get_int()? as u32,
+        })
+    }).collect()
+}
+
+fn rows_to_key_segments(result: &NamedRows) -> Vec<KeySegment> {
+    result.rows.iter().filter_map(|row| {
+        Some(KeySegment {
+            track_id: row.get(0)?.get_int()?,
+            index: row.get(1)?.get_int()? as u32,
+            start_sample: row.get(2)?.get_int()?,
+            end_sample: row.get(3)?.get_int()?,
+            key: row.get(4)?.get_str()?.to_string(),
+        })
+    }).collect()
+}
+
 fn rows_to_tracks(result: &NamedRows) -> Vec<TrackRow> {
     result.rows.iter().filter_map(|row| {
         Some(TrackRow {
@@ -2028,6 +2687,71 @@ mod tests {
         assert!(SimilarityQuery::get_intensity_score(&db, 7).unwrap().is_none());
     }
 
+    #[test]
+    fn test_batch_get_intro_keys() {
+        let db = MeshDb::in_memory().unwrap();
+        let segment = |track_id, index, key: &str| KeySegment {
+            track_id,
+            index,
+            start_sample: index as i64 * 1000,
+            end_sample: (index as i64 + 1) * 1000,
+            key: key.to_string(),
+        };
+        KeySegmentQuery::replace_all(&db, 1, &[segment(1, 0, "Am"), segment(1, 1, "Cm")]).unwrap();
+        KeySegmentQuery::replace_all(&db, 2, &[segment(2, 0, "F#m")]).unwrap();
+        KeySegmentQuery::replace_all(&db, 3, &[segment(3, 0, "G")]).unwrap();
+
+        let keys = KeySegmentQuery::batch_get_intro_keys(&db, &[1, 2, 4]).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[&1], "Am");
+        assert_eq!(keys[&2], "F#m");
+        assert!(KeySegmentQuery::batch_get_intro_keys(&db, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tag_metadata_roundtrip() {
+        let db = MeshDb::in_memory().unwrap();
+        assert!(TagMetadataQuery::get(&db, 1).unwrap().is_none());
+
+        let meta = TagMetadata {
+            track_id: 1,
+            album: Some("Selected Works".to_string()),
+            genre: Some("Drum & Bass".to_string()),
+            year: Some(2019),
+            label: Some("Hospital".to_string()),
+            comment: None,
+            artwork_path: Some("artwork/1.jpg".to_string()),
+            tag_bpm: Some(174.0),
+            tag_key: Some("8A".to_string()),
+        };
+        TagMetadataQuery::upsert(&db, &meta).unwrap();
+        TagMetadataQuery::upsert(&db, &TagMetadata { track_id: 2, ..Default::default() }).unwrap();
+
+        assert_eq!(TagMetadataQuery::get(&db, 1).unwrap(), Some(meta.clone()));
+        let batch = TagMetadataQuery::batch_get(&db, &[1, 2, 3]).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[&2].album, None);
+
+        super::super::batch::BatchQuery::batch_delete_track_metadata(&db, 1).unwrap();
+        assert!(TagMetadataQuery::get(&db, 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_track_rating_roundtrip() {
+        let db = MeshDb::in_memory().unwrap();
+        assert!(TrackRatingQuery::get(&db, 1).unwrap().is_none());
+
+        let rating = TrackRating { track_id: 1, rating: 4, color: Some("#FF0000".to_string()) };
+        TrackRatingQuery::upsert(&db, &rating).unwrap();
+        TrackRatingQuery::upsert(&db, &TrackRating { track_id: 2, rating: 0, color: None }).unwrap();
+
+        assert_eq!(TrackRatingQuery::get(&db, 1).unwrap(), Some(rating));
+        assert_eq!(TrackRatingQuery::batch_get(&db, &[1, 2, 3]).unwrap().len(), 2);
+
+        super::super::batch::BatchQuery::batch_delete_track_metadata(&db, 1).unwrap();
+        assert!(TrackRatingQuery::get(&db, 1).unwrap().is_none());
+    }
+
     #[test]
     fn test_track_crud() {
         let db = MeshDb::in_memory().unwrap();
@@ -3,7 +3,8 @@
 //! This module defines the database schema using Rust structs that map to
 //! CozoDB relations. The schema includes:
 //!
-//! - Core relations: tracks, playlists, cue_points, saved_loops
+//! - Core relations: tracks, playlists, cue_points, saved_loops, tempo_anchors,
+//!   phrase_sections, key_segments
 //! - Graph edges: similar_to, played_after, harmonic_match
 //! - ML embeddings: 1280-dim EffNet + PCA-reduced for similarity search
 //! - Intensity components: multi-frame audio analysis for scoring
@@ -112,6 +113,88 @@ impl SavedLoop {
     }
 }
 
+/// A tempo-change anchor of a variable-tempo beat grid (database format)
+///
+/// Tracks with a constant tempo have no rows here; their grid is regenerated
+/// from `first_beat_sample` and `bpm` on the track row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TempoAnchor {
+    pub track_id: i64,
+    /// Position in the sorted anchor list
+    pub index: u32,
+    pub sample_position: i64,
+    pub bpm: f64,
+}
+
+impl TempoAnchor {
+    /// Create from runtime TempoAnchor format
+    pub fn from_runtime(track_id: i64, index: u32, anchor: &crate::audio_file::TempoAnchor) -> Self {
+        Self {
+            track_id,
+            index,
+            sample_position: anchor.sample_position as i64,
+            bpm: anchor.bpm,
+        }
+    }
+}
+
+/// A detected song section (database format)
+///
+/// One row per phrase-aligned section (intro, build, drop, breakdown, outro),
+/// written by the structure analysis in mesh-cue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhraseSection {
+    pub track_id: i64,
+    /// Position in track order
+    pub index: u32,
+    pub start_sample: i64,
+    pub end_sample: i64,
+    /// Section kind identifier (see `audio_file::SectionKind::as_str`)
+    pub kind: String,
+    pub bars: u32,
+}
+
+impl PhraseSection {
+    /// Create from runtime PhraseSection format
+    pub fn from_runtime(track_id: i64, index: u32, section: &crate::audio_file::PhraseSection) -> Self {
+        Self {
+            track_id,
+            index,
+            start_sample: section.start_sample as i64,
+            end_sample: section.end_sample as i64,
+            kind: section.kind.as_str().to_string(),
+            bars: section.bars,
+        }
+    }
+}
+
+/// A stretch of a track in one key (database format)
+///
+/// One row per segment of the windowed key timeline, written by the key
+/// analysis in mesh-cue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeySegment {
+    pub track_id: i64,
+    /// Position in track order
+    pub index: u32,
+    pub start_sample: i64,
+    pub end_sample: i64,
+    pub key: String,
+}
+
+impl KeySegment {
+    /// Create from runtime KeySegment format
+    pub fn from_runtime(track_id: i64, index: u32, segment: &crate::audio_file::KeySegment) -> Self {
+        Self {
+            track_id,
+            index,
+            start_sample: segment.start_sample as i64,
+            end_sample: segment.end_sample as i64,
+            key: segment.key.clone(),
+        }
+    }
+}
+
 /// A stem link for prepared mode (linking stems between tracks)
 ///
 /// Allows replacing a stem (e.g., drums) from one track with another track's stem,
@@ -153,6 +236,15 @@ pub struct SessionRecord {
     pub ended_at: Option<i64>,
 }
 
+/// Play count and last-played time of one track, aggregated from `track_plays`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackPlayStats {
+    /// Number of loads that were actually played
+    pub play_count: u32,
+    /// Unix ms timestamp of the most recent play
+    pub last_played: i64,
+}
+
 /// Data captured when a track is first loaded to a deck
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackPlayRecord {
@@ -164,7 +256,7 @@ pub struct TrackPlayRecord {
     pub track_name: String,
     pub track_id: Option<i64>,
     pub deck_index: u8,
-    /// "browser" or "suggestions"
+    /// "browser", "suggestions" or "auto_dj"
     pub load_source: String,
     pub suggestion_score: Option<f32>,
     /// JSON: [["Key ▲", "#2d8a4e"], ...]
@@ -227,6 +319,67 @@ pub struct HarmonicMatch {
     pub match_type: HarmonicMatchType,
 }
 
+/// Descriptive metadata imported from a track's embedded tags (database format)
+///
+/// Stored in its own relation so older databases gain it without a `tracks`
+/// migration. `tag_bpm` / `tag_key` are the values written by other software
+/// and are kept for reference even when analysis overrides them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagMetadata {
+    pub track_id: i64,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub year: Option<i32>,
+    /// Record label / publisher
+    pub label: Option<String>,
+    pub comment: Option<String>,
+    /// Extracted cover art, relative to the collection root
+    pub artwork_path: Option<String>,
+    pub tag_bpm: Option<f64>,
+    pub tag_key: Option<String>,
+}
+
+/// Star rating and colour label of a track (database format)
+///
+/// Tracks without a rating or colour have no row.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrackRating {
+    pub track_id: i64,
+    /// Stars, 0–5 (0 = unrated)
+    pub rating: u8,
+    /// Colour label as "#RRGGBB"
+    pub color: Option<String>,
+}
+
+impl TrackRating {
+    /// Colour labels cycled by the rating actions (Traktor's track colours)
+    pub const COLOR_LABELS: [&'static str; 7] = [
+        "#E0302E", // Red
+        "#F28C28", // Orange
+        "#F2D22E", // Yellow
+        "#3CB44B", // Green
+        "#2E7BE0", // Blue
+        "#8E44C9", // Violet
+        "#E040A0", // Magenta
+    ];
+
+    /// Add `delta` stars, clamped to 0–5
+    pub fn step_rating(&mut self, delta: i8) {
+        self.rating = (self.rating as i8 + delta).clamp(0, 5) as u8;
+    }
+
+    /// Move to the next colour label; after the last one the label is cleared
+    pub fn cycle_color(&mut self) {
+        let current = self.color.as_deref()
+            .and_then(|c| Self::COLOR_LABELS.iter().position(|l| l.eq_ignore_ascii_case(c)));
+        self.color = match current {
+            Some(i) if i + 1 == Self::COLOR_LABELS.len() => None,
+            Some(i) => Some(Self::COLOR_LABELS[i + 1].to_string()),
+            None if self.color.is_some() => None,
+            None => Some(Self::COLOR_LABELS[0].to_string()),
+        };
+    }
+}
 
 /// ML analysis results. It has no iced dependencies.
 ///
@@ -300,8 +453,13 @@ pub fn create_all_relations(db: &DbInstance) -> Result<(), DbError> {
     create_tracks_relation(db)?;
     create_playlists_relation(db)?;
     create_playlist_tracks_relation(db)?;
+    // Rule sets for smart playlists (tracks are evaluated, never stored)
+    create_smart_playlists_relation(db)?;
     create_cue_points_relation(db)?;
     create_saved_loops_relation(db)?;
+    create_tempo_anchors_relation(db)?;
+    create_phrase_sections_relation(db)?;
+    create_key_segments_relation(db)?;
     create_stem_links_relation(db)?;
     create_similar_to_relation(db)?;
     create_harmonic_match_relation(db)?;
@@ -377,6 +535,14 @@ pub fn create_all_relations(db: &DbInstance) -> Result<(), DbError> {
     // Stem energy density relation (vocal + other)
     create_stem_energy_relation(db)?;
 
+    // Album / genre / year / label / comment / artwork from embedded tags.
+    // Additive relation (no tracks migration); synced to USB without artwork.
+    create_tag_metadata_relation(db)?;
+
+    // Star ratings and colour labels (set in mesh-cue or imported from
+    // Rekordbox / Traktor libraries)
+    create_track_ratings_relation(db)?;
+
     // Transition graph: tracks played together → time-decayed co-play edges
     // Built explicitly via build_played_after_graph(); not auto-populated on import.
     create_played_after_relation(db)?;
@@ -492,6 +658,17 @@ fn create_playlist_tracks_relation(db: &DbInstance) -> Result<(), DbError> {
     "#)
 }
 
+fn create_smart_playlists_relation(db: &DbInstance) -> Result<(), DbError> {
+    // playlist_id references playlists.id; rules_json is a serialized
+    // playlist::SmartPlaylistRules
+    run_schema(db, r#"
+        {:create smart_playlists {
+            playlist_id: Int =>
+            rules_json: String
+        }}
+    "#)
+}
+
 fn create_cue_points_relation(db: &DbInstance) -> Result<(), DbError> {
     run_schema(db, r#"
         {:create cue_points {
@@ -517,6 +694,42 @@ fn create_saved_loops_relation(db: &DbInstance) -> Result<(), DbError> {
     "#)
 }
 
+fn create_tempo_anchors_relation(db: &DbInstance) -> Result<(), DbError> {
+    run_schema(db, r#"
+        {:create tempo_anchors {
+            track_id: Int,
+            index: Int =>
+            sample_position: Int,
+            bpm: Float
+        }}
+    "#)
+}
+
+fn create_phrase_sections_relation(db: &DbInstance) -> Result<(), DbError> {
+    run_schema(db, r#"
+        {:create phrase_sections {
+            track_id: Int,
+            index: Int =>
+            start_sample: Int,
+            end_sample: Int,
+            kind: String,
+            bars: Int
+        }}
+    "#)
+}
+
+fn create_key_segments_relation(db: &DbInstance) -> Result<(), DbError> {
+    run_schema(db, r#"
+        {:create key_segments {
+            track_id: Int,
+            index: Int =>
+            start_sample: Int,
+            end_sample: Int,
+            key: String
+        }}
+    "#)
+}
+
 fn create_stem_links_relation(db: &DbInstance) -> Result<(), DbError> {
     run_schema(db, r#"
         {:create stem_links {
@@ -947,6 +1160,34 @@ fn create_stem_energy_relation(db: &DbInstance) -> Result<(), DbError> {
     "#)
 }
 
+fn create_tag_metadata_relation(db: &DbInstance) -> Result<(), DbError> {
+    // Embedded tag metadata, one row per track. Point-lookup only.
+    run_schema(db, r#"
+        {:create tag_metadata {
+            track_id: Int =>
+            album: String?,
+            genre: String?,
+            year: Int?,
+            label: String?,
+            comment: String?,
+            artwork_path: String?,
+            tag_bpm: Float?,
+            tag_key: String?
+        }}
+    "#)
+}
+
+fn create_track_ratings_relation(db: &DbInstance) -> Result<(), DbError> {
+    // Rating + colour label, one row per rated track. Point-lookup only.
+    run_schema(db, r#"
+        {:create track_ratings {
+            track_id: Int =>
+            rating: Int,
+            color: String?
+        }}
+    "#)
+}
+
 fn create_played_after_relation(db: &DbInstance) -> Result<(), DbError> {
     // Transition graph: how many times track A was playing when track B started.
     // Bidirectional (both directions stored separately). Built from track_plays.played_with_json
@@ -1050,4 +1291,23 @@ mod tests {
         assert_eq!(HarmonicMatchType::from_str("adjacent"), Some(HarmonicMatchType::Adjacent));
         assert_eq!(HarmonicMatchType::from_str("invalid"), None);
     }
+
+    #[test]
+    fn test_track_rating_steps() {
+        let mut rating = TrackRating { track_id: 1, rating: 4, color: None };
+        rating.step_rating(3);
+        assert_eq!(rating.rating, 5);
+        rating.step_rating(-7);
+        assert_eq!(rating.rating, 0);
+
+        rating.cycle_color();
+        assert_eq!(rating.color.as_deref(), Some("#E0302E"));
+        rating.color = Some(TrackRating::COLOR_LABELS[6].to_string());
+        rating.cycle_color();
+        assert_eq!(rating.color, None);
+        // Imported colours outside the palette are cleared
+        rating.color = Some("#123456".to_string());
+        rating.cycle_color();
+        assert_eq!(rating.color, None);
+    }
 }
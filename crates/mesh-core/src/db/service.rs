@@ -28,11 +28,13 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 use super::batch::BatchQuery;
-use super::queries::{TrackQuery, PlaylistQuery, SimilarityQuery, CuePointQuery, SavedLoopQuery, StemLinkQuery, HistoryQuery};
-use super::schema::{TrackRow, Playlist, CuePoint, SavedLoop, StemLink, TrackPlayRecord, TrackPlayUpdate};
+use super::queries::{TrackQuery, PlaylistQuery, SmartPlaylistQuery, SimilarityQuery, CuePointQuery, SavedLoopQuery, TempoAnchorQuery, PhraseSectionQuery, KeySegmentQuery, StemLinkQuery, TagMetadataQuery, TrackRatingQuery, HistoryQuery};
+use super::schema::{TrackRow, Playlist, CuePoint, SavedLoop, TempoAnchor, PhraseSection, KeySegment, StemLink, TagMetadata, TrackRating, TrackPlayRecord, TrackPlayStats, TrackPlayUpdate};
 use super::{MeshDb, DbError};
+use crate::playlist::SmartPlaylistRules;
+use crate::search::{SearchQuery, SearchVocabulary};
 use cozo::DataValue;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 // ============================================================================
 // ML Scores - Lightweight Struct for Suggestion Scoring
@@ -110,6 +112,12 @@ pub struct Track {
     pub cue_points: Vec<CuePoint>,
     /// Saved loops (up to 8)
     pub saved_loops: Vec<SavedLoop>,
+    /// Tempo-change anchors (empty for constant-tempo tracks)
+    pub tempo_anchors: Vec<TempoAnchor>,
+    /// Detected song structure (empty if not analysed)
+    pub phrase_sections: Vec<PhraseSection>,
+    /// Key timeline (empty if not analysed)
+    pub key_segments: Vec<KeySegment>,
     /// Stem links for prepared mode
     pub stem_links: Vec<StemLink>,
 }
@@ -137,15 +145,31 @@ impl Track {
             waveform_path: None,
             cue_points: Vec::new(),
             saved_loops: Vec::new(),
+            tempo_anchors: Vec::new(),
+            phrase_sections: Vec::new(),
+            key_segments: Vec::new(),
             stem_links: Vec::new(),
         }
     }
 
+    /// Key at the start of the track (first timeline segment, else `key`)
+    pub fn intro_key(&self) -> Option<&str> {
+        self.key_segments.first().map(|s| s.key.as_str()).or(self.key.as_deref())
+    }
+
+    /// Key at the end of the track (last timeline segment, else `key`)
+    pub fn outro_key(&self) -> Option<&str> {
+        self.key_segments.last().map(|s| s.key.as_str()).or(self.key.as_deref())
+    }
+
     /// Convert from internal database row representation
     pub(crate) fn from_row(
         row: TrackRow,
         cue_points: Vec<CuePoint>,
         saved_loops: Vec<SavedLoop>,
+        tempo_anchors: Vec<TempoAnchor>,
+        phrase_sections: Vec<PhraseSection>,
+        key_segments: Vec<KeySegment>,
         stem_links: Vec<StemLink>,
     ) -> Self {
         Self {
@@ -168,13 +192,16 @@ impl Track {
             waveform_path: row.waveform_path,
             cue_points,
             saved_loops,
+            tempo_anchors,
+            phrase_sections,
+            key_segments,
             stem_links,
         }
     }
 
     /// Convert from row without loading associated data (for batch operations)
     pub(crate) fn from_row_only(row: TrackRow) -> Self {
-        Self::from_row(row, Vec::new(), Vec::new(), Vec::new())
+        Self::from_row(row, Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new())
     }
 
     /// Convert to internal database row representation
@@ -364,7 +391,7 @@ impl DatabaseService {
 
     /// Get a track by its database ID with all metadata
     ///
-    /// Returns the track with cue_points, saved_loops, and stem_links loaded.
+    /// Returns the track with cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, and stem_links loaded.
     pub fn get_track(&self, id: i64) -> Result<Option<Track>, DbError> {
         let row = match TrackQuery::get_by_id(&self.db, id)? {
             Some(r) => r,
@@ -376,7 +403,7 @@ impl DatabaseService {
 
     /// Get a track by its file path with all metadata
     ///
-    /// Returns the track with cue_points, saved_loops, and stem_links loaded.
+    /// Returns the track with cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, and stem_links loaded.
     pub fn get_track_by_path(&self, path: &str) -> Result<Option<Track>, DbError> {
         let row = match TrackQuery::get_by_path(&self.db, path)? {
             Some(r) => r,
@@ -401,7 +428,7 @@ impl DatabaseService {
     /// Save a track with all its metadata
     ///
     /// This will insert or update the track and all associated metadata
-    /// (cue_points, saved_loops, stem_links). Returns the track ID.
+    /// (cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links). Returns the track ID.
     ///
     /// # Example
     /// ```ignore
@@ -452,6 +479,27 @@ impl DatabaseService {
             SavedLoopQuery::upsert(&self.db, &loop_with_id)?;
         }
 
+        TempoAnchorQuery::delete_all_for_track(&self.db, track_id)?;
+        for anchor in &track.tempo_anchors {
+            let mut anchor_with_id = anchor.clone();
+            anchor_with_id.track_id = track_id;
+            TempoAnchorQuery::upsert(&self.db, &anchor_with_id)?;
+        }
+
+        PhraseSectionQuery::delete_all_for_track(&self.db, track_id)?;
+        for section in &track.phrase_sections {
+            let mut section_with_id = section.clone();
+            section_with_id.track_id = track_id;
+            PhraseSectionQuery::upsert(&self.db, &section_with_id)?;
+        }
+
+        KeySegmentQuery::delete_all_for_track(&self.db, track_id)?;
+        for segment in &track.key_segments {
+            let mut segment_with_id = segment.clone();
+            segment_with_id.track_id = track_id;
+            KeySegmentQuery::upsert(&self.db, &segment_with_id)?;
+        }
+
         StemLinkQuery::delete_all_for_track(&self.db, track_id)?;
         for link in &track.stem_links {
             let mut link_with_id = link.clone();
@@ -469,10 +517,10 @@ impl DatabaseService {
     /// need to be synced. It uses batch inserts instead of individual queries:
     ///
     /// - 1 query: Upsert track row
-    /// - 3 queries: Delete old metadata (cue_points, saved_loops, stem_links)
-    /// - 3 queries: Batch insert new metadata
+    /// - 6 queries: Delete old metadata (cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links)
+    /// - 6 queries: Batch insert new metadata
     ///
-    /// Total: ~7 queries instead of 18+ with individual inserts.
+    /// Total: ~13 queries instead of 18+ with individual inserts.
     ///
     /// # Arguments
     /// * `track` - The track to sync (from source database)
@@ -514,6 +562,9 @@ impl DatabaseService {
         // 4. Batch insert all metadata
         BatchQuery::batch_insert_cue_points(&self.db, track_id, &track.cue_points)?;
         BatchQuery::batch_insert_saved_loops(&self.db, track_id, &track.saved_loops)?;
+        BatchQuery::batch_insert_tempo_anchors(&self.db, track_id, &track.tempo_anchors)?;
+        BatchQuery::batch_insert_phrase_sections(&self.db, track_id, &track.phrase_sections)?;
+        BatchQuery::batch_insert_key_segments(&self.db, track_id, &track.key_segments)?;
         BatchQuery::batch_insert_stem_links(&self.db, track_id, &remapped_links)?;
 
         // 5. Sync ML analysis data. (Intensity travels separately as the
@@ -551,6 +602,17 @@ impl DatabaseService {
             let _ = self.store_pca_embedding(track_id, &pca_emb);
         }
 
+        // 11. Sync embedded tag metadata. Artwork files stay in the local
+        // collection, so the path is dropped rather than left dangling.
+        if let Ok(Some(meta)) = source_db.get_tag_metadata(source_track_id) {
+            let _ = self.store_tag_metadata(&TagMetadata { track_id, artwork_path: None, ..meta });
+        }
+
+        // 12. Sync star rating and colour label
+        if let Ok(Some(rating)) = source_db.get_track_rating(source_track_id) {
+            let _ = self.set_track_rating(&TrackRating { track_id, ..rating });
+        }
+
         log::debug!("sync_track_atomic: SUCCESS id={}", track_id);
         Ok(track_id)
     }
@@ -654,14 +716,44 @@ impl DatabaseService {
         Ok(rows.into_iter().map(Track::from_row_only).collect())
     }
 
-    /// Search tracks by name or artist
+    /// Search tracks with the browser search syntax
     ///
-    /// Returns tracks with basic metadata only.
+    /// Accepts plain words (title or artist) and `field:value` filters, see
+    /// [`crate::search`]. Returns tracks with basic metadata only.
     pub fn search_tracks(&self, query: &str, limit: usize) -> Result<Vec<Track>, DbError> {
         let rows = TrackQuery::search(&self.db, query, limit)?;
         Ok(rows.into_iter().map(Track::from_row_only).collect())
     }
 
+    /// Paths of every track matching a parsed search
+    ///
+    /// Used by the browsers to filter the rows they already show.
+    pub fn search_track_paths(&self, query: &SearchQuery) -> Result<HashSet<String>, DbError> {
+        TrackQuery::search_paths(&self.db, query)
+    }
+
+    /// Tag labels and genres offered by search autocomplete, sorted
+    pub fn get_search_vocabulary(&self) -> Result<SearchVocabulary, DbError> {
+        let column = |script: &str| -> Result<Vec<String>, DbError> {
+            let result = self.db.run_query(script, BTreeMap::new())?;
+            let mut values: Vec<String> = result.rows.iter()
+                .filter_map(|row| row.first().and_then(|v| v.get_str()).map(|s| s.to_string()))
+                .collect();
+            values.sort_by_key(|s| s.to_lowercase());
+            values.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+            Ok(values)
+        };
+
+        Ok(SearchVocabulary {
+            tags: column("?[label] := *track_tags{label}")?,
+            genres: column(r#"
+                genres[genre] := *ml_analysis{top_genre: genre}, !is_null(genre)
+                genres[genre] := *tag_metadata{genre}, !is_null(genre)
+                ?[genre] := genres[genre]
+            "#)?,
+        })
+    }
+
     /// Get all unique folder paths in the collection
     pub fn get_folders(&self) -> Result<Vec<String>, DbError> {
         TrackQuery::get_folders(&self.db)
@@ -725,6 +817,41 @@ impl DatabaseService {
         SavedLoopQuery::delete(&self.db, track_id, index)
     }
 
+    /// Get tempo anchors for a track (empty for constant-tempo tracks)
+    pub fn get_tempo_anchors(&self, track_id: i64) -> Result<Vec<TempoAnchor>, DbError> {
+        TempoAnchorQuery::get_for_track(&self.db, track_id)
+    }
+
+    /// Delete all tempo anchors for a track (back to a constant-tempo grid)
+    pub fn delete_tempo_anchors(&self, track_id: i64) -> Result<(), DbError> {
+        TempoAnchorQuery::delete_all_for_track(&self.db, track_id)
+    }
+
+    /// Get the detected song structure of a track (empty if not analysed)
+    pub fn get_phrase_sections(&self, track_id: i64) -> Result<Vec<PhraseSection>, DbError> {
+        PhraseSectionQuery::get_for_track(&self.db, track_id)
+    }
+
+    /// Replace the song structure of a track (e.g. after re-analysis)
+    pub fn set_phrase_sections(&self, track_id: i64, sections: &[PhraseSection]) -> Result<(), DbError> {
+        PhraseSectionQuery::replace_all(&self.db, track_id, sections)
+    }
+
+    /// Get the key timeline of a track (empty if not analysed)
+    pub fn get_key_segments(&self, track_id: i64) -> Result<Vec<KeySegment>, DbError> {
+        KeySegmentQuery::get_for_track(&self.db, track_id)
+    }
+
+    /// Replace the key timeline of a track (e.g. after key re-analysis)
+    pub fn set_key_segments(&self, track_id: i64, segments: &[KeySegment]) -> Result<(), DbError> {
+        KeySegmentQuery::replace_all(&self.db, track_id, segments)
+    }
+
+    /// Batch-fetch the key each track starts in (avoids scanning every timeline)
+    pub fn batch_get_intro_keys(&self, track_ids: &[i64]) -> Result<HashMap<i64, String>, DbError> {
+        KeySegmentQuery::batch_get_intro_keys(&self.db, track_ids)
+    }
+
     /// Get stem links for a track
     pub fn get_stem_links(&self, track_id: i64) -> Result<Vec<StemLink>, DbError> {
         StemLinkQuery::get_for_track(&self.db, track_id)
@@ -935,6 +1062,28 @@ impl DatabaseService {
         PlaylistQuery::next_sort_order(&self.db, playlist_id)
     }
 
+    /// Create a smart playlist with the given rules
+    pub fn create_smart_playlist(&self, name: &str, parent_id: Option<i64>, rules: &SmartPlaylistRules) -> Result<i64, DbError> {
+        let id = PlaylistQuery::create(&self.db, name, parent_id)?;
+        SmartPlaylistQuery::set_rules(&self.db, id, rules)?;
+        Ok(id)
+    }
+
+    /// Get the rules of a smart playlist (None for manual playlists)
+    pub fn get_smart_playlist_rules(&self, playlist_id: i64) -> Result<Option<SmartPlaylistRules>, DbError> {
+        SmartPlaylistQuery::get_rules(&self.db, playlist_id)
+    }
+
+    /// Replace the rules of a smart playlist
+    pub fn set_smart_playlist_rules(&self, playlist_id: i64, rules: &SmartPlaylistRules) -> Result<(), DbError> {
+        SmartPlaylistQuery::set_rules(&self.db, playlist_id, rules)
+    }
+
+    /// IDs of all smart playlists
+    pub fn get_smart_playlist_ids(&self) -> Result<HashSet<i64>, DbError> {
+        SmartPlaylistQuery::get_all_ids(&self.db)
+    }
+
     // ========================================================================
     // ML Embeddings & Similarity
     // ========================================================================
@@ -1041,6 +1190,51 @@ impl DatabaseService {
         SimilarityQuery::batch_get_stem_energy(&self.db, track_ids)
     }
 
+    // ── Embedded tag metadata ───────────────────────────────────────────────
+
+    /// Store album / genre / year / label / comment / artwork for a track.
+    pub fn store_tag_metadata(&self, meta: &TagMetadata) -> Result<(), DbError> {
+        TagMetadataQuery::upsert(&self.db, meta)
+    }
+
+    /// Get the embedded tag metadata for a single track.
+    pub fn get_tag_metadata(&self, track_id: i64) -> Result<Option<TagMetadata>, DbError> {
+        TagMetadataQuery::get(&self.db, track_id)
+    }
+
+    /// Batch-fetch tag metadata for multiple tracks (track table listings).
+    pub fn batch_get_tag_metadata(&self, track_ids: &[i64]) -> Result<HashMap<i64, TagMetadata>, DbError> {
+        TagMetadataQuery::batch_get(&self.db, track_ids)
+    }
+
+    // ── Ratings and colour labels ───────────────────────────────────────────
+
+    /// Set a track's star rating and colour label.
+    ///
+    /// A rating of 0 with no colour removes the row.
+    pub fn set_track_rating(&self, rating: &TrackRating) -> Result<(), DbError> {
+        if rating.rating == 0 && rating.color.is_none() {
+            TrackRatingQuery::delete(&self.db, rating.track_id)
+        } else {
+            TrackRatingQuery::upsert(&self.db, rating)
+        }
+    }
+
+    /// Get the rating / colour label of a single track (None if unrated).
+    pub fn get_track_rating(&self, track_id: i64) -> Result<Option<TrackRating>, DbError> {
+        TrackRatingQuery::get(&self.db, track_id)
+    }
+
+    /// Batch-fetch ratings for multiple tracks.
+    pub fn batch_get_track_ratings(&self, track_ids: &[i64]) -> Result<HashMap<i64, TrackRating>, DbError> {
+        TrackRatingQuery::batch_get(&self.db, track_ids)
+    }
+
+    /// Get every rated track's rating, keyed by track ID.
+    pub fn get_all_track_ratings(&self) -> Result<HashMap<i64, TrackRating>, DbError> {
+        TrackRatingQuery::get_all(&self.db)
+    }
+
     // ── PCA 128-dim embeddings ───────────────────────────────────────────────
 
     /// Store a PCA-projected embedding (built by "Build Similarity Index").
@@ -1434,14 +1628,17 @@ impl DatabaseService {
     // Internal Helpers
     // ========================================================================
 
-    /// Load full track metadata (cue_points, saved_loops, stem_links) for a row
+    /// Load full track metadata (cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links) for a row
     fn load_track_metadata(&self, row: TrackRow) -> Result<Track, DbError> {
         let track_id = row.id;
         let cue_points = CuePointQuery::get_for_track(&self.db, track_id)?;
         let saved_loops = SavedLoopQuery::get_for_track(&self.db, track_id)?;
+        let tempo_anchors = TempoAnchorQuery::get_for_track(&self.db, track_id)?;
+        let phrase_sections = PhraseSectionQuery::get_for_track(&self.db, track_id)?;
+        let key_segments = KeySegmentQuery::get_for_track(&self.db, track_id)?;
         let stem_links = StemLinkQuery::get_for_track(&self.db, track_id)?;
 
-        Ok(Track::from_row(row, cue_points, saved_loops, stem_links))
+        Ok(Track::from_row(row, cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links))
     }
 
     // ========================================================================
@@ -1794,6 +1991,45 @@ impl DatabaseService {
         HistoryQuery::get_session_played_paths(&self.db, session_id)
     }
 
+    /// Aggregate play counts and last-played times across several databases
+    ///
+    /// mesh-player writes every play to the local database and to each USB
+    /// stick, so the same play is deduplicated by its (session, load time)
+    /// key. Paths differ between databases, so the result is keyed by file
+    /// name — look rows up with [`DatabaseService::play_stats_key`].
+    pub fn aggregate_play_stats<'a>(
+        dbs: impl IntoIterator<Item = &'a DatabaseService>,
+    ) -> HashMap<String, TrackPlayStats> {
+        let mut seen = HashSet::new();
+        let mut stats: HashMap<String, TrackPlayStats> = HashMap::new();
+        for db in dbs {
+            let events = match HistoryQuery::get_play_events(&db.db) {
+                Ok(events) => events,
+                Err(e) => {
+                    log::warn!("aggregate_play_stats: failed to read plays: {}", e);
+                    continue;
+                }
+            };
+            for (session_id, loaded_at, path, started_at) in events {
+                if !seen.insert((session_id, loaded_at)) {
+                    continue;
+                }
+                let entry = stats.entry(Self::play_stats_key(&path)).or_default();
+                entry.play_count += 1;
+                entry.last_played = entry.last_played.max(started_at);
+            }
+        }
+        stats
+    }
+
+    /// Key of a track path in [`DatabaseService::aggregate_play_stats`] (its file name)
+    pub fn play_stats_key(path: &str) -> String {
+        Path::new(path)
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string())
+    }
+
     // ========================================================================
     // Low-level Access (for advanced usage within mesh-core)
     // ========================================================================
@@ -1982,6 +2218,43 @@ mod tests {
         assert_eq!(by_title.len(), 1);
     }
 
+    #[test]
+    fn test_search_tracks_structured_query() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("tracks");
+        std::fs::create_dir_all(&dir).unwrap();
+        let service = DatabaseService::new(temp.path()).unwrap();
+
+        let mut ids = Vec::new();
+        for (name, bpm, key) in [("dub.flac", 118.0, "8A"), ("peak.flac", 126.0, "Am"), ("rave.flac", 128.0, "10B")] {
+            let path = dir.join(name);
+            std::fs::write(&path, b"dummy").unwrap();
+            let mut t = Track::new(path, name);
+            t.bpm = Some(bpm);
+            t.key = Some(key.to_string());
+            t.duration_seconds = 180.0;
+            ids.push(service.save_track(&t).unwrap());
+        }
+        service.add_tag(ids[1], "Peak Time", None).unwrap();
+        service.add_tag(ids[2], "Peak Time", None).unwrap();
+
+        let titles = |query: &str| {
+            service.search_tracks(query, 10).unwrap().into_iter().map(|t| t.title).collect::<Vec<_>>()
+        };
+        assert_eq!(titles("bpm:124-128 key:8A,9A"), vec!["peak.flac"]);
+        assert_eq!(titles(r#"tag:"peak time" -rave"#), vec!["peak.flac"]);
+        assert_eq!(titles("bpm:<120"), vec!["dub.flac"]);
+        // No session recorded yet: nothing counts as played
+        assert_eq!(titles("-played bpm:>125").len(), 2);
+        assert!(titles("played").is_empty());
+
+        let paths = service.search_track_paths(&SearchQuery::parse("plays:0")).unwrap();
+        assert_eq!(paths.len(), 3);
+
+        let vocab = service.get_search_vocabulary().unwrap();
+        assert_eq!(vocab.tags, vec!["Peak Time"]);
+    }
+
     #[test]
     fn test_get_distinct_artists_excludes_null() {
         let temp = TempDir::new().unwrap();
@@ -2055,4 +2328,84 @@ mod tests {
         assert!(!paths.contains("/music/loaded.flac")); // null play_started_at excluded
         assert_eq!(paths.len(), 1);
     }
+
+    #[test]
+    fn test_aggregate_play_stats_dedupes_across_databases() {
+        let local_dir = TempDir::new().unwrap();
+        let usb_dir = TempDir::new().unwrap();
+        let local = DatabaseService::new(local_dir.path()).unwrap();
+        let usb = DatabaseService::new(usb_dir.path()).unwrap();
+
+        let play = |loaded_at: i64, track_path: &str| TrackPlayRecord {
+            session_id: 1,
+            loaded_at,
+            track_path: track_path.to_string(),
+            track_name: "A - Track".to_string(),
+            track_id: None,
+            deck_index: 0,
+            load_source: "browser".to_string(),
+            suggestion_score: None,
+            suggestion_tags_json: None,
+            suggestion_energy_dir: None,
+        };
+        // The same play lands in both databases, plus one local-only play
+        // and one load that never started
+        for db in [&local, &usb] {
+            db.create_session(1).unwrap();
+            db.insert_track_play(&play(1000, "/music/a.flac")).unwrap();
+            db.update_play_started(1, 1000, 5000, 0, None).unwrap();
+        }
+        local.insert_track_play(&play(2000, "/usb/mesh-collection/tracks/a.flac")).unwrap();
+        local.update_play_started(1, 2000, 9000, 0, None).unwrap();
+        local.insert_track_play(&play(3000, "/music/b.flac")).unwrap();
+
+        let stats = DatabaseService::aggregate_play_stats([&local, &usb]);
+        let a = stats.get(&DatabaseService::play_stats_key("/other/a.flac")).unwrap();
+        assert_eq!(a.play_count, 2);
+        assert_eq!(a.last_played, 9000);
+        assert!(!stats.contains_key("b.flac"));
+    }
+
+    #[test]
+    fn test_smart_playlist_evaluates_rules() {
+        use crate::playlist::{SmartMatch, SmartRule};
+
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("tracks");
+        std::fs::create_dir_all(&dir).unwrap();
+        let service = DatabaseService::new(temp.path()).unwrap();
+
+        let mut ids = Vec::new();
+        for (name, bpm, key) in [("a.flac", 118.0, "8A"), ("b.flac", 124.0, "Am"), ("c.flac", 128.0, "9A")] {
+            let path = dir.join(name);
+            std::fs::write(&path, b"dummy").unwrap();
+            let mut t = Track::new(path, name);
+            t.bpm = Some(bpm);
+            t.key = Some(key.to_string());
+            t.duration_seconds = 180.0;
+            ids.push(service.save_track(&t).unwrap());
+        }
+        service.add_tag(ids[0], "Warmup", None).unwrap();
+
+        let mut rules = SmartPlaylistRules {
+            rules: vec![
+                SmartRule::Bpm { min: Some(120.0), max: Some(130.0) },
+                SmartRule::Key { keys: vec!["8A".to_string()] },
+            ],
+            ..Default::default()
+        };
+        let id = service.create_smart_playlist("Peak 8A", None, &rules).unwrap();
+        let titles = |tracks: Vec<Track>| tracks.into_iter().map(|t| t.title).collect::<Vec<_>>();
+        // "Am" is stored in musical notation but is the same key as 8A
+        assert_eq!(titles(service.get_playlist_tracks(id).unwrap()), vec!["b.flac"]);
+
+        rules.match_mode = SmartMatch::Any;
+        rules.rules.push(SmartRule::TagsAny { tags: vec!["Warmup".to_string()] });
+        service.set_smart_playlist_rules(id, &rules).unwrap();
+        assert_eq!(titles(service.get_playlist_tracks(id).unwrap()), vec!["a.flac", "b.flac", "c.flac"]);
+        assert_eq!(service.get_smart_playlist_rules(id).unwrap(), Some(rules));
+
+        service.delete_playlist(id).unwrap();
+        assert!(service.get_smart_playlist_ids().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,235 @@
+//! Timecode vinyl formats and their bit sequences
+//!
+//! Each format is a stereo sine carrier in quadrature with one bit per
+//! carrier cycle, encoded in the amplitude of one channel's peak (loud = 1,
+//! quiet = 0). The bits come from a linear feedback shift register, so any
+//! `bits` consecutive bits identify a unique position on the record.
+//!
+//! Which channel carries the bits, which of its peaks holds them and the
+//! phase between the channels differ per format; they follow the
+//! `SWITCH_PRIMARY`, `SWITCH_POLARITY` and `SWITCH_PHASE` flags of xwax's
+//! timecoder, where the LFSR parameters come from as well.
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Timecode record or CD the decks are controlled with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimecodeFormat {
+    /// Serato Control Vinyl, 2nd edition, side A (1 kHz)
+    #[default]
+    SeratoA,
+    /// Serato Control Vinyl, 2nd edition, side B (1 kHz)
+    SeratoB,
+    /// Serato Control CD (1 kHz)
+    SeratoCd,
+    /// Traktor Scratch MK1/MK2 vinyl, side A (2 kHz)
+    TraktorA,
+    /// Traktor Scratch MK1/MK2 vinyl, side B (2 kHz)
+    TraktorB,
+}
+
+/// LFSR parameters and channel layout of one format
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FormatDef {
+    /// Carrier frequency at normal speed (cycles per second = bits per second)
+    pub carrier_hz: f64,
+    /// Shift register width
+    pub bits: u32,
+    /// Register value at the start of the record
+    pub seed: u32,
+    /// Feedback taps
+    pub taps: u32,
+    /// Cycles in the sequence (length of the record)
+    pub length: u32,
+    /// Bits are carried by the left channel instead of the right
+    /// (xwax `SWITCH_PRIMARY`)
+    pub primary_left: bool,
+    /// Bits are read at the negative peaks of the primary channel instead of
+    /// the positive ones (xwax `SWITCH_POLARITY`)
+    pub negative_polarity: bool,
+    /// Playing forwards the primary channel lags the other one by 90°
+    /// instead of leading it (xwax `SWITCH_PHASE`)
+    pub phase_270: bool,
+}
+
+impl TimecodeFormat {
+    /// All formats, in display order
+    pub const ALL: [TimecodeFormat; 5] = [
+        TimecodeFormat::SeratoA,
+        TimecodeFormat::SeratoB,
+        TimecodeFormat::SeratoCd,
+        TimecodeFormat::TraktorA,
+        TimecodeFormat::TraktorB,
+    ];
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            TimecodeFormat::SeratoA => "Serato CV02 A",
+            TimecodeFormat::SeratoB => "Serato CV02 B",
+            TimecodeFormat::SeratoCd => "Serato CD",
+            TimecodeFormat::TraktorA => "Traktor A",
+            TimecodeFormat::TraktorB => "Traktor B",
+        }
+    }
+
+    /// Carrier frequency at normal speed
+    pub fn carrier_hz(self) -> f64 {
+        self.def().carrier_hz
+    }
+
+    /// Playing time of the timecode at normal speed
+    pub fn length_secs(self) -> f64 {
+        let def = self.def();
+        def.length as f64 / def.carrier_hz
+    }
+
+    pub(crate) fn def(self) -> FormatDef {
+        match self {
+            TimecodeFormat::SeratoA => FormatDef {
+                carrier_hz: 1000.0, bits: 20, seed: 0x59017, taps: 0x361e4, length: 712_000,
+                ..FormatDef::SERATO
+            },
+            TimecodeFormat::SeratoB => FormatDef {
+                carrier_hz: 1000.0, bits: 20, seed: 0x8f3c6, taps: 0x4f0d8, length: 922_000,
+                ..FormatDef::SERATO
+            },
+            TimecodeFormat::SeratoCd => FormatDef {
+                carrier_hz: 1000.0, bits: 20, seed: 0xd8b40, taps: 0x34d54, length: 950_000,
+                ..FormatDef::SERATO
+            },
+            TimecodeFormat::TraktorA => FormatDef {
+                carrier_hz: 2000.0, bits: 23, seed: 0x134503, taps: 0x041040, length: 1_500_000,
+                ..FormatDef::TRAKTOR
+            },
+            TimecodeFormat::TraktorB => FormatDef {
+                carrier_hz: 2000.0, bits: 23, seed: 0x32066c, taps: 0x041040, length: 2_110_000,
+                ..FormatDef::TRAKTOR
+            },
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|f| *f == self).unwrap_or(0)
+    }
+}
+
+impl std::fmt::Display for TimecodeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Parity of the tapped bits
+fn parity(code: u32, taps: u32) -> u32 {
+    (code & taps).count_ones() & 1
+}
+
+impl FormatDef {
+    /// Channel layout of Serato records and CDs: bits on the right
+    /// channel's positive peaks, right channel leading
+    const SERATO: FormatDef = FormatDef {
+        carrier_hz: 0.0, bits: 0, seed: 0, taps: 0, length: 0,
+        primary_left: false,
+        negative_polarity: false,
+        phase_270: false,
+    };
+
+    /// Channel layout of Traktor Scratch records: bits on the left
+    /// channel's negative peaks, left channel lagging
+    const TRAKTOR: FormatDef = FormatDef {
+        primary_left: true,
+        negative_polarity: true,
+        phase_270: true,
+        ..FormatDef::SERATO
+    };
+
+    fn mask(&self) -> u32 {
+        (1 << self.bits) - 1
+    }
+
+    /// Register value one cycle further into the record
+    ///
+    /// New bits enter at the MSB, so the register holds the last `bits` bits
+    /// with the most recent one on top.
+    pub fn forward(&self, code: u32) -> u32 {
+        let bit = parity(code, self.taps | 1);
+        (code >> 1) | (bit << (self.bits - 1))
+    }
+
+    /// Register value one cycle back towards the start of the record
+    pub fn reverse(&self, code: u32) -> u32 {
+        let bit = parity(code, (self.taps >> 1) | (1 << (self.bits - 1)));
+        ((code << 1) & self.mask()) | bit
+    }
+
+    /// Bit carried by the cycle that moved the register to `code`
+    #[cfg(test)]
+    pub fn newest_bit(&self, code: u32) -> u32 {
+        code >> (self.bits - 1)
+    }
+}
+
+/// Register value → cycle index table for one format
+pub(crate) struct Lookup {
+    /// (register value, cycle index), sorted by value
+    entries: Vec<(u32, u32)>,
+}
+
+impl Lookup {
+    fn build(def: &FormatDef) -> Self {
+        let mut entries = Vec::with_capacity(def.length as usize);
+        let mut code = def.seed;
+        for cycle in 0..def.length {
+            entries.push((code, cycle));
+            code = def.forward(code);
+        }
+        entries.sort_unstable_by_key(|&(code, _)| code);
+        Self { entries }
+    }
+
+    /// Cycle index at which the register holds `code`
+    pub fn find(&self, code: u32) -> Option<u32> {
+        self.entries
+            .binary_search_by_key(&code, |&(c, _)| c)
+            .ok()
+            .map(|i| self.entries[i].1)
+    }
+}
+
+static LOOKUPS: [OnceLock<Lookup>; TimecodeFormat::ALL.len()] =
+    [OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new()];
+
+/// Position lookup table for a format, built on first use
+///
+/// Building takes a few hundred milliseconds and several megabytes, so
+/// this runs off the audio thread via [`prepare_format`](super::prepare_format).
+pub(crate) fn lookup(format: TimecodeFormat) -> &'static Lookup {
+    LOOKUPS[format.index()].get_or_init(|| Lookup::build(&format.def()))
+}
+
+/// Lookup table for a format if it has been built (RT-safe)
+pub(crate) fn prepared_lookup(format: TimecodeFormat) -> Option<&'static Lookup> {
+    LOOKUPS[format.index()].get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_and_reverse_are_inverse() {
+        for format in TimecodeFormat::ALL {
+            let def = format.def();
+            let mut code = def.seed;
+            for _ in 0..1000 {
+                let next = def.forward(code);
+                assert_eq!(def.reverse(next), code, "{:?}", format);
+                code = next;
+            }
+        }
+    }
+}
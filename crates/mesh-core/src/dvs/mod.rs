@@ -0,0 +1,94 @@
+//! DVS: timecode vinyl control
+//!
+//! A timecode record (or CD) on a real turntable plays a control signal
+//! instead of music. The audio backend feeds each deck's stereo input to a
+//! [`TimecodeDecoder`], which reads platter speed, direction and position
+//! from it, and the deck follows along through its scratch path:
+//!
+//! ```text
+//! turntable ──► input 1 L/R ──► TimecodeDecoder ──► TimecodeReading
+//!                                                    │ speed, position
+//!                                                    ▼
+//!                              Deck::follow_timecode (scratch / seek)
+//! ```
+//!
+//! Two modes, as on other DVS software:
+//!
+//! - **Relative** — only speed and direction are used. Needle drops don't
+//!   move the track, so cue points and loops work as usual.
+//! - **Absolute** — the position on the record maps to the position in the
+//!   track. Dropping the needle further in jumps there.
+//!
+//! Formats and their bit sequences are in `format`. Decoding a format's
+//! absolute positions needs a lookup table that is built once per process
+//! with [`prepare_format`] — do that off the audio thread. Decoders pick the
+//! table up as soon as it exists.
+//!
+//! # Module structure
+//! - `format`: Timecode formats and LFSR tables
+//! - `decoder`: Signal decoder
+
+mod decoder;
+mod format;
+
+use serde::{Deserialize, Serialize};
+
+pub use decoder::{TimecodeDecoder, TimecodeReading};
+pub use format::TimecodeFormat;
+
+/// How a deck follows its timecode input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DvsMode {
+    /// Internal playback, input ignored
+    #[default]
+    Off,
+    /// Speed and direction from the record
+    Relative,
+    /// Speed, direction and position from the record
+    Absolute,
+}
+
+impl DvsMode {
+    /// Next mode in the UI cycle (Off → Relative → Absolute → Off)
+    pub fn next(self) -> Self {
+        match self {
+            DvsMode::Off => DvsMode::Relative,
+            DvsMode::Relative => DvsMode::Absolute,
+            DvsMode::Absolute => DvsMode::Off,
+        }
+    }
+
+    /// Short label for deck buttons
+    pub fn label(self) -> &'static str {
+        match self {
+            DvsMode::Off => "INT",
+            DvsMode::Relative => "REL",
+            DvsMode::Absolute => "ABS",
+        }
+    }
+
+    pub(crate) fn to_u8(self) -> u8 {
+        match self {
+            DvsMode::Off => 0,
+            DvsMode::Relative => 1,
+            DvsMode::Absolute => 2,
+        }
+    }
+
+    pub(crate) fn from_u8(value: u8) -> Self {
+        match value {
+            1 => DvsMode::Relative,
+            2 => DvsMode::Absolute,
+            _ => DvsMode::Off,
+        }
+    }
+}
+
+/// Build the position lookup table for a format (blocking, allocates)
+///
+/// Cheap after the first call. Until it has run, decoders of the format
+/// only read speed and direction.
+pub fn prepare_format(format: TimecodeFormat) {
+    format::lookup(format);
+}
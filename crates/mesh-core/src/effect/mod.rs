@@ -189,6 +189,12 @@ pub trait Effect: Send {
     fn poll_restart(&mut self) -> Option<u32> {
         None
     }
+
+    /// Set the tempo for beat-synced effects
+    ///
+    /// Called with the master deck's BPM whenever it changes. Default
+    /// implementation ignores it (effect has no tempo-dependent behaviour).
+    fn set_tempo(&mut self, _bpm: f64) {}
 }
 
 /// Base implementation helper for effects
@@ -481,6 +481,9 @@ pub struct MultibandHost {
     /// Buffer size for new bands
     buffer_size: usize,
 
+    /// Tempo forwarded to beat-synced effects (0 = not set yet)
+    tempo: f64,
+
     // ─────────────────────────────────────────────────────────────────────
     // Dry/Wet Mix Controls
     // ─────────────────────────────────────────────────────────────────────
@@ -550,6 +553,7 @@ impl MultibandHost {
             any_soloed: false,
             cached_latency: 0,
             buffer_size,
+            tempo: 0.0,
             // Dry/wet controls - default to 1.0 (100% wet = normal processing)
             pre_fx_effect_dry_wet: Vec::new(),
             pre_fx_chain_dry_wet: 1.0,
@@ -737,7 +741,7 @@ impl MultibandHost {
     }
 
     /// Add an effect to the pre-fx chain
-    pub fn add_pre_fx(&mut self, effect: Box<dyn Effect>) -> MultibandResult<usize> {
+    pub fn add_pre_fx(&mut self, mut effect: Box<dyn Effect>) -> MultibandResult<usize> {
         if self.pre_fx.len() >= MAX_EFFECTS_PER_BAND {
             return Err(MultibandError::ConfigError(format!(
                 "Pre-FX chain already has maximum {} effects",
@@ -745,6 +749,9 @@ impl MultibandHost {
             )));
         }
 
+        if self.tempo > 0.0 {
+            effect.set_tempo(self.tempo);
+        }
         let effect_index = self.pre_fx.len();
         self.pre_fx.push(effect);
         self.pre_fx_effect_dry_wet.push(1.0); // Default: 100% wet
@@ -828,7 +835,7 @@ impl MultibandHost {
     }
 
     /// Add an effect to the post-fx chain
-    pub fn add_post_fx(&mut self, effect: Box<dyn Effect>) -> MultibandResult<usize> {
+    pub fn add_post_fx(&mut self, mut effect: Box<dyn Effect>) -> MultibandResult<usize> {
         if self.post_fx.len() >= MAX_EFFECTS_PER_BAND {
             return Err(MultibandError::ConfigError(format!(
                 "Post-FX chain already has maximum {} effects",
@@ -836,6 +843,9 @@ impl MultibandHost {
             )));
         }
 
+        if self.tempo > 0.0 {
+            effect.set_tempo(self.tempo);
+        }
         let effect_index = self.post_fx.len();
         self.post_fx.push(effect);
         self.post_fx_effect_dry_wet.push(1.0); // Default: 100% wet
@@ -902,7 +912,7 @@ impl MultibandHost {
     pub fn add_effect_to_band(
         &mut self,
         band_index: usize,
-        effect: Box<dyn Effect>,
+        mut effect: Box<dyn Effect>,
     ) -> MultibandResult<usize> {
         if band_index >= self.bands.len() {
             return Err(MultibandError::BandIndexOutOfBounds {
@@ -919,6 +929,9 @@ impl MultibandHost {
             )));
         }
 
+        if self.tempo > 0.0 {
+            effect.set_tempo(self.tempo);
+        }
         let effect_index = band.effects.len();
         band.effects.push(effect);
         band.effect_dry_wet.push(1.0); // Default: 100% wet
@@ -1722,6 +1735,21 @@ impl Effect for MultibandHost {
 
         self.global_dry_delay_line.clear();
     }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.tempo = bpm;
+        for effect in &mut self.pre_fx {
+            effect.set_tempo(bpm);
+        }
+        for band in &mut self.bands {
+            for effect in &mut band.effects {
+                effect.set_tempo(bpm);
+            }
+        }
+        for effect in &mut self.post_fx {
+            effect.set_tempo(bpm);
+        }
+    }
 }
 
 // Safety: MultibandHost is Send because all fields are Send
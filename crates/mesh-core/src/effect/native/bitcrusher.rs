@@ -0,0 +1,143 @@
+//! Bitcrusher effect
+//!
+//! Lo-fi degradation: reduces the bit depth (quantization noise) and the
+//! sample rate (sample-and-hold aliasing). Not tempo dependent.
+
+use crate::effect::{Effect, EffectBase, EffectInfo, ParamInfo, ParamValue};
+use crate::types::{StereoBuffer, StereoSample};
+
+/// Bit depth and sample rate reducer
+///
+/// Parameters:
+/// - Bits: Bit depth (2-16 bits)
+/// - Downsample: Sample rate divisor (1-32×)
+/// - Mix: Dry/wet balance
+///
+/// This effect has zero latency.
+pub struct BitcrusherEffect {
+    base: EffectBase,
+    /// Sample being held
+    held: StereoSample,
+    /// Samples left until the next capture
+    hold_counter: f32,
+}
+
+impl BitcrusherEffect {
+    /// Create a new bitcrusher effect (8 bits, 4× downsample)
+    pub fn new() -> Self {
+        let info = EffectInfo::new("Bitcrusher", "Distortion")
+            .with_param(
+                ParamInfo::new("Bits", 6.0 / 14.0) // 8 bits
+                    .with_range(2.0, 16.0)
+                    .with_unit("bits"),
+            )
+            .with_param(
+                ParamInfo::new("Downsample", 3.0 / 31.0) // 4×
+                    .with_range(1.0, 32.0)
+                    .with_unit("×"),
+            )
+            .with_param(ParamInfo::new("Mix", 1.0).with_range(0.0, 1.0));
+
+        Self {
+            base: EffectBase::new(info),
+            held: StereoSample::default(),
+            hold_counter: 0.0,
+        }
+    }
+}
+
+impl Default for BitcrusherEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Effect for BitcrusherEffect {
+    fn process(&mut self, buffer: &mut StereoBuffer) {
+        if self.base.is_bypassed() {
+            return;
+        }
+
+        let steps = 2.0_f32.powf(self.base.param_actual(0) - 1.0);
+        let factor = self.base.param_actual(1);
+        let mix = self.base.param_actual(2);
+        let dry = 1.0 - mix;
+        let quantize = |x: f32| (x * steps).round() / steps;
+
+        for sample in buffer.iter_mut() {
+            if self.hold_counter <= 0.0 {
+                self.held = StereoSample::new(quantize(sample.left), quantize(sample.right));
+                self.hold_counter += factor;
+            }
+            self.hold_counter -= 1.0;
+
+            sample.left = sample.left * dry + self.held.left * mix;
+            sample.right = sample.right * dry + self.held.right * mix;
+        }
+    }
+
+    fn latency_samples(&self) -> u32 {
+        0
+    }
+
+    fn info(&self) -> &EffectInfo {
+        self.base.info()
+    }
+
+    fn get_params(&self) -> &[ParamValue] {
+        self.base.get_params()
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) {
+        self.base.set_param(index, value);
+    }
+
+    fn set_bypass(&mut self, bypass: bool) {
+        self.base.set_bypass(bypass);
+    }
+
+    fn is_bypassed(&self) -> bool {
+        self.base.is_bypassed()
+    }
+
+    fn reset(&mut self) {
+        self.held = StereoSample::default();
+        self.hold_counter = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcrusher_quantizes() {
+        let mut effect = BitcrusherEffect::new();
+        effect.set_param(0, 0.0); // 2 bits: levels at multiples of 0.5
+        effect.set_param(1, 0.0); // No downsampling
+
+        let mut buffer = StereoBuffer::silence(2);
+        buffer.as_mut_slice()[0] = StereoSample::new(0.3, 0.2);
+        buffer.as_mut_slice()[1] = StereoSample::new(-0.8, 0.9);
+        effect.process(&mut buffer);
+
+        assert_eq!(buffer[0], StereoSample::new(0.5, 0.0));
+        assert_eq!(buffer[1], StereoSample::new(-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_bitcrusher_holds_samples() {
+        let mut effect = BitcrusherEffect::new();
+        effect.set_param(0, 1.0); // 16 bits
+        effect.set_param(1, 1.0); // 32× downsample
+
+        let mut buffer = StereoBuffer::silence(64);
+        for (i, s) in buffer.iter_mut().enumerate() {
+            *s = StereoSample::new(i as f32 / 64.0, 0.0);
+        }
+        effect.process(&mut buffer);
+
+        assert!(buffer.iter().take(32).all(|s| s.left == buffer[0].left));
+        assert!((buffer[32].left - 0.5).abs() < 1e-3);
+    }
+}
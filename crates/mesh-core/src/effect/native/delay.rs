@@ -230,6 +230,10 @@ impl Effect for DelayEffect {
     fn reset(&mut self) {
         self.delay_line.reset();
     }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.set_bpm(bpm);
+    }
 }
 
 /// Tempo-synced delay times (in beats)
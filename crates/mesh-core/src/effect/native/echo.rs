@@ -0,0 +1,206 @@
+//! Beat Echo effect
+//!
+//! A tempo-synced echo for performance:
+//! - Echo time in beats, stepping through musical divisions
+//! - Feedback with a lowpass in the loop, so repeats get darker
+//! - Dry/wet mix
+//!
+//! Unlike the [`DelayEffect`](super::DelayEffect), the time is always
+//! locked to the tempo. When the time or tempo changes, the delay glides
+//! to the new length (a short tape-style bend) instead of clicking.
+
+use std::f32::consts::TAU;
+
+use super::delay::TEMPO_SYNC_VALUES;
+use super::sync::{beats_to_samples, clamp_bpm, division_normalized, sync_division, DEFAULT_SYNC_BPM};
+use crate::effect::{Effect, EffectBase, EffectInfo, ParamInfo, ParamValue};
+use crate::types::{StereoBuffer, SAMPLE_RATE};
+
+/// Longest echo: 2 beats at 30 BPM
+const MAX_ECHO_SAMPLES: usize = SAMPLE_RATE as usize * 4;
+/// Per-sample glide toward a new echo time
+const TIME_GLIDE: f32 = 0.0005;
+
+/// Tempo-synced echo with filtered feedback
+///
+/// Parameters:
+/// - Time: Echo time, 1/32 to 1/2 note (0.125-2 beats)
+/// - Feedback: Amount of each echo fed back (0-95%)
+/// - Tone: Lowpass cutoff on the echoes (500 Hz - 16 kHz)
+/// - Mix: Dry/wet balance
+///
+/// This effect has zero processing latency.
+pub struct EchoEffect {
+    base: EffectBase,
+    /// Left channel delay line
+    buffer_l: Vec<f32>,
+    /// Right channel delay line
+    buffer_r: Vec<f32>,
+    /// Write position in the delay lines
+    write_pos: usize,
+    /// Current delay in samples (glides toward the synced time)
+    delay: f32,
+    /// Lowpass state per channel
+    tone_l: f32,
+    tone_r: f32,
+    /// Current tempo
+    bpm: f64,
+}
+
+impl EchoEffect {
+    /// Create a new echo effect (1/4 note at 120 BPM until a tempo arrives)
+    pub fn new() -> Self {
+        let info = EffectInfo::new("Beat Echo", "Delay")
+            .with_param(
+                ParamInfo::new("Time", division_normalized(5, &TEMPO_SYNC_VALUES)) // 1/4
+                    .with_range(0.125, 2.0)
+                    .with_unit("beats"),
+            )
+            .with_param(ParamInfo::new("Feedback", 0.5).with_range(0.0, 0.95))
+            .with_param(
+                ParamInfo::new("Tone", 0.7)
+                    .with_range(500.0, 16000.0)
+                    .with_unit("Hz"),
+            )
+            .with_param(ParamInfo::new("Mix", 0.4).with_range(0.0, 1.0));
+
+        let mut effect = Self {
+            base: EffectBase::new(info),
+            buffer_l: vec![0.0; MAX_ECHO_SAMPLES],
+            buffer_r: vec![0.0; MAX_ECHO_SAMPLES],
+            write_pos: 0,
+            delay: 0.0,
+            tone_l: 0.0,
+            tone_r: 0.0,
+            bpm: DEFAULT_SYNC_BPM,
+        };
+        effect.delay = effect.target_delay();
+        effect
+    }
+
+    /// Echo time in samples at the current tempo
+    fn target_delay(&self) -> f32 {
+        let (beats, _) = sync_division(self.base.param_normalized(0), &TEMPO_SYNC_VALUES);
+        beats_to_samples(beats, self.bpm).clamp(1.0, (MAX_ECHO_SAMPLES - 2) as f32)
+    }
+
+    /// Read a delay line `delay` samples behind the write position
+    #[inline]
+    fn read(buffer: &[f32], write_pos: usize, delay: f32) -> f32 {
+        let pos = write_pos as f32 - delay;
+        let pos = if pos < 0.0 { pos + MAX_ECHO_SAMPLES as f32 } else { pos };
+        let i = pos as usize % MAX_ECHO_SAMPLES;
+        let next = (i + 1) % MAX_ECHO_SAMPLES;
+        let frac = pos - pos.floor();
+        buffer[i] + (buffer[next] - buffer[i]) * frac
+    }
+}
+
+impl Default for EchoEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Effect for EchoEffect {
+    fn process(&mut self, buffer: &mut StereoBuffer) {
+        if self.base.is_bypassed() {
+            return;
+        }
+
+        let target = self.target_delay();
+        let feedback = self.base.param_actual(1);
+        let tone_hz = self.base.param_actual(2);
+        let mix = self.base.param_actual(3);
+        let dry = 1.0 - mix;
+        let tone_coef = 1.0 - (-TAU * tone_hz / SAMPLE_RATE as f32).exp();
+
+        for sample in buffer.iter_mut() {
+            self.delay += (target - self.delay) * TIME_GLIDE;
+
+            let delayed_l = Self::read(&self.buffer_l, self.write_pos, self.delay);
+            let delayed_r = Self::read(&self.buffer_r, self.write_pos, self.delay);
+            self.tone_l += (delayed_l - self.tone_l) * tone_coef;
+            self.tone_r += (delayed_r - self.tone_r) * tone_coef;
+
+            self.buffer_l[self.write_pos] = sample.left + self.tone_l * feedback;
+            self.buffer_r[self.write_pos] = sample.right + self.tone_r * feedback;
+            self.write_pos = (self.write_pos + 1) % MAX_ECHO_SAMPLES;
+
+            sample.left = sample.left * dry + self.tone_l * mix;
+            sample.right = sample.right * dry + self.tone_r * mix;
+        }
+    }
+
+    fn latency_samples(&self) -> u32 {
+        0
+    }
+
+    fn info(&self) -> &EffectInfo {
+        self.base.info()
+    }
+
+    fn get_params(&self) -> &[ParamValue] {
+        self.base.get_params()
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) {
+        self.base.set_param(index, value);
+    }
+
+    fn set_bypass(&mut self, bypass: bool) {
+        self.base.set_bypass(bypass);
+    }
+
+    fn is_bypassed(&self) -> bool {
+        self.base.is_bypassed()
+    }
+
+    fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_pos = 0;
+        self.tone_l = 0.0;
+        self.tone_r = 0.0;
+        self.delay = self.target_delay();
+    }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.bpm = clamp_bpm(bpm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StereoSample;
+
+    #[test]
+    fn test_echo_creation() {
+        let effect = EchoEffect::new();
+        assert_eq!(effect.info().name, "Beat Echo");
+        assert_eq!(effect.info().param_count(), 4);
+    }
+
+    #[test]
+    fn test_echo_lands_on_the_beat() {
+        let mut effect = EchoEffect::new();
+        effect.set_tempo(120.0);
+        effect.set_param(1, 0.0); // No feedback
+        effect.set_param(2, 1.0); // Tone fully open
+        effect.set_param(3, 1.0); // Full wet
+        effect.reset();
+
+        let mut buffer = StereoBuffer::silence(30000);
+        buffer.as_mut_slice()[0] = StereoSample::new(1.0, 1.0);
+        effect.process(&mut buffer);
+
+        // 1/4 note at 120 BPM = 0.5 s
+        let beat = (SAMPLE_RATE / 2) as usize;
+        let peak = (0..buffer.len())
+            .max_by(|&a, &b| buffer[a].left.abs().total_cmp(&buffer[b].left.abs()))
+            .unwrap();
+        assert!((peak as i64 - beat as i64).abs() <= 2, "echo at {} (expected {})", peak, beat);
+        assert!(buffer[beat / 2].left.abs() < 1e-6);
+    }
+}
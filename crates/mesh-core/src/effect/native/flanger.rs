@@ -0,0 +1,189 @@
+//! Flanger effect
+//!
+//! A short modulated delay mixed back with the dry signal. The sweep rate
+//! is locked to the tempo: one LFO cycle lasts a musical division (1/8 note
+//! to 8 bars), with the right channel a quarter cycle ahead for width. The
+//! LFO phase runs freely and is not lined up with the beat.
+
+use std::f64::consts::TAU;
+
+use super::sync::{beats_to_samples, clamp_bpm, division_normalized, sync_division, DEFAULT_SYNC_BPM, LFO_DIVISIONS};
+use crate::effect::{Effect, EffectBase, EffectInfo, ParamInfo, ParamValue};
+use crate::types::{StereoBuffer, SAMPLE_RATE};
+
+/// Delay line length (power of two, ~21 ms)
+const BUFFER_SIZE: usize = 1024;
+/// Shortest delay in the sweep
+const MIN_DELAY_MS: f32 = 0.5;
+/// Sweep width at full depth
+const MAX_SWEEP_MS: f32 = 6.0;
+
+/// Tempo-synced stereo flanger
+///
+/// Parameters:
+/// - Rate: Sweep period, 1/8 note to 8 bars (0.5-32 beats)
+/// - Depth: Sweep width (0-100%)
+/// - Feedback: Resonance of the comb (0-90%)
+/// - Mix: Dry/wet balance
+///
+/// This effect has zero processing latency.
+pub struct FlangerEffect {
+    base: EffectBase,
+    /// Left channel delay line
+    buffer_l: Vec<f32>,
+    /// Right channel delay line
+    buffer_r: Vec<f32>,
+    /// Write position in the delay lines
+    write_pos: usize,
+    /// LFO phase (0.0 - 1.0)
+    phase: f64,
+    /// Current tempo
+    bpm: f64,
+}
+
+impl FlangerEffect {
+    /// Create a new flanger effect (1 bar sweep)
+    pub fn new() -> Self {
+        let info = EffectInfo::new("Flanger", "Modulation")
+            .with_param(
+                ParamInfo::new("Rate", division_normalized(3, &LFO_DIVISIONS)) // 1 bar
+                    .with_range(0.5, 32.0)
+                    .with_unit("beats"),
+            )
+            .with_param(ParamInfo::new("Depth", 0.8).with_range(0.0, 1.0))
+            .with_param(ParamInfo::new("Feedback", 0.5).with_range(0.0, 0.9))
+            .with_param(ParamInfo::new("Mix", 0.5).with_range(0.0, 1.0));
+
+        Self {
+            base: EffectBase::new(info),
+            buffer_l: vec![0.0; BUFFER_SIZE],
+            buffer_r: vec![0.0; BUFFER_SIZE],
+            write_pos: 0,
+            phase: 0.0,
+            bpm: DEFAULT_SYNC_BPM,
+        }
+    }
+
+    /// Read a delay line `delay` samples behind the write position
+    #[inline]
+    fn read(buffer: &[f32], write_pos: usize, delay: f32) -> f32 {
+        let pos = write_pos as f32 - delay + BUFFER_SIZE as f32;
+        let i = pos as usize & (BUFFER_SIZE - 1);
+        let next = (i + 1) & (BUFFER_SIZE - 1);
+        let frac = pos - pos.floor();
+        buffer[i] + (buffer[next] - buffer[i]) * frac
+    }
+}
+
+impl Default for FlangerEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Effect for FlangerEffect {
+    fn process(&mut self, buffer: &mut StereoBuffer) {
+        if self.base.is_bypassed() {
+            return;
+        }
+
+        let (beats, _) = sync_division(self.base.param_normalized(0), &LFO_DIVISIONS);
+        let phase_step = 1.0 / beats_to_samples(beats, self.bpm).max(1.0) as f64;
+        let depth = self.base.param_actual(1);
+        let feedback = self.base.param_actual(2);
+        let mix = self.base.param_actual(3);
+        let dry = 1.0 - mix;
+        let ms_to_samples = SAMPLE_RATE as f32 / 1000.0;
+        let min_delay = MIN_DELAY_MS * ms_to_samples;
+        let sweep = MAX_SWEEP_MS * ms_to_samples * depth;
+
+        for sample in buffer.iter_mut() {
+            // Raised cosine LFO (0..1), right channel a quarter cycle ahead
+            let lfo_l = (0.5 - 0.5 * (TAU * self.phase).cos()) as f32;
+            let lfo_r = (0.5 - 0.5 * (TAU * (self.phase + 0.25)).cos()) as f32;
+
+            let delayed_l = Self::read(&self.buffer_l, self.write_pos, min_delay + sweep * lfo_l);
+            let delayed_r = Self::read(&self.buffer_r, self.write_pos, min_delay + sweep * lfo_r);
+
+            self.buffer_l[self.write_pos] = sample.left + delayed_l * feedback;
+            self.buffer_r[self.write_pos] = sample.right + delayed_r * feedback;
+            self.write_pos = (self.write_pos + 1) & (BUFFER_SIZE - 1);
+
+            sample.left = sample.left * dry + delayed_l * mix;
+            sample.right = sample.right * dry + delayed_r * mix;
+
+            self.phase += phase_step;
+            if self.phase >= 1.0 {
+                self.phase -= 1.0;
+            }
+        }
+    }
+
+    fn latency_samples(&self) -> u32 {
+        0
+    }
+
+    fn info(&self) -> &EffectInfo {
+        self.base.info()
+    }
+
+    fn get_params(&self) -> &[ParamValue] {
+        self.base.get_params()
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) {
+        self.base.set_param(index, value);
+    }
+
+    fn set_bypass(&mut self, bypass: bool) {
+        self.base.set_bypass(bypass);
+    }
+
+    fn is_bypassed(&self) -> bool {
+        self.base.is_bypassed()
+    }
+
+    fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_pos = 0;
+        self.phase = 0.0;
+    }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.bpm = clamp_bpm(bpm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StereoSample;
+
+    #[test]
+    fn test_flanger_dry_passthrough() {
+        let mut effect = FlangerEffect::new();
+        effect.set_param(3, 0.0); // Full dry
+
+        let mut buffer = StereoBuffer::silence(256);
+        for (i, s) in buffer.iter_mut().enumerate() {
+            *s = StereoSample::new((i as f32 * 0.1).sin(), 0.5);
+        }
+        let input: Vec<_> = buffer.iter().copied().collect();
+        effect.process(&mut buffer);
+
+        for (out, inp) in buffer.iter().zip(&input) {
+            assert!((out.left - inp.left).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_flanger_sweep_follows_tempo() {
+        // At 120 BPM a 1 bar sweep is 2 s; after 1 s the LFO is half way
+        let mut effect = FlangerEffect::new();
+        effect.set_tempo(120.0);
+        let mut buffer = StereoBuffer::silence(SAMPLE_RATE as usize);
+        effect.process(&mut buffer);
+        assert!((effect.phase - 0.5).abs() < 1e-3, "phase {}", effect.phase);
+    }
+}
@@ -0,0 +1,163 @@
+//! Gater effect
+//!
+//! Chops the signal at the music's tempo: each gate cycle (1/32 note to
+//! 1 bar) opens for the first part of the cycle and closes for the rest.
+//! A short smoothing time keeps the edges from clicking. The cycle length
+//! follows the tempo, but the gate free-runs from when the effect starts
+//! rather than opening on the beat.
+
+use super::sync::{beats_to_samples, clamp_bpm, division_normalized, sync_division, DEFAULT_SYNC_BPM, GATE_DIVISIONS};
+use crate::effect::{Effect, EffectBase, EffectInfo, ParamInfo, ParamValue};
+use crate::types::{StereoBuffer, SAMPLE_RATE};
+
+/// Tempo-synced rhythmic gate
+///
+/// Parameters:
+/// - Rate: Gate cycle, 1/32 note to 1 bar (0.125-4 beats)
+/// - Length: Open part of each cycle (10-90%)
+/// - Depth: How far the gate closes (0% = no effect, 100% = silence)
+/// - Smooth: Edge smoothing time (0.5-20 ms)
+///
+/// This effect has zero latency.
+pub struct GaterEffect {
+    base: EffectBase,
+    /// Position in the gate cycle (0.0 - 1.0)
+    phase: f64,
+    /// Current (smoothed) gain
+    gain: f32,
+    /// Current tempo
+    bpm: f64,
+}
+
+impl GaterEffect {
+    /// Create a new gater effect (1/16 note gate)
+    pub fn new() -> Self {
+        let info = EffectInfo::new("Gater", "Modulation")
+            .with_param(
+                ParamInfo::new("Rate", division_normalized(1, &GATE_DIVISIONS)) // 1/16
+                    .with_range(0.125, 4.0)
+                    .with_unit("beats"),
+            )
+            .with_param(
+                ParamInfo::new("Length", 0.5)
+                    .with_range(10.0, 90.0)
+                    .with_unit("%"),
+            )
+            .with_param(ParamInfo::new("Depth", 1.0).with_range(0.0, 1.0))
+            .with_param(
+                ParamInfo::new("Smooth", 0.2)
+                    .with_range(0.5, 20.0)
+                    .with_unit("ms"),
+            );
+
+        Self {
+            base: EffectBase::new(info),
+            phase: 0.0,
+            gain: 1.0,
+            bpm: DEFAULT_SYNC_BPM,
+        }
+    }
+}
+
+impl Default for GaterEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Effect for GaterEffect {
+    fn process(&mut self, buffer: &mut StereoBuffer) {
+        if self.base.is_bypassed() {
+            return;
+        }
+
+        let (beats, _) = sync_division(self.base.param_normalized(0), &GATE_DIVISIONS);
+        let phase_step = 1.0 / beats_to_samples(beats, self.bpm).max(1.0) as f64;
+        let open_until = (self.base.param_actual(1) / 100.0) as f64;
+        let closed_gain = 1.0 - self.base.param_actual(2);
+        let smooth_samples = self.base.param_actual(3) * SAMPLE_RATE as f32 / 1000.0;
+        let smooth_coef = 1.0 - (-1.0 / smooth_samples).exp();
+
+        for sample in buffer.iter_mut() {
+            let target = if self.phase < open_until { 1.0 } else { closed_gain };
+            self.gain += (target - self.gain) * smooth_coef;
+            sample.left *= self.gain;
+            sample.right *= self.gain;
+
+            self.phase += phase_step;
+            if self.phase >= 1.0 {
+                self.phase -= 1.0;
+            }
+        }
+    }
+
+    fn latency_samples(&self) -> u32 {
+        0
+    }
+
+    fn info(&self) -> &EffectInfo {
+        self.base.info()
+    }
+
+    fn get_params(&self) -> &[ParamValue] {
+        self.base.get_params()
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) {
+        self.base.set_param(index, value);
+    }
+
+    fn set_bypass(&mut self, bypass: bool) {
+        self.base.set_bypass(bypass);
+    }
+
+    fn is_bypassed(&self) -> bool {
+        self.base.is_bypassed()
+    }
+
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.gain = 1.0;
+    }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.bpm = clamp_bpm(bpm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StereoSample;
+
+    #[test]
+    fn test_gater_cycle_follows_tempo() {
+        let mut effect = GaterEffect::new();
+        effect.set_tempo(120.0);
+        effect.set_param(0, division_normalized(3, &GATE_DIVISIONS)); // 1/4 = 24000 samples
+
+        let mut buffer = StereoBuffer::silence(24000);
+        for s in buffer.iter_mut() {
+            *s = StereoSample::new(1.0, 1.0);
+        }
+        effect.process(&mut buffer);
+
+        // The cycle starts with the first buffer: open for the first half of
+        // the beat's length, closed for the second
+        assert!((buffer[6000].left - 1.0).abs() < 1e-3);
+        assert!(buffer[18000].left.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_gater_zero_depth_is_transparent() {
+        let mut effect = GaterEffect::new();
+        effect.set_param(2, 0.0);
+
+        let mut buffer = StereoBuffer::silence(48000);
+        for s in buffer.iter_mut() {
+            *s = StereoSample::new(0.7, 0.7);
+        }
+        effect.process(&mut buffer);
+        assert!(buffer.iter().all(|s| (s.left - 0.7).abs() < 1e-5));
+    }
+}
@@ -1,18 +1,55 @@
 //! Native Rust effects
 //!
 //! These effects are implemented directly in Rust for minimal latency
-//! and maximum performance.
+//! and maximum performance. They need no plugin host, so they are also
+//! available on builds without the `pd-effects` feature. Echo, flanger,
+//! phaser and gater lock their time/rate to the tempo (see [`sync`]).
 
+mod bitcrusher;
 mod crossover;
 mod delay;
+mod echo;
 mod filter;
+mod flanger;
 mod gain;
+mod gater;
+mod phaser;
 mod reverb;
 pub mod svf;
+pub mod sync;
 
+pub use bitcrusher::BitcrusherEffect;
 pub use crossover::{LinkwitzRileyCrossover, MAX_BANDS};
 pub use delay::{beats_to_ms, DelayEffect, TEMPO_SYNC_VALUES};
+pub use echo::EchoEffect;
 pub use filter::DjFilterEffect;
+pub use flanger::FlangerEffect;
 pub use gain::GainEffect;
+pub use gater::GaterEffect;
+pub use phaser::PhaserEffect;
 pub use reverb::ReverbEffect;
 pub use svf::SvfFilter;
+
+use super::Effect;
+
+/// Identifiers of the native effects, for configs and pickers
+pub const NATIVE_EFFECT_IDS: [&str; 9] = [
+    "filter", "delay", "reverb", "gain", "echo", "flanger", "phaser", "gater", "bitcrusher",
+];
+
+/// Create a native effect by identifier (see [`NATIVE_EFFECT_IDS`])
+pub fn create_native_effect(id: &str) -> Option<Box<dyn Effect>> {
+    let effect: Box<dyn Effect> = match id {
+        "filter" => Box::new(DjFilterEffect::new()),
+        "delay" => Box::new(DelayEffect::new()),
+        "reverb" => Box::new(ReverbEffect::new()),
+        "gain" => Box::new(GainEffect::new()),
+        "echo" => Box::new(EchoEffect::new()),
+        "flanger" => Box::new(FlangerEffect::new()),
+        "phaser" => Box::new(PhaserEffect::new()),
+        "gater" => Box::new(GaterEffect::new()),
+        "bitcrusher" => Box::new(BitcrusherEffect::new()),
+        _ => return None,
+    };
+    Some(effect)
+}
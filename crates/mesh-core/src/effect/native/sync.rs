@@ -0,0 +1,82 @@
+//! Tempo sync helpers shared by the beat-synced native effects
+//!
+//! Synced time and rate knobs step through a table of musical divisions
+//! (in beats) rather than sweeping continuously: the knob range is split
+//! evenly between the table entries, so every division gets the same
+//! amount of travel. The engine forwards the tempo through
+//! [`Effect::set_tempo`](crate::effect::Effect::set_tempo).
+//!
+//! Only the rate is synced. Effects get no beat position, so an LFO or gate
+//! cycle starts wherever the effect happens to start processing and is not
+//! aligned to the deck's beat grid.
+
+use crate::types::SAMPLE_RATE;
+
+/// Tempo assumed until the engine forwards one
+pub const DEFAULT_SYNC_BPM: f64 = 120.0;
+
+/// LFO periods for modulation effects (flanger, phaser), in beats
+pub const LFO_DIVISIONS: [(f32, &str); 7] = [
+    (0.5, "1/8"),
+    (1.0, "1/4"),
+    (2.0, "1/2"),
+    (4.0, "1 bar"),
+    (8.0, "2 bars"),
+    (16.0, "4 bars"),
+    (32.0, "8 bars"),
+];
+
+/// Gate cycle lengths for the gater, in beats
+pub const GATE_DIVISIONS: [(f32, &str); 6] = [
+    (0.125, "1/32"),
+    (0.25, "1/16"),
+    (0.5, "1/8"),
+    (1.0, "1/4"),
+    (2.0, "1/2"),
+    (4.0, "1 bar"),
+];
+
+/// Division selected by a normalized knob value (0.0 - 1.0)
+pub fn sync_division(normalized: f32, divisions: &[(f32, &'static str)]) -> (f32, &'static str) {
+    let last = divisions.len().saturating_sub(1);
+    let index = (normalized.clamp(0.0, 1.0) * last as f32).round() as usize;
+    divisions[index.min(last)]
+}
+
+/// Normalized knob value that selects a division (for parameter defaults)
+pub fn division_normalized(index: usize, divisions: &[(f32, &'static str)]) -> f32 {
+    let last = divisions.len().saturating_sub(1).max(1);
+    index.min(last) as f32 / last as f32
+}
+
+/// Length of a number of beats in samples at the given tempo
+pub fn beats_to_samples(beats: f32, bpm: f64) -> f32 {
+    (beats as f64 * 60.0 / bpm * SAMPLE_RATE as f64) as f32
+}
+
+/// Keep a forwarded tempo in a sane range (same limits as the stereo delay)
+pub fn clamp_bpm(bpm: f64) -> f64 {
+    bpm.clamp(30.0, 300.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_division_steps_evenly() {
+        assert_eq!(sync_division(0.0, &LFO_DIVISIONS).1, "1/8");
+        assert_eq!(sync_division(0.5, &LFO_DIVISIONS).1, "1 bar");
+        assert_eq!(sync_division(1.0, &LFO_DIVISIONS).1, "8 bars");
+        for (i, division) in GATE_DIVISIONS.iter().enumerate() {
+            let n = division_normalized(i, &GATE_DIVISIONS);
+            assert_eq!(sync_division(n, &GATE_DIVISIONS), *division);
+        }
+    }
+
+    #[test]
+    fn test_beats_to_samples() {
+        // One beat at 120 BPM = 0.5 s
+        assert!((beats_to_samples(1.0, 120.0) - SAMPLE_RATE as f32 / 2.0).abs() < 0.5);
+    }
+}
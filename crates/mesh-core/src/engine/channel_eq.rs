@@ -0,0 +1,471 @@
+//! Channel EQ - Selectable EQ models for the mixer channel strips
+//!
+//! - **Classic**: low shelf / mid peak / high shelf biquads (DJM-style)
+//! - **Isolator**: 3-band Linkwitz-Riley split with a gain per band, so each
+//!   band can be killed completely
+//! - **4-band**: the isolator with its mid range split in two (Xone-style)
+//!
+//! Switching models crossfades the outgoing and incoming EQ over a few
+//! milliseconds, so a deck can change model mid-set without a click.
+
+use crate::config::{EqCrossovers, EqModel};
+use crate::effect::native::LinkwitzRileyCrossover;
+use crate::types::{StereoSample, SAMPLE_RATE};
+
+/// Classic EQ frequency centers
+const EQ_LO_FREQ: f32 = 100.0;   // Low shelf at 100 Hz
+const EQ_MID_FREQ: f32 = 1000.0; // Mid peak at 1 kHz
+const EQ_HI_FREQ: f32 = 10000.0; // High shelf at 10 kHz
+const EQ_MID_Q: f32 = 0.7;       // Q for mid band
+
+/// Crossfade length when switching models (20 ms)
+const MODEL_FADE_SAMPLES: u32 = SAMPLE_RATE / 50;
+/// Isolator band gain smoothing time constant (seconds)
+const GAIN_SMOOTHING_SECS: f32 = 0.005;
+
+/// Convert EQ knob position (0-1) to dB gain
+/// 0.0 = -inf (kill), 0.5 = 0dB, 1.0 = +6dB
+fn eq_to_db(value: f32) -> f32 {
+    if value < 0.01 {
+        -60.0  // Near-kill
+    } else if value < 0.5 {
+        // 0.01 to 0.5 -> -60dB to 0dB (logarithmic)
+        let t = (value - 0.01) / 0.49;
+        -60.0 * (1.0 - t)
+    } else {
+        // 0.5 to 1.0 -> 0dB to +6dB (linear)
+        (value - 0.5) * 12.0
+    }
+}
+
+/// Convert EQ knob position (0-1) to an isolator band gain (linear)
+///
+/// Same curve as the classic EQ, except the kill position is true silence.
+fn isolator_gain(value: f32) -> f32 {
+    if value < 0.01 {
+        0.0
+    } else {
+        10.0_f32.powf(eq_to_db(value) / 20.0)
+    }
+}
+
+/// Biquad filter state for EQ bands
+#[derive(Debug, Clone, Default)]
+struct BiquadState {
+    x1_l: f32, x2_l: f32, y1_l: f32, y2_l: f32,
+    x1_r: f32, x2_r: f32, y1_r: f32, y2_r: f32,
+}
+
+impl BiquadState {
+    fn process(&mut self, input_l: f32, input_r: f32, coeffs: &BiquadCoeffs) -> (f32, f32) {
+        // Left channel
+        let out_l = coeffs.b0 * input_l + coeffs.b1 * self.x1_l + coeffs.b2 * self.x2_l
+                  - coeffs.a1 * self.y1_l - coeffs.a2 * self.y2_l;
+        self.x2_l = self.x1_l;
+        self.x1_l = input_l;
+        self.y2_l = self.y1_l;
+        self.y1_l = out_l;
+
+        // Right channel
+        let out_r = coeffs.b0 * input_r + coeffs.b1 * self.x1_r + coeffs.b2 * self.x2_r
+                  - coeffs.a1 * self.y1_r - coeffs.a2 * self.y2_r;
+        self.x2_r = self.x1_r;
+        self.x1_r = input_r;
+        self.y2_r = self.y1_r;
+        self.y1_r = out_r;
+
+        (out_l, out_r)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Biquad filter coefficients
+#[derive(Debug, Clone)]
+struct BiquadCoeffs {
+    b0: f32, b1: f32, b2: f32,
+    a1: f32, a2: f32,
+}
+
+impl BiquadCoeffs {
+    /// Create low shelf filter coefficients
+    /// gain_db: boost/cut in dB, freq: shelf frequency
+    fn low_shelf(freq: f32, gain_db: f32, sample_rate: f32) -> Self {
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let sin_w0 = w0.sin();
+        let alpha = sin_w0 / 2.0 * ((a + 1.0/a) * (1.0/0.9 - 1.0) + 2.0).sqrt();
+
+        let a0 = (a + 1.0) + (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha;
+        Self {
+            b0: (a * ((a + 1.0) - (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha)) / a0,
+            b1: (2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0,
+            b2: (a * ((a + 1.0) - (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha)) / a0,
+            a1: (-2.0 * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0,
+            a2: ((a + 1.0) + (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha) / a0,
+        }
+    }
+
+    /// Create peaking EQ filter coefficients
+    fn peaking(freq: f32, gain_db: f32, q: f32, sample_rate: f32) -> Self {
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let sin_w0 = w0.sin();
+        let alpha = sin_w0 / (2.0 * q);
+
+        let a0 = 1.0 + alpha / a;
+        Self {
+            b0: (1.0 + alpha * a) / a0,
+            b1: (-2.0 * cos_w0) / a0,
+            b2: (1.0 - alpha * a) / a0,
+            a1: (-2.0 * cos_w0) / a0,
+            a2: (1.0 - alpha / a) / a0,
+        }
+    }
+
+    /// Create high shelf filter coefficients
+    fn high_shelf(freq: f32, gain_db: f32, sample_rate: f32) -> Self {
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let sin_w0 = w0.sin();
+        let alpha = sin_w0 / 2.0 * ((a + 1.0/a) * (1.0/0.9 - 1.0) + 2.0).sqrt();
+
+        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha;
+        Self {
+            b0: (a * ((a + 1.0) + (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha)) / a0,
+            b1: (-2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0,
+            b2: (a * ((a + 1.0) + (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha)) / a0,
+            a1: (2.0 * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0,
+            a2: ((a + 1.0) - (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha) / a0,
+        }
+    }
+
+    /// Passthrough (unity gain, no filtering)
+    fn passthrough() -> Self {
+        Self { b0: 1.0, b1: 0.0, b2: 0.0, a1: 0.0, a2: 0.0 }
+    }
+}
+
+/// Shelving/peaking 3-band EQ (the original mixer EQ)
+#[derive(Debug, Clone)]
+struct ClassicEq {
+    lo_state: BiquadState,
+    mid_state: BiquadState,
+    hi_state: BiquadState,
+    lo_coeffs: BiquadCoeffs,
+    mid_coeffs: BiquadCoeffs,
+    hi_coeffs: BiquadCoeffs,
+}
+
+impl ClassicEq {
+    fn new() -> Self {
+        Self {
+            lo_state: BiquadState::default(),
+            mid_state: BiquadState::default(),
+            hi_state: BiquadState::default(),
+            lo_coeffs: BiquadCoeffs::passthrough(),
+            mid_coeffs: BiquadCoeffs::passthrough(),
+            hi_coeffs: BiquadCoeffs::passthrough(),
+        }
+    }
+
+    /// Recalculate coefficients from the knob positions
+    fn set_knobs(&mut self, lo: f32, mid: f32, hi: f32) {
+        let sr = SAMPLE_RATE as f32;
+        let lo_db = eq_to_db(lo);
+        let mid_db = eq_to_db(mid);
+        let hi_db = eq_to_db(hi);
+
+        // Only update if significantly different from flat
+        if lo_db.abs() > 0.1 {
+            self.lo_coeffs = BiquadCoeffs::low_shelf(EQ_LO_FREQ, lo_db, sr);
+        } else {
+            self.lo_coeffs = BiquadCoeffs::passthrough();
+        }
+
+        if mid_db.abs() > 0.1 {
+            self.mid_coeffs = BiquadCoeffs::peaking(EQ_MID_FREQ, mid_db, EQ_MID_Q, sr);
+        } else {
+            self.mid_coeffs = BiquadCoeffs::passthrough();
+        }
+
+        if hi_db.abs() > 0.1 {
+            self.hi_coeffs = BiquadCoeffs::high_shelf(EQ_HI_FREQ, hi_db, sr);
+        } else {
+            self.hi_coeffs = BiquadCoeffs::passthrough();
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let (left, right) = self.lo_state.process(left, right, &self.lo_coeffs);
+        let (left, right) = self.mid_state.process(left, right, &self.mid_coeffs);
+        self.hi_state.process(left, right, &self.hi_coeffs)
+    }
+
+    fn reset(&mut self) {
+        self.lo_state.reset();
+        self.mid_state.reset();
+        self.hi_state.reset();
+    }
+}
+
+/// Linkwitz-Riley isolator: split into bands, scale each, sum
+#[derive(Clone)]
+struct IsolatorEq {
+    crossover: LinkwitzRileyCrossover,
+    /// Number of bands (3 or 4)
+    bands: usize,
+    /// Current (smoothed) band gains, low to high
+    gains: [f32; 4],
+    /// Gains set by the knobs
+    targets: [f32; 4],
+    /// Per-sample smoothing coefficient
+    smoothing: f32,
+}
+
+impl IsolatorEq {
+    fn new(bands: usize) -> Self {
+        let mut crossover = LinkwitzRileyCrossover::new();
+        crossover.set_band_count(bands);
+        Self {
+            crossover,
+            bands,
+            gains: [1.0; 4],
+            targets: [1.0; 4],
+            smoothing: 1.0 - (-1.0 / (GAIN_SMOOTHING_SECS * SAMPLE_RATE as f32)).exp(),
+        }
+    }
+
+    /// Set crossover points (low to high, `bands - 1` of them)
+    fn set_crossovers(&mut self, frequencies: &[f32]) {
+        for (i, &freq) in frequencies.iter().enumerate().take(self.bands - 1) {
+            self.crossover.set_frequency(i, freq);
+        }
+    }
+
+    /// Set band gains from knob positions (low to high)
+    fn set_knobs(&mut self, knobs: &[f32]) {
+        for (target, &knob) in self.targets.iter_mut().zip(knobs).take(self.bands) {
+            *target = isolator_gain(knob);
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let split = self.crossover.process(StereoSample::new(left, right));
+        let mut out_l = 0.0;
+        let mut out_r = 0.0;
+        let bands = split.iter().zip(self.gains.iter_mut().zip(&self.targets));
+        for (band, (gain, target)) in bands.take(self.bands) {
+            *gain += (target - *gain) * self.smoothing;
+            out_l += band.left * *gain;
+            out_r += band.right * *gain;
+        }
+        (out_l, out_r)
+    }
+
+    fn reset(&mut self) {
+        self.crossover.reset();
+        self.gains = self.targets;
+    }
+}
+
+/// Per-channel EQ with a selectable model
+///
+/// All models are kept allocated so switching is real-time safe. Only the
+/// active model runs, plus the outgoing one for the duration of a switch.
+#[derive(Clone)]
+pub struct ChannelEq {
+    model: EqModel,
+    /// Model being faded out after a switch
+    fading_from: Option<EqModel>,
+    /// Samples left in the switch crossfade
+    fade_remaining: u32,
+    classic: ClassicEq,
+    isolator: IsolatorEq,
+    four_band: IsolatorEq,
+}
+
+impl ChannelEq {
+    /// Create a flat EQ using the given model
+    pub fn new(model: EqModel) -> Self {
+        let mut eq = Self {
+            model,
+            fading_from: None,
+            fade_remaining: 0,
+            classic: ClassicEq::new(),
+            isolator: IsolatorEq::new(3),
+            four_band: IsolatorEq::new(4),
+        };
+        eq.set_crossovers(&EqCrossovers::default());
+        eq
+    }
+
+    /// Active model
+    pub fn model(&self) -> EqModel {
+        self.model
+    }
+
+    /// Switch model, crossfading from the current one
+    pub fn set_model(&mut self, model: EqModel) {
+        if model == self.model {
+            return;
+        }
+        // The incoming model has been idle; start it from a clean state
+        match model {
+            EqModel::Classic => self.classic.reset(),
+            EqModel::Isolator => self.isolator.reset(),
+            EqModel::FourBand => self.four_band.reset(),
+        }
+        self.fading_from = Some(self.model);
+        self.fade_remaining = MODEL_FADE_SAMPLES;
+        self.model = model;
+    }
+
+    /// Set the isolator crossover frequencies
+    pub fn set_crossovers(&mut self, crossovers: &EqCrossovers) {
+        let c = crossovers.validated();
+        self.isolator.set_crossovers(&[c.low_hz, c.high_hz]);
+        self.four_band.set_crossovers(&[c.low_hz, c.mid_hz, c.high_hz]);
+    }
+
+    /// Apply knob positions (0.0 = kill, 0.5 = flat, 1.0 = +6dB)
+    ///
+    /// `hi_mid` is only used by the 4-band model, where `mid` becomes the
+    /// low-mid band. All models are updated so a switch keeps the settings.
+    pub fn set_knobs(&mut self, lo: f32, mid: f32, hi_mid: f32, hi: f32) {
+        self.classic.set_knobs(lo, mid, hi);
+        self.isolator.set_knobs(&[lo, mid, hi]);
+        self.four_band.set_knobs(&[lo, mid, hi_mid, hi]);
+    }
+
+    #[inline]
+    fn process_model(&mut self, model: EqModel, left: f32, right: f32) -> (f32, f32) {
+        match model {
+            EqModel::Classic => self.classic.process(left, right),
+            EqModel::Isolator => self.isolator.process(left, right),
+            EqModel::FourBand => self.four_band.process(left, right),
+        }
+    }
+
+    /// Process one stereo sample
+    #[inline]
+    pub fn process(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let (out_l, out_r) = self.process_model(self.model, left, right);
+        let Some(from) = self.fading_from else {
+            return (out_l, out_r);
+        };
+
+        let (old_l, old_r) = self.process_model(from, left, right);
+        let old_weight = self.fade_remaining as f32 / MODEL_FADE_SAMPLES as f32;
+        self.fade_remaining -= 1;
+        if self.fade_remaining == 0 {
+            self.fading_from = None;
+        }
+        (
+            out_l + (old_l - out_l) * old_weight,
+            out_r + (old_r - out_r) * old_weight,
+        )
+    }
+
+    /// Reset all filter states
+    pub fn reset(&mut self) {
+        self.classic.reset();
+        self.isolator.reset();
+        self.four_band.reset();
+        self.fading_from = None;
+        self.fade_remaining = 0;
+    }
+}
+
+impl Default for ChannelEq {
+    fn default() -> Self {
+        Self::new(EqModel::default())
+    }
+}
+
+impl std::fmt::Debug for ChannelEq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelEq")
+            .field("model", &self.model)
+            .field("fading_from", &self.fading_from)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a sine through the EQ, returning its amplitude (from RMS) once settled
+    fn sine_level(eq: &mut ChannelEq, freq: f32) -> f32 {
+        let settle = SAMPLE_RATE as usize / 4;
+        let mut sum_sq = 0.0_f32;
+        for i in 0..(2 * settle) {
+            let x = (std::f32::consts::TAU * freq * i as f32 / SAMPLE_RATE as f32).sin() * 0.5;
+            let (l, _) = eq.process(x, x);
+            if i >= settle {
+                sum_sq += l * l;
+            }
+        }
+        (sum_sq / settle as f32).sqrt() * std::f32::consts::SQRT_2
+    }
+
+    #[test]
+    fn test_isolator_flat_is_transparent() {
+        let mut eq = ChannelEq::new(EqModel::Isolator);
+        eq.set_knobs(0.5, 0.5, 0.5, 0.5);
+        for freq in [60.0, 1000.0, 8000.0] {
+            let level = sine_level(&mut eq, freq);
+            assert!((level - 0.5).abs() < 0.02, "{} Hz level {}", freq, level);
+        }
+    }
+
+    #[test]
+    fn test_isolator_kills_band() {
+        let mut eq = ChannelEq::new(EqModel::Isolator);
+        eq.set_knobs(0.0, 0.5, 0.5, 0.5);
+        assert!(sine_level(&mut eq, 40.0) < 0.01);
+        assert!(sine_level(&mut eq, 1000.0) > 0.45);
+    }
+
+    #[test]
+    fn test_four_band_hi_mid_knob() {
+        let mut eq = ChannelEq::new(EqModel::FourBand);
+        eq.set_knobs(0.5, 0.5, 0.0, 0.5);
+        assert!(sine_level(&mut eq, 1600.0) < 0.1);
+        assert!(sine_level(&mut eq, 100.0) > 0.45);
+
+        // The 3-band models ignore the hi-mid knob
+        eq.set_model(EqModel::Isolator);
+        assert!(sine_level(&mut eq, 1600.0) > 0.45);
+    }
+
+    #[test]
+    fn test_model_switch_is_click_free() {
+        let mut eq = ChannelEq::new(EqModel::Classic);
+        eq.set_knobs(0.5, 0.5, 0.5, 0.5);
+        let freq = 100.0;
+        let max_step = std::f32::consts::TAU * freq / SAMPLE_RATE as f32 * 0.5;
+
+        let mut prev = 0.0;
+        let mut worst = 0.0_f32;
+        for i in 0..SAMPLE_RATE as usize {
+            if i == SAMPLE_RATE as usize / 2 {
+                eq.set_model(EqModel::Isolator);
+            }
+            let x = (std::f32::consts::TAU * freq * i as f32 / SAMPLE_RATE as f32).sin() * 0.5;
+            let (l, _) = eq.process(x, x);
+            worst = worst.max((l - prev).abs());
+            prev = l;
+        }
+        assert_eq!(eq.model(), EqModel::Isolator);
+        assert!(worst < max_step * 2.0, "step {} (sine max {})", worst, max_step);
+    }
+}
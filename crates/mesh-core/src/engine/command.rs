@@ -36,10 +36,11 @@
 //! engine.process_commands(&mut rx);
 //! ```
 
-use super::{LinkedStemData, PreparedTrack};
-use super::scratch::InterpolationMethod;
+use super::{FxBus, LineInput, LinkedStemData, PitchRange, PreparedTrack};
+use super::scratch::{InterpolationMethod, JogRelease};
 use super::slicer::{SlicerPreset, StepSequence};
 use crate::audio_file::StemBuffers;
+use crate::dvs::{DvsMode, TimecodeFormat};
 use crate::types::Stem;
 use basedrop::Shared;
 
@@ -114,6 +115,29 @@ pub enum EngineCommand {
     /// Linear = fast, acceptable quality; Cubic = better quality, more CPU
     SetScratchInterpolation { deck: usize, method: InterpolationMethod },
 
+    // ─────────────────────────────────────────────────────────────
+    // Jog Wheel
+    // ─────────────────────────────────────────────────────────────
+    /// Jog wheel touch sensor pressed or released
+    /// In vinyl mode a touch holds the platter; on release it glides per `release`
+    JogTouch { deck: usize, touched: bool, release: JogRelease },
+    /// Jog wheel turned by a number of platter revolutions (negative = backwards)
+    /// Scratches a held platter, scrubs a stopped deck, or bends the pitch
+    JogTurn { deck: usize, revolutions: f32 },
+    /// Temporary pitch bend as a rate offset (0.05 = 5% faster), decays to 0
+    Nudge { deck: usize, amount: f32 },
+    /// Enable/disable vinyl mode (jog touch scratches instead of bending)
+    SetVinylMode { deck: usize, enabled: bool },
+
+    // ─────────────────────────────────────────────────────────────
+    // DVS (timecode vinyl)
+    // ─────────────────────────────────────────────────────────────
+    /// Follow the deck's timecode input (or stop following it)
+    SetDvsMode { deck: usize, mode: DvsMode },
+    /// Timecode record/CD used on all decks
+    /// Needle positions decode once `dvs::prepare_format` has built the format's table
+    SetTimecodeFormat { format: TimecodeFormat },
+
     // ─────────────────────────────────────────────────────────────
     // CDJ-Style Cueing
     // ─────────────────────────────────────────────────────────────
@@ -166,6 +190,10 @@ pub enum EngineCommand {
     BeatJumpForward { deck: usize },
     /// Jump backward by beat_jump_size beats (equals loop length)
     BeatJumpBackward { deck: usize },
+    /// Jump to the start of the next phrase (analysed sections or 16 bars)
+    PhraseJumpForward { deck: usize },
+    /// Jump to the start of the current (or previous) phrase
+    PhraseJumpBackward { deck: usize },
     /// Update beat grid on a deck (for live beatgrid nudging in editors)
     ///
     /// This allows the beat grid to be updated without reloading the track,
@@ -190,9 +218,24 @@ pub enum EngineCommand {
     /// Enable/disable automatic key matching for a deck
     /// When enabled, the deck will transpose to match the master deck's key
     SetKeyMatchEnabled { deck: usize, enabled: bool },
-    /// Set the track's musical key (parsed from metadata)
+    /// Override the track's musical key (normally parsed from metadata on
+    /// load). Replaces the key timeline for key matching.
     SetTrackKey { deck: usize, key: Option<String> },
 
+    // ─────────────────────────────────────────────────────────────
+    // Pitch / Keylock
+    // ─────────────────────────────────────────────────────────────
+    /// Set the pitch fader position (-1.0 to +1.0, 0 = synced to global BPM)
+    SetPitchFader { deck: usize, value: f32 },
+    /// Set the pitch fader range (±6/10/16/50%)
+    SetPitchRange { deck: usize, range: PitchRange },
+    /// Enable/disable keylock (master tempo)
+    /// When disabled, tempo changes also shift pitch like a turntable
+    SetKeylock { deck: usize, enabled: bool },
+    /// Set the manual key shift in semitones and fine cents
+    /// Applied on top of automatic key matching
+    SetKeyShift { deck: usize, semitones: i8, cents: i8 },
+
     // ─────────────────────────────────────────────────────────────
     // Slicer Control
     // ─────────────────────────────────────────────────────────────
@@ -511,6 +554,12 @@ pub enum EngineCommand {
     SetVolume { deck: usize, volume: f32 },
     /// Set crossfader position (-1.0 = A, 0.0 = center, 1.0 = B)
     SetCrossfader { position: f32 },
+    /// Assign a channel to crossfader side A, B, or thru
+    SetCrossfaderAssign { deck: usize, assign: crate::config::CrossfaderAssign },
+    /// Set the crossfader gain curve (constant-power, linear, scratch)
+    SetCrossfaderCurve { curve: crate::config::CrossfaderCurve },
+    /// Reverse the crossfader direction (hamster mode)
+    SetCrossfaderReverse { enabled: bool },
     /// Set channel to cue (pre-fader listen)
     SetCueListen { deck: usize, enabled: bool },
     /// Set channel EQ high (0.0 = kill, 0.5 = flat, 1.0 = boost)
@@ -519,6 +568,12 @@ pub enum EngineCommand {
     SetEqMid { deck: usize, value: f32 },
     /// Set channel EQ low (0.0 = kill, 0.5 = flat, 1.0 = boost)
     SetEqLo { deck: usize, value: f32 },
+    /// Set channel EQ high-mid, 4-band model only (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetEqHiMid { deck: usize, value: f32 },
+    /// Switch a channel's EQ model (crossfaded, click-free)
+    SetEqModel { deck: usize, model: crate::config::EqModel },
+    /// Set the isolator crossover frequencies on all deck channels
+    SetEqCrossovers { crossovers: crate::config::EqCrossovers },
     /// Set channel filter (-1.0 = full LP, 0.0 = flat, 1.0 = full HP)
     SetFilter { deck: usize, value: f32 },
     /// Set master output volume (0.0 - 1.0)
@@ -534,6 +589,49 @@ pub enum EngineCommand {
     /// Manual CUE buttons always force cue_weight = 1.0 (additive/independent).
     SetAutoCue { enabled: bool },
 
+    // ─────────────────────────────────────────────────────────────
+    // Line Inputs (mic / aux)
+    // ─────────────────────────────────────────────────────────────
+    /// Set a line input's channel volume (0.0 - 1.0)
+    SetLineVolume { input: LineInput, volume: f32 },
+    /// Set a line input's trim in dB (-24 to +12)
+    SetLineTrim { input: LineInput, db: f32 },
+    /// Set a line input's EQ high (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetLineEqHi { input: LineInput, value: f32 },
+    /// Set a line input's EQ mid (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetLineEqMid { input: LineInput, value: f32 },
+    /// Set a line input's EQ low (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetLineEqLo { input: LineInput, value: f32 },
+    /// Route a line input to the cue bus (pre-fader listen)
+    SetLineCue { input: LineInput, enabled: bool },
+    /// Duck the decks while there is signal on a line input
+    SetTalkover { input: LineInput, enabled: bool },
+    /// Set how far talkover ducks the decks in dB (-40 to 0)
+    SetTalkoverDepth { db: f32 },
+
+    // ─────────────────────────────────────────────────────────────
+    // Mixer FX Buses (send/return + master insert)
+    // ─────────────────────────────────────────────────────────────
+    /// Append an effect to an FX bus chain (ignored when the chain is full)
+    AddBusEffect { bus: FxBus, effect: Box<dyn crate::effect::Effect> },
+    /// Remove an effect from an FX bus chain
+    RemoveBusEffect { bus: FxBus, effect_index: usize },
+    /// Set a parameter of an effect on an FX bus (normalized 0.0 - 1.0)
+    SetBusEffectParam {
+        bus: FxBus,
+        effect_index: usize,
+        param_index: usize,
+        value: f32,
+    },
+    /// Bypass an effect on an FX bus
+    SetBusEffectBypass { bus: FxBus, effect_index: usize, bypass: bool },
+    /// Turn an FX bus chain on or off
+    SetBusEnabled { bus: FxBus, enabled: bool },
+    /// Set a send bus return level into master (0.0 - 1.0)
+    SetBusReturn { bus: FxBus, level: f32 },
+    /// Set a channel's post-fader send level to a send bus (0.0 - 1.0)
+    SetFxSend { deck: usize, send: usize, level: f32 },
+
     // ─────────────────────────────────────────────────────────────
     // Loudness Compensation
     // ─────────────────────────────────────────────────────────────
@@ -575,6 +673,36 @@ pub enum EngineCommand {
     /// automatically align to the master deck's beat phase.
     SetPhaseSync(bool),
 
+    // ─────────────────────────────────────────────────────────────
+    // Sampler
+    // ─────────────────────────────────────────────────────────────
+    /// Put a clip into a sampler slot (`None` clears it)
+    ///
+    /// The clip is decoded/captured on the UI side and shared via
+    /// `basedrop::Shared`, so the replaced clip is freed on the GC thread.
+    LoadSamplerSlot {
+        slot: usize,
+        clip: Option<Shared<crate::sampler::SampleClip>>,
+    },
+    /// Trigger a sampler slot (one-shots restart, loops toggle)
+    SamplerTrigger { slot: usize },
+    /// Stop a sampler slot
+    SamplerStop { slot: usize },
+    /// Stop all sampler slots
+    SamplerStopAll,
+    /// Set sampler slot volume (0.0 - 1.0)
+    SetSamplerVolume { slot: usize, volume: f32 },
+    /// Set sampler slot EQ high (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetSamplerEqHi { slot: usize, value: f32 },
+    /// Set sampler slot EQ mid (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetSamplerEqMid { slot: usize, value: f32 },
+    /// Set sampler slot EQ low (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    SetSamplerEqLo { slot: usize, value: f32 },
+    /// Set sampler slot filter (-1.0 = full LP, 0.0 = flat, 1.0 = full HP)
+    SetSamplerFilter { slot: usize, value: f32 },
+    /// Route a sampler slot to the cue bus (pre-fader listen)
+    SetSamplerCue { slot: usize, enabled: bool },
+
     // ─────────────────────────────────────────────────────────────
     // Set Recording
     // ─────────────────────────────────────────────────────────────
@@ -585,7 +713,14 @@ pub enum EngineCommand {
     StartRecording {
         producer: Box<rtrb::Producer<crate::types::StereoSample>>,
     },
-    /// Stop all active recordings (drops all producers)
+    /// Start capturing the per-stem buses for multitrack recording
+    ///
+    /// The capture owns 16 time stretchers, so it is built on the UI thread
+    /// and wrapped in `basedrop::Owned` (pointer-sized, freed by the GC).
+    StartStemRecording {
+        capture: basedrop::Owned<super::StemCapture>,
+    },
+    /// Stop all active recordings (drops all producers and the stem capture)
     StopRecording,
 }
 
@@ -5,14 +5,16 @@ use std::sync::Arc;
 
 use rayon::prelude::*;
 
-use crate::audio_file::{LoadedTrack, StemBuffers};
+use crate::audio_file::{LoadedTrack, PhraseSection, StemBuffers};
 use basedrop::Shared;
+use crate::dvs::{DvsMode, TimecodeReading};
 use crate::effect::{Effect, MultibandHost};
 use crate::types::{
     DeckId, PlayState, Stem, StereoBuffer, StereoSample, TransportPosition,
     NUM_STEMS, SAMPLE_RATE,
 };
 
+use super::scratch::SECONDS_PER_REVOLUTION;
 use super::{LatencyCompensator, LinkedStemAtomics, StemLink, MAX_BUFFER_SIZE};
 
 /// Process-wide epoch for timestamp-based playhead interpolation.
@@ -31,9 +33,94 @@ const STEM_FADE_SAMPLES: f32 = SAMPLE_RATE as f32 * 0.050;
 /// Per-sample gain step for the stem mute fade (≈ 0.000417 at 48kHz/50ms).
 const STEM_FADE_STEP: f32 = 1.0 / STEM_FADE_SAMPLES;
 
+/// Pitch bend added per jog wheel revolution on a playing deck
+const JOG_BEND_PER_REVOLUTION: f64 = 0.25;
+/// Largest pitch bend from the jog wheel (rate offset either direction)
+pub const MAX_PITCH_BEND: f64 = 0.5;
+/// Time constant of the pitch bend fading out once the jog wheel stops
+const PITCH_BEND_DECAY_SECS: f64 = 0.1;
+/// In absolute DVS mode, drift from the needle position that triggers a seek
+const DVS_RESYNC_SECS: f64 = 0.05;
+
+/// Phrase length used for phrase jumps on tracks without structure analysis
+/// (16 bars of 4/4)
+const FALLBACK_PHRASE_BEATS: i64 = 64;
+
 /// Loop lengths available in beats (1/8 beat to 64 bars = 256 beats)
 pub const LOOP_LENGTHS: [f64; 12] = [0.125, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0];
 
+/// Pitch fader range: how far a fully moved fader changes the tempo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PitchRange {
+    /// ±6%
+    Six,
+    /// ±10%
+    #[default]
+    Ten,
+    /// ±16%
+    Sixteen,
+    /// ±50%
+    Fifty,
+}
+
+impl PitchRange {
+    /// All ranges, narrowest first
+    pub const ALL: [PitchRange; 4] = [Self::Six, Self::Ten, Self::Sixteen, Self::Fifty];
+
+    /// Tempo change at full fader travel (0.10 for ±10%)
+    pub fn fraction(self) -> f64 {
+        match self {
+            Self::Six => 0.06,
+            Self::Ten => 0.10,
+            Self::Sixteen => 0.16,
+            Self::Fifty => 0.50,
+        }
+    }
+
+    /// Range in whole percent, for display
+    pub fn percent(self) -> u8 {
+        (self.fraction() * 100.0).round() as u8
+    }
+
+    /// Next wider range, wrapping from ±50% back to ±6%
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|r| *r == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    fn to_u8(self) -> u8 {
+        Self::ALL.iter().position(|r| *r == self).unwrap_or(0) as u8
+    }
+
+    fn from_u8(v: u8) -> Self {
+        Self::ALL.get(v as usize).copied().unwrap_or_default()
+    }
+}
+
+/// Largest manual key shift in semitones (either direction)
+pub const KEY_SHIFT_MAX_SEMITONES: i8 = 12;
+/// Largest fine key shift in cents (either direction)
+pub const KEY_SHIFT_MAX_CENTS: i8 = 50;
+
+/// Step a (semitones, cents) key shift by a delta
+///
+/// Cents carry into semitones so the result stays within ±50 cents, and the
+/// total is limited to ±12 semitones.
+pub fn step_key_shift(current: (i8, i8), semitones: i8, cents: i8) -> (i8, i8) {
+    let max = KEY_SHIFT_MAX_SEMITONES as i32 * 100;
+    let total = (current.0 as i32 * 100 + current.1 as i32 + semitones as i32 * 100 + cents as i32)
+        .clamp(-max, max);
+    let (mut semis, mut rest) = (total / 100, total % 100);
+    if rest > KEY_SHIFT_MAX_CENTS as i32 {
+        semis += 1;
+        rest -= 100;
+    } else if rest < -(KEY_SHIFT_MAX_CENTS as i32) {
+        semis -= 1;
+        rest += 100;
+    }
+    (semis as i8, rest as i8)
+}
+
 /// A hot cue point stored in a slot
 #[derive(Debug, Clone)]
 pub struct HotCue {
@@ -64,6 +151,43 @@ pub struct PreparedTrack {
     pub hot_cues: [Option<HotCue>; HOT_CUE_SLOTS],
     /// First beat position for initial cue point
     pub first_beat: usize,
+    /// Global key, and the keys at the start and end of the key timeline
+    /// (parsed here so loading does no string work on the audio thread)
+    pub keys: TrackKeys,
+}
+
+/// Parsed keys of a track, used by key matching
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrackKeys {
+    /// Whole-track key
+    pub track: Option<crate::music::MusicalKey>,
+    /// Key of the first key segment (None without a key timeline)
+    pub intro: Option<crate::music::MusicalKey>,
+    /// Key of the last key segment (None without a key timeline)
+    pub outro: Option<crate::music::MusicalKey>,
+}
+
+impl TrackKeys {
+    /// Parse the global key and the key timeline ends from track metadata
+    pub fn from_metadata(metadata: &crate::audio_file::TrackMetadata) -> Self {
+        use crate::music::MusicalKey;
+        let timeline = &metadata.key_timeline;
+        Self {
+            track: metadata.key.as_deref().and_then(MusicalKey::parse),
+            intro: timeline.first().and_then(|s| MusicalKey::parse(&s.key)),
+            outro: timeline.last().and_then(|s| MusicalKey::parse(&s.key)),
+        }
+    }
+
+    /// Key at the start of the track (falls back to the global key)
+    pub fn intro_key(&self) -> Option<crate::music::MusicalKey> {
+        self.intro.or(self.track)
+    }
+
+    /// Key at the end of the track (falls back to the global key)
+    pub fn outro_key(&self) -> Option<crate::music::MusicalKey> {
+        self.outro.or(self.track)
+    }
 }
 
 impl PreparedTrack {
@@ -90,10 +214,13 @@ impl PreparedTrack {
             .map(|b| b as usize)
             .unwrap_or(0);
 
+        let keys = TrackKeys::from_metadata(&track.metadata);
+
         Self {
             track,
             hot_cues,
             first_beat,
+            keys,
         }
     }
 }
@@ -142,6 +269,24 @@ pub struct DeckAtomics {
     pub current_transpose: AtomicI8,
     /// Whether keys are compatible (no transpose needed even with key match on)
     pub keys_compatible: AtomicBool,
+    /// Pitch fader position (f32 stored as bits, -1.0 to +1.0, 0 = centre)
+    pitch_fader: AtomicU32,
+    /// Pitch fader range index into `PitchRange::ALL`
+    pitch_range: AtomicU8,
+    /// Whether keylock (master tempo) is on
+    pub keylock: AtomicBool,
+    /// Manual key shift in semitones (-12 to +12)
+    pub key_shift_semitones: AtomicI8,
+    /// Manual fine key shift in cents (-50 to +50)
+    pub key_shift_cents: AtomicI8,
+    /// Whether vinyl mode is on (jog touch scratches)
+    pub vinyl_mode: AtomicBool,
+    /// DVS mode (`DvsMode` as u8)
+    dvs_mode: AtomicU8,
+    /// Whether the timecode input carries a signal
+    pub dvs_signal: AtomicBool,
+    /// Platter speed read from the timecode (f32 stored as bits)
+    dvs_speed: AtomicU32,
     /// LUFS-based gain compensation (f32 stored as bits)
     /// 1.0 = unity gain, calculated from target_lufs - track_lufs
     pub lufs_gain: AtomicU32,
@@ -175,6 +320,15 @@ impl DeckAtomics {
             key_match_enabled: AtomicBool::new(false),
             current_transpose: AtomicI8::new(0),
             keys_compatible: AtomicBool::new(true),
+            pitch_fader: AtomicU32::new(0.0_f32.to_bits()),
+            pitch_range: AtomicU8::new(PitchRange::default().to_u8()),
+            keylock: AtomicBool::new(true),
+            key_shift_semitones: AtomicI8::new(0),
+            key_shift_cents: AtomicI8::new(0),
+            vinyl_mode: AtomicBool::new(true),
+            dvs_mode: AtomicU8::new(DvsMode::Off.to_u8()),
+            dvs_signal: AtomicBool::new(false),
+            dvs_speed: AtomicU32::new(0.0_f32.to_bits()),
             lufs_gain: AtomicU32::new(1.0_f32.to_bits()), // Unity gain by default
             lufs_gain_db: AtomicU32::new(f32::NAN.to_bits()), // No gain display
             track_lufs: AtomicU32::new(f32::NAN.to_bits()), // Unknown
@@ -195,6 +349,36 @@ impl DeckAtomics {
         self.position_timestamp_ns.load(Ordering::Relaxed)
     }
 
+    /// Get pitch fader position, -1.0 to +1.0 (lock-free)
+    #[inline]
+    pub fn pitch_fader(&self) -> f32 {
+        f32::from_bits(self.pitch_fader.load(Ordering::Relaxed))
+    }
+
+    /// Get pitch fader range (lock-free)
+    #[inline]
+    pub fn pitch_range(&self) -> PitchRange {
+        PitchRange::from_u8(self.pitch_range.load(Ordering::Relaxed))
+    }
+
+    /// Get pitch fader tempo offset in percent, e.g. +3.2 (lock-free)
+    #[inline]
+    pub fn pitch_percent(&self) -> f32 {
+        self.pitch_fader() * self.pitch_range().fraction() as f32 * 100.0
+    }
+
+    /// Get DVS mode (lock-free)
+    #[inline]
+    pub fn dvs_mode(&self) -> DvsMode {
+        DvsMode::from_u8(self.dvs_mode.load(Ordering::Relaxed))
+    }
+
+    /// Get platter speed from the timecode, 1.0 = normal (lock-free)
+    #[inline]
+    pub fn dvs_speed(&self) -> f32 {
+        f32::from_bits(self.dvs_speed.load(Ordering::Relaxed))
+    }
+
     /// Get current playback rate (lock-free)
     #[inline]
     pub fn playback_rate(&self) -> f32 {
@@ -407,8 +591,8 @@ pub struct Deck {
     loop_state: LoopState,
     /// Per-stem state (effect chains, mute/solo)
     stems: [StemState; NUM_STEMS],
-    /// Scratch/jog adjustment in samples (for pitch bending)
-    scratch_offset: f64,
+    /// Temporary jog pitch bend as a rate offset (0.1 = 10% faster), decays to 0
+    pitch_bend: f64,
     /// Whether shift is held (for alternate button functions)
     shift_held: bool,
     /// Time stretch ratio (target_bpm / track_bpm)
@@ -427,6 +611,9 @@ pub struct Deck {
     /// One buffer per stem enables parallel processing with Rayon
     /// Capacity is MAX_BUFFER_SIZE to handle any audio buffer size
     stem_buffers: [StereoBuffer; NUM_STEMS],
+    /// Whether `stem_buffers` hold this buffer's audio (false after an
+    /// early return: no track, stopped, or stationary scratch)
+    stem_output_valid: bool,
     /// Accumulated fractional samples for time stretch accuracy
     ///
     /// When time stretching, the ideal number of samples to read is often
@@ -438,8 +625,20 @@ pub struct Deck {
     key_match_enabled: bool,
     /// Current transposition in semitones (0 if disabled or compatible)
     current_transpose: i8,
-    /// Track's parsed musical key (None if not detected or unavailable)
-    track_key: Option<crate::music::MusicalKey>,
+    /// Track's parsed keys (global and key-timeline ends)
+    track_keys: TrackKeys,
+    /// Pitch fader position (-1.0 to +1.0), scaled by `pitch_range`
+    pitch_fader: f32,
+    /// Pitch fader range
+    pitch_range: PitchRange,
+    /// Keylock (master tempo): when off, tempo changes also change pitch
+    keylock: bool,
+    /// Manual key shift in semitones, added on top of key matching
+    key_shift_semitones: i8,
+    /// Manual fine key shift in cents
+    key_shift_cents: i8,
+    /// Tempo last forwarded to the stem effects (track BPM at the playhead)
+    effect_tempo: f64,
     /// Per-stem slicer states (initially only Drums is used, but modular for future)
     slicer_states: [super::slicer::SlicerState; NUM_STEMS],
     /// Per-stem link state for hot-swappable stems from other tracks
@@ -466,6 +665,14 @@ pub struct Deck {
     scratch: super::scratch::ScratchState,
     /// Play state before scratch started (to restore on scratch end)
     scratch_previous_state: PlayState,
+    /// Play state to enter once a release glide (spin-back, brake) finishes
+    glide_end_state: PlayState,
+    /// Vinyl mode: touching the jog wheel scratches instead of bending pitch
+    vinyl_mode: bool,
+    /// Whether the jog wheel's touch sensor is held
+    jog_touched: bool,
+    /// Whether (and how) the deck follows its timecode input
+    dvs_mode: DvsMode,
 }
 
 impl Deck {
@@ -480,7 +687,7 @@ impl Deck {
             hot_cues: std::array::from_fn(|_| None),
             loop_state: LoopState::default(),
             stems: std::array::from_fn(|_| StemState::new()),
-            scratch_offset: 0.0,
+            pitch_bend: 0.0,
             shift_held: false,
             stretch_ratio: 1.0, // No stretching by default
             hot_cue_preview_return: None,
@@ -488,10 +695,17 @@ impl Deck {
             slip_position: None,
             atomics: Arc::new(DeckAtomics::new()),
             stem_buffers: std::array::from_fn(|_| StereoBuffer::silence(MAX_BUFFER_SIZE)),
+            stem_output_valid: false,
             fractional_position: 0.0,
             key_match_enabled: false,
             current_transpose: 0,
-            track_key: None,
+            track_keys: TrackKeys::default(),
+            pitch_fader: 0.0,
+            pitch_range: PitchRange::default(),
+            keylock: true,
+            key_shift_semitones: 0,
+            key_shift_cents: 0,
+            effect_tempo: 0.0,
             slicer_states: std::array::from_fn(|_| super::slicer::SlicerState::new()),
             stem_links: std::array::from_fn(|_| StemLink::new()),
             linked_stem_atomics: Arc::new(LinkedStemAtomics::new()),
@@ -500,6 +714,10 @@ impl Deck {
             host_lufs: None,
             scratch: super::scratch::ScratchState::new(),
             scratch_previous_state: PlayState::Stopped,
+            glide_end_state: PlayState::Stopped,
+            vinyl_mode: true,
+            jog_touched: false,
+            dvs_mode: DvsMode::Off,
         }
     }
 
@@ -539,7 +757,7 @@ impl Deck {
         let now_ns = PROCESS_EPOCH.elapsed().as_nanos() as u64;
         self.atomics.position_timestamp_ns.store(now_ns, Ordering::Relaxed);
         // Zero rate during scratch so UI interpolation doesn't add forward drift
-        let rate = if self.scratch.active { 0.0_f32 } else { self.stretch_ratio as f32 };
+        let rate = if self.scratch.active { 0.0_f32 } else { self.playback_speed() as f32 };
         self.atomics.playback_rate.store(rate.to_bits(), Ordering::Relaxed);
     }
 
@@ -584,6 +802,16 @@ impl Deck {
         self.atomics.keys_compatible.store(self.current_transpose == 0, Ordering::Relaxed);
     }
 
+    /// Write pitch fader, keylock and key shift to atomics (internal helper)
+    #[inline]
+    fn sync_pitch_atomic(&self) {
+        self.atomics.pitch_fader.store(self.pitch_fader.to_bits(), Ordering::Relaxed);
+        self.atomics.pitch_range.store(self.pitch_range.to_u8(), Ordering::Relaxed);
+        self.atomics.keylock.store(self.keylock, Ordering::Relaxed);
+        self.atomics.key_shift_semitones.store(self.key_shift_semitones, Ordering::Relaxed);
+        self.atomics.key_shift_cents.store(self.key_shift_cents, Ordering::Relaxed);
+    }
+
     /// Get the deck ID
     pub fn id(&self) -> DeckId {
         self.id
@@ -617,7 +845,7 @@ impl Deck {
             length_index,
             ..LoopState::default()
         };
-        self.scratch_offset = 0.0;
+        self.pitch_bend = 0.0;
         self.hot_cue_preview_return = None;
         self.fractional_position = 0.0; // Reset stretch accumulator
 
@@ -643,6 +871,7 @@ impl Deck {
         // Reset transient playback state (mutes, solos, slicers, key match)
         // Preserves slip mode and loop length
         self.reset_playback_state();
+        self.track_keys = prepared.keys;
 
         // Sync atomics for lock-free UI reads (fast atomic stores)
         self.sync_position_atomic();
@@ -686,7 +915,7 @@ impl Deck {
         // Reset key matching
         self.key_match_enabled = false;
         self.current_transpose = 0;
-        self.track_key = None;
+        self.track_keys = TrackKeys::default();
         self.atomics.key_match_enabled.store(false, Ordering::Relaxed);
         self.atomics.current_transpose.store(0, Ordering::Relaxed);
 
@@ -767,14 +996,30 @@ impl Deck {
         }
     }
 
-    /// Get the samples per beat for the loaded track
+    /// Get the samples per beat at the current playhead
+    ///
+    /// Measured from the beat grid so variable-tempo tracks use the length of
+    /// the current beat; falls back to the track BPM for sparse grids.
     pub fn samples_per_beat(&self) -> f64 {
         self.track
             .as_ref()
-            .map(|t| t.samples_per_beat())
+            .map(|t| {
+                t.metadata
+                    .beat_grid
+                    .beat_length_at(self.position as u64)
+                    .unwrap_or_else(|| t.samples_per_beat_at(self.position as u64))
+            })
             .unwrap_or(SAMPLE_RATE as f64 * 60.0 / 120.0) // Default 120 BPM
     }
 
+    /// Get the native BPM of the loaded track at the current playhead
+    ///
+    /// For variable-tempo grids this is the tempo of the segment under the
+    /// playhead; otherwise the track BPM.
+    pub fn local_bpm(&self) -> Option<f64> {
+        self.track.as_ref().map(|t| t.bpm_at(self.position as u64))
+    }
+
     /// Snap a sample position to the nearest beat in the grid
     pub fn snap_to_beat(&self, position: usize) -> usize {
         if let Some(track) = &self.track {
@@ -974,6 +1219,11 @@ impl Deck {
         if self.track.is_none() {
             return;
         }
+        if self.scratch.is_gliding() {
+            // Catching a coasting platter: keep the state from the original touch
+            self.scratch.start(self.position);
+            return;
+        }
         self.scratch_previous_state = self.state;
         self.scratch.start(self.position);
         // Enter a playing-like state so audio callback runs
@@ -1018,6 +1268,174 @@ impl Deck {
         self.scratch.active
     }
 
+    // --- Jog wheel ---
+
+    /// Check if vinyl mode is on
+    pub fn vinyl_mode(&self) -> bool {
+        self.vinyl_mode
+    }
+
+    /// Turn vinyl mode on or off
+    ///
+    /// With vinyl mode on, touching the jog wheel holds the platter and turning
+    /// it scratches. With it off, the jog wheel always bends the pitch.
+    pub fn set_vinyl_mode(&mut self, enabled: bool) {
+        self.vinyl_mode = enabled;
+        self.atomics.vinyl_mode.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Jog wheel touch sensor pressed or released
+    ///
+    /// In vinyl mode a touch enters scratch mode, stopping the platter under
+    /// the hand. Releasing it lets the platter go with the given effect.
+    pub fn jog_touch(&mut self, touched: bool, release: super::scratch::JogRelease) {
+        self.jog_touched = touched;
+        if touched {
+            if self.vinyl_mode {
+                self.scratch_start();
+            }
+        } else if self.scratch.active && !self.scratch.is_gliding() {
+            self.release_platter(release);
+        }
+    }
+
+    /// Jog wheel turned by a number of platter revolutions (negative = backwards)
+    ///
+    /// A held platter scratches, a stopped deck scrubs through the track, and
+    /// a playing deck gets a temporary pitch bend that decays when the wheel
+    /// stops.
+    pub fn jog_turn(&mut self, revolutions: f64) {
+        let Some(track) = &self.track else { return };
+        let samples = revolutions * SECONDS_PER_REVOLUTION * SAMPLE_RATE as f64 * self.stretch_ratio;
+        let max_position = track.duration_samples.saturating_sub(1);
+
+        if self.scratch.active && self.jog_touched && !self.scratch.is_gliding() {
+            let target = self.scratch.move_by(samples, max_position as f64);
+            self.position = target as usize;
+            self.sync_position_atomic();
+        } else if self.scratch.active {
+            // Coasting platter: the wheel has no grip until it is touched again
+        } else if self.state == PlayState::Stopped {
+            let position = (self.position as f64 + samples).clamp(0.0, max_position as f64);
+            self.seek(position as usize);
+        } else {
+            self.nudge(revolutions * JOG_BEND_PER_REVOLUTION);
+        }
+    }
+
+    /// Add a temporary rate offset (0.05 = 5% faster) that decays back to 0
+    pub fn nudge(&mut self, amount: f64) {
+        self.pitch_bend = (self.pitch_bend + amount).clamp(-MAX_PITCH_BEND, MAX_PITCH_BEND);
+    }
+
+    /// Current temporary pitch bend as a rate offset
+    pub fn pitch_bend(&self) -> f64 {
+        self.pitch_bend
+    }
+
+    /// Playback speed relative to the track's native tempo (stretch ratio plus bend)
+    fn playback_speed(&self) -> f64 {
+        self.stretch_ratio * (1.0 + self.pitch_bend)
+    }
+
+    /// Let the pitch bend fade out over `PITCH_BEND_DECAY_SECS`
+    fn decay_pitch_bend(&mut self, output_len: usize) {
+        if self.pitch_bend != 0.0 {
+            self.pitch_bend *= (-(output_len as f64) / (PITCH_BEND_DECAY_SECS * SAMPLE_RATE as f64)).exp();
+            if self.pitch_bend.abs() < 1e-4 {
+                self.pitch_bend = 0.0;
+            }
+        }
+    }
+
+    /// Start the glide that follows letting go of a scratched platter
+    fn release_platter(&mut self, release: super::scratch::JogRelease) {
+        use super::scratch::{JogRelease, BRAKE_SECS, MOTOR_RAMP_SECS, SPINBACK_SECS, SPINBACK_SPEED};
+
+        let motor_speed = if self.scratch_previous_state == PlayState::Playing { self.stretch_ratio } else { 0.0 };
+        let (from, to, secs, end_state) = match release {
+            JogRelease::Resume => (self.scratch.velocity(), motor_speed, MOTOR_RAMP_SECS, self.scratch_previous_state),
+            JogRelease::SpinBack => (SPINBACK_SPEED, 0.0, SPINBACK_SECS, self.scratch_previous_state),
+            JogRelease::Brake => (motor_speed, 0.0, BRAKE_SECS, PlayState::Stopped),
+        };
+
+        self.glide_end_state = end_state;
+        if from == to {
+            self.finish_glide();
+        } else {
+            self.scratch.glide(from, to, secs * SAMPLE_RATE as f64);
+        }
+    }
+
+    /// Leave scratch mode once the platter has settled after a release
+    fn finish_glide(&mut self) {
+        if let Some(track) = &self.track {
+            self.position = self.scratch.current_position().min(track.duration_samples.saturating_sub(1));
+        }
+        self.scratch.end();
+        self.fractional_position = 0.0;
+        self.state = self.glide_end_state;
+        self.sync_state_atomic();
+        self.sync_position_atomic();
+    }
+
+    // --- DVS ---
+
+    /// Get the DVS mode
+    pub fn dvs_mode(&self) -> DvsMode {
+        self.dvs_mode
+    }
+
+    /// Switch between internal playback and timecode control
+    ///
+    /// Turning DVS off hands the platter back to the deck: it keeps playing
+    /// if the record was spinning forwards, otherwise it stops.
+    pub fn set_dvs_mode(&mut self, mode: DvsMode) {
+        if mode == DvsMode::Off && self.dvs_mode != DvsMode::Off && self.scratch.active {
+            let spinning = self.scratch.velocity() > 0.5;
+            self.position = self.scratch.current_position();
+            self.scratch.end();
+            self.fractional_position = 0.0;
+            self.state = if spinning { PlayState::Playing } else { PlayState::Stopped };
+            self.sync_state_atomic();
+            self.sync_position_atomic();
+        }
+        self.dvs_mode = mode;
+        self.atomics.dvs_mode.store(mode.to_u8(), Ordering::Relaxed);
+    }
+
+    /// Follow the timecode record for one buffer
+    ///
+    /// The needle holds the platter, so playback runs through the scratch
+    /// path with the record's speed. In absolute mode the deck also seeks to
+    /// the needle position when it drifts away (needle drops).
+    pub fn follow_timecode(&mut self, reading: &TimecodeReading, output_len: usize) {
+        self.atomics.dvs_signal.store(reading.signal, Ordering::Relaxed);
+        self.atomics.dvs_speed.store((reading.speed as f32).to_bits(), Ordering::Relaxed);
+        if self.dvs_mode == DvsMode::Off {
+            return;
+        }
+        let Some(track) = &self.track else { return };
+        let max_position = track.duration_samples.saturating_sub(1) as f64;
+
+        if !self.scratch.active || self.scratch.is_gliding() || self.state != PlayState::Playing {
+            self.scratch.start(self.position);
+            self.state = PlayState::Playing;
+            self.sync_state_atomic();
+        }
+
+        let mut target = self.scratch.move_by(reading.speed * output_len as f64, max_position);
+        if let (DvsMode::Absolute, Some(secs)) = (self.dvs_mode, reading.position) {
+            let needle = (secs * SAMPLE_RATE as f64).min(max_position);
+            if (needle - target).abs() > DVS_RESYNC_SECS * SAMPLE_RATE as f64 {
+                self.scratch.start(needle as usize);
+                target = needle;
+            }
+        }
+        self.position = target as usize;
+        self.sync_position_atomic();
+    }
+
     /// Set scratch interpolation method
     pub fn set_scratch_interpolation(&mut self, method: super::scratch::InterpolationMethod) {
         self.scratch.set_interpolation(method);
@@ -1070,14 +1488,113 @@ impl Deck {
         self.sync_key_match_atomic();
     }
 
+    // --- Pitch fader, keylock and key shift ---
+
+    /// Get the pitch fader position (-1.0 to +1.0)
+    pub fn pitch_fader(&self) -> f32 {
+        self.pitch_fader
+    }
+
+    /// Set the pitch fader position, clamped to -1.0..+1.0
+    ///
+    /// The engine multiplies the synced stretch ratio by [`Self::tempo_factor`],
+    /// so the fader offsets the deck from the global BPM.
+    pub fn set_pitch_fader(&mut self, value: f32) {
+        self.pitch_fader = value.clamp(-1.0, 1.0);
+        self.sync_pitch_atomic();
+    }
+
+    /// Get the pitch fader range
+    pub fn pitch_range(&self) -> PitchRange {
+        self.pitch_range
+    }
+
+    /// Set the pitch fader range (the fader position is kept)
+    pub fn set_pitch_range(&mut self, range: PitchRange) {
+        self.pitch_range = range;
+        self.sync_pitch_atomic();
+    }
+
+    /// Tempo multiplier from the pitch fader (1.0 at centre)
+    pub fn tempo_factor(&self) -> f64 {
+        1.0 + self.pitch_fader as f64 * self.pitch_range.fraction()
+    }
+
+    /// Forward the tempo to beat-synced stem effects
+    ///
+    /// Stem effects run before the time stretcher, on audio at the track's
+    /// own speed, so they are synced to the track BPM at the playhead; the
+    /// stretcher then carries their timing to the global tempo with the
+    /// music. Changes under 0.01 BPM are ignored.
+    pub fn set_effect_tempo(&mut self, bpm: f64) {
+        if bpm <= 0.0 || (bpm - self.effect_tempo).abs() < 0.01 {
+            return;
+        }
+        self.effect_tempo = bpm;
+        for stem in &mut self.stems {
+            stem.multiband.set_tempo(bpm);
+        }
+    }
+
+    /// Check if keylock (master tempo) is on
+    pub fn keylock(&self) -> bool {
+        self.keylock
+    }
+
+    /// Turn keylock on or off
+    pub fn set_keylock(&mut self, enabled: bool) {
+        self.keylock = enabled;
+        self.sync_pitch_atomic();
+    }
+
+    /// Get the manual key shift as (semitones, cents)
+    pub fn key_shift(&self) -> (i8, i8) {
+        (self.key_shift_semitones, self.key_shift_cents)
+    }
+
+    /// Set the manual key shift, clamped to ±12 semitones and ±50 cents
+    pub fn set_key_shift(&mut self, semitones: i8, cents: i8) {
+        self.key_shift_semitones = semitones.clamp(-KEY_SHIFT_MAX_SEMITONES, KEY_SHIFT_MAX_SEMITONES);
+        self.key_shift_cents = cents.clamp(-KEY_SHIFT_MAX_CENTS, KEY_SHIFT_MAX_CENTS);
+        self.sync_pitch_atomic();
+    }
+
+    /// Total pitch shift for the time stretcher in semitones
+    ///
+    /// Key match transpose plus the manual key shift. With keylock off the
+    /// pitch follows the playback speed like a turntable, so the stretch
+    /// ratio's pitch change is added as well.
+    pub fn pitch_shift_semitones(&self) -> f64 {
+        let mut semitones = self.current_transpose as f64
+            + self.key_shift_semitones as f64
+            + self.key_shift_cents as f64 / 100.0;
+        if !self.keylock {
+            semitones += 12.0 * self.stretch_ratio.log2();
+        }
+        semitones
+    }
+
     /// Get the track's musical key
     pub fn track_key(&self) -> Option<crate::music::MusicalKey> {
-        self.track_key
+        self.track_keys.track
+    }
+
+    /// Key at the start of the track — what an incoming deck is matched by
+    pub fn intro_key(&self) -> Option<crate::music::MusicalKey> {
+        self.track_keys.intro_key()
     }
 
-    /// Set the track's musical key (parsed from metadata)
+    /// Key at the end of the track — what an outgoing (master) deck is matched to
+    pub fn outro_key(&self) -> Option<crate::music::MusicalKey> {
+        self.track_keys.outro_key()
+    }
+
+    /// Override the track's musical key
+    ///
+    /// An explicit key replaces the key timeline too, so key matching uses
+    /// it for both the intro and the outro.
     pub fn set_track_key(&mut self, key: Option<crate::music::MusicalKey>) {
-        self.track_key = key;
+        self.track_keys = TrackKeys { track: key, intro: None, outro: None };
     }
 
     // --- Loudness Compensation ---
@@ -1087,6 +1604,14 @@ impl Deck {
         self.lufs_gain
     }
 
+    /// Processed stem buffers from the last `process()` call
+    ///
+    /// Post-effects and latency-compensated, but before LUFS gain and time
+    /// stretching. `None` when the deck produced no audio in that buffer.
+    pub fn stem_output(&self) -> Option<&[StereoBuffer; NUM_STEMS]> {
+        self.stem_output_valid.then_some(&self.stem_buffers)
+    }
+
     /// Get the track's measured LUFS (from metadata)
     ///
     /// Returns the track's integrated loudness in LUFS. Used to calculate
@@ -1233,6 +1758,100 @@ impl Deck {
         }
     }
 
+    /// Jump to the start of the next phrase.
+    ///
+    /// Phrases come from the track's structure analysis; unanalysed tracks
+    /// fall back to 16-bar phrases counted from the grid's first downbeat.
+    /// Like beat jumps, the sub-beat phase offset is preserved.
+    pub fn phrase_jump_forward(&mut self) {
+        self.phrase_jump(true);
+    }
+
+    /// Jump back to the start of the current phrase, or to the previous one
+    /// when the playhead is within a beat of the current phrase start.
+    pub fn phrase_jump_backward(&mut self) {
+        self.phrase_jump(false);
+    }
+
+    fn phrase_jump(&mut self, forward: bool) {
+        let Some(track) = &self.track else { return };
+        let beats = &track.metadata.beat_grid.beats;
+        if beats.is_empty() {
+            return;
+        }
+        let current_idx = Self::nearest_beat_index(beats, self.position);
+        let anchor_idx = track
+            .metadata
+            .beat_grid
+            .first_beat_sample
+            .map(|first| Self::nearest_beat_index(beats, first as usize))
+            .unwrap_or(0);
+        let Some(target_beat) = Self::phrase_jump_target(
+            beats,
+            &track.metadata.phrases,
+            anchor_idx,
+            current_idx,
+            forward,
+        ) else {
+            return;
+        };
+
+        let current_beat = beats[current_idx];
+        let old_position = self.position;
+        let new_position = (self.position as i64 + target_beat as i64 - current_beat as i64).max(0) as usize;
+        self.position = match track.duration_samples.checked_sub(1) {
+            Some(dur) => new_position.min(dur),
+            None => new_position,
+        };
+        self.sync_position_atomic();
+
+        // Move an active loop along with the playhead
+        if self.loop_state.active {
+            let distance = self.position as i64 - old_position as i64;
+            let new_start = (self.loop_state.start as i64 + distance).max(0) as usize;
+            let new_end = (self.loop_state.end as i64 + distance).max(0) as usize;
+            self.loop_state.start = self.snap_to_beat(new_start);
+            self.loop_state.end = self.snap_to_beat(new_end);
+            self.sync_loop_atomic();
+        }
+    }
+
+    /// Beat position of the phrase start to jump to from beat `current_idx`
+    ///
+    /// Uses the analysed `phrases` when present, otherwise every
+    /// `FALLBACK_PHRASE_BEATS` beats counted from beat `anchor_idx`.
+    fn phrase_jump_target(
+        beats: &[u64],
+        phrases: &[PhraseSection],
+        anchor_idx: usize,
+        current_idx: usize,
+        forward: bool,
+    ) -> Option<u64> {
+        let current_beat = *beats.get(current_idx)?;
+
+        if !phrases.is_empty() {
+            return if forward {
+                phrases.iter().map(|p| p.start_sample).find(|&start| start > current_beat)
+            } else {
+                // Within a beat of a phrase start → go to the one before it
+                let threshold = beats[current_idx.saturating_sub(1)];
+                phrases.iter().map(|p| p.start_sample).rev().find(|&start| start < threshold)
+            };
+        }
+
+        let relative = current_idx as i64 - anchor_idx as i64;
+        let target_relative = if forward {
+            (relative.div_euclid(FALLBACK_PHRASE_BEATS) + 1) * FALLBACK_PHRASE_BEATS
+        } else {
+            (relative - 2).div_euclid(FALLBACK_PHRASE_BEATS) * FALLBACK_PHRASE_BEATS
+        };
+        let target_idx = anchor_idx as i64 + target_relative;
+        if target_idx < 0 {
+            return None;
+        }
+        beats.get(target_idx as usize).copied()
+    }
+
     /// Find the index of the nearest beat to a given sample position (binary search).
     ///
     /// Beat grids are sorted ascending, so we use `partition_point` (O(log n))
@@ -1489,6 +2108,9 @@ impl Deck {
         let stem_idx = stem as usize;
         if stem_idx < self.stems.len() {
             self.stems[stem_idx].multiband = MultibandHost::new(MAX_BUFFER_SIZE);
+            if self.effect_tempo > 0.0 {
+                self.stems[stem_idx].multiband.set_tempo(self.effect_tempo);
+            }
         }
     }
 
@@ -1498,9 +2120,12 @@ impl Deck {
     /// The old MultibandHost is dropped (with its effects), and the new one
     /// takes over immediately. The drop happens on the audio thread but is
     /// rare (only on preset load) so the brief allocation is acceptable.
-    pub fn swap_multiband(&mut self, stem: Stem, multiband: MultibandHost) {
+    pub fn swap_multiband(&mut self, stem: Stem, mut multiband: MultibandHost) {
         let stem_idx = stem as usize;
         if stem_idx < self.stems.len() {
+            if self.effect_tempo > 0.0 {
+                multiband.set_tempo(self.effect_tempo);
+            }
             self.stems[stem_idx].multiband = multiband;
         }
     }
@@ -1623,6 +2248,8 @@ impl Deck {
         compensator: Option<&mut LatencyCompensator>,
         deck_id: usize,
     ) {
+        self.stem_output_valid = false;
+
         let Some(track) = &self.track else {
             stretch_input.set_len_from_capacity(output_len);
             stretch_input.fill_silence();
@@ -1644,10 +2271,20 @@ impl Deck {
             // velocity_ratio: 1.0 = normal speed, 2.0 = 2x, -1.0 = reverse
             let (should_output, velocity_ratio, read_position) = self.scratch.update(output_len);
 
+            // A released platter moves on its own: the playhead follows it,
+            // and normal playback takes over once it reaches its final speed
+            let glide_finished = self.scratch.glide_done();
+            if self.scratch.is_gliding() {
+                self.position = self.scratch.current_position().min(track.duration_samples.saturating_sub(1));
+            }
+
             if !should_output {
                 // Stationary or near-stationary: output silence (like vinyl not moving)
                 stretch_input.set_len_from_capacity(output_len);
                 stretch_input.fill_silence();
+                if glide_finished {
+                    self.finish_glide();
+                }
                 return;
             }
 
@@ -1722,6 +2359,12 @@ impl Deck {
             if self.lufs_gain != 1.0 {
                 stretch_input.scale(self.lufs_gain);
             }
+            self.stem_output_valid = true;
+
+            if glide_finished {
+                self.finish_glide();
+                return;
+            }
 
             // Sync position for UI
             self.sync_position_atomic();
@@ -1735,7 +2378,8 @@ impl Deck {
         // IMPORTANT: We accumulate fractional samples to prevent drift.
         // Without this, rounding each frame loses ~0.5 samples, causing
         // ~1 second of drift per 10 minutes of playback.
-        self.fractional_position += (output_len as f64) * self.stretch_ratio;
+        self.fractional_position += (output_len as f64) * self.playback_speed();
+        self.decay_pitch_bend(output_len);
         let samples_to_read = self.fractional_position.floor() as usize;
         self.fractional_position -= samples_to_read as f64;
         let samples_to_read = samples_to_read.clamp(1, MAX_BUFFER_SIZE);
@@ -1747,7 +2391,11 @@ impl Deck {
         // Extract values needed for parallel processing (avoids borrow conflicts)
         let any_soloed = self.any_stem_soloed();
         let position = self.position;
-        let samples_per_beat = track.samples_per_beat();
+        let samples_per_beat = track
+            .metadata
+            .beat_grid
+            .beat_length_at(position as u64)
+            .unwrap_or_else(|| track.samples_per_beat_at(position as u64));
 
         // Extract linked stem buffer references and gains before parallel section
         // This avoids borrow checker issues with stem_links in the parallel closure
@@ -1911,6 +2559,7 @@ impl Deck {
         if self.lufs_gain != 1.0 {
             stretch_input.scale(self.lufs_gain);
         }
+        self.stem_output_valid = true;
 
         // Advance playhead by samples actually read (not output_len!)
         // This ensures playback speed matches the stretch ratio
@@ -2056,6 +2705,53 @@ mod tests {
         assert_eq!(deck.position(), 0);
     }
 
+    #[test]
+    fn test_track_keys_from_key_timeline() {
+        use crate::audio_file::{KeySegment, TrackMetadata};
+        use crate::music::MusicalKey;
+
+        let segment = |start, key: &str| KeySegment { start_sample: start, end_sample: start + 100, key: key.to_string() };
+        let mut metadata = TrackMetadata { key: Some("Am".to_string()), ..Default::default() };
+
+        // No timeline: intro and outro are the global key
+        let keys = TrackKeys::from_metadata(&metadata);
+        assert_eq!(keys.intro_key(), MusicalKey::parse("Am"));
+        assert_eq!(keys.outro_key(), MusicalKey::parse("Am"));
+
+        // Modulating track: A minor intro, B minor outro
+        metadata.key_timeline = vec![segment(0, "Am"), segment(100, "C"), segment(200, "Bm")];
+        let keys = TrackKeys::from_metadata(&metadata);
+        assert_eq!(keys.intro_key(), MusicalKey::parse("Am"));
+        assert_eq!(keys.outro_key(), MusicalKey::parse("Bm"));
+        assert_eq!(keys.track, MusicalKey::parse("Am"));
+    }
+
+    #[test]
+    fn test_phrase_jump_target() {
+        use crate::audio_file::SectionKind;
+
+        let beats: Vec<u64> = (0..200).map(|b| b * 24_000).collect();
+        let section = |start_beat: u64, end_beat: u64| PhraseSection {
+            start_sample: start_beat * 24_000,
+            end_sample: end_beat * 24_000,
+            kind: SectionKind::Drop,
+            bars: ((end_beat - start_beat) / 4) as u32,
+        };
+        let phrases = [section(0, 32), section(32, 96), section(96, 200)];
+
+        // Analysed phrases
+        assert_eq!(Deck::phrase_jump_target(&beats, &phrases, 0, 10, true), Some(32 * 24_000));
+        assert_eq!(Deck::phrase_jump_target(&beats, &phrases, 0, 40, false), Some(32 * 24_000));
+        assert_eq!(Deck::phrase_jump_target(&beats, &phrases, 0, 33, false), Some(0));
+        assert_eq!(Deck::phrase_jump_target(&beats, &phrases, 0, 100, true), None);
+
+        // 16-bar fallback, counted from a downbeat at beat 2
+        assert_eq!(Deck::phrase_jump_target(&beats, &[], 2, 10, true), Some(66 * 24_000));
+        assert_eq!(Deck::phrase_jump_target(&beats, &[], 2, 70, false), Some(66 * 24_000));
+        assert_eq!(Deck::phrase_jump_target(&beats, &[], 2, 67, false), Some(2 * 24_000));
+        assert_eq!(Deck::phrase_jump_target(&beats, &[], 2, 3, false), None);
+    }
+
     #[test]
     fn test_loop_state() {
         let mut loop_state = LoopState::default();
@@ -2087,4 +2783,61 @@ mod tests {
         deck.hot_cue_press(0);
         assert!(deck.hot_cue(0).is_none());
     }
+
+    #[test]
+    fn test_pitch_fader_and_keylock() {
+        let mut deck = Deck::new(DeckId::new(0));
+        assert!(deck.keylock());
+        assert_eq!(deck.tempo_factor(), 1.0);
+
+        deck.set_pitch_range(PitchRange::Sixteen);
+        deck.set_pitch_fader(0.5);
+        assert!((deck.tempo_factor() - 1.08).abs() < 1e-6);
+        assert!((deck.atomics().pitch_percent() - 8.0).abs() < 1e-4);
+        deck.set_pitch_fader(3.0);
+        assert_eq!(deck.pitch_fader(), 1.0);
+
+        // Keylock on: only the manual shift moves the pitch
+        deck.set_stretch_ratio(2.0);
+        deck.set_key_shift(-2, 25);
+        assert!((deck.pitch_shift_semitones() - -1.75).abs() < 1e-9);
+
+        // Keylock off: double speed adds an octave
+        deck.set_keylock(false);
+        assert!((deck.pitch_shift_semitones() - 10.25).abs() < 1e-9);
+
+        deck.set_key_shift(40, -90);
+        assert_eq!(deck.key_shift(), (KEY_SHIFT_MAX_SEMITONES, -KEY_SHIFT_MAX_CENTS));
+        assert_eq!(PitchRange::Fifty.next(), PitchRange::Six);
+    }
+
+    #[test]
+    fn test_jog_pitch_bend_decays() {
+        let mut deck = Deck::new(DeckId::new(0));
+        deck.set_stretch_ratio(1.2);
+        deck.nudge(0.3);
+        deck.nudge(0.3);
+        assert_eq!(deck.pitch_bend(), MAX_PITCH_BEND);
+        assert!((deck.playback_speed() - 1.8).abs() < 1e-9);
+
+        // One time constant later the bend has fallen to 1/e
+        deck.decay_pitch_bend((PITCH_BEND_DECAY_SECS * SAMPLE_RATE as f64) as usize);
+        assert!((deck.pitch_bend() - MAX_PITCH_BEND / std::f64::consts::E).abs() < 1e-3);
+
+        for _ in 0..100 {
+            deck.decay_pitch_bend(1024);
+        }
+        assert_eq!(deck.pitch_bend(), 0.0);
+    }
+
+    #[test]
+    fn test_step_key_shift_carries_cents() {
+        assert_eq!(step_key_shift((0, 0), 1, 0), (1, 0));
+        assert_eq!(step_key_shift((0, 40), 0, 10), (0, 50));
+        assert_eq!(step_key_shift((0, 50), 0, 10), (1, -40));
+        assert_eq!(step_key_shift((0, -50), 0, -10), (-1, 40));
+        assert_eq!(step_key_shift((1, -40), -1, 0), (0, -40));
+        assert_eq!(step_key_shift((12, 0), 0, 10), (12, 0));
+        assert_eq!(step_key_shift((-11, -50), -1, 0), (-12, 0));
+    }
 }
@@ -2,16 +2,18 @@
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use crate::config::LoudnessConfig;
 use crate::effect::Effect;
 use crate::db::DatabaseService;
+use crate::dvs::{DvsMode, TimecodeDecoder, TimecodeFormat};
 use crate::loader::{HostTrackParams, LinkedStemLoader, LinkedStemResultReceiver};
 use crate::music::semitones_to_match;
 use crate::timestretch::TimeStretcher;
 use crate::types::{DeckId, PlayState, Stem, StereoBuffer, StereoSample, NUM_DECKS, NUM_STEMS};
 
 use super::slicer::SlicerPreset;
-use super::{Deck, DeckAtomics, EngineCommand, LatencyCompensator, Mixer, PreparedTrack};
+use super::{Deck, DeckAtomics, EngineCommand, LatencyCompensator, LineInput, MasterClockAtomics, Mixer, NUM_LINE_INPUTS, PreparedTrack, SamplerAtomics, SamplerBank, StemCapture};
 
 /// Global BPM range
 pub const MIN_BPM: f64 = 30.0;
@@ -26,6 +28,17 @@ pub const BUFFER_SIZE: usize = 256;
 /// Pre-allocating to this size eliminates allocations in the audio callback
 pub const MAX_BUFFER_SIZE: usize = 8192;
 
+/// Stereo inputs the backends can feed
+///
+/// Inputs `0..NUM_DECKS` are the decks' timecode inputs, followed by the
+/// mixer's line inputs in [`LineInput`] order (see [`line_input_slot`]).
+pub const NUM_INPUTS: usize = NUM_DECKS + NUM_LINE_INPUTS;
+
+/// Input index a mixer line input is fed through
+pub fn line_input_slot(input: LineInput) -> usize {
+    NUM_DECKS + input.index()
+}
+
 /// The main audio engine
 ///
 /// Manages 4 decks, global BPM synchronization, latency compensation,
@@ -59,6 +72,9 @@ pub struct AudioEngine {
     frame_counter: u64,
     /// Whether phase sync is enabled (can be toggled via config)
     phase_sync_enabled: bool,
+    /// Native BPM currently applied to each deck's stretch ratio
+    /// Follows the segment under the playhead on variable-tempo grids.
+    deck_native_bpm: [f64; NUM_DECKS],
     /// Slicer presets (8 presets, each with per-stem patterns)
     /// When a preset button is pressed, patterns are loaded to all stems that have
     /// a defined pattern in the preset (others are bypassed).
@@ -89,6 +105,8 @@ pub struct AudioEngine {
     /// Internal effect chain latency in samples (global max across all stems/decks).
     /// Updated when effect chains change; read by UI for display.
     internal_latency_samples: Arc<AtomicU32>,
+    /// Master deck beat position, published every buffer for MIDI clock output
+    master_clock: Arc<MasterClockAtomics>,
 
     // ─────────────────────────────────────────────────────────────
     // Set Recording
@@ -96,6 +114,23 @@ pub struct AudioEngine {
     /// Active recording producers — master samples are pushed to each one.
     /// Backends call `retain_mut` to drop abandoned producers automatically.
     pub(crate) recording_producers: Vec<rtrb::Producer<StereoSample>>,
+    /// Per-stem bus capture for multitrack recording (None = master only).
+    /// Owned by the GC so dropping it never deallocates on the audio thread.
+    stem_capture: Option<basedrop::Owned<StemCapture>>,
+
+    // ─────────────────────────────────────────────────────────────
+    // Sampler
+    // ─────────────────────────────────────────────────────────────
+    /// One-shot / loop sample slots, mixed in as an auxiliary bus
+    sampler: SamplerBank,
+
+    // ─────────────────────────────────────────────────────────────
+    // Inputs / DVS
+    // ─────────────────────────────────────────────────────────────
+    /// Stereo inputs for this cycle, filled by the backend (empty = no input)
+    inputs: [StereoBuffer; NUM_INPUTS],
+    /// Timecode decoder per deck, reading the deck's input
+    timecode: [TimecodeDecoder; NUM_DECKS],
 }
 
 impl AudioEngine {
@@ -122,6 +157,7 @@ impl AudioEngine {
             deck_play_start: [None; NUM_DECKS],
             frame_counter: 0,
             phase_sync_enabled: true, // Enabled by default
+            deck_native_bpm: [DEFAULT_BPM; NUM_DECKS],
             // Default slicer presets (can be overwritten via SetSlicerPresets command)
             // These defaults apply patterns to drums only (for backward compatibility)
             slicer_presets: [
@@ -142,8 +178,15 @@ impl AudioEngine {
             // Output latency measurement (shared with UI)
             output_latency_samples: Arc::new(AtomicU64::new(0)),
             internal_latency_samples: Arc::new(AtomicU32::new(0)),
+            master_clock: Arc::new(MasterClockAtomics::new()),
             // Set recording
             recording_producers: Vec::new(),
+            stem_capture: None,
+            sampler: SamplerBank::new(output_sample_rate),
+            inputs: std::array::from_fn(|_| StereoBuffer::with_capacity(MAX_BUFFER_SIZE)),
+            timecode: std::array::from_fn(|_| {
+                TimecodeDecoder::new(TimecodeFormat::default(), output_sample_rate)
+            }),
         }
     }
 
@@ -220,6 +263,29 @@ impl AudioEngine {
         self.mixer.level_atomics()
     }
 
+    /// Get the sampler slot atomics (loaded/playing) for pads and LEDs
+    pub fn sampler_atomics(&self) -> Arc<SamplerAtomics> {
+        self.sampler.atomics()
+    }
+
+    /// Get the master transport clock (for MIDI clock output)
+    pub fn master_clock(&self) -> Arc<MasterClockAtomics> {
+        self.master_clock.clone()
+    }
+
+    /// Stereo input buffers for the backend to fill before `process`
+    ///
+    /// Every input is set to `n_frames` of silence for the backend to
+    /// overwrite the ones it has. A backend without inputs doesn't call
+    /// this, and empty inputs read as no signal.
+    pub fn inputs_mut(&mut self, n_frames: usize) -> &mut [StereoBuffer; NUM_INPUTS] {
+        for buffer in self.inputs.iter_mut() {
+            buffer.set_len_from_capacity(n_frames.min(MAX_BUFFER_SIZE));
+            buffer.fill_silence();
+        }
+        &mut self.inputs
+    }
+
     /// Get a reference to the mixer
     pub fn mixer(&self) -> &Mixer {
         &self.mixer
@@ -237,8 +303,9 @@ impl AudioEngine {
     /// Find the master deck (longest playing with audible volume)
     ///
     /// The master is the deck that has been playing the longest (lowest start frame)
-    /// AND has its mixer volume above zero. A faded-out deck should not drive
-    /// phase sync — the audience can't hear it, so syncing to it is wrong.
+    /// AND is audible on the master bus (volume above zero, not cut by the
+    /// crossfader). A faded-out deck should not drive phase sync — the
+    /// audience can't hear it, so syncing to it is wrong.
     ///
     /// Returns None if no deck is currently playing at audible volume.
     fn master_deck_id(&self) -> Option<usize> {
@@ -246,15 +313,48 @@ impl AudioEngine {
             .iter()
             .enumerate()
             .filter_map(|(id, start)| start.map(|s| (id, s)))
-            .filter(|(id, _)| {
-                self.mixer.channel(*id)
-                    .map(|ch| ch.volume > 0.0)
-                    .unwrap_or(false)
-            })
+            .filter(|(id, _)| self.mixer.is_audible(*id))
             .min_by_key(|(_, start)| *start)
             .map(|(id, _)| id)
     }
 
+    /// How far the master deck is through its current beat (0.0–1.0)
+    ///
+    /// Used to start sampler loops in phase. None when no deck is master
+    /// or its track has no beat grid.
+    fn master_beat_phase(&self) -> Option<f64> {
+        let master = &self.decks[self.master_deck_id()?];
+        let grid = &master.track()?.metadata.beat_grid;
+        let position = master.position();
+        let idx = grid.beats.partition_point(|&b| b <= position).checked_sub(1)?;
+        let beat_length = grid.beat_length_at(position)?;
+        Some(((position - grid.beats[idx]) as f64 / beat_length).clamp(0.0, 1.0))
+    }
+
+    /// Master deck position in beats (beat index + fraction through the beat)
+    ///
+    /// Drives MIDI clock output. None when no deck is master or its track
+    /// has no beat grid.
+    fn master_beat_position(&self) -> Option<f64> {
+        let master = &self.decks[self.master_deck_id()?];
+        let grid = &master.track()?.metadata.beat_grid;
+        let position = master.position();
+        let idx = grid.beats.partition_point(|&b| b <= position).checked_sub(1)?;
+        let beat_length = grid.beat_length_at(position)?;
+        Some(idx as f64 + ((position - grid.beats[idx]) as f64 / beat_length).clamp(0.0, 1.0))
+    }
+
+    /// Publish the master transport for MIDI clock output
+    ///
+    /// Called at the start of each buffer, before the decks advance, so the
+    /// beat position belongs to the buffer's first sample. That sample is heard
+    /// one output latency from now.
+    fn publish_master_clock(&self) {
+        let latency_samples = self.output_latency_samples.load(Ordering::Relaxed);
+        let latency = Duration::from_nanos(latency_samples * 1_000_000_000 / self.output_sample_rate.max(1) as u64);
+        self.master_clock.publish(self.master_beat_position(), self.master_tempo(), Instant::now() + latency);
+    }
+
     /// Calculate phase-locked position for a deck syncing to master
     ///
     /// When a deck starts playing or jumps while another deck is playing,
@@ -318,7 +418,14 @@ impl AudioEngine {
         // Find nearest beat to slave's target position
         let slave_nearest_beat = slave.snap_to_beat(target_position);
 
-        // Apply master's phase offset to slave
+        // Apply master's phase offset to slave, carried over as a fraction of
+        // a beat so it stays correct when the two beat lengths differ
+        // (different native BPMs or variable-tempo segments)
+        let phase_offset = Self::rescale_phase_offset(
+            phase_offset,
+            master_track.metadata.beat_grid.beat_length_at(master_nearest_beat as u64),
+            slave_track.metadata.beat_grid.beat_length_at(slave_nearest_beat as u64),
+        );
         let result = (slave_nearest_beat as i64 + phase_offset).max(0) as usize;
 
         // Clamp to track bounds
@@ -354,13 +461,29 @@ impl AudioEngine {
         let current_nearest_beat = deck.snap_to_beat(pre_jump_position);
         let phase_offset = pre_jump_position as i64 - current_nearest_beat as i64;
 
-        // Apply to target
+        // Apply to target (rescaled if the jump crossed a tempo change)
         let target_nearest_beat = deck.snap_to_beat(target_position);
+        let phase_offset = Self::rescale_phase_offset(
+            phase_offset,
+            track.metadata.beat_grid.beat_length_at(current_nearest_beat as u64),
+            track.metadata.beat_grid.beat_length_at(target_nearest_beat as u64),
+        );
         let result = (target_nearest_beat as i64 + phase_offset).max(0) as usize;
 
         result.min(track.duration_samples.saturating_sub(1))
     }
 
+    /// Convert a phase offset between two beat lengths
+    ///
+    /// The offset is kept as the same fraction of a beat. Unknown beat
+    /// lengths (sparse grids) leave the offset unchanged.
+    fn rescale_phase_offset(offset: i64, from_beat_len: Option<f64>, to_beat_len: Option<f64>) -> i64 {
+        match (from_beat_len, to_beat_len) {
+            (Some(from), Some(to)) if from > 0.0 => (offset as f64 * to / from).round() as i64,
+            _ => offset,
+        }
+    }
+
     /// Apply phase sync correction after a position jump
     ///
     /// Call this after any operation that moves a playing deck's position
@@ -427,14 +550,6 @@ impl AudioEngine {
         if let Some(d) = self.decks.get_mut(deck) {
             d.apply_prepared_track(prepared);
 
-            // Set stretch ratio for this deck based on track BPM vs global BPM
-            let ratio = self.global_bpm / track_bpm;
-            log::debug!(
-                "Track loaded on deck {}: track_bpm={:.2}, global_bpm={:.2}, ratio={:.4}",
-                deck, track_bpm, self.global_bpm, ratio
-            );
-            d.set_stretch_ratio(ratio);
-
             // ─────────────────────────────────────────────────────────────
             // Auto-apply LUFS gain compensation
             // ─────────────────────────────────────────────────────────────
@@ -457,10 +572,16 @@ impl AudioEngine {
                     deck
                 );
             }
-        }
 
-        // Update time stretcher for this deck's BPM
-        self.stretchers[deck].set_bpm(track_bpm, self.global_bpm);
+            // Set stretch ratio for this deck based on the native BPM at the
+            // playhead (the first segment for variable-tempo grids) vs global BPM
+            let native_bpm = d.local_bpm().unwrap_or(track_bpm);
+            self.apply_deck_tempo(deck, native_bpm);
+            log::debug!(
+                "Track loaded on deck {}: track_bpm={:.2}, native_bpm={:.2}, global_bpm={:.2}, ratio={:.4}",
+                deck, track_bpm, native_bpm, self.global_bpm, self.decks[deck].stretch_ratio()
+            );
+        }
 
         // Clear latency compensation buffers for this deck
         self.latency_compensator.clear_deck(deck);
@@ -505,20 +626,45 @@ impl AudioEngine {
         self.global_bpm = bpm.clamp(MIN_BPM, MAX_BPM);
 
         // Update all deck stretch ratios and stretchers
-        for (i, deck) in self.decks.iter_mut().enumerate() {
-            if let Some(track) = deck.track() {
-                let track_bpm = track.bpm();
-                let ratio = self.global_bpm / track_bpm;
+        for i in 0..NUM_DECKS {
+            if let Some(track_bpm) = self.decks[i].local_bpm() {
+                self.apply_deck_tempo(i, track_bpm);
                 log::debug!(
                     "BPM changed - deck {}: track_bpm={:.2}, global_bpm={:.2}, ratio={:.4}",
-                    i, track_bpm, self.global_bpm, ratio
+                    i, track_bpm, self.global_bpm, self.decks[i].stretch_ratio()
                 );
-                deck.set_stretch_ratio(ratio);
-                self.stretchers[i].set_bpm(track_bpm, self.global_bpm);
             }
         }
     }
 
+    /// Set a deck's stretch ratio from the global BPM and its pitch fader
+    ///
+    /// The synced ratio (`global_bpm / native_bpm`) is scaled by the deck's
+    /// tempo factor, so a centred fader plays exactly at the global BPM.
+    fn apply_deck_tempo(&mut self, deck: usize, native_bpm: f64) {
+        let ratio = TimeStretcher::ratio_from_bpm(native_bpm, self.global_bpm)
+            * self.decks[deck].tempo_factor();
+        self.decks[deck].set_stretch_ratio(ratio);
+        self.stretchers[deck].set_ratio(ratio);
+        self.deck_native_bpm[deck] = native_bpm;
+    }
+
+    /// Re-derive a deck's stretch ratio after its pitch fader changed
+    fn refresh_deck_tempo(&mut self, deck: usize) {
+        if let Some(native_bpm) = self.decks[deck].local_bpm() {
+            self.apply_deck_tempo(deck, native_bpm);
+        }
+    }
+
+    /// Tempo the master deck plays at, including its pitch fader
+    /// (global BPM when no deck is master)
+    fn master_tempo(&self) -> f64 {
+        match self.master_deck_id() {
+            Some(id) => self.global_bpm * self.decks[id].tempo_factor(),
+            None => self.global_bpm,
+        }
+    }
+
     /// Get the global BPM
     pub fn global_bpm(&self) -> f64 {
         self.global_bpm
@@ -751,6 +897,40 @@ impl AudioEngine {
                     }
                 }
 
+                // Jog Wheel
+                EngineCommand::JogTouch { deck, touched, release } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.jog_touch(touched, release);
+                    }
+                }
+                EngineCommand::JogTurn { deck, revolutions } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.jog_turn(revolutions as f64);
+                    }
+                }
+                EngineCommand::Nudge { deck, amount } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.nudge(amount as f64);
+                    }
+                }
+                EngineCommand::SetVinylMode { deck, enabled } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.set_vinyl_mode(enabled);
+                    }
+                }
+
+                // DVS
+                EngineCommand::SetDvsMode { deck, mode } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.set_dvs_mode(mode);
+                    }
+                }
+                EngineCommand::SetTimecodeFormat { format } => {
+                    for decoder in &mut self.timecode {
+                        decoder.set_format(format);
+                    }
+                }
+
                 // CDJ-Style Cueing (with inter-deck phase sync on preview)
                 EngineCommand::CuePress { deck } => {
                     if deck < NUM_DECKS {
@@ -889,6 +1069,16 @@ impl AudioEngine {
                         self.decks[deck].beat_jump_backward();
                     }
                 }
+                EngineCommand::PhraseJumpForward { deck } => {
+                    if deck < NUM_DECKS {
+                        self.decks[deck].phrase_jump_forward();
+                    }
+                }
+                EngineCommand::PhraseJumpBackward { deck } => {
+                    if deck < NUM_DECKS {
+                        self.decks[deck].phrase_jump_backward();
+                    }
+                }
                 EngineCommand::SetBeatGrid { deck, beats } => {
                     if let Some(d) = self.decks.get_mut(deck) {
                         d.set_beat_grid(beats);
@@ -922,6 +1112,28 @@ impl AudioEngine {
                         d.set_key_match_enabled(enabled);
                     }
                 }
+                EngineCommand::SetPitchFader { deck, value } => {
+                    if deck < NUM_DECKS {
+                        self.decks[deck].set_pitch_fader(value);
+                        self.refresh_deck_tempo(deck);
+                    }
+                }
+                EngineCommand::SetPitchRange { deck, range } => {
+                    if deck < NUM_DECKS {
+                        self.decks[deck].set_pitch_range(range);
+                        self.refresh_deck_tempo(deck);
+                    }
+                }
+                EngineCommand::SetKeylock { deck, enabled } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.set_keylock(enabled);
+                    }
+                }
+                EngineCommand::SetKeyShift { deck, semitones, cents } => {
+                    if let Some(d) = self.decks.get_mut(deck) {
+                        d.set_key_shift(semitones, cents);
+                    }
+                }
                 EngineCommand::SetTrackKey { deck, key } => {
                     if let Some(d) = self.decks.get_mut(deck) {
                         let parsed_key = key.as_ref().and_then(|k| crate::music::MusicalKey::parse(k));
@@ -1301,8 +1513,22 @@ impl AudioEngine {
                     // when a faded-out deck is brought back up.
                     self.sync_master_atomics();
                 }
-                EngineCommand::SetCrossfader { position: _ } => {
-                    // TODO: Crossfader not yet implemented in mixer
+                EngineCommand::SetCrossfader { position } => {
+                    self.mixer.set_crossfader(position);
+                    // A deck cut by the crossfader is inaudible — re-evaluate master
+                    self.sync_master_atomics();
+                }
+                EngineCommand::SetCrossfaderAssign { deck, assign } => {
+                    self.mixer.set_crossfader_assign(deck, assign);
+                    self.sync_master_atomics();
+                }
+                EngineCommand::SetCrossfaderCurve { curve } => {
+                    self.mixer.set_crossfader_curve(curve);
+                    self.sync_master_atomics();
+                }
+                EngineCommand::SetCrossfaderReverse { enabled } => {
+                    self.mixer.set_crossfader_reverse(enabled);
+                    self.sync_master_atomics();
                 }
                 EngineCommand::SetCueListen { deck, enabled } => {
                     if let Some(ch) = self.mixer.channel_mut(deck) {
@@ -1324,6 +1550,19 @@ impl AudioEngine {
                         ch.set_eq_lo(value);
                     }
                 }
+                EngineCommand::SetEqHiMid { deck, value } => {
+                    if let Some(ch) = self.mixer.channel_mut(deck) {
+                        ch.set_eq_hi_mid(value);
+                    }
+                }
+                EngineCommand::SetEqModel { deck, model } => {
+                    if let Some(ch) = self.mixer.channel_mut(deck) {
+                        ch.set_eq_model(model);
+                    }
+                }
+                EngineCommand::SetEqCrossovers { crossovers } => {
+                    self.mixer.set_eq_crossovers(&crossovers);
+                }
                 EngineCommand::SetFilter { deck, value } => {
                     if let Some(ch) = self.mixer.channel_mut(deck) {
                         ch.filter = value;
@@ -1342,6 +1581,57 @@ impl AudioEngine {
                     self.mixer.set_auto_cue(enabled);
                 }
 
+                // Line Inputs
+                EngineCommand::SetLineVolume { input, volume } => {
+                    self.mixer.line_channel_mut(input).volume = volume.clamp(0.0, 1.0);
+                }
+                EngineCommand::SetLineTrim { input, db } => {
+                    self.mixer.line_channel_mut(input).set_trim_db(db);
+                }
+                EngineCommand::SetLineEqHi { input, value } => {
+                    self.mixer.line_channel_mut(input).set_eq_hi(value);
+                }
+                EngineCommand::SetLineEqMid { input, value } => {
+                    self.mixer.line_channel_mut(input).set_eq_mid(value);
+                }
+                EngineCommand::SetLineEqLo { input, value } => {
+                    self.mixer.line_channel_mut(input).set_eq_lo(value);
+                }
+                EngineCommand::SetLineCue { input, enabled } => {
+                    self.mixer.line_channel_mut(input).cue_enabled = enabled;
+                }
+                EngineCommand::SetTalkover { input, enabled } => {
+                    self.mixer.set_talkover(input, enabled);
+                }
+                EngineCommand::SetTalkoverDepth { db } => {
+                    self.mixer.set_talkover_depth_db(db);
+                }
+
+                // Mixer FX Buses
+                EngineCommand::AddBusEffect { bus, effect } => {
+                    if self.mixer.fx_chain_mut(bus).add_effect(effect).is_none() {
+                        log::warn!("{} chain is full, effect dropped", bus.label());
+                    }
+                }
+                EngineCommand::RemoveBusEffect { bus, effect_index } => {
+                    self.mixer.fx_chain_mut(bus).remove_effect(effect_index);
+                }
+                EngineCommand::SetBusEffectParam { bus, effect_index, param_index, value } => {
+                    self.mixer.fx_chain_mut(bus).set_effect_param(effect_index, param_index, value);
+                }
+                EngineCommand::SetBusEffectBypass { bus, effect_index, bypass } => {
+                    self.mixer.fx_chain_mut(bus).set_effect_bypass(effect_index, bypass);
+                }
+                EngineCommand::SetBusEnabled { bus, enabled } => {
+                    self.mixer.fx_chain_mut(bus).set_enabled(enabled);
+                }
+                EngineCommand::SetBusReturn { bus, level } => {
+                    self.mixer.fx_chain_mut(bus).set_return_level(level);
+                }
+                EngineCommand::SetFxSend { deck, send, level } => {
+                    self.mixer.set_send_level(deck, send, level);
+                }
+
                 // Loudness Compensation
                 EngineCommand::SetLufsGain { deck, gain, host_lufs } => {
                     if let Some(d) = self.decks.get_mut(deck) {
@@ -1383,11 +1673,62 @@ impl AudioEngine {
                     self.recording_producers.push(*producer);
                     log::info!("[ENGINE] Recording started ({} active)", self.recording_producers.len());
                 }
+                EngineCommand::StartStemRecording { capture } => {
+                    // Replaced capture (if any) is freed on the GC thread
+                    self.stem_capture = Some(capture);
+                    log::info!("[ENGINE] Stem recording started");
+                }
                 EngineCommand::StopRecording => {
                     let count = self.recording_producers.len();
                     self.recording_producers.clear();
+                    self.stem_capture = None;
                     log::info!("[ENGINE] Recording stopped ({count} producers dropped)");
                 }
+
+                // Sampler
+                EngineCommand::LoadSamplerSlot { slot, clip } => {
+                    self.sampler.load(slot, clip);
+                }
+                EngineCommand::SamplerTrigger { slot } => {
+                    let phase = self.master_beat_phase();
+                    self.sampler.trigger(slot, phase);
+                }
+                EngineCommand::SamplerStop { slot } => {
+                    self.sampler.stop(slot);
+                }
+                EngineCommand::SamplerStopAll => {
+                    self.sampler.stop_all();
+                }
+                EngineCommand::SetSamplerVolume { slot, volume } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.volume = volume.clamp(0.0, 1.0);
+                    }
+                }
+                EngineCommand::SetSamplerEqHi { slot, value } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.set_eq_hi(value);
+                    }
+                }
+                EngineCommand::SetSamplerEqMid { slot, value } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.set_eq_mid(value);
+                    }
+                }
+                EngineCommand::SetSamplerEqLo { slot, value } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.set_eq_lo(value);
+                    }
+                }
+                EngineCommand::SetSamplerFilter { slot, value } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.filter = value;
+                    }
+                }
+                EngineCommand::SetSamplerCue { slot, enabled } => {
+                    if let Some(ch) = self.sampler.channel_mut(slot) {
+                        ch.cue_enabled = enabled;
+                    }
+                }
             }
         }
     }
@@ -1408,32 +1749,43 @@ impl AudioEngine {
         // Increment frame counter for phase sync tracking
         self.frame_counter = self.frame_counter.wrapping_add(1);
 
+        // MIDI clock follows the master deck from the audio thread's timing
+        self.publish_master_clock();
+
         // Update key matching transposition for each deck
-        // The master deck is the one that has been playing longest
+        // The master deck is the one that has been playing longest. Matching
+        // pairs the key at the master's outro with the key at the incoming
+        // deck's intro (the global key when there is no key timeline).
         let master_id = self.master_deck_id();
-        let master_key = master_id.and_then(|id| self.decks[id].track_key());
+        let master_key = master_id.and_then(|id| self.decks[id].outro_key());
 
         for (i, deck) in self.decks.iter_mut().enumerate() {
             if deck.key_match_enabled() && Some(i) != master_id {
                 // Slave deck with key matching enabled: transpose to match master
-                if let (Some(deck_key), Some(master_key)) = (deck.track_key(), master_key) {
-                    let semitones = semitones_to_match(&deck_key, &master_key);
-                    deck.set_current_transpose(semitones);
-                    self.stretchers[i].set_pitch_semitones(semitones as f64);
+                if let (Some(deck_key), Some(master_key)) = (deck.intro_key(), master_key) {
+                    deck.set_current_transpose(semitones_to_match(&deck_key, &master_key));
                 } else {
                     // Missing key info: reset to no transpose
                     deck.set_current_transpose(0);
-                    self.stretchers[i].set_pitch_semitones(0.0);
                 }
             } else {
                 // Master deck or key matching disabled: no transpose
                 deck.set_current_transpose(0);
-                self.stretchers[i].set_pitch_semitones(0.0);
             }
+            // Manual key shift and non-keylocked tempo ride on top of key matching
+            self.stretchers[i].set_pitch_semitones(deck.pitch_shift_semitones());
         }
 
         let output_len = master_out.len();
 
+        // DVS: decode each following deck's timecode input and move its platter
+        for deck_idx in 0..NUM_DECKS {
+            if self.decks[deck_idx].dvs_mode() != DvsMode::Off {
+                let reading = self.timecode[deck_idx].process(self.inputs[deck_idx].as_slice());
+                self.decks[deck_idx].follow_timecode(&reading, output_len);
+            }
+        }
+
         // Set working length of deck output buffers (real-time safe: no allocation)
         // Capacity remains at MAX_BUFFER_SIZE, only the length field changes
         for buf in &mut self.deck_buffers {
@@ -1442,6 +1794,16 @@ impl AudioEngine {
 
         // Process each deck with per-stem latency compensation and time stretching
         for deck_idx in 0..NUM_DECKS {
+            // Follow tempo changes on variable-tempo grids: when the playhead
+            // crosses into a new segment, re-derive the stretch ratio so the
+            // deck keeps playing at the global BPM
+            if let Some(native_bpm) = self.decks[deck_idx].local_bpm() {
+                if native_bpm != self.deck_native_bpm[deck_idx] {
+                    self.apply_deck_tempo(deck_idx, native_bpm);
+                }
+            }
+            self.decks[deck_idx].set_effect_tempo(self.deck_native_bpm[deck_idx]);
+
             // Deck fills stretch_input with variable samples based on stretch_ratio
             // The deck reads output_len * stretch_ratio samples from the track
             self.decks[deck_idx].process(
@@ -1475,8 +1837,43 @@ impl AudioEngine {
             self.sync_master_atomics();
         }
 
-        // Mix deck outputs to master and cue
-        self.mixer.process(&mut self.deck_buffers, master_out, cue_out);
+        // Render the sampler bus (time-stretched to the global BPM)
+        let sampler_active = self.sampler.is_active();
+        if sampler_active {
+            self.sampler.process(output_len, self.global_bpm);
+        }
+
+        // Beat-synced effects on the FX buses follow the master deck
+        self.mixer.set_fx_tempo(self.master_tempo());
+
+        // Mix deck outputs, line inputs and the sampler bus to master and cue
+        let aux = sampler_active.then(|| (self.sampler.bus(), self.sampler.cue_bus()));
+        let (_, line_inputs) = self.inputs.split_at_mut(NUM_DECKS);
+        self.mixer.process_with_inputs(&mut self.deck_buffers, line_inputs, aux, master_out, cue_out);
+        // Inputs are consumed once per cycle
+        for input in &mut self.inputs {
+            input.truncate(0);
+        }
+
+        // Multitrack recording: stretch each deck's stems and sum them into
+        // the per-stem buses with the same fader gains the mixer just applied
+        if let Some(capture) = self.stem_capture.as_deref_mut() {
+            capture.begin(output_len);
+            for deck_idx in 0..NUM_DECKS {
+                let deck = &self.decks[deck_idx];
+                let gain = deck.lufs_gain() * self.mixer.channel_master_gain(deck_idx);
+                capture.add_deck(
+                    deck_idx,
+                    deck.stem_output(),
+                    self.stretchers[deck_idx].pitch_semitones(),
+                    gain,
+                );
+            }
+            if !capture.finish() {
+                // All stem recording threads exited — hand the capture to the GC
+                self.stem_capture = None;
+            }
+        }
     }
 
     /// Reset all decks and the mixer
@@ -1493,6 +1890,7 @@ impl AudioEngine {
         }
         self.latency_compensator.clear();
         self.mixer.reset();
+        self.sampler.reset();
     }
 }
 
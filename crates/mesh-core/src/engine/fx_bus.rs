@@ -0,0 +1,315 @@
+//! Mixer effect buses - master insert chain and aux send/return buses
+//!
+//! Besides the per-stem multiband hosts, the mixer has three effect chains
+//! that hold any [`Effect`] (native, PD or CLAP):
+//!
+//! - **FX 1 / FX 2**: send/return buses. Each deck channel sends a
+//!   post-fader copy of its signal at its own send level; the chain output
+//!   comes back into master at the bus return level. Effects on a send bus
+//!   should run 100% wet.
+//! - **Master**: insert chain on the summed master bus, before master
+//!   volume, clipper and limiter.
+//!
+//! Chains keep running while nothing is sent, so reverb and delay tails
+//! ring out after a send is closed. Effect latency on these chains is not
+//! compensated.
+
+use crate::effect::{Effect, EffectInfo};
+use crate::types::StereoBuffer;
+
+/// Number of send/return buses
+pub const NUM_FX_SENDS: usize = 2;
+
+/// Maximum number of effects in one bus chain
+pub const MAX_BUS_EFFECTS: usize = 8;
+
+/// Tempo changes smaller than this are not forwarded to bus effects
+const TEMPO_EPSILON: f64 = 0.01;
+
+/// One of the mixer's effect chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FxBus {
+    /// Send/return bus 1
+    Send1,
+    /// Send/return bus 2
+    Send2,
+    /// Insert chain on the master bus
+    Master,
+}
+
+impl FxBus {
+    /// All buses, in display order
+    pub const ALL: [FxBus; 3] = [FxBus::Send1, FxBus::Send2, FxBus::Master];
+
+    /// Bus index (0 = FX 1, 1 = FX 2, 2 = master)
+    pub fn index(self) -> usize {
+        match self {
+            FxBus::Send1 => 0,
+            FxBus::Send2 => 1,
+            FxBus::Master => 2,
+        }
+    }
+
+    /// Bus for an index
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// Send slot of a send/return bus (`None` for the master insert)
+    pub fn send_index(self) -> Option<usize> {
+        match self {
+            FxBus::Send1 => Some(0),
+            FxBus::Send2 => Some(1),
+            FxBus::Master => None,
+        }
+    }
+
+    /// Short label for the mixer
+    pub fn label(self) -> &'static str {
+        match self {
+            FxBus::Send1 => "FX 1",
+            FxBus::Send2 => "FX 2",
+            FxBus::Master => "MASTER FX",
+        }
+    }
+}
+
+/// Chain of effects on a mixer bus
+pub struct FxChain {
+    /// Effects in processing order
+    effects: Vec<Box<dyn Effect>>,
+    /// Chain on/off (off = the bus passes nothing back / master stays dry)
+    enabled: bool,
+    /// Return level into master (send buses only, 0.0 - 1.0)
+    return_level: f32,
+    /// Tempo last forwarded to the effects
+    tempo: f64,
+}
+
+impl FxChain {
+    /// Create an empty, enabled chain at full return level
+    pub fn new() -> Self {
+        Self {
+            effects: Vec::with_capacity(MAX_BUS_EFFECTS),
+            enabled: true,
+            return_level: 1.0,
+            tempo: 0.0,
+        }
+    }
+
+    /// Number of effects in the chain
+    pub fn effect_count(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Info of an effect in the chain
+    pub fn effect_info(&self, index: usize) -> Option<&EffectInfo> {
+        self.effects.get(index).map(|e| e.info())
+    }
+
+    /// Append an effect; returns its index, or `None` if the chain is full
+    ///
+    /// The effect picks up the chain's current tempo.
+    pub fn add_effect(&mut self, mut effect: Box<dyn Effect>) -> Option<usize> {
+        if self.effects.len() >= MAX_BUS_EFFECTS {
+            return None;
+        }
+        if self.tempo > 0.0 {
+            effect.set_tempo(self.tempo);
+        }
+        self.effects.push(effect);
+        Some(self.effects.len() - 1)
+    }
+
+    /// Remove an effect, returning it
+    pub fn remove_effect(&mut self, index: usize) -> Option<Box<dyn Effect>> {
+        (index < self.effects.len()).then(|| self.effects.remove(index))
+    }
+
+    /// Set a parameter of an effect (normalized 0.0 - 1.0)
+    pub fn set_effect_param(&mut self, index: usize, param_index: usize, value: f32) {
+        if let Some(effect) = self.effects.get_mut(index) {
+            effect.set_param(param_index, value);
+        }
+    }
+
+    /// Bypass an effect
+    pub fn set_effect_bypass(&mut self, index: usize, bypass: bool) {
+        if let Some(effect) = self.effects.get_mut(index) {
+            effect.set_bypass(bypass);
+        }
+    }
+
+    /// Turn the whole chain on or off
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether the chain is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether the chain has anything to process
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.effects.is_empty()
+    }
+
+    /// Set the return level into master (0.0 - 1.0)
+    pub fn set_return_level(&mut self, level: f32) {
+        self.return_level = level.clamp(0.0, 1.0);
+    }
+
+    /// Return level into master
+    pub fn return_level(&self) -> f32 {
+        self.return_level
+    }
+
+    /// Forward the master tempo to the effects (skipped when unchanged)
+    pub fn set_tempo(&mut self, bpm: f64) {
+        if (bpm - self.tempo).abs() < TEMPO_EPSILON {
+            return;
+        }
+        self.tempo = bpm;
+        for effect in &mut self.effects {
+            effect.set_tempo(bpm);
+        }
+    }
+
+    /// Run the buffer through every effect in order
+    pub fn process(&mut self, buffer: &mut StereoBuffer) {
+        if !self.enabled {
+            return;
+        }
+        for effect in &mut self.effects {
+            if !effect.is_bypassed() {
+                effect.process(buffer);
+            }
+        }
+    }
+
+    /// Clear effect state (delay lines, reverb tails)
+    pub fn reset(&mut self) {
+        for effect in &mut self.effects {
+            effect.reset();
+        }
+    }
+}
+
+impl Default for FxChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::native::GainEffect;
+    use crate::engine::Mixer;
+    use crate::types::{StereoSample, NUM_DECKS};
+
+    /// Buffer length, longer than the limiter lookahead
+    const LEN: usize = 256;
+
+    /// DC buffer of `LEN` samples at `level`
+    fn dc(level: f32) -> StereoBuffer {
+        let mut buf = StereoBuffer::silence(LEN);
+        for s in buf.iter_mut() {
+            *s = StereoSample::new(level, level);
+        }
+        buf
+    }
+
+    /// Run one mixer pass with 0.2 DC on deck 1; returns the settled master level
+    fn master_level(mixer: &mut Mixer) -> f32 {
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|i| {
+            if i == 0 { dc(0.2) } else { StereoBuffer::silence(LEN) }
+        });
+        let mut master = StereoBuffer::silence(LEN);
+        let mut cue = StereoBuffer::silence(LEN);
+        mixer.process(&mut decks, &mut master, &mut cue);
+        master[LEN - 1].left
+    }
+
+    /// Mixer with a unity-gain effect on FX 1, at half master volume to stay
+    /// below the clipper and limiter
+    fn mixer_with_send_effect() -> Mixer {
+        let mut mixer = Mixer::new();
+        mixer.set_master_volume(0.5);
+        mixer.fx_chain_mut(FxBus::Send1).add_effect(Box::new(GainEffect::new()));
+        mixer
+    }
+
+    #[test]
+    fn test_send_level_zero_and_full() {
+        let mut mixer = mixer_with_send_effect();
+
+        // Closed send: dry deck only
+        mixer.set_send_level(0, 0, 0.0);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.1).abs() < 1e-3, "got {}", level);
+
+        // Full send: the bus returns a second copy of the deck
+        mixer.set_send_level(0, 0, 1.0);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.2).abs() < 1e-3, "got {}", level);
+
+        // The other bus stays out of it
+        mixer.set_send_level(0, 0, 0.0);
+        mixer.set_send_level(0, 1, 1.0);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.1).abs() < 1e-3, "got {}", level);
+    }
+
+    #[test]
+    fn test_return_level_scales_bus_into_master() {
+        let mut mixer = mixer_with_send_effect();
+        mixer.set_send_level(0, 0, 1.0);
+
+        mixer.fx_chain_mut(FxBus::Send1).set_return_level(0.5);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.15).abs() < 1e-3, "got {}", level);
+
+        mixer.fx_chain_mut(FxBus::Send1).set_return_level(0.0);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.1).abs() < 1e-3, "got {}", level);
+
+        // A disabled bus returns nothing whatever its level
+        let chain = mixer.fx_chain_mut(FxBus::Send1);
+        chain.set_return_level(1.0);
+        chain.set_enabled(false);
+        let level = master_level(&mut mixer);
+        assert!((level - 0.1).abs() < 1e-3, "got {}", level);
+    }
+
+    #[test]
+    fn test_remove_effect_from_running_chain() {
+        let mut chain = FxChain::new();
+        chain.add_effect(Box::new(GainEffect::new()));
+        assert_eq!(chain.add_effect(Box::new(GainEffect::new())), Some(1));
+        chain.set_effect_param(1, 0, 0.0);
+
+        let mut buffer = dc(0.5);
+        chain.process(&mut buffer);
+        assert_eq!(buffer.peak(), 0.0);
+
+        // Removing the muting effect lets the rest of the chain through
+        let removed = chain.remove_effect(1).unwrap();
+        assert_eq!(removed.info().name, "Gain");
+        assert_eq!(chain.effect_count(), 1);
+        assert!(chain.remove_effect(1).is_none());
+        let mut buffer = dc(0.5);
+        chain.process(&mut buffer);
+        assert!((buffer.peak() - 0.5).abs() < 1e-6);
+
+        // Emptying a send bus mid-set turns its return off
+        let mut mixer = mixer_with_send_effect();
+        mixer.set_send_level(0, 0, 1.0);
+        assert!((master_level(&mut mixer) - 0.2).abs() < 1e-3);
+        assert!(mixer.fx_chain_mut(FxBus::Send1).remove_effect(0).is_some());
+        assert!(!mixer.fx_chain(FxBus::Send1).is_active());
+        let level = master_level(&mut mixer);
+        assert!((level - 0.1).abs() < 1e-3, "got {}", level);
+    }
+}
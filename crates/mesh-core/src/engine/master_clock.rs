@@ -0,0 +1,129 @@
+//! Master transport clock shared with MIDI clock output
+//!
+//! The audio thread publishes where the master deck is in its beat grid at
+//! the start of every buffer, stamped with the time that buffer reaches the
+//! speakers. Readers on other threads (MIDI clock output) extrapolate from the
+//! latest snapshot, so clock timing follows the audio callback rather than the
+//! UI tick.
+//!
+//! Fields are published under a sequence lock: the writer never blocks and a
+//! reader retries if it raced a publish, so a snapshot is always consistent.
+
+use std::sync::atomic::{fence, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// `beat` value marking "no master deck / no beat grid"
+const NO_BEAT: u64 = u64::MAX;
+
+/// A consistent view of the master transport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MasterClock {
+    /// Beat position of the master deck (beat index + fraction), None when
+    /// no deck is master or its track has no beat grid
+    pub beat: Option<f64>,
+    /// Effective master tempo in BPM
+    pub bpm: f64,
+    /// When `beat` is heard at the output
+    pub at: Instant,
+}
+
+/// Lock-free master clock published by the audio thread
+pub struct MasterClockAtomics {
+    /// Reference point for `at_nanos`
+    epoch: Instant,
+    /// Sequence counter (odd while a publish is in progress, 0 = never published)
+    seq: AtomicU32,
+    /// Beat position as f64 bits (`NO_BEAT` = none)
+    beat: AtomicU64,
+    /// Tempo as f64 bits
+    bpm: AtomicU64,
+    /// Output time of `beat` in nanoseconds since `epoch`
+    at_nanos: AtomicU64,
+}
+
+impl MasterClockAtomics {
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            seq: AtomicU32::new(0),
+            beat: AtomicU64::new(NO_BEAT),
+            bpm: AtomicU64::new(0),
+            at_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Publish a new snapshot (audio thread only — single writer)
+    pub fn publish(&self, beat: Option<f64>, bpm: f64, at: Instant) {
+        let seq = self.seq.load(Ordering::Relaxed);
+        self.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.beat.store(beat.map_or(NO_BEAT, f64::to_bits), Ordering::Relaxed);
+        self.bpm.store(bpm.to_bits(), Ordering::Relaxed);
+        let nanos = at.saturating_duration_since(self.epoch).as_nanos() as u64;
+        self.at_nanos.store(nanos, Ordering::Relaxed);
+
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Read the latest snapshot, or None if the audio thread hasn't published yet
+    pub fn read(&self) -> Option<MasterClock> {
+        loop {
+            let before = self.seq.load(Ordering::Acquire);
+            if before == 0 {
+                return None;
+            }
+            if before % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let beat = self.beat.load(Ordering::Relaxed);
+            let bpm = f64::from_bits(self.bpm.load(Ordering::Relaxed));
+            let at_nanos = self.at_nanos.load(Ordering::Relaxed);
+
+            fence(Ordering::Acquire);
+            if self.seq.load(Ordering::Relaxed) == before {
+                return Some(MasterClock {
+                    beat: (beat != NO_BEAT).then(|| f64::from_bits(beat)),
+                    bpm,
+                    at: self.epoch + Duration::from_nanos(at_nanos),
+                });
+            }
+        }
+    }
+}
+
+impl Default for MasterClockAtomics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpublished_reads_none() {
+        assert_eq!(MasterClockAtomics::new().read(), None);
+    }
+
+    #[test]
+    fn test_publish_roundtrip() {
+        let clock = MasterClockAtomics::new();
+        let at = Instant::now() + Duration::from_millis(12);
+
+        clock.publish(Some(33.25), 174.0, at);
+        let snapshot = clock.read().unwrap();
+        assert_eq!(snapshot.beat, Some(33.25));
+        assert_eq!(snapshot.bpm, 174.0);
+        assert!(snapshot.at.duration_since(at) < Duration::from_micros(1));
+        assert!(at.duration_since(snapshot.at) < Duration::from_micros(1));
+
+        clock.publish(None, 128.0, at);
+        let snapshot = clock.read().unwrap();
+        assert_eq!(snapshot.beat, None);
+        assert_eq!(snapshot.bpm, 128.0);
+    }
+}
@@ -1,18 +1,26 @@
 //! Mixer - Combines deck outputs with volume/filter/cue controls
 //!
 //! Features:
-//! - Per-channel trim, 3-band EQ, filter, volume, cue
+//! - Per-channel trim, EQ (classic, isolator or 4-band), filter, volume, cue
+//! - Crossfader with per-channel A/B/thru assignment and selectable curve
 //! - Master volume and cue/master blend
+//! - Auxiliary post-fader bus input (sampler)
+//! - Mic and aux line input channels with talkover ducking of the decks
+//! - Two post-fader send/return FX buses and a master insert FX chain
 
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
 use rayon::prelude::*;
 
+use super::channel_eq::ChannelEq;
+use super::fx_bus::{FxBus, FxChain, NUM_FX_SENDS};
 use super::master_clipper::MasterClipper;
 use super::master_limiter::MasterLimiter;
+use crate::config::{CrossfaderAssign, CrossfaderConfig, CrossfaderCurve, EqCrossovers, EqModel};
 use crate::effect::native::svf::SvfFilter;
 use crate::types::{StereoBuffer, StereoSample, NUM_DECKS, SAMPLE_RATE};
+use super::MAX_BUFFER_SIZE;
 
 /// Lock-free peak level atomics exposed to the UI thread for metering.
 ///
@@ -21,6 +29,8 @@ use crate::types::{StereoBuffer, StereoSample, NUM_DECKS, SAMPLE_RATE};
 pub struct LevelAtomics {
     /// Per-channel post-fader peak (f32 bits, linear 0.0..~2.0)
     pub channel_peaks: [AtomicU32; NUM_DECKS],
+    /// Per-line-input post-fader peak (f32 bits, linear 0.0..~2.0)
+    pub line_peaks: [AtomicU32; NUM_LINE_INPUTS],
     /// Master bus peak after clipper/limiter (f32 bits, linear 0.0..~2.0)
     pub master_peak: AtomicU32,
 }
@@ -29,6 +39,7 @@ impl LevelAtomics {
     pub fn new() -> Self {
         Self {
             channel_peaks: std::array::from_fn(|_| AtomicU32::new(0.0f32.to_bits())),
+            line_peaks: std::array::from_fn(|_| AtomicU32::new(0.0f32.to_bits())),
             master_peak: AtomicU32::new(0.0f32.to_bits()),
         }
     }
@@ -37,6 +48,10 @@ impl LevelAtomics {
         f32::from_bits(self.channel_peaks[deck].load(Ordering::Relaxed))
     }
 
+    pub fn line_peak(&self, input: LineInput) -> f32 {
+        f32::from_bits(self.line_peaks[input.index()].load(Ordering::Relaxed))
+    }
+
     pub fn master_peak(&self) -> f32 {
         f32::from_bits(self.master_peak.load(Ordering::Relaxed))
     }
@@ -46,114 +61,6 @@ impl Default for LevelAtomics {
     fn default() -> Self { Self::new() }
 }
 
-/// Biquad filter state for EQ bands
-#[derive(Debug, Clone, Default)]
-struct BiquadState {
-    x1_l: f32, x2_l: f32, y1_l: f32, y2_l: f32,
-    x1_r: f32, x2_r: f32, y1_r: f32, y2_r: f32,
-}
-
-impl BiquadState {
-    fn process(&mut self, input_l: f32, input_r: f32, coeffs: &BiquadCoeffs) -> (f32, f32) {
-        // Left channel
-        let out_l = coeffs.b0 * input_l + coeffs.b1 * self.x1_l + coeffs.b2 * self.x2_l
-                  - coeffs.a1 * self.y1_l - coeffs.a2 * self.y2_l;
-        self.x2_l = self.x1_l;
-        self.x1_l = input_l;
-        self.y2_l = self.y1_l;
-        self.y1_l = out_l;
-
-        // Right channel
-        let out_r = coeffs.b0 * input_r + coeffs.b1 * self.x1_r + coeffs.b2 * self.x2_r
-                  - coeffs.a1 * self.y1_r - coeffs.a2 * self.y2_r;
-        self.x2_r = self.x1_r;
-        self.x1_r = input_r;
-        self.y2_r = self.y1_r;
-        self.y1_r = out_r;
-
-        (out_l, out_r)
-    }
-
-    fn reset(&mut self) {
-        *self = Self::default();
-    }
-}
-
-/// Biquad filter coefficients
-#[derive(Debug, Clone)]
-struct BiquadCoeffs {
-    b0: f32, b1: f32, b2: f32,
-    a1: f32, a2: f32,
-}
-
-impl BiquadCoeffs {
-    /// Create low shelf filter coefficients
-    /// gain_db: boost/cut in dB, freq: shelf frequency
-    fn low_shelf(freq: f32, gain_db: f32, sample_rate: f32) -> Self {
-        let a = 10.0_f32.powf(gain_db / 40.0);
-        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
-        let cos_w0 = w0.cos();
-        let sin_w0 = w0.sin();
-        let alpha = sin_w0 / 2.0 * ((a + 1.0/a) * (1.0/0.9 - 1.0) + 2.0).sqrt();
-
-        let a0 = (a + 1.0) + (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha;
-        Self {
-            b0: (a * ((a + 1.0) - (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha)) / a0,
-            b1: (2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0,
-            b2: (a * ((a + 1.0) - (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha)) / a0,
-            a1: (-2.0 * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0,
-            a2: ((a + 1.0) + (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha) / a0,
-        }
-    }
-
-    /// Create peaking EQ filter coefficients
-    fn peaking(freq: f32, gain_db: f32, q: f32, sample_rate: f32) -> Self {
-        let a = 10.0_f32.powf(gain_db / 40.0);
-        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
-        let cos_w0 = w0.cos();
-        let sin_w0 = w0.sin();
-        let alpha = sin_w0 / (2.0 * q);
-
-        let a0 = 1.0 + alpha / a;
-        Self {
-            b0: (1.0 + alpha * a) / a0,
-            b1: (-2.0 * cos_w0) / a0,
-            b2: (1.0 - alpha * a) / a0,
-            a1: (-2.0 * cos_w0) / a0,
-            a2: (1.0 - alpha / a) / a0,
-        }
-    }
-
-    /// Create high shelf filter coefficients
-    fn high_shelf(freq: f32, gain_db: f32, sample_rate: f32) -> Self {
-        let a = 10.0_f32.powf(gain_db / 40.0);
-        let w0 = 2.0 * std::f32::consts::PI * freq / sample_rate;
-        let cos_w0 = w0.cos();
-        let sin_w0 = w0.sin();
-        let alpha = sin_w0 / 2.0 * ((a + 1.0/a) * (1.0/0.9 - 1.0) + 2.0).sqrt();
-
-        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha;
-        Self {
-            b0: (a * ((a + 1.0) + (a - 1.0) * cos_w0 + 2.0 * a.sqrt() * alpha)) / a0,
-            b1: (-2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0,
-            b2: (a * ((a + 1.0) + (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha)) / a0,
-            a1: (2.0 * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0,
-            a2: ((a + 1.0) - (a - 1.0) * cos_w0 - 2.0 * a.sqrt() * alpha) / a0,
-        }
-    }
-
-    /// Passthrough (unity gain, no filtering)
-    fn passthrough() -> Self {
-        Self { b0: 1.0, b1: 0.0, b2: 0.0, a1: 0.0, a2: 0.0 }
-    }
-}
-
-/// EQ frequency centers
-const EQ_LO_FREQ: f32 = 100.0;   // Low shelf at 100 Hz
-const EQ_MID_FREQ: f32 = 1000.0; // Mid peak at 1 kHz
-const EQ_HI_FREQ: f32 = 10000.0; // High shelf at 10 kHz
-const EQ_MID_Q: f32 = 0.7;       // Q for mid band
-
 // ── DJ Filter (24 dB/oct cascaded SVF with adaptive Q) ──────────────
 
 /// Dead zone half-width around center position (bypass when |pos| < this)
@@ -271,22 +178,21 @@ pub struct ChannelStrip {
     pub eq_mid: f32,
     /// EQ High band (0.0 = kill, 0.5 = flat, 1.0 = +6dB)
     pub eq_hi: f32,
+    /// EQ High-mid band, 4-band model only (0.0 = kill, 0.5 = flat, 1.0 = +6dB)
+    pub eq_hi_mid: f32,
     /// Filter position (-1.0 = full LP, 0.0 = flat, 1.0 = full HP)
     pub filter: f32,
     /// Volume fader (0.0 to 1.0)
     pub volume: f32,
     /// Cue button state (routes to cue bus)
     pub cue_enabled: bool,
+    /// Crossfader side assignment (A, B, or thru)
+    pub crossfader_assign: CrossfaderAssign,
+    /// Post-fader send levels to the FX buses (0.0 to 1.0)
+    pub send_levels: [f32; NUM_FX_SENDS],
 
-    // EQ filter states
-    eq_lo_state: BiquadState,
-    eq_mid_state: BiquadState,
-    eq_hi_state: BiquadState,
-
-    // EQ coefficients (cached, recalculated when EQ changes)
-    eq_lo_coeffs: BiquadCoeffs,
-    eq_mid_coeffs: BiquadCoeffs,
-    eq_hi_coeffs: BiquadCoeffs,
+    // EQ (model selectable, knob values applied when changed)
+    eq: ChannelEq,
     eq_dirty: bool,
 
     // DJ filter (24 dB/oct cascaded SVF)
@@ -300,15 +206,13 @@ impl Default for ChannelStrip {
             eq_lo: 0.5,      // Flat
             eq_mid: 0.5,     // Flat
             eq_hi: 0.5,      // Flat
+            eq_hi_mid: 0.5,  // Flat
             filter: 0.0,     // Flat
             volume: 1.0,     // Full volume
             cue_enabled: false,
-            eq_lo_state: BiquadState::default(),
-            eq_mid_state: BiquadState::default(),
-            eq_hi_state: BiquadState::default(),
-            eq_lo_coeffs: BiquadCoeffs::passthrough(),
-            eq_mid_coeffs: BiquadCoeffs::passthrough(),
-            eq_hi_coeffs: BiquadCoeffs::passthrough(),
+            crossfader_assign: CrossfaderAssign::Thru,
+            send_levels: [0.0; NUM_FX_SENDS],
+            eq: ChannelEq::default(),
             eq_dirty: true,
             dj_filter: DjFilter::default(),
         }
@@ -350,58 +254,34 @@ impl ChannelStrip {
         self.eq_dirty = true;
     }
 
-    /// Convert EQ knob position (0-1) to dB gain
-    /// 0.0 = -inf (kill), 0.5 = 0dB, 1.0 = +6dB
-    fn eq_to_db(value: f32) -> f32 {
-        if value < 0.01 {
-            -60.0  // Near-kill
-        } else if value < 0.5 {
-            // 0.01 to 0.5 -> -60dB to 0dB (logarithmic)
-            let t = (value - 0.01) / 0.49;
-            -60.0 * (1.0 - t)
-        } else {
-            // 0.5 to 1.0 -> 0dB to +6dB (linear)
-            (value - 0.5) * 12.0
-        }
+    /// Set EQ high-mid band, used by the 4-band model (0.0 = kill, 0.5 = flat, 1.0 = +6dB boost)
+    pub fn set_eq_hi_mid(&mut self, value: f32) {
+        self.eq_hi_mid = value.clamp(0.0, 1.0);
+        self.eq_dirty = true;
     }
 
-    /// Recalculate EQ coefficients if dirty
-    fn update_eq_coeffs(&mut self) {
-        if !self.eq_dirty {
-            return;
-        }
-
-        let sr = SAMPLE_RATE as f32;
-        let lo_db = Self::eq_to_db(self.eq_lo);
-        let mid_db = Self::eq_to_db(self.eq_mid);
-        let hi_db = Self::eq_to_db(self.eq_hi);
-
-        // Only update if significantly different from flat
-        if lo_db.abs() > 0.1 {
-            self.eq_lo_coeffs = BiquadCoeffs::low_shelf(EQ_LO_FREQ, lo_db, sr);
-        } else {
-            self.eq_lo_coeffs = BiquadCoeffs::passthrough();
-        }
-
-        if mid_db.abs() > 0.1 {
-            self.eq_mid_coeffs = BiquadCoeffs::peaking(EQ_MID_FREQ, mid_db, EQ_MID_Q, sr);
-        } else {
-            self.eq_mid_coeffs = BiquadCoeffs::passthrough();
-        }
+    /// Switch the EQ model (crossfaded, click-free)
+    pub fn set_eq_model(&mut self, model: EqModel) {
+        self.eq.set_model(model);
+    }
 
-        if hi_db.abs() > 0.1 {
-            self.eq_hi_coeffs = BiquadCoeffs::high_shelf(EQ_HI_FREQ, hi_db, sr);
-        } else {
-            self.eq_hi_coeffs = BiquadCoeffs::passthrough();
-        }
+    /// Current EQ model
+    pub fn eq_model(&self) -> EqModel {
+        self.eq.model()
+    }
 
-        self.eq_dirty = false;
+    /// Set the isolator crossover frequencies
+    pub fn set_eq_crossovers(&mut self, crossovers: &EqCrossovers) {
+        self.eq.set_crossovers(crossovers);
     }
 
     /// Process audio through the channel strip (trim + EQ + DJ filter)
     pub fn process(&mut self, buffer: &mut StereoBuffer) {
-        // Update EQ coefficients if needed
-        self.update_eq_coeffs();
+        // Apply EQ knob changes (recalculates coefficients / band gains)
+        if self.eq_dirty {
+            self.eq.set_knobs(self.eq_lo, self.eq_mid, self.eq_hi_mid, self.eq_hi);
+            self.eq_dirty = false;
+        }
 
         let filter_pos = self.filter.clamp(-1.0, 1.0);
         let filter_active = filter_pos.abs() > DJ_FILTER_DEAD_ZONE;
@@ -418,10 +298,8 @@ impl ChannelStrip {
             let mut left = sample.left * self.trim;
             let mut right = sample.right * self.trim;
 
-            // Apply 3-band EQ
-            (left, right) = self.eq_lo_state.process(left, right, &self.eq_lo_coeffs);
-            (left, right) = self.eq_mid_state.process(left, right, &self.eq_mid_coeffs);
-            (left, right) = self.eq_hi_state.process(left, right, &self.eq_hi_coeffs);
+            // Apply EQ
+            (left, right) = self.eq.process(left, right);
 
             // Apply 24 dB/oct DJ filter (bypassed in dead zone)
             if filter_active {
@@ -434,9 +312,7 @@ impl ChannelStrip {
 
     /// Reset all filter states
     pub fn reset(&mut self) {
-        self.eq_lo_state.reset();
-        self.eq_mid_state.reset();
-        self.eq_hi_state.reset();
+        self.eq.reset();
         self.dj_filter.reset();
     }
 }
@@ -457,6 +333,67 @@ fn auto_cue_weight(volume: f32) -> f32 {
     ((-K * volume).exp() - EXP_NEG_K) / NORM
 }
 
+// ── Line inputs and talkover ────────────────────────────────────────
+
+/// Number of line input channels (mic + aux)
+pub const NUM_LINE_INPUTS: usize = 2;
+
+/// Default talkover depth: how far the decks duck while the mic is used
+pub const DEFAULT_TALKOVER_DEPTH_DB: f32 = -18.0;
+/// Post-fader input peak that counts as talking (≈ -40 dBFS)
+const TALKOVER_THRESHOLD: f32 = 0.01;
+/// Decks stay ducked this long after the input falls silent
+const TALKOVER_HOLD_SECS: f32 = 0.3;
+/// Time to duck the decks fully
+const TALKOVER_ATTACK_SECS: f32 = 0.01;
+/// Time to bring the decks back up fully
+const TALKOVER_RELEASE_SECS: f32 = 0.25;
+
+/// External line input channel on the mixer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineInput {
+    /// MC microphone
+    Mic,
+    /// Aux line in (drum machine, second DJ, ...)
+    Aux,
+}
+
+impl LineInput {
+    /// All line inputs, in channel order
+    pub const ALL: [LineInput; NUM_LINE_INPUTS] = [LineInput::Mic, LineInput::Aux];
+
+    /// Channel index (0 = mic, 1 = aux)
+    pub fn index(self) -> usize {
+        match self {
+            LineInput::Mic => 0,
+            LineInput::Aux => 1,
+        }
+    }
+
+    /// Line input for a channel index
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// Short label for mixer strips
+    pub fn label(self) -> &'static str {
+        match self {
+            LineInput::Mic => "MIC",
+            LineInput::Aux => "AUX",
+        }
+    }
+}
+
+/// Trim in dB for a 0..1 knob position (-24 dB at 0, unity at 0.5, +12 dB at 1)
+pub fn trim_db_from_knob(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value < 0.5 {
+        -24.0 * (1.0 - value * 2.0)
+    } else {
+        12.0 * (value * 2.0 - 1.0)
+    }
+}
+
 /// Main mixer combining all deck outputs
 pub struct Mixer {
     /// Per-deck channel strips
@@ -469,6 +406,29 @@ pub struct Mixer {
     cue_volume: f32,
     /// Auto-cue: route low-volume decks to headphone output automatically
     auto_cue: bool,
+    /// Crossfader position (-1.0 = full A, 0.0 = center, 1.0 = full B)
+    crossfader: f32,
+    /// Crossfader curve and reverse (hamster) setting
+    crossfader_config: CrossfaderConfig,
+    /// Crossfader gain applied at the end of the previous buffer, per channel.
+    /// Gain changes are ramped across one buffer to avoid zipper noise.
+    crossfader_gains: [f32; NUM_DECKS],
+    /// Mic and aux line input channel strips (crossfader assignment unused)
+    line_channels: [ChannelStrip; NUM_LINE_INPUTS],
+    /// Per-line-input talkover: signal on the input ducks the decks
+    talkover: [bool; NUM_LINE_INPUTS],
+    /// Deck gain while talkover is active (linear)
+    talkover_depth: f32,
+    /// Talkover gain applied to the deck channels at the end of the previous buffer
+    duck_gain: f32,
+    /// Samples left before the decks come back up after talking stops
+    talkover_hold: usize,
+    /// Send/return FX bus chains
+    fx_sends: [FxChain; NUM_FX_SENDS],
+    /// Pre-allocated send bus buffers (sum of the channel sends)
+    send_buffers: [StereoBuffer; NUM_FX_SENDS],
+    /// Insert FX chain on the master bus
+    master_fx: FxChain,
     /// Lock-free peak levels for UI metering
     level_atomics: Arc<LevelAtomics>,
     /// Master bus lookahead limiter (transparent, before clipper)
@@ -486,6 +446,18 @@ impl Mixer {
             cue_mix: 0.0,
             cue_volume: 0.8,
             auto_cue: true,
+            crossfader: 0.0,
+            crossfader_config: CrossfaderConfig::default(),
+            crossfader_gains: [1.0; NUM_DECKS],
+            // Line inputs start with their fader down
+            line_channels: std::array::from_fn(|_| ChannelStrip { volume: 0.0, ..ChannelStrip::new() }),
+            talkover: [false; NUM_LINE_INPUTS],
+            talkover_depth: 10.0_f32.powf(DEFAULT_TALKOVER_DEPTH_DB / 20.0),
+            duck_gain: 1.0,
+            talkover_hold: 0,
+            fx_sends: std::array::from_fn(|_| FxChain::new()),
+            send_buffers: std::array::from_fn(|_| StereoBuffer::with_capacity(MAX_BUFFER_SIZE)),
+            master_fx: FxChain::new(),
             level_atomics: Arc::new(LevelAtomics::new()),
             limiter: MasterLimiter::new(),
             clipper: MasterClipper::new(),
@@ -497,6 +469,71 @@ impl Mixer {
         self.auto_cue = enabled;
     }
 
+    /// Set crossfader position (-1.0 = full A, 0.0 = center, 1.0 = full B)
+    pub fn set_crossfader(&mut self, position: f32) {
+        self.crossfader = position.clamp(-1.0, 1.0);
+    }
+
+    /// Get crossfader position
+    pub fn crossfader(&self) -> f32 {
+        self.crossfader
+    }
+
+    /// Set the crossfader gain curve
+    pub fn set_crossfader_curve(&mut self, curve: CrossfaderCurve) {
+        self.crossfader_config.curve = curve;
+    }
+
+    /// Enable or disable reversed (hamster) crossfader direction
+    pub fn set_crossfader_reverse(&mut self, reverse: bool) {
+        self.crossfader_config.reverse = reverse;
+    }
+
+    /// Assign a channel to a crossfader side
+    pub fn set_crossfader_assign(&mut self, deck: usize, assign: CrossfaderAssign) {
+        if let Some(ch) = self.channels.get_mut(deck) {
+            ch.crossfader_assign = assign;
+        }
+    }
+
+    /// Apply a full crossfader configuration (curve, reverse, assignments)
+    pub fn set_crossfader_config(&mut self, config: CrossfaderConfig) {
+        for (ch, assign) in self.channels.iter_mut().zip(config.assignments) {
+            ch.crossfader_assign = assign;
+        }
+        self.crossfader_config = config;
+    }
+
+    /// Get the current crossfader configuration (assignments read from channels)
+    pub fn crossfader_config(&self) -> CrossfaderConfig {
+        CrossfaderConfig {
+            assignments: std::array::from_fn(|i| self.channels[i].crossfader_assign),
+            ..self.crossfader_config
+        }
+    }
+
+    /// Set the isolator crossover frequencies on all deck channels
+    pub fn set_eq_crossovers(&mut self, crossovers: &EqCrossovers) {
+        for ch in &mut self.channels {
+            ch.set_eq_crossovers(crossovers);
+        }
+    }
+
+    /// Crossfader gain for a channel at the current fader position
+    pub fn crossfader_gain(&self, deck: usize) -> f32 {
+        self.channels.get(deck)
+            .map(|ch| self.crossfader_config.gain_for(ch.crossfader_assign, self.crossfader))
+            .unwrap_or(1.0)
+    }
+
+    /// Whether a channel currently reaches the master bus
+    /// (channel fader up and not cut by the crossfader)
+    pub fn is_audible(&self, deck: usize) -> bool {
+        self.channels.get(deck)
+            .map(|ch| ch.volume > 0.0 && self.crossfader_gain(deck) > 0.0)
+            .unwrap_or(false)
+    }
+
     /// Get a clone of the level atomics Arc for the UI thread
     pub fn level_atomics(&self) -> Arc<LevelAtomics> {
         Arc::clone(&self.level_atomics)
@@ -512,6 +549,71 @@ impl Mixer {
         self.channels.get_mut(deck)
     }
 
+    /// Get a reference to a line input channel strip
+    pub fn line_channel(&self, input: LineInput) -> &ChannelStrip {
+        &self.line_channels[input.index()]
+    }
+
+    /// Get a mutable reference to a line input channel strip
+    pub fn line_channel_mut(&mut self, input: LineInput) -> &mut ChannelStrip {
+        &mut self.line_channels[input.index()]
+    }
+
+    /// Enable or disable talkover for a line input
+    pub fn set_talkover(&mut self, input: LineInput, enabled: bool) {
+        self.talkover[input.index()] = enabled;
+    }
+
+    /// Whether a line input ducks the decks
+    pub fn talkover(&self, input: LineInput) -> bool {
+        self.talkover[input.index()]
+    }
+
+    /// Set how far the decks duck during talkover (-40 to 0 dB)
+    pub fn set_talkover_depth_db(&mut self, db: f32) {
+        self.talkover_depth = 10.0_f32.powf(db.clamp(-40.0, 0.0) / 20.0);
+    }
+
+    /// Get the talkover depth in dB
+    pub fn talkover_depth_db(&self) -> f32 {
+        20.0 * self.talkover_depth.log10()
+    }
+
+    /// Talkover gain currently applied to the deck channels (1.0 = not ducked)
+    pub fn duck_gain(&self) -> f32 {
+        self.duck_gain
+    }
+
+    /// Get an FX bus chain
+    pub fn fx_chain(&self, bus: FxBus) -> &FxChain {
+        match bus.send_index() {
+            Some(send) => &self.fx_sends[send],
+            None => &self.master_fx,
+        }
+    }
+
+    /// Get a mutable reference to an FX bus chain
+    pub fn fx_chain_mut(&mut self, bus: FxBus) -> &mut FxChain {
+        match bus.send_index() {
+            Some(send) => &mut self.fx_sends[send],
+            None => &mut self.master_fx,
+        }
+    }
+
+    /// Set a channel's send level to a send/return bus (0.0 to 1.0)
+    pub fn set_send_level(&mut self, deck: usize, send: usize, level: f32) {
+        if let Some(slot) = self.channels.get_mut(deck).and_then(|ch| ch.send_levels.get_mut(send)) {
+            *slot = level.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Forward the master tempo to the effects on all FX buses
+    pub fn set_fx_tempo(&mut self, bpm: f64) {
+        for chain in self.fx_sends.iter_mut().chain(std::iter::once(&mut self.master_fx)) {
+            chain.set_tempo(bpm);
+        }
+    }
+
     /// Set master volume (0.0 to 1.0)
     pub fn set_master_volume(&mut self, volume: f32) {
         self.master_volume = volume.clamp(0.0, 1.0);
@@ -560,6 +662,42 @@ impl Mixer {
         deck_buffers: &mut [StereoBuffer; NUM_DECKS],
         master_out: &mut StereoBuffer,
         cue_out: &mut StereoBuffer,
+    ) {
+        self.process_with_aux(deck_buffers, None, master_out, cue_out);
+    }
+
+    /// Process deck outputs plus an auxiliary (master, cue) bus pair
+    ///
+    /// The aux buses (e.g. the sampler) are already post-fader: they are
+    /// added after the deck channels, before master volume, clipper and
+    /// limiter. Not affected by the crossfader.
+    pub fn process_with_aux(
+        &mut self,
+        deck_buffers: &mut [StereoBuffer; NUM_DECKS],
+        aux: Option<(&StereoBuffer, &StereoBuffer)>,
+        master_out: &mut StereoBuffer,
+        cue_out: &mut StereoBuffer,
+    ) {
+        self.process_with_inputs(deck_buffers, &mut [], aux, master_out, cue_out);
+    }
+
+    /// Process deck outputs, line inputs and an auxiliary bus pair
+    ///
+    /// `line_inputs` holds the mic and aux signals in [`LineInput`] order;
+    /// missing or empty buffers count as silence. Each runs through its own
+    /// channel strip and is summed post-fader into master (not affected by
+    /// the crossfader) and pre-fader into cue when its CUE is on. Signal on
+    /// a line input with talkover enabled ducks the deck channels.
+    ///
+    /// Deck channels feed the FX send buses post-fader; the bus returns and
+    /// the master insert chain are applied before master volume.
+    pub fn process_with_inputs(
+        &mut self,
+        deck_buffers: &mut [StereoBuffer; NUM_DECKS],
+        line_inputs: &mut [StereoBuffer],
+        aux: Option<(&StereoBuffer, &StereoBuffer)>,
+        master_out: &mut StereoBuffer,
+        cue_out: &mut StereoBuffer,
     ) {
         let buffer_len = master_out.len();
         master_out.fill_silence();
@@ -574,6 +712,35 @@ impl Mixer {
                 channel.process(buffer);
             });
 
+        // Line input strips run before the deck sum so talkover detected in
+        // this buffer already ducks it
+        let mut line_peaks = [0.0f32; NUM_LINE_INPUTS];
+        let mut talking = false;
+        for (idx, buffer) in line_inputs.iter_mut().enumerate().take(NUM_LINE_INPUTS) {
+            if buffer.is_empty() {
+                continue;
+            }
+            let channel = &mut self.line_channels[idx];
+            channel.process(buffer);
+            let peak = buffer.as_slice().iter()
+                .fold(0.0f32, |acc, s| acc.max(s.left.abs()).max(s.right.abs()))
+                * channel.volume;
+            line_peaks[idx] = peak;
+            talking |= self.talkover[idx] && peak > TALKOVER_THRESHOLD;
+        }
+        let duck_start = self.duck_gain;
+        let duck_target = self.update_duck_gain(talking, buffer_len);
+        let duck_step = if buffer_len > 0 { (duck_target - duck_start) / buffer_len as f32 } else { 0.0 };
+
+        // Send buses are only summed and processed when they hold effects
+        let sends_active: [bool; NUM_FX_SENDS] = std::array::from_fn(|s| self.fx_sends[s].is_active());
+        for (buffer, active) in self.send_buffers.iter_mut().zip(sends_active) {
+            if active {
+                buffer.set_len_from_capacity(buffer_len.min(MAX_BUFFER_SIZE));
+                buffer.fill_silence();
+            }
+        }
+
         // Phase 2: Sequential summing to master/cue buses + per-channel peak tracking
         // This is fast O(n) and must be sequential to avoid race conditions
         let mut channel_peaks = [0.0f32; NUM_DECKS];
@@ -581,14 +748,31 @@ impl Mixer {
             let channel = &self.channels[deck_idx];
             let mut deck_peak: f32 = 0.0;
 
-            // Add to master output (with volume fader)
+            // Crossfader gain ramps linearly from last buffer's value to the
+            // target across this buffer (post-fader only; cue bus is pre-fader)
+            let xf_start = self.crossfader_gains[deck_idx];
+            let xf_target = self.crossfader_config.gain_for(channel.crossfader_assign, self.crossfader);
+            let xf_step = if buffer_len > 0 { (xf_target - xf_start) / buffer_len as f32 } else { 0.0 };
+            self.crossfader_gains[deck_idx] = xf_target;
+
+            // Add to master output (with volume fader and crossfader)
             for i in 0..buffer_len.min(buffer.len()) {
                 let sample = buffer[i];
 
-                // Master bus: apply volume fader
-                let master_sample = sample * channel.volume;
+                // Master bus: apply volume fader, crossfader and talkover gain
+                let xf_gain = xf_start + xf_step * (i + 1) as f32;
+                let duck = duck_start + duck_step * (i + 1) as f32;
+                let master_sample = sample * (channel.volume * xf_gain * duck);
                 master_out.as_mut_slice()[i] += master_sample;
 
+                // FX sends: post-fader copy at the channel's send level
+                for (send, buffer) in self.send_buffers.iter_mut().enumerate() {
+                    let level = channel.send_levels[send];
+                    if sends_active[send] && level > 0.0 && i < buffer.len() {
+                        buffer.as_mut_slice()[i] += master_sample * level;
+                    }
+                }
+
                 // Track post-fader peak (stereo max)
                 deck_peak = deck_peak
                     .max(master_sample.left.abs())
@@ -615,6 +799,43 @@ impl Mixer {
             self.level_atomics.channel_peaks[i].store(channel_peaks[i].to_bits(), Ordering::Relaxed);
         }
 
+        // Line inputs: post-fader into master, pre-fader into cue
+        for (idx, buffer) in line_inputs.iter().enumerate().take(NUM_LINE_INPUTS) {
+            let channel = &self.line_channels[idx];
+            for i in 0..buffer_len.min(buffer.len()) {
+                master_out.as_mut_slice()[i] += buffer[i] * channel.volume;
+                if channel.cue_enabled {
+                    cue_out.as_mut_slice()[i] += buffer[i];
+                }
+            }
+        }
+        for (atomic, peak) in self.level_atomics.line_peaks.iter().zip(line_peaks) {
+            atomic.store(peak.to_bits(), Ordering::Relaxed);
+        }
+
+        // Auxiliary buses (sampler)
+        if let Some((aux_master, aux_cue)) = aux {
+            let len = buffer_len.min(aux_master.len()).min(aux_cue.len());
+            for i in 0..len {
+                master_out.as_mut_slice()[i] += aux_master[i];
+                cue_out.as_mut_slice()[i] += aux_cue[i];
+            }
+        }
+
+        // FX bus returns, then the master insert chain
+        for (send, active) in sends_active.into_iter().enumerate() {
+            if !active {
+                continue;
+            }
+            let buffer = &mut self.send_buffers[send];
+            self.fx_sends[send].process(buffer);
+            let level = self.fx_sends[send].return_level();
+            for i in 0..buffer_len.min(buffer.len()) {
+                master_out.as_mut_slice()[i] += buffer[i] * level;
+            }
+        }
+        self.master_fx.process(master_out);
+
         // Apply master volume
         master_out.scale(self.master_volume);
 
@@ -642,11 +863,45 @@ impl Mixer {
         }
     }
 
-    /// Reset all channel strip filter states
+    /// Advance the talkover envelope by one buffer, returning the new deck gain
+    ///
+    /// The gain moves linearly: a full duck takes `TALKOVER_ATTACK_SECS`, a
+    /// full recovery `TALKOVER_RELEASE_SECS`, after a hold once talking stops.
+    fn update_duck_gain(&mut self, talking: bool, buffer_len: usize) -> f32 {
+        if talking {
+            self.talkover_hold = (TALKOVER_HOLD_SECS * SAMPLE_RATE as f32) as usize;
+        } else {
+            self.talkover_hold = self.talkover_hold.saturating_sub(buffer_len);
+        }
+
+        let (target, secs) = if self.talkover_hold > 0 {
+            (self.talkover_depth, TALKOVER_ATTACK_SECS)
+        } else {
+            (1.0, TALKOVER_RELEASE_SECS)
+        };
+        let max_step = (1.0 - self.talkover_depth) * buffer_len as f32 / (secs * SAMPLE_RATE as f32);
+        self.duck_gain += (target - self.duck_gain).clamp(-max_step, max_step);
+        self.duck_gain
+    }
+
+    /// Post-fader gain of a channel on the master bus
+    ///
+    /// Trim × volume × crossfader × talkover × master volume, as applied by
+    /// the last `process()` call. EQ, filter and the master limiter are not
+    /// included.
+    pub fn channel_master_gain(&self, deck_idx: usize) -> f32 {
+        let channel = &self.channels[deck_idx];
+        channel.trim * channel.volume * self.crossfader_gains[deck_idx] * self.duck_gain * self.master_volume
+    }
+
+    /// Reset all channel strip filter states and FX bus effects
     pub fn reset(&mut self) {
-        for channel in &mut self.channels {
+        for channel in self.channels.iter_mut().chain(&mut self.line_channels) {
             channel.reset();
         }
+        for chain in self.fx_sends.iter_mut().chain(std::iter::once(&mut self.master_fx)) {
+            chain.reset();
+        }
     }
 }
 
@@ -691,6 +946,229 @@ mod tests {
         assert_eq!(mixer.cue_volume(), 0.8);
     }
 
+    /// Fill all four deck buffers with DC 1.0 and run one mixer pass
+    fn process_dc(mixer: &mut Mixer, len: usize) -> StereoBuffer {
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|_| {
+            let mut buf = StereoBuffer::silence(len);
+            for s in buf.iter_mut() {
+                *s = StereoSample::new(1.0, 1.0);
+            }
+            buf
+        });
+        let mut master = StereoBuffer::silence(len);
+        let mut cue = StereoBuffer::silence(len);
+        mixer.process(&mut decks, &mut master, &mut cue);
+        master
+    }
+
+    #[test]
+    fn test_aux_bus_is_summed_into_master_and_cue() {
+        let mut mixer = Mixer::new();
+        // Longer than the limiter lookahead so the end of the buffer is settled
+        let len = 256;
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|_| StereoBuffer::silence(len));
+        let mut aux_master = StereoBuffer::silence(len);
+        let mut aux_cue = StereoBuffer::silence(len);
+        for s in aux_master.iter_mut() {
+            *s = StereoSample::new(0.25, 0.25);
+        }
+        for s in aux_cue.iter_mut() {
+            *s = StereoSample::new(0.5, 0.5);
+        }
+        let mut master = StereoBuffer::silence(len);
+        let mut cue = StereoBuffer::silence(len);
+        mixer.set_cue_volume(1.0);
+        mixer.process_with_aux(&mut decks, Some((&aux_master, &aux_cue)), &mut master, &mut cue);
+
+        assert!((master[len - 1].left - 0.25).abs() < 1e-4);
+        // Cue mix 0.0 = headphones hear only the cue bus
+        assert!((cue[len - 1].left - 0.5).abs() < 1e-4);
+    }
+
+    /// DC buffer of `len` samples at `level`
+    fn dc(len: usize, level: f32) -> StereoBuffer {
+        let mut buf = StereoBuffer::silence(len);
+        for s in buf.iter_mut() {
+            *s = StereoSample::new(level, level);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_line_input_is_summed_post_fader() {
+        let mut mixer = Mixer::new();
+        let len = 256;
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|_| StereoBuffer::silence(len));
+        let mut master = StereoBuffer::silence(len);
+        let mut cue = StereoBuffer::silence(len);
+
+        // Fader down by default: nothing reaches master
+        let mut lines = [dc(len, 0.5), StereoBuffer::silence(0)];
+        mixer.process_with_inputs(&mut decks, &mut lines, None, &mut master, &mut cue);
+        assert!(master[len - 1].left.abs() < 1e-4);
+
+        mixer.line_channel_mut(LineInput::Mic).volume = 0.5;
+        mixer.line_channel_mut(LineInput::Mic).cue_enabled = true;
+        mixer.set_cue_volume(1.0);
+        let mut lines = [dc(len, 0.5), StereoBuffer::silence(0)];
+        mixer.process_with_inputs(&mut decks, &mut lines, None, &mut master, &mut cue);
+        assert!((master[len - 1].left - 0.25).abs() < 1e-3, "got {}", master[len - 1].left);
+        // Cue gets the pre-fader signal
+        assert!((cue[len - 1].left - 0.5).abs() < 1e-3, "got {}", cue[len - 1].left);
+        assert!((mixer.level_atomics().line_peak(LineInput::Mic) - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_talkover_ducks_decks_and_recovers() {
+        let mut mixer = Mixer::new();
+        mixer.set_master_volume(0.1); // stay below clipper/limiter threshold
+        mixer.set_talkover(LineInput::Mic, true);
+        mixer.set_talkover_depth_db(-20.0);
+        mixer.line_channel_mut(LineInput::Mic).volume = 1.0;
+        let len = 256;
+        let run = |mixer: &mut Mixer, mic_level: f32| {
+            let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|_| dc(len, 1.0));
+            let mut lines = [dc(len, mic_level), StereoBuffer::silence(0)];
+            let mut master = StereoBuffer::silence(len);
+            let mut cue = StereoBuffer::silence(len);
+            mixer.process_with_inputs(&mut decks, &mut lines, None, &mut master, &mut cue);
+        };
+
+        // Silence on the mic leaves the decks alone
+        run(&mut mixer, 0.0);
+        assert_eq!(mixer.duck_gain(), 1.0);
+
+        // Talking ducks to the set depth within the attack time
+        for _ in 0..4 {
+            run(&mut mixer, 0.2);
+        }
+        assert!((mixer.duck_gain() - 0.1).abs() < 1e-3, "got {}", mixer.duck_gain());
+        assert!((mixer.channel_master_gain(0) - 0.01).abs() < 1e-3);
+
+        // Held briefly after talking stops, then released
+        run(&mut mixer, 0.0);
+        assert!((mixer.duck_gain() - 0.1).abs() < 1e-3);
+        let release_buffers = ((TALKOVER_HOLD_SECS + TALKOVER_RELEASE_SECS) * SAMPLE_RATE as f32) as usize / len + 2;
+        for _ in 0..release_buffers {
+            run(&mut mixer, 0.0);
+        }
+        assert_eq!(mixer.duck_gain(), 1.0);
+    }
+
+    #[test]
+    fn test_channel_eq_model_switch() {
+        let mut strip = ChannelStrip::new();
+        assert_eq!(strip.eq_model(), EqModel::Classic);
+        strip.set_eq_model(EqModel::FourBand);
+        strip.set_eq_hi_mid(0.0);
+        assert_eq!(strip.eq_model(), EqModel::FourBand);
+        assert_eq!(strip.eq_hi_mid, 0.0);
+
+        // All bands killed on an isolator model is silence
+        strip.set_eq_lo(0.0);
+        strip.set_eq_mid(0.0);
+        strip.set_eq_hi(0.0);
+        let mut buffer = StereoBuffer::silence(4800);
+        let mut peak = 0.0_f32;
+        for _ in 0..4 {
+            for s in buffer.iter_mut() {
+                *s = StereoSample::new(0.5, -0.5);
+            }
+            strip.process(&mut buffer);
+            peak = buffer.peak();
+        }
+        assert!(peak < 1e-3, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_trim_db_from_knob() {
+        assert_eq!(trim_db_from_knob(0.0), -24.0);
+        assert_eq!(trim_db_from_knob(0.5), 0.0);
+        assert_eq!(trim_db_from_knob(1.0), 12.0);
+    }
+
+    #[test]
+    fn test_fx_send_return_and_master_insert() {
+        use crate::effect::native::GainEffect;
+
+        let mut mixer = Mixer::new();
+        mixer.set_master_volume(0.5); // stay below clipper/limiter threshold
+        let len = 256;
+        let mut master = StereoBuffer::silence(len);
+        let mut cue = StereoBuffer::silence(len);
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|i| {
+            if i == 0 { dc(len, 0.2) } else { StereoBuffer::silence(len) }
+        });
+
+        // Send level without an effect on the bus: dry deck only
+        mixer.set_send_level(0, 0, 0.5);
+        mixer.process(&mut decks, &mut master, &mut cue);
+        assert!((master[len - 1].left - 0.1).abs() < 1e-3, "got {}", master[len - 1].left);
+
+        // Unity gain on FX 1 returns half the deck signal on top
+        mixer.fx_chain_mut(FxBus::Send1).add_effect(Box::new(GainEffect::new()));
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|i| {
+            if i == 0 { dc(len, 0.2) } else { StereoBuffer::silence(len) }
+        });
+        mixer.process(&mut decks, &mut master, &mut cue);
+        assert!((master[len - 1].left - 0.15).abs() < 1e-3, "got {}", master[len - 1].left);
+
+        // Muting gain on the master insert silences everything
+        let master_fx = mixer.fx_chain_mut(FxBus::Master);
+        master_fx.add_effect(Box::new(GainEffect::new()));
+        master_fx.set_effect_param(0, 0, 0.0);
+        let mut decks: [StereoBuffer; NUM_DECKS] = std::array::from_fn(|i| {
+            if i == 0 { dc(len, 0.2) } else { StereoBuffer::silence(len) }
+        });
+        mixer.process(&mut decks, &mut master, &mut cue);
+        assert!(master[len - 1].left.abs() < 1e-4, "got {}", master[len - 1].left);
+    }
+
+    #[test]
+    fn test_crossfader_thru_by_default() {
+        let mut mixer = Mixer::new();
+        mixer.set_crossfader(-1.0);
+        for deck in 0..NUM_DECKS {
+            assert_eq!(mixer.crossfader_gain(deck), 1.0);
+            assert!(mixer.is_audible(deck));
+        }
+    }
+
+    #[test]
+    fn test_crossfader_cuts_opposite_side() {
+        let mut mixer = Mixer::new();
+        mixer.set_master_volume(0.1); // stay below clipper/limiter threshold
+        for deck in 0..NUM_DECKS {
+            mixer.channel_mut(deck).unwrap().volume = if deck == 0 || deck == 1 { 1.0 } else { 0.0 };
+        }
+        mixer.set_crossfader_assign(0, CrossfaderAssign::A);
+        mixer.set_crossfader_assign(1, CrossfaderAssign::B);
+        mixer.set_crossfader(-1.0);
+
+        assert!(mixer.is_audible(0));
+        assert!(!mixer.is_audible(1));
+
+        // Run twice so the per-buffer gain ramp settles on the target
+        // (256 samples comfortably covers the limiter's lookahead delay)
+        process_dc(&mut mixer, 256);
+        let master = process_dc(&mut mixer, 256);
+        // Only deck A contributes: 1.0 * master 0.1
+        assert!((master[255].left - 0.1).abs() < 0.01, "got {}", master[255].left);
+    }
+
+    #[test]
+    fn test_crossfader_config_roundtrip() {
+        let mut mixer = Mixer::new();
+        let config = CrossfaderConfig {
+            curve: CrossfaderCurve::Scratch,
+            reverse: true,
+            assignments: [CrossfaderAssign::A, CrossfaderAssign::B, CrossfaderAssign::Thru, CrossfaderAssign::B],
+        };
+        mixer.set_crossfader_config(config);
+        assert_eq!(mixer.crossfader_config(), config);
+        assert_eq!(mixer.channel(3).unwrap().crossfader_assign, CrossfaderAssign::B);
+    }
+
     #[test]
     fn test_dj_filter_bypass_at_center() {
         let mut strip = ChannelStrip::new();
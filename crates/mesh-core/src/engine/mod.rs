@@ -3,7 +3,9 @@
 //! This module contains the core audio engine components for the DJ player:
 //! - [`Deck`]: Individual track player with stems and effect chains
 //! - [`Mixer`]: Combines deck outputs with volume/filter controls
+//! - [`FxChain`]: Effect chains on the mixer's send/return and master FX buses
 //! - [`LatencyCompensator`]: Per-stem latency compensation using delay lines
+//! - [`SamplerBank`]: One-shot and looped sample slots with their own channel strips
 //! - [`AudioEngine`]: Main engine tying everything together
 //!
 //! # Multi-Threading Architecture
@@ -56,25 +58,35 @@
 //! | Pre-allocated memory | ~1.5MB | Buffers for 4 decks |
 //! | Max latency compensation | 4410 samples | 100ms @ 44.1kHz |
 
+mod channel_eq;
 mod command;
 mod deck;
 mod engine;
+mod fx_bus;
 pub mod gc;
 mod latency;
 mod linked_stem;
 mod master_clipper;
+mod master_clock;
 mod master_limiter;
 mod mixer;
+mod sampler;
 pub mod scratch;
 mod slicer;
+mod stem_capture;
 
+pub use channel_eq::ChannelEq;
 pub use command::*;
 pub use deck::*;
 pub use engine::*;
+pub use fx_bus::*;
 pub use latency::*;
 pub use linked_stem::*;
 pub use master_clipper::*;
+pub use master_clock::{MasterClock, MasterClockAtomics};
 pub use master_limiter::*;
 pub use mixer::*;
-pub use scratch::{InterpolationMethod, ScratchState};
+pub use sampler::*;
+pub use scratch::{InterpolationMethod, JogRelease, ScratchState};
 pub use slicer::*;
+pub use stem_capture::StemCapture;
@@ -0,0 +1,373 @@
+//! Sampler bank — one-shot and looped clips on their own channel strips
+//!
+//! The bank holds [`NUM_SAMPLER_SLOTS`] slots, each with a
+//! [`SampleClip`](crate::sampler::SampleClip), a time stretcher and a
+//! [`ChannelStrip`]:
+//!
+//! ```text
+//! SampleClip (Shared, loaded off-thread)
+//!     │ read at global_bpm / clip_bpm (loops wrap, one-shots run out)
+//!     ▼
+//! TimeStretcher → ChannelStrip (trim, EQ, filter) × volume
+//!     ▼
+//! Sampler bus ──► Mixer (pre master volume, clipper, limiter)
+//!     └─ cue ───► Cue bus (pre-fader, when the slot's cue is on)
+//! ```
+//!
+//! Clips with a BPM follow the global tempo like the decks do; clips
+//! without one play at their native speed. Loops started while a master
+//! deck is playing begin at the master's beat phase so they land on the
+//! grid.
+//!
+//! Clips arrive as `basedrop::Shared`, so replacing or clearing a slot on
+//! the audio thread never deallocates there.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use basedrop::Shared;
+
+use crate::sampler::{ClipMode, SampleClip};
+use crate::timestretch::TimeStretcher;
+use crate::types::StereoBuffer;
+
+use super::{ChannelStrip, MAX_BUFFER_SIZE};
+
+/// Number of sampler slots (one per performance pad)
+pub const NUM_SAMPLER_SLOTS: usize = 8;
+
+/// Lock-free slot state exposed to the UI thread (pads and LED feedback)
+pub struct SamplerAtomics {
+    /// Slot holds a clip
+    pub loaded: [AtomicBool; NUM_SAMPLER_SLOTS],
+    /// Slot is currently playing
+    pub playing: [AtomicBool; NUM_SAMPLER_SLOTS],
+}
+
+impl SamplerAtomics {
+    pub fn new() -> Self {
+        Self {
+            loaded: std::array::from_fn(|_| AtomicBool::new(false)),
+            playing: std::array::from_fn(|_| AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_loaded(&self, slot: usize) -> bool {
+        self.loaded.get(slot).is_some_and(|a| a.load(Ordering::Relaxed))
+    }
+
+    pub fn is_playing(&self, slot: usize) -> bool {
+        self.playing.get(slot).is_some_and(|a| a.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for SamplerAtomics {
+    fn default() -> Self { Self::new() }
+}
+
+/// A single sampler slot
+struct SamplerSlot {
+    clip: Option<Shared<SampleClip>>,
+    playing: bool,
+    /// Next frame to read from the clip
+    read_pos: usize,
+    /// Frames of stretcher tail still to flush after a one-shot ran out
+    tail_remaining: usize,
+    stretcher: TimeStretcher,
+    strip: ChannelStrip,
+}
+
+impl SamplerSlot {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            clip: None,
+            playing: false,
+            read_pos: 0,
+            tail_remaining: 0,
+            stretcher: TimeStretcher::new_with_sample_rate(sample_rate),
+            strip: ChannelStrip::new(),
+        }
+    }
+
+    fn stop(&mut self) {
+        self.playing = false;
+        self.read_pos = 0;
+        self.tail_remaining = 0;
+    }
+}
+
+/// Bank of sampler slots mixed into the master and cue buses
+pub struct SamplerBank {
+    slots: [SamplerSlot; NUM_SAMPLER_SLOTS],
+    /// Clip frames read for the current buffer (stretcher input)
+    input: StereoBuffer,
+    /// Stretched output of one slot
+    output: StereoBuffer,
+    /// Post-fader sum of all slots
+    bus: StereoBuffer,
+    /// Pre-fader sum of the slots with cue enabled
+    cue_bus: StereoBuffer,
+    atomics: Arc<SamplerAtomics>,
+}
+
+impl SamplerBank {
+    /// Create an empty bank
+    ///
+    /// Allocates the slot stretchers and buffers — call at engine creation.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            slots: std::array::from_fn(|_| SamplerSlot::new(sample_rate)),
+            // Stretch ratios go up to 2.0, so the input can be twice the output
+            input: StereoBuffer::silence(MAX_BUFFER_SIZE * 2),
+            output: StereoBuffer::silence(MAX_BUFFER_SIZE),
+            bus: StereoBuffer::silence(MAX_BUFFER_SIZE),
+            cue_bus: StereoBuffer::silence(MAX_BUFFER_SIZE),
+            atomics: Arc::new(SamplerAtomics::new()),
+        }
+    }
+
+    /// Lock-free slot state for the UI
+    pub fn atomics(&self) -> Arc<SamplerAtomics> {
+        self.atomics.clone()
+    }
+
+    /// Put a clip into a slot (or clear it with `None`), stopping playback
+    pub fn load(&mut self, slot: usize, clip: Option<Shared<SampleClip>>) {
+        let Some(s) = self.slots.get_mut(slot) else { return };
+        s.stop();
+        s.stretcher.reset();
+        self.atomics.loaded[slot].store(clip.is_some(), Ordering::Relaxed);
+        self.atomics.playing[slot].store(false, Ordering::Relaxed);
+        // The previous clip (if any) is handed to the GC here
+        s.clip = clip;
+    }
+
+    /// Trigger a slot
+    ///
+    /// One-shots restart from the top. Loops toggle: a stopped loop starts
+    /// at `beat_phase` (0.0–1.0 through the master's current beat) so it is
+    /// in phase with the master deck, a playing loop stops.
+    pub fn trigger(&mut self, slot: usize, beat_phase: Option<f64>) {
+        let Some(s) = self.slots.get_mut(slot) else { return };
+        let Some(clip) = s.clip.as_ref() else { return };
+
+        match clip.mode {
+            ClipMode::OneShot => {
+                if !s.playing {
+                    s.stretcher.reset();
+                }
+                s.read_pos = 0;
+                s.tail_remaining = 0;
+                s.playing = true;
+            }
+            ClipMode::Loop if s.playing => s.stop(),
+            ClipMode::Loop => {
+                s.read_pos = match (clip.bpm, beat_phase) {
+                    (Some(bpm), Some(phase)) if !clip.is_empty() => {
+                        let beat_len = clip.sample_rate as f64 * 60.0 / bpm;
+                        (phase * beat_len) as usize % clip.len()
+                    }
+                    _ => 0,
+                };
+                s.tail_remaining = 0;
+                s.stretcher.reset();
+                s.playing = true;
+            }
+        }
+        self.atomics.playing[slot].store(s.playing, Ordering::Relaxed);
+    }
+
+    /// Stop a slot immediately
+    pub fn stop(&mut self, slot: usize) {
+        if let Some(s) = self.slots.get_mut(slot) {
+            s.stop();
+            self.atomics.playing[slot].store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Stop every slot
+    pub fn stop_all(&mut self) {
+        for slot in 0..NUM_SAMPLER_SLOTS {
+            self.stop(slot);
+        }
+    }
+
+    /// Channel strip of a slot (trim, EQ, filter, volume, cue)
+    pub fn channel_mut(&mut self, slot: usize) -> Option<&mut ChannelStrip> {
+        self.slots.get_mut(slot).map(|s| &mut s.strip)
+    }
+
+    /// Whether any slot is playing (the mixer skips the buses otherwise)
+    pub fn is_active(&self) -> bool {
+        self.slots.iter().any(|s| s.playing)
+    }
+
+    /// Post-fader sampler bus from the last `process()` call
+    pub fn bus(&self) -> &StereoBuffer {
+        &self.bus
+    }
+
+    /// Cue bus from the last `process()` call
+    pub fn cue_bus(&self) -> &StereoBuffer {
+        &self.cue_bus
+    }
+
+    /// Render all playing slots into the buses
+    pub fn process(&mut self, output_len: usize, global_bpm: f64) {
+        let output_len = output_len.min(MAX_BUFFER_SIZE);
+        self.bus.set_len_from_capacity(output_len);
+        self.bus.fill_silence();
+        self.cue_bus.set_len_from_capacity(output_len);
+        self.cue_bus.fill_silence();
+        self.output.set_len_from_capacity(output_len);
+
+        for (idx, slot) in self.slots.iter_mut().enumerate() {
+            if !slot.playing {
+                continue;
+            }
+            let Some(clip) = slot.clip.as_ref() else {
+                slot.stop();
+                self.atomics.playing[idx].store(false, Ordering::Relaxed);
+                continue;
+            };
+
+            let ratio = clip.bpm.map_or(1.0, |bpm| TimeStretcher::ratio_from_bpm(bpm, global_bpm));
+            slot.stretcher.set_ratio(ratio);
+            let needed = ((output_len as f64 * slot.stretcher.ratio()).round() as usize)
+                .min(self.input.capacity());
+            self.input.set_len_from_capacity(needed);
+
+            // Fill the stretcher input from the clip
+            let source = clip.audio.as_slice();
+            let input = self.input.as_mut_slice();
+            let mut filled = 0;
+            while filled < needed {
+                if slot.read_pos >= source.len() {
+                    if clip.mode == ClipMode::Loop && !source.is_empty() {
+                        slot.read_pos = 0;
+                    } else {
+                        break;
+                    }
+                }
+                let n = (needed - filled).min(source.len() - slot.read_pos);
+                input[filled..filled + n].copy_from_slice(&source[slot.read_pos..slot.read_pos + n]);
+                filled += n;
+                slot.read_pos += n;
+            }
+            if filled < needed {
+                // One-shot ran out: pad with silence and flush the stretcher tail
+                input[filled..needed].fill(Default::default());
+                if slot.tail_remaining == 0 {
+                    slot.tail_remaining = slot.stretcher.total_latency().max(1);
+                }
+            }
+
+            slot.stretcher.process(&self.input, &mut self.output);
+            slot.strip.process(&mut self.output);
+
+            let volume = slot.strip.volume;
+            let cue = slot.strip.cue_enabled;
+            for i in 0..output_len {
+                let sample = self.output[i];
+                self.bus.as_mut_slice()[i] += sample * volume;
+                if cue {
+                    self.cue_bus.as_mut_slice()[i] += sample;
+                }
+            }
+
+            if slot.tail_remaining > 0 {
+                slot.tail_remaining = slot.tail_remaining.saturating_sub(output_len);
+                if slot.tail_remaining == 0 {
+                    slot.stop();
+                    self.atomics.playing[idx].store(false, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Reset stretcher and filter state on all slots
+    pub fn reset(&mut self) {
+        for slot in &mut self.slots {
+            slot.stretcher.reset();
+            slot.strip.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::gc::gc_handle;
+    use crate::types::StereoSample;
+
+    fn shared_clip(len: usize, mode: ClipMode, bpm: Option<f64>) -> Shared<SampleClip> {
+        Shared::new(
+            &gc_handle(),
+            SampleClip {
+                name: "test".to_string(),
+                audio: StereoBuffer::from_vec(vec![StereoSample::mono(0.5); len]),
+                sample_rate: 48000,
+                bpm,
+                mode,
+            },
+        )
+    }
+
+    #[test]
+    fn test_one_shot_plays_then_stops() {
+        let mut bank = SamplerBank::new(48000);
+        let atomics = bank.atomics();
+        bank.load(0, Some(shared_clip(1024, ClipMode::OneShot, None)));
+        assert!(atomics.is_loaded(0));
+
+        bank.trigger(0, None);
+        assert!(atomics.is_playing(0));
+        assert!(bank.is_active());
+
+        // Clip plus stretcher tail drains within a few seconds of buffers
+        for _ in 0..400 {
+            bank.process(256, 128.0);
+        }
+        assert!(!atomics.is_playing(0));
+        assert!(!bank.is_active());
+    }
+
+    #[test]
+    fn test_loop_toggles_and_keeps_playing() {
+        let mut bank = SamplerBank::new(48000);
+        let atomics = bank.atomics();
+        bank.load(2, Some(shared_clip(512, ClipMode::Loop, Some(120.0))));
+
+        bank.trigger(2, None);
+        for _ in 0..100 {
+            bank.process(256, 128.0);
+        }
+        assert!(atomics.is_playing(2), "loops wrap instead of running out");
+
+        bank.trigger(2, None);
+        assert!(!atomics.is_playing(2), "second trigger stops the loop");
+    }
+
+    #[test]
+    fn test_loop_starts_at_master_phase() {
+        let mut bank = SamplerBank::new(48000);
+        // 120 BPM: one beat = 24000 samples
+        bank.load(1, Some(shared_clip(96000, ClipMode::Loop, Some(120.0))));
+        bank.trigger(1, Some(0.25));
+        assert_eq!(bank.slots[1].read_pos, 6000);
+    }
+
+    #[test]
+    fn test_empty_and_unloaded_slots_are_ignored() {
+        let mut bank = SamplerBank::new(48000);
+        bank.trigger(3, None);
+        bank.trigger(NUM_SAMPLER_SLOTS + 1, None);
+        assert!(!bank.is_active());
+
+        bank.load(3, Some(shared_clip(64, ClipMode::OneShot, None)));
+        bank.load(3, None);
+        assert!(!bank.atomics().is_loaded(3));
+        bank.trigger(3, None);
+        assert!(!bank.is_active());
+    }
+}
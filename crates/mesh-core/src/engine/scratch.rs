@@ -40,6 +40,41 @@ const POSITION_CORRECTION_RATE: f64 = 0.001;
 /// Maximum drift allowed between read position and target before correction kicks in
 const MAX_POSITION_DRIFT: f64 = 44100.0; // ~1 second at 44.1kHz
 
+/// Audio under the needle per platter revolution at normal speed (33⅓ RPM)
+pub const SECONDS_PER_REVOLUTION: f64 = 1.8;
+
+/// Time for the motor to bring a released platter back to speed
+pub const MOTOR_RAMP_SECS: f64 = 0.15;
+
+/// Spin-back starting speed (negative = backwards)
+pub const SPINBACK_SPEED: f64 = -4.0;
+/// Time for a spin-back to slow to a halt
+pub const SPINBACK_SECS: f64 = 0.8;
+
+/// Time for a brake to bring the platter from normal speed to a halt
+pub const BRAKE_SECS: f64 = 0.9;
+
+/// What the platter does when a touch-sensitive jog wheel is let go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JogRelease {
+    /// Motor spins the platter back up to speed (or lets it settle if stopped)
+    #[default]
+    Resume,
+    /// Fast backwards spin that slows to a halt, then playback resumes
+    SpinBack,
+    /// Platter slows to a halt and the deck stops, like a turntable's stop button
+    Brake,
+}
+
+/// Platter coasting without a hand on it: velocity ramps linearly to `target`
+#[derive(Debug, Clone, Copy)]
+struct Glide {
+    /// Velocity at which the glide ends
+    target: f64,
+    /// Velocity change per output sample
+    step: f64,
+}
+
 /// Interpolation method for variable-speed playback
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -84,6 +119,8 @@ pub struct ScratchState {
     smoothed_velocity: f64,
     /// Current interpolation method
     pub interpolation: InterpolationMethod,
+    /// Active release glide (spin-up, spin-back or brake)
+    glide: Option<Glide>,
 }
 
 impl Default for ScratchState {
@@ -95,6 +132,7 @@ impl Default for ScratchState {
             read_position: 0.0,
             smoothed_velocity: 0.0,
             interpolation: InterpolationMethod::default(),
+            glide: None,
         }
     }
 }
@@ -112,6 +150,7 @@ impl ScratchState {
         self.prev_target_position = position as f64;
         self.read_position = position as f64;
         self.smoothed_velocity = 0.0;
+        self.glide = None;
     }
 
     /// Update target scratch position (called on mouse move)
@@ -119,10 +158,47 @@ impl ScratchState {
         self.target_position = position as f64;
     }
 
+    /// Move the target scratch position by a relative amount (jog wheel ticks)
+    ///
+    /// The target is kept within `0..=max_position`. Returns the new target.
+    pub fn move_by(&mut self, delta: f64, max_position: f64) -> f64 {
+        self.target_position = (self.target_position + delta).clamp(0.0, max_position.max(0.0));
+        self.target_position
+    }
+
     /// Exit scratch mode
     pub fn end(&mut self) {
         self.active = false;
         self.smoothed_velocity = 0.0;
+        self.glide = None;
+    }
+
+    /// Let go of the platter: velocity ramps from `from` to `to` over `samples`
+    ///
+    /// While gliding, [`update`](Self::update) ignores the target position and
+    /// advances at the ramped velocity. Check [`glide_done`](Self::glide_done)
+    /// to know when the platter has reached its final speed.
+    pub fn glide(&mut self, from: f64, to: f64, samples: f64) {
+        self.smoothed_velocity = from;
+        self.glide = Some(Glide {
+            target: to,
+            step: (to - from).abs() / samples.max(1.0),
+        });
+    }
+
+    /// Whether the platter is coasting after a release
+    pub fn is_gliding(&self) -> bool {
+        self.glide.is_some()
+    }
+
+    /// Whether a release glide has reached its final velocity
+    pub fn glide_done(&self) -> bool {
+        matches!(self.glide, Some(g) if self.smoothed_velocity == g.target)
+    }
+
+    /// Current smoothed platter velocity (1.0 = normal speed)
+    pub fn velocity(&self) -> f64 {
+        self.smoothed_velocity
     }
 
     /// Set interpolation method
@@ -141,6 +217,10 @@ impl ScratchState {
     /// - velocity_ratio: playback speed (1.0 = normal, 2.0 = 2x, -1.0 = reverse normal)
     /// - read_position: starting position for this frame (continuous, no clicks)
     pub fn update(&mut self, output_len: usize) -> (bool, f64, f64) {
+        if let Some(glide) = self.glide {
+            return self.update_glide(glide, output_len);
+        }
+
         // Calculate raw velocity from target position change
         let target_delta = self.target_position - self.prev_target_position;
         let raw_velocity = target_delta / output_len as f64;
@@ -173,6 +253,26 @@ impl ScratchState {
         (true, self.smoothed_velocity, frame_start_position)
     }
 
+    /// Advance a release glide by one frame (same return value as `update`)
+    fn update_glide(&mut self, glide: Glide, output_len: usize) -> (bool, f64, f64) {
+        let max_change = glide.step * output_len as f64;
+        let remaining = glide.target - self.smoothed_velocity;
+        self.smoothed_velocity = if remaining.abs() <= max_change {
+            glide.target
+        } else {
+            self.smoothed_velocity + max_change.copysign(remaining)
+        };
+
+        let frame_start_position = self.read_position;
+        self.read_position = (self.read_position + self.smoothed_velocity * output_len as f64).max(0.0);
+        // Keep the target with the platter so grabbing it again starts from here
+        self.target_position = self.read_position;
+        self.prev_target_position = self.read_position;
+
+        let should_output = self.smoothed_velocity.abs() >= VELOCITY_THRESHOLD;
+        (should_output, self.smoothed_velocity, frame_start_position)
+    }
+
     /// Get current read position (for deck position sync)
     pub fn current_position(&self) -> usize {
         self.read_position.max(0.0) as usize
@@ -419,4 +519,38 @@ mod tests {
         // Should be close to samples[3] (0.5)
         assert!((result.left - 0.5).abs() < 0.05, "Got {}", result.left);
     }
+
+    #[test]
+    fn test_release_glide_ramps_to_target() {
+        let mut state = ScratchState::new();
+        state.start(10_000);
+        // Brake from normal speed to a halt over 1024 samples
+        state.glide(1.0, 0.0, 1024.0);
+
+        let (out, vel, pos) = state.update(256);
+        assert!(out);
+        assert!((vel - 0.75).abs() < 1e-9);
+        assert_eq!(pos, 10_000.0);
+        assert!(!state.glide_done());
+
+        for _ in 0..3 {
+            state.update(256);
+        }
+        assert!(state.glide_done());
+        assert_eq!(state.velocity(), 0.0);
+        // 0.75 + 0.5 + 0.25 buffers of travel
+        assert_eq!(state.current_position(), 10_000 + 384);
+
+        // Grabbing the platter again cancels the glide
+        state.start(state.current_position());
+        assert!(!state.is_gliding());
+    }
+
+    #[test]
+    fn test_move_by_clamps_to_track() {
+        let mut state = ScratchState::new();
+        state.start(100);
+        assert_eq!(state.move_by(-500.0, 1000.0), 0.0);
+        assert_eq!(state.move_by(2000.0, 1000.0), 1000.0);
+    }
 }
@@ -0,0 +1,132 @@
+//! Per-stem bus capture for multitrack set recording
+//!
+//! The deck sums its four stems *before* time-stretching, so the stretched
+//! deck output no longer contains the individual stems. To record stems that
+//! line up with the master recording, [`StemCapture`] runs its own time
+//! stretcher for every deck × stem (same preset as the deck stretcher, so the
+//! latency is identical) and sums each stem across all decks into four
+//! stereo buses:
+//!
+//! ```text
+//! Deck stem buffers (post-FX, pre-stretch)
+//!     │ per-stem TimeStretcher (pitch follows the deck stretcher)
+//!     │ × LUFS gain × trim × volume × crossfader × master volume
+//!     ▼
+//! Stem buses [Vocals, Drums, Bass, Other] → rtrb::Producer<StemFrame>
+//! ```
+//!
+//! The buses are post-fader but pre-EQ/filter: the channel EQ and DJ filter
+//! act on the summed deck signal and cannot be split back into stems. The
+//! master clipper and limiter are not applied either, so the stems can be
+//! re-balanced and remastered after the set.
+//!
+//! The capture is built off the audio thread (the 16 stretchers allocate)
+//! and handed to the engine as a `basedrop::Owned` so it is also freed on
+//! the GC thread when recording stops.
+
+use crate::timestretch::TimeStretcher;
+use crate::types::{StemFrame, StereoBuffer, NUM_DECKS, NUM_STEMS};
+
+use super::MAX_BUFFER_SIZE;
+
+/// Per-stem time stretchers and summing buses for multitrack recording
+pub struct StemCapture {
+    /// One stretcher per deck × stem
+    stretchers: [[TimeStretcher; NUM_STEMS]; NUM_DECKS],
+    /// Whether each deck produced stem audio in the previous buffer
+    /// (stretchers are reset when a deck goes silent so no stale tail
+    /// leaks into the next playback)
+    deck_active: [bool; NUM_DECKS],
+    /// Scratch output of a single stem stretcher
+    stretch_out: StereoBuffer,
+    /// Summed stem buses for the current buffer
+    buses: [StereoBuffer; NUM_STEMS],
+    /// Ring buffers to the stem recording threads (one per USB stick)
+    producers: Vec<rtrb::Producer<StemFrame>>,
+}
+
+impl StemCapture {
+    /// Create a stem capture feeding the given recording producers
+    ///
+    /// Allocates — call this on the UI thread, never in the audio callback.
+    pub fn new(sample_rate: u32, producers: Vec<rtrb::Producer<StemFrame>>) -> Self {
+        Self {
+            stretchers: std::array::from_fn(|_| {
+                std::array::from_fn(|_| TimeStretcher::new_with_sample_rate(sample_rate))
+            }),
+            deck_active: [false; NUM_DECKS],
+            stretch_out: StereoBuffer::silence(MAX_BUFFER_SIZE),
+            buses: std::array::from_fn(|_| StereoBuffer::silence(MAX_BUFFER_SIZE)),
+            producers,
+        }
+    }
+
+    /// Clear the buses at the start of an audio buffer
+    pub(crate) fn begin(&mut self, output_len: usize) {
+        for bus in &mut self.buses {
+            bus.set_len_from_capacity(output_len);
+            bus.fill_silence();
+        }
+        self.stretch_out.set_len_from_capacity(output_len);
+    }
+
+    /// Stretch one deck's stems and add them to the buses
+    ///
+    /// `stems` is `None` when the deck produced no stem audio this buffer
+    /// (no track, stopped, or stationary scratch).
+    pub(crate) fn add_deck(
+        &mut self,
+        deck_idx: usize,
+        stems: Option<&[StereoBuffer; NUM_STEMS]>,
+        pitch_semitones: f64,
+        gain: f32,
+    ) {
+        let Some(stems) = stems else {
+            if self.deck_active[deck_idx] {
+                for stretcher in &mut self.stretchers[deck_idx] {
+                    stretcher.reset();
+                }
+                self.deck_active[deck_idx] = false;
+            }
+            return;
+        };
+        self.deck_active[deck_idx] = true;
+
+        for (stem_idx, stem_buffer) in stems.iter().enumerate() {
+            let stretcher = &mut self.stretchers[deck_idx][stem_idx];
+            if stretcher.pitch_semitones() != pitch_semitones {
+                stretcher.set_pitch_semitones(pitch_semitones);
+            }
+            stretcher.process(stem_buffer, &mut self.stretch_out);
+
+            // Inaudible stems still run through the stretcher (keeps its
+            // history continuous) but are not summed
+            if gain == 0.0 {
+                continue;
+            }
+            let bus = self.buses[stem_idx].as_mut_slice();
+            for (out, &sample) in bus.iter_mut().zip(self.stretch_out.as_slice()) {
+                *out += sample * gain;
+            }
+        }
+    }
+
+    /// Push the buses to all recording producers
+    ///
+    /// Abandoned producers (recording thread exited) are dropped. Returns
+    /// `false` once no producer is left.
+    pub(crate) fn finish(&mut self) -> bool {
+        let buses = &self.buses;
+        let len = buses[0].len();
+        self.producers.retain_mut(|producer| {
+            for i in 0..len {
+                let frame: StemFrame = std::array::from_fn(|stem| buses[stem][i]);
+                if producer.push(frame).is_err() {
+                    break; // Buffer full — drop rather than block
+                }
+            }
+            !producer.is_abandoned()
+        });
+        !self.producers.is_empty()
+    }
+}
@@ -93,5 +93,8 @@ pub mod buffer_pool;
 pub mod resource_monitor;
 pub mod rt;
 pub mod recording;
+pub mod sampler;
+pub mod search;
+pub mod dvs;
 
 pub use types::*;
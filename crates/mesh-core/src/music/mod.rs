@@ -27,7 +27,7 @@ impl MusicalKey {
     /// - Single letter: C, D, E, F, G, A, B
     /// - With sharp: C#, D#, F#, G#, A#
     /// - With flat: Db, Eb, Gb, Ab, Bb
-    /// - Minor suffix: Am, C#m, Bbm
+    /// - Minor suffix: Am, C#m, Bbm, A minor (but not "A major")
     pub fn parse(s: &str) -> Option<Self> {
         let s = s.trim();
         if s.is_empty() {
@@ -63,13 +63,31 @@ impl MusicalKey {
         };
 
         // Check for minor suffix
-        let remaining: String = chars.collect();
-        let minor = remaining.to_lowercase().starts_with('m')
-            || remaining.to_lowercase().contains("min");
+        let remaining = chars.collect::<String>().trim().to_lowercase();
+        let minor = (remaining.starts_with('m') && !remaining.starts_with("maj"))
+            || remaining.contains("min");
 
         Some(Self { root, minor })
     }
 
+    /// Parse Camelot notation like "8A" or "11b" (A = minor, B = major)
+    pub fn parse_camelot(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s.len() < 2 || !s.is_ascii() {
+            return None;
+        }
+        let (number, letter) = s.split_at(s.len() - 1);
+        let position: u8 = number.parse().ok()?;
+        let minor = match letter {
+            "A" | "a" => true,
+            "B" | "b" => false,
+            _ => return None,
+        };
+        (0..12)
+            .map(|root| Self::new(root, minor))
+            .find(|key| key.camelot().0 == position)
+    }
+
     /// Get the relative major/minor key
     ///
     /// For minor keys: relative major is 3 semitones up
@@ -269,6 +287,22 @@ mod tests {
         assert_eq!(c.camelot(), (8, 'B'));
     }
 
+    #[test]
+    fn test_parse_camelot() {
+        assert_eq!(MusicalKey::parse_camelot("8A"), MusicalKey::parse("Am"));
+        assert_eq!(MusicalKey::parse_camelot("8B"), MusicalKey::parse("C"));
+        assert_eq!(MusicalKey::parse_camelot("12a"), MusicalKey::parse("C#m"));
+        assert_eq!(MusicalKey::parse_camelot("13A"), None);
+        assert_eq!(MusicalKey::parse_camelot("Am"), None);
+    }
+
+    #[test]
+    fn test_parse_spelled_out_scale() {
+        assert_eq!(MusicalKey::parse("A minor"), Some(MusicalKey::new(9, true)));
+        assert_eq!(MusicalKey::parse("A major"), Some(MusicalKey::new(9, false)));
+        assert_eq!(MusicalKey::parse("Ebmaj"), Some(MusicalKey::new(3, false)));
+    }
+
     #[test]
     fn test_to_string() {
         assert_eq!(MusicalKey::parse("Am").unwrap().to_string(), "Am");
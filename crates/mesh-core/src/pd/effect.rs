@@ -44,6 +44,9 @@ pub struct PdEffect {
 
     /// Effect ID (folder name)
     effect_id: String,
+
+    /// Host tempo in BPM, sent to `$0-bpm` (0 = not set yet)
+    bpm: f32,
 }
 
 impl PdEffect {
@@ -104,6 +107,7 @@ impl PdEffect {
             input_buffer,
             output_buffer,
             effect_id,
+            bpm: 0.0,
         })
     }
 
@@ -183,6 +187,10 @@ impl PdEffect {
         let bypass_value = if self.base.is_bypassed() { 0.0 } else { 1.0 };
         instance.send_float(&bypass_receiver, bypass_value)?;
 
+        if self.bpm > 0.0 {
+            instance.send_float(&format!("{}-bpm", self.dollar_zero), self.bpm)?;
+        }
+
         Ok(())
     }
 
@@ -223,6 +231,24 @@ impl PdEffect {
         instance.send_float(&receiver, if bypass { 0.0 } else { 1.0 })
     }
 
+    /// Send the host tempo to the patch
+    fn send_bpm(&self) -> PdResult<()> {
+        if self.patch_handle.is_none() {
+            return Ok(()); // Not open yet, will send on open
+        }
+
+        let instance = self.instance.lock().map_err(|_| {
+            PdError::SendFailed {
+                msg_type: "float".to_string(),
+                receiver: "bpm".to_string(),
+                reason: "Failed to lock PD instance".to_string(),
+            }
+        })?;
+
+        let receiver = format!("{}-bpm", self.dollar_zero);
+        instance.send_float(&receiver, self.bpm)
+    }
+
     /// Get the effect ID
     pub fn effect_id(&self) -> &str {
         &self.effect_id
@@ -330,6 +356,15 @@ impl Effect for PdEffect {
             log::warn!("Failed to reset PD effect params: {}", e);
         }
     }
+
+    fn set_tempo(&mut self, bpm: f64) {
+        self.bpm = bpm as f32;
+
+        // Send to PD (ignore errors during tempo updates)
+        if let Err(e) = self.send_bpm() {
+            log::warn!("Failed to send tempo to PD: {}", e);
+        }
+    }
 }
 
 impl Drop for PdEffect {
@@ -6,8 +6,8 @@
 //!
 //! No caching is needed - CozoDB queries are fast enough for real-time use.
 
-use super::{NodeId, NodeKind, PlaylistError, PlaylistNode, PlaylistStorage, TrackInfo};
-use crate::db::{DatabaseService, TrackQuery, PlaylistQuery};
+use super::{NodeId, NodeKind, PlaylistError, PlaylistNode, PlaylistStorage, SmartPlaylistRules, TrackInfo};
+use crate::db::{DatabaseService, TrackQuery, PlaylistQuery, SmartPlaylistQuery};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -40,6 +40,13 @@ impl DatabaseStorage {
             lufs: track.lufs,
             tags: Vec::new(),
             cue_count: 0,
+            album: None,
+            genre: None,
+            year: None,
+            label: None,
+            comment: None,
+            rating: 0,
+            color_label: None,
         }
     }
 
@@ -64,6 +71,57 @@ impl DatabaseStorage {
         parent_id.ok_or_else(|| PlaylistError::NotFound(node_id.0.clone()))
     }
 
+    /// Whether a playlist (by database ID) is a smart playlist
+    fn is_smart_db_id(&self, playlist_db_id: i64) -> bool {
+        SmartPlaylistQuery::get_rules(self.service.db(), playlist_db_id)
+            .ok()
+            .flatten()
+            .is_some()
+    }
+
+    /// Reject track edits on smart playlists, whose contents come from their rules
+    fn ensure_manual(&self, playlist_db_id: i64) -> Result<(), PlaylistError> {
+        if self.is_smart_db_id(playlist_db_id) {
+            return Err(PlaylistError::InvalidOperation(
+                "Smart playlist tracks are defined by its rules".into()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if a node is a smart playlist
+    pub fn is_smart_playlist(&self, id: &NodeId) -> bool {
+        self.get_playlist_db_id(id).is_ok_and(|db_id| self.is_smart_db_id(db_id))
+    }
+
+    /// Create a smart playlist under `parent` ("playlists" or a manual playlist)
+    pub fn create_smart_playlist(
+        &mut self,
+        parent: &NodeId,
+        name: &str,
+        rules: &SmartPlaylistRules,
+    ) -> Result<NodeId, PlaylistError> {
+        let id = self.create_playlist(parent, name)?;
+        let db_id = self.get_playlist_db_id(&id)?;
+        SmartPlaylistQuery::set_rules(self.service.db(), db_id, rules)
+            .map_err(|e| PlaylistError::InvalidOperation(e.to_string()))?;
+        Ok(id)
+    }
+
+    /// Get the rules of a smart playlist (None for manual playlists)
+    pub fn smart_playlist_rules(&self, id: &NodeId) -> Result<Option<SmartPlaylistRules>, PlaylistError> {
+        let db_id = self.get_playlist_db_id(id)?;
+        SmartPlaylistQuery::get_rules(self.service.db(), db_id)
+            .map_err(|e| PlaylistError::InvalidOperation(e.to_string()))
+    }
+
+    /// Replace the rules of a smart playlist
+    pub fn set_smart_playlist_rules(&mut self, id: &NodeId, rules: &SmartPlaylistRules) -> Result<(), PlaylistError> {
+        let db_id = self.get_playlist_db_id(id)?;
+        SmartPlaylistQuery::set_rules(self.service.db(), db_id, rules)
+            .map_err(|e| PlaylistError::InvalidOperation(e.to_string()))
+    }
+
     /// Get database track ID from a file path
     fn get_track_db_id(&self, track_path: &PathBuf) -> Result<i64, PlaylistError> {
         let track = TrackQuery::get_by_path(self.service.db(), track_path.to_str().unwrap_or(""))
@@ -182,9 +240,15 @@ impl PlaylistStorage for DatabaseStorage {
                     .map(|p| id.child(&p.name))
                     .collect();
 
+                let kind = if self.is_smart_db_id(playlist_db_id) {
+                    NodeKind::SmartPlaylist
+                } else {
+                    NodeKind::Playlist
+                };
+
                 return Some(PlaylistNode {
                     id: id.clone(),
-                    kind: NodeKind::Playlist,
+                    kind,
                     name: display_name,
                     children,
                     track_path: None,
@@ -231,10 +295,12 @@ impl PlaylistStorage for DatabaseStorage {
                         Ok(tracks) => {
                             log::debug!("get_tracks: found {} tracks in playlist", tracks.len());
 
-                            // Batch-load tags and cue counts for all playlist tracks
+                            // Batch-load tags, cue counts, tag metadata and ratings for all playlist tracks
                             let track_ids: Vec<i64> = tracks.iter().map(|t| t.id).collect();
                             let tags_map = self.service.get_tags_batch(&track_ids).unwrap_or_default();
                             let cue_counts = self.service.get_cue_counts_batch(&track_ids).unwrap_or_default();
+                            let meta_map = self.service.batch_get_tag_metadata(&track_ids).unwrap_or_default();
+                            let ratings = self.service.batch_get_track_ratings(&track_ids).unwrap_or_default();
 
                             // Tracks are already ordered by sort_order from DB, use enumerate for display order
                             return tracks.iter()
@@ -251,7 +317,15 @@ impl PlaylistStorage for DatabaseStorage {
                                     lufs: track.lufs,
                                     tags: tags_map.get(&track.id).cloned().unwrap_or_default(),
                                     cue_count: cue_counts.get(&track.id).copied().unwrap_or(0),
-                                })
+                                    album: None,
+                                    genre: None,
+                                    year: None,
+                                    label: None,
+                                    comment: None,
+                                    rating: 0,
+                                    color_label: None,
+                                }.with_tag_metadata(meta_map.get(&track.id))
+                                    .with_rating(ratings.get(&track.id)))
                                 .collect();
                         }
                         Err(e) => {
@@ -275,10 +349,12 @@ impl PlaylistStorage for DatabaseStorage {
             }
         };
 
-        // Batch-load tags and cue counts for all tracks in this folder
+        // Batch-load tags, cue counts, tag metadata and ratings for all tracks in this folder
         let track_ids: Vec<i64> = tracks.iter().filter_map(|t| t.id).collect();
         let tags_map = self.service.get_tags_batch(&track_ids).unwrap_or_default();
         let cue_counts = self.service.get_cue_counts_batch(&track_ids).unwrap_or_default();
+        let meta_map = self.service.batch_get_tag_metadata(&track_ids).unwrap_or_default();
+        let ratings = self.service.batch_get_track_ratings(&track_ids).unwrap_or_default();
 
         // Use enumerate for collection tracks - order represents import order
         tracks.iter()
@@ -288,6 +364,7 @@ impl PlaylistStorage for DatabaseStorage {
                 if let Some(id) = track.id {
                     info.tags = tags_map.get(&id).cloned().unwrap_or_default();
                     info.cue_count = cue_counts.get(&id).copied().unwrap_or(0);
+                    info = info.with_tag_metadata(meta_map.get(&id)).with_rating(ratings.get(&id));
                 }
                 info
             })
@@ -303,7 +380,13 @@ impl PlaylistStorage for DatabaseStorage {
         let parent_db_id = if parent.0 == "playlists" {
             None
         } else {
-            Some(self.get_playlist_db_id(parent)?)
+            let db_id = self.get_playlist_db_id(parent)?;
+            if self.is_smart_db_id(db_id) {
+                return Err(PlaylistError::InvalidOperation(
+                    "Smart playlists cannot contain playlists".into()
+                ));
+            }
+            Some(db_id)
         };
 
         // Insert into database
@@ -353,6 +436,7 @@ impl PlaylistStorage for DatabaseStorage {
 
         // Get the database IDs
         let playlist_db_id = self.get_playlist_db_id(playlist)?;
+        self.ensure_manual(playlist_db_id)?;
         let track_db_id = self.get_track_db_id(track_path)?;
 
         // Get next sort order for the playlist
@@ -377,6 +461,7 @@ impl PlaylistStorage for DatabaseStorage {
             .ok_or_else(|| PlaylistError::InvalidOperation("Invalid track ID".into()))?;
 
         let playlist_db_id = self.get_playlist_db_id(&playlist_id)?;
+        self.ensure_manual(playlist_db_id)?;
 
         let leaf = track_id.name();
         let track_db_id = leaf.parse::<i64>()
@@ -404,6 +489,8 @@ impl PlaylistStorage for DatabaseStorage {
 
         let source_playlist_db_id = self.get_playlist_db_id(&source_playlist_id)?;
         let target_playlist_db_id = self.get_playlist_db_id(target_playlist)?;
+        self.ensure_manual(source_playlist_db_id)?;
+        self.ensure_manual(target_playlist_db_id)?;
 
         let leaf = track_id.name();
         let track_db_id = leaf.parse::<i64>()
@@ -486,4 +573,27 @@ mod tests {
         let tracks = storage.get_tracks(&NodeId::tracks());
         assert!(tracks.is_empty());
     }
+
+    #[test]
+    fn test_smart_playlist_node_is_read_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = DatabaseService::in_memory(temp_dir.path()).unwrap();
+        let mut storage = DatabaseStorage::new(service).unwrap();
+
+        let rules = SmartPlaylistRules::default();
+        let id = storage.create_smart_playlist(&NodeId::playlists(), "Everything", &rules).unwrap();
+        assert_eq!(id.0, "playlists/Everything");
+        assert_eq!(storage.get_node(&id).unwrap().kind, NodeKind::SmartPlaylist);
+        assert!(storage.is_smart_playlist(&id));
+        assert_eq!(storage.smart_playlist_rules(&id).unwrap(), Some(rules));
+
+        assert!(matches!(
+            storage.create_playlist(&id, "Child"),
+            Err(PlaylistError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            storage.add_track_to_playlist(&PathBuf::from("/music/a.flac"), &id),
+            Err(PlaylistError::InvalidOperation(_))
+        ));
+    }
 }
@@ -6,8 +6,10 @@
 //! - All metadata stored in database (no filesystem symlinks)
 
 pub mod database;
+pub mod smart;
 
 pub use database::DatabaseStorage;
+pub use smart::{SmartContext, SmartMatch, SmartOrder, SmartPlaylistRules, SmartRule};
 
 use std::path::PathBuf;
 
@@ -95,6 +97,8 @@ pub enum NodeKind {
     PlaylistsRoot,
     /// A user-created playlist folder
     Playlist,
+    /// A rule-based playlist whose tracks are evaluated on every read
+    SmartPlaylist,
     /// An audio track (real file or symlink)
     Track,
 }
@@ -163,9 +167,44 @@ pub struct TrackInfo {
     pub tags: Vec<(String, Option<String>)>,
     /// Number of hot cue points set (0 = hide in browser)
     pub cue_count: u8,
+    /// Album from embedded tags
+    pub album: Option<String>,
+    /// Genre from embedded tags
+    pub genre: Option<String>,
+    /// Release year from embedded tags
+    pub year: Option<i32>,
+    /// Record label from embedded tags
+    pub label: Option<String>,
+    /// Comment from embedded tags
+    pub comment: Option<String>,
+    /// Star rating 0-5 (0 = unrated)
+    pub rating: u8,
+    /// Colour label as hex (e.g. "#d04040")
+    pub color_label: Option<String>,
 }
 
 impl TrackInfo {
+    /// Fill the embedded tag fields (album, genre, ...) from the database row
+    pub fn with_tag_metadata(mut self, meta: Option<&crate::db::TagMetadata>) -> Self {
+        if let Some(meta) = meta {
+            self.album = meta.album.clone();
+            self.genre = meta.genre.clone();
+            self.year = meta.year;
+            self.label = meta.label.clone();
+            self.comment = meta.comment.clone();
+        }
+        self
+    }
+
+    /// Fill the star rating and colour label from the database row
+    pub fn with_rating(mut self, rating: Option<&crate::db::TrackRating>) -> Self {
+        if let Some(rating) = rating {
+            self.rating = rating.rating;
+            self.color_label = rating.color.clone();
+        }
+        self
+    }
+
     /// Format duration as MM:SS
     pub fn format_duration(&self) -> String {
         self.duration
@@ -0,0 +1,423 @@
+//! Smart (rule-based) playlists
+//!
+//! A smart playlist is an ordinary row in `playlists` plus a JSON rule set in
+//! `smart_playlists`. Its tracks are never stored: every read compiles the
+//! rules to a CozoScript query and runs it, so the list follows analysis,
+//! tag and play history changes without any refresh bookkeeping. USB export
+//! writes the evaluated tracks to the stick as a normal playlist.
+
+use crate::music::MusicalKey;
+use cozo::DataValue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How the rules of a smart playlist combine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartMatch {
+    /// A track must satisfy every rule
+    #[default]
+    All,
+    /// A track must satisfy at least one rule
+    Any,
+}
+
+/// Order of the evaluated tracks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartOrder {
+    /// Alphabetical by title
+    #[default]
+    Title,
+    /// Slowest first
+    Bpm,
+    /// Most recently added first
+    Newest,
+}
+
+impl SmartOrder {
+    /// All orders, for pickers
+    pub const ALL: [SmartOrder; 3] = [SmartOrder::Title, SmartOrder::Bpm, SmartOrder::Newest];
+
+    /// CozoScript `:order` columns
+    fn order_clause(&self) -> &'static str {
+        match self {
+            SmartOrder::Title => "title",
+            SmartOrder::Bpm => "bpm, title",
+            SmartOrder::Newest => "-file_mtime, title",
+        }
+    }
+}
+
+impl std::fmt::Display for SmartOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SmartOrder::Title => write!(f, "Title"),
+            SmartOrder::Bpm => write!(f, "BPM"),
+            SmartOrder::Newest => write!(f, "Newest first"),
+        }
+    }
+}
+
+/// One condition of a smart playlist
+///
+/// Ranges are inclusive; an unset bound is open. A range rule with neither
+/// bound (or a list rule with an empty list) is ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum SmartRule {
+    /// Track BPM
+    Bpm { min: Option<f64>, max: Option<f64> },
+    /// Key is one of these (Camelot like "8A", or "Am"); matches any
+    /// notation stored in the library
+    Key { keys: Vec<String> },
+    /// Genre contains one of these (case-insensitive), from ML analysis or
+    /// the track's embedded tags
+    Genre { genres: Vec<String> },
+    /// Intensity score, 0.0–1.0
+    Intensity { min: Option<f32>, max: Option<f32> },
+    /// Has at least one of these tags
+    TagsAny { tags: Vec<String> },
+    /// Has every one of these tags
+    TagsAll { tags: Vec<String> },
+    /// Drop loudness in LUFS
+    Lufs { min: Option<f32>, max: Option<f32> },
+    /// Vocal stem share of the track's energy, 0.0–1.0
+    Vocals { min: Option<f32>, max: Option<f32> },
+    /// Number of times the track was played in mesh-player
+    PlayCount { min: Option<u32>, max: Option<u32> },
+    /// Added to the collection in the last N days
+    AddedWithinDays { days: u32 },
+    /// Not played in the current (or most recent) mesh-player session
+    NotPlayedInSession,
+}
+
+/// A smart playlist's rule set, stored as JSON in `smart_playlists`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartPlaylistRules {
+    pub match_mode: SmartMatch,
+    pub rules: Vec<SmartRule>,
+    pub order: SmartOrder,
+    /// Keep only the first N tracks
+    pub limit: Option<usize>,
+}
+
+/// Library facts the rules are compiled against
+#[derive(Debug, Clone, Default)]
+pub struct SmartContext {
+    /// Distinct key strings stored in `tracks` (only needed for key rules)
+    pub library_keys: Vec<String>,
+    /// Current Unix time in seconds
+    pub now_secs: i64,
+    /// Session for "not played in session" (None matches every track)
+    pub session_id: Option<i64>,
+}
+
+/// Parse a key in Camelot or musical notation
+fn parse_any_key(s: &str) -> Option<MusicalKey> {
+    MusicalKey::parse_camelot(s).or_else(|| MusicalKey::parse(s))
+}
+
+/// Append inclusive range filters on `column`; returns false if both bounds are unset
+pub(crate) fn push_range(
+    filters: &mut Vec<String>,
+    params: &mut BTreeMap<String, DataValue>,
+    prefix: &str,
+    column: &str,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> bool {
+    if let Some(min) = min {
+        filters.push(format!("{} >= ${}_min", column, prefix));
+        params.insert(format!("{}_min", prefix), DataValue::from(min));
+    }
+    if let Some(max) = max {
+        filters.push(format!("{} <= ${}_max", column, prefix));
+        params.insert(format!("{}_max", prefix), DataValue::from(max));
+    }
+    min.is_some() || max.is_some()
+}
+
+pub(crate) fn str_list(items: &[String]) -> DataValue {
+    DataValue::List(items.iter().map(|s| DataValue::Str(s.as_str().into())).collect())
+}
+
+impl SmartRule {
+    /// Whether this rule constrains anything
+    pub fn is_active(&self) -> bool {
+        match self {
+            SmartRule::Bpm { min, max } => min.is_some() || max.is_some(),
+            SmartRule::Intensity { min, max }
+            | SmartRule::Lufs { min, max }
+            | SmartRule::Vocals { min, max } => min.is_some() || max.is_some(),
+            SmartRule::PlayCount { min, max } => min.is_some() || max.is_some(),
+            SmartRule::Key { keys } => !keys.is_empty(),
+            SmartRule::Genre { genres } => !genres.is_empty(),
+            SmartRule::TagsAny { tags } | SmartRule::TagsAll { tags } => !tags.is_empty(),
+            SmartRule::AddedWithinDays { .. } | SmartRule::NotPlayedInSession => true,
+        }
+    }
+
+    /// Rule bodies binding `id`; several bodies are alternatives (OR)
+    pub(crate) fn bodies(&self, prefix: &str, ctx: &SmartContext, params: &mut BTreeMap<String, DataValue>) -> Vec<String> {
+        let opt = |v: Option<f32>| v.map(f64::from);
+        let mut filters = Vec::new();
+        match self {
+            SmartRule::Bpm { min, max } => {
+                push_range(&mut filters, params, prefix, "bpm", *min, *max);
+                vec![format!("*tracks{{id, bpm}}, !is_null(bpm), {}", filters.join(", "))]
+            }
+            SmartRule::Lufs { min, max } => {
+                push_range(&mut filters, params, prefix, "lufs", opt(*min), opt(*max));
+                vec![format!("*tracks{{id, lufs}}, !is_null(lufs), {}", filters.join(", "))]
+            }
+            SmartRule::Intensity { min, max } => {
+                push_range(&mut filters, params, prefix, "intensity", opt(*min), opt(*max));
+                vec![format!("*intensity_score{{track_id: id, intensity}}, {}", filters.join(", "))]
+            }
+            SmartRule::Vocals { min, max } => {
+                push_range(&mut filters, params, prefix, "vocal_density", opt(*min), opt(*max));
+                vec![format!("*stem_energy{{track_id: id, vocal_density}}, {}", filters.join(", "))]
+            }
+            SmartRule::PlayCount { min, max } => {
+                push_range(&mut filters, params, prefix, "n", min.map(f64::from), max.map(f64::from));
+                vec![format!("play_count[id, n], {}", filters.join(", "))]
+            }
+            SmartRule::Key { keys } => {
+                // Keys are stored in whatever notation the analyser or the
+                // imported tags used, so match on the Camelot position of
+                // every distinct library key
+                let wanted: Vec<(u8, char)> = keys.iter()
+                    .filter_map(|k| parse_any_key(k))
+                    .map(|k| k.camelot())
+                    .collect();
+                let matching: Vec<String> = ctx.library_keys.iter()
+                    .filter(|k| parse_any_key(k).is_some_and(|key| wanted.contains(&key.camelot())))
+                    .cloned()
+                    .collect();
+                params.insert(format!("{}_keys", prefix), str_list(&matching));
+                vec![format!("*tracks{{id, key}}, is_in(key, ${}_keys)", prefix)]
+            }
+            SmartRule::Genre { genres } => {
+                let mut bodies = Vec::new();
+                for (i, genre) in genres.iter().enumerate() {
+                    let param = format!("{}_g{}", prefix, i);
+                    params.insert(param.clone(), DataValue::Str(genre.to_lowercase().into()));
+                    bodies.push(format!(
+                        "*ml_analysis{{track_id: id, top_genre}}, !is_null(top_genre), str_includes(lowercase(top_genre), ${})",
+                        param
+                    ));
+                    bodies.push(format!(
+                        "*tag_metadata{{track_id: id, genre}}, !is_null(genre), str_includes(lowercase(genre), ${})",
+                        param
+                    ));
+                }
+                bodies
+            }
+            SmartRule::TagsAny { tags } => {
+                // Same match as DatabaseService::find_tracks_by_tags_any
+                params.insert(format!("{}_tags", prefix), str_list(tags));
+                vec![format!("*track_tags{{track_id: id, label}}, is_in(label, ${}_tags)", prefix)]
+            }
+            SmartRule::TagsAll { tags } => {
+                // Same match as DatabaseService::find_tracks_by_tags_all
+                let atoms: Vec<String> = tags.iter().enumerate().map(|(i, tag)| {
+                    let param = format!("{}_t{}", prefix, i);
+                    params.insert(param.clone(), DataValue::Str(tag.as_str().into()));
+                    format!("*track_tags{{track_id: id, label: ${}}}", param)
+                }).collect();
+                vec![atoms.join(", ")]
+            }
+            SmartRule::AddedWithinDays { days } => {
+                let since = ctx.now_secs - i64::from(*days) * 86_400;
+                params.insert(format!("{}_since", prefix), DataValue::from(since));
+                vec![format!("*tracks{{id, file_mtime}}, file_mtime >= ${}_since", prefix)]
+            }
+            SmartRule::NotPlayedInSession => match ctx.session_id {
+                Some(session) => {
+                    params.insert("session".to_string(), DataValue::from(session));
+                    vec!["*tracks{id}, not session_played[id]".to_string()]
+                }
+                None => vec!["*tracks{id}".to_string()],
+            },
+        }
+    }
+}
+
+/// Append the play history rules referenced by rule bodies
+///
+/// `play_count` is only defined when asked for; `session_played` whenever a
+/// body bound the `$session` parameter.
+pub(crate) fn push_support_rules(script: &mut String, params: &BTreeMap<String, DataValue>, play_count: bool) {
+    if play_count {
+        // Plays are recorded by path; tracks never played get an explicit 0
+        script.push_str(concat!(
+            "plays[id, count(loaded_at)] := *tracks{id, path}, ",
+            "*track_plays{track_path: path, loaded_at, play_started_at}, !is_null(play_started_at)\n",
+            "played[id] := plays[id, n]\n",
+            "play_count[id, n] := plays[id, n]\n",
+            "play_count[id, n] := *tracks{id}, not played[id], n = 0\n",
+        ));
+    }
+    if params.contains_key("session") {
+        script.push_str(concat!(
+            "session_played[id] := *tracks{id, path}, ",
+            "*track_plays{session_id: $session, track_path: path, play_started_at}, !is_null(play_started_at)\n",
+        ));
+    }
+}
+
+impl SmartPlaylistRules {
+    /// Whether any rule needs the library's distinct key strings
+    pub fn uses_keys(&self) -> bool {
+        self.rules.iter().any(|r| matches!(r, SmartRule::Key { .. }) && r.is_active())
+    }
+
+    /// Whether any rule needs the current session
+    pub fn uses_session(&self) -> bool {
+        self.rules.iter().any(|r| matches!(r, SmartRule::NotPlayedInSession))
+    }
+
+    /// Compile the rules to CozoScript rules defining `matched[id]`
+    ///
+    /// The caller appends the entry query that joins `matched` with
+    /// `tracks`, plus [`Self::order_and_limit`].
+    pub fn compile(&self, ctx: &SmartContext) -> (String, BTreeMap<String, DataValue>) {
+        let mut script = String::new();
+        let mut params = BTreeMap::new();
+        let mut names = Vec::new();
+
+        let active: Vec<&SmartRule> = self.rules.iter().filter(|r| r.is_active()).collect();
+        for (i, rule) in active.iter().enumerate() {
+            let name = format!("rule_{}", i);
+            for body in rule.bodies(&name, ctx, &mut params) {
+                script.push_str(&format!("{}[id] := {}\n", name, body));
+            }
+            names.push(name);
+        }
+
+        let uses_play_count = active.iter().any(|r| matches!(r, SmartRule::PlayCount { .. }));
+        push_support_rules(&mut script, &params, uses_play_count);
+
+        if names.is_empty() {
+            script.push_str("matched[id] := *tracks{id}\n");
+        } else if self.match_mode == SmartMatch::All {
+            let atoms: Vec<String> = names.iter().map(|n| format!("{}[id]", n)).collect();
+            script.push_str(&format!("matched[id] := {}\n", atoms.join(", ")));
+        } else {
+            for name in &names {
+                script.push_str(&format!("matched[id] := {}[id]\n", name));
+            }
+        }
+
+        (script, params)
+    }
+
+    /// `:order` / `:limit` options for the entry query
+    pub fn order_and_limit(&self) -> String {
+        let mut options = format!(":order {}", self.order.order_clause());
+        if let Some(limit) = self.limit {
+            options.push_str(&format!("\n:limit {}", limit));
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_json_roundtrip() {
+        let rules = SmartPlaylistRules {
+            match_mode: SmartMatch::Any,
+            rules: vec![
+                SmartRule::Bpm { min: Some(120.0), max: None },
+                SmartRule::TagsAll { tags: vec!["Peak".into(), "Vocal".into()] },
+                SmartRule::NotPlayedInSession,
+            ],
+            order: SmartOrder::Newest,
+            limit: Some(50),
+        };
+        let json = serde_json::to_string(&rules).unwrap();
+        let parsed: SmartPlaylistRules = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, rules);
+
+        // Missing fields fall back to defaults
+        let parsed: SmartPlaylistRules = serde_json::from_str("{}").unwrap();
+        assert_eq!(parsed, SmartPlaylistRules::default());
+    }
+
+    #[test]
+    fn test_compile_all_joins_rules() {
+        let rules = SmartPlaylistRules {
+            rules: vec![
+                SmartRule::Bpm { min: Some(120.0), max: Some(130.0) },
+                SmartRule::Lufs { min: None, max: None }, // inactive, skipped
+                SmartRule::Intensity { min: Some(0.5), max: None },
+            ],
+            ..Default::default()
+        };
+        let (script, params) = rules.compile(&SmartContext::default());
+        assert!(script.contains("rule_0[id] := *tracks{id, bpm}, !is_null(bpm), bpm >= $rule_0_min, bpm <= $rule_0_max"));
+        assert!(script.contains("rule_1[id] := *intensity_score{track_id: id, intensity}, intensity >= $rule_1_min"));
+        assert!(script.contains("matched[id] := rule_0[id], rule_1[id]"));
+        assert!(!script.contains("lufs"));
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn test_compile_any_and_empty() {
+        let rules = SmartPlaylistRules {
+            match_mode: SmartMatch::Any,
+            rules: vec![
+                SmartRule::TagsAny { tags: vec!["Warmup".into()] },
+                SmartRule::PlayCount { min: None, max: Some(0) },
+            ],
+            ..Default::default()
+        };
+        let (script, _) = rules.compile(&SmartContext::default());
+        assert!(script.contains("matched[id] := rule_0[id]\n"));
+        assert!(script.contains("matched[id] := rule_1[id]\n"));
+        assert!(script.contains("play_count[id, n] := *tracks{id}, not played[id], n = 0"));
+
+        let (script, params) = SmartPlaylistRules::default().compile(&SmartContext::default());
+        assert_eq!(script, "matched[id] := *tracks{id}\n");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_key_rule_matches_any_notation() {
+        let rules = SmartPlaylistRules {
+            rules: vec![SmartRule::Key { keys: vec!["8A".into(), "C".into()] }],
+            ..Default::default()
+        };
+        let ctx = SmartContext {
+            library_keys: vec!["Am".into(), "8A".into(), "8B".into(), "9A".into()],
+            ..Default::default()
+        };
+        let (_, params) = rules.compile(&ctx);
+        let DataValue::List(keys) = &params["rule_0_keys"] else { panic!("keys param missing") };
+        let keys: Vec<&str> = keys.iter().filter_map(|k| k.get_str()).collect();
+        // 8A == Am, C major == 8B
+        assert_eq!(keys, vec!["Am", "8A", "8B"]);
+    }
+
+    #[test]
+    fn test_session_rule_without_session_matches_all() {
+        let rules = SmartPlaylistRules {
+            rules: vec![SmartRule::NotPlayedInSession],
+            ..Default::default()
+        };
+        let (script, _) = rules.compile(&SmartContext::default());
+        assert!(script.contains("rule_0[id] := *tracks{id}\n"));
+        assert!(!script.contains("session_played"));
+
+        let ctx = SmartContext { session_id: Some(42), ..Default::default() };
+        let (script, params) = rules.compile(&ctx);
+        assert!(script.contains("not session_played[id]"));
+        assert!(script.contains("session_played[id] :="));
+        assert_eq!(params["session"], DataValue::from(42_i64));
+    }
+}
@@ -175,5 +175,9 @@ fn create_effect(
                 .create_effect(&spec.plugin_id)
                 .map_err(|e| format!("PD create_effect failed: {}", e))
         }
+        EffectSourceType::Native => {
+            crate::effect::native::create_native_effect(&spec.plugin_id)
+                .ok_or_else(|| format!("Unknown native effect '{}'", spec.plugin_id))
+        }
     }
 }
@@ -83,6 +83,8 @@ pub enum EffectSourceType {
     Pd,
     /// CLAP plugin
     Clap,
+    /// Built-in Rust effect
+    Native,
 }
 
 /// Specification for creating a single effect.
@@ -91,7 +93,7 @@ pub enum EffectSourceType {
 /// without any trait objects.
 #[derive(Debug, Clone)]
 pub struct EffectBuildSpec {
-    /// Plugin identifier (folder name for PD, plugin ID for CLAP)
+    /// Plugin identifier (folder name for PD, plugin ID for CLAP, native effect id)
     pub plugin_id: String,
     /// Effect source type
     pub source: EffectSourceType,
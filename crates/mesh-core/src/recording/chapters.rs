@@ -0,0 +1,274 @@
+//! Embed chapter markers into finished recordings
+//!
+//! - **WAV**: a `cue ` chunk with one cue point per chapter plus a
+//!   `LIST`/`adtl` chunk with `labl` names, appended after the audio data
+//!   (read by most DAWs and editors as markers/regions).
+//! - **FLAC**: `CHAPTERxxx` / `CHAPTERxxxNAME` Vorbis comments written into
+//!   the PADDING block reserved by the recorder, so the audio frames never
+//!   move. Files without enough padding are left unchanged.
+
+use super::tracklist::Chapter;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// FLAC metadata block types
+const FLAC_BLOCK_PADDING: u8 = 1;
+const FLAC_BLOCK_VORBIS_COMMENT: u8 = 4;
+
+/// Write chapter markers into a WAV or FLAC file (chosen by extension)
+pub(super) fn embed_chapters(path: &Path, sample_rate: u32, chapters: &[Chapter]) -> Result<(), String> {
+    if chapters.is_empty() {
+        return Ok(());
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("wav") => embed_wav_chapters(path, sample_rate, chapters),
+        Some("flac") => embed_flac_chapters(path, chapters),
+        _ => Err(format!("Unsupported file type for chapters: {}", path.display())),
+    }
+}
+
+/// Build the `cue ` and `LIST`/`adtl` chunks for a WAV file
+fn wav_chapter_chunks(sample_rate: u32, chapters: &[Chapter]) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    // cue chunk: count + 24 bytes per cue point
+    out.extend_from_slice(b"cue ");
+    out.extend_from_slice(&(4 + 24 * chapters.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(chapters.len() as u32).to_le_bytes());
+    for (i, chapter) in chapters.iter().enumerate() {
+        let sample_offset = (chapter.start_secs * sample_rate as f64).round() as u32;
+        out.extend_from_slice(&(i as u32 + 1).to_le_bytes()); // cue point ID
+        out.extend_from_slice(&0u32.to_le_bytes()); // play order position
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&0u32.to_le_bytes()); // chunk start
+        out.extend_from_slice(&0u32.to_le_bytes()); // block start
+        out.extend_from_slice(&sample_offset.to_le_bytes());
+    }
+
+    // LIST/adtl chunk with one NUL-terminated label per cue point
+    let mut adtl = Vec::new();
+    adtl.extend_from_slice(b"adtl");
+    for (i, chapter) in chapters.iter().enumerate() {
+        let text = chapter.title.as_bytes();
+        let size = 4 + text.len() as u32 + 1;
+        adtl.extend_from_slice(b"labl");
+        adtl.extend_from_slice(&size.to_le_bytes());
+        adtl.extend_from_slice(&(i as u32 + 1).to_le_bytes());
+        adtl.extend_from_slice(text);
+        adtl.push(0);
+        // Chunks are word-aligned
+        if size % 2 == 1 {
+            adtl.push(0);
+        }
+    }
+    out.extend_from_slice(b"LIST");
+    out.extend_from_slice(&(adtl.len() as u32).to_le_bytes());
+    out.extend_from_slice(&adtl);
+    out
+}
+
+fn embed_wav_chapters(path: &Path, sample_rate: u32, chapters: &[Chapter]) -> Result<(), String> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path).map_err(|e| e.to_string())?;
+
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).map_err(|e| e.to_string())?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Err(format!("Not a WAV file: {}", path.display()));
+    }
+
+    let mut len = file.seek(SeekFrom::End(0)).map_err(|e| e.to_string())?;
+    if len % 2 == 1 {
+        file.write_all(&[0]).map_err(|e| e.to_string())?;
+        len += 1;
+    }
+    let chunks = wav_chapter_chunks(sample_rate, chapters);
+    let riff_size = len + chunks.len() as u64 - 8;
+    if riff_size > u32::MAX as u64 {
+        return Err("WAV file too large to add chapters".to_string());
+    }
+    file.write_all(&chunks).map_err(|e| e.to_string())?;
+
+    // Update the RIFF size to include the new chunks
+    file.seek(SeekFrom::Start(4)).map_err(|e| e.to_string())?;
+    file.write_all(&(riff_size as u32).to_le_bytes()).map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())
+}
+
+/// Format seconds as `HH:MM:SS.mmm` (Vorbis comment chapter format)
+fn format_chapter_time(secs: f64) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    let h = total_ms / 3_600_000;
+    let m = (total_ms / 60_000) % 60;
+    let s = (total_ms / 1000) % 60;
+    let ms = total_ms % 1000;
+    format!("{h:02}:{m:02}:{s:02}.{ms:03}")
+}
+
+/// Build a VORBIS_COMMENT block body with `CHAPTERxxx` tags
+fn vorbis_chapter_comments(chapters: &[Chapter]) -> Vec<u8> {
+    let vendor = b"Mesh";
+    let mut comments = Vec::with_capacity(chapters.len() * 2);
+    for (i, chapter) in chapters.iter().enumerate() {
+        comments.push(format!("CHAPTER{:03}={}", i + 1, format_chapter_time(chapter.start_secs)));
+        comments.push(format!("CHAPTER{:03}NAME={}", i + 1, chapter.title));
+    }
+
+    // Vorbis comment lengths are little-endian (unlike the FLAC block headers)
+    let mut out = Vec::new();
+    out.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    out.extend_from_slice(vendor);
+    out.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+    for comment in &comments {
+        out.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+        out.extend_from_slice(comment.as_bytes());
+    }
+    out
+}
+
+fn embed_flac_chapters(path: &Path, chapters: &[Chapter]) -> Result<(), String> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path).map_err(|e| e.to_string())?;
+
+    let mut marker = [0u8; 4];
+    file.read_exact(&mut marker).map_err(|e| e.to_string())?;
+    if &marker != b"fLaC" {
+        return Err(format!("Not a FLAC file: {}", path.display()));
+    }
+
+    // Walk the metadata blocks looking for PADDING
+    let mut offset = 4u64;
+    let (padding_offset, padding_len, padding_is_last) = loop {
+        let mut block_header = [0u8; 4];
+        file.read_exact(&mut block_header).map_err(|e| e.to_string())?;
+        let is_last = block_header[0] & 0x80 != 0;
+        let block_type = block_header[0] & 0x7F;
+        let len = u32::from_be_bytes([0, block_header[1], block_header[2], block_header[3]]) as u64;
+        if block_type == FLAC_BLOCK_PADDING {
+            break (offset, len, is_last);
+        }
+        if is_last {
+            return Err("No padding block reserved for chapters".to_string());
+        }
+        offset += 4 + len;
+        file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+    };
+
+    let body = vorbis_chapter_comments(chapters);
+    let body_len = body.len() as u64;
+    // Either the comments fill the padding exactly, or a smaller PADDING
+    // block (with its own 4-byte header) follows them
+    let remaining = if body_len == padding_len {
+        None
+    } else if body_len + 4 <= padding_len {
+        Some(padding_len - body_len - 4)
+    } else {
+        return Err(format!(
+            "Chapter comments ({body_len} bytes) don't fit the reserved padding ({padding_len} bytes)"
+        ));
+    };
+
+    let mut block = Vec::with_capacity(4 + padding_len as usize);
+    let comment_last = if remaining.is_none() && padding_is_last { 0x80 } else { 0 };
+    block.push(comment_last | FLAC_BLOCK_VORBIS_COMMENT);
+    block.extend_from_slice(&(body_len as u32).to_be_bytes()[1..]);
+    block.extend_from_slice(&body);
+    if let Some(rest) = remaining {
+        let padding_last = if padding_is_last { 0x80 } else { 0 };
+        block.push(padding_last | FLAC_BLOCK_PADDING);
+        block.extend_from_slice(&(rest as u32).to_be_bytes()[1..]);
+        block.resize(block.len() + rest as usize, 0);
+    }
+
+    file.seek(SeekFrom::Start(padding_offset)).map_err(|e| e.to_string())?;
+    file.write_all(&block).map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapters() -> Vec<Chapter> {
+        vec![
+            Chapter { start_secs: 0.0, title: "Intro".to_string() },
+            Chapter { start_secs: 62.25, title: "Artist - Track".to_string() },
+        ]
+    }
+
+    #[test]
+    fn test_chapter_time_format() {
+        assert_eq!(format_chapter_time(0.0), "00:00:00.000");
+        assert_eq!(format_chapter_time(3723.5), "01:02:03.500");
+    }
+
+    #[test]
+    fn test_wav_chunks_layout() {
+        let chunks = wav_chapter_chunks(48000, &chapters());
+        assert_eq!(&chunks[0..4], b"cue ");
+        assert_eq!(u32::from_le_bytes(chunks[4..8].try_into().unwrap()), 4 + 24 * 2);
+        // Second cue point sample offset (header 12 + first point 24 + 20)
+        let second_offset = u32::from_le_bytes(chunks[56..60].try_into().unwrap());
+        assert_eq!(second_offset, (62.25 * 48000.0) as u32);
+        // All chunks are word-aligned
+        assert_eq!(chunks.len() % 2, 0);
+        let list_start = 8 + 4 + 24 * 2;
+        assert_eq!(&chunks[list_start..list_start + 4], b"LIST");
+        assert_eq!(&chunks[list_start + 8..list_start + 12], b"adtl");
+    }
+
+    #[test]
+    fn test_embed_wav_updates_riff_size() {
+        let dir = std::env::temp_dir().join(format!("mesh-chapters-wav-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("set.wav");
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for _ in 0..48000 * 2 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        embed_chapters(&path, 48000, &chapters()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let riff_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size, bytes.len() - 8);
+        // Audio is still readable
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.duration(), 48000);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_embed_flac_uses_reserved_padding() {
+        let dir = std::env::temp_dir().join(format!("mesh-chapters-flac-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("set.flac");
+        let mut writer = super::super::flac::FlacStreamWriter::create(&path, 48000, 16).unwrap();
+        for _ in 0..8192 {
+            writer.write_frame(0, 0).unwrap();
+        }
+        writer.finalize().unwrap();
+        let before = std::fs::read(&path).unwrap();
+
+        embed_chapters(&path, 48000, &chapters()).unwrap();
+        let after = std::fs::read(&path).unwrap();
+
+        // File size and audio frames are unchanged
+        assert_eq!(before.len(), after.len());
+        let frames_start = 42 + 4 + super::super::flac::CHAPTER_PADDING_LEN;
+        assert_eq!(before[frames_start..], after[frames_start..]);
+        // VORBIS_COMMENT (not last) replaces the padding header
+        assert_eq!(after[42], FLAC_BLOCK_VORBIS_COMMENT);
+        let text = String::from_utf8_lossy(&after[42..frames_start]);
+        assert!(text.contains("CHAPTER002=00:01:02.250"));
+        assert!(text.contains("CHAPTER002NAME=Artist - Track"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
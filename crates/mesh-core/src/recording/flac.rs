@@ -0,0 +1,264 @@
+//! Streaming FLAC writer for set recordings
+//!
+//! `flacenc::encode_with_fixed_block_size` needs the whole signal up front,
+//! which doesn't work for a recording of unknown length. Instead we write
+//! the `fLaC` marker and STREAMINFO block ourselves, encode one fixed-size
+//! frame at a time with `flacenc::encode_fixed_size_frame`, and rewrite the
+//! STREAMINFO block on finalize with the real sample count and frame sizes.
+//!
+//! Until finalize, STREAMINFO reports an unknown total sample count, which
+//! decoders accept — so partial recordings remain playable after a crash.
+//!
+//! A PADDING block is reserved after STREAMINFO so chapter markers can be
+//! written into the file after recording without rewriting the audio.
+
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Size of the STREAMINFO metadata block body in bytes
+const STREAMINFO_LEN: usize = 34;
+
+/// Size of the PADDING block reserved for chapter markers (fits ~99 chapters)
+pub(super) const CHAPTER_PADDING_LEN: usize = 16 * 1024;
+
+/// Incremental FLAC encoder writing fixed-size frames to a file
+pub struct FlacStreamWriter {
+    file: BufWriter<File>,
+    /// Number of interleaved channels (2 for the master, 8 for stems)
+    channels: usize,
+    config: flacenc::error::Verified<flacenc::config::Encoder>,
+    stream_info: flacenc::component::StreamInfo,
+    framebuf: flacenc::source::FrameBuf,
+    /// Interleaved samples waiting for a full block
+    pending: Vec<i32>,
+    block_size: usize,
+    sample_rate: u32,
+    bits_per_sample: u32,
+    frame_number: usize,
+    /// Samples per channel written so far
+    total_samples: u64,
+    min_frame_bytes: u32,
+    max_frame_bytes: u32,
+    /// Bytes written to the file so far (header + encoded frames)
+    bytes_written: u64,
+}
+
+impl FlacStreamWriter {
+    /// Create the file and write the stream header
+    pub fn create(
+        path: &Path,
+        sample_rate: u32,
+        channels: usize,
+        bits_per_sample: u32,
+    ) -> Result<Self, String> {
+        let config = flacenc::config::Encoder::default()
+            .into_verified()
+            .map_err(|e| format!("FLAC encoder config error: {e:?}"))?;
+        let block_size = config.block_size;
+        let stream_info = flacenc::component::StreamInfo::new(
+            sample_rate as usize,
+            channels,
+            bits_per_sample as usize,
+        )
+        .map_err(|e| format!("FLAC stream info error: {e:?}"))?;
+        let framebuf = flacenc::source::FrameBuf::with_size(channels, block_size)
+            .map_err(|e| format!("FLAC frame buffer error: {e:?}"))?;
+
+        let file = File::create(path).map_err(|e| format!("Failed to create FLAC file: {e}"))?;
+        let mut writer = Self {
+            file: BufWriter::with_capacity(128 * 1024, file),
+            channels,
+            config,
+            stream_info,
+            framebuf,
+            pending: Vec::with_capacity(block_size * channels),
+            block_size,
+            sample_rate,
+            bits_per_sample,
+            frame_number: 0,
+            total_samples: 0,
+            min_frame_bytes: u32::MAX,
+            max_frame_bytes: 0,
+            bytes_written: 0,
+        };
+
+        let mut header = writer.stream_header();
+        // PADDING block (last metadata block) reserved for chapter markers
+        header.push(0x80 | 1);
+        header.extend_from_slice(&(CHAPTER_PADDING_LEN as u32).to_be_bytes()[1..]);
+        header.resize(header.len() + CHAPTER_PADDING_LEN, 0);
+        writer.write_bytes(&header)?;
+        Ok(writer)
+    }
+
+    /// Queue one frame of `channels` samples (already scaled to the target bit depth)
+    pub fn write_frame(&mut self, frame: &[i32]) -> Result<(), String> {
+        debug_assert_eq!(frame.len(), self.channels);
+        self.pending.extend_from_slice(frame);
+        self.total_samples += 1;
+        if self.pending.len() == self.block_size * self.channels {
+            self.encode_pending()?;
+        }
+        Ok(())
+    }
+
+    /// Bytes written to disk so far (excludes the partially filled block)
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Flush buffered I/O to the file
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.file.flush().map_err(|e| e.to_string())
+    }
+
+    /// Encode the final block and rewrite STREAMINFO with the real totals
+    pub fn finalize(mut self) -> Result<(), String> {
+        if !self.pending.is_empty() {
+            // Pad the last block with silence: flacenc produces malformed
+            // frames for a short final block (flacenc-rs#242). At most one
+            // block (~85ms at 48kHz) of silence is appended.
+            let padding = self.block_size - self.pending.len() / self.channels;
+            self.pending.resize(self.block_size * self.channels, 0);
+            self.total_samples += padding as u64;
+            self.encode_pending()?;
+        }
+
+        let header = self.stream_header();
+        self.file.flush().map_err(|e| e.to_string())?;
+        let file = self.file.get_mut();
+        file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        file.write_all(&header).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())
+    }
+
+    fn encode_pending(&mut self) -> Result<(), String> {
+        self.framebuf
+            .fill_interleaved(&self.pending)
+            .map_err(|e| format!("FLAC frame buffer error: {e:?}"))?;
+        let frame = flacenc::encode_fixed_size_frame(
+            &self.config,
+            &self.framebuf,
+            self.frame_number,
+            &self.stream_info,
+        )
+        .map_err(|e| format!("FLAC encoding failed: {e:?}"))?;
+
+        let mut sink = flacenc::bitsink::ByteSink::new();
+        frame
+            .write(&mut sink)
+            .map_err(|e| format!("FLAC frame write failed: {e:?}"))?;
+        let bytes = sink.as_slice();
+        self.min_frame_bytes = self.min_frame_bytes.min(bytes.len() as u32);
+        self.max_frame_bytes = self.max_frame_bytes.max(bytes.len() as u32);
+        self.write_bytes(bytes)?;
+
+        self.frame_number += 1;
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.file.write_all(bytes).map_err(|e| e.to_string())?;
+        self.bytes_written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// `fLaC` marker followed by a STREAMINFO block (always the same length,
+    /// so it can be rewritten in place on finalize)
+    fn stream_header(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + 4 + STREAMINFO_LEN);
+        out.extend_from_slice(b"fLaC");
+        // Metadata block header: type 0 (STREAMINFO), not last (PADDING follows) + length
+        out.push(0x00);
+        out.extend_from_slice(&(STREAMINFO_LEN as u32).to_be_bytes()[1..]);
+
+        let block_size = self.block_size as u16;
+        out.extend_from_slice(&block_size.to_be_bytes());
+        out.extend_from_slice(&block_size.to_be_bytes());
+
+        // Frame sizes: 0 = unknown (until at least one frame is written)
+        let (min_frame, max_frame) = if self.max_frame_bytes == 0 {
+            (0, 0)
+        } else {
+            (self.min_frame_bytes, self.max_frame_bytes)
+        };
+        out.extend_from_slice(&min_frame.to_be_bytes()[1..]);
+        out.extend_from_slice(&max_frame.to_be_bytes()[1..]);
+
+        // 20 bits sample rate | 3 bits (channels - 1) | 5 bits (bps - 1) | 36 bits total samples
+        let packed: u64 = ((self.sample_rate as u64 & 0xF_FFFF) << 44)
+            | (((self.channels as u64 - 1) & 0x7) << 41)
+            | (((self.bits_per_sample as u64 - 1) & 0x1F) << 36)
+            | (self.total_samples & 0xF_FFFF_FFFF);
+        out.extend_from_slice(&packed.to_be_bytes());
+
+        // MD5 signature: all zeros = not computed
+        out.extend_from_slice(&[0u8; 16]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_flac_header_totals() {
+        let dir = std::env::temp_dir().join(format!("mesh-flac-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.flac");
+
+        let mut writer = FlacStreamWriter::create(&path, 48000, 2, 16).unwrap();
+        let block_size = writer.block_size as u64;
+        let frames = 10_000u64;
+        for i in 0..frames {
+            let v = ((i as f32 * 0.05).sin() * 8000.0) as i32;
+            writer.write_frame(&[v, -v]).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[0..4], b"fLaC");
+        // STREAMINFO packed fields start at offset 8 + 10
+        let packed = u64::from_be_bytes(bytes[18..26].try_into().unwrap());
+        assert_eq!(packed >> 44, 48000);
+        assert_eq!((packed >> 41) & 0x7, 1);
+        assert_eq!((packed >> 36) & 0x1F, 15);
+        // Total includes the silence padding of the final block
+        assert_eq!(packed & 0xF_FFFF_FFFF, frames.div_ceil(block_size) * block_size);
+        // PADDING block is the last metadata block
+        assert_eq!(bytes[42], 0x81);
+        // First frame sync code follows the padding
+        let frame_start = 42 + 4 + CHAPTER_PADDING_LEN;
+        assert_eq!(bytes[frame_start], 0xFF);
+        assert_eq!(bytes[frame_start + 1] & 0xFC, 0xF8);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_streaming_flac_eight_channels() {
+        let dir = std::env::temp_dir().join(format!("mesh-flac-8ch-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stems.flac");
+
+        let mut writer = FlacStreamWriter::create(&path, 44100, 8, 24).unwrap();
+        for i in 0..5_000 {
+            let v = ((i as f32 * 0.01).sin() * 100_000.0) as i32;
+            writer.write_frame(&[v, v, -v, -v, v / 2, v / 2, 0, 0]).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        let packed = u64::from_be_bytes(bytes[18..26].try_into().unwrap());
+        assert_eq!(packed >> 44, 44100);
+        assert_eq!((packed >> 41) & 0x7, 7);
+        assert_eq!((packed >> 36) & 0x1F, 23);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,4 @@
-//! Set recording — capture master output to WAV files
+//! Set recording — capture master output to WAV or FLAC files
 //!
 //! # Architecture
 //!
@@ -9,7 +9,7 @@
 //!     │ rtrb::Producer<StereoSample> (never blocks)
 //!     ▼
 //! Recording Thread (normal priority)
-//!     │ hound::WavWriter with periodic flush()
+//!     │ hound::WavWriter / FlacStreamWriter with periodic flush()
 //!     ▼
 //! USB Stick (mesh-recordings/YYYY-MM-DD_HH-MM.wav)
 //! ```
@@ -17,10 +17,32 @@
 //! The ring buffer absorbs I/O stalls (2 seconds capacity).
 //! If the buffer fills (writer fell behind), samples are dropped
 //! rather than blocking the audio thread.
+//!
+//! The file format (16/24-bit or float WAV, 16/24-bit FLAC) and the split
+//! policy come from [`RecordingConfig`]. A recording starts as a single
+//! `YYYY-MM-DD_HH-MM.flac`; once it actually splits, that file is renamed to
+//! `..._part01.flac` and the following parts continue as `..._part02.flac`, …
+//!
+//! When a recording stops, [`generate_tracklist`] writes TXT, CUE and JSON
+//! tracklists next to it and can embed the tracks as chapter markers.
+//!
+//! # Multitrack (stem) recording
+//!
+//! With [`RecordingStems`] enabled, a second ring buffer carries the summed
+//! per-stem buses captured by `engine::StemCapture` (all vocals, all drums,
+//! all bass, all other across the decks). A second thread writes them next
+//! to the master recording, either as one 8-channel file in mesh's stem
+//! layout (`..._stems.flac`) or as one stereo file per stem
+//! (`..._(Vocals).wav`, … — the naming mesh-cue's stem import expects).
 
+mod chapters;
+mod flac;
+mod tracklist;
 mod writer;
 
-use crate::types::StereoSample;
+use crate::config::{RecordingConfig, RecordingStems};
+use crate::types::{Stem, StemFrame, StereoSample};
+use tracklist::TracklistEntry;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
@@ -32,17 +54,41 @@ use std::thread::JoinHandle;
 pub enum RecordingEvent {
     /// Recording started successfully
     Started {
-        /// Path to the WAV file being written
+        /// Path to the (first) file being written. Moves to its `_part01`
+        /// name if the recording splits (see `FirstPartRenamed`).
+        path: PathBuf,
+    },
+    /// The recording split and its first file was renamed to `_part01`
+    /// (sent just before the `PartStarted` of part 2)
+    FirstPartRenamed {
+        /// Path reported by `Started`
+        from: PathBuf,
+        /// Numbered path the first part now lives at
+        to: PathBuf,
+    },
+    /// A split boundary was reached and a new part file was opened
+    PartStarted {
+        /// Path to the new part file
         path: PathBuf,
+        /// 1-based part number
+        part: usize,
     },
     /// Recording stopped normally
     Stopped {
-        /// Path to the completed WAV file
+        /// Path to the first completed file
         path: PathBuf,
         /// Duration in seconds
         duration_secs: f64,
         /// Path to the tracklist file (if generated)
         tracklist_path: Option<PathBuf>,
+        /// All completed part files in order (a single entry when not split)
+        parts: Vec<RecordingPart>,
+    },
+    /// Stem recording stopped normally (sent in addition to `Stopped`)
+    StemsStopped {
+        /// All completed stem files in part order (four per part when each
+        /// stem has its own file)
+        parts: Vec<RecordingPart>,
     },
     /// Recording failed (I/O error, disk full, etc.)
     Error {
@@ -53,24 +99,110 @@ pub enum RecordingEvent {
     },
 }
 
+/// One completed file of a (possibly split) recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingPart {
+    /// Path to the part file
+    pub path: PathBuf,
+    /// Offset of the part's first sample from the start of the recording
+    pub start_secs: f64,
+    /// Length of the part in seconds
+    pub duration_secs: f64,
+    /// Sample rate of the part file (for placing chapter markers)
+    pub sample_rate: u32,
+}
+
+/// Output location and naming for a recording's part files
+#[derive(Debug, Clone)]
+pub(crate) struct RecordingTarget {
+    /// Directory holding the recording (`mesh-recordings/`)
+    dir: PathBuf,
+    /// Base filename without extension (e.g. `2025-06-01_22-30`)
+    stem: String,
+    /// Appended after the part number (e.g. `_stems`, `_(Vocals)`)
+    suffix: String,
+    /// File extension for the configured format
+    extension: &'static str,
+}
+
+impl RecordingTarget {
+    fn new(dir: PathBuf, stem: String, config: &RecordingConfig) -> Self {
+        Self {
+            dir,
+            stem,
+            suffix: String::new(),
+            extension: config.format.extension(),
+        }
+    }
+
+    /// Same recording, with `suffix` appended to every part filename
+    fn with_suffix(&self, suffix: &str) -> Self {
+        Self {
+            suffix: suffix.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Targets for the stem files of this recording (one target for a
+    /// single 8-channel file, otherwise one per stem)
+    fn stem_targets(&self, stems: RecordingStems) -> Vec<RecordingTarget> {
+        match stems {
+            RecordingStems::Off => Vec::new(),
+            RecordingStems::Multichannel => vec![self.with_suffix("_stems")],
+            RecordingStems::SeparateFiles => Stem::ALL
+                .iter()
+                .map(|stem| self.with_suffix(&format!("_({})", stem.name())))
+                .collect(),
+        }
+    }
+
+    /// Path of the 1-based part file while it is being written
+    ///
+    /// The first part has no part number, so recordings that never split
+    /// keep a plain filename. It moves to [`Self::numbered_part_path`] when
+    /// the second part opens.
+    pub(crate) fn part_path(&self, part: usize) -> PathBuf {
+        if part > 1 {
+            self.numbered_part_path(part)
+        } else {
+            self.dir.join(format!("{}{}.{}", self.stem, self.suffix, self.extension))
+        }
+    }
+
+    /// Path of the 1-based part file with its part number
+    pub(crate) fn numbered_part_path(&self, part: usize) -> PathBuf {
+        self.dir.join(format!("{}_part{:02}{}.{}", self.stem, part, self.suffix, self.extension))
+    }
+
+    /// Whether any file of this recording already exists
+    fn exists(&self) -> bool {
+        self.part_path(1).exists() || self.numbered_part_path(1).exists()
+    }
+}
+
 /// Receiver type for recording events (Arc<Mutex<>> for iced subscription)
 pub type RecordingEventReceiver = Arc<std::sync::Mutex<mpsc::Receiver<RecordingEvent>>>;
 
 /// Handle to an active recording on a single USB stick
 ///
-/// Each `RecordingHandle` owns one recording thread writing to one WAV file.
-/// Multiple handles can be active simultaneously (one per USB stick).
+/// Each `RecordingHandle` owns one recording thread writing one recording
+/// (one or more part files). Multiple handles can be active simultaneously
+/// (one per USB stick).
 pub struct RecordingHandle {
-    /// Path to the WAV file being written
+    /// Path to the first file being written. Not updated when the recording
+    /// splits and the file moves to its `_part01` name; follow
+    /// `RecordingEvent::FirstPartRenamed` for the current location.
     pub path: PathBuf,
     /// Stop flag — set to true to gracefully stop recording
     stop_flag: Arc<AtomicBool>,
     /// Thread handle (joined on drop)
     thread: Option<JoinHandle<()>>,
+    /// Stem recording thread (multitrack mode only, joined on drop)
+    stem_thread: Option<JoinHandle<()>>,
 }
 
 impl RecordingHandle {
-    /// Signal the recording thread to stop and finalize the WAV file
+    /// Signal the recording thread to stop and finalize the current file
     pub fn stop(&self) {
         self.stop_flag.store(true, Ordering::Release);
     }
@@ -79,7 +211,7 @@ impl RecordingHandle {
 impl Drop for RecordingHandle {
     fn drop(&mut self) {
         self.stop_flag.store(true, Ordering::Release);
-        if let Some(handle) = self.thread.take() {
+        for handle in [self.thread.take(), self.stem_thread.take()].into_iter().flatten() {
             let _ = handle.join();
         }
     }
@@ -89,26 +221,33 @@ impl Drop for RecordingHandle {
 /// A 2-hour WAV at 48kHz/16-bit/stereo is ~1.32 GB.
 const MIN_FREE_SPACE_BYTES: u64 = 2_000_000_000;
 
-/// Start recording master output to a WAV file on the given USB stick.
+/// Start recording master output on the given USB stick.
 ///
-/// Creates `{usb_mount}/mesh-recordings/YYYY-MM-DD_HH-MM.wav` and spawns
-/// a recording thread that reads from the returned ring buffer producer.
+/// Creates `{usb_mount}/mesh-recordings/YYYY-MM-DD_HH-MM.{wav,flac}` (with
+/// `_partNN` suffixes once the recording splits) and spawns a recording
+/// thread that reads from the returned ring buffer producer.
+///
+/// When `config.stems` is enabled, a second thread writes the stem files and
+/// a stem producer is returned as well; the UI combines the stem producers of
+/// all sticks into one `engine::StemCapture`.
 ///
 /// # Arguments
 /// * `usb_mount` — Mount point of the USB stick (e.g., `/media/user/MESH_USB`)
 /// * `sample_rate` — Audio sample rate (44100 or 48000)
 /// * `available_bytes` — Free space on the USB stick in bytes
+/// * `config` — File format, split policy and stem mode
 /// * `event_tx` — Channel to send recording events back to the UI
 ///
 /// # Returns
-/// * `(rtrb::Producer<StereoSample>, RecordingHandle)` — the producer goes to
-///   the audio thread, the handle stays with the UI
+/// * `(master producer, stem producer, RecordingHandle)` — the producers go
+///   to the audio thread, the handle stays with the UI
 pub fn start_recording(
     usb_mount: &Path,
     sample_rate: u32,
     available_bytes: u64,
+    config: RecordingConfig,
     event_tx: mpsc::Sender<RecordingEvent>,
-) -> Result<(rtrb::Producer<StereoSample>, RecordingHandle), String> {
+) -> Result<(rtrb::Producer<StereoSample>, Option<rtrb::Producer<StemFrame>>, RecordingHandle), String> {
     // Pre-flight disk space check
     if available_bytes < MIN_FREE_SPACE_BYTES {
         let avail_gb = available_bytes as f64 / 1_000_000_000.0;
@@ -125,37 +264,66 @@ pub fn start_recording(
     // Generate timestamped filename
     let now = chrono::Local::now();
     let filename = now.format("%Y-%m-%d_%H-%M").to_string();
-    let wav_path = recordings_dir.join(format!("{filename}.wav"));
+    let target = RecordingTarget::new(recordings_dir.clone(), filename.clone(), &config);
 
     // Avoid overwriting: append suffix if file exists
-    let wav_path = if wav_path.exists() {
+    let target = if target.exists() {
         let mut suffix = 1u32;
         loop {
-            let candidate = recordings_dir.join(format!("{filename}_{suffix}.wav"));
+            let candidate = RecordingTarget::new(
+                recordings_dir.clone(),
+                format!("{filename}_{suffix}"),
+                &config,
+            );
             if !candidate.exists() {
                 break candidate;
             }
             suffix += 1;
         }
     } else {
-        wav_path
+        target
     };
+    let first_path = target.part_path(1);
 
     // Ring buffer: 2 seconds of stereo audio
     let capacity = sample_rate as usize * 2;
     let (producer, consumer) = rtrb::RingBuffer::<StereoSample>::new(capacity);
 
     let stop_flag = Arc::new(AtomicBool::new(false));
-    let stop_clone = Arc::clone(&stop_flag);
-    let path_clone = wav_path.clone();
 
+    // Stem thread first: if it fails to spawn, no master thread is left running
+    let stem_targets = target.stem_targets(config.stems);
+    let (stem_producer, stem_thread) = if stem_targets.is_empty() {
+        (None, None)
+    } else {
+        let (stem_producer, stem_consumer) = rtrb::RingBuffer::<StemFrame>::new(capacity);
+        let stop_clone = Arc::clone(&stop_flag);
+        let event_tx = event_tx.clone();
+        let thread = std::thread::Builder::new()
+            .name("stem-recorder".to_string())
+            .spawn(move || {
+                writer::recording_thread(
+                    stem_consumer,
+                    stem_targets,
+                    sample_rate,
+                    config,
+                    stop_clone,
+                    event_tx,
+                );
+            })
+            .map_err(|e| format!("Failed to spawn stem recording thread: {e}"))?;
+        (Some(stem_producer), Some(thread))
+    };
+
+    let stop_clone = Arc::clone(&stop_flag);
     let thread = std::thread::Builder::new()
         .name("set-recorder".to_string())
         .spawn(move || {
             writer::recording_thread(
                 consumer,
-                &path_clone,
+                vec![target],
                 sample_rate,
+                config,
                 stop_clone,
                 event_tx,
             );
@@ -163,33 +331,48 @@ pub fn start_recording(
         .map_err(|e| format!("Failed to spawn recording thread: {e}"))?;
 
     let handle = RecordingHandle {
-        path: wav_path,
+        path: first_path,
         stop_flag,
         thread: Some(thread),
+        stem_thread,
     };
 
-    Ok((producer, handle))
+    Ok((producer, stem_producer, handle))
 }
 
-/// Generate a tracklist TXT file from session history.
+/// Generate tracklist files from session history.
 ///
 /// Queries the `track_plays` DB relation for all tracks played during the
-/// recording window and writes a formatted tracklist next to the WAV file.
+/// recording window and writes, next to the first part:
+///
+/// - `.txt` — human-readable tracklist (grouped by part for split recordings,
+///   with offsets relative to each part file)
+/// - `.cue` — CUE sheet with one `FILE` per part and an `INDEX` per track
+/// - `.json` — full per-track metadata (BPM, key, deck, load/suggestion source)
+///
+/// Track start times come from `play_started_at` (falling back to the load
+/// time for tracks that were never started). When `embed_chapters` is set,
+/// the tracks are also written into each part file as chapter markers.
 ///
 /// # Arguments
-/// * `wav_path` — Path to the WAV file (tracklist goes next to it with .txt extension)
+/// * `parts` — Completed part files in order (from `RecordingEvent::Stopped`)
 /// * `recording_start_ms` — Unix timestamp (ms) when recording started
 /// * `recording_end_ms` — Unix timestamp (ms) when recording stopped
 /// * `session_id` — Session ID to filter plays
+/// * `embed_chapters` — Also write chapter markers into the recording files
 /// * `db` — Database service to query play history from
+///
+/// # Returns
+/// Path to the TXT tracklist (None if nothing was played or writing failed)
 pub fn generate_tracklist(
-    wav_path: &Path,
+    parts: &[RecordingPart],
     recording_start_ms: i64,
     recording_end_ms: i64,
     session_id: i64,
+    embed_chapters: bool,
     db: &crate::db::DatabaseService,
 ) -> Option<PathBuf> {
-    let txt_path = wav_path.with_extension("txt");
+    let first_part = parts.first()?.path.clone();
 
     // Query all track plays from this session that overlap with the recording window
     let plays = match query_plays_in_window(db, session_id, recording_start_ms, recording_end_ms) {
@@ -205,28 +388,47 @@ pub fn generate_tracklist(
         return None;
     }
 
+    let entries: Vec<TracklistEntry> = plays
+        .into_iter()
+        .map(|play| play.into_entry(recording_start_ms, db))
+        .collect();
+
     // Format tracklist
     let duration_secs = (recording_end_ms - recording_start_ms) as f64 / 1000.0;
     let duration_str = format_duration(duration_secs);
     let start_time = chrono::DateTime::from_timestamp_millis(recording_start_ms)
         .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
         .unwrap_or_else(|| "Unknown".to_string());
+    let title = format!("Mesh Set Recording — {start_time}");
 
-    let mut content = format!(
-        "Mesh Set Recording — {start_time}\nDuration: {duration_str}\n\n"
-    );
-
-    for play in &plays {
-        let offset_ms = play.loaded_at.saturating_sub(recording_start_ms).max(0);
-        let offset_str = format_duration(offset_ms as f64 / 1000.0);
-        content.push_str(&format!(
-            "{}  {} [Deck {}]\n",
-            offset_str,
-            play.track_name,
-            play.deck_index + 1,
-        ));
+    let mut content = format!("{title}\nDuration: {duration_str}\n\n");
+    content.push_str(&tracklist::format_txt_body(parts, &entries));
+
+    let cue_path = tracklist::tracklist_path(&first_part, "cue");
+    let cue = tracklist::format_cue(parts, &entries, &title);
+    match std::fs::write(&cue_path, cue) {
+        Ok(()) => log::info!("[RECORDING] CUE sheet written: {}", cue_path.display()),
+        Err(e) => log::warn!("[RECORDING] Failed to write CUE sheet: {e}"),
+    }
+
+    let json_path = tracklist::tracklist_path(&first_part, "json");
+    let json = tracklist::format_json(parts, &entries, &title, recording_start_ms, duration_secs)
+        .and_then(|json| std::fs::write(&json_path, json).map_err(|e| e.to_string()));
+    match json {
+        Ok(()) => log::info!("[RECORDING] JSON tracklist written: {}", json_path.display()),
+        Err(e) => log::warn!("[RECORDING] Failed to write JSON tracklist: {e}"),
+    }
+
+    if embed_chapters {
+        let chapters = tracklist::chapters_per_part(parts, &entries);
+        for (part, part_chapters) in parts.iter().zip(&chapters) {
+            if let Err(e) = chapters::embed_chapters(&part.path, part.sample_rate, part_chapters) {
+                log::warn!("[RECORDING] Failed to embed chapters in {}: {e}", part.path.display());
+            }
+        }
     }
 
+    let txt_path = tracklist::tracklist_path(&first_part, "txt");
     match std::fs::write(&txt_path, &content) {
         Ok(()) => {
             log::info!("[RECORDING] Tracklist written: {}", txt_path.display());
@@ -243,11 +445,58 @@ pub fn generate_tracklist(
 #[derive(Debug)]
 struct TrackPlayEntry {
     track_name: String,
+    track_path: String,
+    track_id: Option<i64>,
     deck_index: u8,
+    load_source: String,
+    suggestion_score: Option<f64>,
+    suggestion_tags_json: Option<String>,
     loaded_at: i64,
+    play_started_at: Option<i64>,
+    play_ended_at: Option<i64>,
+    seconds_played: Option<f64>,
+}
+
+impl TrackPlayEntry {
+    /// When the track became audible (play time, or load time if never started)
+    fn start_ms(&self) -> i64 {
+        self.play_started_at.unwrap_or(self.loaded_at)
+    }
+
+    /// Whether the play overlaps the recording window: started inside it, or
+    /// started before and still playing when the recording began
+    fn overlaps(&self, start_ms: i64, end_ms: i64) -> bool {
+        let started = self.start_ms();
+        if started > end_ms {
+            return false;
+        }
+        started >= start_ms
+            || (self.play_started_at.is_some() && self.play_ended_at.map_or(true, |ended| ended > start_ms))
+    }
+
+    /// Resolve collection metadata (title/artist/BPM/key) for the tracklist
+    fn into_entry(self, recording_start_ms: i64, db: &crate::db::DatabaseService) -> TracklistEntry {
+        let track = self.track_id.and_then(|id| db.get_track(id).ok().flatten());
+        let offset_ms = self.start_ms().saturating_sub(recording_start_ms).max(0);
+        TracklistEntry {
+            title: track.as_ref().map(|t| t.title.clone()),
+            artist: track.as_ref().and_then(|t| t.artist.clone()),
+            bpm: track.as_ref().and_then(|t| t.bpm),
+            key: track.as_ref().and_then(|t| t.key.clone()),
+            track_name: self.track_name,
+            track_path: self.track_path,
+            deck_index: self.deck_index,
+            load_source: self.load_source,
+            suggestion_score: self.suggestion_score,
+            suggestion_tags_json: self.suggestion_tags_json,
+            offset_secs: offset_ms as f64 / 1000.0,
+            played: self.play_started_at.is_some(),
+            seconds_played: self.seconds_played,
+        }
+    }
 }
 
-/// Query track plays that fall within the recording time window
+/// Query track plays that overlap the recording time window
 fn query_plays_in_window(
     db: &crate::db::DatabaseService,
     session_id: i64,
@@ -259,23 +508,39 @@ fn query_plays_in_window(
 
     let mut params = BTreeMap::new();
     params.insert("session_id".to_string(), DataValue::from(session_id));
-    params.insert("start_ms".to_string(), DataValue::from(start_ms));
     params.insert("end_ms".to_string(), DataValue::from(end_ms));
 
     let result = db.run_query(r#"
-        ?[track_name, deck_index, loaded_at] :=
-            *track_plays{session_id: $session_id, loaded_at, track_name, deck_index},
-            loaded_at >= $start_ms,
+        ?[track_name, deck_index, loaded_at, track_path, track_id, load_source,
+          suggestion_score, suggestion_tags_json, play_started_at, play_ended_at, seconds_played] :=
+            *track_plays{session_id: $session_id, loaded_at, track_name, deck_index, track_path,
+                         track_id, load_source, suggestion_score, suggestion_tags_json,
+                         play_started_at, play_ended_at, seconds_played},
             loaded_at <= $end_ms
         :order loaded_at
     "#, params).map_err(|e| e.to_string())?;
 
-    let plays = result.rows.iter().filter_map(|row| {
+    let mut plays: Vec<TrackPlayEntry> = result.rows.iter().filter_map(|row| {
         let track_name = row.get(0)?.get_str()?.to_string();
         let deck_index = row.get(1)?.get_int()? as u8;
         let loaded_at = row.get(2)?.get_int()?;
-        Some(TrackPlayEntry { track_name, deck_index, loaded_at })
-    }).collect();
+        Some(TrackPlayEntry {
+            track_name,
+            deck_index,
+            loaded_at,
+            track_path: row.get(3).and_then(|v| v.get_str()).unwrap_or_default().to_string(),
+            track_id: row.get(4).and_then(|v| v.get_int()),
+            load_source: row.get(5).and_then(|v| v.get_str()).unwrap_or_default().to_string(),
+            suggestion_score: row.get(6).and_then(|v| v.get_float()),
+            suggestion_tags_json: row.get(7).and_then(|v| v.get_str()).map(|s| s.to_string()),
+            play_started_at: row.get(8).and_then(|v| v.get_int()),
+            play_ended_at: row.get(9).and_then(|v| v.get_int()),
+            seconds_played: row.get(10).and_then(|v| v.get_float()),
+        })
+    })
+    .filter(|play| play.overlaps(start_ms, end_ms))
+    .collect();
+    plays.sort_by_key(|play| play.start_ms());
 
     Ok(plays)
 }
@@ -288,3 +553,73 @@ fn format_duration(secs: f64) -> String {
     let s = total % 60;
     format!("{h:02}:{m:02}:{s:02}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_naming() {
+        let dir = PathBuf::from("/usb/mesh-recordings");
+        let single = RecordingTarget::new(dir.clone(), "2025-06-01_22-30".into(), &RecordingConfig {
+            format: crate::config::RecordingFormat::Wav24,
+            split: crate::config::RecordingSplit::Off,
+            ..Default::default()
+        });
+        assert_eq!(single.part_path(1), dir.join("2025-06-01_22-30.wav"));
+
+        let split = RecordingTarget::new(dir.clone(), "2025-06-01_22-30".into(), &RecordingConfig {
+            format: crate::config::RecordingFormat::Flac16,
+            split: crate::config::RecordingSplit::Minutes60,
+            ..Default::default()
+        });
+        // Only split recordings get part numbers, starting with the second part
+        assert_eq!(split.part_path(1), dir.join("2025-06-01_22-30.flac"));
+        assert_eq!(split.numbered_part_path(1), dir.join("2025-06-01_22-30_part01.flac"));
+        assert_eq!(split.part_path(2), dir.join("2025-06-01_22-30_part02.flac"));
+
+        let stems = split.stem_targets(RecordingStems::Multichannel);
+        assert_eq!(stems.len(), 1);
+        assert_eq!(stems[0].part_path(1), dir.join("2025-06-01_22-30_stems.flac"));
+        assert_eq!(stems[0].numbered_part_path(1), dir.join("2025-06-01_22-30_part01_stems.flac"));
+
+        let stems = single.stem_targets(RecordingStems::SeparateFiles);
+        assert_eq!(stems.len(), 4);
+        assert_eq!(stems[0].part_path(1), dir.join("2025-06-01_22-30_(Vocals).wav"));
+        assert_eq!(stems[3].part_path(1), dir.join("2025-06-01_22-30_(Other).wav"));
+        assert!(single.stem_targets(RecordingStems::Off).is_empty());
+    }
+
+    fn play(loaded_at: i64, play_started_at: Option<i64>, play_ended_at: Option<i64>) -> TrackPlayEntry {
+        TrackPlayEntry {
+            track_name: "Track".to_string(),
+            track_path: String::new(),
+            track_id: None,
+            deck_index: 0,
+            load_source: "browser".to_string(),
+            suggestion_score: None,
+            suggestion_tags_json: None,
+            loaded_at,
+            play_started_at,
+            play_ended_at,
+            seconds_played: None,
+        }
+    }
+
+    #[test]
+    fn test_play_window_overlap() {
+        // Started inside the window
+        assert!(play(900, Some(1500), None).overlaps(1000, 5000));
+        // Loaded inside, never started (still listed in the TXT)
+        assert!(play(1200, None, None).overlaps(1000, 5000));
+        // Started before the recording and still playing at its start
+        assert!(play(100, Some(200), Some(2000)).overlaps(1000, 5000));
+        assert!(play(100, Some(200), None).overlaps(1000, 5000));
+        // Finished before the recording started
+        assert!(!play(100, Some(200), Some(900)).overlaps(1000, 5000));
+        // Loaded before, never started
+        assert!(!play(100, None, None).overlaps(1000, 5000));
+        // Started after the recording stopped
+        assert!(!play(4000, Some(6000), None).overlaps(1000, 5000));
+    }
+}
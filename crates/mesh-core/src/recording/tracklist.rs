@@ -0,0 +1,410 @@
+//! Tracklist formatting — TXT, CUE sheet and JSON exports
+//!
+//! All formats are built from the same list of [`TracklistEntry`] values
+//! (one per track play, sorted by start time) and the recording's parts.
+//! Offsets are measured from the start of the recording; for split
+//! recordings they are mapped onto the part file each track starts in.
+
+use super::{format_duration, RecordingPart};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// CUE sheet time base (frames per second, from the Red Book CD standard)
+const CUE_FRAMES_PER_SEC: f64 = 75.0;
+
+/// Maximum number of tracks a CUE sheet may contain
+const CUE_MAX_TRACKS: usize = 99;
+
+/// One track in a recording's tracklist
+#[derive(Debug, Clone, Default)]
+pub(super) struct TracklistEntry {
+    /// Name recorded at load time (fallback when the track isn't in the DB)
+    pub track_name: String,
+    pub track_path: String,
+    /// Title/artist/BPM/key from the collection database (if the track is known)
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub bpm: Option<f64>,
+    pub key: Option<String>,
+    /// 0-based deck index
+    pub deck_index: u8,
+    /// How the track was loaded ("browser", "suggestion", …)
+    pub load_source: String,
+    pub suggestion_score: Option<f64>,
+    pub suggestion_tags_json: Option<String>,
+    /// Start of the track relative to the recording start (seconds, ≥ 0)
+    pub offset_secs: f64,
+    /// Whether play was actually pressed (false = loaded only)
+    pub played: bool,
+    pub seconds_played: Option<f64>,
+}
+
+impl TracklistEntry {
+    /// Title for CUE/chapters (DB title, falling back to the load-time name)
+    fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.track_name)
+    }
+}
+
+/// A chapter marker inside one part file
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Chapter {
+    /// Start relative to the beginning of the part file (seconds)
+    pub start_secs: f64,
+    pub title: String,
+}
+
+/// Part index and offset within that part for a recording offset
+fn locate_part(parts: &[RecordingPart], offset_secs: f64) -> (usize, f64) {
+    // Last part starting at or before this offset (first part if none)
+    let index = parts
+        .iter()
+        .rposition(|p| p.start_secs <= offset_secs)
+        .unwrap_or(0);
+    let start = parts.get(index).map_or(0.0, |p| p.start_secs);
+    (index, (offset_secs - start).max(0.0))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Tracklist base path for a recording: next to the first part, without the
+/// `_part01` suffix (`2025-06-01_22-30_part01.flac` → `2025-06-01_22-30.txt`)
+pub(super) fn tracklist_path(first_part: &Path, extension: &str) -> PathBuf {
+    let stem = first_part
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = stem.strip_suffix("_part01").unwrap_or(&stem);
+    first_part.with_file_name(format!("{stem}.{extension}"))
+}
+
+/// Format the TXT track lines, grouped by part for split recordings
+pub(super) fn format_txt_body(parts: &[RecordingPart], entries: &[TracklistEntry]) -> String {
+    let mut content = String::new();
+    let mut current_part = None;
+
+    for entry in entries {
+        let mut line_offset = entry.offset_secs;
+        if parts.len() > 1 {
+            let (index, part_offset) = locate_part(parts, entry.offset_secs);
+            if current_part != Some(index) {
+                let part = &parts[index];
+                if current_part.is_some() {
+                    content.push('\n');
+                }
+                content.push_str(&format!(
+                    "Part {} — {} (starts at {})\n",
+                    index + 1,
+                    file_name(&part.path),
+                    format_duration(part.start_secs),
+                ));
+                current_part = Some(index);
+            }
+            line_offset = part_offset;
+        }
+
+        content.push_str(&format!(
+            "{}  {} [Deck {}]\n",
+            format_duration(line_offset),
+            entry.track_name,
+            entry.deck_index + 1,
+        ));
+    }
+    content
+}
+
+/// Format seconds as a CUE `MM:SS:FF` timestamp (75 frames per second)
+fn format_cue_time(secs: f64) -> String {
+    let frames = (secs.max(0.0) * CUE_FRAMES_PER_SEC).round() as u64;
+    let m = frames / (75 * 60);
+    let s = (frames / 75) % 60;
+    let f = frames % 75;
+    format!("{m:02}:{s:02}:{f:02}")
+}
+
+/// CUE strings are double-quoted with no escape mechanism
+fn cue_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "'"))
+}
+
+/// Format a CUE sheet with one `FILE` per part and an `INDEX 01` per
+/// played track (loaded-but-never-played tracks are skipped)
+pub(super) fn format_cue(parts: &[RecordingPart], entries: &[TracklistEntry], title: &str) -> String {
+    let mut content = String::new();
+    content.push_str("REM COMMENT \"Generated by Mesh\"\n");
+    content.push_str("PERFORMER \"Mesh\"\n");
+    content.push_str(&format!("TITLE {}\n", cue_quote(title)));
+
+    let played: Vec<&TracklistEntry> = entries.iter().filter(|e| e.played).collect();
+    if played.len() > CUE_MAX_TRACKS {
+        log::warn!(
+            "[RECORDING] {} tracks played — CUE sheet limited to the first {}",
+            played.len(), CUE_MAX_TRACKS
+        );
+    }
+
+    let mut current_part = None;
+    // Last INDEX written per file, in CUE frames — indices must increase
+    let mut last_frames: Option<u64> = None;
+    for (number, entry) in played.iter().take(CUE_MAX_TRACKS).enumerate() {
+        let (index, part_offset) = locate_part(parts, entry.offset_secs);
+        if current_part != Some(index) {
+            if let Some(part) = parts.get(index) {
+                content.push_str(&format!("FILE {} WAVE\n", cue_quote(&file_name(&part.path))));
+            }
+            current_part = Some(index);
+            last_frames = None;
+        }
+
+        // Two tracks starting within the same frame (e.g. both decks started
+        // together): push the later one forward by a frame
+        let mut frames = (part_offset * CUE_FRAMES_PER_SEC).round() as u64;
+        if let Some(last) = last_frames {
+            frames = frames.max(last + 1);
+        }
+        last_frames = Some(frames);
+
+        content.push_str(&format!("  TRACK {:02} AUDIO\n", number + 1));
+        content.push_str(&format!("    TITLE {}\n", cue_quote(entry.display_title())));
+        if let Some(ref artist) = entry.artist {
+            content.push_str(&format!("    PERFORMER {}\n", cue_quote(artist)));
+        }
+        content.push_str(&format!(
+            "    INDEX 01 {}\n",
+            format_cue_time(frames as f64 / CUE_FRAMES_PER_SEC)
+        ));
+    }
+    content
+}
+
+/// JSON tracklist document
+#[derive(Debug, Serialize)]
+struct JsonTracklist<'a> {
+    title: &'a str,
+    started_at_ms: i64,
+    duration_secs: f64,
+    parts: Vec<JsonPart>,
+    tracks: Vec<JsonTrack<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonPart {
+    file: String,
+    start_secs: f64,
+    duration_secs: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonTrack<'a> {
+    position: usize,
+    offset_secs: f64,
+    /// 1-based part number the track starts in
+    part: usize,
+    part_offset_secs: f64,
+    title: &'a str,
+    artist: Option<&'a str>,
+    path: &'a str,
+    bpm: Option<f64>,
+    key: Option<&'a str>,
+    /// 1-based deck number
+    deck: u8,
+    load_source: &'a str,
+    suggestion_score: Option<f64>,
+    suggestion_tags: Option<serde_json::Value>,
+    played: bool,
+    seconds_played: Option<f64>,
+}
+
+/// Format the full per-track metadata as pretty-printed JSON
+pub(super) fn format_json(
+    parts: &[RecordingPart],
+    entries: &[TracklistEntry],
+    title: &str,
+    started_at_ms: i64,
+    duration_secs: f64,
+) -> Result<String, String> {
+    let doc = JsonTracklist {
+        title,
+        started_at_ms,
+        duration_secs,
+        parts: parts
+            .iter()
+            .map(|p| JsonPart {
+                file: file_name(&p.path),
+                start_secs: p.start_secs,
+                duration_secs: p.duration_secs,
+            })
+            .collect(),
+        tracks: entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let (part, part_offset_secs) = locate_part(parts, e.offset_secs);
+                JsonTrack {
+                    position: i + 1,
+                    offset_secs: e.offset_secs,
+                    part: part + 1,
+                    part_offset_secs,
+                    title: e.display_title(),
+                    artist: e.artist.as_deref(),
+                    path: &e.track_path,
+                    bpm: e.bpm,
+                    key: e.key.as_deref(),
+                    deck: e.deck_index + 1,
+                    load_source: &e.load_source,
+                    suggestion_score: e.suggestion_score,
+                    suggestion_tags: e
+                        .suggestion_tags_json
+                        .as_deref()
+                        .and_then(|s| serde_json::from_str(s).ok()),
+                    played: e.played,
+                    seconds_played: e.seconds_played,
+                }
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())
+}
+
+/// Chapter markers for each part (same order as `parts`)
+pub(super) fn chapters_per_part(parts: &[RecordingPart], entries: &[TracklistEntry]) -> Vec<Vec<Chapter>> {
+    let mut chapters = vec![Vec::new(); parts.len()];
+    for entry in entries.iter().filter(|e| e.played) {
+        let (index, start_secs) = locate_part(parts, entry.offset_secs);
+        let title = match entry.artist {
+            Some(ref artist) => format!("{} - {}", artist, entry.display_title()),
+            None => entry.display_title().to_string(),
+        };
+        if let Some(list) = chapters.get_mut(index) {
+            list.push(Chapter { start_secs, title });
+        }
+    }
+    chapters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(name: &str, start_secs: f64, duration_secs: f64) -> RecordingPart {
+        RecordingPart {
+            path: PathBuf::from(format!("/usb/mesh-recordings/{name}")),
+            start_secs,
+            duration_secs,
+            sample_rate: 48000,
+        }
+    }
+
+    fn entry(name: &str, offset_secs: f64) -> TracklistEntry {
+        TracklistEntry {
+            track_name: name.to_string(),
+            offset_secs,
+            played: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tracklist_path_strips_part_suffix() {
+        let dir = PathBuf::from("/usb/mesh-recordings");
+        assert_eq!(
+            tracklist_path(&dir.join("2025-06-01_22-30_part01.flac"), "txt"),
+            dir.join("2025-06-01_22-30.txt")
+        );
+        assert_eq!(
+            tracklist_path(&dir.join("2025-06-01_22-30.wav"), "cue"),
+            dir.join("2025-06-01_22-30.cue")
+        );
+    }
+
+    #[test]
+    fn test_txt_single_part_uses_recording_offsets() {
+        let parts = [part("set.wav", 0.0, 600.0)];
+        let body = format_txt_body(&parts, &[entry("A", 0.0), entry("B", 125.0)]);
+        assert_eq!(body, "00:00:00  A [Deck 1]\n00:02:05  B [Deck 1]\n");
+    }
+
+    #[test]
+    fn test_txt_split_parts_use_part_offsets() {
+        let parts = [part("set_part01.flac", 0.0, 1800.0), part("set_part02.flac", 1800.0, 900.0)];
+        let body = format_txt_body(&parts, &[entry("A", 60.0), entry("B", 1810.0), entry("C", 2000.0)]);
+        assert_eq!(
+            body,
+            "Part 1 — set_part01.flac (starts at 00:00:00)\n\
+             00:01:00  A [Deck 1]\n\
+             \n\
+             Part 2 — set_part02.flac (starts at 00:30:00)\n\
+             00:00:10  B [Deck 1]\n\
+             00:03:20  C [Deck 1]\n"
+        );
+    }
+
+    #[test]
+    fn test_cue_time_format() {
+        assert_eq!(format_cue_time(0.0), "00:00:00");
+        assert_eq!(format_cue_time(61.5), "01:01:38");
+        // Minutes are not wrapped into hours
+        assert_eq!(format_cue_time(2.0 * 3600.0), "120:00:00");
+    }
+
+    #[test]
+    fn test_cue_sheet_files_and_indices() {
+        let parts = [part("set_part01.wav", 0.0, 1800.0), part("set_part02.wav", 1800.0, 900.0)];
+        let mut a = entry("A", 0.0);
+        a.title = Some("Title \"A\"".to_string());
+        a.artist = Some("Artist".to_string());
+        let mut skipped = entry("Loaded only", 100.0);
+        skipped.played = false;
+        let cue = format_cue(&parts, &[a, skipped, entry("B", 1830.0)], "My Set");
+
+        assert!(cue.contains("TITLE \"My Set\"\n"));
+        assert!(cue.contains(
+            "FILE \"set_part01.wav\" WAVE\n  TRACK 01 AUDIO\n    TITLE \"Title 'A'\"\n    PERFORMER \"Artist\"\n    INDEX 01 00:00:00\n"
+        ));
+        assert!(cue.contains("FILE \"set_part02.wav\" WAVE\n  TRACK 02 AUDIO\n    TITLE \"B\"\n    INDEX 01 00:30:00\n"));
+        assert!(!cue.contains("Loaded only"));
+    }
+
+    #[test]
+    fn test_cue_indices_strictly_increase() {
+        let parts = [part("set.wav", 0.0, 600.0)];
+        let cue = format_cue(&parts, &[entry("A", 10.0), entry("B", 10.0)], "Set");
+        assert!(cue.contains("INDEX 01 00:10:00\n"));
+        assert!(cue.contains("INDEX 01 00:10:01\n"));
+    }
+
+    #[test]
+    fn test_json_contains_track_metadata() {
+        let parts = [part("set.flac", 0.0, 600.0)];
+        let mut a = entry("A", 30.0);
+        a.bpm = Some(174.0);
+        a.key = Some("8A".to_string());
+        a.deck_index = 2;
+        a.load_source = "suggestion".to_string();
+        a.suggestion_tags_json = Some("[\"energy_up\"]".to_string());
+        let json = format_json(&parts, &[a], "Set", 0, 600.0).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let track = &value["tracks"][0];
+        assert_eq!(track["bpm"], 174.0);
+        assert_eq!(track["key"], "8A");
+        assert_eq!(track["deck"], 3);
+        assert_eq!(track["load_source"], "suggestion");
+        assert_eq!(track["suggestion_tags"][0], "energy_up");
+        assert_eq!(value["parts"][0]["file"], "set.flac");
+    }
+
+    #[test]
+    fn test_chapters_follow_parts() {
+        let parts = [part("a.wav", 0.0, 60.0), part("b.wav", 60.0, 60.0)];
+        let mut b = entry("B", 90.0);
+        b.artist = Some("Artist".to_string());
+        let chapters = chapters_per_part(&parts, &[entry("A", 5.0), b]);
+        assert_eq!(chapters[0], vec![Chapter { start_secs: 5.0, title: "A".to_string() }]);
+        assert_eq!(chapters[1], vec![Chapter { start_secs: 30.0, title: "Artist - B".to_string() }]);
+    }
+}
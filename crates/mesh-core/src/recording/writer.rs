@@ -1,12 +1,25 @@
-//! Recording thread — reads from ring buffer, writes WAV via hound
+//! Recording thread — reads from ring buffer, writes WAV (hound) or FLAC
 //!
 //! The thread runs at normal priority and uses buffered I/O.
-//! It periodically calls `hound::WavWriter::flush()` to update the WAV
-//! header, so partial recordings are recoverable after crashes.
+//! It periodically flushes the writer (`hound::WavWriter::flush()` updates
+//! the WAV header), so partial recordings are recoverable after crashes.
+//!
+//! When a split policy is configured, the thread finalizes the current part
+//! and opens the next one as soon as the part reaches its size or duration
+//! limit. The switch happens between two stereo frames, so the parts join
+//! back together sample-accurately. The first part is written without a part
+//! number and only renamed to `_part01` when the second part opens.
+//!
+//! The same thread writes multitrack stem recordings: frames then carry four
+//! stereo pairs, written either to one 8-channel file or to one stereo file
+//! per stem (all files roll over to the next part together).
 
-use crate::types::StereoSample;
-use super::RecordingEvent;
-use std::path::Path;
+use crate::config::{RecordingConfig, RecordingFormat};
+use crate::types::{StemFrame, StereoSample, NUM_STEMS};
+use super::flac::FlacStreamWriter;
+use super::{RecordingEvent, RecordingPart, RecordingTarget};
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
@@ -15,14 +28,169 @@ use std::io::BufWriter;
 /// Flush interval: update WAV header every ~10 seconds for crash safety
 const FLUSH_INTERVAL_SAMPLES: u64 = 48000 * 10; // ~10s at 48kHz
 
+/// A frame type the recording thread can write
+pub(crate) trait RecordingFrame: Copy + Send + 'static {
+    /// Whether this is the per-stem stream (reported as `StemsStopped`
+    /// instead of the master recording lifecycle events)
+    const STEMS: bool;
+
+    /// Stereo pairs in file channel order
+    fn pairs(&self) -> &[StereoSample];
+}
+
+impl RecordingFrame for StereoSample {
+    const STEMS: bool = false;
+
+    fn pairs(&self) -> &[StereoSample] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl RecordingFrame for StemFrame {
+    const STEMS: bool = true;
+
+    fn pairs(&self) -> &[StereoSample] {
+        self
+    }
+}
+
+/// Writer for a single part file in the configured format
+enum PartWriter {
+    Wav(hound::WavWriter<BufWriter<File>>),
+    Flac(FlacStreamWriter),
+}
+
+impl PartWriter {
+    /// Create the part file (with `channels` interleaved channels) and write its header
+    fn create(
+        path: &Path,
+        sample_rate: u32,
+        channels: u16,
+        format: RecordingFormat,
+    ) -> Result<Self, String> {
+        if format.is_flac() {
+            return FlacStreamWriter::create(
+                path,
+                sample_rate,
+                channels as usize,
+                format.bits_per_sample() as u32,
+            )
+            .map(PartWriter::Flac);
+        }
+
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: format.bits_per_sample(),
+            sample_format: if format == RecordingFormat::WavFloat32 {
+                hound::SampleFormat::Float
+            } else {
+                hound::SampleFormat::Int
+            },
+        };
+
+        // Open WAV writer with buffered I/O (128 KB buffer)
+        let file = File::create(path).map_err(|e| format!("Failed to create WAV file: {e}"))?;
+        let buf_writer = BufWriter::with_capacity(128 * 1024, file);
+        hound::WavWriter::new(buf_writer, spec)
+            .map(PartWriter::Wav)
+            .map_err(|e| format!("Failed to initialize WAV writer: {e}"))
+    }
+
+    /// Write one frame of stereo pairs, converting to the target sample format
+    fn write_frame(&mut self, pairs: &[StereoSample], format: RecordingFormat) -> Result<(), String> {
+        match self {
+            PartWriter::Wav(writer) => {
+                for sample in pairs {
+                    let result = match format {
+                        RecordingFormat::WavFloat32 => writer
+                            .write_sample(sample.left)
+                            .and_then(|()| writer.write_sample(sample.right)),
+                        RecordingFormat::Wav24 => writer
+                            .write_sample(f32_to_i24(sample.left))
+                            .and_then(|()| writer.write_sample(f32_to_i24(sample.right))),
+                        _ => writer
+                            .write_sample(f32_to_i16(sample.left))
+                            .and_then(|()| writer.write_sample(f32_to_i16(sample.right))),
+                    };
+                    result.map_err(|e| format!("WAV write error: {e}"))?;
+                }
+                Ok(())
+            }
+            PartWriter::Flac(writer) => {
+                let mut frame = [0i32; 2 * NUM_STEMS];
+                for (out, sample) in frame.chunks_exact_mut(2).zip(pairs) {
+                    if format.bits_per_sample() == 24 {
+                        out[0] = f32_to_i24(sample.left);
+                        out[1] = f32_to_i24(sample.right);
+                    } else {
+                        out[0] = f32_to_i16(sample.left) as i32;
+                        out[1] = f32_to_i16(sample.right) as i32;
+                    }
+                }
+                writer
+                    .write_frame(&frame[..pairs.len() * 2])
+                    .map_err(|e| format!("FLAC write error: {e}"))
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        match self {
+            PartWriter::Wav(writer) => writer.flush().map_err(|e| e.to_string()),
+            PartWriter::Flac(writer) => writer.flush(),
+        }
+    }
+
+    /// Finalize the file (writes correct header sizes)
+    fn finalize(self) -> Result<(), String> {
+        match self {
+            PartWriter::Wav(writer) => writer.finalize().map_err(|e| e.to_string()),
+            PartWriter::Flac(writer) => writer.finalize(),
+        }
+    }
+
+    /// Current file size in bytes (used for size-based splitting)
+    fn bytes_written(&self, frames: u64, format: RecordingFormat) -> u64 {
+        match self {
+            // Header is a few dozen bytes; the split limit leaves ample margin
+            PartWriter::Wav(writer) => {
+                frames * writer.spec().channels as u64 * (format.bits_per_sample() as u64 / 8)
+            }
+            PartWriter::Flac(writer) => writer.bytes_written(),
+        }
+    }
+}
+
+/// Open one part file per target (all part files of a frame stream roll over together)
+fn create_part_writers(
+    targets: &[RecordingTarget],
+    part: usize,
+    sample_rate: u32,
+    channels: u16,
+    format: RecordingFormat,
+) -> Result<Vec<PartWriter>, (PathBuf, String)> {
+    targets
+        .iter()
+        .map(|target| {
+            let path = target.part_path(part);
+            PartWriter::create(&path, sample_rate, channels, format).map_err(|e| (path, e))
+        })
+        .collect()
+}
+
 /// Recording thread main function
 ///
-/// Reads `StereoSample` from the ring buffer consumer, converts to 16-bit PCM,
-/// and writes via hound. Runs until `stop_flag` is set or the producer is dropped.
-pub fn recording_thread(
-    mut consumer: rtrb::Consumer<StereoSample>,
-    path: &Path,
+/// Reads frames (a `StereoSample` for the master, a `StemFrame` for stems)
+/// from the ring buffer consumer, converts them to the configured sample
+/// format, and writes them to one or more part files. With a single target
+/// all stereo pairs of a frame go into one file; otherwise pair `i` goes to
+/// `targets[i]`. Runs until `stop_flag` is set or the producer is dropped.
+pub(crate) fn recording_thread<F: RecordingFrame>(
+    mut consumer: rtrb::Consumer<F>,
+    targets: Vec<RecordingTarget>,
     sample_rate: u32,
+    config: RecordingConfig,
     stop_flag: Arc<AtomicBool>,
     event_tx: mpsc::Sender<RecordingEvent>,
 ) {
@@ -30,42 +198,32 @@ pub fn recording_thread(
     // with the RT JACK audio thread on the LITTLE A55 cores 0-3.
     crate::rt::pin_to_big_cores();
 
-    let spec = hound::WavSpec {
-        channels: 2,
-        sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
+    let format = config.format;
+    let max_part_bytes = config.split.max_part_bytes();
+    let max_part_frames = config.split.max_part_frames(sample_rate);
+    let interleaved = targets.len() == 1;
+    let channels = if interleaved && F::STEMS { 2 * NUM_STEMS as u16 } else { 2 };
 
-    // Open WAV writer with buffered I/O (128 KB buffer)
-    let file = match std::fs::File::create(path) {
-        Ok(f) => f,
-        Err(e) => {
-            let _ = event_tx.send(RecordingEvent::Error {
-                path: path.to_path_buf(),
-                message: format!("Failed to create WAV file: {e}"),
-            });
-            return;
-        }
-    };
-    let buf_writer = BufWriter::with_capacity(128 * 1024, file);
-    let mut writer = match hound::WavWriter::new(buf_writer, spec) {
+    let mut part_index = 1usize;
+    let mut paths: Vec<PathBuf> = targets.iter().map(|t| t.part_path(part_index)).collect();
+    let mut writers = match create_part_writers(&targets, part_index, sample_rate, channels, format) {
         Ok(w) => w,
-        Err(e) => {
-            let _ = event_tx.send(RecordingEvent::Error {
-                path: path.to_path_buf(),
-                message: format!("Failed to initialize WAV writer: {e}"),
-            });
+        Err((path, message)) => {
+            let _ = event_tx.send(RecordingEvent::Error { path, message });
             return;
         }
     };
 
     // Notify UI that recording has started
-    let _ = event_tx.send(RecordingEvent::Started {
-        path: path.to_path_buf(),
-    });
+    if !F::STEMS {
+        let _ = event_tx.send(RecordingEvent::Started {
+            path: paths[0].clone(),
+        });
+    }
 
+    let mut parts: Vec<RecordingPart> = Vec::new();
     let mut total_samples: u64 = 0;
+    let mut part_start_sample: u64 = 0;
     let mut samples_since_flush: u64 = 0;
     let start_time = std::time::Instant::now();
 
@@ -90,23 +248,75 @@ pub fn recording_thread(
         let (first, second) = chunk.as_slices();
 
         for slice in [first, second] {
-            for sample in slice {
-                // Convert f32 [-1.0, 1.0] to i16 with clipping
-                let left = f32_to_i16(sample.left);
-                let right = f32_to_i16(sample.right);
-
-                if let Err(e) = writer.write_sample(left) {
-                    let _ = event_tx.send(RecordingEvent::Error {
-                        path: path.to_path_buf(),
-                        message: format!("WAV write error: {e}"),
+            for frame in slice {
+                // Roll over to the next part once the current one is full
+                let part_frames = total_samples - part_start_sample;
+                let part_full = max_part_frames.is_some_and(|max| part_frames >= max)
+                    || max_part_bytes.is_some_and(|max| {
+                        writers.iter().any(|w| w.bytes_written(part_frames, format) >= max)
                     });
-                    return;
+                if part_full {
+                    let next_writers = match create_part_writers(
+                        &targets, part_index + 1, sample_rate, channels, format,
+                    ) {
+                        Ok(w) => w,
+                        Err((path, message)) => {
+                            let _ = event_tx.send(RecordingEvent::Error { path, message });
+                            return;
+                        }
+                    };
+                    let finished = std::mem::replace(&mut writers, next_writers);
+                    for (writer, path) in finished.into_iter().zip(&paths) {
+                        if let Err(e) = writer.finalize() {
+                            let _ = event_tx.send(RecordingEvent::Error {
+                                path: path.clone(),
+                                message: format!("Finalize error: {e}"),
+                            });
+                            return;
+                        }
+                        log::info!("[RECORDING] Part {} complete: {}", part_index, path.display());
+                    }
+                    let next_paths = targets.iter().map(|t| t.part_path(part_index + 1)).collect();
+                    let finished_paths = std::mem::replace(&mut paths, next_paths);
+                    for (mut path, target) in finished_paths.into_iter().zip(&targets) {
+                        if part_index == 1 {
+                            let numbered = number_first_part(&path, target);
+                            if numbered != path && !F::STEMS {
+                                let _ = event_tx.send(RecordingEvent::FirstPartRenamed {
+                                    from: path.clone(),
+                                    to: numbered.clone(),
+                                });
+                            }
+                            path = numbered;
+                        }
+                        parts.push(RecordingPart {
+                            path,
+                            start_secs: part_start_sample as f64 / sample_rate as f64,
+                            duration_secs: part_frames as f64 / sample_rate as f64,
+                            sample_rate,
+                        });
+                    }
+                    part_index += 1;
+                    part_start_sample = total_samples;
+                    if !F::STEMS {
+                        let _ = event_tx.send(RecordingEvent::PartStarted {
+                            path: paths[0].clone(),
+                            part: part_index,
+                        });
+                    }
                 }
-                if let Err(e) = writer.write_sample(right) {
-                    let _ = event_tx.send(RecordingEvent::Error {
-                        path: path.to_path_buf(),
-                        message: format!("WAV write error: {e}"),
-                    });
+
+                let pairs = frame.pairs();
+                let result = if interleaved {
+                    writers[0].write_frame(pairs, format)
+                } else {
+                    writers
+                        .iter_mut()
+                        .zip(pairs)
+                        .try_for_each(|(w, pair)| w.write_frame(std::slice::from_ref(pair), format))
+                };
+                if let Err(message) = result {
+                    let _ = event_tx.send(RecordingEvent::Error { path: paths[0].clone(), message });
                     return;
                 }
 
@@ -118,11 +328,11 @@ pub fn recording_thread(
 
         // Periodic flush for crash safety
         if samples_since_flush >= FLUSH_INTERVAL_SAMPLES {
-            if let Err(e) = writer.flush() {
+            if let Err(e) = writers.iter_mut().try_for_each(|w| w.flush()) {
                 // Covers both USB removal (ENODEV/EIO) and disk full (ENOSPC)
                 let _ = event_tx.send(RecordingEvent::Error {
-                    path: path.to_path_buf(),
-                    message: format!("WAV flush error (USB removed or disk full?): {e}"),
+                    path: paths[0].clone(),
+                    message: format!("Flush error (USB removed or disk full?): {e}"),
                 });
                 return;
             }
@@ -130,26 +340,51 @@ pub fn recording_thread(
         }
     }
 
-    // Finalize WAV file (writes correct header sizes)
+    // Finalize the last part
     let duration = start_time.elapsed().as_secs_f64();
-    match writer.finalize() {
-        Ok(()) => {
-            log::info!(
-                "[RECORDING] Finalized: {} ({:.1}s, {} samples)",
-                path.display(), duration, total_samples
-            );
-            // Note: tracklist generation happens on the UI side after this event
-            let _ = event_tx.send(RecordingEvent::Stopped {
-                path: path.to_path_buf(),
-                duration_secs: duration,
-                tracklist_path: None,
+    for (writer, path) in writers.into_iter().zip(paths) {
+        if let Err(e) = writer.finalize() {
+            let _ = event_tx.send(RecordingEvent::Error {
+                path,
+                message: format!("Finalize error: {e}"),
             });
+            return;
+        }
+        log::info!(
+            "[RECORDING] Finalized: {} ({:.1}s, {} samples, {} part(s))",
+            path.display(), duration, total_samples, part_index
+        );
+        parts.push(RecordingPart {
+            path,
+            start_secs: part_start_sample as f64 / sample_rate as f64,
+            duration_secs: (total_samples - part_start_sample) as f64 / sample_rate as f64,
+            sample_rate,
+        });
+    }
+
+    // Note: tracklist generation happens on the UI side after this event
+    let event = if F::STEMS {
+        RecordingEvent::StemsStopped { parts }
+    } else {
+        RecordingEvent::Stopped {
+            path: parts[0].path.clone(),
+            duration_secs: duration,
+            tracklist_path: None,
+            parts,
         }
+    };
+    let _ = event_tx.send(event);
+}
+
+/// Rename the finalized first part to its numbered name once the recording
+/// splits. On failure the part keeps its plain name (the recording goes on).
+fn number_first_part(path: &Path, target: &RecordingTarget) -> PathBuf {
+    let numbered = target.numbered_part_path(1);
+    match std::fs::rename(path, &numbered) {
+        Ok(()) => numbered,
         Err(e) => {
-            let _ = event_tx.send(RecordingEvent::Error {
-                path: path.to_path_buf(),
-                message: format!("WAV finalize error: {e}"),
-            });
+            log::warn!("[RECORDING] Failed to rename {} to {}: {e}", path.display(), numbered.display());
+            path.to_path_buf()
         }
     }
 }
@@ -160,3 +395,44 @@ fn f32_to_i16(sample: f32) -> i16 {
     let clamped = sample.clamp(-1.0, 1.0);
     (clamped * i16::MAX as f32) as i16
 }
+
+/// Largest positive 24-bit sample value
+const I24_MAX: f32 = 8_388_607.0;
+
+/// Convert f32 sample [-1.0, 1.0] to a 24-bit integer (in an i32) with hard clipping
+#[inline]
+fn f32_to_i24(sample: f32) -> i32 {
+    let clamped = sample.clamp(-1.0, 1.0);
+    (clamped * I24_MAX) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_to_i24_clipping() {
+        assert_eq!(f32_to_i24(0.0), 0);
+        assert_eq!(f32_to_i24(1.0), 8_388_607);
+        assert_eq!(f32_to_i24(2.0), 8_388_607);
+        assert_eq!(f32_to_i24(-1.5), -8_388_607);
+    }
+
+    #[test]
+    fn test_first_part_gets_numbered_on_split() {
+        let dir = std::env::temp_dir().join(format!("mesh-part-rename-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = RecordingTarget::new(dir.clone(), "2025-06-01_22-30".into(), &RecordingConfig::default());
+        let first = target.part_path(1);
+        std::fs::write(&first, b"RIFF").unwrap();
+
+        let renamed = number_first_part(&first, &target);
+        assert_eq!(renamed, dir.join("2025-06-01_22-30_part01.wav"));
+        assert!(renamed.exists() && !first.exists());
+
+        // A missing file stays where it was reported
+        assert_eq!(number_first_part(&first, &target), first);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,540 @@
+//! Sampler clips and sample packs
+//!
+//! A [`SampleClip`] is a stereo buffer that the engine's sampler bank can
+//! play as a one-shot or as a beat-synced loop. Clips come from two places:
+//!
+//! - **Audio files** — any format symphonia can decode (WAV, FLAC, MP3,
+//!   OGG), decoded to stereo and resampled to the engine rate with
+//!   [`decode_sample_file`]
+//! - **Deck captures** — a region of a loaded track (usually its active
+//!   loop) summed from the selected stems with [`capture_region`]
+//!
+//! Clips are grouped into sample packs stored in the collection:
+//!
+//! ```text
+//! mesh-collection/samples/<pack>/
+//!     pack.yaml        # slot → file, mode, BPM
+//!     slot-1.wav       # 32-bit float stereo
+//!     slot-5.wav
+//! ```
+//!
+//! A folder without `pack.yaml` is a pack too: its audio files fill the
+//! slots in name order, so a pack can be assembled by dropping files in.
+//!
+//! All functions here allocate and do file I/O — call them off the audio
+//! thread and hand the result to the engine wrapped in `basedrop::Shared`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audio_file::{resample_stereo_buffer, AudioFileError, StemBuffers};
+use crate::engine::NUM_SAMPLER_SLOTS;
+use crate::types::{Stem, StereoBuffer, StereoSample, NUM_STEMS};
+
+/// Collection subdirectory holding the sample packs
+pub const SAMPLES_DIR: &str = "samples";
+
+/// Manifest file inside each pack directory
+const PACK_MANIFEST: &str = "pack.yaml";
+
+/// File extensions picked up from packs without a manifest
+const AUDIO_EXTENSIONS: [&str; 4] = ["wav", "flac", "mp3", "ogg"];
+
+/// How a sampler slot plays its clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipMode {
+    /// Play once from the start on every trigger
+    #[default]
+    OneShot,
+    /// Loop until triggered again (phase-aligned to the master deck)
+    Loop,
+}
+
+impl ClipMode {
+    pub const ALL: [ClipMode; 2] = [ClipMode::OneShot, ClipMode::Loop];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ClipMode::OneShot => "One-Shot",
+            ClipMode::Loop => "Loop",
+        }
+    }
+}
+
+/// Audio held by a sampler slot
+#[derive(Debug, Clone)]
+pub struct SampleClip {
+    /// Display name (file stem or "<track> loop")
+    pub name: String,
+    /// Stereo audio at `sample_rate`
+    pub audio: StereoBuffer,
+    /// Sample rate of `audio`
+    pub sample_rate: u32,
+    /// Native tempo; clips with a BPM are time-stretched to the global BPM
+    pub bpm: Option<f64>,
+    /// One-shot or loop playback
+    pub mode: ClipMode,
+}
+
+impl SampleClip {
+    /// Number of sample frames
+    pub fn len(&self) -> usize {
+        self.audio.len()
+    }
+
+    /// Whether the clip holds no audio
+    pub fn is_empty(&self) -> bool {
+        self.audio.is_empty()
+    }
+
+    /// Length in beats at the clip's native tempo
+    pub fn beats(&self) -> Option<f64> {
+        let bpm = self.bpm?;
+        Some(self.len() as f64 / self.sample_rate as f64 * bpm / 60.0)
+    }
+
+    /// Convert the clip to another sample rate (no-op when already there)
+    pub fn resampled(self, target_rate: u32) -> Result<Self, AudioFileError> {
+        if self.sample_rate == target_rate || self.is_empty() {
+            return Ok(Self { sample_rate: target_rate, ..self });
+        }
+        let ratio = target_rate as f64 / self.sample_rate as f64;
+        let mut audio = StereoBuffer::silence((self.len() as f64 * ratio).ceil() as usize);
+        resample_stereo_buffer(&self.audio, &mut audio, self.sample_rate, target_rate)?;
+        Ok(Self { audio, sample_rate: target_rate, ..self })
+    }
+}
+
+/// Decode an audio file into a one-shot clip at `target_rate`
+///
+/// Mono files are duplicated to both channels; files with more than two
+/// channels keep the first pair. A tempo written in the file name
+/// (`Chord Stab 124bpm.wav`) becomes the clip BPM.
+pub fn decode_sample_file(path: &Path, target_rate: u32) -> Result<SampleClip, AudioFileError> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = std::fs::File::open(path).map_err(|e| AudioFileError::IoError(e.to_string()))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| AudioFileError::InvalidFormat(e.to_string()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AudioFileError::InvalidFormat("No audio track found".to_string()))?;
+    let track_id = track.id;
+    let source_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| AudioFileError::InvalidFormat("Unknown sample rate".to_string()))?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| AudioFileError::InvalidFormat(e.to_string()))?;
+
+    let mut audio = StereoBuffer::with_capacity(0);
+    let mut sample_buf: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(symphonia::core::errors::Error::IoError(e))
+                if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            Err(e) => {
+                log::warn!("[SAMPLER] Error reading packet in {}: {}", path.display(), e);
+                break;
+            }
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                log::warn!("[SAMPLER] Error decoding packet in {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        let buf = sample_buf.get_or_insert_with(|| SampleBuffer::new(decoded.capacity() as u64, spec));
+        buf.copy_interleaved_ref(decoded);
+
+        for frame in buf.samples().chunks_exact(channels) {
+            let left = frame[0];
+            let right = if channels > 1 { frame[1] } else { frame[0] };
+            audio.push(StereoSample::new(left, right));
+        }
+    }
+
+    if audio.is_empty() {
+        return Err(AudioFileError::Corrupted(format!("No audio decoded from {}", path.display())));
+    }
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Sample".to_string());
+    let clip = SampleClip {
+        bpm: bpm_from_name(&name),
+        name,
+        audio,
+        sample_rate: source_rate,
+        mode: ClipMode::OneShot,
+    };
+    clip.resampled(target_rate)
+}
+
+/// Parse a tempo written in a file name ("Loop 124bpm", "loop_124_BPM")
+fn bpm_from_name(name: &str) -> Option<f64> {
+    let lower = name.to_lowercase();
+    let idx = lower.find("bpm")?;
+    let digits: String = lower[..idx]
+        .trim_end_matches([' ', '_', '-'])
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let bpm: f64 = digits.parse().ok()?;
+    (40.0..=250.0).contains(&bpm).then_some(bpm)
+}
+
+/// Sum a region of a track's stems into one stereo buffer
+///
+/// Only stems enabled in `stem_mask` are mixed, so a capture can take a
+/// single stem (e.g. just the drums of the current loop) or everything
+/// that is audible on the deck. The region is clamped to the track length.
+pub fn capture_region(
+    stems: &StemBuffers,
+    start: usize,
+    end: usize,
+    stem_mask: [bool; NUM_STEMS],
+) -> StereoBuffer {
+    let end = end.min(stems.len());
+    let start = start.min(end);
+    let mut out = StereoBuffer::silence(end - start);
+    for (idx, stem) in Stem::ALL.iter().enumerate() {
+        if !stem_mask[idx] {
+            continue;
+        }
+        let source = &stems.get(*stem).as_slice()[start..end];
+        for (dst, src) in out.iter_mut().zip(source) {
+            *dst += *src;
+        }
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sample packs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One slot entry in a pack manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackSlot {
+    /// Slot index (0-based)
+    slot: usize,
+    name: String,
+    /// Audio file relative to the pack directory
+    file: String,
+    #[serde(default)]
+    mode: ClipMode,
+    #[serde(default)]
+    bpm: Option<f64>,
+}
+
+/// `pack.yaml` contents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PackManifest {
+    #[serde(default)]
+    slots: Vec<PackSlot>,
+}
+
+/// Directory holding a pack (the name is sanitized for the file system)
+pub fn pack_dir(collection_root: &Path, name: &str) -> PathBuf {
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') { c } else { '_' })
+        .collect();
+    collection_root.join(SAMPLES_DIR).join(safe.trim())
+}
+
+/// Name for a pack saved without one ("Pack 2026-03-14 21-05")
+pub fn dated_pack_name() -> String {
+    format!("Pack {}", chrono::Local::now().format("%Y-%m-%d %H-%M"))
+}
+
+/// Names of all sample packs in the collection, sorted
+pub fn list_packs(collection_root: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(collection_root.join(SAMPLES_DIR)) else {
+        return Vec::new();
+    };
+    let mut packs: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let path = e.path();
+            path.join(PACK_MANIFEST).is_file() || !loose_audio_files(&path).is_empty()
+        })
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    packs.sort_by_key(|name| name.to_lowercase());
+    packs
+}
+
+/// Save the loaded slots as a sample pack, replacing any pack of that name
+///
+/// Each clip is written as a 32-bit float WAV next to the manifest.
+pub fn save_pack(
+    collection_root: &Path,
+    name: &str,
+    slots: &[Option<&SampleClip>],
+) -> Result<PathBuf, String> {
+    let dir = pack_dir(collection_root, name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to replace pack {}: {}", dir.display(), e))?;
+    }
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create pack {}: {}", dir.display(), e))?;
+
+    let mut manifest = PackManifest::default();
+    for (slot, clip) in slots.iter().enumerate().take(NUM_SAMPLER_SLOTS) {
+        let Some(clip) = clip else { continue };
+        let file = format!("slot-{}.wav", slot + 1);
+        write_wav(&dir.join(&file), clip)?;
+        manifest.slots.push(PackSlot {
+            slot,
+            name: clip.name.clone(),
+            file,
+            mode: clip.mode,
+            bpm: clip.bpm,
+        });
+    }
+
+    let yaml = serde_yaml::to_string(&manifest)
+        .map_err(|e| format!("Failed to serialize pack manifest: {}", e))?;
+    std::fs::write(dir.join(PACK_MANIFEST), yaml)
+        .map_err(|e| format!("Failed to write pack manifest: {}", e))?;
+    Ok(dir)
+}
+
+/// Load a sample pack, resampling every clip to `target_rate`
+///
+/// Returns one entry per sampler slot; slots the pack leaves empty (or
+/// whose audio file is missing) are `None`. Packs without a manifest load
+/// their audio files in name order; files with a tempo in their name
+/// become loops, everything else a one-shot.
+pub fn load_pack(
+    collection_root: &Path,
+    name: &str,
+    target_rate: u32,
+) -> Result<Vec<Option<SampleClip>>, String> {
+    let dir = pack_dir(collection_root, name);
+    if !dir.join(PACK_MANIFEST).is_file() {
+        let files = loose_audio_files(&dir);
+        if files.is_empty() {
+            return Err(format!("No samples found in {}", dir.display()));
+        }
+        let mut clips: Vec<Option<SampleClip>> = vec![None; NUM_SAMPLER_SLOTS];
+        for (slot, file) in files.iter().take(NUM_SAMPLER_SLOTS).enumerate() {
+            match decode_sample_file(file, target_rate) {
+                Ok(clip) => {
+                    let mode = if clip.bpm.is_some() { ClipMode::Loop } else { ClipMode::OneShot };
+                    clips[slot] = Some(SampleClip { mode, ..clip });
+                }
+                Err(e) => log::warn!("[SAMPLER] Skipping {} in pack '{}': {}", file.display(), name, e),
+            }
+        }
+        return Ok(clips);
+    }
+
+    let yaml = std::fs::read_to_string(dir.join(PACK_MANIFEST))
+        .map_err(|e| format!("Failed to read pack {}: {}", dir.display(), e))?;
+    let manifest: PackManifest = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("Invalid pack manifest {}: {}", dir.display(), e))?;
+
+    let mut clips: Vec<Option<SampleClip>> = vec![None; NUM_SAMPLER_SLOTS];
+    for entry in manifest.slots {
+        if entry.slot >= NUM_SAMPLER_SLOTS {
+            continue;
+        }
+        match decode_sample_file(&dir.join(&entry.file), target_rate) {
+            Ok(clip) => {
+                clips[entry.slot] = Some(SampleClip {
+                    name: entry.name,
+                    mode: entry.mode,
+                    bpm: entry.bpm,
+                    ..clip
+                });
+            }
+            Err(e) => log::warn!("[SAMPLER] Skipping {} in pack '{}': {}", entry.file, name, e),
+        }
+    }
+    Ok(clips)
+}
+
+/// Audio files directly inside a pack directory, sorted by name
+fn loose_audio_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    files.sort_by_key(|p| p.file_name().map(|n| n.to_string_lossy().to_lowercase()));
+    files
+}
+
+/// Write a clip as a 32-bit float stereo WAV
+fn write_wav(path: &Path, clip: &SampleClip) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: clip.sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    for &sample in clip.audio.as_interleaved() {
+        writer
+            .write_sample(sample)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(name: &str, len: usize, mode: ClipMode, bpm: Option<f64>) -> SampleClip {
+        SampleClip {
+            name: name.to_string(),
+            audio: StereoBuffer::from_vec(
+                (0..len).map(|i| StereoSample::new(i as f32 / len as f32, -0.5)).collect(),
+            ),
+            sample_rate: 48000,
+            bpm,
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_bpm_from_name() {
+        assert_eq!(bpm_from_name("Chord Stab 124bpm"), Some(124.0));
+        assert_eq!(bpm_from_name("loop_174_BPM"), Some(174.0));
+        assert_eq!(bpm_from_name("Hit - 90 bpm - A minor"), Some(90.0));
+        assert_eq!(bpm_from_name("Crash"), None);
+        assert_eq!(bpm_from_name("bpm"), None);
+    }
+
+    #[test]
+    fn test_capture_region_sums_selected_stems() {
+        let mut stems = StemBuffers::with_length(100);
+        for (i, stem) in Stem::ALL.iter().enumerate() {
+            for s in stems.get_mut(*stem).iter_mut() {
+                *s = StereoSample::mono(1.0 + i as f32);
+            }
+        }
+
+        // Drums (2.0) + Bass (3.0)
+        let out = capture_region(&stems, 10, 30, [false, true, true, false]);
+        assert_eq!(out.len(), 20);
+        assert!(out.iter().all(|s| s.left == 5.0 && s.right == 5.0));
+
+        // Region past the end is clamped
+        let out = capture_region(&stems, 90, 200, [true, false, false, false]);
+        assert_eq!(out.len(), 10);
+        assert_eq!(out[0].left, 1.0);
+    }
+
+    #[test]
+    fn test_clip_beats() {
+        // 2 seconds at 120 BPM = 4 beats
+        let c = clip("loop", 96000, ClipMode::Loop, Some(120.0));
+        assert!((c.beats().unwrap() - 4.0).abs() < 1e-9);
+        assert!(clip("hit", 1000, ClipMode::OneShot, None).beats().is_none());
+    }
+
+    #[test]
+    fn test_pack_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+
+        let kick = clip("Kick", 480, ClipMode::OneShot, None);
+        let groove = clip("Groove 124bpm", 960, ClipMode::Loop, Some(124.0));
+        let mut slots: Vec<Option<&SampleClip>> = vec![None; NUM_SAMPLER_SLOTS];
+        slots[0] = Some(&kick);
+        slots[5] = Some(&groove);
+
+        save_pack(root, "Warehouse/Set", &slots).unwrap();
+        assert_eq!(list_packs(root), vec!["Warehouse_Set".to_string()]);
+
+        let loaded = load_pack(root, "Warehouse/Set", 48000).unwrap();
+        assert_eq!(loaded.len(), NUM_SAMPLER_SLOTS);
+        let first = loaded[0].as_ref().unwrap();
+        assert_eq!(first.name, "Kick");
+        assert_eq!(first.mode, ClipMode::OneShot);
+        assert_eq!(first.len(), 480);
+        assert!((first.audio[240].left - 0.5).abs() < 1e-6);
+
+        let sixth = loaded[5].as_ref().unwrap();
+        assert_eq!(sixth.mode, ClipMode::Loop);
+        assert_eq!(sixth.bpm, Some(124.0));
+        assert!(loaded[1].is_none());
+    }
+
+    #[test]
+    fn test_pack_without_manifest_loads_files_in_order() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let dir = pack_dir(root, "Loose");
+        std::fs::create_dir_all(&dir).unwrap();
+        write_wav(&dir.join("b Groove 128bpm.wav"), &clip("b", 480, ClipMode::OneShot, None)).unwrap();
+        write_wav(&dir.join("a Snare.wav"), &clip("a", 240, ClipMode::OneShot, None)).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not audio").unwrap();
+
+        assert_eq!(list_packs(root), vec!["Loose".to_string()]);
+
+        let loaded = load_pack(root, "Loose", 48000).unwrap();
+        let first = loaded[0].as_ref().unwrap();
+        assert_eq!(first.name, "a Snare");
+        assert_eq!(first.mode, ClipMode::OneShot);
+        let second = loaded[1].as_ref().unwrap();
+        assert_eq!(second.bpm, Some(128.0));
+        assert_eq!(second.mode, ClipMode::Loop);
+        assert!(loaded[2].is_none());
+    }
+}
@@ -0,0 +1,136 @@
+//! Autocomplete for the last term of a search string
+
+use super::query::FIELDS;
+
+/// Suggestions shown at most at once
+const MAX_COMPLETIONS: usize = 8;
+
+/// Library values offered when completing `tag:` and `genre:` terms
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchVocabulary {
+    pub tags: Vec<String>,
+    pub genres: Vec<String>,
+}
+
+/// One suggestion: picking it replaces the search string with `query`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    /// Text inserted for the last term
+    pub label: String,
+    /// Example usage, for field names
+    pub detail: Option<&'static str>,
+    /// The whole search string after completion
+    pub query: String,
+}
+
+/// Byte offset where the last term starts, or None if the input ends
+/// between terms
+fn last_term_start(input: &str) -> Option<usize> {
+    let mut start = 0;
+    let mut in_quotes = false;
+    for (i, c) in input.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c.is_whitespace() && !in_quotes {
+            start = i + c.len_utf8();
+        }
+    }
+    (start < input.len()).then_some(start)
+}
+
+/// The 24 Camelot keys, 1A..12B
+fn camelot_keys() -> Vec<String> {
+    (1..=12).flat_map(|n| [format!("{}A", n), format!("{}B", n)]).collect()
+}
+
+/// Complete the last term of `input`
+///
+/// A bare word completes to field names (`bp` → `bpm:`). After `key:`,
+/// `tag:` or `genre:` the value after the last comma completes to Camelot
+/// keys or the library's tags and genres.
+pub fn complete(input: &str, vocab: &SearchVocabulary) -> Vec<Completion> {
+    let Some(start) = last_term_start(input) else { return Vec::new() };
+    let (head, term) = input.split_at(start);
+    let (sign, body) = match term.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", term),
+    };
+
+    let Some((field, value)) = body.split_once(':') else {
+        let typed = body.to_ascii_lowercase();
+        if typed.is_empty() {
+            return Vec::new();
+        }
+        return FIELDS.iter()
+            .filter(|(name, _)| name.starts_with(&typed) && *name != typed)
+            .take(MAX_COMPLETIONS)
+            .map(|(name, hint)| {
+                // "played" is a flag, the rest take a value
+                let label = if *name == "played" { name.to_string() } else { format!("{}:", name) };
+                Completion { query: format!("{}{}{}", head, sign, label), label, detail: Some(*hint) }
+            })
+            .collect();
+    };
+
+    let candidates = match field.to_ascii_lowercase().as_str() {
+        "key" => camelot_keys(),
+        "tag" | "tags" => vocab.tags.clone(),
+        "genre" => vocab.genres.clone(),
+        _ => return Vec::new(),
+    };
+    let (chosen, partial) = match value.rfind(',') {
+        Some(i) => (&value[..=i], &value[i + 1..]),
+        None => ("", value),
+    };
+    let partial_lower = partial.trim_matches('"').to_lowercase();
+    let already: Vec<String> = chosen.split(',').map(|s| s.trim_matches('"').to_lowercase()).collect();
+
+    candidates.into_iter()
+        .filter(|c| {
+            let lower = c.to_lowercase();
+            lower.starts_with(&partial_lower) && lower != partial_lower && !already.contains(&lower)
+        })
+        .take(MAX_COMPLETIONS)
+        .map(|c| {
+            let value = if c.contains(char::is_whitespace) { format!("\"{}\"", c) } else { c.clone() };
+            Completion {
+                query: format!("{}{}{}:{}{}", head, sign, field, chosen, value),
+                label: c,
+                detail: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queries(input: &str, vocab: &SearchVocabulary) -> Vec<String> {
+        complete(input, vocab).into_iter().map(|c| c.query).collect()
+    }
+
+    #[test]
+    fn test_complete_field_names() {
+        let vocab = SearchVocabulary::default();
+        assert_eq!(queries("techno bp", &vocab), vec!["techno bpm:"]);
+        assert_eq!(queries("-pla", &vocab), vec!["-plays:", "-played"]);
+        assert!(queries("techno ", &vocab).is_empty());
+        assert!(queries("bpm", &vocab).is_empty());
+    }
+
+    #[test]
+    fn test_complete_values() {
+        let vocab = SearchVocabulary {
+            tags: vec!["Peak".into(), "Peak Time".into(), "Warmup".into()],
+            genres: vec!["Techno".into(), "Tech House".into()],
+        };
+        assert_eq!(queries("tag:pe", &vocab), vec!["tag:Peak", r#"tag:"Peak Time""#]);
+        assert_eq!(queries("tag:peak,", &vocab), vec![r#"tag:peak,"Peak Time""#, "tag:peak,Warmup"]);
+        assert_eq!(queries("bpm:124 genre:tec", &vocab), vec!["bpm:124 genre:Techno", r#"bpm:124 genre:"Tech House""#]);
+        // 1A, 1B, 10A ... 12B
+        assert_eq!(queries("key:8A,1", &vocab).len(), 8);
+        assert_eq!(queries("key:12", &vocab), vec!["key:12A", "key:12B"]);
+        assert!(queries("bpm:12", &vocab).is_empty());
+    }
+}
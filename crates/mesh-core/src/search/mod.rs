@@ -0,0 +1,22 @@
+//! Structured search for the collection browsers
+//!
+//! The search box accepts plain words mixed with typed filters:
+//!
+//! ```text
+//! bpm:124-128 key:8A,9A genre:techno tag:peak -played lufs:<-8
+//! ```
+//!
+//! [`SearchQuery::parse`] turns the string into a filter AST, which compiles
+//! to a CozoScript query over the library (see `TrackQuery::search`).
+//! [`complete`] suggests field names and tag/genre/key values for the term
+//! being typed.
+//!
+//! # Module structure
+//! - `query`: AST, parser and compiler
+//! - `complete`: Autocomplete
+
+pub mod complete;
+pub mod query;
+
+pub use complete::{complete, Completion, SearchVocabulary};
+pub use query::{NumRange, SearchFilter, SearchQuery, SearchTerm, FIELDS};
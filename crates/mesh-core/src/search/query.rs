@@ -0,0 +1,389 @@
+//! Search query AST, parser and CozoScript compiler
+
+use crate::playlist::smart::{push_range, push_support_rules, str_list};
+use crate::playlist::{SmartContext, SmartRule};
+use cozo::DataValue;
+use std::collections::BTreeMap;
+
+/// Inclusive numeric range; an unset bound is open
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NumRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl NumRange {
+    fn scaled(self, factor: f64) -> Self {
+        Self { min: self.min.map(|v| v * factor), max: self.max.map(|v| v * factor) }
+    }
+
+    fn min_f32(&self) -> Option<f32> {
+        self.min.map(|v| v as f32)
+    }
+
+    fn max_f32(&self) -> Option<f32> {
+        self.max.map(|v| v as f32)
+    }
+}
+
+/// A single typed filter
+///
+/// Text values are lowercased by the parser; matching is case-insensitive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchFilter {
+    /// Bare word: title or artist contains it
+    Text(String),
+    Title(String),
+    Artist(String),
+    Album(String),
+    Label(String),
+    Bpm(NumRange),
+    /// Camelot ("8A") or musical ("Am") keys, any notation in the library matches
+    Key(Vec<String>),
+    /// ML or embedded-tag genre contains one of these
+    Genre(Vec<String>),
+    /// Has one of these tags
+    Tag(Vec<String>),
+    /// Drop loudness in LUFS
+    Lufs(NumRange),
+    /// Intensity score, in percent
+    Intensity(NumRange),
+    /// Vocal share of the stem energy, in percent
+    Vocals(NumRange),
+    Year(NumRange),
+    /// Number of times played in mesh-player
+    Plays(NumRange),
+    /// Played in the current (or most recent) mesh-player session
+    Played,
+}
+
+/// A filter, optionally negated with a leading `-`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchTerm {
+    pub negated: bool,
+    pub filter: SearchFilter,
+}
+
+/// A parsed search: every term must hold
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<SearchTerm>,
+}
+
+/// Field names understood by the parser, with a short hint for autocomplete
+pub const FIELDS: &[(&str, &str)] = &[
+    ("bpm", "bpm:124-128"),
+    ("key", "key:8A,9A"),
+    ("genre", "genre:techno"),
+    ("tag", "tag:peak"),
+    ("lufs", "lufs:<-8"),
+    ("intensity", "intensity:>70"),
+    ("vocals", "vocals:<20"),
+    ("plays", "plays:0"),
+    ("played", "-played"),
+    ("title", "title:dub"),
+    ("artist", "artist:\"daft punk\""),
+    ("album", "album:remixes"),
+    ("label", "label:kompakt"),
+    ("year", "year:2019-2021"),
+];
+
+/// Split on whitespace outside double quotes; quote characters are dropped
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Parse `a-b`, `<x`, `<=x`, `>x`, `>=x` or a single value
+///
+/// Comparisons are inclusive. A single value matches within `tolerance`.
+fn parse_range(value: &str, tolerance: f64) -> Option<NumRange> {
+    let num = |s: &str| s.trim().parse::<f64>().ok();
+    if let Some(v) = value.strip_prefix(">=").or_else(|| value.strip_prefix('>')) {
+        return Some(NumRange { min: Some(num(v)?), max: None });
+    }
+    if let Some(v) = value.strip_prefix("<=").or_else(|| value.strip_prefix('<')) {
+        return Some(NumRange { min: None, max: Some(num(v)?) });
+    }
+    // The range dash is the first '-' after a digit, so "-10--6" works
+    let bytes = value.as_bytes();
+    let dash = (1..bytes.len()).find(|&i| bytes[i] == b'-' && bytes[i - 1].is_ascii_digit());
+    if let Some(i) = dash {
+        let (lo, hi) = (&value[..i], &value[i + 1..]);
+        let range = NumRange {
+            min: if lo.is_empty() { None } else { Some(num(lo)?) },
+            max: if hi.is_empty() { None } else { Some(num(hi)?) },
+        };
+        return (range.min.is_some() || range.max.is_some()).then_some(range);
+    }
+    let v = num(value)?;
+    Some(NumRange { min: Some(v - tolerance), max: Some(v + tolerance) })
+}
+
+fn list(value: &str) -> Vec<String> {
+    value.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Parse `field:value`; None for an empty or malformed value
+fn parse_field(field: &str, value: &str) -> Option<Option<SearchFilter>> {
+    let text = || (!value.is_empty()).then(|| value.to_lowercase());
+    let values = || Some(list(value)).filter(|v| !v.is_empty());
+    let lower = |v: Vec<String>| -> Vec<String> { v.into_iter().map(|s| s.to_lowercase()).collect() };
+    let filter = match field {
+        "title" => text().map(SearchFilter::Title),
+        "artist" => text().map(SearchFilter::Artist),
+        "album" => text().map(SearchFilter::Album),
+        "label" => text().map(SearchFilter::Label),
+        "bpm" => parse_range(value, 0.5).map(SearchFilter::Bpm),
+        "key" => values().map(SearchFilter::Key),
+        "genre" => values().map(lower).map(SearchFilter::Genre),
+        "tag" | "tags" => values().map(lower).map(SearchFilter::Tag),
+        "lufs" => parse_range(value, 0.5).map(SearchFilter::Lufs),
+        "intensity" => parse_range(value, 0.5).map(SearchFilter::Intensity),
+        "vocals" => parse_range(value, 0.5).map(SearchFilter::Vocals),
+        "year" => parse_range(value, 0.0).map(SearchFilter::Year),
+        "plays" => parse_range(value, 0.0).map(SearchFilter::Plays),
+        _ => return None,
+    };
+    Some(filter)
+}
+
+fn parse_term(token: &str) -> Option<SearchTerm> {
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let filter = if body.eq_ignore_ascii_case("played") {
+        SearchFilter::Played
+    } else if let Some((field, value)) = body.split_once(':') {
+        match parse_field(&field.to_ascii_lowercase(), value.trim()) {
+            Some(parsed) => parsed?,
+            // Not a field we know: search for the literal text
+            None => SearchFilter::Text(body.to_lowercase()),
+        }
+    } else {
+        SearchFilter::Text(body.to_lowercase())
+    };
+    Some(SearchTerm { negated, filter })
+}
+
+impl SearchQuery {
+    /// Parse a search string
+    ///
+    /// Never fails: terms with an empty or malformed value (`bpm:`,
+    /// `bpm:fast`) are dropped, so the results don't flicker to nothing
+    /// while a term is still being typed.
+    pub fn parse(input: &str) -> Self {
+        Self { terms: tokenize(input).iter().filter_map(|t| parse_term(t)).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether the query needs the database, i.e. is more than plain words
+    ///
+    /// Plain words can be matched against the loaded rows directly.
+    pub fn is_structured(&self) -> bool {
+        self.terms.iter().any(|t| t.negated || !matches!(t.filter, SearchFilter::Text(_)))
+    }
+
+    /// Whether any term needs the library's distinct key strings
+    pub fn uses_keys(&self) -> bool {
+        self.terms.iter().any(|t| matches!(t.filter, SearchFilter::Key(_)))
+    }
+
+    /// Whether any term needs the current session
+    pub fn uses_session(&self) -> bool {
+        self.terms.iter().any(|t| matches!(t.filter, SearchFilter::Played))
+    }
+
+    /// Compile to CozoScript rules defining `matched[id]`
+    ///
+    /// Same contract as [`crate::playlist::SmartPlaylistRules::compile`]:
+    /// the caller appends the entry query.
+    pub fn compile(&self, ctx: &SmartContext) -> (String, BTreeMap<String, DataValue>) {
+        let mut script = String::new();
+        let mut params = BTreeMap::new();
+        let mut atoms = vec!["*tracks{id}".to_string()];
+
+        for (i, term) in self.terms.iter().enumerate() {
+            let name = format!("term_{}", i);
+            let (bodies, flip) = term.filter.bodies(&name, ctx, &mut params);
+            for body in bodies {
+                script.push_str(&format!("{}[id] := {}\n", name, body));
+            }
+            let negated = term.negated != flip;
+            atoms.push(format!("{}{}[id]", if negated { "not " } else { "" }, name));
+        }
+
+        let uses_play_count = self.terms.iter().any(|t| matches!(t.filter, SearchFilter::Plays(_)));
+        push_support_rules(&mut script, &params, uses_play_count);
+        script.push_str(&format!("matched[id] := {}\n", atoms.join(", ")));
+        (script, params)
+    }
+}
+
+/// Bind a text value as `$<prefix>_text`
+fn text_param(prefix: &str, value: &str, params: &mut BTreeMap<String, DataValue>) -> String {
+    params.insert(format!("{}_text", prefix), DataValue::Str(value.into()));
+    format!("${}_text", prefix)
+}
+
+impl SearchFilter {
+    /// Rule bodies binding `id` (alternatives), and whether they match the
+    /// complement of the filter
+    fn bodies(&self, prefix: &str, ctx: &SmartContext, params: &mut BTreeMap<String, DataValue>) -> (Vec<String>, bool) {
+        // Numeric filters share the smart playlist rules
+        let rule = match self {
+            SearchFilter::Text(s) => {
+                let p = text_param(prefix, s, params);
+                // coalesce() keeps lowercase() off the nullable artist column
+                return (vec![
+                    format!("*tracks{{id, title}}, str_includes(lowercase(title), {})", p),
+                    format!("*tracks{{id, artist}}, str_includes(lowercase(coalesce(artist, \"\")), {})", p),
+                ], false);
+            }
+            SearchFilter::Title(s) => {
+                let p = text_param(prefix, s, params);
+                return (vec![format!("*tracks{{id, title}}, str_includes(lowercase(title), {})", p)], false);
+            }
+            SearchFilter::Artist(s) => {
+                let p = text_param(prefix, s, params);
+                return (vec![format!(
+                    "*tracks{{id, artist}}, !is_null(artist), str_includes(lowercase(artist), {})", p
+                )], false);
+            }
+            SearchFilter::Album(s) | SearchFilter::Label(s) => {
+                let column = if matches!(self, SearchFilter::Album(_)) { "album" } else { "label" };
+                let p = text_param(prefix, s, params);
+                return (vec![format!(
+                    "*tag_metadata{{track_id: id, {0}}}, !is_null({0}), str_includes(lowercase({0}), {1})",
+                    column, p
+                )], false);
+            }
+            SearchFilter::Year(range) => {
+                let mut filters = Vec::new();
+                push_range(&mut filters, params, prefix, "year", range.min, range.max);
+                return (vec![format!(
+                    "*tag_metadata{{track_id: id, year}}, !is_null(year), {}", filters.join(", ")
+                )], false);
+            }
+            SearchFilter::Tag(tags) => {
+                // Tags are shown with their original case but typed lowercase
+                params.insert(format!("{}_tags", prefix), str_list(tags));
+                return (vec![format!(
+                    "*track_tags{{track_id: id, label}}, is_in(lowercase(label), ${}_tags)", prefix
+                )], false);
+            }
+            SearchFilter::Bpm(r) => SmartRule::Bpm { min: r.min, max: r.max },
+            SearchFilter::Lufs(r) => SmartRule::Lufs { min: r.min_f32(), max: r.max_f32() },
+            SearchFilter::Intensity(r) => {
+                let r = r.scaled(0.01);
+                SmartRule::Intensity { min: r.min_f32(), max: r.max_f32() }
+            }
+            SearchFilter::Vocals(r) => {
+                let r = r.scaled(0.01);
+                SmartRule::Vocals { min: r.min_f32(), max: r.max_f32() }
+            }
+            SearchFilter::Plays(r) => SmartRule::PlayCount {
+                min: r.min.map(|v| v.max(0.0).ceil() as u32),
+                max: r.max.map(|v| v.max(0.0).floor() as u32),
+            },
+            SearchFilter::Key(keys) => SmartRule::Key { keys: keys.clone() },
+            SearchFilter::Genre(genres) => SmartRule::Genre { genres: genres.clone() },
+            // "Played" is the complement of the smart playlist session rule
+            SearchFilter::Played => return (SmartRule::NotPlayedInSession.bodies(prefix, ctx, params), true),
+        };
+        (rule.bodies(prefix, ctx, params), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filters(input: &str) -> Vec<(bool, SearchFilter)> {
+        SearchQuery::parse(input).terms.into_iter().map(|t| (t.negated, t.filter)).collect()
+    }
+
+    #[test]
+    fn test_parse_full_query() {
+        let parsed = filters("bpm:124-128 key:8A,9A genre:techno tag:peak -played lufs:<-8");
+        assert_eq!(parsed, vec![
+            (false, SearchFilter::Bpm(NumRange { min: Some(124.0), max: Some(128.0) })),
+            (false, SearchFilter::Key(vec!["8A".into(), "9A".into()])),
+            (false, SearchFilter::Genre(vec!["techno".into()])),
+            (false, SearchFilter::Tag(vec!["peak".into()])),
+            (true, SearchFilter::Played),
+            (false, SearchFilter::Lufs(NumRange { min: None, max: Some(-8.0) })),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(parse_range("126", 0.5), Some(NumRange { min: Some(125.5), max: Some(126.5) }));
+        assert_eq!(parse_range(">=120", 0.5), Some(NumRange { min: Some(120.0), max: None }));
+        assert_eq!(parse_range("-10--6", 0.5), Some(NumRange { min: Some(-10.0), max: Some(-6.0) }));
+        assert_eq!(parse_range("120-", 0.5), Some(NumRange { min: Some(120.0), max: None }));
+        assert_eq!(parse_range("fast", 0.5), None);
+        assert_eq!(parse_range("-", 0.5), None);
+    }
+
+    #[test]
+    fn test_parse_text_quotes_and_partial_terms() {
+        let parsed = filters("artist:\"Daft Punk\" -remix foo:bar bpm: bpm:fast");
+        assert_eq!(parsed, vec![
+            (false, SearchFilter::Artist("daft punk".into())),
+            (true, SearchFilter::Text("remix".into())),
+            (false, SearchFilter::Text("foo:bar".into())),
+        ]);
+
+        assert!(!SearchQuery::parse("daft punk").is_structured());
+        assert!(SearchQuery::parse("daft -punk").is_structured());
+        assert!(SearchQuery::parse("tag:peak").is_structured());
+        assert!(SearchQuery::parse("  ").is_empty());
+    }
+
+    #[test]
+    fn test_compile_negation_and_played() {
+        let query = SearchQuery::parse("techno -played -tag:warmup");
+        let ctx = SmartContext { session_id: Some(7), ..Default::default() };
+        let (script, params) = query.compile(&ctx);
+        // -played flips the "not played in session" rule back to positive
+        assert!(script.contains("term_1[id] := *tracks{id}, not session_played[id]"));
+        assert!(script.contains("session_played[id] :="));
+        assert!(script.contains("matched[id] := *tracks{id}, term_0[id], term_1[id], not term_2[id]\n"));
+        assert_eq!(params["term_0_text"], DataValue::Str("techno".into()));
+    }
+
+    #[test]
+    fn test_compile_scales_percent_and_play_count() {
+        let query = SearchQuery::parse("intensity:>70 plays:0");
+        let (script, params) = query.compile(&SmartContext::default());
+        assert!(script.contains("*intensity_score{track_id: id, intensity}, intensity >= $term_0_min"));
+        assert_eq!(params["term_0_min"], DataValue::from(0.7_f32 as f64));
+        assert!(script.contains("play_count[id, n], n >= $term_1_min, n <= $term_1_max"));
+        assert!(script.contains("play_count[id, n] := *tracks{id}, not played[id], n = 0"));
+    }
+}
@@ -321,6 +321,9 @@ mod tests {
             waveform_path: None,
             cue_points: Vec::new(),
             saved_loops: Vec::new(),
+            tempo_anchors: Vec::new(),
+            phrase_sections: Vec::new(),
+            key_segments: Vec::new(),
             stem_links: Vec::new(),
         };
 
@@ -501,11 +501,33 @@ pub fn query_suggestions(
 
     // Step 4: Compute seed averages for scoring
 
+    // Seeds are outgoing tracks: match against the key they end in.
     let seed_keys: Vec<MusicalKey> = seed_tracks
         .iter()
-        .filter_map(|(_, t)| t.key.as_deref().and_then(MusicalKey::parse))
+        .filter_map(|(_, t)| t.outro_key().and_then(MusicalKey::parse))
         .collect();
 
+    // Candidates are incoming tracks: the key they start in matters. Only
+    // tracks whose key changes need an entry; the rest fall back to `key`.
+    let candidate_intro_keys: HashMap<(usize, i64), String> = {
+        let mut ids_by_source: HashMap<usize, Vec<i64>> = HashMap::new();
+        for &(src_idx, track_id) in candidates.keys() {
+            ids_by_source.entry(src_idx).or_default().push(track_id);
+        }
+        let mut map = HashMap::new();
+        for (src_idx, ids) in &ids_by_source {
+            match sources[*src_idx].db.batch_get_intro_keys(ids) {
+                Ok(keys) => {
+                    for (id, key) in keys {
+                        map.insert((*src_idx, id), key);
+                    }
+                }
+                Err(e) => log::debug!("[SUGGESTIONS] intro key fetch failed for source {}: {}", sources[*src_idx].name, e),
+            }
+        }
+        map
+    };
+
     // Energy direction bias: -1.0 (drop) through 0.0 (maintain) to +1.0 (peak)
     let energy_bias = (energy_direction - 0.5) * 2.0;
 
@@ -670,9 +692,10 @@ pub fn query_suggestions(
         .into_iter()
         .filter_map(|((src_idx, track_id), (track, hnsw_dist))| {
             // Key transition score: best match across all seeds
-            let (best_key_score, best_tt) = track
-                .key
-                .as_deref()
+            let (best_key_score, best_tt) = candidate_intro_keys
+                .get(&(src_idx, track_id))
+                .map(String::as_str)
+                .or(track.key.as_deref())
                 .and_then(|k| MusicalKey::parse(k))
                 .map(|ck| {
                     seed_keys
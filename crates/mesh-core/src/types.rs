@@ -155,6 +155,10 @@ impl std::ops::MulAssign<Sample> for StereoSample {
     }
 }
 
+/// One frame of all four stems as stereo pairs, in mesh's 8-channel stem
+/// order (Vocals, Drums, Bass, Other) — used by multitrack set recording
+pub type StemFrame = [StereoSample; NUM_STEMS];
+
 /// A buffer of stereo samples
 ///
 /// This is the primary audio buffer type used throughout Mesh for processing
@@ -94,6 +94,16 @@ pub fn clear_usb_database(collection_root: &Path) {
     }
 }
 
+/// Get every cached USB database
+///
+/// Used to aggregate play history across all mounted sticks.
+pub fn cached_usb_databases() -> Vec<Arc<DatabaseService>> {
+    USB_DB_CACHE.read()
+        .ok()
+        .and_then(|cache| cache.as_ref().map(|map| map.values().cloned().collect()))
+        .unwrap_or_default()
+}
+
 /// Clear all USB databases from the cache
 ///
 /// Called on application shutdown or when clearing all USB state.
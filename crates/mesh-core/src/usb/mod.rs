@@ -38,7 +38,7 @@ pub mod sync;
 // Re-export main types for convenience
 pub use cache::{
     get_usb_database, get_usb_database_for_path, get_or_open_usb_database,
-    register_usb_database, clear_usb_database, clear_all_usb_databases,
+    register_usb_database, clear_usb_database, clear_all_usb_databases, cached_usb_databases,
     find_collection_root,
 };
 pub use config::{
@@ -275,10 +275,12 @@ impl PlaylistStorage for UsbStorage {
             return Vec::new();
         };
 
-        // Batch-load tags and cue counts for all tracks in this playlist
+        // Batch-load tags, cue counts, tag metadata and ratings for all tracks in this playlist
         let track_db_ids: Vec<i64> = tracks.iter().map(|t| t.id).collect();
         let tags_map = db_service.get_tags_batch(&track_db_ids).unwrap_or_default();
         let cue_counts = db_service.get_cue_counts_batch(&track_db_ids).unwrap_or_default();
+        let meta_map = db_service.batch_get_tag_metadata(&track_db_ids).unwrap_or_default();
+        let ratings = db_service.batch_get_track_ratings(&track_db_ids).unwrap_or_default();
 
         // Tracks are already ordered by sort_order from DB, use enumerate for display order
         tracks
@@ -306,7 +308,16 @@ impl PlaylistStorage for UsbStorage {
                     lufs: track.lufs,
                     tags: tags_map.get(&track.id).cloned().unwrap_or_default(),
                     cue_count: cue_counts.get(&track.id).copied().unwrap_or(0),
+                    album: None,
+                    genre: None,
+                    year: None,
+                    label: None,
+                    comment: None,
+                    rating: 0,
+                    color_label: None,
                 }
+                .with_tag_metadata(meta_map.get(&track.id))
+                .with_rating(ratings.get(&track.id))
             })
             .collect()
     }
@@ -8,7 +8,7 @@
 //!
 //! Both local and USB collections use CozoDB databases for track and playlist metadata.
 
-use crate::db::{DatabaseService, MeshDb, MlAnalysisData, PlaylistQuery, TrackRow, CuePoint, SavedLoop, StemLink, CuePointQuery, SavedLoopQuery, StemLinkQuery, TrackQuery, SimilarityQuery};
+use crate::db::{DatabaseService, MeshDb, MlAnalysisData, PlaylistQuery, TrackRow, CuePoint, SavedLoop, TempoAnchor, PhraseSection, KeySegment, StemLink, CuePointQuery, SavedLoopQuery, TempoAnchorQuery, PhraseSectionQuery, KeySegmentQuery, StemLinkQuery, TagMetadataQuery, TrackQuery, SimilarityQuery};
 use super::cache::get_or_open_usb_database;
 // NOTE: No rayon here — USB drives are sequential I/O, and par_iter would
 // pollute the global rayon pool that the audio engine depends on.
@@ -35,6 +35,12 @@ pub struct TrackInfo {
     pub cue_points: Vec<CuePoint>,
     /// Saved loops for this track
     pub saved_loops: Vec<SavedLoop>,
+    /// Tempo anchors for this track (variable-tempo beat grid)
+    pub tempo_anchors: Vec<TempoAnchor>,
+    /// Phrase sections for this track (song structure)
+    pub phrase_sections: Vec<PhraseSection>,
+    /// Key segments for this track (key timeline)
+    pub key_segments: Vec<KeySegment>,
     /// Stem links for this track (linked stems for prepared mode)
     pub stem_links: Vec<StemLink>,
     /// ML analysis data (for metadata-only sync detection)
@@ -45,10 +51,14 @@ pub struct TrackInfo {
     pub has_ml_embedding: bool,
     /// Whether stem energy densities are stored for this track
     pub has_stem_energy: bool,
+    /// Whether embedded tag metadata (album, genre, ...) is stored for this track
+    pub has_tag_metadata: bool,
     /// Axis version of the stored V18.X intensity scalar (None = no scalar).
     /// Compared local-vs-USB so a stick with missing OR stale-axis scalars
     /// gets a metadata-only refresh on the next export.
     pub intensity_version: Option<String>,
+    /// Star rating and colour label (None = unrated)
+    pub rating: Option<(u8, Option<String>)>,
 }
 
 /// A track membership in a playlist (database record)
@@ -263,7 +273,8 @@ pub fn scan_local_collection_from_db(
             sort_order: playlist.sort_order,
         });
 
-        // Get tracks in this playlist
+        // Get tracks in this playlist (smart playlists are evaluated here and
+        // land on the stick as plain playlists)
         let tracks = PlaylistQuery::get_tracks(db, playlist.id)
             .map_err(|e| format!("Failed to get tracks for playlist {}: {}", playlist.name, e))?;
 
@@ -287,24 +298,30 @@ pub fn scan_local_collection_from_db(
         }
     }
 
-    // Bulk-fetch all supplementary data in 6 queries instead of 6×N
+    // Bulk-fetch all supplementary data in 7 queries instead of 7×N
     let cue_points_map = CuePointQuery::get_all(db).unwrap_or_default();
     let loops_map = SavedLoopQuery::get_all(db).unwrap_or_default();
+    let tempo_anchors_map = TempoAnchorQuery::get_all(db).unwrap_or_default();
+    let phrase_sections_map = PhraseSectionQuery::get_all(db).unwrap_or_default();
+    let key_segments_map = KeySegmentQuery::get_all(db).unwrap_or_default();
     let stem_links_map = StemLinkQuery::get_all(db).unwrap_or_default();
 
     let (ml_analysis_map, tags_map,
-         ml_embedding_set, stem_energy_set, intensity_version_map) = if let Some(svc) = db_service {
+         ml_embedding_set, stem_energy_set, tag_metadata_set, intensity_version_map, ratings_map) = if let Some(svc) = db_service {
         let ml = svc.get_all_ml_analysis().unwrap_or_default();
         let tags = svc.get_all_track_tags().unwrap_or_default();
         let embeddings: HashSet<i64> = SimilarityQuery::get_tracks_with_ml_embeddings(db)
             .unwrap_or_default().into_iter().collect();
         let stem_energy: HashSet<i64> = SimilarityQuery::get_tracks_with_stem_energy(db)
             .unwrap_or_default().into_iter().collect();
+        let tag_metadata: HashSet<i64> = TagMetadataQuery::get_track_ids(db)
+            .unwrap_or_default().into_iter().collect();
         let intensity: HashMap<i64, String> = SimilarityQuery::get_all_intensity_scores(db)
             .unwrap_or_default().into_iter().map(|(id, _, v)| (id, v)).collect();
-        (ml, tags, embeddings, stem_energy, intensity)
+        let ratings = svc.get_all_track_ratings().unwrap_or_default();
+        (ml, tags, embeddings, stem_energy, tag_metadata, intensity, ratings)
     } else {
-        (HashMap::new(), HashMap::new(), HashSet::new(), HashSet::new(), HashMap::new())
+        (HashMap::new(), HashMap::new(), HashSet::new(), HashSet::new(), HashSet::new(), HashMap::new(), HashMap::new())
     };
 
     // Get file metadata for all unique tracks (sequential — local disk I/O
@@ -331,12 +348,17 @@ pub fn scan_local_collection_from_db(
             // Get pre-fetched data
             let cue_points = cue_points_map.get(&db_track.id).cloned().unwrap_or_default();
             let saved_loops = loops_map.get(&db_track.id).cloned().unwrap_or_default();
+            let tempo_anchors = tempo_anchors_map.get(&db_track.id).cloned().unwrap_or_default();
+            let phrase_sections = phrase_sections_map.get(&db_track.id).cloned().unwrap_or_default();
+            let key_segments = key_segments_map.get(&db_track.id).cloned().unwrap_or_default();
             let stem_links = stem_links_map.get(&db_track.id).cloned().unwrap_or_default();
             let ml_analysis = ml_analysis_map.get(&db_track.id).cloned();
             let tags = tags_map.get(&db_track.id).cloned().unwrap_or_default();
             let has_ml_embedding   = ml_embedding_set.contains(&db_track.id);
             let has_stem_energy    = stem_energy_set.contains(&db_track.id);
+            let has_tag_metadata   = tag_metadata_set.contains(&db_track.id);
             let intensity_version  = intensity_version_map.get(&db_track.id).cloned();
+            let rating = ratings_map.get(&db_track.id).map(|r| (r.rating, r.color.clone()));
 
             Ok(TrackInfo {
                 path,
@@ -346,12 +368,17 @@ pub fn scan_local_collection_from_db(
                 db_track: Some(db_track),
                 cue_points,
                 saved_loops,
+                tempo_anchors,
+                phrase_sections,
+                key_segments,
                 stem_links,
                 ml_analysis,
                 tags,
                 has_ml_embedding,
                 has_stem_energy,
+                has_tag_metadata,
                 intensity_version,
+                rating,
             })
         })
         .collect();
@@ -398,12 +425,16 @@ pub fn scan_usb_collection(
 
     // Build map of filename -> metadata from USB database (bulk queries)
     #[allow(clippy::type_complexity)]
-    let mut db_metadata: HashMap<String, (TrackRow, Vec<CuePoint>, Vec<SavedLoop>, Vec<StemLink>,
-        Option<MlAnalysisData>, Vec<(String, Option<String>)>, bool, bool, Option<String>)> = HashMap::new();
+    let mut db_metadata: HashMap<String, (TrackRow, Vec<CuePoint>, Vec<SavedLoop>, Vec<TempoAnchor>, Vec<PhraseSection>, Vec<KeySegment>, Vec<StemLink>,
+        Option<MlAnalysisData>, Vec<(String, Option<String>)>, bool, bool, bool, Option<String>,
+        Option<(u8, Option<String>)>)> = HashMap::new();
     if let Some(ref db_service) = usb_db_service {
         if let Ok(all_tracks) = TrackQuery::get_all(db_service.db()) {
             let cue_map = CuePointQuery::get_all(db_service.db()).unwrap_or_default();
             let loop_map = SavedLoopQuery::get_all(db_service.db()).unwrap_or_default();
+            let anchor_map = TempoAnchorQuery::get_all(db_service.db()).unwrap_or_default();
+            let section_map = PhraseSectionQuery::get_all(db_service.db()).unwrap_or_default();
+            let key_map = KeySegmentQuery::get_all(db_service.db()).unwrap_or_default();
             let link_map = StemLinkQuery::get_all(db_service.db()).unwrap_or_default();
             let ml_map = db_service.get_all_ml_analysis().unwrap_or_default();
             let tag_map = db_service.get_all_track_tags().unwrap_or_default();
@@ -411,8 +442,11 @@ pub fn scan_usb_collection(
                 .unwrap_or_default().into_iter().collect();
             let stem_energy_set: HashSet<i64> = SimilarityQuery::get_tracks_with_stem_energy(db_service.db())
                 .unwrap_or_default().into_iter().collect();
+            let tag_metadata_set: HashSet<i64> = TagMetadataQuery::get_track_ids(db_service.db())
+                .unwrap_or_default().into_iter().collect();
             let intensity_version_map: HashMap<i64, String> = SimilarityQuery::get_all_intensity_scores(db_service.db())
                 .unwrap_or_default().into_iter().map(|(id, _, v)| (id, v)).collect();
+            let ratings_map = db_service.get_all_track_ratings().unwrap_or_default();
 
             for track in all_tracks {
                 let filename = PathBuf::from(&track.path)
@@ -423,15 +457,20 @@ pub fn scan_usb_collection(
 
                 let cue_points = cue_map.get(&track.id).cloned().unwrap_or_default();
                 let saved_loops = loop_map.get(&track.id).cloned().unwrap_or_default();
+                let tempo_anchors = anchor_map.get(&track.id).cloned().unwrap_or_default();
+                let phrase_sections = section_map.get(&track.id).cloned().unwrap_or_default();
+                let key_segments = key_map.get(&track.id).cloned().unwrap_or_default();
                 let stem_links = link_map.get(&track.id).cloned().unwrap_or_default();
                 let ml_analysis = ml_map.get(&track.id).cloned();
                 let tags = tag_map.get(&track.id).cloned().unwrap_or_default();
                 let has_ml_embedding   = embedding_set.contains(&track.id);
                 let has_stem_energy    = stem_energy_set.contains(&track.id);
+                let has_tag_metadata   = tag_metadata_set.contains(&track.id);
                 let intensity_version  = intensity_version_map.get(&track.id).cloned();
+                let rating = ratings_map.get(&track.id).map(|r| (r.rating, r.color.clone()));
 
-                db_metadata.insert(filename, (track, cue_points, saved_loops, stem_links,
-                    ml_analysis, tags, has_ml_embedding, has_stem_energy, intensity_version));
+                db_metadata.insert(filename, (track, cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links,
+                    ml_analysis, tags, has_ml_embedding, has_stem_energy, has_tag_metadata, intensity_version, rating));
             }
         }
 
@@ -503,14 +542,14 @@ pub fn scan_usb_collection(
             }
 
             // Get database metadata if available
-            let (db_track, cue_points, saved_loops, stem_links, ml_analysis, tags,
-                 has_ml_embedding, has_stem_energy, intensity_version) =
+            let (db_track, cue_points, saved_loops, tempo_anchors, phrase_sections, key_segments, stem_links, ml_analysis, tags,
+                 has_ml_embedding, has_stem_energy, has_tag_metadata, intensity_version, rating) =
                 db_metadata
                     .get(&filename)
-                    .map(|(t, c, l, s, ml, tg, me, se, iv)| {
-                        (Some(t.clone()), c.clone(), l.clone(), s.clone(), ml.clone(), tg.clone(), *me, *se, iv.clone())
+                    .map(|(t, c, l, ta, ps, ks, s, ml, tg, me, se, tm, iv, r)| {
+                        (Some(t.clone()), c.clone(), l.clone(), ta.clone(), ps.clone(), ks.clone(), s.clone(), ml.clone(), tg.clone(), *me, *se, *tm, iv.clone(), r.clone())
                     })
-                    .unwrap_or((None, Vec::new(), Vec::new(), Vec::new(), None, Vec::new(), false, false, None));
+                    .unwrap_or((None, Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), None, Vec::new(), false, false, false, None, None));
 
             Ok(TrackInfo {
                 path,
@@ -520,12 +559,17 @@ pub fn scan_usb_collection(
                 db_track,
                 cue_points,
                 saved_loops,
+                tempo_anchors,
+                phrase_sections,
+                key_segments,
                 stem_links,
                 ml_analysis,
                 tags,
                 has_ml_embedding,
                 has_stem_energy,
+                has_tag_metadata,
                 intensity_version,
+                rating,
             })
         })
         .collect();
@@ -542,7 +586,7 @@ pub fn scan_usb_collection(
 
 /// Check if metadata differs between local and USB tracks
 ///
-/// Compares track fields (BPM, key, artist, etc.), cue points, saved loops, and stem links.
+/// Compares track fields (BPM, key, artist, etc.), cue points, saved loops, tempo anchors, and stem links.
 /// Returns true if any metadata field differs and requires re-export.
 fn metadata_differs(local: &TrackInfo, usb: &TrackInfo) -> bool {
     // Compare Track fields if both have db_track
@@ -574,6 +618,11 @@ fn metadata_differs(local: &TrackInfo, usb: &TrackInfo) -> bool {
                 log::debug!("metadata_differs: drop_marker differs for {} ({:?} vs {:?})", local.filename, l.drop_marker, u.drop_marker);
                 return true;
             }
+            // Compare beat grid anchor (grid nudges in the editor)
+            if l.first_beat_sample != u.first_beat_sample {
+                log::debug!("metadata_differs: first_beat_sample differs for {} ({} vs {})", local.filename, l.first_beat_sample, u.first_beat_sample);
+                return true;
+            }
         }
         (Some(_), None) => {
             // Local has metadata, USB doesn't - needs export
@@ -599,6 +648,27 @@ fn metadata_differs(local: &TrackInfo, usb: &TrackInfo) -> bool {
         return true;
     }
 
+    // Compare tempo anchors (variable-tempo beat grid)
+    if !tempo_anchors_equal(&local.tempo_anchors, &usb.tempo_anchors) {
+        log::debug!("metadata_differs: tempo_anchors differ for {} ({} local vs {} usb)",
+            local.filename, local.tempo_anchors.len(), usb.tempo_anchors.len());
+        return true;
+    }
+
+    // Compare phrase sections (song structure)
+    if !phrase_sections_equal(&local.phrase_sections, &usb.phrase_sections) {
+        log::debug!("metadata_differs: phrase_sections differ for {} ({} local vs {} usb)",
+            local.filename, local.phrase_sections.len(), usb.phrase_sections.len());
+        return true;
+    }
+
+    // Compare key timelines
+    if !key_segments_equal(&local.key_segments, &usb.key_segments) {
+        log::debug!("metadata_differs: key_segments differ for {} ({} local vs {} usb)",
+            local.filename, local.key_segments.len(), usb.key_segments.len());
+        return true;
+    }
+
     // Compare stem links (linked stems for prepared mode)
     if !stem_links_equal(&local.stem_links, &usb.stem_links) {
         log::debug!("metadata_differs: stem_links differ for {} ({} local vs {} usb)",
@@ -645,6 +715,10 @@ fn metadata_differs(local: &TrackInfo, usb: &TrackInfo) -> bool {
         log::debug!("metadata_differs: stem_energy presence differs for {}", local.filename);
         return true;
     }
+    if local.has_tag_metadata != usb.has_tag_metadata {
+        log::debug!("metadata_differs: tag_metadata presence differs for {}", local.filename);
+        return true;
+    }
     if local.intensity_version != usb.intensity_version {
         log::debug!(
             "metadata_differs: intensity scalar differs for {} ({:?} vs {:?})",
@@ -652,6 +726,10 @@ fn metadata_differs(local: &TrackInfo, usb: &TrackInfo) -> bool {
         );
         return true;
     }
+    if local.rating != usb.rating {
+        log::debug!("metadata_differs: rating differs for {}", local.filename);
+        return true;
+    }
     false
 }
 
@@ -694,6 +772,44 @@ fn saved_loops_equal(a: &[SavedLoop], b: &[SavedLoop]) -> bool {
     true
 }
 
+/// Compare two phrase section lists for equality (in track order)
+fn phrase_sections_equal(a: &[PhraseSection], b: &[PhraseSection]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(section_a, section_b)| {
+            section_a.start_sample == section_b.start_sample
+                && section_a.end_sample == section_b.end_sample
+                && section_a.kind == section_b.kind
+                && section_a.bars == section_b.bars
+        })
+}
+
+/// Compare two key timelines for equality (in track order)
+fn key_segments_equal(a: &[KeySegment], b: &[KeySegment]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(segment_a, segment_b)| {
+            segment_a.start_sample == segment_b.start_sample
+                && segment_a.end_sample == segment_b.end_sample
+                && segment_a.key == segment_b.key
+        })
+}
+
+/// Compare two tempo anchor lists for equality (ignoring order)
+fn tempo_anchors_equal(a: &[TempoAnchor], b: &[TempoAnchor]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for anchor_a in a {
+        let found = b.iter().any(|anchor_b| {
+            anchor_a.sample_position == anchor_b.sample_position
+                && anchor_a.bpm == anchor_b.bpm
+        });
+        if !found {
+            return false;
+        }
+    }
+    true
+}
+
 /// Compare two stem link lists for equality (ignoring order)
 fn stem_links_equal(a: &[StemLink], b: &[StemLink]) -> bool {
     if a.len() != b.len() {
@@ -997,12 +1113,17 @@ mod tests {
             db_track: None,
             cue_points: Vec::new(),
             saved_loops: Vec::new(),
+            tempo_anchors: Vec::new(),
+            phrase_sections: Vec::new(),
+            key_segments: Vec::new(),
             stem_links: Vec::new(),
             ml_analysis: None,
             tags: Vec::new(),
             has_ml_embedding: false,
             has_stem_energy: false,
+            has_tag_metadata: false,
             intensity_version: None,
+            rating: None,
         }
     }
 
@@ -1028,6 +1149,94 @@ mod tests {
         assert!(metadata_differs(&local, &usb));
     }
 
+    #[test]
+    fn test_metadata_differs_on_rating() {
+        let mtime = SystemTime::UNIX_EPOCH;
+        let mut local = test_track_info("/m/a.flac", "a.flac", 100, mtime);
+        let mut usb = test_track_info("/u/a.flac", "a.flac", 100, mtime);
+
+        local.rating = Some((4, None));
+        assert!(metadata_differs(&local, &usb));
+
+        usb.rating = Some((4, None));
+        assert!(!metadata_differs(&local, &usb));
+
+        // Colour label changed locally
+        local.rating = Some((4, Some("#d04040".to_string())));
+        assert!(metadata_differs(&local, &usb));
+    }
+
+    #[test]
+    fn test_metadata_differs_on_tempo_anchors() {
+        let mtime = SystemTime::UNIX_EPOCH;
+        let mut local = test_track_info("/m/a.flac", "a.flac", 100, mtime);
+        let mut usb = test_track_info("/u/a.flac", "a.flac", 100, mtime);
+
+        local.tempo_anchors = vec![
+            TempoAnchor { track_id: 1, index: 0, sample_position: 0, bpm: 120.0 },
+            TempoAnchor { track_id: 1, index: 1, sample_position: 960_000, bpm: 124.0 },
+        ];
+        assert!(metadata_differs(&local, &usb));
+
+        // Track IDs differ between databases — only positions and tempos matter
+        usb.tempo_anchors = vec![
+            TempoAnchor { track_id: 7, index: 0, sample_position: 0, bpm: 120.0 },
+            TempoAnchor { track_id: 7, index: 1, sample_position: 960_000, bpm: 124.0 },
+        ];
+        assert!(!metadata_differs(&local, &usb));
+
+        usb.tempo_anchors[1].bpm = 126.0;
+        assert!(metadata_differs(&local, &usb));
+    }
+
+    #[test]
+    fn test_metadata_differs_on_phrase_sections() {
+        let mtime = SystemTime::UNIX_EPOCH;
+        let mut local = test_track_info("/m/a.flac", "a.flac", 100, mtime);
+        let mut usb = test_track_info("/u/a.flac", "a.flac", 100, mtime);
+
+        let section = |track_id, kind: &str| PhraseSection {
+            track_id,
+            index: 0,
+            start_sample: 0,
+            end_sample: 1_536_000,
+            kind: kind.to_string(),
+            bars: 16,
+        };
+
+        local.phrase_sections = vec![section(1, "intro")];
+        assert!(metadata_differs(&local, &usb));
+
+        usb.phrase_sections = vec![section(7, "intro")];
+        assert!(!metadata_differs(&local, &usb));
+
+        // Re-analysis relabelled the section
+        usb.phrase_sections = vec![section(7, "build")];
+        assert!(metadata_differs(&local, &usb));
+    }
+
+    #[test]
+    fn test_metadata_differs_on_key_segments() {
+        let mtime = SystemTime::UNIX_EPOCH;
+        let mut local = test_track_info("/m/a.flac", "a.flac", 100, mtime);
+        let mut usb = test_track_info("/u/a.flac", "a.flac", 100, mtime);
+
+        let segment = |track_id, key: &str| KeySegment {
+            track_id,
+            index: 0,
+            start_sample: 0,
+            end_sample: 9_600_000,
+            key: key.to_string(),
+        };
+
+        local.key_segments = vec![segment(1, "Am"), KeySegment { index: 1, start_sample: 9_600_000, end_sample: 14_400_000, ..segment(1, "Bm") }];
+        usb.key_segments = vec![segment(4, "Am")];
+        assert!(metadata_differs(&local, &usb));
+
+        usb.key_segments = local.key_segments.iter().map(|s| KeySegment { track_id: 4, ..s.clone() }).collect();
+        assert!(!metadata_differs(&local, &usb));
+    }
+
     #[test]
     fn test_sync_plan_summary() {
         let plan = SyncPlan {
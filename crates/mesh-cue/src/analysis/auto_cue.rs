@@ -0,0 +1,392 @@
+//! Automatic hot cues and saved loops
+//!
+//! Places hot cues on landmarks of the analysed track (first downbeat,
+//! drop, breakdown, outro) and saved loops on clean intro/outro regions,
+//! following a user-editable template stored in the config
+//! (`analysis.auto_cues`). Slots the user already filled are never touched.
+//!
+//! ## Loop regions
+//!
+//! The intro region is the run of intro sections at the start of the
+//! track, the outro region the run of outro sections at the end. Inside a
+//! region, loops start on 4-bar boundaries; the first window whose vocals
+//! stay quiet and whose drums keep playing is used. Nothing is placed when
+//! no window qualifies.
+
+use mesh_core::audio_file::{CuePoint, PhraseSection, SavedLoop, SectionKind};
+use serde::{Deserialize, Serialize};
+
+use super::phrase::{normalize_bars, BarEnergy, BarProfile, HOT_CUE_SLOTS, MIN_CUE_SPACING};
+
+/// Loop candidates start on multiples of this many bars
+const LOOP_ALIGN_BARS: usize = 4;
+
+/// Longest loop a template may ask for
+const MAX_LOOP_BARS: u32 = 32;
+
+/// Highest normalized vocal level allowed anywhere in a loop
+const VOCAL_CLEAN_LEVEL: f32 = 0.15;
+
+/// Lowest mean normalized drum level of a loop (loops should keep a beat)
+const MIN_LOOP_DRUMS: f32 = 0.1;
+
+/// Where a template hot cue goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CueAnchor {
+    /// First bar of the beat grid
+    FirstDownbeat,
+    /// Start of a build section
+    Build,
+    /// Start of a drop
+    Drop,
+    /// Start of a breakdown
+    Breakdown,
+    /// Start of the outro
+    Outro,
+}
+
+impl CueAnchor {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::FirstDownbeat => "Start",
+            Self::Build => SectionKind::Build.display_name(),
+            Self::Drop => SectionKind::Drop.display_name(),
+            Self::Breakdown => SectionKind::Breakdown.display_name(),
+            Self::Outro => SectionKind::Outro.display_name(),
+        }
+    }
+
+    /// Section kind the anchor points at (`None` for the first downbeat)
+    fn section_kind(&self) -> Option<SectionKind> {
+        match self {
+            Self::FirstDownbeat => None,
+            Self::Build => Some(SectionKind::Build),
+            Self::Drop => Some(SectionKind::Drop),
+            Self::Breakdown => Some(SectionKind::Breakdown),
+            Self::Outro => Some(SectionKind::Outro),
+        }
+    }
+}
+
+/// Where a template loop goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopRegion {
+    /// Intro sections at the start of the track
+    Intro,
+    /// Outro sections at the end of the track
+    Outro,
+}
+
+impl LoopRegion {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Intro => "Intro",
+            Self::Outro => "Outro",
+        }
+    }
+}
+
+/// One hot cue of the template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotCueTemplate {
+    /// Hot cue slot (0-7)
+    pub index: u8,
+    pub anchor: CueAnchor,
+    /// Which occurrence of the section to use (1 = first drop, 2 = second, ...)
+    #[serde(default = "default_occurrence")]
+    pub occurrence: u32,
+    /// Label (`None` = the anchor's name)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Hex colour (`None` = the section's colour)
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// One saved loop of the template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopTemplate {
+    /// Saved loop slot (0-7)
+    pub index: u8,
+    pub region: LoopRegion,
+    /// Loop length in bars (typically 4 or 8)
+    pub bars: u32,
+    /// Label (`None` = the region's name)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Hex colour (`None` = the region's section colour)
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+fn default_occurrence() -> u32 {
+    1
+}
+
+/// Auto-cue generator settings (persisted as `analysis.auto_cues`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoCueConfig {
+    /// Apply the template to every imported track
+    pub enabled: bool,
+    /// Hot cues to place, in priority order
+    pub hot_cues: Vec<HotCueTemplate>,
+    /// Saved loops to place, in priority order
+    pub loops: Vec<LoopTemplate>,
+}
+
+impl Default for AutoCueConfig {
+    fn default() -> Self {
+        let cue = |index, anchor| HotCueTemplate { index, anchor, occurrence: 1, label: None, color: None };
+        let loop_ = |index, region| LoopTemplate { index, region, bars: 8, label: None, color: None };
+        Self {
+            enabled: false,
+            hot_cues: vec![
+                cue(0, CueAnchor::FirstDownbeat),
+                cue(1, CueAnchor::Drop),
+                cue(2, CueAnchor::Breakdown),
+                cue(3, CueAnchor::Outro),
+            ],
+            loops: vec![loop_(0, LoopRegion::Intro), loop_(1, LoopRegion::Outro)],
+        }
+    }
+}
+
+/// Hot cues and saved loops generated for one track
+#[derive(Debug, Clone, Default)]
+pub struct AutoCues {
+    pub cues: Vec<CuePoint>,
+    pub loops: Vec<SavedLoop>,
+}
+
+/// Apply the template to an analysed track
+///
+/// `sections` are the track's phrase sections (may be empty, in which case
+/// only the first-downbeat cue can be placed). Template entries whose slot
+/// is already used by `existing_cues` / `existing_loops` (or by an earlier
+/// entry) are skipped, as are cues within a second of an existing cue.
+pub fn generate_auto_cues(
+    config: &AutoCueConfig,
+    profile: &BarProfile,
+    sections: &[PhraseSection],
+    existing_cues: &[CuePoint],
+    existing_loops: &[SavedLoop],
+) -> AutoCues {
+    let mut placed = AutoCues::default();
+
+    for entry in &config.hot_cues {
+        let slot_used = existing_cues.iter().chain(&placed.cues).any(|c| c.index == entry.index);
+        if entry.index >= HOT_CUE_SLOTS || slot_used {
+            continue;
+        }
+        let Some(position) = anchor_position(entry, profile, sections) else {
+            continue;
+        };
+        // Position 0 marks an empty slot in the cue editor
+        let near_existing = existing_cues
+            .iter()
+            .chain(&placed.cues)
+            .any(|c| c.sample_position.abs_diff(position) < MIN_CUE_SPACING);
+        if position == 0 || near_existing {
+            continue;
+        }
+        placed.cues.push(CuePoint {
+            index: entry.index,
+            sample_position: position,
+            label: entry.label.clone().unwrap_or_else(|| entry.anchor.display_name().to_string()),
+            color: entry
+                .color
+                .clone()
+                .or_else(|| entry.anchor.section_kind().map(|k| k.color().to_string())),
+        });
+    }
+
+    let normalized = normalize_bars(&profile.energies).unwrap_or_default();
+    for entry in &config.loops {
+        let slot_used = existing_loops.iter().chain(&placed.loops).any(|l| l.index == entry.index);
+        if entry.index >= HOT_CUE_SLOTS || slot_used {
+            continue;
+        }
+        let Some((start_bar, end_bar)) = clean_loop(entry, profile, &normalized, sections) else {
+            continue;
+        };
+        let kind = match entry.region {
+            LoopRegion::Intro => SectionKind::Intro,
+            LoopRegion::Outro => SectionKind::Outro,
+        };
+        placed.loops.push(SavedLoop {
+            index: entry.index,
+            start_sample: profile.starts[start_bar],
+            end_sample: profile.starts.get(end_bar).copied().unwrap_or(profile.end_sample),
+            label: entry.label.clone().unwrap_or_else(|| entry.region.display_name().to_string()),
+            color: entry.color.clone().or_else(|| Some(kind.color().to_string())),
+        });
+    }
+
+    placed
+}
+
+/// Sample position of a template cue's landmark
+fn anchor_position(entry: &HotCueTemplate, profile: &BarProfile, sections: &[PhraseSection]) -> Option<u64> {
+    let Some(kind) = entry.anchor.section_kind() else {
+        return profile.starts.first().copied();
+    };
+    // Each run of sections of the kind counts once (two 32-bar drop
+    // halves are one drop)
+    sections
+        .iter()
+        .enumerate()
+        .filter(|&(i, s)| s.kind == kind && (i == 0 || sections[i - 1].kind != kind))
+        .nth(entry.occurrence.max(1) as usize - 1)
+        .map(|(_, s)| s.start_sample)
+}
+
+/// `(start_bar, end_bar)` of the first clean loop in the template's region
+fn clean_loop(
+    entry: &LoopTemplate,
+    profile: &BarProfile,
+    normalized: &[BarEnergy],
+    sections: &[PhraseSection],
+) -> Option<(usize, usize)> {
+    let (first, last) = match entry.region {
+        LoopRegion::Intro => {
+            let run = sections.iter().take_while(|s| s.kind == SectionKind::Intro);
+            (sections.first()?.start_sample, run.last()?.end_sample)
+        }
+        LoopRegion::Outro => {
+            let run = sections.iter().rev().take_while(|s| s.kind == SectionKind::Outro);
+            let first = run.last()?.start_sample;
+            (first, sections.last()?.end_sample)
+        }
+    };
+    let bars = entry.bars.clamp(1, MAX_LOOP_BARS) as usize;
+    let region: Vec<usize> = (0..normalized.len())
+        .filter(|&b| profile.starts[b] >= first && profile.starts[b] < last)
+        .collect();
+    let (&region_start, &region_last) = (region.first()?, region.last()?);
+
+    (region_start..=region_last)
+        .filter(|b| b % LOOP_ALIGN_BARS == 0 && b + bars <= region_last + 1)
+        .find(|&b| {
+            let window = &normalized[b..b + bars];
+            let vocals_quiet = window.iter().all(|bar| bar.vocals <= VOCAL_CLEAN_LEVEL);
+            vocals_quiet && BarEnergy::mean(window).drums >= MIN_LOOP_DRUMS
+        })
+        .map(|b| (b, b + bars))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bar length in samples (2 s, so landmarks are further apart than
+    /// the cue spacing)
+    const BAR: u64 = 96_000;
+
+    fn bar(b: u64) -> u64 {
+        1000 + b * BAR
+    }
+
+    /// 64 bars: intro 0-15, drop 16-31, breakdown 32-39, drop 40-55,
+    /// outro 56-63
+    fn track() -> (BarProfile, Vec<PhraseSection>) {
+        let energies = (0..64)
+            .map(|b| {
+                let vocals = match b {
+                    0..=3 | 60..=63 => 0.8, // vocal intro / outro tag
+                    16..=55 => 0.6,
+                    _ => 0.0,
+                };
+                let drums = if (32..40).contains(&b) { 0.0 } else { 1.0 };
+                BarEnergy { vocals, drums, bass: drums, other: 0.5 }
+            })
+            .collect();
+        let profile = BarProfile {
+            starts: (0..64).map(bar).collect(),
+            energies,
+            end_sample: bar(64),
+        };
+        let section = |start: u64, end: u64, kind| PhraseSection {
+            start_sample: bar(start),
+            end_sample: bar(end),
+            kind,
+            bars: (end - start) as u32,
+        };
+        let sections = vec![
+            section(0, 16, SectionKind::Intro),
+            section(16, 32, SectionKind::Drop),
+            section(32, 40, SectionKind::Breakdown),
+            section(40, 56, SectionKind::Drop),
+            section(56, 64, SectionKind::Outro),
+        ];
+        (profile, sections)
+    }
+
+    #[test]
+    fn test_default_template_fills_free_slots() {
+        let (profile, sections) = track();
+        // Slot 1 is taken; a user cue sits right on the breakdown
+        let existing = [
+            CuePoint { index: 1, sample_position: bar(20), label: String::new(), color: None },
+            CuePoint { index: 5, sample_position: bar(32) + 1000, label: String::new(), color: None },
+        ];
+        let placed = generate_auto_cues(&AutoCueConfig::default(), &profile, &sections, &existing, &[]);
+
+        let cues: Vec<(u8, u64, &str)> = placed
+            .cues
+            .iter()
+            .map(|c| (c.index, c.sample_position, c.label.as_str()))
+            .collect();
+        assert_eq!(cues, vec![(0, bar(0), "Start"), (3, bar(56), "Outro")]);
+        assert_eq!(placed.cues[1].color.as_deref(), Some(SectionKind::Outro.color()));
+    }
+
+    #[test]
+    fn test_loops_avoid_vocals() {
+        let (profile, sections) = track();
+        let existing = [SavedLoop { index: 1, start_sample: 1, end_sample: 2, label: String::new(), color: None }];
+        let mut config = AutoCueConfig::default();
+        config.loops.push(LoopTemplate {
+            index: 2,
+            region: LoopRegion::Outro,
+            bars: 4,
+            label: Some("Tail".to_string()),
+            color: None,
+        });
+        let placed = generate_auto_cues(&config, &profile, &sections, &[], &existing);
+
+        let loops: Vec<(u8, u64, u64, &str)> = placed
+            .loops
+            .iter()
+            .map(|l| (l.index, l.start_sample, l.end_sample, l.label.as_str()))
+            .collect();
+        // Intro: bars 0-3 carry vocals, so the 8-bar loop starts at bar 4.
+        // Outro: 4 clean bars (56-59) before the vocal tag.
+        assert_eq!(loops, vec![(0, bar(4), bar(12), "Intro"), (2, bar(56), bar(60), "Tail")]);
+    }
+
+    #[test]
+    fn test_drop_occurrence() {
+        let (profile, sections) = track();
+        let config = AutoCueConfig {
+            enabled: true,
+            hot_cues: vec![HotCueTemplate {
+                index: 4,
+                anchor: CueAnchor::Drop,
+                occurrence: 2,
+                label: Some("Drop 2".to_string()),
+                color: Some("#ff0000".to_string()),
+            }],
+            loops: Vec::new(),
+        };
+        let placed = generate_auto_cues(&config, &profile, &sections, &[], &[]);
+        assert_eq!(placed.cues.len(), 1);
+        assert_eq!(placed.cues[0].sample_position, bar(40));
+        assert_eq!(placed.cues[0].color.as_deref(), Some("#ff0000"));
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(serde_yaml::from_str::<AutoCueConfig>(&yaml).unwrap(), config);
+    }
+}
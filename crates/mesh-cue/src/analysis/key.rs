@@ -3,11 +3,24 @@
 //! This module detects the musical key of a track using Essentia's
 //! KeyExtractor algorithm, which internally handles HPCP computation
 //! and key correlation in one convenient package.
+//!
+//! Besides the whole-track key, [`detect_key_timeline`] runs the same
+//! extractor over overlapping windows to find where a track modulates.
 
 use anyhow::{Context, Result};
 use essentia::algorithm::tonal::key_extractor::KeyExtractor;
 use essentia::data::GetFromDataContainer;
 use essentia::essentia::Essentia;
+use mesh_core::audio_file::KeySegment;
+
+/// Essentia algorithms expect 44100 Hz input
+const ESSENTIA_RATE: u64 = 44100;
+/// Length of one key-detection window (seconds)
+const WINDOW_SECONDS: u64 = 30;
+/// Hop between consecutive windows (seconds)
+const HOP_SECONDS: u64 = 15;
+/// Runs shorter than this many windows are treated as detection noise
+const MIN_RUN_WINDOWS: usize = 2;
 
 /// Detect the musical key from audio samples
 ///
@@ -16,12 +29,11 @@ use essentia::essentia::Essentia;
 /// The "edma" profile type is used, which is optimized for Electronic Dance Music.
 ///
 /// # Arguments
-/// * `samples` - Mono audio samples at the system sample rate (48kHz)
+/// * `samples` - Mono audio samples at 44100 Hz
 ///
 /// # Returns
 /// Key string in format like "Am", "C", "F#m", "Bb"
 pub fn detect_key(samples: &[f32]) -> Result<String> {
-    use mesh_core::types::SAMPLE_RATE;
     log::info!("Starting key detection on {} samples", samples.len());
 
     // Create Essentia instance
@@ -29,12 +41,12 @@ pub fn detect_key(samples: &[f32]) -> Result<String> {
 
     // Create and configure KeyExtractor
     // - profile_type: "edma" (Electronic Dance Music Average) for EDM tracks
-    // - sample_rate: System sample rate (48kHz default)
+    // - sample_rate: Essentia's analysis rate (44.1kHz)
     let mut key_algo = essentia
         .create::<KeyExtractor>()
         .profile_type("edma")
         .context("Failed to set profile_type")?
-        .sample_rate(SAMPLE_RATE as f32)
+        .sample_rate(ESSENTIA_RATE as f32)
         .context("Failed to set sample_rate")?
         .configure()
         .context("Failed to configure KeyExtractor")?;
@@ -69,7 +81,151 @@ pub fn detect_key(samples: &[f32]) -> Result<String> {
         strength
     );
 
-    // Format as standard key notation: "Am", "C", "F#m", "Bb", etc.
+    Ok(format_key(&key, &scale))
+}
+
+/// Detect how the key develops over the course of a track
+///
+/// Runs KeyExtractor on 30 s windows with a 15 s hop, then smooths the
+/// per-window keys: single-window outliers are absorbed into their
+/// neighbours and equal neighbours are merged. Tracks shorter than one
+/// window produce a single segment.
+///
+/// # Arguments
+/// * `samples` - Mono audio samples at 44100 Hz
+///
+/// # Returns
+/// Segments covering the whole track, positioned at the system sample rate
+pub fn detect_key_timeline(samples: &[f32]) -> Result<Vec<KeySegment>> {
+    use mesh_core::types::SAMPLE_RATE;
+
+    let window = (WINDOW_SECONDS * ESSENTIA_RATE) as usize;
+    let hop = (HOP_SECONDS * ESSENTIA_RATE) as usize;
+    let duration = samples.len() as u64 * SAMPLE_RATE as u64 / ESSENTIA_RATE;
+    let hop_at_system_rate = HOP_SECONDS * SAMPLE_RATE as u64;
+
+    if samples.len() <= window {
+        let key = detect_key(samples)?;
+        return Ok(segments_from_windows(&[key], hop_at_system_rate, duration));
+    }
+
+    let essentia = Essentia::new();
+    let mut key_algo = essentia
+        .create::<KeyExtractor>()
+        .profile_type("edma")
+        .context("Failed to set profile_type")?
+        .sample_rate(ESSENTIA_RATE as f32)
+        .context("Failed to set sample_rate")?
+        .configure()
+        .context("Failed to configure KeyExtractor")?;
+
+    let mut window_keys = Vec::new();
+    let mut start = 0;
+    while start + window <= samples.len() {
+        let result = key_algo
+            .compute(&samples[start..start + window])
+            .context("KeyExtractor computation failed")?;
+        let key: String = result.key().context("Failed to get key output")?.get();
+        let scale: String = result.scale().context("Failed to get scale output")?.get();
+        window_keys.push(format_key(&key, &scale));
+        start += hop;
+    }
+
+    let segments = segments_from_windows(&window_keys, hop_at_system_rate, duration);
+    log::info!(
+        "Key timeline complete: {} windows -> {} segment(s)",
+        window_keys.len(),
+        segments.len()
+    );
+    Ok(segments)
+}
+
+/// Format Essentia's key/scale pair as standard notation ("Am", "C", "F#m")
+fn format_key(key: &str, scale: &str) -> String {
     let suffix = if scale == "minor" { "m" } else { "" };
-    Ok(format!("{}{}", key, suffix))
+    format!("{}{}", key, suffix)
+}
+
+/// Turn per-window keys into smoothed, merged segments
+///
+/// Window `i` owns `[i * hop, (i + 1) * hop)`; the last segment is extended
+/// to `duration`. Runs shorter than [`MIN_RUN_WINDOWS`] take the key of the
+/// preceding run (or the following one at the very start).
+fn segments_from_windows(window_keys: &[String], hop: u64, duration: u64) -> Vec<KeySegment> {
+    // Run-length encode: (key, window count)
+    let mut runs: Vec<(&str, usize)> = Vec::new();
+    for key in window_keys {
+        match runs.last_mut() {
+            Some((k, n)) if *k == key.as_str() => *n += 1,
+            _ => runs.push((key.as_str(), 1)),
+        }
+    }
+
+    // Absorb short runs into their neighbours, then merge equal neighbours
+    if runs.len() > 1 {
+        for i in 0..runs.len() {
+            if runs[i].1 < MIN_RUN_WINDOWS {
+                let neighbour = if i > 0 { runs[i - 1].0 } else { runs[1].0 };
+                runs[i].0 = neighbour;
+            }
+        }
+    }
+    let mut merged: Vec<(&str, usize)> = Vec::new();
+    for (key, n) in runs {
+        match merged.last_mut() {
+            Some((k, count)) if *k == key => *count += n,
+            _ => merged.push((key, n)),
+        }
+    }
+
+    let mut segments = Vec::with_capacity(merged.len());
+    let mut window_index = 0u64;
+    for (key, n) in merged {
+        let start_sample = window_index * hop;
+        window_index += n as u64;
+        segments.push(KeySegment {
+            start_sample,
+            end_sample: window_index * hop,
+            key: key.to_string(),
+        });
+    }
+    if let Some(last) = segments.last_mut() {
+        last.end_sample = duration.max(last.start_sample);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_single_key_is_one_segment() {
+        let segments = segments_from_windows(&keys(&["Am", "Am", "Am"]), 100, 450);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start_sample, 0);
+        assert_eq!(segments[0].end_sample, 450);
+        assert_eq!(segments[0].key, "Am");
+    }
+
+    #[test]
+    fn test_modulation_splits_and_blips_are_absorbed() {
+        // One-window outliers ("C", "G") are noise; the Am -> Bm change is real
+        let windows = keys(&["C", "Am", "Am", "G", "Am", "Bm", "Bm", "Bm"]);
+        let segments = segments_from_windows(&windows, 100, 820);
+        let summary: Vec<(u64, u64, &str)> = segments
+            .iter()
+            .map(|s| (s.start_sample, s.end_sample, s.key.as_str()))
+            .collect();
+        assert_eq!(summary, vec![(0, 500, "Am"), (500, 820, "Bm")]);
+    }
+
+    #[test]
+    fn test_empty_windows() {
+        assert!(segments_from_windows(&[], 100, 0).is_empty());
+    }
 }
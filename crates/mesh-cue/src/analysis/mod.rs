@@ -3,18 +3,22 @@
 //! Provides BPM detection, key detection, and beat grid generation
 //! for imported stem files.
 
+pub mod auto_cue;
 pub mod beatgrid;
 pub mod bpm;
 pub mod key;
 pub mod loudness;
+pub mod phrase;
 
+pub use auto_cue::{generate_auto_cues, AutoCueConfig, AutoCues};
 pub use beatgrid::generate_beat_grid;
 pub use bpm::{detect_bpm, detect_onset_function, fit_bpm_to_range, BpmResult, OnsetFunctionResult};
-pub use key::detect_key;
+pub use key::{detect_key, detect_key_timeline};
 pub use loudness::{
     calculate_gain_compensation, calculate_gain_compensation_clamped, db_to_linear, linear_to_db,
     measure_lufs, LufsResult,
 };
+pub use phrase::{detect_phrases, phrase_hot_cues, BarProfile};
 
 // Note: Re-analysis types (AnalysisType, ReanalysisScope, etc.) and functions
 // (analyze_partial, analyze_partial_in_subprocess) are defined below and
@@ -33,7 +37,8 @@ use std::time::Duration;
 ///
 /// The two top-level categories correspond to the two context menu items:
 /// - **Beats**: BPM detection + beat grid (destructive to manual edits)
-/// - **Metadata**: Everything else (name/artist, loudness, key, ML tags)
+/// - **Metadata**: Everything else (name/artist, loudness, key, ML tags,
+///   auto cues)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AnalysisType {
     /// BPM detection and beat grid generation (destructive to manual edits)
@@ -74,11 +79,14 @@ pub struct MetadataOptions {
     pub key: bool,
     /// ML features: genre, mood, vocal presence, etc. (EffNet pipeline)
     pub tags: bool,
+    /// Apply the auto-cue template to empty hot cue / loop slots
+    pub cues: bool,
 }
 
 impl Default for MetadataOptions {
+    /// Everything except auto cues, which write to the user's cue slots
     fn default() -> Self {
-        Self { name_artist: true, loudness: true, key: true, tags: true }
+        Self { name_artist: true, loudness: true, key: true, tags: true, cues: false }
     }
 }
 
@@ -140,6 +148,8 @@ pub struct PartialAnalysisResult {
     pub beat_grid: Option<Vec<u64>>,
     /// Musical key (if Key or All analysis was requested)
     pub key: Option<String>,
+    /// Key timeline at the system sample rate (if key analysis was requested)
+    pub key_timeline: Option<Vec<mesh_core::audio_file::KeySegment>>,
     /// Drop LUFS loudness (if Loudness or All analysis was requested)
     pub lufs: Option<f32>,
     /// Integrated LUFS loudness (if Loudness or All analysis was requested)
@@ -197,6 +207,8 @@ pub struct AnalysisResult {
     pub original_bpm: f64,
     /// Musical key (e.g., "Am", "C", "F#m")
     pub key: String,
+    /// Key timeline at the system sample rate (empty if detection failed)
+    pub key_timeline: Vec<mesh_core::audio_file::KeySegment>,
     /// Beat grid as sample positions at the system sample rate
     pub beat_grid: Vec<u64>,
     /// Analysis confidence (0.0 - 1.0)
@@ -222,6 +234,7 @@ impl Default for AnalysisResult {
             bpm: 120.0,
             original_bpm: 120.0,
             key: String::from("C"),
+            key_timeline: Vec::new(),
             beat_grid: Vec::new(),
             confidence: 0.0,
             lufs: None,
@@ -294,6 +307,10 @@ pub fn analyze_audio(samples: &[f32], bpm_samples: Option<&[f32]>, bpm_config: &
 
     // Key and LUFS always use full mix
     let key = detect_key(samples)?;
+    let key_timeline = detect_key_timeline(samples).unwrap_or_else(|e| {
+        log::warn!("Key timeline detection failed, skipping: {}", e);
+        Vec::new()
+    });
 
     // Measure LUFS loudness (drop + integrated) for automatic gain staging
     // Samples are at 44100 Hz — pass correct rate for K-weighting filter
@@ -342,6 +359,7 @@ pub fn analyze_audio(samples: &[f32], bpm_samples: Option<&[f32]>, bpm_config: &
         bpm: bpm_result_bpm,
         original_bpm: bpm_result_bpm,
         key,
+        key_timeline,
         beat_grid,
         confidence: bpm_confidence,
         lufs: lufs_result.map(|r| r.drop_lufs),
@@ -424,6 +442,10 @@ pub fn analyze_partial(
 
             if opts.key {
                 result.key = Some(detect_key(samples)?);
+                match detect_key_timeline(samples) {
+                    Ok(timeline) => result.key_timeline = Some(timeline),
+                    Err(e) => log::warn!("Key timeline detection failed: {}", e),
+                }
             }
         }
     }
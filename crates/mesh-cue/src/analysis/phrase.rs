@@ -0,0 +1,438 @@
+//! Song structure (phrase) analysis
+//!
+//! Splits a track into phrase-aligned sections — intro, build, drop,
+//! breakdown, outro — from the beat grid and the per-bar energy of each
+//! stem. Pure Rust, no Essentia: it runs in the import worker while the
+//! separated stems are still in memory.
+//!
+//! ## Algorithm
+//!
+//! 1. **Bars**: every 4th grid beat starting at beat 0 (4/4 assumed)
+//! 2. **Bar energy**: RMS of each stem over the bar, normalized per stem
+//!    to the track's loudest bar
+//! 3. **Boundaries**: candidates every 8 bars; a candidate is kept when the
+//!    mean stem energy of the 4 bars before and after it differs enough
+//!    (relative to the strongest change in the track). Sections longer
+//!    than 32 bars are split on the 32-bar phrase grid.
+//! 4. **Labels**: sections where drums + bass are close to the track's
+//!    peak are drops; everything before the first drop is intro (or build,
+//!    directly before it), everything after the last drop is outro, and
+//!    non-drop sections in between are builds (rising energy into a drop)
+//!    or breakdowns.
+
+use mesh_core::audio_file::{CuePoint, PhraseSection, SectionKind, StemBuffers};
+use mesh_core::types::{StereoBuffer, SAMPLE_RATE};
+
+/// Beats per bar (4/4)
+const BEATS_PER_BAR: usize = 4;
+
+/// Candidate section boundaries fall on multiples of this many bars
+const PHRASE_BARS: usize = 8;
+
+/// Sections longer than this are split on the phrase grid
+const MAX_SECTION_BARS: usize = 32;
+
+/// Bars averaged on each side of a candidate boundary
+const NOVELTY_WINDOW_BARS: usize = 4;
+
+/// A boundary is kept when its novelty reaches this fraction of the
+/// strongest boundary in the track
+const BOUNDARY_RELATIVE_THRESHOLD: f32 = 0.35;
+
+/// Absolute novelty floor (sum of normalized stem differences) so flat
+/// tracks are not cut into noise sections
+const BOUNDARY_MIN_NOVELTY: f32 = 0.15;
+
+/// Drum + bass level (relative to the loudest section) that makes a drop
+const DROP_LEVEL: f32 = 0.75;
+
+/// Drum + bass level above which a section before a drop counts as a build
+/// even without rising energy
+const BUILD_LEVEL: f32 = 0.45;
+
+/// Energy increase (second half vs first half) that makes a section "rising"
+const RISING_RATIO: f32 = 1.2;
+
+/// Minimum distance between an auto hot cue and an existing cue (1 second)
+pub(crate) const MIN_CUE_SPACING: u64 = SAMPLE_RATE as u64;
+
+/// Number of hot cue slots
+pub(crate) const HOT_CUE_SLOTS: u8 = 8;
+
+/// Energy of each stem over one bar
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BarEnergy {
+    pub vocals: f32,
+    pub drums: f32,
+    pub bass: f32,
+    pub other: f32,
+}
+
+impl BarEnergy {
+    fn stems(&self) -> [f32; 4] {
+        [self.vocals, self.drums, self.bass, self.other]
+    }
+
+    fn from_stems(s: [f32; 4]) -> Self {
+        Self { vocals: s[0], drums: s[1], bass: s[2], other: s[3] }
+    }
+
+    /// Rhythm-section intensity (what makes a drop a drop)
+    fn intensity(&self) -> f32 {
+        (self.drums + self.bass) / 2.0
+    }
+
+    /// Sum of absolute per-stem differences
+    fn distance(&self, other: &BarEnergy) -> f32 {
+        self.stems()
+            .iter()
+            .zip(other.stems().iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    }
+
+    pub(crate) fn mean(bars: &[BarEnergy]) -> BarEnergy {
+        if bars.is_empty() {
+            return BarEnergy::default();
+        }
+        let mut sum = [0.0f32; 4];
+        for bar in bars {
+            for (acc, v) in sum.iter_mut().zip(bar.stems()) {
+                *acc += v;
+            }
+        }
+        BarEnergy::from_stems(sum.map(|v| v / bars.len() as f32))
+    }
+}
+
+/// Per-bar stem energies of a track
+#[derive(Debug, Clone, Default)]
+pub struct BarProfile {
+    /// Start of each bar, in samples at `SAMPLE_RATE`
+    pub starts: Vec<u64>,
+    /// Raw (unnormalized) stem energy of each bar
+    pub energies: Vec<BarEnergy>,
+    /// End of the last bar
+    pub end_sample: u64,
+}
+
+impl BarProfile {
+    /// Measure every bar of the beat grid (every 4th beat from beat 0)
+    ///
+    /// `beat_grid` is in samples at `SAMPLE_RATE` (as produced by
+    /// `generate_beat_grid`); `stems` may be at any rate (`stems_sample_rate`).
+    pub fn from_stems(
+        stems: &StemBuffers,
+        beat_grid: &[u64],
+        stems_sample_rate: u32,
+        duration_samples: u64,
+    ) -> Self {
+        let starts: Vec<u64> = beat_grid.iter().step_by(BEATS_PER_BAR).copied().collect();
+        let to_source = stems_sample_rate as f64 / SAMPLE_RATE as f64;
+        let energies = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(duration_samples);
+                let range = ((start as f64 * to_source) as usize, (end as f64 * to_source) as usize);
+                BarEnergy {
+                    vocals: rms_range(&stems.vocals, range),
+                    drums: rms_range(&stems.drums, range),
+                    bass: rms_range(&stems.bass, range),
+                    other: rms_range(&stems.other, range),
+                }
+            })
+            .collect();
+        Self { starts, energies, end_sample: duration_samples }
+    }
+
+    /// Song structure of the profiled track (see [`detect_sections`])
+    pub fn sections(&self) -> Vec<PhraseSection> {
+        detect_sections(&self.starts, &self.energies, self.end_sample)
+    }
+}
+
+/// Detect the song structure of a track
+///
+/// `beat_grid` is in samples at `SAMPLE_RATE` (as produced by
+/// `generate_beat_grid`); `stems` may be at any rate (`stems_sample_rate`).
+/// Returned sections are at `SAMPLE_RATE`, cover the track from the first
+/// beat to `duration_samples`, and are empty when the track is too short
+/// (less than one phrase) or silent.
+pub fn detect_phrases(
+    stems: &StemBuffers,
+    beat_grid: &[u64],
+    stems_sample_rate: u32,
+    duration_samples: u64,
+) -> Vec<PhraseSection> {
+    if beat_grid.len() < PHRASE_BARS * BEATS_PER_BAR {
+        return Vec::new();
+    }
+    BarProfile::from_stems(stems, beat_grid, stems_sample_rate, duration_samples).sections()
+}
+
+/// RMS of a buffer over a sample range (clamped to the buffer)
+fn rms_range(buffer: &StereoBuffer, (start, end): (usize, usize)) -> f32 {
+    let samples = buffer.as_slice();
+    let end = end.min(samples.len());
+    if start >= end {
+        return 0.0;
+    }
+    let sum: f32 = samples[start..end]
+        .iter()
+        .map(|s| s.left * s.left + s.right * s.right)
+        .sum();
+    (sum / (2.0 * (end - start) as f32)).sqrt()
+}
+
+/// Segment and label a track from its per-bar stem energies
+///
+/// `bar_starts[i]` is the start of bar `i`; the last bar ends at
+/// `end_sample`. Exposed separately from [`detect_phrases`] so the
+/// structure logic is testable without audio.
+pub fn detect_sections(bar_starts: &[u64], energies: &[BarEnergy], end_sample: u64) -> Vec<PhraseSection> {
+    let bar_count = bar_starts.len().min(energies.len());
+    if bar_count < PHRASE_BARS {
+        return Vec::new();
+    }
+
+    let Some(normalized) = normalize_bars(&energies[..bar_count]) else {
+        return Vec::new();
+    };
+
+    let bounds = section_bounds(&normalized);
+    label_sections(&bounds, &normalized)
+        .into_iter()
+        .map(|((start_bar, end_bar), kind)| PhraseSection {
+            start_sample: bar_starts[start_bar],
+            end_sample: bar_starts.get(end_bar).copied().unwrap_or(end_sample).max(bar_starts[start_bar]),
+            kind,
+            bars: (end_bar - start_bar) as u32,
+        })
+        .collect()
+}
+
+/// Normalize each stem to its loudest bar so quiet stems still count
+///
+/// `None` when every stem is silent.
+pub(crate) fn normalize_bars(energies: &[BarEnergy]) -> Option<Vec<BarEnergy>> {
+    let mut peaks = [0.0f32; 4];
+    for bar in energies {
+        for (peak, v) in peaks.iter_mut().zip(bar.stems()) {
+            *peak = peak.max(v);
+        }
+    }
+    if peaks.iter().all(|&p| p < 1e-6) {
+        return None;
+    }
+    Some(
+        energies
+            .iter()
+            .map(|bar| {
+                let mut s = bar.stems();
+                for (v, peak) in s.iter_mut().zip(peaks) {
+                    *v = if peak > 1e-6 { *v / peak } else { 0.0 };
+                }
+                BarEnergy::from_stems(s)
+            })
+            .collect(),
+    )
+}
+
+/// Split bars into sections: `(start_bar, end_bar)` half-open ranges
+fn section_bounds(bars: &[BarEnergy]) -> Vec<(usize, usize)> {
+    let novelty = |b: usize| -> f32 {
+        let before = BarEnergy::mean(&bars[b.saturating_sub(NOVELTY_WINDOW_BARS)..b]);
+        let after = BarEnergy::mean(&bars[b..(b + NOVELTY_WINDOW_BARS).min(bars.len())]);
+        before.distance(&after)
+    };
+    let candidates: Vec<(usize, f32)> = (PHRASE_BARS..bars.len())
+        .step_by(PHRASE_BARS)
+        .map(|b| (b, novelty(b)))
+        .collect();
+    let strongest = candidates.iter().map(|&(_, n)| n).fold(0.0f32, f32::max);
+    let threshold = (strongest * BOUNDARY_RELATIVE_THRESHOLD).max(BOUNDARY_MIN_NOVELTY);
+
+    let mut cuts: Vec<usize> = vec![0];
+    cuts.extend(candidates.iter().filter(|&&(_, n)| n >= threshold).map(|&(b, _)| b));
+    cuts.push(bars.len());
+
+    // Split long sections on the phrase grid
+    let mut bounds = Vec::new();
+    for pair in cuts.windows(2) {
+        let (mut start, end) = (pair[0], pair[1]);
+        while end - start > MAX_SECTION_BARS {
+            bounds.push((start, start + MAX_SECTION_BARS));
+            start += MAX_SECTION_BARS;
+        }
+        bounds.push((start, end));
+    }
+    bounds
+}
+
+/// Assign a [`SectionKind`] to each section
+fn label_sections(bounds: &[(usize, usize)], bars: &[BarEnergy]) -> Vec<((usize, usize), SectionKind)> {
+    let levels: Vec<f32> = bounds
+        .iter()
+        .map(|&(s, e)| BarEnergy::mean(&bars[s..e]).intensity())
+        .collect();
+    let loudest = levels.iter().copied().fold(0.0f32, f32::max);
+    let relative: Vec<f32> = levels
+        .iter()
+        .map(|&l| if loudest > 1e-6 { l / loudest } else { 0.0 })
+        .collect();
+    let is_drop: Vec<bool> = relative.iter().map(|&r| r >= DROP_LEVEL).collect();
+
+    let first_drop = is_drop.iter().position(|&d| d);
+    let last_drop = is_drop.iter().rposition(|&d| d);
+    let (Some(first_drop), Some(last_drop)) = (first_drop, last_drop) else {
+        return bounds.iter().map(|&b| (b, SectionKind::Breakdown)).collect();
+    };
+
+    let rising = |(s, e): (usize, usize)| -> bool {
+        let mid = s + (e - s) / 2;
+        if mid == s {
+            return false;
+        }
+        let first = BarEnergy::mean(&bars[s..mid]).intensity();
+        let second = BarEnergy::mean(&bars[mid..e]).intensity();
+        second > first * RISING_RATIO
+    };
+
+    bounds
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            let before_drop = is_drop.get(i + 1).copied().unwrap_or(false);
+            let kind = if is_drop[i] {
+                SectionKind::Drop
+            } else if i < first_drop {
+                // The only section before the first drop stays the intro
+                if before_drop && i > 0 && (rising(b) || relative[i] >= BUILD_LEVEL) {
+                    SectionKind::Build
+                } else {
+                    SectionKind::Intro
+                }
+            } else if i > last_drop {
+                SectionKind::Outro
+            } else if before_drop && (rising(b) || relative[i] >= BUILD_LEVEL) {
+                SectionKind::Build
+            } else {
+                SectionKind::Breakdown
+            };
+            (b, kind)
+        })
+        .collect()
+}
+
+/// Hot cues marking the song structure, for the slots that are still free
+///
+/// One cue per change of section kind (e.g. where the first drop starts),
+/// in track order, labelled and coloured by the section. Slots already used
+/// by `existing` cues are never touched, and positions within a second of
+/// an existing cue are skipped.
+pub fn phrase_hot_cues(sections: &[PhraseSection], existing: &[CuePoint]) -> Vec<CuePoint> {
+    let mut free_slots = (0..HOT_CUE_SLOTS).filter(|slot| !existing.iter().any(|c| c.index == *slot));
+    let mut cues = Vec::new();
+    let mut previous_kind = None;
+
+    for section in sections {
+        let changed = previous_kind != Some(section.kind);
+        previous_kind = Some(section.kind);
+        if !changed || section.start_sample == 0 {
+            continue;
+        }
+        let near_existing = existing
+            .iter()
+            .any(|c| c.sample_position.abs_diff(section.start_sample) < MIN_CUE_SPACING);
+        if near_existing {
+            continue;
+        }
+        let Some(index) = free_slots.next() else {
+            break;
+        };
+        cues.push(CuePoint {
+            index,
+            sample_position: section.start_sample,
+            label: section.kind.display_name().to_string(),
+            color: Some(section.kind.color().to_string()),
+        });
+    }
+    cues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bars with the given drum/bass level (vocals and other at 0.5)
+    fn bars(pattern: &[(usize, f32)]) -> Vec<BarEnergy> {
+        pattern
+            .iter()
+            .flat_map(|&(count, level)| {
+                std::iter::repeat_n(BarEnergy { vocals: 0.5, drums: level, bass: level, other: 0.5 }, count)
+            })
+            .collect()
+    }
+
+    fn kinds(sections: &[PhraseSection]) -> Vec<SectionKind> {
+        sections.iter().map(|s| s.kind).collect()
+    }
+
+    #[test]
+    fn test_detect_sections_typical_structure() {
+        use SectionKind::*;
+        // 16 intro, 16 drop, 16 breakdown, 8 build, 16 drop, 16 outro
+        let energies = bars(&[(16, 0.3), (16, 1.0), (16, 0.05), (8, 0.5), (16, 1.0), (16, 0.3)]);
+        let starts: Vec<u64> = (0..energies.len() as u64).map(|b| 1000 + b * 100).collect();
+        let sections = detect_sections(&starts, &energies, 1000 + 88 * 100);
+
+        assert_eq!(kinds(&sections), vec![Intro, Drop, Breakdown, Build, Drop, Outro]);
+        assert_eq!(sections[0].start_sample, 1000);
+        assert_eq!(sections[1].start_sample, 1000 + 16 * 100);
+        assert_eq!(sections[3].bars, 8);
+        assert_eq!(sections.last().unwrap().end_sample, 1000 + 88 * 100);
+        assert!(sections.windows(2).all(|w| w[0].end_sample == w[1].start_sample));
+    }
+
+    #[test]
+    fn test_detect_sections_splits_long_sections() {
+        let energies = bars(&[(80, 0.8)]);
+        let starts: Vec<u64> = (0..80).collect();
+        let sections = detect_sections(&starts, &energies, 80);
+        assert_eq!(sections.iter().map(|s| s.bars).collect::<Vec<_>>(), vec![32, 32, 16]);
+        assert!(sections.iter().all(|s| s.kind == SectionKind::Drop));
+    }
+
+    #[test]
+    fn test_detect_sections_short_or_silent() {
+        assert!(detect_sections(&[0, 1, 2], &bars(&[(3, 1.0)]), 3).is_empty());
+        let silent = vec![BarEnergy::default(); 32];
+        let starts: Vec<u64> = (0..32).collect();
+        assert!(detect_sections(&starts, &silent, 32).is_empty());
+    }
+
+    #[test]
+    fn test_phrase_hot_cues_fill_free_slots() {
+        use SectionKind::*;
+        let section = |start, kind| PhraseSection { start_sample: start, end_sample: start + 10, kind, bars: 8 };
+        let sections = [
+            section(0, Intro),
+            section(100_000, Drop),
+            section(200_000, Drop),
+            section(300_000, Breakdown),
+            section(400_000, Outro),
+        ];
+        let existing = [
+            CuePoint { index: 0, sample_position: 5000, label: String::new(), color: None },
+            CuePoint { index: 1, sample_position: 300_100, label: String::new(), color: None },
+        ];
+        let cues = phrase_hot_cues(&sections, &existing);
+
+        // Intro at sample 0, repeated drop and the breakdown next to cue 1 are skipped
+        let placed: Vec<(u8, u64)> = cues.iter().map(|c| (c.index, c.sample_position)).collect();
+        assert_eq!(placed, vec![(2, 100_000), (3, 400_000)]);
+        assert_eq!(cues[0].label, "Drop");
+        assert_eq!(cues[0].color.as_deref(), Some(Drop.color()));
+    }
+}
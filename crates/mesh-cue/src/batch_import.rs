@@ -30,8 +30,8 @@
 //! // Poll progress_rx for updates
 //! ```
 
-use crate::analysis::{analyze_audio, AnalysisResult};
-use crate::config::{BpmConfig, BpmSource, LoudnessConfig};
+use crate::analysis::{analyze_audio, AnalysisResult, AutoCueConfig, BarProfile};
+use crate::config::{BpmConfig, BpmSource, LoudnessConfig, TagHints};
 use crate::export::export_stem_file;
 use crate::import::StemImporter;
 use crate::ml_analysis::{self, MlAnalysisResult};
@@ -324,6 +324,12 @@ pub struct ImportConfig {
     pub loudness_config: LoudnessConfig,
     /// Stem separation configuration (for mixed audio files)
     pub separation_config: Option<SeparationConfig>,
+    /// How tag BPM / key affect the analysis results
+    pub tag_hints: TagHints,
+    /// Auto-place hot cues at song-structure changes (empty slots only)
+    pub phrase_hot_cues: bool,
+    /// Auto-cue template (applied when `auto_cues.enabled`)
+    pub auto_cues: AutoCueConfig,
 }
 
 /// A mixed audio file to be separated into stems
@@ -539,6 +545,67 @@ pub fn compute_stem_energy_ratios(
     (v / total, d / total, b / total, o / total)
 }
 
+/// Store hot cues for the song structure on the track's empty cue slots
+fn place_phrase_hot_cues(
+    db_service: &DatabaseService,
+    track_id: i64,
+    phrases: &[mesh_core::audio_file::PhraseSection],
+) {
+    let existing: Vec<mesh_core::audio_file::CuePoint> = match db_service.get_cue_points(track_id) {
+        Ok(cues) => cues.into_iter().map(Into::into).collect(),
+        Err(e) => {
+            log::warn!("place_phrase_hot_cues: Failed to read cues for track {}: {}", track_id, e);
+            return;
+        }
+    };
+    for cue in crate::analysis::phrase_hot_cues(phrases, &existing) {
+        if let Err(e) = db_service.save_cue_point(&mesh_core::db::CuePoint::from_runtime(track_id, &cue)) {
+            log::warn!("place_phrase_hot_cues: Failed to save cue {} for track {}: {}", cue.index, track_id, e);
+        }
+    }
+}
+
+/// Store the auto-cue template's hot cues and loops on the track's empty slots
+pub fn place_auto_cues(
+    db_service: &DatabaseService,
+    track_id: i64,
+    config: &AutoCueConfig,
+    profile: &BarProfile,
+    phrases: &[mesh_core::audio_file::PhraseSection],
+) {
+    let existing_cues: Vec<mesh_core::audio_file::CuePoint> = match db_service.get_cue_points(track_id) {
+        Ok(cues) => cues.into_iter().map(Into::into).collect(),
+        Err(e) => {
+            log::warn!("place_auto_cues: Failed to read cues for track {}: {}", track_id, e);
+            return;
+        }
+    };
+    let existing_loops: Vec<mesh_core::audio_file::SavedLoop> = match db_service.get_saved_loops(track_id) {
+        Ok(loops) => loops.into_iter().map(Into::into).collect(),
+        Err(e) => {
+            log::warn!("place_auto_cues: Failed to read loops for track {}: {}", track_id, e);
+            return;
+        }
+    };
+    let placed = crate::analysis::generate_auto_cues(config, profile, phrases, &existing_cues, &existing_loops);
+    for cue in &placed.cues {
+        if let Err(e) = db_service.save_cue_point(&mesh_core::db::CuePoint::from_runtime(track_id, cue)) {
+            log::warn!("place_auto_cues: Failed to save cue {} for track {}: {}", cue.index, track_id, e);
+        }
+    }
+    for loop_ in &placed.loops {
+        if let Err(e) = db_service.save_saved_loop(&mesh_core::db::SavedLoop::from_runtime(track_id, loop_)) {
+            log::warn!("place_auto_cues: Failed to save loop {} for track {}: {}", loop_.index, track_id, e);
+        }
+    }
+    log::info!(
+        "place_auto_cues: track {} got {} hot cues and {} loops",
+        track_id,
+        placed.cues.len(),
+        placed.loops.len()
+    );
+}
+
 /// Process a single track group: load stems, analyze, export
 ///
 /// This is run by worker threads. When `ml_model_dir` is provided, also runs
@@ -700,9 +767,28 @@ fn process_single_track(
         analysis.key
     );
 
+    // Read the original file's tags once (title/artist, album etc., artwork,
+    // Serato cues, and the BPM/key other software wrote)
+    let embedded = group
+        .source_path
+        .as_deref()
+        .and_then(crate::metadata::tags::read_embedded_tags);
+
+    // Let tag BPM / key correct or override the analysis
+    let tag_bpm = embedded.as_ref().and_then(|t| t.bpm);
+    let tag_key = embedded.as_ref().and_then(|t| t.key.as_deref());
+    let bpm = crate::metadata::hints::resolve_bpm(analysis.bpm, tag_bpm, config.tag_hints);
+    let key = crate::metadata::hints::resolve_key(&analysis.key, tag_key, config.tag_hints);
+    if bpm != analysis.bpm || key != analysis.key {
+        log::info!(
+            "process_single_track: '{}' tag hints ({:?}) adjusted BPM {:.1} → {:.1}, Key {} → {}",
+            base_name, config.tag_hints, analysis.bpm, bpm, analysis.key, key
+        );
+    }
+
     // Extract artist/title from embedded tags and filename patterns
     let resolved = crate::metadata::resolve_metadata(
-        group.source_path.as_deref(),
+        embedded.as_ref(),
         &base_name,
         known_artists,
     );
@@ -725,6 +811,11 @@ fn process_single_track(
         source_sample_rate, SAMPLE_RATE, resample_ratio, source_duration_samples, duration_samples, first_beat
     );
 
+    // Song structure from the beat grid and the separated stems (grid is at SAMPLE_RATE)
+    let bar_profile = BarProfile::from_stems(&buffers, &analysis.beat_grid, source_sample_rate, duration_samples);
+    let phrases = bar_profile.sections();
+    log::info!("process_single_track: '{}' {} phrase sections", base_name, phrases.len());
+
     // Export to temp file first (export handles resampling from source_sample_rate to SAMPLE_RATE)
     let temp_dir = std::env::temp_dir();
     let sanitized_name = sanitize_filename(&base_name);
@@ -842,13 +933,31 @@ fn process_single_track(
     let mut track = Track::new(final_path.clone(), resolved.title.clone());
     track.original_name = base_name.clone();
     track.artist = resolved.artist;
-    track.bpm = Some(analysis.bpm);
+    track.bpm = Some(bpm);
     track.original_bpm = Some(analysis.original_bpm);
-    track.key = Some(analysis.key.clone());
+    track.key = Some(key);
     track.duration_seconds = (duration_samples as f64) / (SAMPLE_RATE as f64);
     track.lufs = analysis.lufs;
     track.integrated_lufs = analysis.integrated_lufs;
     track.first_beat_sample = first_beat as i64;
+    track.phrase_sections = phrases
+        .iter()
+        .enumerate()
+        .map(|(i, p)| mesh_core::db::PhraseSection::from_runtime(0, i as u32, p))
+        .collect();
+    // A tag key that overrides the analysis wins over a non-modulating
+    // timeline too; otherwise intro/outro matching would use the analysed key
+    let key_overridden = track.key.as_deref() != Some(analysis.key.as_str());
+    let key_timeline: &[_] = if key_overridden && analysis.key_timeline.len() <= 1 {
+        &[]
+    } else {
+        &analysis.key_timeline
+    };
+    track.key_segments = key_timeline
+        .iter()
+        .enumerate()
+        .map(|(i, s)| mesh_core::db::KeySegment::from_runtime(0, i as u32, s))
+        .collect();
 
     match config.db_service.save_track(&track) {
         Ok(track_id) => {
@@ -857,6 +966,26 @@ fn process_single_track(
                 base_name, track_id
             );
 
+            // Album, genre, artwork, Serato cues, ... from the original file
+            if let Some(ref tags) = embedded {
+                crate::metadata::store_embedded_metadata(
+                    &config.db_service,
+                    &config.collection_path,
+                    track_id,
+                    &sanitized_name,
+                    tags,
+                );
+            }
+
+            // Template cues first, then hot cues at the remaining structure
+            // changes — both after embedded cues so those keep their slots
+            if config.auto_cues.enabled {
+                place_auto_cues(&config.db_service, track_id, &config.auto_cues, &bar_profile, &phrases);
+            }
+            if config.phrase_hot_cues {
+                place_phrase_hot_cues(&config.db_service, track_id, &phrases);
+            }
+
             // Store ML analysis results and auto-tag
             if let Some(ref ml) = ml_result {
                 if let Err(e) = config.db_service.store_ml_analysis(track_id, &ml.data) {
@@ -1387,7 +1516,7 @@ pub fn run_batch_import_mixed(
 }
 
 /// Sanitize a filename by removing invalid characters
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| match c {
             '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
@@ -0,0 +1,75 @@
+//! Export a playlist subtree to Rekordbox XML, Traktor NML, M3U8 and PLS
+//! so other DJ software can load mesh's playlists, hot cues, loops and grids.
+//!
+//! Usage:
+//!   cargo run -p mesh-cue --release --bin export_library -- \
+//!     [--collection ~/Music/mesh-collection] \
+//!     [--playlist "Sets/Friday"] \
+//!     [--format rekordbox,traktor,m3u8,pls] \
+//!     [--mixdown] \
+//!     [--out ~/Music/mesh-export]
+//!
+//! `--playlist` is a path below Playlists (default: all playlists).
+//! `--mixdown` renders a stereo WAV per track into `<out>/audio` and points
+//! the exported entries at it — other software can't play 8-channel stems.
+use std::path::PathBuf;
+use mesh_core::db::DatabaseService;
+use mesh_core::playlist::{DatabaseStorage, NodeId};
+use mesh_cue::library_export::{export_library, ExportFormat, ExportOptions};
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    let mut collection = home.join("Music").join("mesh-collection");
+    let mut out = home.join("Music").join("mesh-export");
+    let mut root = NodeId::playlists();
+    let mut options = ExportOptions::default();
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--collection" | "-c" => { collection = PathBuf::from(&args[i+1]); i += 2; }
+            "--out" | "-o" => { out = PathBuf::from(&args[i+1]); i += 2; }
+            "--playlist" | "-p" => {
+                root = args[i+1].trim_matches('/').split('/').fold(NodeId::playlists(), |id, name| id.child(name));
+                i += 2;
+            }
+            "--format" | "-f" => {
+                options.formats = Vec::new();
+                for name in args[i+1].split(',') {
+                    match ExportFormat::parse(name.trim()) {
+                        Some(format) => options.formats.push(format),
+                        None => { eprintln!("unknown format: {name}"); std::process::exit(2); }
+                    }
+                }
+                i += 2;
+            }
+            "--mixdown" | "-m" => { options.mixdown = true; i += 1; }
+            "--help" | "-h" => {
+                eprintln!("usage: export_library [--collection <path>] [--playlist <a/b>] [--format rekordbox,traktor,m3u8,pls] [--mixdown] [--out <dir>]");
+                std::process::exit(0);
+            }
+            other => { eprintln!("unknown arg: {other}"); std::process::exit(2); }
+        }
+    }
+
+    let db = DatabaseService::new(&collection)
+        .map_err(|e| anyhow::anyhow!("DB open: {e}"))?;
+    let storage = DatabaseStorage::new(db.clone())
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let summary = export_library(&db, &storage, &root, &out, &options)?;
+    eprintln!(
+        "[export] {} tracks, {} playlists, {} mixdowns → {}",
+        summary.tracks,
+        summary.playlists,
+        summary.mixdowns,
+        out.display()
+    );
+    for file in &summary.files {
+        eprintln!("  {}", file.display());
+    }
+    if !summary.failed.is_empty() {
+        eprintln!("[export] mixdown failed for {} tracks: {}", summary.failed.len(), summary.failed.join(", "));
+    }
+    Ok(())
+}
@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use mesh_widgets::{AppFont, FontSize};
 
-// Re-export separation config for convenience
+// Re-export separation and auto-cue config for convenience
+pub use crate::analysis::auto_cue::{AutoCueConfig, CueAnchor, HotCueTemplate, LoopRegion, LoopTemplate};
 pub use crate::separation::{BackendType, ModelType, SeparationConfig};
 
 // Re-export shared config utilities from mesh-core
@@ -163,6 +164,15 @@ pub struct AnalysisConfig {
     pub loudness: LoudnessConfig,
     /// Stem separation settings (for mixed audio import)
     pub separation: SeparationConfig,
+    /// How BPM / key found in embedded tags affect import analysis
+    pub tag_hints: TagHints,
+    /// Place hot cues at song-structure changes (intro → drop, ...) on
+    /// import, using only the empty cue slots. Off by default: it competes
+    /// with the `auto_cues` template for the same slots
+    pub phrase_hot_cues: bool,
+    /// Template-based hot cues and saved loops (first downbeat, drop,
+    /// breakdown, outro; clean intro/outro loops)
+    pub auto_cues: AutoCueConfig,
 }
 
 impl Default for AnalysisConfig {
@@ -171,6 +181,9 @@ impl Default for AnalysisConfig {
             bpm: BpmConfig::default(),
             loudness: LoudnessConfig::default(),
             separation: SeparationConfig::default(),
+            tag_hints: TagHints::default(),
+            phrase_hot_cues: false,
+            auto_cues: AutoCueConfig::default(),
         }
     }
 }
@@ -213,6 +226,32 @@ impl std::fmt::Display for BpmSource {
     }
 }
 
+/// Use of BPM / key values written into tags by other DJ software
+///
+/// Only applies to mixed-audio imports (pre-separated stems carry no tags).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TagHints {
+    /// Analysis results only
+    Ignore,
+    /// Tag BPM resolves half/double-tempo detections, tag key resolves
+    /// relative major/minor confusion
+    #[default]
+    Hint,
+    /// Tag BPM and key replace the analysis results when present
+    Override,
+}
+
+impl std::fmt::Display for TagHints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagHints::Ignore => write!(f, "Ignore"),
+            TagHints::Hint => write!(f, "Hint"),
+            TagHints::Override => write!(f, "Override"),
+        }
+    }
+}
+
 /// BPM detection configuration
 ///
 /// These values map directly to Essentia's RhythmExtractor2013 parameters:
@@ -302,6 +341,7 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.analysis.bpm.min_tempo, 40);
         assert_eq!(config.analysis.bpm.max_tempo, 208);
+        assert!(!config.analysis.phrase_hot_cues);
     }
 
     #[test]
@@ -338,6 +378,9 @@ mod tests {
                 },
                 loudness: LoudnessConfig::default(),
                 separation: SeparationConfig::default(),
+                tag_hints: TagHints::Override,
+                phrase_hot_cues: true,
+                auto_cues: AutoCueConfig { enabled: true, ..Default::default() },
             },
             display: DisplayConfig::default(),
             audio: AudioConfig::default(),
@@ -350,5 +393,8 @@ mod tests {
 
         assert_eq!(parsed.analysis.bpm.min_tempo, 160);
         assert_eq!(parsed.analysis.bpm.max_tempo, 190);
+        assert_eq!(parsed.analysis.tag_hints, TagHints::Override);
+        assert!(parsed.analysis.phrase_hot_cues);
+        assert_eq!(parsed.analysis.auto_cues, config.analysis.auto_cues);
     }
 }
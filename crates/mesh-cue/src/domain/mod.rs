@@ -20,13 +20,14 @@ use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 
 use anyhow::{anyhow, Result};
-use mesh_core::db::{DatabaseService, Track, Playlist, CuePoint as DbCuePoint, SavedLoop as DbSavedLoop, StemLink as DbStemLink};
-use mesh_core::audio_file::{CuePoint, SavedLoop, StemLinkReference};
-use mesh_core::playlist::{DatabaseStorage, NodeId, NodeKind, PlaylistNode, PlaylistStorage};
+use mesh_core::db::{DatabaseService, Track, TrackRating, Playlist, CuePoint as DbCuePoint, SavedLoop as DbSavedLoop, StemLink as DbStemLink, TempoAnchor as DbTempoAnchor};
+use mesh_core::audio_file::{CuePoint, SavedLoop, StemLinkReference, TempoAnchor};
+use mesh_core::playlist::{DatabaseStorage, NodeId, NodeKind, PlaylistNode, PlaylistStorage, SmartPlaylistRules};
+use mesh_core::search::{SearchQuery, SearchVocabulary};
 use mesh_core::pd::{DiscoveredEffect, PdManager};
 use mesh_core::clap::{ClapManager, ClapGuiHandle, DiscoveredClapPlugin};
 use mesh_core::preset_loader::{PresetLoader, PresetLoadResultReceiver, MultibandBuildSpec};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use mesh_widgets::TrackRow;
 use mesh_core::usb::{UsbManager, UsbCommand, UsbMessage, SyncPlan, ExportableConfig};
 use mesh_widgets::TreeNode;
@@ -374,6 +375,18 @@ impl MeshCueDomain {
             .map_err(|e| anyhow!("Failed to search tracks: {}", e))
     }
 
+    /// Paths of every track matching a structured search
+    pub fn search_track_paths(&self, query: &SearchQuery) -> Result<HashSet<String>> {
+        self.db_service.search_track_paths(query)
+            .map_err(|e| anyhow!("Failed to search tracks: {}", e))
+    }
+
+    /// Tags and genres for search autocomplete
+    pub fn search_vocabulary(&self) -> Result<SearchVocabulary> {
+        self.db_service.get_search_vocabulary()
+            .map_err(|e| anyhow!("Failed to load search vocabulary: {}", e))
+    }
+
     /// Get total track count
     pub fn track_count(&self) -> Result<usize> {
         self.db_service.track_count()
@@ -470,6 +483,10 @@ impl MeshCueDomain {
         // Update from state
         track.bpm = Some(state.bpm);
         track.original_bpm = Some(state.original_bpm);
+        // A hand-edited key replaces a non-modulating timeline
+        if track.key.as_deref() != Some(state.key.as_str()) && track.key_segments.len() <= 1 {
+            track.key_segments.clear();
+        }
         track.key = Some(state.key.clone());
         track.drop_marker = state.drop_marker.map(|s| s as i64);
         track.first_beat_sample = state.first_beat_sample as i64;
@@ -520,6 +537,7 @@ impl MeshCueDomain {
         key: &str,
         drop_marker: Option<u64>,
         first_beat_sample: u64,
+        tempo_anchors: &[TempoAnchor],
         cue_points: &[CuePoint],
         saved_loops: &[SavedLoop],
         stem_links: &[StemLinkReference],
@@ -532,6 +550,10 @@ impl MeshCueDomain {
 
         // Update track fields
         track.bpm = Some(bpm);
+        // A hand-edited key replaces a non-modulating timeline
+        if track.key.as_deref() != Some(key) && track.key_segments.len() <= 1 {
+            track.key_segments.clear();
+        }
         track.key = Some(key.to_string());
         track.drop_marker = drop_marker.map(|d| d as i64);
         track.first_beat_sample = first_beat_sample as i64;
@@ -546,6 +568,11 @@ impl MeshCueDomain {
             .map(|l| DbSavedLoop::from_runtime(track_id, l))
             .collect();
 
+        track.tempo_anchors = tempo_anchors.iter()
+            .enumerate()
+            .map(|(i, a)| DbTempoAnchor::from_runtime(track_id, i as u32, a))
+            .collect();
+
         // Use central conversion method for stem links
         track.stem_links = self.convert_stem_links_to_db(track_id, stem_links);
 
@@ -803,13 +830,49 @@ impl MeshCueDomain {
         self.playlist_storage.get_children(id)
     }
 
-    /// Get tracks for display in a folder/playlist, with ML intensity populated.
+    /// Get tracks for display in a folder/playlist, with ML intensity and
+    /// play history populated.
     pub fn get_tracks_for_display(&self, folder_id: &NodeId) -> Vec<TrackRow<NodeId>> {
         let mut rows = crate::ui::utils::get_tracks_for_folder(&*self.playlist_storage, folder_id);
         self.enrich_with_intensity(&mut rows);
+        self.enrich_with_play_stats(&mut rows);
         rows
     }
 
+    /// Fill play counts and last-played times from the local history plus
+    /// every mounted USB stick (where mesh-player logs its sets).
+    fn enrich_with_play_stats(&self, rows: &mut [TrackRow<NodeId>]) {
+        let usb_dbs = mesh_core::usb::cached_usb_databases();
+        let stats = DatabaseService::aggregate_play_stats(
+            std::iter::once(self.db_service.as_ref()).chain(usb_dbs.iter().map(|db| db.as_ref())),
+        );
+        for row in rows.iter_mut() {
+            let Some(path) = &row.track_path else { continue };
+            if let Some(s) = stats.get(&DatabaseService::play_stats_key(path)) {
+                row.play_count = s.play_count;
+                row.last_played = Some(s.last_played);
+            }
+        }
+    }
+
+    /// Change the star rating and/or colour label of a track
+    ///
+    /// Returns the updated rating, or None if the track is not in the library.
+    pub fn update_track_rating(
+        &self,
+        path: &Path,
+        update: impl FnOnce(&mut TrackRating),
+    ) -> Result<Option<TrackRating>> {
+        let Some(track_id) = self.db_service.get_track_by_path(&path.to_string_lossy())?.and_then(|t| t.id) else {
+            return Ok(None);
+        };
+        let mut rating = self.db_service.get_track_rating(track_id)?
+            .unwrap_or(TrackRating { track_id, ..Default::default() });
+        update(&mut rating);
+        self.db_service.set_track_rating(&rating)?;
+        Ok(Some(rating))
+    }
+
     /// Compute cosine distances between consecutive tracks' PCA embeddings.
     /// Returns a Vec of length `paths.len() - 1`.
     pub fn compute_consecutive_similarities(&self, paths: &[Option<String>]) -> Vec<f32> {
@@ -892,6 +955,51 @@ impl MeshCueDomain {
         Ok(())
     }
 
+    /// Check if a node is a smart (rule-based) playlist
+    pub fn is_smart_playlist(&self, id: &NodeId) -> bool {
+        self.playlist_storage.get_node(id).is_some_and(|n| n.kind == NodeKind::SmartPlaylist)
+    }
+
+    /// Create a smart playlist under a parent NodeId
+    ///
+    /// Parent should be `NodeId::playlists()` or a manual playlist's NodeId.
+    pub fn create_smart_playlist(&mut self, parent_id: &NodeId, name: &str, rules: &SmartPlaylistRules) -> Result<NodeId> {
+        let parent_db_id = if *parent_id == NodeId::playlists() {
+            None
+        } else {
+            if self.is_smart_playlist(parent_id) {
+                return Err(anyhow!("Smart playlists cannot contain playlists"));
+            }
+            let id = self.db_service.resolve_playlist_path(parent_id.as_str())
+                .map_err(|e| anyhow!("Failed to resolve playlist: {}", e))?
+                .ok_or_else(|| anyhow!("Playlist not found: {}", parent_id))?;
+            Some(id)
+        };
+        self.db_service.create_smart_playlist(name, parent_db_id, rules)
+            .map_err(|e| anyhow!("Failed to create smart playlist: {}", e))?;
+        self.refresh_tree();
+        Ok(parent_id.child(name))
+    }
+
+    /// Get the rules of a smart playlist (None for manual playlists)
+    pub fn smart_playlist_rules(&self, id: &NodeId) -> Result<Option<SmartPlaylistRules>> {
+        match self.db_service.resolve_playlist_path(id.as_str()) {
+            Ok(Some(db_id)) => self.db_service.get_smart_playlist_rules(db_id)
+                .map_err(|e| anyhow!("Failed to load smart playlist rules: {}", e)),
+            Ok(None) => Ok(None),
+            Err(e) => Err(anyhow!("Failed to resolve playlist: {}", e)),
+        }
+    }
+
+    /// Replace the rules of a smart playlist
+    pub fn set_smart_playlist_rules(&mut self, id: &NodeId, rules: &SmartPlaylistRules) -> Result<()> {
+        let db_id = self.db_service.resolve_playlist_path(id.as_str())
+            .map_err(|e| anyhow!("Failed to resolve playlist: {}", e))?
+            .ok_or_else(|| anyhow!("Playlist not found: {}", id))?;
+        self.db_service.set_smart_playlist_rules(db_id, rules)
+            .map_err(|e| anyhow!("Failed to save smart playlist rules: {}", e))
+    }
+
     /// Remove a track from its playlist (track NodeId)
     ///
     /// The track's NodeId encodes which playlist it belongs to.
@@ -962,6 +1070,12 @@ impl MeshCueDomain {
                 Ok(Some(track)) => {
                     all_db_ids.push(db_id);
                     all_paths.push(track.path.clone());
+                    // Extracted cover art goes with the track
+                    if let Ok(Some(meta)) = self.db_service.get_tag_metadata(db_id) {
+                        if let Some(artwork) = meta.artwork_path {
+                            all_paths.push(self.collection_root.join(artwork));
+                        }
+                    }
                 }
                 Ok(None) => log::warn!("Track not found in DB for ID {}", db_id),
                 Err(e) => log::error!("Failed to fetch track {}: {}", db_id, e),
@@ -1054,7 +1168,7 @@ impl MeshCueDomain {
     /// Helper to collect playlists recursively
     fn collect_playlists_recursive(&self, parent: &NodeId, results: &mut Vec<(NodeId, String)>) {
         for node in self.playlist_storage.get_children(parent) {
-            if node.kind == NodeKind::Playlist {
+            if matches!(node.kind, NodeKind::Playlist | NodeKind::SmartPlaylist) {
                 results.push((node.id.clone(), node.name.clone()));
                 // Recurse into nested playlists
                 self.collect_playlists_recursive(&node.id, results);
@@ -1091,6 +1205,9 @@ impl MeshCueDomain {
             bpm_config: self.config.analysis.bpm.clone(),
             loudness_config: self.config.analysis.loudness.clone(),
             separation_config: Some(self.config.analysis.separation.clone()),
+            tag_hints: self.config.analysis.tag_hints,
+            phrase_hot_cues: self.config.analysis.phrase_hot_cues,
+            auto_cues: self.config.analysis.auto_cues.clone(),
         };
 
         let cancel = cancel_flag.clone();
@@ -1132,6 +1249,9 @@ impl MeshCueDomain {
             bpm_config: self.config.analysis.bpm.clone(),
             loudness_config: self.config.analysis.loudness.clone(),
             separation_config: Some(self.config.analysis.separation.clone()),
+            tag_hints: self.config.analysis.tag_hints,
+            phrase_hot_cues: self.config.analysis.phrase_hot_cues,
+            auto_cues: self.config.analysis.auto_cues.clone(),
         };
 
         let cancel = cancel_flag.clone();
@@ -1188,7 +1308,7 @@ impl MeshCueDomain {
     ///
     /// Routes to the appropriate pipeline based on analysis type:
     /// - Beats → Essentia subprocess for BPM + beat grid (+ optional Beat This!)
-    /// - Metadata → unified pipeline: name/artist, LUFS, key, ML tags
+    /// - Metadata → unified pipeline: name/artist, LUFS, key, ML tags, auto cues
     pub fn start_reanalysis(
         &mut self,
         tracks: Vec<PathBuf>,
@@ -1216,10 +1336,12 @@ impl MeshCueDomain {
             }
             AnalysisType::Metadata => {
                 let options = metadata_options.unwrap_or_default();
+                let auto_cues = self.config.analysis.auto_cues.clone();
                 std::thread::spawn(move || {
                     crate::reanalysis::run_batch_metadata_reanalysis(
                         tracks,
                         options,
+                        auto_cues,
                         progress_tx,
                         cancel,
                         db,
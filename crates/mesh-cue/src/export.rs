@@ -10,6 +10,9 @@
 //!
 //! If the source audio is at a different sample rate (e.g., 44100 Hz from demucs),
 //! it is resampled to SAMPLE_RATE (48000 Hz) before encoding.
+//!
+//! For other DJ software, which can't read the 8-channel format, the stems can
+//! also be summed into a plain stereo WAV ([`export_stereo_mixdown`]).
 
 use anyhow::{Context, Result};
 use flacenc::component::BitRepr;
@@ -116,6 +119,32 @@ pub fn export_stem_file(
     Ok(())
 }
 
+/// Sum the four stems into a 16-bit stereo WAV at SAMPLE_RATE
+///
+/// Used when exporting playlists to other DJ software. The stems are summed
+/// at unity gain (they add up to the original mix) and clipped to full scale.
+pub fn export_stereo_mixdown(path: &Path, buffers: &StemBuffers) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create {:?}", path))?;
+
+    for i in 0..buffers.len() {
+        let left = buffers.vocals[i].left + buffers.drums[i].left + buffers.bass[i].left + buffers.other[i].left;
+        let right = buffers.vocals[i].right + buffers.drums[i].right + buffers.bass[i].right + buffers.other[i].right;
+        writer.write_sample(f32_to_i32_16bit(left) as i16)?;
+        writer.write_sample(f32_to_i32_16bit(right) as i16)?;
+    }
+    writer.finalize().with_context(|| format!("Failed to finalize {:?}", path))?;
+
+    log::info!("export_stereo_mixdown: {} samples written to {:?}", buffers.len(), path);
+    Ok(())
+}
+
 /// Convert f32 sample [-1.0, 1.0] to i32 in 16-bit range
 #[inline]
 fn f32_to_i32_16bit(sample: f32) -> i32 {
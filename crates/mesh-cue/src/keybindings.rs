@@ -38,6 +38,10 @@ pub struct EditingKeybindings {
     pub grid_nudge_backward: Vec<String>,
     /// Align beat grid - set current playhead as downbeat
     pub align_beat_grid: Vec<String>,
+    /// Add a tempo-change marker at the beat nearest the playhead
+    pub add_tempo_marker: Vec<String>,
+    /// Remove the tempo-change marker of the segment under the playhead
+    pub remove_tempo_marker: Vec<String>,
     /// Increase beat jump size
     pub increase_jump_size: Vec<String>,
     /// Decrease beat jump size
@@ -46,6 +50,12 @@ pub struct EditingKeybindings {
     pub cue_button: Vec<String>,
     /// Set/update drop marker at current playhead (Shift to clear)
     pub set_drop_marker: Vec<String>,
+    /// Add a star to the loaded track's rating
+    pub rating_up: Vec<String>,
+    /// Remove a star from the loaded track's rating
+    pub rating_down: Vec<String>,
+    /// Cycle the loaded track's colour label
+    pub cycle_color_label: Vec<String>,
     /// Hot cue buttons (1-8)
     pub hot_cue_1: Vec<String>,
     pub hot_cue_2: Vec<String>,
@@ -75,10 +85,15 @@ impl Default for EditingKeybindings {
             grid_nudge_forward: vec!["Shift+Right".into()],
             grid_nudge_backward: vec!["Shift+Left".into()],
             align_beat_grid: vec!["m".into()],
+            add_tempo_marker: vec!["t".into()],
+            remove_tempo_marker: vec!["Shift+T".into()],
             increase_jump_size: vec!["Up".into()],
             decrease_jump_size: vec!["Down".into()],
             cue_button: vec!["c".into()],
             set_drop_marker: vec!["d".into()],
+            rating_up: vec!["]".into()],
+            rating_down: vec!["[".into()],
+            cycle_color_label: vec!["l".into()],
             hot_cue_1: vec!["1".into()],
             hot_cue_2: vec!["2".into()],
             hot_cue_3: vec!["3".into()],
@@ -102,6 +102,8 @@ pub mod export;
 pub mod features;
 pub mod import;
 pub mod keybindings;
+pub mod library_export;
+pub mod library_import;
 pub mod loader;
 pub mod ml_analysis;
 pub mod pca;
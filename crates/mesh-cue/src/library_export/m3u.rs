@@ -0,0 +1,80 @@
+//! M3U8 and PLS playlist writers
+//!
+//! Plain track lists with absolute paths, readable by practically any
+//! player. They carry no cues or grids — only duration and a display title.
+
+use std::fmt::Write;
+
+use crate::library_import::ExternalTrack;
+
+/// Write an extended M3U playlist (UTF-8)
+pub fn write_m3u8(tracks: &[&ExternalTrack]) -> String {
+    let mut m3u = String::from("#EXTM3U\n");
+    for track in tracks {
+        let _ = writeln!(m3u, "#EXTINF:{},{}", duration(track), display_title(track));
+        let _ = writeln!(m3u, "{}", track.location.display());
+    }
+    m3u
+}
+
+/// Write a PLS playlist (version 2)
+pub fn write_pls(tracks: &[&ExternalTrack]) -> String {
+    let mut pls = String::from("[playlist]\n");
+    for (i, track) in tracks.iter().enumerate() {
+        let n = i + 1;
+        let _ = writeln!(pls, "File{}={}", n, track.location.display());
+        let _ = writeln!(pls, "Title{}={}", n, display_title(track));
+        let _ = writeln!(pls, "Length{}={}", n, duration(track));
+    }
+    let _ = writeln!(pls, "NumberOfEntries={}", tracks.len());
+    pls.push_str("Version=2\n");
+    pls
+}
+
+/// "Artist - Title", falling back to the file name
+fn display_title(track: &ExternalTrack) -> String {
+    let title = track.title.clone().unwrap_or_else(|| {
+        track.location
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    match &track.artist {
+        Some(artist) => format!("{} - {}", artist, title),
+        None => title,
+    }
+}
+
+/// Duration in whole seconds (-1 = unknown, as both formats specify)
+fn duration(track: &ExternalTrack) -> i64 {
+    track.duration_secs.map_or(-1, |d| d.round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn writes_entries_in_order() {
+        let a = ExternalTrack {
+            location: PathBuf::from("/music/a.flac"),
+            title: Some("Title".to_string()),
+            artist: Some("Artist".to_string()),
+            duration_secs: Some(301.6),
+            ..Default::default()
+        };
+        let b = ExternalTrack {
+            location: PathBuf::from("/music/b.flac"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            write_m3u8(&[&a, &b]),
+            "#EXTM3U\n#EXTINF:302,Artist - Title\n/music/a.flac\n#EXTINF:-1,b\n/music/b.flac\n"
+        );
+        let pls = write_pls(&[&a, &b]);
+        assert!(pls.starts_with("[playlist]\nFile1=/music/a.flac\nTitle1=Artist - Title\nLength1=302\n"));
+        assert!(pls.ends_with("NumberOfEntries=2\nVersion=2\n"));
+    }
+}
@@ -0,0 +1,606 @@
+//! Rekordbox / Traktor / M3U8 / PLS library export
+//!
+//! The reverse of [`crate::library_import`]: writes a playlist subtree of the
+//! mesh collection in formats other DJ software reads:
+//!
+//! - **Rekordbox XML** — tracks, hot cues, memory loops, beat grids, ratings,
+//!   colours and the playlist tree (File → Import in Rekordbox)
+//! - **Traktor NML** — the same as a Traktor collection file
+//! - **M3U8 / PLS** — plain track lists, one file per playlist
+//!
+//! The subtree is walked through [`PlaylistStorage`] and turned into the same
+//! [`ExternalLibrary`] model the importers produce, so both directions share
+//! one description of a library. Cues, loops and grids come from the
+//! database at full detail.
+//!
+//! mesh tracks are 8-channel stem files that other software can't play. With
+//! [`ExportOptions::mixdown`] each track is summed to a stereo WAV next to the
+//! exported files, and the entries point at the WAV instead. Positions carry
+//! over unchanged — the mixdown has the same timeline as the stems.
+
+pub mod m3u;
+pub mod rekordbox;
+pub mod traktor;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use mesh_core::audio_file::LoadedTrack;
+use mesh_core::db::{DatabaseService, Track, TrackRating};
+use mesh_core::playlist::{NodeId, NodeKind, PlaylistNode, PlaylistStorage, TrackInfo};
+use mesh_core::types::SAMPLE_RATE;
+
+use crate::batch_import::sanitize_filename;
+use crate::export::export_stereo_mixdown;
+use crate::library_import::{ExternalCue, ExternalLibrary, ExternalLoop, ExternalNode, ExternalTrack, GridMarker};
+use crate::metadata::hints::parse_tag_key;
+
+/// Output format of a library export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Rekordbox XML (`<name>.xml`)
+    RekordboxXml,
+    /// Traktor collection (`<name>.nml`)
+    TraktorNml,
+    /// Extended M3U, UTF-8 (one `.m3u8` per playlist)
+    M3u8,
+    /// PLS (one `.pls` per playlist)
+    Pls,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::RekordboxXml,
+        ExportFormat::TraktorNml,
+        ExportFormat::M3u8,
+        ExportFormat::Pls,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ExportFormat::RekordboxXml => "Rekordbox XML",
+            ExportFormat::TraktorNml => "Traktor NML",
+            ExportFormat::M3u8 => "M3U8",
+            ExportFormat::Pls => "PLS",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::RekordboxXml => "xml",
+            ExportFormat::TraktorNml => "nml",
+            ExportFormat::M3u8 => "m3u8",
+            ExportFormat::Pls => "pls",
+        }
+    }
+
+    /// Parse a format name as given on the command line
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "rekordbox" | "xml" => Some(ExportFormat::RekordboxXml),
+            "traktor" | "nml" => Some(ExportFormat::TraktorNml),
+            "m3u8" | "m3u" => Some(ExportFormat::M3u8),
+            "pls" => Some(ExportFormat::Pls),
+            _ => None,
+        }
+    }
+}
+
+/// What to write in a library export
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub formats: Vec<ExportFormat>,
+    /// Render a stereo WAV of every track and reference it instead of the
+    /// stem file
+    pub mixdown: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            formats: ExportFormat::ALL.to_vec(),
+            mixdown: false,
+        }
+    }
+}
+
+/// Outcome of a library export
+#[derive(Debug, Clone, Default)]
+pub struct LibraryExportSummary {
+    pub tracks: usize,
+    pub playlists: usize,
+    /// Stereo mixdowns rendered (or reused from an earlier export)
+    pub mixdowns: usize,
+    /// Files written
+    pub files: Vec<PathBuf>,
+    /// File names of tracks whose mixdown failed (exported with the stem
+    /// file location)
+    pub failed: Vec<String>,
+}
+
+/// Export a playlist subtree to `out_dir`
+///
+/// `root` may be a playlist (exported with its sub-playlists), the
+/// Playlists root (every top-level playlist) or a collection folder
+/// (exported as one flat playlist).
+pub fn export_library(
+    db: &DatabaseService,
+    storage: &dyn PlaylistStorage,
+    root: &NodeId,
+    out_dir: &Path,
+    options: &ExportOptions,
+) -> Result<LibraryExportSummary> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create export folder {:?}", out_dir))?;
+
+    let mut library = collect_library(db, storage, root)?;
+    let mut summary = LibraryExportSummary {
+        tracks: library.tracks.len(),
+        playlists: count_playlists(&library.playlists),
+        ..Default::default()
+    };
+
+    if options.mixdown {
+        render_mixdowns(&mut library, &out_dir.join("audio"), &mut summary)?;
+    }
+
+    let base_name = storage.get_node(root)
+        .map(|node| sanitize_filename(&node.name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "mesh".to_string());
+
+    for format in &options.formats {
+        match format {
+            ExportFormat::RekordboxXml | ExportFormat::TraktorNml => {
+                let path = out_dir.join(format!("{}.{}", base_name, format.extension()));
+                let content = match format {
+                    ExportFormat::RekordboxXml => rekordbox::write(&library),
+                    _ => traktor::write(&library),
+                };
+                write_file(&path, &content)?;
+                summary.files.push(path);
+            }
+            ExportFormat::M3u8 | ExportFormat::Pls => {
+                let by_key: HashMap<&str, &ExternalTrack> = library.tracks.iter()
+                    .map(|t| (t.key.as_str(), t))
+                    .collect();
+                let mut playlists = Vec::new();
+                flatten_playlists(&library.playlists, "", &mut playlists);
+                for (name, keys) in playlists {
+                    let tracks: Vec<&ExternalTrack> = keys.iter()
+                        .filter_map(|key| by_key.get(key.as_str()).copied())
+                        .collect();
+                    let path = out_dir.join(format!("{}.{}", sanitize_filename(&name), format.extension()));
+                    let content = match format {
+                        ExportFormat::M3u8 => m3u::write_m3u8(&tracks),
+                        _ => m3u::write_pls(&tracks),
+                    };
+                    write_file(&path, &content)?;
+                    summary.files.push(path);
+                }
+            }
+        }
+    }
+
+    log::info!(
+        "library_export: {} tracks, {} playlists, {} mixdowns, {} files written to {:?}",
+        summary.tracks,
+        summary.playlists,
+        summary.mixdowns,
+        summary.files.len(),
+        out_dir
+    );
+    Ok(summary)
+}
+
+/// Build the library model for a playlist subtree
+pub fn collect_library(
+    db: &DatabaseService,
+    storage: &dyn PlaylistStorage,
+    root: &NodeId,
+) -> Result<ExternalLibrary> {
+    let node = storage.get_node(root)
+        .with_context(|| format!("Playlist {} not found", root))?;
+
+    let mut collector = Collector {
+        db,
+        storage,
+        tracks: Vec::new(),
+        keys: HashMap::new(),
+    };
+    let playlists = if node.kind == NodeKind::PlaylistsRoot {
+        storage.get_children(root)
+            .iter()
+            .map(|child| collector.node(child))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        vec![collector.node(&node)?]
+    };
+
+    Ok(ExternalLibrary {
+        tracks: collector.tracks,
+        playlists,
+    })
+}
+
+/// Walks the playlist tree, collecting each track once
+struct Collector<'a> {
+    db: &'a DatabaseService,
+    storage: &'a dyn PlaylistStorage,
+    tracks: Vec<ExternalTrack>,
+    /// Track path → library key
+    keys: HashMap<PathBuf, String>,
+}
+
+impl Collector<'_> {
+    fn node(&mut self, node: &PlaylistNode) -> Result<ExternalNode> {
+        let mut tracks = Vec::new();
+        for info in self.storage.get_tracks(&node.id) {
+            tracks.push(self.track_key(&info)?);
+        }
+
+        // Collection folders are exported flat; playlists keep their nesting
+        let sub_playlists: Vec<PlaylistNode> = if node.kind == NodeKind::Playlist {
+            self.storage.get_children(&node.id)
+                .into_iter()
+                .filter(|child| child.is_container())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if sub_playlists.is_empty() {
+            return Ok(ExternalNode::Playlist { name: node.name.clone(), tracks });
+        }
+
+        // Other software can't hold tracks and playlists in one node: the
+        // playlist's own tracks become a playlist of the same name inside
+        // the folder
+        let mut children = Vec::new();
+        if !tracks.is_empty() {
+            children.push(ExternalNode::Playlist { name: node.name.clone(), tracks });
+        }
+        for sub in &sub_playlists {
+            children.push(self.node(sub)?);
+        }
+        Ok(ExternalNode::Folder { name: node.name.clone(), children })
+    }
+
+    fn track_key(&mut self, info: &TrackInfo) -> Result<String> {
+        if let Some(key) = self.keys.get(&info.path) {
+            return Ok(key.clone());
+        }
+
+        // Sequential numeric keys double as Rekordbox TrackIDs
+        let key = (self.tracks.len() + 1).to_string();
+        let track = self.db.get_track_by_path(&info.path.to_string_lossy())?;
+        let rating = match track.as_ref().and_then(|t| t.id) {
+            Some(id) => self.db.get_track_rating(id)?,
+            None => None,
+        };
+        self.tracks.push(external_track(key.clone(), info, track.as_ref(), rating.as_ref()));
+        self.keys.insert(info.path.clone(), key.clone());
+        Ok(key)
+    }
+}
+
+/// Convert a mesh track into a library entry
+fn external_track(
+    key: String,
+    info: &TrackInfo,
+    track: Option<&Track>,
+    rating: Option<&TrackRating>,
+) -> ExternalTrack {
+    let mut ext = ExternalTrack {
+        key,
+        location: info.path.clone(),
+        title: Some(info.title.clone()).filter(|t| !t.is_empty()),
+        artist: info.artist.clone(),
+        album: info.album.clone(),
+        genre: info.genre.clone(),
+        bpm: info.bpm,
+        musical_key: info.key.as_deref().and_then(parse_tag_key).map(|k| k.to_string()),
+        duration_secs: info.duration,
+        rating: rating.map_or(0, |r| r.rating.min(5)),
+        color: rating.and_then(|r| r.color.clone()),
+        ..Default::default()
+    };
+
+    if let Some(track) = track {
+        ext.cues = track.cue_points.iter()
+            .map(|c| ExternalCue {
+                slot: Some(c.index),
+                position_secs: samples_to_secs(c.sample_position),
+                label: c.label.clone(),
+                color: c.color.clone(),
+            })
+            .collect();
+        ext.loops = track.saved_loops.iter()
+            .filter(|l| l.end_sample > l.start_sample)
+            .map(|l| ExternalLoop {
+                slot: Some(l.index),
+                start_secs: samples_to_secs(l.start_sample),
+                end_secs: samples_to_secs(l.end_sample),
+                label: l.label.clone(),
+                color: l.color.clone(),
+            })
+            .collect();
+        ext.grid = grid_markers(track);
+    }
+    ext
+}
+
+/// Beat grid markers for a track: one for a constant tempo, one per tempo
+/// anchor otherwise
+///
+/// The first beat is the start of a bar. Anchor markers get their position
+/// in the bar from the number of beats since the first beat.
+fn grid_markers(track: &Track) -> Vec<GridMarker> {
+    let Some(bpm) = track.bpm.filter(|&bpm| bpm > 0.0) else {
+        return Vec::new();
+    };
+    if track.tempo_anchors.len() < 2 {
+        return vec![GridMarker {
+            position_secs: samples_to_secs(track.first_beat_sample),
+            bpm,
+            beat_in_bar: 1,
+        }];
+    }
+
+    let mut anchors = track.tempo_anchors.clone();
+    anchors.sort_by_key(|a| a.index);
+
+    let mut markers = Vec::with_capacity(anchors.len());
+    let mut beats = 0.0;
+    for (i, anchor) in anchors.iter().enumerate() {
+        if i > 0 {
+            let prev = &anchors[i - 1];
+            beats += samples_to_secs(anchor.sample_position - prev.sample_position) * prev.bpm / 60.0;
+        }
+        markers.push(GridMarker {
+            position_secs: samples_to_secs(anchor.sample_position),
+            bpm: anchor.bpm,
+            beat_in_bar: (beats.round() as i64).rem_euclid(4) as u8 + 1,
+        });
+    }
+    markers
+}
+
+/// Render stereo mixdowns into `audio_dir` and point the tracks at them
+///
+/// A mixdown newer than its stem file is reused, so re-exporting a playlist
+/// only renders tracks that changed.
+fn render_mixdowns(library: &mut ExternalLibrary, audio_dir: &Path, summary: &mut LibraryExportSummary) -> Result<()> {
+    std::fs::create_dir_all(audio_dir)
+        .with_context(|| format!("Failed to create {:?}", audio_dir))?;
+
+    for track in &mut library.tracks {
+        let stem_name = track.location.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| track.key.clone());
+        let target = audio_dir.join(mixdown_file_name(&stem_name, &track.location));
+
+        if !is_up_to_date(&target, &track.location) {
+            log::info!("library_export: rendering mixdown of {:?}", track.location);
+            let rendered = LoadedTrack::load_stems(&track.location)
+                .map_err(anyhow::Error::from)
+                .and_then(|stems| export_stereo_mixdown(&target, &stems));
+            if let Err(e) = rendered {
+                log::warn!("library_export: mixdown of {:?} failed: {:#}", track.location, e);
+                summary.failed.push(stem_name);
+                continue;
+            }
+        }
+        track.location = target;
+        summary.mixdowns += 1;
+    }
+    Ok(())
+}
+
+/// File name of a track's mixdown
+///
+/// Tracks with the same file name in different folders share one audio
+/// folder, so a hash of the source path keeps their mixdowns apart. The hash
+/// is stable across exports, so unchanged mixdowns are still reused.
+fn mixdown_file_name(stem_name: &str, source: &Path) -> String {
+    let hash = fnv1a_32(source.to_string_lossy().as_bytes());
+    format!("{}_{:08x}.wav", stem_name, hash)
+}
+
+/// 32-bit FNV-1a (fixed algorithm, unlike `DefaultHasher`, so mixdown names
+/// survive toolchain upgrades)
+fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &b| (hash ^ b as u32).wrapping_mul(0x0100_0193))
+}
+
+/// Whether `target` exists and is newer than `source`
+fn is_up_to_date(target: &Path, source: &Path) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    match (modified(target), modified(source)) {
+        (Some(target), Some(source)) => target >= source,
+        _ => false,
+    }
+}
+
+/// Playlists of a tree as ("Folder - Playlist", track keys), depth first
+fn flatten_playlists<'a>(nodes: &'a [ExternalNode], prefix: &str, out: &mut Vec<(String, &'a [String])>) {
+    for node in nodes {
+        match node {
+            ExternalNode::Folder { name, children } => {
+                flatten_playlists(children, &join_name(prefix, name), out);
+            }
+            ExternalNode::Playlist { name, tracks } => {
+                // A folder's own tracks are exported under the folder's name
+                let is_folder_tracks = prefix == name || prefix.ends_with(&format!(" - {}", name));
+                let full_name = if is_folder_tracks { prefix.to_string() } else { join_name(prefix, name) };
+                out.push((full_name, tracks.as_slice()));
+            }
+        }
+    }
+}
+
+fn join_name(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} - {}", prefix, name)
+    }
+}
+
+fn count_playlists(nodes: &[ExternalNode]) -> usize {
+    nodes.iter()
+        .map(|node| match node {
+            ExternalNode::Folder { children, .. } => count_playlists(children),
+            ExternalNode::Playlist { .. } => 1,
+        })
+        .sum()
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content).with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn samples_to_secs(samples: i64) -> f64 {
+    samples as f64 / SAMPLE_RATE as f64
+}
+
+/// Escape text for an XML attribute value
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' | '\r' | '\t' => out.push(' '),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Convert a "#RRGGBB" colour to its components
+pub(crate) fn parse_rgb(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+/// Convert stars to the 0–255 rating both Rekordbox and Traktor store
+pub(crate) fn byte_from_stars(stars: u8) -> u32 {
+    stars.min(5) as u32 * 51
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mesh_core::db::{CuePoint, TempoAnchor};
+    use mesh_core::playlist::DatabaseStorage;
+
+    #[test]
+    fn tempo_anchors_count_beats_into_the_bar() {
+        let mut track = Track::new("/tmp/a.flac", "A");
+        track.bpm = Some(120.0);
+        track.tempo_anchors = vec![
+            TempoAnchor { track_id: 1, index: 0, sample_position: 0, bpm: 120.0 },
+            // 3 beats of 0.5 s later
+            TempoAnchor { track_id: 1, index: 1, sample_position: (1.5 * SAMPLE_RATE as f64) as i64, bpm: 125.0 },
+        ];
+        let markers = grid_markers(&track);
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].beat_in_bar, 1);
+        assert_eq!(markers[1].beat_in_bar, 4);
+        assert_eq!(markers[1].bpm, 125.0);
+
+        track.tempo_anchors.clear();
+        track.first_beat_sample = SAMPLE_RATE as i64;
+        assert_eq!(grid_markers(&track), vec![GridMarker { position_secs: 1.0, bpm: 120.0, beat_in_bar: 1 }]);
+    }
+
+    #[test]
+    fn mixdowns_of_same_named_tracks_dont_collide() {
+        let a = mixdown_file_name("Intro", Path::new("/music/house/Intro.flac"));
+        let b = mixdown_file_name("Intro", Path::new("/music/techno/Intro.flac"));
+        assert_ne!(a, b);
+        assert!(a.starts_with("Intro_") && a.ends_with(".wav"));
+        // Stable across exports so existing mixdowns are reused
+        assert_eq!(a, mixdown_file_name("Intro", Path::new("/music/house/Intro.flac")));
+        // Published FNV-1a test vectors
+        assert_eq!(fnv1a_32(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a_32(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a_32(b"foobar"), 0xbf9c_f968);
+    }
+
+    #[test]
+    fn nested_playlists_flatten_with_folder_names() {
+        let nodes = vec![ExternalNode::Folder {
+            name: "Sets".to_string(),
+            children: vec![
+                ExternalNode::Playlist { name: "Sets".to_string(), tracks: vec!["1".to_string()] },
+                ExternalNode::Playlist { name: "Friday".to_string(), tracks: vec!["2".to_string()] },
+            ],
+        }];
+        let mut out = Vec::new();
+        flatten_playlists(&nodes, "", &mut out);
+        let names: Vec<&str> = out.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["Sets", "Sets - Friday"]);
+        assert_eq!(count_playlists(&nodes), 2);
+    }
+
+    #[test]
+    fn export_round_trips_through_the_importers() {
+        let root = std::env::temp_dir().join(format!("mesh-library-export-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let db = DatabaseService::in_memory(&root).unwrap();
+
+        let mut track = Track::new(root.join("tracks/Artist - Title.flac"), "Title");
+        track.artist = Some("Artist".to_string());
+        track.bpm = Some(128.0);
+        track.first_beat_sample = SAMPLE_RATE as i64 / 2;
+        let track_id = db.save_track(&track).unwrap();
+        track.id = Some(track_id);
+        track.cue_points.push(CuePoint {
+            track_id,
+            index: 2,
+            sample_position: 10 * SAMPLE_RATE as i64,
+            label: Some("Drop".to_string()),
+            color: Some("#FF0000".to_string()),
+        });
+        db.save_track(&track).unwrap();
+        db.set_track_rating(&TrackRating { track_id, rating: 3, color: None }).unwrap();
+
+        let sets = db.create_playlist("Sets", None).unwrap();
+        let friday = db.create_playlist("Friday", Some(sets)).unwrap();
+        db.add_track_to_playlist(friday, track_id, 0).unwrap();
+
+        let storage = DatabaseStorage::new(db.clone()).unwrap();
+        let out_dir = root.join("export");
+        let summary = export_library(&db, &storage, &NodeId::playlists(), &out_dir, &ExportOptions::default()).unwrap();
+        assert_eq!(summary.tracks, 1);
+        assert_eq!(summary.playlists, 1);
+
+        let xml = std::fs::read_to_string(out_dir.join("Playlists.xml")).unwrap();
+        let library = crate::library_import::rekordbox::parse(&xml).unwrap();
+        let ext = &library.tracks[0];
+        assert_eq!(ext.location, root.join("tracks/Artist - Title.flac"));
+        assert_eq!(ext.rating, 3);
+        assert_eq!(ext.cues[0].slot, Some(2));
+        assert_eq!(ext.cues[0].position_secs, 10.0);
+        assert_eq!(ext.grid[0].position_secs, 0.5);
+
+        let nml = std::fs::read_to_string(out_dir.join("Playlists.nml")).unwrap();
+        let library = crate::library_import::traktor::parse(&nml).unwrap();
+        assert_eq!(library.tracks[0].cues[0].label.as_deref(), Some("Drop"));
+        assert_eq!(library.tracks[0].grid.len(), 1);
+        assert!(matches!(&library.playlists[0], ExternalNode::Folder { name, .. } if name == "Sets"));
+
+        let m3u = std::fs::read_to_string(out_dir.join("Sets - Friday.m3u8")).unwrap();
+        assert!(m3u.contains("Artist - Title.flac"));
+    }
+}
@@ -0,0 +1,218 @@
+//! Rekordbox XML library writer
+//!
+//! Writes the format read by File → Import Collection in xml format (see
+//! [`crate::library_import::rekordbox`] for the layout). Hot cues keep their
+//! slot and colour. Saved loops are written as memory loops: Rekordbox hot
+//! cue slots hold either a cue or a loop, so mesh's separate loop slots
+//! can't be mapped onto them without overwriting cues.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+use super::{byte_from_stars, parse_rgb, xml_escape};
+use crate::library_import::{ExternalLibrary, ExternalNode, ExternalTrack};
+
+/// Characters escaped in `Location` URLs (everything but unreserved and "/")
+const LOCATION: &AsciiSet = &CONTROLS
+    .add(b' ').add(b'"').add(b'#').add(b'%').add(b'&').add(b'\'').add(b'<').add(b'>')
+    .add(b'?').add(b'[').add(b']').add(b'`').add(b'{').add(b'}').add(b'|').add(b'^');
+
+/// Write a library as Rekordbox XML
+pub fn write(library: &ExternalLibrary) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<DJ_PLAYLISTS Version=\"1.0.0\">\n");
+    let _ = writeln!(
+        xml,
+        "  <PRODUCT Name=\"mesh\" Version=\"{}\" Company=\"mesh\"/>",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let _ = writeln!(xml, "  <COLLECTION Entries=\"{}\">", library.tracks.len());
+    for track in &library.tracks {
+        write_track(&mut xml, track);
+    }
+    xml.push_str("  </COLLECTION>\n");
+
+    xml.push_str("  <PLAYLISTS>\n");
+    let _ = writeln!(xml, "    <NODE Type=\"0\" Name=\"ROOT\" Count=\"{}\">", library.playlists.len());
+    for node in &library.playlists {
+        write_node(&mut xml, node, 3);
+    }
+    xml.push_str("    </NODE>\n");
+    xml.push_str("  </PLAYLISTS>\n");
+    xml.push_str("</DJ_PLAYLISTS>\n");
+    xml
+}
+
+fn write_track(xml: &mut String, track: &ExternalTrack) {
+    let _ = write!(xml, "    <TRACK TrackID=\"{}\"", xml_escape(&track.key));
+    for (name, value) in [
+        ("Name", &track.title),
+        ("Artist", &track.artist),
+        ("Album", &track.album),
+        ("Genre", &track.genre),
+        ("Tonality", &track.musical_key),
+    ] {
+        if let Some(value) = value {
+            let _ = write!(xml, " {}=\"{}\"", name, xml_escape(value));
+        }
+    }
+    let _ = write!(xml, " Kind=\"{}\"", file_kind(&track.location));
+    if let Some(duration) = track.duration_secs {
+        let _ = write!(xml, " TotalTime=\"{}\"", duration.round() as i64);
+    }
+    if let Some(bpm) = track.bpm {
+        let _ = write!(xml, " AverageBpm=\"{:.2}\"", bpm);
+    }
+    if track.rating > 0 {
+        let _ = write!(xml, " Rating=\"{}\"", byte_from_stars(track.rating));
+    }
+    if let Some((r, g, b)) = track.color.as_deref().and_then(parse_rgb) {
+        let _ = write!(xml, " Colour=\"0x{:02X}{:02X}{:02X}\"", r, g, b);
+    }
+    let _ = writeln!(xml, " Location=\"{}\">", xml_escape(&location_url(&track.location)));
+
+    for marker in &track.grid {
+        let _ = writeln!(
+            xml,
+            "      <TEMPO Inizio=\"{:.3}\" Bpm=\"{:.2}\" Metro=\"4/4\" Battito=\"{}\"/>",
+            marker.position_secs, marker.bpm, marker.beat_in_bar
+        );
+    }
+
+    for cue in &track.cues {
+        let _ = write!(
+            xml,
+            "      <POSITION_MARK Name=\"{}\" Type=\"0\" Start=\"{:.3}\" Num=\"{}\"",
+            xml_escape(cue.label.as_deref().unwrap_or("")),
+            cue.position_secs,
+            cue.slot.map_or(-1, i32::from)
+        );
+        if let Some((r, g, b)) = cue.color.as_deref().and_then(parse_rgb) {
+            let _ = write!(xml, " Red=\"{}\" Green=\"{}\" Blue=\"{}\"", r, g, b);
+        }
+        xml.push_str("/>\n");
+    }
+
+    for l in &track.loops {
+        let _ = writeln!(
+            xml,
+            "      <POSITION_MARK Name=\"{}\" Type=\"4\" Start=\"{:.3}\" End=\"{:.3}\" Num=\"-1\"/>",
+            xml_escape(l.label.as_deref().unwrap_or("")),
+            l.start_secs,
+            l.end_secs
+        );
+    }
+
+    xml.push_str("    </TRACK>\n");
+}
+
+fn write_node(xml: &mut String, node: &ExternalNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match node {
+        ExternalNode::Folder { name, children } => {
+            let _ = writeln!(
+                xml,
+                "{}<NODE Type=\"0\" Name=\"{}\" Count=\"{}\">",
+                indent,
+                xml_escape(name),
+                children.len()
+            );
+            for child in children {
+                write_node(xml, child, depth + 1);
+            }
+            let _ = writeln!(xml, "{}</NODE>", indent);
+        }
+        ExternalNode::Playlist { name, tracks } => {
+            let _ = writeln!(
+                xml,
+                "{}<NODE Name=\"{}\" Type=\"1\" KeyType=\"0\" Entries=\"{}\">",
+                indent,
+                xml_escape(name),
+                tracks.len()
+            );
+            for key in tracks {
+                let _ = writeln!(xml, "{}  <TRACK Key=\"{}\"/>", indent, xml_escape(key));
+            }
+            let _ = writeln!(xml, "{}</NODE>", indent);
+        }
+    }
+}
+
+/// Convert a path to a `file://localhost/...` URL
+fn location_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = if path.starts_with('/') { path } else { format!("/{}", path) };
+    format!("file://localhost{}", utf8_percent_encode(&path, LOCATION))
+}
+
+/// Rekordbox's file type label
+fn file_kind(path: &Path) -> String {
+    let ext = path.extension()
+        .map(|e| e.to_string_lossy().to_ascii_uppercase())
+        .unwrap_or_default();
+    format!("{} File", ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library_import::{ExternalCue, ExternalLoop, GridMarker};
+    use std::path::PathBuf;
+
+    #[test]
+    fn written_library_parses_back() {
+        let library = ExternalLibrary {
+            tracks: vec![ExternalTrack {
+                key: "1".to_string(),
+                location: PathBuf::from("/Music/Drum & Bass/A #1.wav"),
+                title: Some("A \"quoted\" <title>".to_string()),
+                rating: 2,
+                color: Some("#28E214".to_string()),
+                grid: vec![GridMarker { position_secs: 0.25, bpm: 174.0, beat_in_bar: 1 }],
+                cues: vec![ExternalCue {
+                    slot: Some(4),
+                    position_secs: 30.5,
+                    label: Some("Drop".to_string()),
+                    color: Some("#FF0000".to_string()),
+                }],
+                loops: vec![ExternalLoop {
+                    slot: Some(0),
+                    start_secs: 40.0,
+                    end_secs: 42.0,
+                    label: None,
+                    color: None,
+                }],
+                ..Default::default()
+            }],
+            playlists: vec![ExternalNode::Folder {
+                name: "Sets".to_string(),
+                children: vec![ExternalNode::Playlist {
+                    name: "Friday".to_string(),
+                    tracks: vec!["1".to_string()],
+                }],
+            }],
+        };
+
+        let parsed = crate::library_import::rekordbox::parse(&write(&library)).unwrap();
+        let track = &parsed.tracks[0];
+        assert_eq!(track.location, PathBuf::from("/Music/Drum & Bass/A #1.wav"));
+        assert_eq!(track.title.as_deref(), Some("A \"quoted\" <title>"));
+        assert_eq!(track.rating, 2);
+        assert_eq!(track.color.as_deref(), Some("#28E214"));
+        assert_eq!(track.grid, library.tracks[0].grid);
+        assert_eq!(track.cues, library.tracks[0].cues);
+        // Loops become memory loops
+        assert_eq!(track.loops[0].slot, None);
+        assert_eq!(track.loops[0].end_secs, 42.0);
+        assert_eq!(parsed.playlists, library.playlists);
+    }
+
+    #[test]
+    fn windows_paths_get_a_leading_slash() {
+        assert_eq!(location_url(Path::new("C:/Music/a b.wav")), "file://localhost/C:/Music/a%20b.wav");
+    }
+}
@@ -0,0 +1,274 @@
+//! Traktor NML collection writer
+//!
+//! Writes a `collection.nml`-style file (see [`crate::library_import::traktor`]
+//! for the layout) that Traktor opens via Import Collection. Grid markers
+//! are written as `CUE_V2` type 4 entries that sit on no hot cue, so they
+//! don't take a slot from mesh's cues. Saved loops become stored loops
+//! (`HOTCUE="-1"`) for the same reason as in the Rekordbox writer. Traktor
+//! has no per-cue colours; track colours snap to its seven-colour palette.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use super::{byte_from_stars, parse_rgb, xml_escape};
+use crate::library_import::traktor::TRACK_COLOURS;
+use crate::library_import::{ExternalLibrary, ExternalNode, ExternalTrack};
+use crate::metadata::hints::parse_tag_key;
+
+/// `CUE_V2` type of a plain cue
+const CUE_TYPE_CUE: u8 = 0;
+
+/// `CUE_V2` type of a beat grid marker
+const CUE_TYPE_GRID: u8 = 4;
+
+/// `CUE_V2` type of a loop
+const CUE_TYPE_LOOP: u8 = 5;
+
+/// Write a library as a Traktor NML collection
+pub fn write(library: &ExternalLibrary) -> String {
+    let mut nml = String::new();
+    nml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\" ?>\n");
+    nml.push_str("<NML VERSION=\"19\">\n");
+    nml.push_str("  <HEAD COMPANY=\"www.native-instruments.com\" PROGRAM=\"Traktor\"></HEAD>\n");
+
+    let _ = writeln!(nml, "  <COLLECTION ENTRIES=\"{}\">", library.tracks.len());
+    for track in &library.tracks {
+        write_entry(&mut nml, track);
+    }
+    nml.push_str("  </COLLECTION>\n");
+
+    // Playlist entries reference tracks by primary key, not library key
+    let primary_key = |key: &str| -> Option<String> {
+        library.tracks.iter()
+            .find(|t| t.key == key)
+            .map(|t| {
+                let (volume, dir, file) = encode_location(&t.location);
+                format!("{}{}{}", volume, dir, file)
+            })
+    };
+
+    nml.push_str("  <PLAYLISTS>\n");
+    nml.push_str("    <NODE TYPE=\"FOLDER\" NAME=\"$ROOT\">\n");
+    let _ = writeln!(nml, "      <SUBNODES COUNT=\"{}\">", library.playlists.len());
+    for node in &library.playlists {
+        write_node(&mut nml, node, 4, &primary_key);
+    }
+    nml.push_str("      </SUBNODES>\n");
+    nml.push_str("    </NODE>\n");
+    nml.push_str("  </PLAYLISTS>\n");
+    nml.push_str("</NML>\n");
+    nml
+}
+
+fn write_entry(nml: &mut String, track: &ExternalTrack) {
+    nml.push_str("    <ENTRY");
+    if let Some(title) = &track.title {
+        let _ = write!(nml, " TITLE=\"{}\"", xml_escape(title));
+    }
+    if let Some(artist) = &track.artist {
+        let _ = write!(nml, " ARTIST=\"{}\"", xml_escape(artist));
+    }
+    nml.push_str(">\n");
+
+    let (volume, dir, file) = encode_location(&track.location);
+    let _ = writeln!(
+        nml,
+        "      <LOCATION DIR=\"{}\" FILE=\"{}\" VOLUME=\"{}\" VOLUMEID=\"{}\"></LOCATION>",
+        xml_escape(&dir),
+        xml_escape(&file),
+        xml_escape(&volume),
+        xml_escape(&volume)
+    );
+    if let Some(album) = &track.album {
+        let _ = writeln!(nml, "      <ALBUM TITLE=\"{}\"></ALBUM>", xml_escape(album));
+    }
+
+    nml.push_str("      <INFO");
+    if let Some(genre) = &track.genre {
+        let _ = write!(nml, " GENRE=\"{}\"", xml_escape(genre));
+    }
+    if let Some(key) = &track.musical_key {
+        let _ = write!(nml, " KEY=\"{}\"", xml_escape(key));
+    }
+    if let Some(duration) = track.duration_secs {
+        let _ = write!(nml, " PLAYTIME=\"{}\"", duration.round() as i64);
+    }
+    if track.rating > 0 {
+        let _ = write!(nml, " RANKING=\"{}\"", byte_from_stars(track.rating));
+    }
+    if let Some(index) = track.color.as_deref().and_then(palette_index) {
+        let _ = write!(nml, " COLOR=\"{}\"", index);
+    }
+    nml.push_str("></INFO>\n");
+
+    if let Some(bpm) = track.bpm {
+        let _ = writeln!(nml, "      <TEMPO BPM=\"{:.6}\" BPM_QUALITY=\"100.000000\"></TEMPO>", bpm);
+    }
+    if let Some(value) = track.musical_key.as_deref().and_then(key_value) {
+        let _ = writeln!(nml, "      <MUSICAL_KEY VALUE=\"{}\"></MUSICAL_KEY>", value);
+    }
+
+    for marker in &track.grid {
+        let _ = writeln!(
+            nml,
+            "      <CUE_V2 NAME=\"AutoGrid\" DISPL_ORDER=\"0\" TYPE=\"{}\" START=\"{:.6}\" LEN=\"0.000000\" REPEATS=\"-1\" HOTCUE=\"-1\"><GRID BPM=\"{:.6}\"></GRID></CUE_V2>",
+            CUE_TYPE_GRID,
+            marker.position_secs * 1000.0,
+            marker.bpm
+        );
+    }
+    for cue in &track.cues {
+        let _ = writeln!(
+            nml,
+            "      <CUE_V2 NAME=\"{}\" DISPL_ORDER=\"0\" TYPE=\"{}\" START=\"{:.6}\" LEN=\"0.000000\" REPEATS=\"-1\" HOTCUE=\"{}\"></CUE_V2>",
+            xml_escape(cue.label.as_deref().unwrap_or("n.n.")),
+            CUE_TYPE_CUE,
+            cue.position_secs * 1000.0,
+            cue.slot.map_or(-1, i32::from)
+        );
+    }
+    for l in &track.loops {
+        let _ = writeln!(
+            nml,
+            "      <CUE_V2 NAME=\"{}\" DISPL_ORDER=\"0\" TYPE=\"{}\" START=\"{:.6}\" LEN=\"{:.6}\" REPEATS=\"-1\" HOTCUE=\"-1\"></CUE_V2>",
+            xml_escape(l.label.as_deref().unwrap_or("n.n.")),
+            CUE_TYPE_LOOP,
+            l.start_secs * 1000.0,
+            (l.end_secs - l.start_secs) * 1000.0
+        );
+    }
+
+    nml.push_str("    </ENTRY>\n");
+}
+
+fn write_node(nml: &mut String, node: &ExternalNode, depth: usize, primary_key: &dyn Fn(&str) -> Option<String>) {
+    let indent = "  ".repeat(depth);
+    match node {
+        ExternalNode::Folder { name, children } => {
+            let _ = writeln!(nml, "{}<NODE TYPE=\"FOLDER\" NAME=\"{}\">", indent, xml_escape(name));
+            let _ = writeln!(nml, "{}  <SUBNODES COUNT=\"{}\">", indent, children.len());
+            for child in children {
+                write_node(nml, child, depth + 2, primary_key);
+            }
+            let _ = writeln!(nml, "{}  </SUBNODES>", indent);
+            let _ = writeln!(nml, "{}</NODE>", indent);
+        }
+        ExternalNode::Playlist { name, tracks } => {
+            let keys: Vec<String> = tracks.iter().filter_map(|key| primary_key(key)).collect();
+            let _ = writeln!(nml, "{}<NODE TYPE=\"PLAYLIST\" NAME=\"{}\">", indent, xml_escape(name));
+            let _ = writeln!(nml, "{}  <PLAYLIST ENTRIES=\"{}\" TYPE=\"LIST\">", indent, keys.len());
+            for key in keys {
+                let _ = writeln!(
+                    nml,
+                    "{}    <ENTRY><PRIMARYKEY TYPE=\"TRACK\" KEY=\"{}\"></PRIMARYKEY></ENTRY>",
+                    indent,
+                    xml_escape(&key)
+                );
+            }
+            let _ = writeln!(nml, "{}  </PLAYLIST>", indent);
+            let _ = writeln!(nml, "{}</NODE>", indent);
+        }
+    }
+}
+
+/// Split a path into Traktor's (VOLUME, DIR, FILE)
+///
+/// Windows paths keep the drive letter as the volume; other paths are
+/// written with an empty volume and the directory from the filesystem root.
+fn encode_location(path: &Path) -> (String, String, String) {
+    let file = path.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let parent = path.parent()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+
+    let has_drive = matches!(parent.as_bytes(), [drive, b':', ..] if drive.is_ascii_alphabetic());
+    let (volume, dir) = if has_drive {
+        (parent[..2].to_string(), parent[2..].to_string())
+    } else {
+        (String::new(), parent)
+    };
+    let mut encoded: String = dir.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| format!("/:{}", segment))
+        .collect();
+    encoded.push_str("/:");
+    (volume, encoded, file)
+}
+
+/// Nearest of Traktor's track colours (1-based `COLOR` value)
+fn palette_index(color: &str) -> Option<usize> {
+    let (r, g, b) = parse_rgb(color)?;
+    let distance = |candidate: &str| {
+        let (cr, cg, cb) = parse_rgb(candidate).unwrap_or_default();
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, cr) + d(g, cg) + d(b, cb)
+    };
+    (0..TRACK_COLOURS.len())
+        .min_by_key(|&i| distance(TRACK_COLOURS[i]))
+        .map(|i| i + 1)
+}
+
+/// Traktor's `MUSICAL_KEY` value: 0–11 major from C, 12–23 minor from Cm
+fn key_value(key: &str) -> Option<u8> {
+    let key = parse_tag_key(key)?;
+    Some(key.root % 12 + if key.minor { 12 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library_import::{ExternalCue, GridMarker};
+    use std::path::PathBuf;
+
+    #[test]
+    fn written_collection_parses_back() {
+        let library = ExternalLibrary {
+            tracks: vec![ExternalTrack {
+                key: "1".to_string(),
+                location: PathBuf::from("/Music/House/Artist - Title.wav"),
+                title: Some("Title".to_string()),
+                rating: 5,
+                color: Some("#3050E0".to_string()),
+                grid: vec![GridMarker { position_secs: 0.25, bpm: 128.0, beat_in_bar: 1 }],
+                cues: vec![ExternalCue {
+                    slot: Some(0),
+                    position_secs: 64.1,
+                    label: Some("Drop".to_string()),
+                    color: None,
+                }],
+                ..Default::default()
+            }],
+            playlists: vec![ExternalNode::Playlist {
+                name: "Friday".to_string(),
+                tracks: vec!["1".to_string()],
+            }],
+        };
+
+        let parsed = crate::library_import::traktor::parse(&write(&library)).unwrap();
+        let track = &parsed.tracks[0];
+        assert_eq!(track.location, library.tracks[0].location);
+        assert_eq!(track.rating, 5);
+        // Snapped to Traktor's blue
+        assert_eq!(track.color.as_deref(), Some("#2E7BE0"));
+        assert_eq!(track.grid, library.tracks[0].grid);
+        assert_eq!(track.cues.len(), 1);
+        assert_eq!(track.cues[0].slot, Some(0));
+        assert!((track.cues[0].position_secs - 64.1).abs() < 1e-6);
+        assert_eq!(
+            parsed.playlists,
+            vec![ExternalNode::Playlist { name: "Friday".to_string(), tracks: vec![track.key.clone()] }]
+        );
+    }
+
+    #[test]
+    fn locations_and_keys() {
+        assert_eq!(
+            encode_location(Path::new("C:/Music/a.mp3")),
+            ("C:".to_string(), "/:Music/:".to_string(), "a.mp3".to_string())
+        );
+        assert_eq!(key_value("Am"), Some(21));
+        assert_eq!(key_value("8B"), Some(0));
+    }
+}
@@ -0,0 +1,631 @@
+//! Rekordbox / Traktor library import
+//!
+//! Migrates the preparation work done in other DJ software onto tracks that
+//! are already in the mesh collection:
+//!
+//! - Hot cues (slots 1–8) and saved loops
+//! - Beat grids (first beat, tempo and tempo-change markers)
+//! - Playlists and playlist folders
+//! - Star ratings and track colours
+//!
+//! Library files are parsed into a common [`ExternalLibrary`] by the
+//! [`rekordbox`] (`rekordbox.xml`) and [`traktor`] (`collection.nml`)
+//! parsers. Entries are then matched to mesh tracks by path, falling back to
+//! the file name — batch import names converted tracks after the source file,
+//! so `Music/Artist - Title.mp3` matches `tracks/Artist - Title.flac`.
+//!
+//! Positions in the library refer to the source file's timeline. Stems are
+//! separated from that same file, so positions carry over unchanged (only
+//! converted from seconds to samples at `SAMPLE_RATE`).
+
+pub mod rekordbox;
+pub mod traktor;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use mesh_core::db::{CuePoint, DatabaseService, SavedLoop, TempoAnchor, TrackRating};
+use mesh_core::types::SAMPLE_RATE;
+
+/// Number of hot cue slots in mesh (library cues beyond this are dropped)
+const HOT_CUE_SLOTS: u8 = 8;
+
+/// Number of saved loop slots in mesh
+const SAVED_LOOP_SLOTS: u8 = 8;
+
+/// Consecutive grid markers closer than this in tempo are merged
+/// (Rekordbox writes one marker per bar on dynamic grids)
+const GRID_BPM_EPSILON: f64 = 0.01;
+
+/// DJ software a library file was written by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryKind {
+    /// Rekordbox XML export (`rekordbox.xml`)
+    Rekordbox,
+    /// Traktor collection (`collection.nml`)
+    Traktor,
+}
+
+impl LibraryKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LibraryKind::Rekordbox => "Rekordbox",
+            LibraryKind::Traktor => "Traktor",
+        }
+    }
+}
+
+/// A library file found in the import folder
+#[derive(Debug, Clone)]
+pub struct LibraryFile {
+    pub path: PathBuf,
+    pub kind: LibraryKind,
+}
+
+impl LibraryFile {
+    /// File name for display
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// A parsed library, independent of the software that wrote it
+#[derive(Debug, Clone, Default)]
+pub struct ExternalLibrary {
+    pub tracks: Vec<ExternalTrack>,
+    /// Top-level playlists and folders
+    pub playlists: Vec<ExternalNode>,
+}
+
+/// One track entry of a library
+#[derive(Debug, Clone, Default)]
+pub struct ExternalTrack {
+    /// Key used by playlist entries to reference this track
+    pub key: String,
+    /// Location of the source file on the machine that wrote the library
+    pub location: PathBuf,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub bpm: Option<f64>,
+    /// Musical key in mesh notation ("Am", "F#")
+    pub musical_key: Option<String>,
+    pub duration_secs: Option<f64>,
+    /// Stars, 0–5
+    pub rating: u8,
+    /// Track colour as "#RRGGBB"
+    pub color: Option<String>,
+    /// Beat grid markers, sorted by position
+    pub grid: Vec<GridMarker>,
+    pub cues: Vec<ExternalCue>,
+    pub loops: Vec<ExternalLoop>,
+}
+
+/// A beat grid marker: the grid runs at `bpm` from this beat on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridMarker {
+    pub position_secs: f64,
+    pub bpm: f64,
+    /// Position of this beat in its bar (1–4)
+    pub beat_in_bar: u8,
+}
+
+/// A cue point (hot cue if `slot` is set, memory cue otherwise)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalCue {
+    pub slot: Option<u8>,
+    pub position_secs: f64,
+    pub label: Option<String>,
+    pub color: Option<String>,
+}
+
+/// A loop (hot loop if `slot` is set, memory loop otherwise)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalLoop {
+    pub slot: Option<u8>,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub label: Option<String>,
+    pub color: Option<String>,
+}
+
+/// A node of the library's playlist tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalNode {
+    Folder {
+        name: String,
+        children: Vec<ExternalNode>,
+    },
+    Playlist {
+        name: String,
+        /// Track keys (see [`ExternalTrack::key`]) in playlist order
+        tracks: Vec<String>,
+    },
+}
+
+/// Outcome of a library import
+#[derive(Debug, Clone, Default)]
+pub struct LibraryImportSummary {
+    /// Library entries matched to a mesh track
+    pub matched: usize,
+    /// File names of library entries with no mesh track
+    pub unmatched: Vec<String>,
+    pub cues: usize,
+    pub loops: usize,
+    pub grids: usize,
+    pub playlists: usize,
+    pub ratings: usize,
+}
+
+/// Find Rekordbox XML and Traktor NML files in a folder (non-recursive)
+pub fn scan_library_files(folder: &Path) -> Result<Vec<LibraryFile>> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<LibraryFile> = std::fs::read_dir(folder)
+        .with_context(|| format!("Failed to read import folder {:?}", folder))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| detect_kind(&path).map(|kind| LibraryFile { path, kind }))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Identify a library file by extension (and root element for `.xml`)
+fn detect_kind(path: &Path) -> Option<LibraryKind> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "nml" => Some(LibraryKind::Traktor),
+        "xml" => {
+            // Only read the head: the root element comes right after the prolog
+            use std::io::Read;
+            let mut head = vec![0u8; 4096];
+            let n = std::fs::File::open(path).ok()?.read(&mut head).ok()?;
+            String::from_utf8_lossy(&head[..n])
+                .contains("<DJ_PLAYLISTS")
+                .then_some(LibraryKind::Rekordbox)
+        }
+        _ => None,
+    }
+}
+
+/// Read and parse a library file
+pub fn parse_library(file: &LibraryFile) -> Result<ExternalLibrary> {
+    let content = std::fs::read_to_string(&file.path)
+        .with_context(|| format!("Failed to read {:?}", file.path))?;
+    match file.kind {
+        LibraryKind::Rekordbox => rekordbox::parse(&content),
+        LibraryKind::Traktor => traktor::parse(&content),
+    }
+}
+
+/// Parse a library file and apply it to the collection
+///
+/// Playlists are created below a top-level folder named after the source
+/// software, so re-importing an updated library merges into the same tree.
+pub fn import_library_file(db: &DatabaseService, file: &LibraryFile) -> Result<LibraryImportSummary> {
+    let library = parse_library(file)?;
+    log::info!(
+        "library_import: {:?} parsed ({} tracks, {} top-level playlist nodes)",
+        file.path,
+        library.tracks.len(),
+        library.playlists.len()
+    );
+    import_library(db, &library, file.kind.display_name())
+}
+
+/// Apply a parsed library to the collection
+pub fn import_library(
+    db: &DatabaseService,
+    library: &ExternalLibrary,
+    root_folder: &str,
+) -> Result<LibraryImportSummary> {
+    let mut summary = LibraryImportSummary::default();
+    // Library key → mesh track ID (for playlist entries)
+    let mut matched_ids: HashMap<&str, i64> = HashMap::new();
+
+    for ext in &library.tracks {
+        let Some(mut track) = find_mesh_track(db, &ext.location)? else {
+            summary.unmatched.push(file_name(&ext.location));
+            continue;
+        };
+        let Some(track_id) = track.id else { continue };
+        matched_ids.insert(ext.key.as_str(), track_id);
+        summary.matched += 1;
+
+        let cues = merge_cues(&mut track.cue_points, track_id, &ext.cues);
+        let loops = merge_loops(&mut track.saved_loops, track_id, &ext.loops);
+        summary.cues += cues;
+        summary.loops += loops;
+
+        let grid = grid_from_markers(&ext.grid);
+        if let Some(grid) = &grid {
+            track.first_beat_sample = grid.first_beat_sample;
+            track.bpm = Some(grid.bpm);
+            track.tempo_anchors = grid.anchors.iter()
+                .enumerate()
+                .map(|(i, &(sample_position, bpm))| TempoAnchor {
+                    track_id,
+                    index: i as u32,
+                    sample_position,
+                    bpm,
+                })
+                .collect();
+            summary.grids += 1;
+        }
+
+        if cues > 0 || loops > 0 || grid.is_some() {
+            db.save_track(&track)
+                .with_context(|| format!("Failed to save {:?}", track.path))?;
+        }
+
+        if ext.rating > 0 || ext.color.is_some() {
+            db.set_track_rating(&TrackRating {
+                track_id,
+                rating: ext.rating.min(5),
+                color: ext.color.clone(),
+            })?;
+            summary.ratings += 1;
+        }
+    }
+
+    if !library.playlists.is_empty() {
+        let root_id = get_or_create_playlist(db, root_folder, None)?;
+        for node in &library.playlists {
+            import_node(db, node, root_id, &matched_ids, &mut summary)?;
+        }
+    }
+
+    log::info!(
+        "library_import: {} matched, {} unmatched, {} cues, {} loops, {} grids, {} playlists",
+        summary.matched,
+        summary.unmatched.len(),
+        summary.cues,
+        summary.loops,
+        summary.grids,
+        summary.playlists
+    );
+    Ok(summary)
+}
+
+/// Find the mesh track for a library location
+///
+/// Tries the exact path first (libraries that reference mesh files
+/// directly), then the converted file name, then the source file name.
+fn find_mesh_track(db: &DatabaseService, location: &Path) -> Result<Option<mesh_core::db::Track>> {
+    if let Some(track) = db.get_track_by_path(&location.to_string_lossy())? {
+        return Ok(Some(track));
+    }
+
+    let Some(stem) = location.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+        return Ok(None);
+    };
+    let candidates = [
+        format!("/{}.flac", crate::batch_import::sanitize_filename(&stem)),
+        format!("/{}", file_name(location)),
+    ];
+    for candidate in &candidates {
+        if let Some(found) = db.find_track_by_filename(candidate)? {
+            // find_track_by_filename returns the bare row; load cues, loops etc.
+            if let Some(id) = found.id {
+                return Ok(db.get_track(id)?);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// Convert seconds on the source timeline to a sample position
+pub(crate) fn secs_to_samples(secs: f64) -> i64 {
+    (secs.max(0.0) * SAMPLE_RATE as f64).round() as i64
+}
+
+/// Overwrite hot cue slots with the library's hot cues (memory cues are
+/// skipped). Returns the number of cues written.
+fn merge_cues(cue_points: &mut Vec<CuePoint>, track_id: i64, cues: &[ExternalCue]) -> usize {
+    let mut written = 0;
+    for cue in cues {
+        let Some(slot) = cue.slot.filter(|&s| s < HOT_CUE_SLOTS) else { continue };
+        cue_points.retain(|c| c.index != slot);
+        cue_points.push(CuePoint {
+            track_id,
+            index: slot,
+            sample_position: secs_to_samples(cue.position_secs),
+            label: cue.label.clone(),
+            color: cue.color.clone(),
+        });
+        written += 1;
+    }
+    cue_points.sort_by_key(|c| c.index);
+    written
+}
+
+/// Overwrite saved loop slots with the library's hot loops, then put memory
+/// loops into the remaining free slots. Returns the number of loops written.
+fn merge_loops(saved_loops: &mut Vec<SavedLoop>, track_id: i64, loops: &[ExternalLoop]) -> usize {
+    let mut written = 0;
+    let to_saved = |index: u8, l: &ExternalLoop| SavedLoop {
+        track_id,
+        index,
+        start_sample: secs_to_samples(l.start_secs),
+        end_sample: secs_to_samples(l.end_secs),
+        label: l.label.clone(),
+        color: l.color.clone(),
+    };
+
+    for l in loops.iter().filter(|l| l.end_secs > l.start_secs) {
+        let Some(slot) = l.slot.filter(|&s| s < SAVED_LOOP_SLOTS) else { continue };
+        saved_loops.retain(|s| s.index != slot);
+        saved_loops.push(to_saved(slot, l));
+        written += 1;
+    }
+
+    for l in loops.iter().filter(|l| l.slot.is_none() && l.end_secs > l.start_secs) {
+        let start = secs_to_samples(l.start_secs);
+        if saved_loops.iter().any(|s| s.start_sample == start) {
+            continue; // Already imported on an earlier run
+        }
+        let Some(free) = (0..SAVED_LOOP_SLOTS).find(|i| saved_loops.iter().all(|s| s.index != *i)) else {
+            break;
+        };
+        saved_loops.push(to_saved(free, l));
+        written += 1;
+    }
+    saved_loops.sort_by_key(|s| s.index);
+    written
+}
+
+/// A beat grid in mesh terms
+#[derive(Debug, Clone, PartialEq)]
+struct ImportedGrid {
+    first_beat_sample: i64,
+    /// Tempo of the first segment
+    bpm: f64,
+    /// (sample position, bpm) per tempo segment; empty for constant tempo
+    anchors: Vec<(i64, f64)>,
+}
+
+/// Convert library grid markers into first beat + tempo anchors
+///
+/// Markers that don't change the tempo are merged. The first beat is moved
+/// back to the start of its bar so mesh's bar counting lines up with the
+/// library's downbeats. When the bar starts before the track, the next
+/// downbeat is used instead, or the marker itself if a tempo change comes
+/// first.
+fn grid_from_markers(markers: &[GridMarker]) -> Option<ImportedGrid> {
+    let first = markers.iter().find(|m| m.bpm > 0.0)?;
+
+    let mut segments: Vec<(f64, f64)> = Vec::new();
+    for m in markers.iter().filter(|m| m.bpm > 0.0) {
+        match segments.last() {
+            Some(&(_, bpm)) if (bpm - m.bpm).abs() < GRID_BPM_EPSILON => {}
+            _ => segments.push((m.position_secs, m.bpm)),
+        }
+    }
+
+    let beat_secs = 60.0 / first.bpm;
+    let beats_to_bar = (first.beat_in_bar.clamp(1, 4) - 1) as f64;
+    let mut first_beat = first.position_secs - beats_to_bar * beat_secs;
+    if first_beat < 0.0 {
+        // Not enough room before the marker: use the next downbeat instead,
+        // as long as it stays inside the first tempo segment
+        let next_downbeat = first_beat + 4.0 * beat_secs;
+        first_beat = match segments.get(1) {
+            Some(&(next_secs, _)) if next_downbeat >= next_secs => first.position_secs,
+            _ => next_downbeat,
+        };
+    }
+    segments[0].0 = first_beat;
+
+    let anchors = if segments.len() > 1 {
+        segments.iter().map(|&(secs, bpm)| (secs_to_samples(secs), bpm)).collect()
+    } else {
+        Vec::new()
+    };
+
+    Some(ImportedGrid {
+        first_beat_sample: secs_to_samples(first_beat),
+        bpm: first.bpm,
+        anchors,
+    })
+}
+
+fn get_or_create_playlist(db: &DatabaseService, name: &str, parent_id: Option<i64>) -> Result<i64> {
+    if let Some(existing) = db.get_playlist_by_name(name, parent_id)? {
+        return Ok(existing.id);
+    }
+    Ok(db.create_playlist(name, parent_id)?)
+}
+
+/// Create a playlist tree node, appending matched tracks not yet in it
+fn import_node(
+    db: &DatabaseService,
+    node: &ExternalNode,
+    parent_id: i64,
+    matched_ids: &HashMap<&str, i64>,
+    summary: &mut LibraryImportSummary,
+) -> Result<()> {
+    match node {
+        ExternalNode::Folder { name, children } => {
+            let folder_id = get_or_create_playlist(db, name, Some(parent_id))?;
+            for child in children {
+                import_node(db, child, folder_id, matched_ids, summary)?;
+            }
+        }
+        ExternalNode::Playlist { name, tracks } => {
+            let playlist_id = get_or_create_playlist(db, name, Some(parent_id))?;
+            let mut present: HashSet<i64> = db.get_playlist_tracks(playlist_id)?
+                .iter()
+                .filter_map(|t| t.id)
+                .collect();
+            let mut sort_order = db.next_playlist_sort_order(playlist_id)?;
+            let mut entries = Vec::new();
+            for key in tracks {
+                if let Some(&id) = matched_ids.get(key.as_str()) {
+                    if present.insert(id) {
+                        entries.push((id, sort_order));
+                        sort_order += 1;
+                    }
+                }
+            }
+            db.add_tracks_to_playlist_batch(playlist_id, &entries)?;
+            summary.playlists += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Convert a 0–255 rating (both Rekordbox and Traktor step by 51) to stars
+pub(crate) fn stars_from_byte(value: u32) -> u8 {
+    ((value + 25) / 51).min(5) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(position_secs: f64, bpm: f64, beat_in_bar: u8) -> GridMarker {
+        GridMarker { position_secs, bpm, beat_in_bar }
+    }
+
+    #[test]
+    fn stars_from_byte_steps() {
+        assert_eq!(stars_from_byte(0), 0);
+        assert_eq!(stars_from_byte(51), 1);
+        assert_eq!(stars_from_byte(204), 4);
+        assert_eq!(stars_from_byte(255), 5);
+    }
+
+    #[test]
+    fn constant_grid_moves_to_downbeat() {
+        let grid = grid_from_markers(&[marker(1.0, 120.0, 3)]).unwrap();
+        // Two beats of 0.5 s back to the bar start
+        assert_eq!(grid.first_beat_sample, 0);
+        assert_eq!(grid.bpm, 120.0);
+        assert!(grid.anchors.is_empty());
+
+        // No room for the bar start: next downbeat, 4 beats later
+        let grid = grid_from_markers(&[marker(0.5, 120.0, 3)]).unwrap();
+        assert_eq!(grid.first_beat_sample, secs_to_samples(1.5));
+    }
+
+    #[test]
+    fn downbeat_fallback_stays_before_next_tempo_change() {
+        // The next downbeat (1.5 s) would land past the tempo change at 1.0 s
+        let grid = grid_from_markers(&[marker(0.5, 120.0, 3), marker(1.0, 140.0, 1)]).unwrap();
+        assert_eq!(grid.first_beat_sample, secs_to_samples(0.5));
+        assert_eq!(
+            grid.anchors,
+            vec![(secs_to_samples(0.5), 120.0), (secs_to_samples(1.0), 140.0)]
+        );
+
+        // A later tempo change leaves room for the next downbeat
+        let grid = grid_from_markers(&[marker(0.5, 120.0, 3), marker(8.0, 140.0, 1)]).unwrap();
+        assert_eq!(grid.first_beat_sample, secs_to_samples(1.5));
+        assert_eq!(grid.anchors[0], (secs_to_samples(1.5), 120.0));
+    }
+
+    #[test]
+    fn tempo_changes_become_anchors() {
+        let grid = grid_from_markers(&[
+            marker(0.5, 128.0, 1),
+            marker(8.0, 128.0, 1),
+            marker(30.0, 130.0, 1),
+        ])
+        .unwrap();
+        assert_eq!(
+            grid.anchors,
+            vec![(secs_to_samples(0.5), 128.0), (secs_to_samples(30.0), 130.0)]
+        );
+        assert!(grid_from_markers(&[]).is_none());
+    }
+
+    #[test]
+    fn memory_loops_fill_free_slots() {
+        let mut loops = vec![SavedLoop {
+            track_id: 1,
+            index: 0,
+            start_sample: 0,
+            end_sample: 100,
+            label: None,
+            color: None,
+        }];
+        let ext = [
+            ExternalLoop { slot: None, start_secs: 1.0, end_secs: 2.0, label: None, color: None },
+            ExternalLoop { slot: Some(2), start_secs: 3.0, end_secs: 4.0, label: None, color: None },
+        ];
+        assert_eq!(merge_loops(&mut loops, 1, &ext), 2);
+        let indices: Vec<u8> = loops.iter().map(|l| l.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert_eq!(loops[1].start_sample, SAMPLE_RATE as i64);
+    }
+
+    #[test]
+    fn import_matches_by_file_name() {
+        let root = std::env::temp_dir().join(format!("mesh-library-import-test-{}", std::process::id()));
+        let db = DatabaseService::in_memory(&root).unwrap();
+        let track = mesh_core::db::Track::new(root.join("tracks/Artist - Title.flac"), "Title");
+        let track_id = db.save_track(&track).unwrap();
+
+        let library = ExternalLibrary {
+            tracks: vec![
+                ExternalTrack {
+                    key: "1".to_string(),
+                    location: PathBuf::from("/Users/dj/Music/Artist - Title.mp3"),
+                    rating: 4,
+                    cues: vec![ExternalCue {
+                        slot: Some(1),
+                        position_secs: 2.0,
+                        label: Some("Drop".to_string()),
+                        color: Some("#FF0000".to_string()),
+                    }],
+                    ..Default::default()
+                },
+                ExternalTrack {
+                    key: "2".to_string(),
+                    location: PathBuf::from("/Users/dj/Music/Missing.mp3"),
+                    ..Default::default()
+                },
+            ],
+            playlists: vec![ExternalNode::Folder {
+                name: "Sets".to_string(),
+                children: vec![ExternalNode::Playlist {
+                    name: "Warmup".to_string(),
+                    tracks: vec!["1".to_string(), "2".to_string()],
+                }],
+            }],
+        };
+
+        let summary = import_library(&db, &library, "Rekordbox").unwrap();
+        assert_eq!(summary.matched, 1);
+        assert_eq!(summary.unmatched, vec!["Missing.mp3".to_string()]);
+
+        let cues = db.get_cue_points(track_id).unwrap();
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].sample_position, 2 * SAMPLE_RATE as i64);
+        assert_eq!(db.get_track_rating(track_id).unwrap().unwrap().rating, 4);
+
+        let root_id = db.get_playlist_by_name("Rekordbox", None).unwrap().unwrap().id;
+        let sets_id = db.get_playlist_by_name("Sets", Some(root_id)).unwrap().unwrap().id;
+        let warmup_id = db.get_playlist_by_name("Warmup", Some(sets_id)).unwrap().unwrap().id;
+        assert_eq!(db.get_playlist_tracks(warmup_id).unwrap().len(), 1);
+
+        // Re-import doesn't duplicate playlist entries
+        import_library(&db, &library, "Rekordbox").unwrap();
+        assert_eq!(db.get_playlist_tracks(warmup_id).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,292 @@
+//! Rekordbox XML library parser
+//!
+//! Reads the `rekordbox.xml` written by File → Export Collection in xml
+//! format:
+//!
+//! ```text
+//! <DJ_PLAYLISTS>
+//!   <COLLECTION>
+//!     <TRACK TrackID="1" Location="file://localhost/..." Rating="204" Colour="0xFF0000">
+//!       <TEMPO Inizio="0.025" Bpm="128.00" Metro="4/4" Battito="1"/>
+//!       <POSITION_MARK Name="Drop" Type="0" Start="64.1" Num="0" Red="40" Green="226" Blue="20"/>
+//!       <POSITION_MARK Type="4" Start="96.0" End="103.5" Num="-1"/>
+//!     </TRACK>
+//!   </COLLECTION>
+//!   <PLAYLISTS>
+//!     <NODE Type="0" Name="ROOT">
+//!       <NODE Type="1" Name="Warmup" KeyType="0"><TRACK Key="1"/></NODE>
+//!     </NODE>
+//!   </PLAYLISTS>
+//! </DJ_PLAYLISTS>
+//! ```
+//!
+//! Positions are in seconds. `Num` is the hot cue slot (-1 = memory cue),
+//! `Type` 4 marks a loop. Playlist entries reference tracks by `TrackID`
+//! (`KeyType="0"`) or by `Location` (`KeyType="1"`).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use percent_encoding::percent_decode_str;
+use roxmltree::Node;
+
+use super::{stars_from_byte, ExternalCue, ExternalLibrary, ExternalLoop, ExternalNode, ExternalTrack, GridMarker};
+
+/// `POSITION_MARK` type of a loop
+const MARK_TYPE_LOOP: &str = "4";
+
+/// Parse the contents of a Rekordbox XML library
+pub fn parse(xml: &str) -> Result<ExternalLibrary> {
+    let doc = roxmltree::Document::parse(xml).context("Invalid Rekordbox XML")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("DJ_PLAYLISTS") {
+        bail!("Not a Rekordbox library (root element <{}>)", root.tag_name().name());
+    }
+
+    let mut library = ExternalLibrary::default();
+    // Raw Location attribute → TrackID, for KeyType="1" playlists
+    let mut key_by_location: HashMap<&str, String> = HashMap::new();
+
+    if let Some(collection) = child(root, "COLLECTION") {
+        for node in children(collection, "TRACK") {
+            let (Some(id), Some(location)) = (node.attribute("TrackID"), node.attribute("Location")) else {
+                continue;
+            };
+            key_by_location.insert(location, id.to_string());
+            library.tracks.push(parse_track(node, id, location));
+        }
+    }
+
+    if let Some(root_node) = child(root, "PLAYLISTS").and_then(|p| child(p, "NODE")) {
+        library.playlists = children(root_node, "NODE")
+            .filter_map(|node| parse_node(node, &key_by_location))
+            .collect();
+    }
+
+    Ok(library)
+}
+
+fn parse_track(node: Node, id: &str, location: &str) -> ExternalTrack {
+    let mut track = ExternalTrack {
+        key: id.to_string(),
+        location: decode_location(location),
+        title: text_attr(node, "Name"),
+        artist: text_attr(node, "Artist"),
+        album: text_attr(node, "Album"),
+        genre: text_attr(node, "Genre"),
+        bpm: float_attr(node, "AverageBpm").filter(|&bpm| bpm > 0.0),
+        musical_key: node.attribute("Tonality")
+            .and_then(crate::metadata::hints::parse_tag_key)
+            .map(|k| k.to_string()),
+        duration_secs: float_attr(node, "TotalTime"),
+        rating: node.attribute("Rating")
+            .and_then(|r| r.parse::<u32>().ok())
+            .map(stars_from_byte)
+            .unwrap_or(0),
+        color: node.attribute("Colour").and_then(parse_hex_colour),
+        ..Default::default()
+    };
+
+    for tempo in children(node, "TEMPO") {
+        let (Some(position_secs), Some(bpm)) = (float_attr(tempo, "Inizio"), float_attr(tempo, "Bpm")) else {
+            continue;
+        };
+        track.grid.push(GridMarker {
+            position_secs,
+            bpm,
+            beat_in_bar: tempo.attribute("Battito").and_then(|b| b.parse().ok()).unwrap_or(1),
+        });
+    }
+    track.grid.sort_by(|a, b| a.position_secs.total_cmp(&b.position_secs));
+
+    for mark in children(node, "POSITION_MARK") {
+        let Some(start) = float_attr(mark, "Start") else { continue };
+        let slot = mark.attribute("Num")
+            .and_then(|n| n.parse::<i32>().ok())
+            .filter(|&n| n >= 0)
+            .map(|n| n.min(u8::MAX as i32) as u8);
+        let label = mark.attribute("Name").map(str::trim).filter(|n| !n.is_empty()).map(String::from);
+        let color = mark_colour(mark);
+
+        match (mark.attribute("Type"), float_attr(mark, "End")) {
+            (Some(MARK_TYPE_LOOP), Some(end)) => track.loops.push(ExternalLoop {
+                slot,
+                start_secs: start,
+                end_secs: end,
+                label,
+                color,
+            }),
+            _ => track.cues.push(ExternalCue {
+                slot,
+                position_secs: start,
+                label,
+                color,
+            }),
+        }
+    }
+
+    track
+}
+
+fn parse_node(node: Node, key_by_location: &HashMap<&str, String>) -> Option<ExternalNode> {
+    let name = node.attribute("Name")?.to_string();
+    match node.attribute("Type") {
+        Some("0") => Some(ExternalNode::Folder {
+            name,
+            children: children(node, "NODE")
+                .filter_map(|child| parse_node(child, key_by_location))
+                .collect(),
+        }),
+        Some("1") => {
+            let by_location = node.attribute("KeyType") == Some("1");
+            let tracks = children(node, "TRACK")
+                .filter_map(|entry| entry.attribute("Key"))
+                .filter_map(|key| {
+                    if by_location {
+                        key_by_location.get(key).cloned()
+                    } else {
+                        Some(key.to_string())
+                    }
+                })
+                .collect();
+            Some(ExternalNode::Playlist { name, tracks })
+        }
+        _ => None,
+    }
+}
+
+/// Convert a `file://localhost/...` URL to a path
+fn decode_location(location: &str) -> PathBuf {
+    let raw = location
+        .strip_prefix("file://localhost")
+        .or_else(|| location.strip_prefix("file://"))
+        .unwrap_or(location);
+    let decoded = percent_decode_str(raw).decode_utf8_lossy();
+
+    // Windows paths come out as "/C:/Music/..."
+    let bytes = decoded.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        return PathBuf::from(&decoded[1..]);
+    }
+    PathBuf::from(decoded.as_ref())
+}
+
+/// Parse a track colour ("0xRRGGBB")
+fn parse_hex_colour(value: &str) -> Option<String> {
+    let hex = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X"))?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(format!("#{:06X}", rgb & 0xFF_FFFF))
+}
+
+/// Hot cue colour from the Red / Green / Blue attributes
+fn mark_colour(mark: Node) -> Option<String> {
+    let component = |name| mark.attribute(name).and_then(|v| v.parse::<u8>().ok());
+    let (r, g, b) = (component("Red")?, component("Green")?, component("Blue")?);
+    Some(format!("#{:02X}{:02X}{:02X}", r, g, b))
+}
+
+fn text_attr(node: Node, name: &str) -> Option<String> {
+    node.attribute(name).map(str::trim).filter(|v| !v.is_empty()).map(String::from)
+}
+
+fn float_attr(node: Node, name: &str) -> Option<f64> {
+    node.attribute(name)?.trim().parse().ok()
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(name))
+}
+
+fn children<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children().filter(move |c| c.has_tag_name(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DJ_PLAYLISTS Version="1.0.0">
+  <PRODUCT Name="rekordbox" Version="6.8.5" Company="AlphaTheta"/>
+  <COLLECTION Entries="2">
+    <TRACK TrackID="11" Name="Title" Artist="Artist" Rating="204" Colour="0xFF007F"
+           Location="file://localhost/C:/Music/Artist%20-%20Title.mp3">
+      <TEMPO Inizio="0.500" Bpm="120.00" Metro="4/4" Battito="2"/>
+      <TEMPO Inizio="60.500" Bpm="124.00" Metro="4/4" Battito="1"/>
+      <POSITION_MARK Name="Drop" Type="0" Start="64.100" Num="0" Red="40" Green="226" Blue="20"/>
+      <POSITION_MARK Name="" Type="0" Start="12.000" Num="-1"/>
+      <POSITION_MARK Name="Roll" Type="4" Start="96.000" End="100.000" Num="3"/>
+    </TRACK>
+    <TRACK TrackID="12" Location="file://localhost/Users/dj/Music/Other.flac"/>
+  </COLLECTION>
+  <PLAYLISTS>
+    <NODE Type="0" Name="ROOT" Count="2">
+      <NODE Type="0" Name="Sets" Count="1">
+        <NODE Name="Friday" Type="1" KeyType="0" Entries="2">
+          <TRACK Key="12"/>
+          <TRACK Key="11"/>
+        </NODE>
+      </NODE>
+      <NODE Name="By Path" Type="1" KeyType="1" Entries="1">
+        <TRACK Key="file://localhost/Users/dj/Music/Other.flac"/>
+      </NODE>
+    </NODE>
+  </PLAYLISTS>
+</DJ_PLAYLISTS>"#;
+
+    #[test]
+    fn parses_tracks() {
+        let library = parse(LIBRARY).unwrap();
+        assert_eq!(library.tracks.len(), 2);
+
+        let track = &library.tracks[0];
+        assert_eq!(track.key, "11");
+        assert_eq!(track.location, PathBuf::from("C:/Music/Artist - Title.mp3"));
+        assert_eq!(track.title.as_deref(), Some("Title"));
+        assert_eq!(track.artist.as_deref(), Some("Artist"));
+        assert_eq!(track.rating, 4);
+        assert_eq!(track.color.as_deref(), Some("#FF007F"));
+        assert_eq!(track.grid.len(), 2);
+        assert_eq!(track.grid[0].beat_in_bar, 2);
+
+        assert_eq!(track.cues.len(), 2);
+        assert_eq!(track.cues[0].slot, Some(0));
+        assert_eq!(track.cues[0].label.as_deref(), Some("Drop"));
+        assert_eq!(track.cues[0].color.as_deref(), Some("#28E214"));
+        assert_eq!(track.cues[1].slot, None);
+
+        assert_eq!(track.loops.len(), 1);
+        assert_eq!(track.loops[0].slot, Some(3));
+        assert_eq!(track.loops[0].end_secs, 100.0);
+
+        assert_eq!(library.tracks[1].location, PathBuf::from("/Users/dj/Music/Other.flac"));
+    }
+
+    #[test]
+    fn parses_playlist_tree() {
+        let library = parse(LIBRARY).unwrap();
+        assert_eq!(
+            library.playlists,
+            vec![
+                ExternalNode::Folder {
+                    name: "Sets".to_string(),
+                    children: vec![ExternalNode::Playlist {
+                        name: "Friday".to_string(),
+                        tracks: vec!["12".to_string(), "11".to_string()],
+                    }],
+                },
+                ExternalNode::Playlist {
+                    name: "By Path".to_string(),
+                    tracks: vec!["12".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_xml() {
+        assert!(parse("<NML VERSION=\"19\"/>").is_err());
+        assert!(parse("not xml").is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! Traktor NML collection parser
+//!
+//! Reads Traktor's `collection.nml` (or an exported playlist `.nml`):
+//!
+//! ```text
+//! <NML VERSION="19">
+//!   <COLLECTION>
+//!     <ENTRY TITLE="Title" ARTIST="Artist">
+//!       <LOCATION DIR="/:Users/:dj/:Music/:" FILE="Artist - Title.mp3" VOLUME="Macintosh HD"/>
+//!       <INFO RANKING="204" COLOR="1"/>
+//!       <TEMPO BPM="128.000000"/>
+//!       <CUE_V2 NAME="AutoGrid" TYPE="4" START="25.3" LEN="0" HOTCUE="0"><GRID BPM="128.0"/></CUE_V2>
+//!       <CUE_V2 NAME="Drop" TYPE="0" START="64100.0" LEN="0" HOTCUE="1"/>
+//!       <CUE_V2 NAME="Roll" TYPE="5" START="96000.0" LEN="3750.0" HOTCUE="-1"/>
+//!     </ENTRY>
+//!   </COLLECTION>
+//!   <PLAYLISTS>
+//!     <NODE TYPE="FOLDER" NAME="$ROOT"><SUBNODES>
+//!       <NODE TYPE="PLAYLIST" NAME="Warmup"><PLAYLIST TYPE="LIST">
+//!         <ENTRY><PRIMARYKEY TYPE="TRACK" KEY="Macintosh HD/:Users/:dj/:Music/:Artist - Title.mp3"/></ENTRY>
+//!       </PLAYLIST></NODE>
+//!     </SUBNODES></NODE>
+//!   </PLAYLISTS>
+//! </NML>
+//! ```
+//!
+//! Cue positions are in milliseconds. `TYPE` 4 is a grid marker, 5 a loop;
+//! `HOTCUE` is the hot cue slot (-1 = not on a hot cue). Grid markers in
+//! Traktor are not hot cues in mesh, so they only feed the beat grid.
+//! Traktor does not store hot cue colours (they follow the cue type).
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use roxmltree::Node;
+
+use super::{stars_from_byte, ExternalCue, ExternalLibrary, ExternalLoop, ExternalNode, ExternalTrack, GridMarker};
+
+/// `CUE_V2` type of a beat grid marker
+const CUE_TYPE_GRID: &str = "4";
+
+/// `CUE_V2` type of a loop
+const CUE_TYPE_LOOP: &str = "5";
+
+/// Traktor's built-in playlists that don't hold user content
+const SYSTEM_PLAYLISTS: [&str; 2] = ["_LOOPS", "_RECORDINGS"];
+
+/// Traktor's seven track colours (INFO COLOR="1".."7")
+pub(crate) const TRACK_COLOURS: [&str; 7] = [
+    "#E0302E", // Red
+    "#F28C28", // Orange
+    "#F2D22E", // Yellow
+    "#3CB44B", // Green
+    "#2E7BE0", // Blue
+    "#8E44C9", // Violet
+    "#E040A0", // Magenta
+];
+
+/// Parse the contents of a Traktor NML collection
+pub fn parse(nml: &str) -> Result<ExternalLibrary> {
+    let doc = roxmltree::Document::parse(nml).context("Invalid Traktor NML")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("NML") {
+        bail!("Not a Traktor collection (root element <{}>)", root.tag_name().name());
+    }
+
+    let mut library = ExternalLibrary::default();
+
+    if let Some(collection) = child(root, "COLLECTION") {
+        library.tracks = children(collection, "ENTRY")
+            .filter_map(parse_entry)
+            .collect();
+    }
+
+    if let Some(root_node) = child(root, "PLAYLISTS").and_then(|p| child(p, "NODE")) {
+        library.playlists = subnodes(root_node).filter_map(parse_node).collect();
+    }
+
+    Ok(library)
+}
+
+fn parse_entry(entry: Node) -> Option<ExternalTrack> {
+    let location = child(entry, "LOCATION")?;
+    let volume = location.attribute("VOLUME").unwrap_or("");
+    let dir = location.attribute("DIR").unwrap_or("");
+    let file = location.attribute("FILE")?;
+
+    let info = child(entry, "INFO");
+    let mut track = ExternalTrack {
+        // Playlist PRIMARYKEYs are the volume followed by the Traktor-style path
+        key: format!("{}{}{}", volume, dir, file),
+        location: decode_location(volume, dir, file),
+        title: text_attr(entry, "TITLE"),
+        artist: text_attr(entry, "ARTIST"),
+        album: child(entry, "ALBUM").and_then(|a| text_attr(a, "TITLE")),
+        genre: info.and_then(|i| text_attr(i, "GENRE")),
+        bpm: child(entry, "TEMPO").and_then(|t| float_attr(t, "BPM")).filter(|&bpm| bpm > 0.0),
+        musical_key: info
+            .and_then(|i| i.attribute("KEY"))
+            .and_then(crate::metadata::hints::parse_tag_key)
+            .map(|k| k.to_string()),
+        duration_secs: info.and_then(|i| float_attr(i, "PLAYTIME")),
+        rating: info
+            .and_then(|i| i.attribute("RANKING"))
+            .and_then(|r| r.parse::<u32>().ok())
+            .map(stars_from_byte)
+            .unwrap_or(0),
+        color: info
+            .and_then(|i| i.attribute("COLOR"))
+            .and_then(|c| c.parse::<usize>().ok())
+            .and_then(|c| c.checked_sub(1))
+            .and_then(|c| TRACK_COLOURS.get(c))
+            .map(|c| c.to_string()),
+        ..Default::default()
+    };
+
+    let track_bpm = child(entry, "TEMPO").and_then(|t| float_attr(t, "BPM"));
+
+    for cue in children(entry, "CUE_V2") {
+        let Some(start_ms) = float_attr(cue, "START") else { continue };
+        let start_secs = start_ms / 1000.0;
+        let slot = cue.attribute("HOTCUE")
+            .and_then(|n| n.parse::<i32>().ok())
+            .filter(|&n| n >= 0)
+            .map(|n| n.min(u8::MAX as i32) as u8);
+        let label = cue.attribute("NAME")
+            .map(str::trim)
+            .filter(|n| !n.is_empty() && *n != "n.n." && *n != "AutoGrid")
+            .map(String::from);
+
+        match cue.attribute("TYPE") {
+            Some(CUE_TYPE_GRID) => {
+                let bpm = child(cue, "GRID").and_then(|g| float_attr(g, "BPM")).or(track_bpm);
+                if let Some(bpm) = bpm {
+                    track.grid.push(GridMarker { position_secs: start_secs, bpm, beat_in_bar: 1 });
+                }
+            }
+            Some(CUE_TYPE_LOOP) => {
+                let len_secs = float_attr(cue, "LEN").unwrap_or(0.0) / 1000.0;
+                track.loops.push(ExternalLoop {
+                    slot,
+                    start_secs,
+                    end_secs: start_secs + len_secs,
+                    label,
+                    color: None,
+                });
+            }
+            _ => track.cues.push(ExternalCue {
+                slot,
+                position_secs: start_secs,
+                label,
+                color: None,
+            }),
+        }
+    }
+    track.grid.sort_by(|a, b| a.position_secs.total_cmp(&b.position_secs));
+
+    Some(track)
+}
+
+fn parse_node(node: Node) -> Option<ExternalNode> {
+    let name = node.attribute("NAME")?.to_string();
+    match node.attribute("TYPE") {
+        Some("FOLDER") => Some(ExternalNode::Folder {
+            name,
+            children: subnodes(node).filter_map(parse_node).collect(),
+        }),
+        Some("PLAYLIST") if !SYSTEM_PLAYLISTS.contains(&name.as_str()) => {
+            let tracks = child(node, "PLAYLIST")
+                .into_iter()
+                .flat_map(|playlist| children(playlist, "ENTRY"))
+                .filter_map(|entry| child(entry, "PRIMARYKEY"))
+                .filter(|key| key.attribute("TYPE") == Some("TRACK"))
+                .filter_map(|key| key.attribute("KEY").map(String::from))
+                .collect();
+            Some(ExternalNode::Playlist { name, tracks })
+        }
+        _ => None,
+    }
+}
+
+/// Child nodes of a folder (wrapped in `<SUBNODES>`)
+fn subnodes<'a, 'input: 'a>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    child(node, "SUBNODES")
+        .into_iter()
+        .flat_map(|subnodes| children(subnodes, "NODE"))
+}
+
+/// Build a path from Traktor's LOCATION attributes
+///
+/// Directories are separated by "/:". Windows volumes are drive letters
+/// ("C:"); on macOS the volume is the disk name and the directory is
+/// already absolute from the disk root.
+fn decode_location(volume: &str, dir: &str, file: &str) -> PathBuf {
+    let dir = dir.replace("/:", "/");
+    if volume.len() == 2 && volume.ends_with(':') {
+        PathBuf::from(format!("{}{}{}", volume, dir, file))
+    } else {
+        PathBuf::from(format!("{}{}", dir, file))
+    }
+}
+
+fn text_attr(node: Node, name: &str) -> Option<String> {
+    node.attribute(name).map(str::trim).filter(|v| !v.is_empty()).map(String::from)
+}
+
+fn float_attr(node: Node, name: &str) -> Option<f64> {
+    node.attribute(name)?.trim().parse().ok()
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(name))
+}
+
+fn children<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children().filter(move |c| c.has_tag_name(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="19">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"></HEAD>
+  <COLLECTION ENTRIES="1">
+    <ENTRY MODIFIED_DATE="2024/5/1" TITLE="Title" ARTIST="Artist">
+      <LOCATION DIR="/:Users/:dj/:Music/:" FILE="Artist - Title.mp3" VOLUME="Macintosh HD" VOLUMEID="Macintosh HD"></LOCATION>
+      <INFO BITRATE="320000" RANKING="255" COLOR="5"></INFO>
+      <TEMPO BPM="128.000000" BPM_QUALITY="100.000000"></TEMPO>
+      <CUE_V2 NAME="AutoGrid" DISPL_ORDER="0" TYPE="4" START="250.0" LEN="0.000000" REPEATS="-1" HOTCUE="0">
+        <GRID BPM="128.000000"></GRID>
+      </CUE_V2>
+      <CUE_V2 NAME="Drop" DISPL_ORDER="0" TYPE="0" START="64100.0" LEN="0.000000" REPEATS="-1" HOTCUE="1"></CUE_V2>
+      <CUE_V2 NAME="n.n." DISPL_ORDER="0" TYPE="5" START="96000.0" LEN="3750.0" REPEATS="-1" HOTCUE="-1"></CUE_V2>
+    </ENTRY>
+  </COLLECTION>
+  <PLAYLISTS>
+    <NODE TYPE="FOLDER" NAME="$ROOT">
+      <SUBNODES COUNT="2">
+        <NODE TYPE="PLAYLIST" NAME="_RECORDINGS">
+          <PLAYLIST ENTRIES="0" TYPE="LIST" UUID="a"></PLAYLIST>
+        </NODE>
+        <NODE TYPE="FOLDER" NAME="Sets">
+          <SUBNODES COUNT="1">
+            <NODE TYPE="PLAYLIST" NAME="Friday">
+              <PLAYLIST ENTRIES="1" TYPE="LIST" UUID="b">
+                <ENTRY><PRIMARYKEY TYPE="TRACK" KEY="Macintosh HD/:Users/:dj/:Music/:Artist - Title.mp3"></PRIMARYKEY></ENTRY>
+              </PLAYLIST>
+            </NODE>
+          </SUBNODES>
+        </NODE>
+      </SUBNODES>
+    </NODE>
+  </PLAYLISTS>
+</NML>"#;
+
+    #[test]
+    fn parses_entries() {
+        let library = parse(COLLECTION).unwrap();
+        assert_eq!(library.tracks.len(), 1);
+
+        let track = &library.tracks[0];
+        assert_eq!(track.location, PathBuf::from("/Users/dj/Music/Artist - Title.mp3"));
+        assert_eq!(track.rating, 5);
+        assert_eq!(track.color.as_deref(), Some("#2E7BE0"));
+        assert_eq!(track.grid, vec![GridMarker { position_secs: 0.25, bpm: 128.0, beat_in_bar: 1 }]);
+
+        // The grid marker on hot cue 1 is not a cue
+        assert_eq!(track.cues.len(), 1);
+        assert_eq!(track.cues[0].slot, Some(1));
+        assert_eq!(track.cues[0].position_secs, 64.1);
+        assert_eq!(track.cues[0].label.as_deref(), Some("Drop"));
+
+        assert_eq!(track.loops.len(), 1);
+        assert_eq!(track.loops[0].slot, None);
+        assert_eq!(track.loops[0].end_secs, 99.75);
+        assert_eq!(track.loops[0].label, None);
+    }
+
+    #[test]
+    fn parses_playlists_and_skips_system_lists() {
+        let library = parse(COLLECTION).unwrap();
+        assert_eq!(
+            library.playlists,
+            vec![ExternalNode::Folder {
+                name: "Sets".to_string(),
+                children: vec![ExternalNode::Playlist {
+                    name: "Friday".to_string(),
+                    tracks: vec![library.tracks[0].key.clone()],
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn windows_locations_keep_the_drive() {
+        assert_eq!(
+            decode_location("C:", "/:Music/:House/:", "a.mp3"),
+            PathBuf::from("C:/Music/House/a.mp3")
+        );
+    }
+}
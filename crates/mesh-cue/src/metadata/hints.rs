@@ -0,0 +1,85 @@
+//! Tag BPM / key as analysis hints
+//!
+//! DJ software writes its own BPM and key into the file tags. Those values
+//! are usually right about the things beat and key detection get wrong:
+//!
+//! - **BPM**: detectors often lock onto half or double tempo (87 vs 174).
+//!   If the detected tempo is within tolerance of 2× or ½× the tag BPM, it is
+//!   folded into the tag's octave. The phase of the grid is kept.
+//! - **Key**: the commonest detection error is picking the relative
+//!   major/minor (Am vs C — same notes). If the tag key is the relative of
+//!   the detected key, the tag's mode wins.
+//!
+//! In override mode the tag values replace the analysis results outright.
+
+use mesh_core::music::MusicalKey;
+
+use crate::config::TagHints;
+
+/// Relative tolerance when comparing a detected tempo with a tag tempo
+const BPM_TOLERANCE: f64 = 0.03;
+
+/// Parse a tag key in musical ("Am", "F# minor") or Camelot ("8A") notation
+pub fn parse_tag_key(s: &str) -> Option<MusicalKey> {
+    MusicalKey::parse_camelot(s).or_else(|| MusicalKey::parse(s))
+}
+
+/// Combine the analysed BPM with the tag BPM
+pub fn resolve_bpm(detected: f64, tag_bpm: Option<f64>, mode: TagHints) -> f64 {
+    let Some(tag_bpm) = tag_bpm else {
+        return detected;
+    };
+    match mode {
+        TagHints::Ignore => detected,
+        TagHints::Override => tag_bpm,
+        TagHints::Hint => {
+            for factor in [2.0, 0.5] {
+                let folded = detected * factor;
+                if (folded - tag_bpm).abs() <= tag_bpm * BPM_TOLERANCE {
+                    return folded;
+                }
+            }
+            detected
+        }
+    }
+}
+
+/// Combine the analysed key with the tag key (result in analysis notation)
+pub fn resolve_key(detected: &str, tag_key: Option<&str>, mode: TagHints) -> String {
+    let Some(tagged) = tag_key.and_then(parse_tag_key) else {
+        return detected.to_string();
+    };
+    match mode {
+        TagHints::Ignore => detected.to_string(),
+        TagHints::Override => tagged.to_string(),
+        TagHints::Hint => match MusicalKey::parse(detected) {
+            Some(analysed) if analysed.relative() == tagged => tagged.to_string(),
+            _ => detected.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hint_folds_half_and_double_tempo() {
+        assert_eq!(resolve_bpm(87.0, Some(174.0), TagHints::Hint), 174.0);
+        assert_eq!(resolve_bpm(256.0, Some(128.0), TagHints::Hint), 128.0);
+        // Unrelated tag tempo leaves the detection alone
+        assert_eq!(resolve_bpm(140.0, Some(128.0), TagHints::Hint), 140.0);
+        assert_eq!(resolve_bpm(87.0, None, TagHints::Override), 87.0);
+        assert_eq!(resolve_bpm(87.0, Some(174.0), TagHints::Ignore), 87.0);
+        assert_eq!(resolve_bpm(87.0, Some(90.0), TagHints::Override), 90.0);
+    }
+
+    #[test]
+    fn hint_fixes_relative_mode_only() {
+        assert_eq!(resolve_key("C", Some("Am"), TagHints::Hint), "Am");
+        assert_eq!(resolve_key("C", Some("8A"), TagHints::Hint), "Am");
+        assert_eq!(resolve_key("C", Some("Dm"), TagHints::Hint), "C");
+        assert_eq!(resolve_key("C", Some("Dm"), TagHints::Override), "Dm");
+        assert_eq!(resolve_key("C", Some("??"), TagHints::Override), "C");
+    }
+}
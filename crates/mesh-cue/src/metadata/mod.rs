@@ -5,18 +5,31 @@
 //! 2. Filename pattern parsing (with known-artist disambiguation)
 //!
 //! Priority: embedded tags > filename parsing > raw base_name
+//!
+//! The remaining tag fields (album, genre, year, label, comment, cover art)
+//! and Serato hot cues are stored alongside the track by
+//! [`store_embedded_metadata`]; tag BPM/key feed the analysis via [`hints`].
 
 pub mod filename;
+pub mod hints;
+pub mod serato;
 pub mod tags;
 
 use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
 
-use mesh_core::db::DatabaseService;
+use mesh_core::db::{CuePoint, DatabaseService, TagMetadata};
+use mesh_core::types::SAMPLE_RATE;
 
 use filename::parse_filename;
-use tags::read_embedded_tags;
+use tags::EmbeddedTags;
+
+/// Collection subfolder holding extracted cover art
+pub const ARTWORK_DIR: &str = "artwork";
+
+/// Number of hot cue slots (Serato cues beyond this are dropped)
+const MAX_HOT_CUES: u8 = 8;
 
 /// Resolved metadata ready for track creation
 #[derive(Debug, Clone)]
@@ -29,7 +42,7 @@ pub struct ResolvedMetadata {
 
 /// Resolve metadata for a track from tags and/or filename.
 ///
-/// `source_path` is the original audio file for tag reading (None for pre-separated stems).
+/// `embedded` are the tags of the original audio file (None for pre-separated stems).
 /// `base_name` is the filename-derived display name (without extension).
 /// `known_artists` is a lowercase-normalized set for disambiguation.
 ///
@@ -37,25 +50,20 @@ pub struct ResolvedMetadata {
 /// Normalization (artist connectors → commas, brackets → parens, Original Mix stripped)
 /// is applied to ALL sources — tags from Beatport etc. have the same quirks.
 pub fn resolve_metadata(
-    source_path: Option<&Path>,
+    embedded: Option<&EmbeddedTags>,
     base_name: &str,
     known_artists: &HashSet<String>,
 ) -> ResolvedMetadata {
-    // Try embedded tags first
-    let embedded = source_path.and_then(read_embedded_tags);
-
     // Always parse filename as fallback
     let parsed = parse_filename(base_name, known_artists);
 
     // Resolve artist: tags > filename
     let raw_artist = embedded
-        .as_ref()
         .and_then(|t| t.artist.clone())
         .or(parsed.artist);
 
     // Resolve title: tags > filename parsed title > base_name
     let raw_title = embedded
-        .as_ref()
         .and_then(|t| t.title.clone())
         .unwrap_or(parsed.title);
 
@@ -66,6 +74,62 @@ pub fn resolve_metadata(
     ResolvedMetadata { title, artist }
 }
 
+/// Store the embedded tag fields of a freshly imported track.
+///
+/// Writes the cover art to `artwork/<file_stem>.<ext>` in the collection,
+/// stores the `tag_metadata` row and imports Serato hot cues (positions are
+/// milliseconds, converted to samples at the collection rate). Failures are
+/// logged — a track without artwork or cues is still a valid import.
+pub fn store_embedded_metadata(
+    db: &DatabaseService,
+    collection_root: &Path,
+    track_id: i64,
+    file_stem: &str,
+    tags: &EmbeddedTags,
+) {
+    let artwork_path = tags.artwork.as_ref().and_then(|artwork| {
+        let relative = format!("{}/{}.{}", ARTWORK_DIR, file_stem, artwork.extension);
+        let absolute = collection_root.join(&relative);
+        let written = std::fs::create_dir_all(collection_root.join(ARTWORK_DIR))
+            .and_then(|_| std::fs::write(&absolute, &artwork.data));
+        match written {
+            Ok(()) => Some(relative),
+            Err(e) => {
+                log::warn!("metadata: Failed to write artwork {:?}: {}", absolute, e);
+                None
+            }
+        }
+    });
+
+    let meta = TagMetadata {
+        track_id,
+        album: tags.album.clone(),
+        genre: tags.genre.clone(),
+        year: tags.year,
+        label: tags.label.clone(),
+        comment: tags.comment.clone(),
+        artwork_path,
+        tag_bpm: tags.bpm,
+        tag_key: tags.key.clone(),
+    };
+    if let Err(e) = db.store_tag_metadata(&meta) {
+        log::warn!("metadata: Failed to store tag metadata for track {}: {}", track_id, e);
+    }
+
+    for cue in tags.cues.iter().filter(|c| c.index < MAX_HOT_CUES) {
+        let cue_point = CuePoint {
+            track_id,
+            index: cue.index,
+            sample_position: cue.position_ms as i64 * SAMPLE_RATE as i64 / 1000,
+            label: cue.label.clone(),
+            color: cue.color.clone(),
+        };
+        if let Err(e) = db.save_cue_point(&cue_point) {
+            log::warn!("metadata: Failed to import Serato cue {} for track {}: {}", cue.index, track_id, e);
+        }
+    }
+}
+
 /// Load the set of known artist names from the database.
 ///
 /// Returns a lowercase-normalized HashSet for case-insensitive matching
@@ -0,0 +1,321 @@
+//! Serato DJ cue point parsing ("Serato Markers2")
+//!
+//! Serato stores hot cues in a binary blob that is embedded differently per
+//! container:
+//!
+//! - **MP3**: ID3v2 `GEOB` frame with description `Serato Markers2`
+//! - **FLAC / Ogg**: Vorbis comment `SERATO_MARKERS_V2`, base64 of the same
+//!   GEOB payload (`application/octet-stream\0\0Serato Markers2\0` + data)
+//!
+//! The payload itself is `01 01` followed by base64 text (newline-wrapped,
+//! often without padding) that decodes to `01 01` and a sequence of entries:
+//! NUL-terminated type name, big-endian u32 length, entry data. Only `CUE`
+//! entries are read — loops and the track color are ignored.
+//!
+//! Lofty does not expose GEOB frames in its generic tag API, so the ID3v2
+//! tag at the start of an MP3 is scanned directly.
+
+use std::io::Read;
+use std::path::Path;
+
+/// GEOB description Serato uses for its marker blob
+const MARKERS2_DESCRIPTION: &[u8] = b"Serato Markers2";
+
+/// Largest ID3v2 tag read while scanning for GEOB frames (cover art
+/// makes tags large, but anything beyond this is not a sane tag)
+const MAX_ID3_TAG_BYTES: u32 = 32 * 1024 * 1024;
+
+/// A hot cue stored by Serato DJ
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeratoCue {
+    /// Hot cue slot (0-based)
+    pub index: u8,
+    /// Position in milliseconds from the start of the file
+    pub position_ms: u32,
+    /// Cue color as "#RRGGBB"
+    pub color: Option<String>,
+    /// Cue name (None if empty)
+    pub label: Option<String>,
+}
+
+/// Read Serato hot cues from the ID3v2 tag of an MP3 file
+///
+/// Returns an empty list if the file has no ID3v2 tag or no Serato markers.
+pub fn read_id3_cues(path: &Path) -> Vec<SeratoCue> {
+    read_id3_geob(path, MARKERS2_DESCRIPTION)
+        .map(|data| parse_markers2(&data))
+        .unwrap_or_default()
+}
+
+/// Parse a `SERATO_MARKERS_V2` Vorbis comment value (FLAC / Ogg)
+pub fn parse_vorbis_markers2(value: &str) -> Vec<SeratoCue> {
+    let Some(geob) = decode_base64(value) else {
+        return Vec::new();
+    };
+    // Skip the GEOB header: mime type, empty filename, description
+    let Some(data) = skip_terminated(&geob, 3) else {
+        return Vec::new();
+    };
+    parse_markers2(data)
+}
+
+/// Parse a `Serato Markers2` GEOB payload into hot cues
+pub fn parse_markers2(data: &[u8]) -> Vec<SeratoCue> {
+    let Some(text) = data.strip_prefix(&[0x01, 0x01]) else {
+        return Vec::new();
+    };
+    let Some(decoded) = decode_base64_bytes(text) else {
+        return Vec::new();
+    };
+    let Some(mut entries) = decoded.strip_prefix(&[0x01, 0x01]) else {
+        return Vec::new();
+    };
+
+    let mut cues = Vec::new();
+    loop {
+        let Some(name_end) = entries.iter().position(|&b| b == 0) else { break };
+        if name_end == 0 {
+            break; // Empty name terminates the entry list
+        }
+        let name = &entries[..name_end];
+        let rest = &entries[name_end + 1..];
+        if rest.len() < 4 {
+            break;
+        }
+        let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let Some(entry) = rest.get(4..4 + len) else { break };
+
+        if name == b"CUE" {
+            if let Some(cue) = parse_cue_entry(entry) {
+                cues.push(cue);
+            }
+        }
+        entries = &rest[4 + len..];
+    }
+    cues
+}
+
+/// Parse one `CUE` entry:
+/// `00 | index u8 | position u32 BE (ms) | 00 | RGB | 00 00 | name\0`
+fn parse_cue_entry(entry: &[u8]) -> Option<SeratoCue> {
+    if entry.len() < 12 {
+        return None;
+    }
+    let index = entry[1];
+    let position_ms = u32::from_be_bytes([entry[2], entry[3], entry[4], entry[5]]);
+    let color = format!("#{:02X}{:02X}{:02X}", entry[7], entry[8], entry[9]);
+    let name_bytes = &entry[12..];
+    let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+    let label = String::from_utf8_lossy(&name_bytes[..name_end]).trim().to_string();
+
+    Some(SeratoCue {
+        index,
+        position_ms,
+        color: Some(color),
+        label: if label.is_empty() { None } else { Some(label) },
+    })
+}
+
+/// Find a GEOB frame with the given description in a file's ID3v2 tag
+fn read_id3_geob(path: &Path, description: &[u8]) -> Option<Vec<u8>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut header = [0u8; 10];
+    file.read_exact(&mut header).ok()?;
+    if &header[0..3] != b"ID3" {
+        return None;
+    }
+    let major = header[3];
+    if !(3..=4).contains(&major) {
+        return None; // ID3v2.2 uses 3-char frame IDs and predates Serato
+    }
+    let flags = header[5];
+    let tag_size = syncsafe(&header[6..10]);
+    if tag_size > MAX_ID3_TAG_BYTES {
+        return None;
+    }
+
+    let mut tag = vec![0u8; tag_size as usize];
+    file.read_exact(&mut tag).ok()?;
+
+    let mut pos = 0usize;
+    if flags & 0x40 != 0 {
+        // Extended header: v2.3 size excludes itself, v2.4 size includes itself
+        let size_bytes = tag.get(0..4)?;
+        pos = if major == 4 {
+            syncsafe(size_bytes) as usize
+        } else {
+            u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]) as usize + 4
+        };
+    }
+
+    while pos + 10 <= tag.len() {
+        let id = &tag[pos..pos + 4];
+        if id[0] == 0 {
+            break; // Padding
+        }
+        let size_bytes = &tag[pos + 4..pos + 8];
+        let size = if major == 4 {
+            syncsafe(size_bytes) as usize
+        } else {
+            u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]) as usize
+        };
+        let body = tag.get(pos + 10..pos + 10 + size)?;
+
+        if id == b"GEOB" {
+            if let Some((desc, data)) = split_geob(body) {
+                if desc == description {
+                    return Some(data.to_vec());
+                }
+            }
+        }
+        pos += 10 + size;
+    }
+    None
+}
+
+/// Split a GEOB frame body into (description, data)
+fn split_geob(body: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (&encoding, rest) = body.split_first()?;
+    let wide = matches!(encoding, 1 | 2);
+    // MIME type is always Latin-1
+    let mime_end = rest.iter().position(|&b| b == 0)?;
+    let rest = &rest[mime_end + 1..];
+    let (_filename, rest) = split_encoded(rest, wide)?;
+    let (desc, data) = split_encoded(rest, wide)?;
+    Some((desc, data))
+}
+
+/// Split at the first (single- or double-byte) NUL terminator
+fn split_encoded(data: &[u8], wide: bool) -> Option<(&[u8], &[u8])> {
+    if wide {
+        let end = data.chunks_exact(2).position(|c| c == [0, 0])? * 2;
+        Some((&data[..end], &data[end + 2..]))
+    } else {
+        let end = data.iter().position(|&b| b == 0)?;
+        Some((&data[..end], &data[end + 1..]))
+    }
+}
+
+/// Skip `count` NUL-terminated strings
+fn skip_terminated(mut data: &[u8], count: usize) -> Option<&[u8]> {
+    for _ in 0..count {
+        data = split_encoded(data, false)?.1;
+    }
+    Some(data)
+}
+
+/// Decode a 28-bit syncsafe integer
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 7) | (b & 0x7F) as u32)
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    decode_base64_bytes(text.as_bytes())
+}
+
+/// Lenient standard-alphabet base64 decoder
+///
+/// Serato wraps lines, omits padding and NUL-pads the payload, so whitespace
+/// is skipped and decoding stops at the first `=` or NUL.
+fn decode_base64_bytes(text: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    for &c in text {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' | 0 => break,
+            b'\n' | b'\r' | b' ' | b'\t' => continue,
+            _ => return None,
+        };
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_base64(data: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut out = String::new();
+        for chunk in data.chunks(3) {
+            let n = chunk.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32) << (8 * (3 - chunk.len()));
+            for i in 0..=chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            }
+        }
+        out
+    }
+
+    fn markers2_payload() -> Vec<u8> {
+        let mut cue = vec![0x00, 0x02];
+        cue.extend_from_slice(&1500u32.to_be_bytes());
+        cue.extend_from_slice(&[0x00, 0xCC, 0x00, 0x00, 0x00, 0x00]);
+        cue.extend_from_slice(b"Drop\0");
+
+        let mut decoded = vec![0x01, 0x01];
+        decoded.extend_from_slice(b"COLOR\0");
+        decoded.extend_from_slice(&4u32.to_be_bytes());
+        decoded.extend_from_slice(&[0x00, 0xFF, 0xFF, 0xFF]);
+        decoded.extend_from_slice(b"CUE\0");
+        decoded.extend_from_slice(&(cue.len() as u32).to_be_bytes());
+        decoded.extend_from_slice(&cue);
+        decoded.push(0x00);
+
+        let mut payload = vec![0x01, 0x01];
+        payload.extend_from_slice(encode_base64(&decoded).as_bytes());
+        payload.extend_from_slice(&[0, 0, 0]);
+        payload
+    }
+
+    #[test]
+    fn base64_round_trip_without_padding() {
+        let data = b"mesh cue import";
+        let mut encoded = encode_base64(data);
+        encoded.insert(8, '\n');
+        assert_eq!(decode_base64(&encoded).unwrap(), data);
+        assert!(decode_base64("not*base64").is_none());
+    }
+
+    #[test]
+    fn parses_cue_entries() {
+        let cues = parse_markers2(&markers2_payload());
+        assert_eq!(
+            cues,
+            vec![SeratoCue {
+                index: 2,
+                position_ms: 1500,
+                color: Some("#CC0000".to_string()),
+                label: Some("Drop".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_vorbis_comment() {
+        let mut geob = b"application/octet-stream\0\0Serato Markers2\0".to_vec();
+        geob.extend_from_slice(&markers2_payload());
+        let cues = parse_vorbis_markers2(&encode_base64(&geob));
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].position_ms, 1500);
+    }
+
+    #[test]
+    fn garbage_yields_no_cues() {
+        assert!(parse_markers2(b"").is_empty());
+        assert!(parse_markers2(&[0x01, 0x01, b'!']).is_empty());
+        assert!(read_id3_cues(Path::new("/nonexistent/file.mp3")).is_empty());
+    }
+}
@@ -2,22 +2,73 @@
 //!
 //! Reads ID3v2, Vorbis Comments, MP4 (iTunes), FLAC, and WAV RIFF tags
 //! in a format-agnostic way. Safe to call from rayon threads.
+//!
+//! Besides the descriptive fields (title, artist, album, genre, year, label,
+//! comment) this extracts the front cover, the BPM/key written by other DJ
+//! software, and Serato hot cues (see [`super::serato`]).
 
 use std::path::Path;
 
 use lofty::file::TaggedFileExt;
-use lofty::tag::Accessor;
+use lofty::picture::{MimeType, PictureType};
+use lofty::tag::{Accessor, ItemKey, Tag};
+
+use super::serato::{self, SeratoCue};
+
+/// Vorbis comment key Serato uses for its marker blob in FLAC / Ogg files
+const SERATO_MARKERS2_KEY: &str = "SERATO_MARKERS_V2";
+
+/// Embedded cover art
+#[derive(Debug, Clone)]
+pub struct EmbeddedArtwork {
+    /// Raw image bytes
+    pub data: Vec<u8>,
+    /// File extension matching the image format ("jpg", "png", ...)
+    pub extension: &'static str,
+}
 
 /// Metadata extracted from embedded audio tags
+#[derive(Debug, Clone, Default)]
 pub struct EmbeddedTags {
     pub title: Option<String>,
     pub artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub year: Option<i32>,
+    /// Record label (falls back to the publisher field)
+    pub label: Option<String>,
+    pub comment: Option<String>,
+    /// BPM written by other software (TBPM / BPM / tmpo)
+    pub bpm: Option<f64>,
+    /// Key as written in the tag (TKEY / INITIALKEY), unnormalized
+    pub key: Option<String>,
+    /// Front cover (or the first picture if no front cover is tagged)
+    pub artwork: Option<EmbeddedArtwork>,
+    /// Hot cues stored by Serato DJ
+    pub cues: Vec<SeratoCue>,
+}
+
+impl EmbeddedTags {
+    /// Whether no field carries any information
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.artist.is_none()
+            && self.album.is_none()
+            && self.genre.is_none()
+            && self.year.is_none()
+            && self.label.is_none()
+            && self.comment.is_none()
+            && self.bpm.is_none()
+            && self.key.is_none()
+            && self.artwork.is_none()
+            && self.cues.is_empty()
+    }
 }
 
 /// Read embedded tags from an audio file.
 ///
 /// Returns `None` if the file can't be read or contains no useful metadata.
-/// "Useful" means at least one of title/artist is a non-empty string.
+/// "Useful" means at least one field is populated.
 pub fn read_embedded_tags(path: &Path) -> Option<EmbeddedTags> {
     let tagged_file = lofty::read_from_path(path).ok()?;
 
@@ -26,15 +77,90 @@ pub fn read_embedded_tags(path: &Path) -> Option<EmbeddedTags> {
         .primary_tag()
         .or_else(|| tagged_file.first_tag())?;
 
-    let title = tag.title().and_then(|s| non_empty(s.to_string()));
-    let artist = tag.artist().and_then(|s| non_empty(s.to_string()));
+    let mut tags = EmbeddedTags {
+        title: tag.title().and_then(|s| non_empty(s.to_string())),
+        artist: tag.artist().and_then(|s| non_empty(s.to_string())),
+        album: tag.album().and_then(|s| non_empty(s.to_string())),
+        genre: tag.genre().and_then(|s| non_empty(s.to_string())),
+        year: read_year(tag),
+        label: get_string(tag, &ItemKey::Label).or_else(|| get_string(tag, &ItemKey::Publisher)),
+        comment: tag.comment().and_then(|s| non_empty(s.to_string())),
+        bpm: get_string(tag, &ItemKey::Bpm)
+            .or_else(|| get_string(tag, &ItemKey::IntegerBpm))
+            .and_then(|s| parse_bpm(&s)),
+        key: get_string(tag, &ItemKey::InitialKey),
+        artwork: read_artwork(tag),
+        cues: Vec::new(),
+    };
 
-    // Only return if at least one field is populated
-    if title.is_none() && artist.is_none() {
+    // Serato cues: GEOB frame in MP3, base64 Vorbis comment in FLAC / Ogg
+    tags.cues = match get_string(tag, &ItemKey::Unknown(SERATO_MARKERS2_KEY.to_string())) {
+        Some(value) => serato::parse_vorbis_markers2(&value),
+        None if has_extension(path, "mp3") => serato::read_id3_cues(path),
+        None => Vec::new(),
+    };
+
+    if tags.is_empty() {
         return None;
     }
 
-    Some(EmbeddedTags { title, artist })
+    Some(tags)
+}
+
+/// Get a trimmed, non-empty string item
+fn get_string(tag: &Tag, key: &ItemKey) -> Option<String> {
+    tag.get_string(key).and_then(|s| non_empty(s.to_string()))
+}
+
+/// Read the release year from the year / date fields ("2019", "2019-05-17")
+fn read_year(tag: &Tag) -> Option<i32> {
+    [ItemKey::Year, ItemKey::RecordingDate, ItemKey::OriginalReleaseDate]
+        .iter()
+        .find_map(|key| tag.get_string(key).and_then(parse_year))
+}
+
+/// Parse the leading four-digit year of a date string
+fn parse_year(s: &str) -> Option<i32> {
+    let digits = s.trim().get(..4)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&y| y > 0)
+}
+
+/// Parse a tag BPM, rejecting values no analysis range could produce
+fn parse_bpm(s: &str) -> Option<f64> {
+    let bpm: f64 = s.trim().replace(',', ".").parse().ok()?;
+    (20.0..=400.0).contains(&bpm).then_some(bpm)
+}
+
+/// Pick the front cover, falling back to the first embedded picture
+fn read_artwork(tag: &Tag) -> Option<EmbeddedArtwork> {
+    let pictures = tag.pictures();
+    let picture = pictures
+        .iter()
+        .find(|p| p.pic_type() == PictureType::CoverFront)
+        .or_else(|| pictures.first())?;
+    if picture.data().is_empty() {
+        return None;
+    }
+    let extension = match picture.mime_type() {
+        Some(MimeType::Png) => "png",
+        Some(MimeType::Gif) => "gif",
+        Some(MimeType::Bmp) => "bmp",
+        Some(MimeType::Tiff) => "tiff",
+        _ => "jpg",
+    };
+    Some(EmbeddedArtwork {
+        data: picture.data().to_vec(),
+        extension,
+    })
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
 }
 
 /// Filter empty/whitespace-only strings to None
@@ -63,4 +189,20 @@ mod tests {
     fn read_nonexistent_file_returns_none() {
         assert!(read_embedded_tags(Path::new("/nonexistent/file.mp3")).is_none());
     }
+
+    #[test]
+    fn parse_year_accepts_dates() {
+        assert_eq!(parse_year("2019"), Some(2019));
+        assert_eq!(parse_year("2019-05-17"), Some(2019));
+        assert_eq!(parse_year("19"), None);
+        assert_eq!(parse_year("May 2019"), None);
+    }
+
+    #[test]
+    fn parse_bpm_rejects_garbage() {
+        assert_eq!(parse_bpm("174"), Some(174.0));
+        assert_eq!(parse_bpm("127,5"), Some(127.5));
+        assert_eq!(parse_bpm("0"), None);
+        assert_eq!(parse_bpm("fast"), None);
+    }
 }
@@ -5,7 +5,7 @@
 //! preserving existing cue points, loops, and other data.
 
 use crate::analysis::{
-    analyze_partial_in_subprocess, AnalysisType, MetadataOptions,
+    analyze_partial_in_subprocess, AnalysisType, AutoCueConfig, BarProfile, MetadataOptions,
     PartialAnalysisResult, ReanalysisProgress, SubprocessTask,
 };
 use crate::config::{BpmConfig, BpmSource};
@@ -113,6 +113,24 @@ pub fn reanalyze_track(
             if let Err(e) = db_service.update_track_field(track_id, "first_beat_sample", &first_beat.to_string()) {
                 log::error!("Failed to update first_beat_sample in database: {:?}", e);
             }
+            // A freshly detected grid is constant-tempo; drop any hand-placed tempo markers
+            if let Err(e) = db_service.delete_tempo_anchors(track_id) {
+                log::error!("Failed to clear tempo anchors in database: {:?}", e);
+            }
+        }
+
+        // Phrase sections follow the new grid
+        if let Some(grid) = result.beat_grid.as_ref() {
+            let duration_samples = (stems.len() as u64 * SAMPLE_RATE as u64) / ESSENTIA_RATE as u64;
+            let phrases = crate::analysis::detect_phrases(&stems, grid, ESSENTIA_RATE, duration_samples);
+            let sections: Vec<_> = phrases
+                .iter()
+                .enumerate()
+                .map(|(i, p)| mesh_core::db::PhraseSection::from_runtime(track_id, i as u32, p))
+                .collect();
+            if let Err(e) = db_service.set_phrase_sections(track_id, &sections) {
+                log::error!("Failed to update phrase sections in database: {:?}", e);
+            }
         }
 
         log::info!("reanalyze_track: Updated database for {:?}", path);
@@ -294,18 +312,20 @@ pub fn run_batch_reanalysis(
 /// 1. Name/Artist: look up original_name from DB, re-parse with metadata module
 /// 2. Essentia subprocess (LUFS and/or Key): read audio at 44100Hz, run analysis
 /// 3. ML features (Tags): compute mel spectrogram, run EffNet, auto-tag
+/// 4. Auto cues: apply the auto-cue template to the empty cue/loop slots
 /// `ml_model_dir`: path to the directory containing the MAEST ONNX.
 /// If `Some`, ML tags are run via a per-worker (thread-local) analyzer
 /// to avoid the global Mutex bottleneck. `None` skips ML tags entirely.
 fn reanalyze_metadata_track(
     path: &Path,
     options: &MetadataOptions,
+    auto_cues: &AutoCueConfig,
     db: &Arc<DatabaseService>,
     known_artists: &HashSet<String>,
     ml_model_dir: Option<&Path>,
 ) -> Result<()> {
-    log::info!("reanalyze_metadata_track: {:?} (name={}, loudness={}, key={}, tags={})",
-        path, options.name_artist, options.loudness, options.key, options.tags);
+    log::info!("reanalyze_metadata_track: {:?} (name={}, loudness={}, key={}, tags={}, cues={})",
+        path, options.name_artist, options.loudness, options.key, options.tags, options.cues);
 
     // Look up track by path to get track_id and original_name
     let path_str = path.to_string_lossy();
@@ -349,6 +369,7 @@ fn reanalyze_metadata_track(
             loudness: options.loudness,
             key: options.key,
             tags: false,
+            cues: false,
         };
 
         let result = analyze_partial_in_subprocess(
@@ -371,6 +392,16 @@ fn reanalyze_metadata_track(
                 log::error!("reanalyze_metadata_track: Failed to update key: {:?}", e);
             }
         }
+        if let Some(ref timeline) = result.key_timeline {
+            let segments: Vec<_> = timeline
+                .iter()
+                .enumerate()
+                .map(|(i, s)| mesh_core::db::KeySegment::from_runtime(track_id, i as u32, s))
+                .collect();
+            if let Err(e) = db.set_key_segments(track_id, &segments) {
+                log::error!("reanalyze_metadata_track: Failed to update key timeline: {:?}", e);
+            }
+        }
     }
 
     // Step 3: ML features (Tags) — per-worker analyzer, no shared lock
@@ -490,6 +521,38 @@ fn reanalyze_metadata_track(
         // projected at analysis time. No DSP intensity extraction here.
     }
 
+    // Step 4: Auto cues from the stored beat grid and song structure
+    if options.cues {
+        let reader = AudioFileReader::open(path)
+            .with_context(|| format!("Failed to open file: {:?}", path))?;
+        let file_sample_rate = reader.format().sample_rate;
+        let stems = reader
+            .read_all_stems()
+            .with_context(|| format!("Failed to read stems from: {:?}", path))?;
+        let duration_samples = (stems.len() as u64 * SAMPLE_RATE as u64) / file_sample_rate as u64;
+
+        let metadata = mesh_core::audio_file::TrackMetadata::from(track);
+        let profile = BarProfile::from_stems(&stems, &metadata.beat_grid.beats, file_sample_rate, duration_samples);
+        drop(stems);
+        // Tracks imported before song-structure analysis have no sections yet;
+        // store the ones detected here so they are not recomputed next time
+        let phrases = if metadata.phrases.is_empty() {
+            let phrases = profile.sections();
+            let sections: Vec<_> = phrases
+                .iter()
+                .enumerate()
+                .map(|(i, p)| mesh_core::db::PhraseSection::from_runtime(track_id, i as u32, p))
+                .collect();
+            if let Err(e) = db.set_phrase_sections(track_id, &sections) {
+                log::warn!("reanalyze_metadata_track: Failed to store phrase sections: {:?}", e);
+            }
+            phrases
+        } else {
+            metadata.phrases
+        };
+        crate::batch_import::place_auto_cues(db, track_id, auto_cues, &profile, &phrases);
+    }
+
     log::info!("reanalyze_metadata_track: Complete for {:?}", path);
     Ok(())
 }
@@ -502,12 +565,14 @@ fn reanalyze_metadata_track(
 /// # Arguments
 /// * `tracks` - List of track file paths to re-analyze
 /// * `options` - Which metadata sub-analyses to run
+/// * `auto_cues` - Template for the auto cues option
 /// * `progress_tx` - Channel to send progress updates
 /// * `cancel_flag` - Atomic flag to check for cancellation
 /// * `db` - Database service for storing results
 pub fn run_batch_metadata_reanalysis(
     tracks: Vec<PathBuf>,
     options: MetadataOptions,
+    auto_cues: AutoCueConfig,
     progress_tx: Sender<ReanalysisProgress>,
     cancel_flag: Arc<AtomicBool>,
     db: Arc<DatabaseService>,
@@ -519,8 +584,8 @@ pub fn run_batch_metadata_reanalysis(
     let total = tracks.len();
 
     log::info!(
-        "run_batch_metadata_reanalysis: Starting for {} tracks (name={}, loudness={}, key={}, tags={})",
-        total, options.name_artist, options.loudness, options.key, options.tags
+        "run_batch_metadata_reanalysis: Starting for {} tracks (name={}, loudness={}, key={}, tags={}, cues={})",
+        total, options.name_artist, options.loudness, options.key, options.tags, options.cues
     );
 
     // Send start notification
@@ -596,7 +661,7 @@ pub fn run_batch_metadata_reanalysis(
                     total,
                 });
 
-                match reanalyze_metadata_track(path, &options, &db, &known_artists, ml_model_dir.as_deref()) {
+                match reanalyze_metadata_track(path, &options, &auto_cues, &db, &known_artists, ml_model_dir.as_deref()) {
                     Ok(()) => {
                         let _ = progress_tx.send(ReanalysisProgress::TrackCompleted {
                             track_name,
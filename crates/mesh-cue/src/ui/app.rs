@@ -75,6 +75,8 @@ pub struct MeshCueApp {
     pub(crate) delete_state: super::delete_modal::DeleteState,
     /// Context menu state
     pub(crate) context_menu_state: super::context_menu::ContextMenuState,
+    /// Smart playlist rule editor state
+    pub(crate) smart_playlist_state: super::smart_playlist_modal::SmartPlaylistState,
     /// Global mouse position (window coordinates) for context menu placement
     pub(crate) global_mouse_position: iced::Point,
     /// Stem link selection mode - Some(stem_index) when selecting a source track for linking
@@ -230,6 +232,7 @@ impl MeshCueApp {
             import_state: ImportState::default(),
             delete_state: Default::default(),
             context_menu_state: Default::default(),
+            smart_playlist_state: Default::default(),
             global_mouse_position: iced::Point::ORIGIN,
             stem_link_selection: None,
             reanalysis_state: ReanalysisState::default(),
@@ -284,6 +287,7 @@ impl MeshCueApp {
                     &state.key,
                     state.drop_marker,
                     state.first_beat_sample,
+                    &state.tempo_anchors,
                     &state.cue_points,
                     &state.saved_loops,
                     &state.stem_links,
@@ -349,6 +353,7 @@ impl MeshCueApp {
                     let tracks = self.domain.get_tracks_for_display(&folder);
                     self.collection.refresh_tracks(BrowserSide::Right, tracks);
                 }
+                self.refresh_search();
                 log::info!("Refreshed collection tree and track lists from database");
             }
             // Track loading (delegated to handlers/track_loading.rs)
@@ -428,6 +433,12 @@ impl MeshCueApp {
             Message::NudgeBeatGridLeft => return self.handle_nudge_beat_grid_left(),
             Message::NudgeBeatGridRight => return self.handle_nudge_beat_grid_right(),
             Message::AlignBeatGridToPlayhead => return self.handle_align_beat_grid_to_playhead(),
+            Message::AddTempoMarker => return self.handle_add_tempo_marker(),
+            Message::RemoveTempoMarker => return self.handle_remove_tempo_marker(),
+
+            // Rating (delegated to handlers/editing.rs)
+            Message::StepTrackRating(delta) => return self.handle_step_track_rating(delta),
+            Message::CycleTrackColorLabel => return self.handle_cycle_track_color_label(),
 
             // Settings (delegated to handlers/settings.rs)
             Message::OpenSettings => return self.handle_open_settings(),
@@ -437,6 +448,9 @@ impl MeshCueApp {
             Message::UpdateSettingsTrackNameFormat(value) => return self.handle_update_settings_track_name_format(value),
             Message::UpdateSettingsGridBars(value) => return self.handle_update_settings_grid_bars(value),
             Message::UpdateSettingsBpmSource(source) => return self.handle_update_settings_bpm_source(source),
+            Message::UpdateSettingsTagHints(mode) => return self.handle_update_settings_tag_hints(mode),
+            Message::UpdateSettingsPhraseHotCues(enabled) => return self.handle_update_settings_phrase_hot_cues(enabled),
+            Message::UpdateSettingsAutoCues(enabled) => return self.handle_update_settings_auto_cues(enabled),
             Message::UpdateSettingsSlicerBufferBars(bars) => return self.handle_update_settings_slicer_buffer_bars(bars),
             Message::UpdateSettingsOutputPair(idx) => return self.handle_update_settings_output_pair(idx),
             Message::UpdateSettingsScratchInterpolation(method) => return self.handle_update_settings_scratch_interpolation(method),
@@ -499,6 +513,9 @@ impl MeshCueApp {
             Message::MixedAudioFolderScanned(files) => return self.handle_mixed_audio_folder_scanned(files),
             Message::StartBatchImport => return self.handle_start_batch_import(),
             Message::StartMixedAudioImport => return self.handle_start_mixed_audio_import(),
+            Message::LibraryFilesScanned(files) => return self.handle_library_files_scanned(files),
+            Message::StartLibraryImport(index) => return self.handle_start_library_import(index),
+            Message::LibraryImportComplete(result) => return self.handle_library_import_complete(result),
             Message::ImportProgressUpdate(progress) => return self.handle_import_progress_update(progress),
             Message::CancelImport => return self.handle_cancel_import(),
             Message::DismissImportResults => return self.handle_dismiss_import_results(),
@@ -518,6 +535,9 @@ impl MeshCueApp {
             Message::CancelExport => return self.handle_cancel_export(),
             Message::UsbMessage(usb_msg) => return self.handle_usb_message(usb_msg),
             Message::DismissExportResults => return self.handle_dismiss_export_results(),
+            Message::ExportLibraryFiles { root, mixdown } => return self.handle_export_library_files(root, mixdown),
+            Message::LibraryExportComplete(result) => return self.handle_library_export_complete(result),
+            Message::SmartPlaylist(msg) => return self.handle_smart_playlist(msg),
 
             // Delete confirmation (delegated to handlers/delete.rs)
             Message::RequestDelete(browser_side) => return self.handle_request_delete(browser_side),
@@ -541,6 +561,7 @@ impl MeshCueApp {
             Message::ToggleReanalysisLoudness(v) => return self.handle_toggle_reanalysis_loudness(v),
             Message::ToggleReanalysisKey(v) => return self.handle_toggle_reanalysis_key(v),
             Message::ToggleReanalysisTags(v) => return self.handle_toggle_reanalysis_tags(v),
+            Message::ToggleReanalysisCues(v) => return self.handle_toggle_reanalysis_cues(v),
             Message::ConfirmMetadataReanalysis => return self.handle_confirm_metadata_reanalysis(),
             Message::CloseReanalysisConfig => return self.handle_close_reanalysis_config(),
             Message::ReanalysisProgress(progress) => return self.handle_reanalysis_progress(progress),
@@ -799,7 +820,7 @@ impl MeshCueApp {
             .padding(20)
             .into();
 
-        // Overlay modals if open (export > import > delete > settings > context menu)
+        // Overlay modals if open (export > import > delete > smart playlist > settings > context menu)
         if self.export_state.is_open {
             // Export modal needs special handling for playlist tree extraction
             let playlist_tree = find_playlists_tree(&self.collection.tree_nodes);
@@ -823,6 +844,12 @@ impl MeshCueApp {
                 super::delete_modal::view(&self.delete_state),
                 Message::CancelDelete,
             )
+        } else if self.smart_playlist_state.is_open {
+            with_modal_overlay(
+                base,
+                super::smart_playlist_modal::view(&self.smart_playlist_state),
+                Message::SmartPlaylist(super::smart_playlist_modal::SmartPlaylistMessage::Close),
+            )
         } else if self.settings.is_open {
             with_modal_overlay(
                 base,
@@ -1068,7 +1095,8 @@ impl MeshCueApp {
         let any_checked = self.reanalysis_state.config_name_artist
             || self.reanalysis_state.config_loudness
             || self.reanalysis_state.config_key
-            || self.reanalysis_state.config_tags;
+            || self.reanalysis_state.config_tags
+            || self.reanalysis_state.config_cues;
 
         let scope_desc = self.reanalysis_state.config_scope
             .as_ref()
@@ -1094,6 +1122,10 @@ impl MeshCueApp {
             .label("Tags (genre, mood, ML)")
             .on_toggle(Message::ToggleReanalysisTags)
             .size(16);
+        let cb_cues = checkbox(self.reanalysis_state.config_cues)
+            .label("Auto Cues (empty slots only)")
+            .on_toggle(Message::ToggleReanalysisCues)
+            .size(16);
 
         let start_btn = if any_checked {
             button(text("Start").size(sz(14.0)))
@@ -1121,6 +1153,7 @@ impl MeshCueApp {
                 cb_loudness,
                 cb_key,
                 cb_tags,
+                cb_cues,
                 Space::new().height(16),
                 buttons,
             ]
@@ -3,6 +3,7 @@
 //! Provides popup menus for tracks and playlists with appropriate actions.
 
 use super::app::Message;
+use super::smart_playlist_modal::SmartPlaylistMessage;
 use crate::analysis::ReanalysisScope;
 use iced::widget::{button, column, container, text};
 use mesh_widgets::sz;
@@ -26,11 +27,23 @@ pub enum ContextMenuKind {
         /// Other selected tracks (for batch operations)
         selected_tracks: Vec<NodeId>,
     },
+    /// Context menu for a track in a smart playlist (contents follow the rules)
+    SmartPlaylistTrack {
+        track_id: NodeId,
+        track_name: String,
+        /// Other selected tracks (for batch operations)
+        selected_tracks: Vec<NodeId>,
+    },
     /// Context menu for a playlist folder
     Playlist {
         playlist_id: NodeId,
         playlist_name: String,
     },
+    /// Context menu for a smart playlist
+    SmartPlaylist {
+        playlist_id: NodeId,
+        playlist_name: String,
+    },
     /// Context menu for the entire collection
     Collection,
 }
@@ -41,7 +54,9 @@ impl ContextMenuKind {
         match self {
             ContextMenuKind::CollectionTrack { track_id, .. } => Some(track_id),
             ContextMenuKind::PlaylistTrack { track_id, .. } => Some(track_id),
+            ContextMenuKind::SmartPlaylistTrack { track_id, .. } => Some(track_id),
             ContextMenuKind::Playlist { playlist_id, .. } => Some(playlist_id),
+            ContextMenuKind::SmartPlaylist { playlist_id, .. } => Some(playlist_id),
             ContextMenuKind::Collection => None,
         }
     }
@@ -51,7 +66,9 @@ impl ContextMenuKind {
         match self {
             ContextMenuKind::CollectionTrack { track_name, .. } => track_name,
             ContextMenuKind::PlaylistTrack { track_name, .. } => track_name,
+            ContextMenuKind::SmartPlaylistTrack { track_name, .. } => track_name,
             ContextMenuKind::Playlist { playlist_name, .. } => playlist_name,
+            ContextMenuKind::SmartPlaylist { playlist_name, .. } => playlist_name,
             ContextMenuKind::Collection => "Collection",
         }
     }
@@ -62,7 +79,8 @@ impl ContextMenuKind {
     pub fn track_ids(&self) -> Vec<NodeId> {
         match self {
             ContextMenuKind::CollectionTrack { track_id, selected_tracks, .. }
-            | ContextMenuKind::PlaylistTrack { track_id, selected_tracks, .. } => {
+            | ContextMenuKind::PlaylistTrack { track_id, selected_tracks, .. }
+            | ContextMenuKind::SmartPlaylistTrack { track_id, selected_tracks, .. } => {
                 let mut ids = vec![track_id.clone()];
                 for id in selected_tracks {
                     if id != track_id && !ids.contains(id) {
@@ -79,8 +97,9 @@ impl ContextMenuKind {
     pub fn is_batch(&self) -> bool {
         match self {
             ContextMenuKind::CollectionTrack { selected_tracks, .. }
-            | ContextMenuKind::PlaylistTrack { selected_tracks, .. } => !selected_tracks.is_empty(),
-            ContextMenuKind::Playlist { .. } => true,
+            | ContextMenuKind::PlaylistTrack { selected_tracks, .. }
+            | ContextMenuKind::SmartPlaylistTrack { selected_tracks, .. } => !selected_tracks.is_empty(),
+            ContextMenuKind::Playlist { .. } | ContextMenuKind::SmartPlaylist { .. } => true,
             ContextMenuKind::Collection => true,
         }
     }
@@ -170,6 +189,27 @@ pub fn view(state: &ContextMenuState) -> Option<Element<'static, Message>> {
                 menu_item("Remove from Playlist", Message::RequestDeleteById(track_id.clone())),
             ]
         }
+        ContextMenuKind::SmartPlaylistTrack { track_id, selected_tracks, .. } => {
+            let scope = if selected_tracks.is_empty() {
+                ReanalysisScope::SingleTrack(track_id.clone())
+            } else {
+                let mut ids = vec![track_id.clone()];
+                ids.extend(selected_tracks.iter().filter(|id| *id != track_id).cloned());
+                ReanalysisScope::SelectedTracks(ids)
+            };
+            let scope_label = if selected_tracks.is_empty() { "" } else { " (Selected)" };
+
+            vec![
+                menu_item(
+                    &format!("Re-analyse Metadata...{}", scope_label),
+                    Message::OpenMetadataReanalysisConfig { scope: scope.clone() },
+                ),
+                menu_item(
+                    &format!("Re-analyse Beats{}", scope_label),
+                    Message::StartBeatsReanalysis { scope },
+                ),
+            ]
+        }
         ContextMenuKind::Playlist { playlist_id, .. } => {
             let scope = ReanalysisScope::PlaylistFolder(playlist_id.clone());
             vec![
@@ -182,6 +222,49 @@ pub fn view(state: &ContextMenuState) -> Option<Element<'static, Message>> {
                     Message::StartBeatsReanalysis { scope },
                 ),
                 menu_separator(),
+                menu_item(
+                    "Export for DJ Software",
+                    Message::ExportLibraryFiles { root: playlist_id.clone(), mixdown: false },
+                ),
+                menu_item(
+                    "Export for DJ Software (with Audio)",
+                    Message::ExportLibraryFiles { root: playlist_id.clone(), mixdown: true },
+                ),
+                menu_separator(),
+                menu_item(
+                    "New Smart Playlist...",
+                    Message::SmartPlaylist(SmartPlaylistMessage::New(playlist_id.clone())),
+                ),
+                menu_item("Rename", Message::StartRenamePlaylist(playlist_id.clone())),
+                menu_item("Delete Playlist", Message::RequestDeletePlaylist(playlist_id.clone())),
+            ]
+        }
+        ContextMenuKind::SmartPlaylist { playlist_id, .. } => {
+            let scope = ReanalysisScope::PlaylistFolder(playlist_id.clone());
+            vec![
+                menu_item(
+                    "Edit Rules...",
+                    Message::SmartPlaylist(SmartPlaylistMessage::Edit(playlist_id.clone())),
+                ),
+                menu_separator(),
+                menu_item(
+                    "Re-analyse Metadata... (Playlist)",
+                    Message::OpenMetadataReanalysisConfig { scope: scope.clone() },
+                ),
+                menu_item(
+                    "Re-analyse Beats (Playlist)",
+                    Message::StartBeatsReanalysis { scope },
+                ),
+                menu_separator(),
+                menu_item(
+                    "Export for DJ Software",
+                    Message::ExportLibraryFiles { root: playlist_id.clone(), mixdown: false },
+                ),
+                menu_item(
+                    "Export for DJ Software (with Audio)",
+                    Message::ExportLibraryFiles { root: playlist_id.clone(), mixdown: true },
+                ),
+                menu_separator(),
                 menu_item("Rename", Message::StartRenamePlaylist(playlist_id.clone())),
                 menu_item("Delete Playlist", Message::RequestDeletePlaylist(playlist_id.clone())),
             ]
@@ -66,11 +66,13 @@ fn view_header(state: &LoadedTrackState) -> Element<'_, Message> {
     let bpm_minus = button(text("-").size(sz(12.0)))
         .padding([4, 8])
         .on_press(Message::DecreaseBpm);
-    let bpm_input = text_input("BPM", &format!("{:.2}", state.bpm))
-        .on_input(|s| {
+    // Variable-tempo tracks show (and edit) the segment under the playhead
+    let bpm = state.bpm_at_playhead();
+    let bpm_input = text_input("BPM", &format!("{:.2}", bpm))
+        .on_input(move |s| {
             s.parse::<f64>()
                 .map(Message::SetBpm)
-                .unwrap_or(Message::SetBpm(state.bpm))
+                .unwrap_or(Message::SetBpm(bpm))
         })
         .width(Length::Fixed(80.0));
     let bpm_plus = button(text("+").size(sz(12.0)))
@@ -96,6 +98,14 @@ fn view_header(state: &LoadedTrackState) -> Element<'_, Message> {
     let align_grid = button(text("│").size(sz(14.0)))
         .padding([4, 10])
         .on_press(Message::AlignBeatGridToPlayhead);
+    let add_tempo_marker = button(text("+T").size(sz(12.0)))
+        .padding([4, 8])
+        .on_press(Message::AddTempoMarker);
+    let remove_tempo_marker = button(text("-T").size(sz(12.0)))
+        .padding([4, 8])
+        .on_press_maybe(
+            (state.tempo_segment_at_playhead() > 0).then_some(Message::RemoveTempoMarker),
+        );
 
     let modified_indicator = if state.modified {
         text("*").size(sz(20.0))
@@ -121,6 +131,8 @@ fn view_header(state: &LoadedTrackState) -> Element<'_, Message> {
         nudge_left,
         nudge_right,
         align_grid,
+        add_tempo_marker,
+        remove_tempo_marker,
         Space::new().width(Length::Fixed(50.0)),
     ]
     .spacing(10)
@@ -1,17 +1,22 @@
 //! Effect picker modal for adding effects to multiband chains
 //!
 //! Provides a modal dialog for selecting and adding effects.
-//! Supports both PD effects and CLAP plugins, grouped by category.
+//! Lists the built-in native effects, PD effects and CLAP plugins,
+//! grouped by category.
 
 use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Alignment, Element, Length};
 use mesh_core::clap::DiscoveredClapPlugin;
+use mesh_core::effect::native::{create_native_effect, NATIVE_EFFECT_IDS};
+use mesh_core::effect::EffectInfo;
 use mesh_core::pd::DiscoveredEffect;
 use mesh_widgets::sz;
 
 /// Effect source type for distinguishing PD from CLAP effects
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EffectSource {
+    /// Built-in Rust effect
+    Native,
     /// Pure Data effect
     Pd,
     /// CLAP plugin
@@ -54,6 +59,18 @@ impl EffectListItem {
         }
     }
 
+    /// Create from a built-in effect
+    pub fn from_native(id: &str, info: &EffectInfo) -> Self {
+        Self {
+            id: id.to_string(),
+            name: info.name.clone(),
+            category: info.category.clone(),
+            available: true,
+            status_message: None,
+            source: EffectSource::Native,
+        }
+    }
+
     /// Create from a CLAP plugin
     pub fn from_clap(plugin: &DiscoveredClapPlugin) -> Self {
         let status_message = plugin.error_message.clone();
@@ -76,6 +93,8 @@ pub enum EffectPickerMessage {
     Open { target: EffectPickerTarget },
     /// Close the picker without selecting
     Close,
+    /// Select a built-in effect to add
+    SelectNativeEffect(String),
     /// Select a PD effect to add
     SelectPdEffect(String),
     /// Select a CLAP effect to add
@@ -106,6 +125,16 @@ pub struct EffectPickerState {
     pub selected_category: Option<String>,
     /// Filter by source (None = show all, Some = filter)
     pub source_filter: Option<EffectSource>,
+    /// Built-in effects (always available, listed once at startup)
+    native_effects: Vec<EffectListItem>,
+}
+
+/// List entries for every built-in effect
+fn native_effect_items() -> Vec<EffectListItem> {
+    NATIVE_EFFECT_IDS
+        .iter()
+        .filter_map(|id| create_native_effect(id).map(|effect| EffectListItem::from_native(id, effect.info())))
+        .collect()
 }
 
 impl Default for EffectPickerState {
@@ -115,6 +144,7 @@ impl Default for EffectPickerState {
             target: EffectPickerTarget::Band(0),
             selected_category: None,
             source_filter: None,
+            native_effects: native_effect_items(),
         }
     }
 }
@@ -177,6 +207,11 @@ impl EffectPickerState {
         // Build unified effect list
         let mut effects: Vec<EffectListItem> = Vec::new();
 
+        // Add built-in effects (filtered by source if needed)
+        if self.source_filter.is_none() || self.source_filter == Some(EffectSource::Native) {
+            effects.extend(self.native_effects.iter().cloned());
+        }
+
         // Add PD effects (filtered by source if needed)
         if self.source_filter.is_none() || self.source_filter == Some(EffectSource::Pd) {
             for effect in pd_effects {
@@ -213,6 +248,14 @@ impl EffectPickerState {
                 } else {
                     button::secondary
                 }),
+            button(text("Native").size(sz(12.0)))
+                .on_press(EffectPickerMessage::ToggleSourceFilter(Some(EffectSource::Native)))
+                .padding([4, 10])
+                .style(if self.source_filter == Some(EffectSource::Native) {
+                    button::primary
+                } else {
+                    button::secondary
+                }),
             button(text("PD").size(sz(12.0)))
                 .on_press(EffectPickerMessage::ToggleSourceFilter(Some(EffectSource::Pd)))
                 .padding([4, 10])
@@ -231,7 +274,8 @@ impl EffectPickerState {
                 }),
             Space::new().width(Length::Fill),
             text(format!(
-                "{} PD, {} CLAP",
+                "{} native, {} PD, {} CLAP",
+                self.native_effects.len(),
                 pd_effects.len(),
                 clap_plugins.len()
             ))
@@ -328,6 +372,7 @@ impl EffectPickerState {
 
         // Source badge
         let source_badge = match &effect.source {
+            EffectSource::Native => text("NATIVE").size(sz(9.0)),
             EffectSource::Pd => text("PD").size(sz(9.0)),
             EffectSource::Clap => text("CLAP").size(sz(9.0)),
         };
@@ -356,6 +401,7 @@ impl EffectPickerState {
         // Add button (disabled if unavailable)
         let add_btn = if available {
             let msg = match source {
+                EffectSource::Native => EffectPickerMessage::SelectNativeEffect(id),
                 EffectSource::Pd => EffectPickerMessage::SelectPdEffect(id),
                 EffectSource::Clap => EffectPickerMessage::SelectClapEffect(id),
             };
@@ -97,6 +97,11 @@ impl MeshCueApp {
                             log::info!("[{} TREE] Node found: kind={:?}, name={}", side_name, node.kind, node.name);
                             let menu_kind = if node.kind == NodeKind::Collection {
                                 super::super::context_menu::ContextMenuKind::Collection
+                            } else if node.kind == NodeKind::SmartPlaylist {
+                                super::super::context_menu::ContextMenuKind::SmartPlaylist {
+                                    playlist_id: id.clone(),
+                                    playlist_name: node.name.clone(),
+                                }
                             } else {
                                 super::super::context_menu::ContextMenuKind::Playlist {
                                     playlist_id: id.clone(),
@@ -205,9 +210,11 @@ impl MeshCueApp {
         }
 
         // Handle table message and check for edit commits
-        if let Some((track_id, column, new_value)) =
-            self.collection.browser_mut(side).handle_table_message(&table_msg)
-        {
+        let edit = self.collection.browser_mut(side).handle_table_message(&table_msg);
+        if let TrackTableMessage::SearchChanged(_) = table_msg {
+            self.update_search_matches(side);
+        }
+        if let Some((track_id, column, new_value)) = edit {
             if let Some(node) = self.domain.get_node(&track_id) {
                 if let Some(ref path) = node.track_path {
                     let db_field = match column {
@@ -226,6 +233,17 @@ impl MeshCueApp {
                                 let tracks = self.domain.get_tracks_for_display(folder);
                                 self.collection.refresh_tracks(side, tracks);
                             }
+                            // A smart playlist on the other side may gain or lose the track
+                            let other = match side {
+                                BrowserSide::Left => BrowserSide::Right,
+                                BrowserSide::Right => BrowserSide::Left,
+                            };
+                            if let Some(folder) = self.collection.browser(other).current_folder.clone() {
+                                if self.domain.is_smart_playlist(&folder) {
+                                    let tracks = self.domain.get_tracks_for_display(&folder);
+                                    self.collection.refresh_tracks(other, tracks);
+                                }
+                            }
                         }
                         Err(e) => log::error!("Failed to update track field: {:?}", e),
                     }
@@ -363,17 +381,23 @@ impl MeshCueApp {
             if let Some(node) = self.domain.get_node(track_id) {
                 log::info!("[{} TABLE] Track found: name={}", side_name, node.name);
                 let current_folder = self.collection.browser(side).current_folder.clone();
-                let is_playlist_view = current_folder.as_ref()
+                let folder_kind = current_folder.as_ref()
                     .and_then(|f| self.domain.get_node(f))
-                    .map(|n| n.kind == NodeKind::Playlist || n.kind == NodeKind::PlaylistsRoot)
-                    .unwrap_or(false);
+                    .map(|n| n.kind);
+                let is_playlist_view = matches!(folder_kind, Some(NodeKind::Playlist | NodeKind::PlaylistsRoot));
 
                 let selected_tracks: Vec<NodeId> = self.collection.browser(side).table_state.selected.iter()
                     .filter(|id| *id != track_id)
                     .cloned()
                     .collect();
 
-                let menu_kind = if is_playlist_view {
+                let menu_kind = if folder_kind == Some(NodeKind::SmartPlaylist) {
+                    super::super::context_menu::ContextMenuKind::SmartPlaylistTrack {
+                        track_id: track_id.clone(),
+                        track_name: node.name.clone(),
+                        selected_tracks,
+                    }
+                } else if is_playlist_view {
                     super::super::context_menu::ContextMenuKind::PlaylistTrack {
                         track_id: track_id.clone(),
                         track_name: node.name.clone(),
@@ -402,6 +426,38 @@ impl MeshCueApp {
         Task::none()
     }
 
+    /// Evaluate the browser's search against the library if it has filters
+    ///
+    /// Stores the matching track paths on the table state. `None` (no
+    /// structured filters, or the query failed) makes the table filter by
+    /// plain words only.
+    pub fn update_search_matches(&mut self, side: BrowserSide) {
+        let query = self.collection.browser(side).table_state.structured_search();
+        let matches = query.and_then(|query| match self.domain.search_track_paths(&query) {
+            Ok(paths) => Some(paths),
+            Err(e) => {
+                log::warn!("Search failed: {}", e);
+                None
+            }
+        });
+        self.collection.browser_mut(side).table_state.search_matches = matches;
+    }
+
+    /// Reload autocomplete values and re-run both browsers' searches
+    ///
+    /// Called after anything that can change which tracks match.
+    pub fn refresh_search(&mut self) {
+        match self.domain.search_vocabulary() {
+            Ok(vocab) => {
+                self.collection.browser_left.table_state.search_vocabulary = vocab.clone();
+                self.collection.browser_right.table_state.search_vocabulary = vocab;
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+        self.update_search_matches(BrowserSide::Left);
+        self.update_search_matches(BrowserSide::Right);
+    }
+
     /// Handle RefreshPlaylists message
     pub fn handle_refresh_playlists(&mut self) -> Task<Message> {
         // Database queries are always fresh - just rebuild the UI views
@@ -416,6 +472,7 @@ impl MeshCueApp {
             let tracks = self.domain.get_tracks_for_display(&folder);
             self.collection.refresh_tracks(BrowserSide::Right, tracks);
         }
+        self.refresh_search();
         Task::none()
     }
 
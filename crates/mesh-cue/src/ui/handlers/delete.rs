@@ -57,6 +57,10 @@ impl MeshCueApp {
                 track_names,
                 track_ids: selected_ids,
             }
+        } else if current_folder.as_ref().is_some_and(|f| self.domain.is_smart_playlist(f)) {
+            // Smart playlist contents follow its rules; nothing to remove
+            log::debug!("Delete requested in a smart playlist, ignoring");
+            return Task::none();
         } else if let Some(folder_id) = current_folder {
             // In a playlist - just remove from playlist
             let playlist_name = self.domain.get_node(&folder_id)
@@ -231,6 +235,12 @@ impl MeshCueApp {
             .and_then(|id| self.collection.left_tracks.iter().position(|t| &t.id == id));
         let total_items = self.collection.left_tracks.len();
 
+        // Smart playlist contents follow its rules; nothing to remove
+        if track_id.parent().is_some_and(|p| self.domain.is_smart_playlist(&p)) {
+            log::debug!("Delete requested for a smart playlist track, ignoring");
+            return Task::none();
+        }
+
         // Determine if track is in collection or playlist
         if track_id.is_in_tracks() {
             // Collection track - permanent deletion
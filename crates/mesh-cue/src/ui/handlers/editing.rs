@@ -2,13 +2,16 @@
 //!
 //! Handles: SetBpm, SetKey, AddCuePoint, DeleteCuePoint, SetCueLabel, SaveTrack, SaveComplete,
 //! SetCuePoint, ClearCuePoint, JumpToCue, SaveLoop, JumpToSavedLoop, ClearSavedLoop,
-//! SetDropMarker, ClearDropMarker
+//! SetDropMarker, ClearDropMarker, AddTempoMarker, RemoveTempoMarker,
+//! StepTrackRating, CycleTrackColorLabel
 
 use iced::Task;
-use mesh_core::audio_file::{CuePoint, SavedLoop};
+use mesh_core::audio_file::{CuePoint, SavedLoop, TempoAnchor};
+use mesh_core::db::TrackRating;
+use mesh_widgets::parse_hex_color;
 use super::super::app::MeshCueApp;
 use super::super::message::Message;
-use super::super::utils::{find_nearest_beat_with_index, regenerate_beat_grid, snap_to_nearest_beat, update_waveform_beat_grid};
+use super::super::utils::{find_nearest_beat_with_index, rebuild_tempo_grid, regenerate_beat_grid, remove_tempo_anchor, snap_to_nearest_beat, update_waveform_beat_grid};
 
 impl MeshCueApp {
     /// Handle SetBpm message
@@ -16,8 +19,19 @@ impl MeshCueApp {
     /// When BPM changes, the beat grid is recalculated anchored on the beat
     /// nearest to the current playhead. This preserves nudge adjustments —
     /// the beat you're listening to stays in place while other beats shift.
+    ///
+    /// On variable-tempo tracks only the segment under the playhead changes;
+    /// its tempo marker stays in place.
     pub fn handle_set_bpm(&mut self, bpm: f64) -> Task<Message> {
         if let Some(ref mut state) = self.collection.loaded_track {
+            if !state.tempo_anchors.is_empty() {
+                let segment = state.tempo_segment_at_playhead();
+                state.tempo_anchors[segment].bpm = bpm.clamp(1.0, 2500.0);
+                rebuild_tempo_grid(state);
+                self.audio.set_beat_grid(state.beat_grid.clone());
+                return Task::none();
+            }
+
             let old_bpm = state.bpm;
             state.bpm = bpm.clamp(1.0, 2500.0);
 
@@ -49,7 +63,7 @@ impl MeshCueApp {
     /// Handle BPM adjustment (+/- delta)
     pub fn handle_adjust_bpm(&mut self, delta: f64) -> Task<Message> {
         if let Some(ref state) = self.collection.loaded_track {
-            let new_bpm = (state.bpm + delta).clamp(1.0, 2500.0);
+            let new_bpm = (state.bpm_at_playhead() + delta).clamp(1.0, 2500.0);
             return self.handle_set_bpm(new_bpm);
         }
         Task::none()
@@ -100,8 +114,14 @@ impl MeshCueApp {
         let bpm = (60.0 / avg_interval).round().clamp(20.0, 2500.0);
 
         // Write BPM to state so handle_align_beat_grid_to_playhead picks it up
+        // (the segment under the playhead on variable-tempo tracks)
         if let Some(ref mut state) = self.collection.loaded_track {
-            state.bpm = bpm;
+            if state.tempo_anchors.is_empty() {
+                state.bpm = bpm;
+            } else {
+                let segment = state.tempo_segment_at_playhead();
+                state.tempo_anchors[segment].bpm = bpm;
+            }
             state.modified = true;
         }
 
@@ -164,6 +184,7 @@ impl MeshCueApp {
                 &state.key,
                 state.drop_marker,
                 state.first_beat_sample,
+                &state.tempo_anchors,
                 &state.cue_points,
                 &state.saved_loops,
                 &state.stem_links,
@@ -373,6 +394,44 @@ impl MeshCueApp {
         Task::none()
     }
 
+    /// Handle StepTrackRating message
+    pub fn handle_step_track_rating(&mut self, delta: i8) -> Task<Message> {
+        self.update_loaded_track_rating(|rating| rating.step_rating(delta));
+        Task::none()
+    }
+
+    /// Handle CycleTrackColorLabel message
+    pub fn handle_cycle_track_color_label(&mut self) -> Task<Message> {
+        self.update_loaded_track_rating(TrackRating::cycle_color);
+        Task::none()
+    }
+
+    /// Apply a rating change to the loaded track and to its rows in both
+    /// browsers. Ratings are saved immediately, not with the track edits.
+    fn update_loaded_track_rating(&mut self, update: impl FnOnce(&mut TrackRating)) {
+        let Some(path) = self.collection.loaded_track.as_ref().map(|s| s.path.clone()) else {
+            return;
+        };
+        let rating = match self.domain.update_track_rating(&path, update) {
+            Ok(Some(rating)) => rating,
+            Ok(None) => return,
+            Err(e) => {
+                log::error!("Failed to save rating: {}", e);
+                return;
+            }
+        };
+        log::info!("Rated {:?}: {} stars, colour {:?}", path, rating.rating, rating.color);
+
+        let path_str = path.to_string_lossy();
+        let color = rating.color.as_deref().and_then(parse_hex_color);
+        for tracks in [&mut self.collection.left_tracks, &mut self.collection.right_tracks] {
+            for row in tracks.iter_mut().filter(|r| r.track_path.as_deref() == Some(&*path_str)) {
+                row.rating = rating.rating;
+                row.color_label = color;
+            }
+        }
+    }
+
     /// Handle SetZoomBars message
     pub fn handle_set_zoom_bars(&mut self, bars: u32) -> Task<Message> {
         if let Some(ref mut state) = self.collection.loaded_track {
@@ -415,6 +474,23 @@ impl MeshCueApp {
 
             let playhead = state.playhead_position();
 
+            // Variable-tempo grids: move the marker of the segment under the
+            // playhead onto the playhead (if it stays between its neighbours)
+            if !state.tempo_anchors.is_empty() {
+                let segment = state.tempo_segment_at_playhead();
+                let after_prev = segment == 0
+                    || playhead > state.tempo_anchors[segment - 1].sample_position;
+                let before_next = state.tempo_anchors
+                    .get(segment + 1)
+                    .map_or(true, |next| playhead < next.sample_position);
+                if after_prev && before_next {
+                    state.tempo_anchors[segment].sample_position = playhead;
+                    rebuild_tempo_grid(state);
+                    self.audio.set_beat_grid(state.beat_grid.clone());
+                }
+                return Task::none();
+            }
+
             // The playhead itself is the new anchor downbeat. regenerate_beat_grid
             // backfills beats before the anchor so beat-jump and snap reach the
             // start of the track. The shader uses beat_anchor_idx to align red and
@@ -435,4 +511,56 @@ impl MeshCueApp {
         }
         Task::none()
     }
+
+    /// Handle AddTempoMarker message
+    ///
+    /// Starts a new tempo segment on the beat nearest to the playhead. The
+    /// segment initially keeps the current tempo; change it with SetBpm while
+    /// the playhead is inside it. The first marker turns a constant grid into
+    /// a variable-tempo grid anchored on the existing downbeat.
+    pub fn handle_add_tempo_marker(&mut self) -> Task<Message> {
+        if let Some(ref mut state) = self.collection.loaded_track {
+            if state.beat_grid.is_empty() || state.duration_samples == 0 {
+                return Task::none();
+            }
+
+            let playhead = state.playhead_position();
+            let (_idx, beat) = find_nearest_beat_with_index(&state.beat_grid, playhead);
+
+            let first_anchor = state.tempo_anchors.first()
+                .map(|a| a.sample_position)
+                .unwrap_or(state.first_beat_sample);
+            if beat <= first_anchor || state.tempo_anchors.iter().any(|a| a.sample_position == beat) {
+                return Task::none();
+            }
+
+            if state.tempo_anchors.is_empty() {
+                state.tempo_anchors.push(TempoAnchor::new(state.first_beat_sample, state.bpm));
+            }
+            let bpm = state.bpm_at_playhead();
+            let insert_at = state.tempo_anchors.partition_point(|a| a.sample_position < beat);
+            state.tempo_anchors.insert(insert_at, TempoAnchor::new(beat, bpm));
+
+            log::debug!("Added tempo marker at {} ({:.2} BPM)", beat, bpm);
+
+            rebuild_tempo_grid(state);
+            self.audio.set_beat_grid(state.beat_grid.clone());
+        }
+        Task::none()
+    }
+
+    /// Handle RemoveTempoMarker message
+    ///
+    /// Removes the marker starting the segment under the playhead, so the
+    /// previous segment's tempo continues through it. Removing the last
+    /// marker returns the track to a constant-tempo grid.
+    pub fn handle_remove_tempo_marker(&mut self) -> Task<Message> {
+        if let Some(ref mut state) = self.collection.loaded_track {
+            let segment = state.tempo_segment_at_playhead();
+            if remove_tempo_anchor(state, segment) {
+                self.audio.set_beat_grid(state.beat_grid.clone());
+            }
+        }
+        Task::none()
+    }
 }
@@ -25,6 +25,46 @@ impl MeshCueApp {
             Close => {
                 self.effect_picker.close();
             }
+            SelectNativeEffect(effect_id) => {
+                let target = self.effect_picker.target;
+
+                match mesh_core::effect::native::create_native_effect(&effect_id) {
+                    Some(effect) => {
+                        let info = effect.info();
+                        let available_params: Vec<AvailableParam> = info
+                            .params
+                            .iter()
+                            .map(|p| AvailableParam {
+                                name: p.name.clone(),
+                                min: p.min,
+                                max: p.max,
+                                default: p.default,
+                                unit: p.unit.clone(),
+                            })
+                            .collect();
+                        let name = info.name.clone();
+                        let effect_state = EffectUiState::new_with_params(
+                            effect_id.clone(),
+                            name.clone(),
+                            info.category.clone(),
+                            EffectSourceType::Native,
+                            available_params,
+                        );
+
+                        self.add_effect_to_target(target, effect_state);
+                        mesh_widgets::multiband::ensure_effect_knobs_exist(&mut self.effects_editor.editor);
+
+                        log::info!("Added native effect '{}' to {:?}", effect_id, target);
+                        self.effects_editor.set_status(format!("Added '{}'", name));
+                    }
+                    None => {
+                        log::warn!("Native effect '{}' not found", effect_id);
+                        self.effects_editor.set_status(format!("Effect '{}' not found", effect_id));
+                    }
+                }
+
+                self.effect_picker.close();
+            }
             SelectPdEffect(effect_id) => {
                 let target = self.effect_picker.target;
 
@@ -268,8 +308,7 @@ impl MeshCueApp {
                 self.domain.create_clap_effect_with_gui(&effect_ui.id, effect_instance_id).ok()
             }
             EffectSourceType::Native => {
-                // Native effects not supported in presets yet
-                None
+                mesh_core::effect::native::create_native_effect(&effect_ui.id)
             }
         }
     }
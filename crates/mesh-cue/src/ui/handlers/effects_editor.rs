@@ -2400,8 +2400,7 @@ impl MeshCueApp {
                 self.domain.create_clap_effect_with_gui(id, effect_instance_id).ok()
             }
             EffectSourceType::Native => {
-                // Native effects not supported in presets yet
-                None
+                mesh_core::effect::native::create_native_effect(id)
             }
         }
     }
@@ -1,8 +1,9 @@
-//! USB export message handlers
+//! USB and library export message handlers
 //!
 //! Handles: OpenExport, CloseExport, SelectExportDevice, ToggleExportPlaylist,
 //! ToggleExportPlaylistExpand, ToggleExportConfig, BuildSyncPlan, StartExport,
-//! CancelExport, UsbMessage, DismissExportResults
+//! CancelExport, UsbMessage, DismissExportResults, ExportLibraryFiles,
+//! LibraryExportComplete
 
 use iced::Task;
 use mesh_core::usb::{UsbMessage as UsbMsg, ExportableConfig, ExportableAudioConfig, ExportableDisplayConfig, ExportableSlicerConfig};
@@ -10,7 +11,9 @@ use super::super::app::MeshCueApp;
 use super::super::message::Message;
 use super::super::state::ExportPhase;
 use crate::analysis::{AnalysisType, MetadataOptions};
-use mesh_core::playlist::{NodeId, NodeKind};
+use crate::batch_import::sanitize_filename;
+use crate::library_export::{self, ExportOptions, LibraryExportSummary};
+use mesh_core::playlist::{DatabaseStorage, NodeId, NodeKind};
 
 impl MeshCueApp {
     /// Handle OpenExport message
@@ -128,7 +131,7 @@ impl MeshCueApp {
                         self.export_state.pending_lufs_analysis = true;
 
                         // Start reanalysis through domain (owns db_service, config)
-                        let lufs_only = MetadataOptions { name_artist: false, loudness: true, key: false, tags: false };
+                        let lufs_only = MetadataOptions { name_artist: false, loudness: true, key: false, tags: false, cues: false };
                         if let Err(e) = self.domain.start_reanalysis(tracks_missing_lufs, AnalysisType::Metadata, Some(lufs_only)) {
                             log::error!("Failed to start LUFS analysis: {:?}", e);
                             self.reanalysis_state.is_running = false;
@@ -479,4 +482,52 @@ impl MeshCueApp {
             None
         }
     }
+
+    /// Handle ExportLibraryFiles — fires from the playlist context menu
+    ///
+    /// Files go to `<collection>/exports/<playlist name>/`.
+    pub fn handle_export_library_files(&mut self, root: NodeId, mixdown: bool) -> Task<Message> {
+        self.context_menu_state.close();
+
+        let name = self.domain.get_node(&root)
+            .map(|node| node.name)
+            .unwrap_or_else(|| "mesh".to_string());
+        let out_dir = self.domain.collection_root()
+            .join("exports")
+            .join(sanitize_filename(&name));
+        log::info!("Exporting {} to {:?} (mixdown: {})", root, out_dir, mixdown);
+
+        let db = self.domain.db_arc();
+        let options = ExportOptions { mixdown, ..Default::default() };
+        Task::perform(
+            async move {
+                let storage = DatabaseStorage::new(db.clone()).map_err(|e| e.to_string())?;
+                library_export::export_library(&db, &storage, &root, &out_dir, &options)
+                    .map_err(|e| format!("{:#}", e))
+            },
+            Message::LibraryExportComplete,
+        )
+    }
+
+    /// Handle LibraryExportComplete message
+    pub fn handle_library_export_complete(
+        &mut self,
+        result: Result<LibraryExportSummary, String>,
+    ) -> Task<Message> {
+        match result {
+            Ok(summary) => {
+                log::info!(
+                    "Library export complete: {} tracks, {} playlists, {} files",
+                    summary.tracks,
+                    summary.playlists,
+                    summary.files.len()
+                );
+                if !summary.failed.is_empty() {
+                    log::warn!("Mixdown failed for: {}", summary.failed.join(", "));
+                }
+            }
+            Err(e) => log::error!("Library export failed: {}", e),
+        }
+        Task::none()
+    }
 }
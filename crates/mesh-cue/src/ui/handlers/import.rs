@@ -1,11 +1,13 @@
 //! Batch import message handlers
 //!
 //! Handles: OpenImport, CloseImport, SetImportMode, ScanImportFolder, ImportFolderScanned,
-//! MixedAudioFolderScanned, StartBatchImport, StartMixedAudioImport, ImportProgressUpdate,
-//! CancelImport, DismissImportResults
+//! MixedAudioFolderScanned, StartBatchImport, StartMixedAudioImport, LibraryFilesScanned,
+//! StartLibraryImport, LibraryImportComplete, ImportProgressUpdate, CancelImport,
+//! DismissImportResults
 
 use iced::Task;
 use crate::batch_import::{self, ImportProgress, MixedAudioFile};
+use crate::library_import::{self, LibraryFile, LibraryImportSummary};
 use super::super::app::MeshCueApp;
 use super::super::message::Message;
 use super::super::state::{ImportMode, ImportPhase, ImportState};
@@ -76,6 +78,18 @@ impl MeshCueApp {
                     Message::MixedAudioFolderScanned,
                 )
             }
+            ImportMode::DjLibrary => {
+                Task::perform(
+                    async move {
+                        library_import::scan_library_files(&import_folder)
+                            .unwrap_or_else(|e| {
+                                log::error!("Failed to scan import folder for DJ libraries: {}", e);
+                                Vec::new()
+                            })
+                    },
+                    Message::LibraryFilesScanned,
+                )
+            }
         }
     }
 
@@ -95,6 +109,65 @@ impl MeshCueApp {
         Task::none()
     }
 
+    /// Handle LibraryFilesScanned message (Rekordbox / Traktor files)
+    pub fn handle_library_files_scanned(&mut self, files: Vec<LibraryFile>) -> Task<Message> {
+        log::info!("Import folder scanned: {} DJ library files found", files.len());
+        self.import_state.detected_libraries = files;
+        self.import_state.phase = None;
+        Task::none()
+    }
+
+    /// Handle StartLibraryImport message
+    ///
+    /// Parses the library and writes cues, loops, grids, ratings and
+    /// playlists for matching collection tracks. No audio is processed, so
+    /// this runs as a single background task without per-track progress.
+    pub fn handle_start_library_import(&mut self, index: usize) -> Task<Message> {
+        let Some(file) = self.import_state.detected_libraries.get(index).cloned() else {
+            return Task::none();
+        };
+
+        log::info!("Starting {} library import from {:?}", file.kind.display_name(), file.path);
+        self.import_state.library_result = None;
+        self.import_state.phase = Some(ImportPhase::Processing {
+            current_track: file.file_name(),
+            completed: 0,
+            total: 1,
+            start_time: std::time::Instant::now(),
+        });
+
+        let db = self.domain.db_arc();
+        Task::perform(
+            async move {
+                library_import::import_library_file(&db, &file).map_err(|e| format!("{:#}", e))
+            },
+            Message::LibraryImportComplete,
+        )
+    }
+
+    /// Handle LibraryImportComplete message
+    pub fn handle_library_import_complete(
+        &mut self,
+        result: Result<LibraryImportSummary, String>,
+    ) -> Task<Message> {
+        let duration = match self.import_state.phase {
+            Some(ImportPhase::Processing { start_time, .. }) => start_time.elapsed(),
+            _ => std::time::Duration::ZERO,
+        };
+        if let Err(ref e) = result {
+            log::error!("Library import failed: {}", e);
+        }
+
+        self.import_state.phase = Some(ImportPhase::Complete { duration });
+        self.import_state.library_result = Some(result);
+        self.import_state.show_results = true;
+
+        Task::batch([
+            Task::perform(async {}, |_| Message::RefreshCollection),
+            Task::perform(async {}, |_| Message::RefreshPlaylists),
+        ])
+    }
+
     /// Handle StartBatchImport message
     pub fn handle_start_batch_import(&mut self) -> Task<Message> {
         let complete_groups: Vec<_> = self
@@ -121,11 +121,30 @@ impl MeshCueApp {
             return self.update(Message::AlignBeatGridToPlayhead);
         }
 
+        // Tempo-change markers (ignore repeat)
+        if !repeat && bindings.add_tempo_marker.iter().any(|b| b == &key_str) {
+            return self.update(Message::AddTempoMarker);
+        }
+        if !repeat && bindings.remove_tempo_marker.iter().any(|b| b == &key_str) {
+            return self.update(Message::RemoveTempoMarker);
+        }
+
         // Set/update drop marker (ignore repeat)
         if !repeat && bindings.set_drop_marker.iter().any(|b| b == &key_str) {
             return self.update(Message::SetDropMarker);
         }
 
+        // Rating and colour label (ignore repeat)
+        if !repeat && bindings.rating_up.iter().any(|b| b == &key_str) {
+            return self.update(Message::StepTrackRating(1));
+        }
+        if !repeat && bindings.rating_down.iter().any(|b| b == &key_str) {
+            return self.update(Message::StepTrackRating(-1));
+        }
+        if !repeat && bindings.cycle_color_label.iter().any(|b| b == &key_str) {
+            return self.update(Message::CycleTrackColorLabel);
+        }
+
         // Increase/decrease loop length (also affects beat jump size, ignore repeat)
         if !repeat && bindings.increase_jump_size.iter().any(|b| b == &key_str) {
             if self.collection.loaded_track.is_some() {
@@ -17,6 +17,7 @@ pub mod reanalysis;
 pub mod settings;
 pub mod similarity;
 pub mod slicer;
+pub mod smart_playlist;
 pub mod stem_links;
 pub mod tick;
 pub mod track_loading;
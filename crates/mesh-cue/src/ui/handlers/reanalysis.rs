@@ -85,7 +85,8 @@ impl MeshCueApp {
         Task::none()
     }
 
-    /// Handle OpenMetadataReanalysisConfig — opens the config modal with all checkboxes ON
+    /// Handle OpenMetadataReanalysisConfig — opens the config modal with all
+    /// checkboxes ON except Auto Cues, which writes to the cue slots
     pub fn handle_open_metadata_reanalysis_config(&mut self, scope: ReanalysisScope) -> Task<Message> {
         self.context_menu_state.close();
 
@@ -95,6 +96,7 @@ impl MeshCueApp {
         self.reanalysis_state.config_loudness = true;
         self.reanalysis_state.config_key = true;
         self.reanalysis_state.config_tags = true;
+        self.reanalysis_state.config_cues = false;
         Task::none()
     }
 
@@ -122,6 +124,12 @@ impl MeshCueApp {
         Task::none()
     }
 
+    /// Handle checkbox toggle: Auto Cues
+    pub fn handle_toggle_reanalysis_cues(&mut self, value: bool) -> Task<Message> {
+        self.reanalysis_state.config_cues = value;
+        Task::none()
+    }
+
     /// Handle CloseReanalysisConfig — close the modal without starting
     pub fn handle_close_reanalysis_config(&mut self) -> Task<Message> {
         self.reanalysis_state.config_modal_open = false;
@@ -148,10 +156,11 @@ impl MeshCueApp {
             loudness: self.reanalysis_state.config_loudness,
             key: self.reanalysis_state.config_key,
             tags: self.reanalysis_state.config_tags,
+            cues: self.reanalysis_state.config_cues,
         };
 
         // At least one option must be ticked
-        if !options.name_artist && !options.loudness && !options.key && !options.tags {
+        if !options.name_artist && !options.loudness && !options.key && !options.tags && !options.cues {
             log::warn!("No metadata options selected, ignoring");
             return Task::none();
         }
@@ -162,8 +171,8 @@ impl MeshCueApp {
             return Task::none();
         }
 
-        log::info!("Starting Metadata re-analysis for {} tracks (name={}, loudness={}, key={}, tags={})",
-            tracks.len(), options.name_artist, options.loudness, options.key, options.tags);
+        log::info!("Starting Metadata re-analysis for {} tracks (name={}, loudness={}, key={}, tags={}, cues={})",
+            tracks.len(), options.name_artist, options.loudness, options.key, options.tags, options.cues);
 
         // Pause audio stream to free CPU for analysis
         if let Some(ref handle) = self.audio_handle {
@@ -321,7 +330,7 @@ impl MeshCueApp {
     /// key, BPM, beat grid, cue points, saved loops, or the audio file on
     /// disk. Audited 2026-05-09 against `reanalyze_metadata_track` in
     /// `crate::reanalysis` — `name_artist`/`loudness`/`key` branches all
-    /// gated by their respective flags. The defensive assertion below
+    /// gated by their respective flags (as is the later `cues` branch). The defensive assertion below
     /// guards against future accidental flag-flipping in this path.
     pub fn handle_accept_migration_reanalysis(&mut self) -> Task<Message> {
         // Configure the reanalysis state as if the user had ticked the
@@ -331,6 +340,7 @@ impl MeshCueApp {
         self.reanalysis_state.config_loudness = false;
         self.reanalysis_state.config_key = false;
         self.reanalysis_state.config_tags = true;
+        self.reanalysis_state.config_cues = false;
 
         // Defensive: hard-fail before dispatch if any non-tags flag is set.
         // Catches future bugs where another code path leaves stale state on
@@ -339,12 +349,14 @@ impl MeshCueApp {
             !self.reanalysis_state.config_name_artist
                 && !self.reanalysis_state.config_loudness
                 && !self.reanalysis_state.config_key
-                && self.reanalysis_state.config_tags,
-            "migration popup must dispatch tags-only reanalysis (name={}, loudness={}, key={}, tags={})",
+                && self.reanalysis_state.config_tags
+                && !self.reanalysis_state.config_cues,
+            "migration popup must dispatch tags-only reanalysis (name={}, loudness={}, key={}, tags={}, cues={})",
             self.reanalysis_state.config_name_artist,
             self.reanalysis_state.config_loudness,
             self.reanalysis_state.config_key,
             self.reanalysis_state.config_tags,
+            self.reanalysis_state.config_cues,
         );
         log::info!("[migration] AcceptMigrationReanalysis → tags-only reanalysis on EntireCollection");
 
@@ -59,6 +59,24 @@ impl MeshCueApp {
         Task::none()
     }
 
+    /// Handle UpdateSettingsTagHints message
+    pub fn handle_update_settings_tag_hints(&mut self, mode: crate::config::TagHints) -> Task<Message> {
+        self.settings.draft_tag_hints = mode;
+        Task::none()
+    }
+
+    /// Handle UpdateSettingsPhraseHotCues message
+    pub fn handle_update_settings_phrase_hot_cues(&mut self, enabled: bool) -> Task<Message> {
+        self.settings.draft_phrase_hot_cues = enabled;
+        Task::none()
+    }
+
+    /// Handle UpdateSettingsAutoCues message
+    pub fn handle_update_settings_auto_cues(&mut self, enabled: bool) -> Task<Message> {
+        self.settings.draft_auto_cues = enabled;
+        Task::none()
+    }
+
     /// Handle UpdateSettingsSlicerBufferBars message
     pub fn handle_update_settings_slicer_buffer_bars(&mut self, bars: u32) -> Task<Message> {
         self.settings.draft_slicer_buffer_bars = bars;
@@ -144,6 +162,9 @@ impl MeshCueApp {
             config.analysis.bpm.min_tempo = min;
             config.analysis.bpm.max_tempo = max;
             config.analysis.bpm.source = self.settings.draft_bpm_source;
+            config.analysis.tag_hints = self.settings.draft_tag_hints;
+            config.analysis.phrase_hot_cues = self.settings.draft_phrase_hot_cues;
+            config.analysis.auto_cues.enabled = self.settings.draft_auto_cues;
             config.analysis.validate();
 
             // Update track name format
@@ -0,0 +1,78 @@
+//! Smart playlist editor message handlers
+//!
+//! Handles: SmartPlaylist (New, Edit, SetField, SetMatchAll,
+//! SetNotPlayedInSession, SetOrder, Save, Close)
+
+use iced::Task;
+use super::super::app::MeshCueApp;
+use super::super::message::Message;
+use super::super::smart_playlist_modal::SmartPlaylistMessage;
+
+impl MeshCueApp {
+    /// Handle SmartPlaylist messages
+    pub fn handle_smart_playlist(&mut self, msg: SmartPlaylistMessage) -> Task<Message> {
+        match msg {
+            SmartPlaylistMessage::New(parent) => {
+                self.context_menu_state.close();
+                self.smart_playlist_state.open_new(parent);
+            }
+            SmartPlaylistMessage::Edit(id) => {
+                self.context_menu_state.close();
+                match self.domain.smart_playlist_rules(&id) {
+                    Ok(Some(rules)) => self.smart_playlist_state.open_edit(id, &rules),
+                    Ok(None) => log::warn!("{:?} is not a smart playlist", id),
+                    Err(e) => log::error!("Failed to load smart playlist rules: {:?}", e),
+                }
+            }
+            SmartPlaylistMessage::SetField(field, value) => {
+                *self.smart_playlist_state.field_mut(field) = value;
+            }
+            SmartPlaylistMessage::SetMatchAll(v) => self.smart_playlist_state.match_all = v,
+            SmartPlaylistMessage::SetNotPlayedInSession(v) => {
+                self.smart_playlist_state.not_played_in_session = v;
+            }
+            SmartPlaylistMessage::SetOrder(order) => self.smart_playlist_state.order = order,
+            SmartPlaylistMessage::Save => return self.save_smart_playlist(),
+            SmartPlaylistMessage::Close => self.smart_playlist_state.close(),
+        }
+        Task::none()
+    }
+
+    /// Create or update the smart playlist from the editor form
+    fn save_smart_playlist(&mut self) -> Task<Message> {
+        let state = &self.smart_playlist_state;
+        let rules = match state.to_rules() {
+            Ok(rules) => rules,
+            Err(e) => {
+                self.smart_playlist_state.error = Some(e);
+                return Task::none();
+            }
+        };
+
+        let result = if let Some(id) = state.editing.clone() {
+            self.domain.set_smart_playlist_rules(&id, &rules).map(|_| id)
+        } else {
+            let name = state.name.trim().to_string();
+            if name.is_empty() || name.contains('/') {
+                self.smart_playlist_state.error = Some("Enter a name without '/'".to_string());
+                return Task::none();
+            }
+            let parent = state.parent.clone().unwrap_or_else(mesh_core::playlist::NodeId::playlists);
+            self.domain.create_smart_playlist(&parent, &name, &rules)
+        };
+
+        match result {
+            Ok(id) => {
+                log::info!("Saved smart playlist {:?} ({} rules)", id, rules.rules.len());
+                self.smart_playlist_state.close();
+                // Re-evaluates the playlist if either browser is showing it
+                self.handle_refresh_playlists()
+            }
+            Err(e) => {
+                log::error!("Failed to save smart playlist: {:?}", e);
+                self.smart_playlist_state.error = Some(e.to_string());
+                Task::none()
+            }
+        }
+    }
+}
@@ -105,6 +105,9 @@ impl MeshCueApp {
                     key,
                     beat_grid,
                     first_beat_sample,
+                    tempo_anchors: metadata.beat_grid.anchors.clone(),
+                    phrases: metadata.phrases.clone(),
+                    key_timeline: metadata.key_timeline.clone(),
                     drop_marker: metadata.drop_marker,
                     lufs: metadata.lufs,
                     stem_links: metadata.stem_links.clone(),
@@ -230,12 +233,17 @@ impl MeshCueApp {
                                 beat_grid: BeatGrid {
                                     beats: state.beat_grid.clone(),
                                     first_beat_sample: Some(state.first_beat_sample),
+                                    // The editor plays at native tempo: the deck follows
+                                    // the grid itself, not per-segment stretch ratios
+                                    anchors: Vec::new(),
                                 },
                                 cue_points: state.cue_points.clone(),
                                 saved_loops: state.saved_loops.clone(),
                                 drop_marker: state.drop_marker,
                                 stem_links: state.stem_links.clone(),
                                 lufs: None,
+                                phrases: state.phrases.clone(),
+                                key_timeline: state.key_timeline.clone(),
                             },
                             duration_samples: duration,
                             duration_seconds,
@@ -351,12 +359,15 @@ impl MeshCueApp {
                             beat_grid: BeatGrid {
                                 beats: state.beat_grid.clone(),
                                 first_beat_sample: Some(state.first_beat_sample),
+                                anchors: Vec::new(),
                             },
                             cue_points: state.cue_points.clone(),
                             saved_loops: state.saved_loops.clone(),
                             drop_marker: state.drop_marker,
                             stem_links: state.stem_links.clone(),
                             lufs: None,
+                            phrases: state.phrases.clone(),
+                            key_timeline: state.key_timeline.clone(),
                         },
                         duration_samples: duration_samples as usize,
                         duration_seconds,
@@ -1,13 +1,16 @@
 //! Import modal UI
 //!
 //! Provides a modal dialog for batch importing audio files from the import folder.
-//! Supports two modes:
+//! Supports three modes:
 //! - **Stems mode**: Import pre-separated stem files (Artist - Track_(Vocals).wav, etc.)
 //! - **Mixed audio mode**: Import regular audio files and auto-separate into stems
+//! - **DJ library mode**: Import cues, grids and playlists from a Rekordbox XML or
+//!   Traktor NML file onto tracks already in the collection
 
 use super::app::{ImportPhase, ImportState, Message};
 use super::state::ImportMode;
 use crate::batch_import::{MixedAudioFile, StemGroup};
+use crate::library_import::{LibraryFile, LibraryImportSummary};
 use iced::widget::{button, column, container, progress_bar, row, scrollable, text, Space};
 use iced::{Alignment, Element, Length};
 use mesh_widgets::sz;
@@ -21,6 +24,7 @@ pub fn view(state: &ImportState, use_advanced_beats: bool) -> Element<'_, Messag
     let title_text = match state.import_mode {
         ImportMode::Stems => "Import Stems",
         ImportMode::MixedAudio => "Import Audio (Auto-Separate)",
+        ImportMode::DjLibrary => "Import DJ Library",
     };
     let title = text(title_text).size(sz(24.0));
     let close_btn = button(text("×").size(sz(20.0)))
@@ -50,7 +54,16 @@ pub fn view(state: &ImportState, use_advanced_beats: bool) -> Element<'_, Messag
             .style(button::secondary)
             .into()
     };
-    let mode_toggle = row![stems_btn, mixed_btn].spacing(8);
+    let library_btn_base = button(text("DJ Library"));
+    let library_btn: Element<Message> = if state.import_mode == ImportMode::DjLibrary {
+        library_btn_base.style(button::primary).into()
+    } else {
+        library_btn_base
+            .on_press(Message::SetImportMode(ImportMode::DjLibrary))
+            .style(button::secondary)
+            .into()
+    };
+    let mode_toggle = row![stems_btn, mixed_btn, library_btn].spacing(8);
 
     // Import folder display
     let folder_label = text("Import Folder:").size(sz(14.0));
@@ -65,7 +78,12 @@ pub fn view(state: &ImportState, use_advanced_beats: bool) -> Element<'_, Messag
         Some(ImportPhase::Processing { current_track, completed, total, start_time }) => {
             view_processing(current_track, *completed, *total, start_time, use_advanced_beats)
         }
-        Some(ImportPhase::Complete { duration }) => view_complete(duration, &state.results),
+        Some(ImportPhase::Complete { duration }) => match &state.library_result {
+            Some(result) if state.import_mode == ImportMode::DjLibrary => {
+                view_library_complete(duration, result)
+            }
+            _ => view_complete(duration, &state.results),
+        },
     };
 
     let body = column![header, mode_toggle, folder_section, content]
@@ -83,6 +101,7 @@ fn view_scan_results(state: &ImportState) -> Element<'_, Message> {
     match state.import_mode {
         ImportMode::Stems => view_scan_results_stems(state),
         ImportMode::MixedAudio => view_scan_results_mixed(state),
+        ImportMode::DjLibrary => view_scan_results_libraries(state),
     }
 }
 
@@ -222,6 +241,86 @@ fn view_scan_results_mixed(state: &ImportState) -> Element<'_, Message> {
         .into()
 }
 
+/// View scan results for Rekordbox / Traktor library files
+fn view_scan_results_libraries(state: &ImportState) -> Element<'_, Message> {
+    let libraries = &state.detected_libraries;
+
+    let libraries_title = text("Detected Libraries").size(sz(18.0));
+
+    let libraries_list: Element<Message> = if libraries.is_empty() {
+        column![
+            text("No DJ library files found in import folder.").size(sz(14.0)),
+            text("Place a rekordbox.xml (File → Export Collection) or Traktor collection.nml here").size(sz(12.0)),
+        ]
+        .spacing(5)
+        .into()
+    } else {
+        let items: Vec<Element<Message>> = libraries
+            .iter()
+            .enumerate()
+            .map(|(index, library)| view_library_file(index, library))
+            .collect();
+
+        scrollable(column(items).spacing(8))
+            .height(Length::Fixed(300.0))
+            .into()
+    };
+
+    let note = text(
+        "Cues, loops, beat grids, ratings and playlists are applied to tracks already in the \
+         collection, matched by file name. Playlists go into a Rekordbox / Traktor folder.",
+    )
+    .size(sz(11.0))
+    .color(iced::Color::from_rgb(0.5, 0.5, 0.5));
+
+    let refresh_btn = button(text("Refresh"))
+        .on_press(Message::ScanImportFolder)
+        .style(button::secondary);
+
+    let cancel_btn = button(text("Cancel"))
+        .on_press(Message::CloseImport)
+        .style(button::secondary);
+
+    let actions = row![refresh_btn, Space::new().width(Length::Fill), cancel_btn]
+        .spacing(10)
+        .width(Length::Fill);
+
+    column![libraries_title, libraries_list, note, actions]
+        .spacing(15)
+        .into()
+}
+
+/// View a single library file with its import button
+fn view_library_file(index: usize, library: &LibraryFile) -> Element<'_, Message> {
+    let name = text(library.file_name()).size(sz(14.0));
+    let kind_text = text(library.kind.display_name())
+        .size(sz(11.0))
+        .color(iced::Color::from_rgb(0.5, 0.5, 0.5));
+    let import_btn = button(text("Import").size(sz(12.0)))
+        .on_press(Message::StartLibraryImport(index))
+        .style(button::primary);
+
+    container(
+        row![name, Space::new().width(Length::Fill), kind_text, import_btn]
+            .spacing(10)
+            .align_y(Alignment::Center),
+    )
+    .padding(8)
+    .width(Length::Fill)
+    .style(|theme: &iced::Theme| {
+        let palette = theme.extended_palette();
+        container::Style {
+            background: Some(iced::Background::Color(palette.background.weak.color)),
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .into()
+}
+
 /// View a single stem group
 fn view_stem_group(group: &StemGroup) -> Element<'_, Message> {
     let name = text(&group.base_name).size(sz(14.0));
@@ -442,6 +541,78 @@ fn view_complete(
         .into()
 }
 
+/// View when a library import is complete
+fn view_library_complete(
+    duration: &std::time::Duration,
+    result: &Result<LibraryImportSummary, String>,
+) -> Element<'static, Message> {
+    let ok_btn = button(text("OK"))
+        .on_press(Message::DismissImportResults)
+        .style(button::primary);
+    let actions = row![Space::new().width(Length::Fill), ok_btn]
+        .width(Length::Fill);
+
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(e) => {
+            return column![
+                text("Library import failed").size(sz(16.0)),
+                text(e.clone())
+                    .size(sz(12.0))
+                    .color(iced::Color::from_rgb(0.9, 0.3, 0.3)),
+                actions,
+            ]
+            .spacing(15)
+            .into();
+        }
+    };
+
+    let headline = text(format!(
+        "{} tracks matched, {} not in collection",
+        summary.matched,
+        summary.unmatched.len()
+    ))
+    .size(sz(16.0));
+
+    let details = text(format!(
+        "{} hot cues, {} loops, {} beat grids, {} ratings, {} playlists · {:.1}s",
+        summary.cues,
+        summary.loops,
+        summary.grids,
+        summary.ratings,
+        summary.playlists,
+        duration.as_secs_f64()
+    ))
+    .size(sz(12.0));
+
+    // Unmatched entries: usually tracks that haven't been imported into mesh yet
+    let unmatched: Element<Message> = if summary.unmatched.is_empty() {
+        Space::new().height(0).into()
+    } else {
+        let items: Vec<Element<Message>> = summary
+            .unmatched
+            .iter()
+            .map(|name| {
+                text(format!("• {}", name))
+                    .size(sz(12.0))
+                    .color(iced::Color::from_rgb(0.8, 0.6, 0.2))
+                    .into()
+            })
+            .collect();
+
+        column![
+            text("Not in collection (import the audio first):").size(sz(14.0)),
+            scrollable(column(items).spacing(4)).height(Length::Fixed(100.0)),
+        ]
+        .spacing(5)
+        .into()
+    };
+
+    column![headline, details, unmatched, actions]
+        .spacing(15)
+        .into()
+}
+
 /// Render a compact progress bar for the collection browser bottom
 ///
 /// This is displayed at the bottom of the collection view while import is running.
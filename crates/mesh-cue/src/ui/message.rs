@@ -12,7 +12,9 @@ use mesh_widgets::PlaylistBrowserMessage;
 
 use crate::analysis::{ReanalysisProgress, ReanalysisScope};
 use crate::batch_import::{ImportProgress, MixedAudioFile, StemGroup};
-use crate::config::{BackendType, BpmSource, ModelType};
+use crate::library_export::LibraryExportSummary;
+use crate::library_import::{LibraryFile, LibraryImportSummary};
+use crate::config::{BackendType, BpmSource, ModelType, TagHints};
 use mesh_core::usb::UsbMessage;
 use mesh_widgets::MultibandEditorMessage;
 use super::context_menu::ContextMenuKind;
@@ -149,6 +151,16 @@ pub enum Message {
     NudgeBeatGridRight,
     /// Align beat grid so the nearest beat matches the current playhead
     AlignBeatGridToPlayhead,
+    /// Start a new tempo segment at the beat nearest to the playhead
+    AddTempoMarker,
+    /// Remove the tempo marker starting the segment under the playhead
+    RemoveTempoMarker,
+
+    // Rating
+    /// Add (positive) or remove (negative) stars on the loaded track
+    StepTrackRating(i8),
+    /// Move the loaded track to the next colour label
+    CycleTrackColorLabel,
 
     // Settings
     OpenSettings,
@@ -158,6 +170,12 @@ pub enum Message {
     UpdateSettingsTrackNameFormat(String),
     UpdateSettingsGridBars(u32),
     UpdateSettingsBpmSource(BpmSource),
+    /// Update draft use of tag BPM / key during import
+    UpdateSettingsTagHints(TagHints),
+    /// Update draft auto phrase hot cues on import
+    UpdateSettingsPhraseHotCues(bool),
+    /// Update draft auto-cue template on import
+    UpdateSettingsAutoCues(bool),
     UpdateSettingsSlicerBufferBars(u32),
     /// Update selected audio output pair
     UpdateSettingsOutputPair(usize),
@@ -221,7 +239,7 @@ pub enum Message {
     OpenImport,
     /// Close the import modal
     CloseImport,
-    /// Switch import mode (stems vs mixed audio)
+    /// Switch import mode (stems, mixed audio, DJ library)
     SetImportMode(ImportMode),
     /// Scan the import folder for stem groups
     ScanImportFolder,
@@ -233,6 +251,12 @@ pub enum Message {
     StartBatchImport,
     /// Start the mixed audio import process (with stem separation)
     StartMixedAudioImport,
+    /// Library folder scan complete (Rekordbox / Traktor files)
+    LibraryFilesScanned(Vec<LibraryFile>),
+    /// Import the detected library file at this index
+    StartLibraryImport(usize),
+    /// Library import finished
+    LibraryImportComplete(Result<LibraryImportSummary, String>),
     /// Progress update from import thread
     ImportProgressUpdate(ImportProgress),
     /// Cancel the current import
@@ -281,6 +305,7 @@ pub enum Message {
     ToggleReanalysisLoudness(bool),
     ToggleReanalysisKey(bool),
     ToggleReanalysisTags(bool),
+    ToggleReanalysisCues(bool),
     /// Confirm metadata reanalysis from modal
     ConfirmMetadataReanalysis,
     /// Close metadata reanalysis config modal
@@ -316,6 +341,17 @@ pub enum Message {
     /// Dismiss export results
     DismissExportResults,
 
+    // Library Export
+    /// Write a playlist subtree as Rekordbox XML / Traktor NML / M3U8 / PLS
+    /// (`mixdown`: also render stereo WAVs of the stem files)
+    ExportLibraryFiles { root: NodeId, mixdown: bool },
+    /// Library export finished
+    LibraryExportComplete(Result<LibraryExportSummary, String>),
+
+    // Smart Playlists
+    /// Smart playlist rule editor
+    SmartPlaylist(super::smart_playlist_modal::SmartPlaylistMessage),
+
     // Effects Editor
     /// Open the effects editor modal
     OpenEffectsEditor,
@@ -17,6 +17,7 @@ pub mod plugin_gui;
 pub mod saved_loop_buttons;
 pub mod settings;
 pub mod slicer_editor;
+pub mod smart_playlist_modal;
 pub mod state;
 pub mod transport;
 pub mod utils;
@@ -3,9 +3,9 @@
 //! Provides a modal dialog for editing application configuration.
 
 use super::app::{Message, SettingsState};
-use crate::config::{BackendType, BpmSource, ModelType, SeparationConfig};
+use crate::config::{BackendType, BpmSource, ModelType, SeparationConfig, TagHints};
 use mesh_widgets::{sz, AppFont, FontSize};
-use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input, Space};
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Space};
 use mesh_core::engine::InterpolationMethod;
 use iced::{Alignment, Element, Length};
 
@@ -218,6 +218,48 @@ fn view_bpm_section(state: &SettingsState) -> Element<'_, Message> {
     .spacing(10)
     .align_y(Alignment::Center);
 
+    // Tag BPM / key subsection
+    let tags_title = text("Tag BPM / Key").size(sz(14.0));
+    let tags_hint = text(
+        "Hint fixes half/double tempo and major/minor mix-ups using the file's tags; \
+         Override uses tag values as-is",
+    )
+    .size(sz(12.0));
+
+    let tag_buttons: Vec<Element<Message>> = [TagHints::Ignore, TagHints::Hint, TagHints::Override]
+        .iter()
+        .map(|&mode| {
+            let is_selected = state.draft_tag_hints == mode;
+            button(text(mode.to_string()).size(sz(12.0)))
+                .on_press(Message::UpdateSettingsTagHints(mode))
+                .style(if is_selected {
+                    iced::widget::button::primary
+                } else {
+                    iced::widget::button::secondary
+                })
+                .width(Length::Fixed(90.0))
+                .into()
+        })
+        .collect();
+
+    let tags_label = text("Tags:").size(sz(14.0));
+    let tags_row = row![
+        tags_label,
+        row(tag_buttons).spacing(4).align_y(Alignment::Center),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center);
+
+    // Song structure subsection
+    let phrase_title = text("Song Structure").size(sz(14.0));
+    let phrase_toggle = checkbox(state.draft_phrase_hot_cues)
+        .label("Place hot cues at section changes on import (empty slots only)")
+        .on_toggle(Message::UpdateSettingsPhraseHotCues)
+        .size(16);
+    let auto_cue_toggle = checkbox(state.draft_auto_cues)
+        .label("Apply the auto-cue template on import (cues and loops, empty slots only)")
+        .on_toggle(Message::UpdateSettingsAutoCues)
+        .size(16);
 
     container(
         column![
@@ -230,6 +272,14 @@ fn view_bpm_section(state: &SettingsState) -> Element<'_, Message> {
             source_title,
             source_hint,
             source_row,
+            Space::new().height(10),
+            tags_title,
+            tags_hint,
+            tags_row,
+            Space::new().height(10),
+            phrase_title,
+            phrase_toggle,
+            auto_cue_toggle,
         ]
         .spacing(10),
     )
@@ -0,0 +1,403 @@
+//! Smart playlist rule editor modal
+//!
+//! Edits the rule set of a new or existing smart playlist. Every rule is a
+//! plain text field; empty fields are left out of the rule set. Intensity
+//! and vocal presence are entered as percentages.
+
+use super::app::Message;
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Space};
+use iced::{Alignment, Element, Length};
+use mesh_core::playlist::{NodeId, SmartMatch, SmartOrder, SmartPlaylistRules, SmartRule};
+use mesh_widgets::sz;
+
+/// Text fields of the rule form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmartField {
+    Name,
+    BpmMin,
+    BpmMax,
+    Keys,
+    Genres,
+    IntensityMin,
+    IntensityMax,
+    TagsAny,
+    TagsAll,
+    LufsMin,
+    LufsMax,
+    VocalsMin,
+    VocalsMax,
+    PlaysMin,
+    PlaysMax,
+    AddedDays,
+    Limit,
+}
+
+/// Messages for the smart playlist editor
+#[derive(Debug, Clone)]
+pub enum SmartPlaylistMessage {
+    /// Open the editor to create a smart playlist under a parent
+    New(NodeId),
+    /// Open the editor for an existing smart playlist
+    Edit(NodeId),
+    /// Text field edited
+    SetField(SmartField, String),
+    /// Match all rules (true) or any rule (false)
+    SetMatchAll(bool),
+    /// Toggle the "not played in session" rule
+    SetNotPlayedInSession(bool),
+    /// Track order
+    SetOrder(SmartOrder),
+    /// Create or update the playlist
+    Save,
+    /// Close without saving
+    Close,
+}
+
+/// State for the smart playlist editor
+#[derive(Debug, Clone, Default)]
+pub struct SmartPlaylistState {
+    /// Whether the modal is open
+    pub is_open: bool,
+    /// Parent for a new playlist
+    pub parent: Option<NodeId>,
+    /// Playlist being edited (None when creating)
+    pub editing: Option<NodeId>,
+    pub name: String,
+    pub match_all: bool,
+    pub bpm_min: String,
+    pub bpm_max: String,
+    pub keys: String,
+    pub genres: String,
+    pub intensity_min: String,
+    pub intensity_max: String,
+    pub tags_any: String,
+    pub tags_all: String,
+    pub lufs_min: String,
+    pub lufs_max: String,
+    pub vocals_min: String,
+    pub vocals_max: String,
+    pub plays_min: String,
+    pub plays_max: String,
+    pub added_days: String,
+    pub not_played_in_session: bool,
+    pub order: SmartOrder,
+    pub limit: String,
+    /// Validation error from the last save attempt
+    pub error: Option<String>,
+}
+
+fn fmt_opt<T: ToString>(v: Option<T>) -> String {
+    v.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn fmt_percent(v: Option<f32>) -> String {
+    v.map(|v| format!("{}", (v * 100.0).round())).unwrap_or_default()
+}
+
+fn join(items: &[String]) -> String {
+    items.join(", ")
+}
+
+fn split(s: &str) -> Vec<String> {
+    s.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+}
+
+/// Parse an optional number; empty means unset
+fn parse<T: std::str::FromStr>(s: &str, label: &str) -> Result<Option<T>, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.parse().map(Some).map_err(|_| format!("{}: '{}' is not a number", label, s))
+}
+
+fn parse_percent(s: &str, label: &str) -> Result<Option<f32>, String> {
+    Ok(parse::<f32>(s, label)?.map(|p| (p / 100.0).clamp(0.0, 1.0)))
+}
+
+impl SmartPlaylistState {
+    /// Open the editor for a new smart playlist
+    pub fn open_new(&mut self, parent: NodeId) {
+        *self = Self {
+            is_open: true,
+            parent: Some(parent),
+            name: "New Smart Playlist".to_string(),
+            match_all: true,
+            ..Default::default()
+        };
+    }
+
+    /// Open the editor with an existing playlist's rules
+    pub fn open_edit(&mut self, id: NodeId, rules: &SmartPlaylistRules) {
+        *self = Self {
+            is_open: true,
+            name: id.name().to_string(),
+            editing: Some(id),
+            match_all: rules.match_mode == SmartMatch::All,
+            order: rules.order,
+            limit: fmt_opt(rules.limit),
+            ..Default::default()
+        };
+        for rule in &rules.rules {
+            match rule {
+                SmartRule::Bpm { min, max } => {
+                    self.bpm_min = fmt_opt(*min);
+                    self.bpm_max = fmt_opt(*max);
+                }
+                SmartRule::Key { keys } => self.keys = join(keys),
+                SmartRule::Genre { genres } => self.genres = join(genres),
+                SmartRule::Intensity { min, max } => {
+                    self.intensity_min = fmt_percent(*min);
+                    self.intensity_max = fmt_percent(*max);
+                }
+                SmartRule::TagsAny { tags } => self.tags_any = join(tags),
+                SmartRule::TagsAll { tags } => self.tags_all = join(tags),
+                SmartRule::Lufs { min, max } => {
+                    self.lufs_min = fmt_opt(*min);
+                    self.lufs_max = fmt_opt(*max);
+                }
+                SmartRule::Vocals { min, max } => {
+                    self.vocals_min = fmt_percent(*min);
+                    self.vocals_max = fmt_percent(*max);
+                }
+                SmartRule::PlayCount { min, max } => {
+                    self.plays_min = fmt_opt(*min);
+                    self.plays_max = fmt_opt(*max);
+                }
+                SmartRule::AddedWithinDays { days } => self.added_days = days.to_string(),
+                SmartRule::NotPlayedInSession => self.not_played_in_session = true,
+            }
+        }
+    }
+
+    /// Close the editor
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Mutable access to a text field
+    pub fn field_mut(&mut self, field: SmartField) -> &mut String {
+        match field {
+            SmartField::Name => &mut self.name,
+            SmartField::BpmMin => &mut self.bpm_min,
+            SmartField::BpmMax => &mut self.bpm_max,
+            SmartField::Keys => &mut self.keys,
+            SmartField::Genres => &mut self.genres,
+            SmartField::IntensityMin => &mut self.intensity_min,
+            SmartField::IntensityMax => &mut self.intensity_max,
+            SmartField::TagsAny => &mut self.tags_any,
+            SmartField::TagsAll => &mut self.tags_all,
+            SmartField::LufsMin => &mut self.lufs_min,
+            SmartField::LufsMax => &mut self.lufs_max,
+            SmartField::VocalsMin => &mut self.vocals_min,
+            SmartField::VocalsMax => &mut self.vocals_max,
+            SmartField::PlaysMin => &mut self.plays_min,
+            SmartField::PlaysMax => &mut self.plays_max,
+            SmartField::AddedDays => &mut self.added_days,
+            SmartField::Limit => &mut self.limit,
+        }
+    }
+
+    /// Build the rule set from the form, skipping empty fields
+    pub fn to_rules(&self) -> Result<SmartPlaylistRules, String> {
+        let mut rules = Vec::new();
+        let (min, max) = (parse(&self.bpm_min, "BPM")?, parse(&self.bpm_max, "BPM")?);
+        if min.is_some() || max.is_some() {
+            rules.push(SmartRule::Bpm { min, max });
+        }
+        let keys = split(&self.keys);
+        if !keys.is_empty() {
+            if let Some(bad) = keys.iter().find(|k| {
+                mesh_core::music::MusicalKey::parse_camelot(k).is_none()
+                    && mesh_core::music::MusicalKey::parse(k).is_none()
+            }) {
+                return Err(format!("Key: '{}' is not a key", bad));
+            }
+            rules.push(SmartRule::Key { keys });
+        }
+        let genres = split(&self.genres);
+        if !genres.is_empty() {
+            rules.push(SmartRule::Genre { genres });
+        }
+        let (min, max) = (
+            parse_percent(&self.intensity_min, "Intensity")?,
+            parse_percent(&self.intensity_max, "Intensity")?,
+        );
+        if min.is_some() || max.is_some() {
+            rules.push(SmartRule::Intensity { min, max });
+        }
+        let tags = split(&self.tags_any);
+        if !tags.is_empty() {
+            rules.push(SmartRule::TagsAny { tags });
+        }
+        let tags = split(&self.tags_all);
+        if !tags.is_empty() {
+            rules.push(SmartRule::TagsAll { tags });
+        }
+        let (min, max) = (parse(&self.lufs_min, "LUFS")?, parse(&self.lufs_max, "LUFS")?);
+        if min.is_some() || max.is_some() {
+            rules.push(SmartRule::Lufs { min, max });
+        }
+        let (min, max) = (
+            parse_percent(&self.vocals_min, "Vocals")?,
+            parse_percent(&self.vocals_max, "Vocals")?,
+        );
+        if min.is_some() || max.is_some() {
+            rules.push(SmartRule::Vocals { min, max });
+        }
+        let (min, max) = (parse(&self.plays_min, "Play count")?, parse(&self.plays_max, "Play count")?);
+        if min.is_some() || max.is_some() {
+            rules.push(SmartRule::PlayCount { min, max });
+        }
+        if let Some(days) = parse(&self.added_days, "Added within")? {
+            rules.push(SmartRule::AddedWithinDays { days });
+        }
+        if self.not_played_in_session {
+            rules.push(SmartRule::NotPlayedInSession);
+        }
+
+        Ok(SmartPlaylistRules {
+            match_mode: if self.match_all { SmartMatch::All } else { SmartMatch::Any },
+            rules,
+            order: self.order,
+            limit: parse(&self.limit, "Limit")?,
+        })
+    }
+}
+
+/// Labelled single-field row
+fn field_row<'a>(label: &'a str, placeholder: &'a str, value: &'a str, field: SmartField) -> Element<'a, Message> {
+    row![
+        text(label).size(sz(13.0)).width(Length::Fixed(130.0)),
+        text_input(placeholder, value)
+            .on_input(move |v| Message::SmartPlaylist(SmartPlaylistMessage::SetField(field, v)))
+            .size(sz(13.0))
+            .width(Length::Fill),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Labelled min/max row
+fn range_row<'a>(
+    label: &'a str,
+    (min, min_field): (&'a str, SmartField),
+    (max, max_field): (&'a str, SmartField),
+    unit: &'a str,
+) -> Element<'a, Message> {
+    row![
+        text(label).size(sz(13.0)).width(Length::Fixed(130.0)),
+        text_input("min", min)
+            .on_input(move |v| Message::SmartPlaylist(SmartPlaylistMessage::SetField(min_field, v)))
+            .size(sz(13.0))
+            .width(Length::Fixed(80.0)),
+        text("–").size(sz(13.0)),
+        text_input("max", max)
+            .on_input(move |v| Message::SmartPlaylist(SmartPlaylistMessage::SetField(max_field, v)))
+            .size(sz(13.0))
+            .width(Length::Fixed(80.0)),
+        text(unit).size(sz(12.0)),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Render the smart playlist editor
+pub fn view(state: &SmartPlaylistState) -> Element<'_, Message> {
+    let title = if state.editing.is_some() {
+        format!("Smart Playlist: {}", state.name)
+    } else {
+        "New Smart Playlist".to_string()
+    };
+
+    let mut body = column![text(title).size(sz(18.0))].spacing(8);
+
+    if state.editing.is_none() {
+        body = body.push(field_row("Name", "Smart playlist name", &state.name, SmartField::Name));
+    }
+
+    body = body.push(
+        checkbox(state.match_all)
+            .label("Match all rules (off: any rule)")
+            .on_toggle(|v| Message::SmartPlaylist(SmartPlaylistMessage::SetMatchAll(v)))
+            .size(16),
+    );
+
+    let rules = column![
+        range_row("BPM", (&state.bpm_min, SmartField::BpmMin), (&state.bpm_max, SmartField::BpmMax), ""),
+        field_row("Keys", "8A, 9A, Am", &state.keys, SmartField::Keys),
+        field_row("Genre contains", "techno, house", &state.genres, SmartField::Genres),
+        range_row("Intensity", (&state.intensity_min, SmartField::IntensityMin), (&state.intensity_max, SmartField::IntensityMax), "%"),
+        field_row("Any of tags", "Warmup, Peak", &state.tags_any, SmartField::TagsAny),
+        field_row("All of tags", "Vocal, Favourite", &state.tags_all, SmartField::TagsAll),
+        range_row("Loudness", (&state.lufs_min, SmartField::LufsMin), (&state.lufs_max, SmartField::LufsMax), "LUFS"),
+        range_row("Vocals", (&state.vocals_min, SmartField::VocalsMin), (&state.vocals_max, SmartField::VocalsMax), "%"),
+        range_row("Play count", (&state.plays_min, SmartField::PlaysMin), (&state.plays_max, SmartField::PlaysMax), ""),
+        field_row("Added within", "days", &state.added_days, SmartField::AddedDays),
+        checkbox(state.not_played_in_session)
+            .label("Not played in the current session")
+            .on_toggle(|v| Message::SmartPlaylist(SmartPlaylistMessage::SetNotPlayedInSession(v)))
+            .size(16),
+    ]
+    .spacing(8);
+
+    body = body
+        .push(Space::new().height(4))
+        .push(scrollable(rules).height(Length::Shrink))
+        .push(Space::new().height(4))
+        .push(
+            row![
+                text("Order").size(sz(13.0)).width(Length::Fixed(130.0)),
+                pick_list(SmartOrder::ALL, Some(state.order), |o| {
+                    Message::SmartPlaylist(SmartPlaylistMessage::SetOrder(o))
+                })
+                .width(Length::Fixed(160.0)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        )
+        .push(field_row("Limit", "all tracks", &state.limit, SmartField::Limit));
+
+    if let Some(ref error) = state.error {
+        body = body.push(
+            text(error.clone())
+                .size(sz(12.0))
+                .color(iced::Color::from_rgb(0.9, 0.3, 0.3)),
+        );
+    }
+
+    let save_label = if state.editing.is_some() { "Save" } else { "Create" };
+    let buttons = row![
+        button(text(save_label).size(sz(14.0)))
+            .on_press(Message::SmartPlaylist(SmartPlaylistMessage::Save))
+            .padding([8, 20])
+            .style(button::primary),
+        button(text("Cancel").size(sz(14.0)))
+            .on_press(Message::SmartPlaylist(SmartPlaylistMessage::Close))
+            .padding([8, 20])
+            .style(button::secondary),
+    ]
+    .spacing(12);
+
+    container(
+        body.push(Space::new().height(12))
+            .push(buttons)
+            .padding(24)
+            .width(Length::Fixed(520.0)),
+    )
+    .style(|theme: &iced::Theme| {
+        let palette = theme.extended_palette();
+        container::Style {
+            background: Some(iced::Background::Color(palette.background.strong.color)),
+            border: iced::Border {
+                color: palette.background.weak.color,
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            ..Default::default()
+        }
+    })
+    .into()
+}
@@ -276,8 +276,10 @@ impl Default for CollectionState {
         let editable_cols = &[TrackColumn::Name, TrackColumn::Artist, TrackColumn::Bpm, TrackColumn::Key];
         let mut browser_left = PlaylistBrowserState::new();
         browser_left.table_state.set_editable_columns(editable_cols);
+        browser_left.table_state.display_columns = Some(TrackColumn::library());
         let mut browser_right = PlaylistBrowserState::new();
         browser_right.table_state.set_editable_columns(editable_cols);
+        browser_right.table_state.display_columns = Some(TrackColumn::library());
 
         Self {
             collection_path: default_path,
@@ -1,6 +1,7 @@
 //! Batch import state
 
 use crate::batch_import::{self, ImportProgress, MixedAudioFile, StemGroup, TrackImportResult};
+use crate::library_import::{LibraryFile, LibraryImportSummary};
 use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::Receiver;
 use std::sync::Arc;
@@ -13,6 +14,9 @@ pub enum ImportMode {
     Stems,
     /// Import mixed audio files and auto-separate into stems
     MixedAudio,
+    /// Import cues, grids and playlists from a Rekordbox XML / Traktor NML
+    /// library onto tracks already in the collection
+    DjLibrary,
 }
 
 /// Phase of the batch import process
@@ -51,10 +55,14 @@ pub struct ImportState {
     pub detected_groups: Vec<StemGroup>,
     /// Detected mixed audio files from scan (for MixedAudio mode)
     pub detected_mixed_files: Vec<MixedAudioFile>,
+    /// Detected Rekordbox / Traktor library files (for DjLibrary mode)
+    pub detected_libraries: Vec<LibraryFile>,
     /// Current import phase (None if not importing)
     pub phase: Option<ImportPhase>,
     /// Results from completed import (for final popup)
     pub results: Vec<TrackImportResult>,
+    /// Result of the last library import (DjLibrary mode)
+    pub library_result: Option<Result<LibraryImportSummary, String>>,
     /// Show results popup after completion
     pub show_results: bool,
     /// Channel to receive progress updates from import thread
@@ -71,8 +79,10 @@ impl Default for ImportState {
             import_mode: ImportMode::default(),
             detected_groups: Vec::new(),
             detected_mixed_files: Vec::new(),
+            detected_libraries: Vec::new(),
             phase: None,
             results: Vec::new(),
+            library_result: None,
             show_results: false,
             progress_rx: None,
             cancel_flag: None,
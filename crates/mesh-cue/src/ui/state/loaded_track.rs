@@ -1,7 +1,10 @@
 //! Loaded track state for the track editor
 
 use basedrop::Shared;
-use mesh_core::audio_file::{CuePoint, LoadedTrack, SavedLoop, StemBuffers, StemLinkReference};
+use mesh_core::audio_file::{
+    CuePoint, KeySegment, LoadedTrack, PhraseSection, SavedLoop, StemBuffers, StemLinkReference,
+    TempoAnchor,
+};
 use mesh_core::engine::{DeckAtomics, LOOP_LENGTHS};
 use mesh_core::types::PlayState;
 use mesh_widgets::SliceEditorState;
@@ -39,6 +42,13 @@ pub struct LoadedTrackState {
     /// User-anchored downbeat sample position. Saved to metadata as
     /// `BeatGrid.first_beat_sample`; rendering uses it to align red markers.
     pub first_beat_sample: u64,
+    /// Tempo-change markers for variable-tempo tracks (empty = constant tempo).
+    /// When present, `tempo_anchors[0]` mirrors `first_beat_sample` and `bpm`.
+    pub tempo_anchors: Vec<TempoAnchor>,
+    /// Song-structure sections from analysis (read-only in the editor)
+    pub phrases: Vec<PhraseSection>,
+    /// Key timeline from analysis; saved back unchanged
+    pub key_timeline: Vec<KeySegment>,
     /// Drop marker sample position (for linked stem alignment)
     pub drop_marker: Option<u64>,
     /// Track's integrated LUFS (for gain matching with linked stems)
@@ -133,6 +143,22 @@ impl LoadedTrackState {
         self.deck_atomics.cue_point()
     }
 
+    /// Index of the tempo segment under the playhead (0 for constant-tempo tracks)
+    pub fn tempo_segment_at_playhead(&self) -> usize {
+        let playhead = self.playhead_position();
+        self.tempo_anchors
+            .partition_point(|a| a.sample_position <= playhead)
+            .saturating_sub(1)
+    }
+
+    /// BPM of the segment under the playhead (the track BPM for constant-tempo tracks)
+    pub fn bpm_at_playhead(&self) -> f64 {
+        self.tempo_anchors
+            .get(self.tempo_segment_at_playhead())
+            .map(|a| a.bpm)
+            .unwrap_or(self.bpm)
+    }
+
     /// Get beat jump size (same as loop length in beats, minimum 1)
     pub fn beat_jump_size(&self) -> i32 {
         self.loop_length_beats().max(1.0) as i32
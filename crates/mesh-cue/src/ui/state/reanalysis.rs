@@ -45,6 +45,8 @@ pub struct ReanalysisState {
     pub config_key: bool,
     /// Checkbox: ML tags (genre, mood, etc.)
     pub config_tags: bool,
+    /// Checkbox: auto-cue template on empty slots
+    pub config_cues: bool,
 }
 
 /// Snapshot of an in-flight model download for the footer status bar.
@@ -1,7 +1,7 @@
 //! Settings modal state
 
 use crate::audio::StereoPair;
-use crate::config::{BackendType, BpmSource, Config, ModelType};
+use crate::config::{BackendType, BpmSource, Config, ModelType, TagHints};
 use mesh_core::engine::InterpolationMethod;
 use mesh_widgets::{AppFont, FontSize};
 
@@ -20,6 +20,12 @@ pub struct SettingsState {
     pub draft_grid_bars: u32,
     /// Draft BPM source for analysis (drums-only or full mix)
     pub draft_bpm_source: BpmSource,
+    /// Draft use of tag BPM / key during import
+    pub draft_tag_hints: TagHints,
+    /// Draft auto hot cues at song-structure changes during import
+    pub draft_phrase_hot_cues: bool,
+    /// Draft auto-cue template on import (the template itself is config-only)
+    pub draft_auto_cues: bool,
     /// Draft slicer buffer bars (1, 4, 8, or 16)
     pub draft_slicer_buffer_bars: u32,
     /// Available audio output devices (for display only in CPAL mode)
@@ -60,6 +66,9 @@ impl SettingsState {
             draft_track_name_format: config.track_name_format.clone(),
             draft_grid_bars: config.display.grid_bars,
             draft_bpm_source: config.analysis.bpm.source,
+            draft_tag_hints: config.analysis.tag_hints,
+            draft_phrase_hot_cues: config.analysis.phrase_hot_cues,
+            draft_auto_cues: config.analysis.auto_cues.enabled,
             draft_slicer_buffer_bars: config.slicer.validated_buffer_bars(),
             available_stereo_pairs: Vec::new(),
             selected_output_pair: config.audio.output_device.unwrap_or(0),
@@ -3,7 +3,7 @@
 //! Functions for nudging, regenerating, and snapping to beat grids.
 
 use crate::ui::state::LoadedTrackState;
-use mesh_core::audio_file::BeatGrid;
+use mesh_core::audio_file::{BeatGrid, TempoAnchor};
 
 /// Nudge amount in samples (~2.5ms at 48kHz for fine-grained control)
 pub const BEAT_GRID_NUDGE_SAMPLES: i64 = 120;
@@ -21,6 +21,27 @@ pub fn nudge_beat_grid(state: &mut LoadedTrackState, delta_samples: i64) {
         return;
     }
 
+    // Variable-tempo grids: nudge only the tempo marker that starts the
+    // segment under the playhead, keeping it between its neighbours
+    if !state.tempo_anchors.is_empty() {
+        let segment = state.tempo_segment_at_playhead();
+        let new_position = state.tempo_anchors[segment].sample_position as i64 + delta_samples;
+        let lower = match segment {
+            0 => 0,
+            _ => state.tempo_anchors[segment - 1].sample_position as i64 + 1,
+        };
+        let upper = state.tempo_anchors
+            .get(segment + 1)
+            .map(|a| a.sample_position as i64 - 1)
+            .unwrap_or(state.duration_samples as i64 - 1);
+        if new_position < lower || new_position > upper {
+            return;
+        }
+        state.tempo_anchors[segment].sample_position = new_position as u64;
+        rebuild_tempo_grid(state);
+        return;
+    }
+
     // Nudge operates on the user-anchored downbeat (state.first_beat_sample),
     // NOT state.beat_grid[0] — after backfill, beat_grid[0] is a beat near
     // sample 0, not the user's anchor.
@@ -73,6 +94,77 @@ pub fn regenerate_beat_grid(anchor_sample: u64, bpm: f64, duration_samples: u64)
     (beats, anchor_idx)
 }
 
+/// Regenerate a variable-tempo beat grid from tempo anchors.
+///
+/// Each anchor starts a segment at its own BPM (see `BeatGrid::from_anchors`).
+/// Like `regenerate_beat_grid`, beats are backfilled before the first anchor
+/// at the first segment's tempo. Returns `(beats, anchor_idx)` where
+/// `anchor_idx` is the index of the first anchor within the returned Vec.
+pub fn regenerate_tempo_grid(anchors: &[TempoAnchor], duration_samples: u64) -> (Vec<u64>, usize) {
+    let Some(first) = anchors.first() else {
+        return (Vec::new(), 0);
+    };
+    if first.bpm <= 0.0 || duration_samples == 0 {
+        return (Vec::new(), 0);
+    }
+    let spb = SAMPLE_RATE_F64 * 60.0 / first.bpm;
+    let mut start = first.sample_position as f64;
+    let mut anchor_idx: usize = 0;
+    while start > spb {
+        start -= spb;
+        anchor_idx += 1;
+    }
+    let mut beats: Vec<u64> = (0..anchor_idx)
+        .map(|k| (start + k as f64 * spb).round() as u64)
+        .collect();
+    beats.extend(BeatGrid::from_anchors(anchors.to_vec(), duration_samples).beats);
+    (beats, anchor_idx)
+}
+
+/// Rebuild the beat grid of a variable-tempo track from its tempo anchors.
+///
+/// Keeps `first_beat_sample` and `bpm` in sync with the first anchor and
+/// marks the track as modified. Caller propagates the grid to the deck.
+pub fn rebuild_tempo_grid(state: &mut LoadedTrackState) {
+    let (beats, anchor_idx) = regenerate_tempo_grid(&state.tempo_anchors, state.duration_samples);
+    state.beat_grid = beats;
+    update_waveform_beat_grid(state, anchor_idx);
+    // The anchor itself, not the (rounded) backfilled beat, is what gets saved
+    if let Some(first) = state.tempo_anchors.first() {
+        state.bpm = first.bpm;
+        state.first_beat_sample = first.sample_position;
+    }
+    state.modified = true;
+}
+
+/// Remove the tempo marker that starts `segment` (never the first one).
+///
+/// When only the first anchor remains the track falls back to a constant-tempo
+/// grid at that anchor's position and BPM. Returns false if nothing was removed.
+/// Caller propagates the grid to the deck.
+pub fn remove_tempo_anchor(state: &mut LoadedTrackState, segment: usize) -> bool {
+    if segment == 0 || segment >= state.tempo_anchors.len() {
+        return false;
+    }
+    let removed = state.tempo_anchors.remove(segment);
+    log::debug!("Removed tempo marker at {}", removed.sample_position);
+
+    if state.tempo_anchors.len() > 1 {
+        rebuild_tempo_grid(state);
+    } else {
+        let first = state.tempo_anchors[0];
+        state.tempo_anchors.clear();
+        let (beats, anchor_idx) =
+            regenerate_beat_grid(first.sample_position, first.bpm, state.duration_samples);
+        state.beat_grid = beats;
+        update_waveform_beat_grid(state, anchor_idx);
+        state.bpm = first.bpm;
+        state.first_beat_sample = first.sample_position;
+        state.modified = true;
+    }
+    true
+}
+
 /// Update waveform beat grid markers after grid modification.
 ///
 /// `anchor_idx` is the index in `state.beat_grid` of the user's anchored
@@ -141,3 +233,112 @@ pub fn find_nearest_beat_with_index(beat_grid: &[u64], position: u64) -> (usize,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::waveform::CombinedWaveformView;
+    use mesh_core::db::{DatabaseService, TempoAnchor as DbTempoAnchor, Track};
+    use mesh_core::engine::DeckAtomics;
+    use mesh_widgets::SliceEditorState;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    const DURATION: u64 = 240 * 48_000;
+
+    fn state_with_anchors(anchors: Vec<TempoAnchor>) -> LoadedTrackState {
+        LoadedTrackState {
+            path: PathBuf::from("/music/track.flac"),
+            title: "Track".to_string(),
+            artist: None,
+            track: None,
+            stems: None,
+            cue_points: Vec::new(),
+            saved_loops: Vec::new(),
+            bpm: anchors[0].bpm,
+            key: "8A".to_string(),
+            beat_grid: Vec::new(),
+            first_beat_sample: anchors[0].sample_position,
+            tempo_anchors: anchors,
+            phrases: Vec::new(),
+            key_timeline: Vec::new(),
+            drop_marker: None,
+            lufs: None,
+            stem_links: Vec::new(),
+            duration_samples: DURATION,
+            modified: false,
+            combined_waveform: CombinedWaveformView::new(),
+            loading_audio: false,
+            deck_atomics: Arc::new(DeckAtomics::default()),
+            slice_editor: SliceEditorState::new(),
+        }
+    }
+
+    /// Save the editor state the way auto-save does and load it back
+    fn save_and_reload(state: &LoadedTrackState) -> mesh_core::audio_file::TrackMetadata {
+        let db = DatabaseService::in_memory("/music").unwrap();
+        let mut track = Track::new(&state.path, &state.title);
+        track.bpm = Some(state.bpm);
+        track.duration_seconds = DURATION as f64 / 48_000.0;
+        track.first_beat_sample = state.first_beat_sample as i64;
+        track.tempo_anchors = state.tempo_anchors.iter()
+            .enumerate()
+            .map(|(i, a)| DbTempoAnchor::from_runtime(0, i as u32, a))
+            .collect();
+        db.save_track(&track).unwrap();
+        db.get_track_metadata(&state.path.to_string_lossy()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn nudging_first_marker_moves_saved_downbeat() {
+        let mut state = state_with_anchors(vec![
+            TempoAnchor::new(10_000, 128.0),
+            TempoAnchor::new(2_000_000, 130.0),
+        ]);
+        rebuild_tempo_grid(&mut state);
+        nudge_beat_grid(&mut state, BEAT_GRID_NUDGE_SAMPLES);
+        assert_eq!(state.first_beat_sample, 10_120);
+        assert_eq!(state.tempo_anchors[0].sample_position, 10_120);
+    }
+
+    #[test]
+    fn removing_last_marker_round_trips_constant_grid() {
+        let mut state = state_with_anchors(vec![
+            TempoAnchor::new(10_007, 174.0),
+            TempoAnchor::new(2_000_000, 172.0),
+        ]);
+        rebuild_tempo_grid(&mut state);
+        assert!(!remove_tempo_anchor(&mut state, 0));
+        assert!(remove_tempo_anchor(&mut state, 1));
+
+        assert!(state.tempo_anchors.is_empty());
+        assert_eq!(state.first_beat_sample, 10_007);
+        assert_eq!(state.bpm, 174.0);
+
+        let reloaded = save_and_reload(&state);
+        assert!(reloaded.beat_grid.anchors.is_empty());
+        assert_eq!(reloaded.bpm, Some(174.0));
+        assert_eq!(reloaded.beat_grid.first_beat_sample, Some(10_007));
+        // The reloaded grid lays its beats where the editor showed them
+        let reloaded_beats = reloaded.beat_grid.beats;
+        assert!(state.beat_grid.ends_with(&reloaded_beats));
+    }
+
+    #[test]
+    fn removing_middle_marker_round_trips_tempo_grid() {
+        let mut state = state_with_anchors(vec![
+            TempoAnchor::new(10_007, 128.0),
+            TempoAnchor::new(1_000_000, 130.0),
+            TempoAnchor::new(4_000_000, 132.0),
+        ]);
+        rebuild_tempo_grid(&mut state);
+        assert!(remove_tempo_anchor(&mut state, 1));
+
+        assert_eq!(state.tempo_anchors.len(), 2);
+        assert_eq!(state.first_beat_sample, 10_007);
+
+        let reloaded = save_and_reload(&state);
+        assert_eq!(reloaded.beat_grid.anchors, state.tempo_anchors);
+        assert!(state.beat_grid.ends_with(&reloaded.beat_grid.beats));
+    }
+}
@@ -7,7 +7,7 @@ pub mod tree;
 
 // Re-export commonly used items
 pub use beat_grid::{
-    find_nearest_beat_with_index, nudge_beat_grid, regenerate_beat_grid, snap_to_nearest_beat,
-    update_waveform_beat_grid, BEAT_GRID_NUDGE_SAMPLES,
+    find_nearest_beat_with_index, nudge_beat_grid, rebuild_tempo_grid, regenerate_beat_grid,
+    regenerate_tempo_grid, remove_tempo_anchor, snap_to_nearest_beat, update_waveform_beat_grid, BEAT_GRID_NUDGE_SAMPLES,
 };
 pub use tree::{build_tree_nodes, get_tracks_for_folder};
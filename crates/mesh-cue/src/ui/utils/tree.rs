@@ -23,13 +23,14 @@ fn build_node_children(storage: &dyn PlaylistStorage, parent: &PlaylistNode) ->
                 NodeKind::CollectionFolder => TreeIcon::Folder,
                 NodeKind::PlaylistsRoot => TreeIcon::Folder,
                 NodeKind::Playlist => TreeIcon::Playlist,
+                NodeKind::SmartPlaylist => TreeIcon::SmartPlaylist,
                 _ => TreeIcon::Folder,
             };
 
             // Allow creating children in playlists root and playlist folders
             let allow_create = matches!(node.kind, NodeKind::PlaylistsRoot | NodeKind::Playlist);
-            // Allow renaming only playlist folders (not collection, not playlists root)
-            let allow_rename = matches!(node.kind, NodeKind::Playlist);
+            // Allow renaming only playlists (not collection, not playlists root)
+            let allow_rename = matches!(node.kind, NodeKind::Playlist | NodeKind::SmartPlaylist);
 
             TreeNode::with_children(
                 node.id.clone(),
@@ -68,6 +69,8 @@ pub fn get_tracks_for_folder(storage: &dyn PlaylistStorage, folder_id: &NodeId)
             if info.cue_count > 0 {
                 row = row.with_cue_count(info.cue_count);
             }
+            row = row.with_tag_metadata(info.album, info.genre, info.year, info.label, info.comment);
+            row = row.with_rating(info.rating, info.color_label.as_deref().and_then(parse_hex_color));
             row.track_path = Some(info.path.to_string_lossy().into_owned());
             if !info.tags.is_empty() {
                 let mut sorted_tags = info.tags.clone();
@@ -14,6 +14,33 @@ use std::path::{Path, PathBuf};
 pub struct MidiConfig {
     /// Device profiles (matched by port name)
     pub devices: Vec<DeviceProfile>,
+
+    /// MIDI clock output and input ports
+    #[serde(skip_serializing_if = "MidiClockConfig::is_disabled")]
+    pub clock: MidiClockConfig,
+}
+
+/// MIDI clock ports (both optional, matched like `port_match`)
+///
+/// Use different ports for output and input — following a clock that mesh
+/// itself sends would feed the tempo back into itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MidiClockConfig {
+    /// Output port substring to send 24 PPQN clock to (case-insensitive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Input port substring whose clock drives the global BPM (case-insensitive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+}
+
+impl MidiClockConfig {
+    /// Neither clock output nor input is configured
+    pub fn is_disabled(&self) -> bool {
+        self.output.is_none() && self.input.is_none()
+    }
 }
 
 /// Configuration for a specific controller device (MIDI, HID, or mixed)
@@ -243,8 +270,16 @@ pub struct ControlMapping {
     /// Values: "hot_cue", "slicer". None = always active (performance/default)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
+
+    /// Jog wheel resolution: relative ticks per platter revolution (`deck.jog`)
+    /// None = DEFAULT_JOG_TICKS_PER_REVOLUTION
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticks_per_revolution: Option<u32>,
 }
 
+/// Jog wheel ticks per revolution when a mapping doesn't set its own
+pub const DEFAULT_JOG_TICKS_PER_REVOLUTION: u32 = 128;
+
 /// Control behavior type
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -400,6 +435,17 @@ pub fn default_midi_config_path() -> PathBuf {
         .join("midi.yaml")
 }
 
+/// Get the directory scanned for user HID device descriptions
+///
+/// Returns: ~/Music/mesh-collection/hid-devices
+pub fn default_hid_descriptions_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Music")
+        .join("mesh-collection")
+        .join("hid-devices")
+}
+
 /// Load MIDI configuration from a YAML file
 ///
 /// If the file doesn't exist, returns an empty config (no devices).
@@ -633,6 +679,17 @@ devices:
         assert_eq!(config.devices[0].feedback[0].on_color, Some([127, 0, 0]));
     }
 
+    #[test]
+    fn test_yaml_parsing_clock() {
+        let config: MidiConfig = serde_yaml::from_str("clock:\n  output: \"MIDI Sport\"\n").unwrap();
+        assert_eq!(config.clock.output.as_deref(), Some("MIDI Sport"));
+        assert_eq!(config.clock.input, None);
+
+        // Configs without clock ports don't grow an empty section on save
+        let yaml = serde_yaml::to_string(&MidiConfig::default()).unwrap();
+        assert!(!yaml.contains("clock"));
+    }
+
     #[test]
     fn test_normalize_port_name() {
         use super::normalize_port_name;
@@ -98,6 +98,9 @@ const SLICER_COLOR: [u8; 3] = [0, 180, 200];     // Cyan (assigned preset)
 const SLICER_COLOR_DIM: [u8; 3] = [0, 12, 14];   // Dim cyan (empty slot)
 const BROWSE_COLOR: [u8; 3] = [200, 200, 200];    // White (browse mode active)
 const BROWSE_COLOR_DIM: [u8; 3] = [20, 20, 20];   // Dim white (browse mode inactive)
+const SAMPLER_COLOR: [u8; 3] = [200, 40, 120];    // Magenta (sampler slot playing)
+const SAMPLER_COLOR_DIM: [u8; 3] = [30, 6, 18];   // Dim magenta (slot loaded, idle)
+const SAMPLER_COLOR_EMPTY: [u8; 3] = [4, 4, 4];   // Near-off (empty slot)
 
 // Mode indicator / utility LED colors
 const HOT_CUE_MODE_COLOR: [u8; 3] = [0, 100, 200];     // Blue (hot cue mode active)
@@ -110,6 +113,10 @@ const SLIP_COLOR: [u8; 3] = [180, 100, 0];              // Amber (slip mode acti
 const SLIP_COLOR_DIM: [u8; 3] = [8, 8, 8];
 const KEY_MATCH_COLOR: [u8; 3] = [0, 150, 200];         // Teal (key match active)
 const KEY_MATCH_COLOR_DIM: [u8; 3] = [8, 8, 8];
+const TALKOVER_COLOR: [u8; 3] = [220, 0, 0];            // Red (talkover on air)
+const TALKOVER_COLOR_DIM: [u8; 3] = [8, 8, 8];
+const FX_BUS_COLOR: [u8; 3] = [0, 180, 90];             // Green (FX bus chain on)
+const FX_BUS_COLOR_DIM: [u8; 3] = [8, 8, 8];
 const LAYER_A_COLOR: [u8; 3] = [200, 0, 0];             // Red (layer A)
 const LAYER_B_COLOR: [u8; 3] = [0, 200, 0];             // Green (layer B)
 
@@ -126,6 +133,16 @@ pub struct FeedbackState {
     pub beat_phase: f32,
     /// Per-side browse mode active state (0 = left, 1 = right)
     pub browse_active: [bool; 2],
+    /// Sampler slots holding a clip (bit N = slot N)
+    pub sampler_loaded: u8,
+    /// Sampler slots currently playing (bit N = slot N)
+    pub sampler_playing: u8,
+    /// Line input cue (PFL) on, per input (0 = mic, 1 = aux)
+    pub line_cue: [bool; 2],
+    /// Line input talkover on, per input (0 = mic, 1 = aux)
+    pub talkover: [bool; 2],
+    /// FX bus chain on, per bus (0 = FX 1, 1 = FX 2, 2 = master insert)
+    pub fx_bus_enabled: [bool; 3],
 }
 
 /// Action button mode (what the pad grid currently controls)
@@ -163,6 +180,10 @@ pub struct DeckFeedbackState {
     pub slicer_selected_preset: u8,
     /// Is key match enabled?
     pub key_match_enabled: bool,
+    /// Is keylock (master tempo) on?
+    pub keylock: bool,
+    /// Does jog touch scratch (vinyl mode)?
+    pub vinyl_mode: bool,
     /// Which stems are muted? (bitmap, bit N = stem N is muted)
     pub stems_muted: u8,
     /// Which stems have a linked counterpart? (bitmap, bit N = stem N has linked)
@@ -416,6 +437,25 @@ pub fn evaluate_feedback(
                 });
             }
 
+            // Sampler pad: magenta pulsing while playing, dim when loaded, off when empty
+            if mapping.state == "sampler.slot" {
+                let slot = mapping.params.get("slot")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0) as u8;
+                let bit = 1u8.checked_shl(slot as u32).unwrap_or(0);
+                return Some(if state.sampler_playing & bit != 0 {
+                    beat_pulse_result(
+                        address, state.beat_phase,
+                        SAMPLER_COLOR, SAMPLER_COLOR_DIM,
+                        mapping.on_value, mapping.off_value,
+                    )
+                } else if state.sampler_loaded & bit != 0 {
+                    FeedbackResult { address, value: mapping.off_value, color: Some(SAMPLER_COLOR_DIM) }
+                } else {
+                    FeedbackResult { address, value: mapping.off_value, color: Some(SAMPLER_COLOR_EMPTY) }
+                });
+            }
+
             // Mixer cue/PFL: yellow when enabled
             if mapping.state == "mixer.cue_enabled" {
                 let channel = mapping.deck_index.unwrap_or(0).min(3);
@@ -427,6 +467,31 @@ pub fn evaluate_feedback(
                 });
             }
 
+            // Line input cue/PFL: yellow like the deck channels; talkover: red
+            if mapping.state == "line.cue_enabled" || mapping.state == "line.talkover" {
+                let active = evaluate_state(mapping, state, deck_target);
+                let (on, off) = if mapping.state == "line.talkover" {
+                    (TALKOVER_COLOR, TALKOVER_COLOR_DIM)
+                } else {
+                    (CUE_ENABLED_COLOR, CUE_ENABLED_COLOR_DIM)
+                };
+                return Some(if active {
+                    FeedbackResult { address, value: mapping.on_value, color: Some(on) }
+                } else {
+                    FeedbackResult { address, value: mapping.off_value, color: Some(off) }
+                });
+            }
+
+            // FX bus on/off: green when the chain is on
+            if mapping.state == "fx_bus.enabled" {
+                let active = evaluate_state(mapping, state, deck_target);
+                return Some(if active {
+                    FeedbackResult { address, value: mapping.on_value, color: Some(FX_BUS_COLOR) }
+                } else {
+                    FeedbackResult { address, value: mapping.off_value, color: Some(FX_BUS_COLOR_DIM) }
+                });
+            }
+
             // Slip mode: amber when active
             if mapping.state == "deck.slip_active" {
                 let deck_idx = resolve_feedback_deck(mapping, deck_target);
@@ -487,6 +552,8 @@ fn evaluate_state(
         "deck.slip_active" => deck_state.slip_active,
         "deck.slicer_active" => deck_state.slicer_active,
         "deck.key_match_enabled" => deck_state.key_match_enabled,
+        "deck.keylock" => deck_state.keylock,
+        "deck.vinyl_mode" => deck_state.vinyl_mode,
 
         "deck.hot_cue_set" => {
             let slot = mapping
@@ -525,6 +592,42 @@ fn evaluate_state(
             (deck_state.stems_muted & (1 << stem)) != 0
         }
 
+        // Sampler slot playing
+        "sampler.slot" => {
+            let slot = mapping
+                .params
+                .get("slot")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0) as u32;
+            state.sampler_playing & 1u8.checked_shl(slot).unwrap_or(0) != 0
+        }
+
+        // Line inputs (mic/aux)
+        "line.cue_enabled" | "line.talkover" => {
+            let input = mapping
+                .params
+                .get("input")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+                .min(1) as usize;
+            if mapping.state == "line.cue_enabled" {
+                state.line_cue[input]
+            } else {
+                state.talkover[input]
+            }
+        }
+
+        // Mixer FX buses
+        "fx_bus.enabled" => {
+            let bus = mapping
+                .params
+                .get("bus")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+                .min(2) as usize;
+            state.fx_bus_enabled[bus]
+        }
+
         // Layer active is handled above in evaluate_feedback()
         "deck.layer_active" => true,
 
@@ -662,4 +765,75 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].value, 0);
     }
+
+    #[test]
+    fn test_evaluate_feedback_sampler_slot() {
+        use crate::config::FeedbackMapping;
+        use crate::types::MidiAddress;
+
+        let pad = |slot: u64| FeedbackMapping {
+            state: "sampler.slot".to_string(),
+            physical_deck: None,
+            deck_index: None,
+            params: [("slot".to_string(), serde_yaml::Value::from(slot))].into_iter().collect(),
+            output: ControlAddress::Midi(MidiAddress::Note { channel: 9, note: 0x24 + slot as u8 }),
+            on_value: 127,
+            off_value: 0,
+            alt_on_value: None,
+            on_color: None,
+            off_color: None,
+            alt_on_color: None,
+            mode: None,
+        };
+        let mappings = vec![pad(0), pad(1), pad(2)];
+
+        let state = FeedbackState {
+            sampler_loaded: 0b011,
+            sampler_playing: 0b001,
+            ..Default::default()
+        };
+        let results = evaluate_feedback(&mappings, &state, &DeckTargetState::default());
+
+        assert_eq!(results.len(), 3);
+        assert_ne!(results[0].color, Some(SAMPLER_COLOR_EMPTY));
+        assert_eq!(results[1].color, Some(SAMPLER_COLOR_DIM));
+        assert_eq!(results[2].color, Some(SAMPLER_COLOR_EMPTY));
+    }
+
+    #[test]
+    fn test_evaluate_feedback_line_inputs() {
+        use crate::config::FeedbackMapping;
+        use crate::types::MidiAddress;
+
+        let button = |state: &str, input: u64| FeedbackMapping {
+            state: state.to_string(),
+            physical_deck: None,
+            deck_index: None,
+            params: [("input".to_string(), serde_yaml::Value::from(input))].into_iter().collect(),
+            output: ControlAddress::Midi(MidiAddress::Note { channel: 0, note: 0x40 + input as u8 }),
+            on_value: 127,
+            off_value: 0,
+            alt_on_value: None,
+            on_color: None,
+            off_color: None,
+            alt_on_color: None,
+            mode: None,
+        };
+        let mappings = vec![
+            button("line.talkover", 0),
+            button("line.talkover", 1),
+            button("line.cue_enabled", 1),
+        ];
+
+        let state = FeedbackState {
+            line_cue: [false, true],
+            talkover: [true, false],
+            ..Default::default()
+        };
+        let results = evaluate_feedback(&mappings, &state, &DeckTargetState::default());
+
+        assert_eq!(results[0].color, Some(TALKOVER_COLOR));
+        assert_eq!(results[1].value, 0);
+        assert_eq!(results[2].color, Some(CUE_ENABLED_COLOR));
+    }
 }
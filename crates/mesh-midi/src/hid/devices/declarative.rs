@@ -0,0 +1,751 @@
+//! Data-driven HID driver
+//!
+//! Devices without a hand-written driver are described in YAML: report IDs and
+//! sizes, where each control lives in the input report, and which output bytes
+//! drive its LEDs. [`DeclarativeDriver`] interprets a [`DeviceDescription`] at
+//! runtime and implements [`HidDeviceDriver`] exactly like a native driver
+//! (delta detection, first report as baseline, 0x00-0x7F feedback range).
+//!
+//! Byte offsets are 0-indexed from the start of the report, so byte 0 is the
+//! report ID. Multi-byte analog values are little-endian.
+//!
+//! ```yaml
+//! name: "Example Controller"
+//! vendor_id: 0x17CC
+//! product_id: 0x1234
+//! input: { report_id: 0x01, size: 8 }
+//! output: { report_id: 0x80, size: 12 }
+//! palettes:
+//!   pads:
+//!     - { value: 0x00, color: [0, 0, 0] }
+//!     - { value: 0x04, color: [127, 0, 0] }
+//! controls:
+//!   - { name: play, label: "Play", type: button, byte: 1, bit: 0, led: [1] }
+//!   - { name: pad_1, type: button, byte: 1, bit: 1, rgb: { byte: 2, order: brg } }
+//!   - { name: pad_2, type: button, byte: 1, bit: 2, rgb: { byte: 5, palette: pads } }
+//!   - { name: browse, type: encoder, byte: 2, nibble: low }
+//!   - { name: volume, type: fader, byte: 3, width: 2, max: 4095 }
+//! ```
+
+use super::HidDeviceDriver;
+use crate::config::HardwareType;
+use crate::types::{ControlAddress, ControlDescriptor, ControlEvent, ControlValue, FeedbackCommand};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Feedback commands use 0-127 per channel; device values are scaled from this range
+const FEEDBACK_MAX: u16 = 0x7F;
+
+/// Errors from parsing or validating a device description
+#[derive(Debug, thiserror::Error)]
+pub enum DescriptionError {
+    #[error("Failed to parse HID device description: {0}")]
+    Parse(#[from] serde_yaml::Error),
+
+    #[error("Invalid HID device description '{device}': {reason}")]
+    Invalid { device: String, reason: String },
+}
+
+/// Report layout of a HID device, loaded from YAML
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceDescription {
+    /// Human-readable product name (shown in logs and learn mode)
+    pub name: String,
+    /// USB Vendor ID
+    pub vendor_id: u16,
+    /// USB Product ID
+    pub product_id: u16,
+    /// Input report layout
+    pub input: InputReport,
+    /// Output report layout
+    pub output: OutputReport,
+    /// Named color palettes for LEDs that take a color index instead of RGB bytes
+    #[serde(default)]
+    pub palettes: HashMap<String, Vec<PaletteEntry>>,
+    /// All controls on the device
+    pub controls: Vec<ControlSpec>,
+}
+
+/// Input report header
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputReport {
+    /// Only reports starting with this ID are parsed (None = accept all)
+    #[serde(default)]
+    pub report_id: Option<u8>,
+    /// Report size in bytes (including report ID)
+    pub size: usize,
+}
+
+/// Output report header
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutputReport {
+    /// Report ID written to byte 0
+    pub report_id: u8,
+    /// Report size in bytes (including report ID)
+    pub size: usize,
+    /// Value written for a fully lit LED channel
+    #[serde(default = "default_brightness_max")]
+    pub brightness_max: u8,
+    /// Read from the input endpoint after every write (device handshake)
+    #[serde(default)]
+    pub post_write_read: bool,
+}
+
+fn default_brightness_max() -> u8 {
+    FEEDBACK_MAX as u8
+}
+
+/// One color a palette-indexed LED can show
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaletteEntry {
+    /// Byte written to the output report
+    pub value: u8,
+    /// Reference color (0-127 per channel, same scale as feedback commands)
+    pub color: [u8; 3],
+}
+
+/// Physical control kind in the input report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    /// Single bit: set = pressed
+    Button,
+    /// Absolute analog value
+    Knob,
+    /// Absolute analog value (linear travel)
+    Fader,
+    /// Wrapping position counter, reported as relative movement
+    Encoder,
+}
+
+impl InputKind {
+    fn hardware_type(self) -> HardwareType {
+        match self {
+            Self::Button => HardwareType::Button,
+            Self::Knob => HardwareType::Knob,
+            Self::Fader => HardwareType::Fader,
+            Self::Encoder => HardwareType::Encoder,
+        }
+    }
+}
+
+/// Which half of a byte holds a 4-bit encoder position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Nibble {
+    /// Bits 0-3
+    Low,
+    /// Bits 4-7
+    High,
+}
+
+/// Byte order of a three-byte RGB LED
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelOrder {
+    #[default]
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+}
+
+impl ChannelOrder {
+    /// Arrange (r, g, b) into output byte order
+    fn arrange(self, r: u8, g: u8, b: u8) -> [u8; 3] {
+        match self {
+            Self::Rgb => [r, g, b],
+            Self::Rbg => [r, b, g],
+            Self::Grb => [g, r, b],
+            Self::Gbr => [g, b, r],
+            Self::Brg => [b, r, g],
+            Self::Bgr => [b, g, r],
+        }
+    }
+}
+
+/// RGB LED output: three channel bytes, or one palette index byte
+#[derive(Debug, Clone, Deserialize)]
+pub struct RgbSpec {
+    /// First output byte
+    pub byte: usize,
+    /// Channel order for three-byte LEDs
+    #[serde(default)]
+    pub order: ChannelOrder,
+    /// Palette name; when set, a single byte holding the nearest palette entry is written
+    #[serde(default)]
+    pub palette: Option<String>,
+}
+
+/// A single control and its location in the reports
+#[derive(Debug, Clone, Deserialize)]
+pub struct ControlSpec {
+    /// Control name used in mappings (`ControlAddress::Hid { name, .. }`)
+    pub name: String,
+    /// Human-readable label for learn mode (defaults to `name`)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Control kind
+    #[serde(rename = "type")]
+    pub kind: InputKind,
+    /// Input report byte holding the control (first byte for multi-byte analogs)
+    pub byte: usize,
+    /// Button bit within `byte` (0 = LSB)
+    #[serde(default)]
+    pub bit: u8,
+    /// Analog value width in bytes (1 or 2, little-endian)
+    #[serde(default = "default_width")]
+    pub width: u8,
+    /// Analog value at full travel (defaults to the largest value `width` can hold)
+    #[serde(default)]
+    pub max: Option<u16>,
+    /// Report 1.0 at the bottom of travel instead of the top
+    #[serde(default)]
+    pub invert: bool,
+    /// Minimum analog change that produces an event
+    #[serde(default = "default_deadzone")]
+    pub deadzone: u16,
+    /// Encoder nibble (None = the whole byte is the position counter)
+    #[serde(default)]
+    pub nibble: Option<Nibble>,
+    /// Single-color LED output bytes (all receive the same brightness)
+    #[serde(default)]
+    pub led: Vec<usize>,
+    /// RGB LED output
+    #[serde(default)]
+    pub rgb: Option<RgbSpec>,
+}
+
+fn default_width() -> u8 {
+    2
+}
+
+fn default_deadzone() -> u16 {
+    2
+}
+
+impl ControlSpec {
+    /// Analog full-scale value
+    fn analog_max(&self) -> u16 {
+        self.max.unwrap_or(if self.width == 1 { u8::MAX as u16 } else { u16::MAX })
+    }
+
+    /// Read the raw analog value from a report
+    fn read_analog(&self, data: &[u8]) -> u16 {
+        if self.width == 1 {
+            data[self.byte] as u16
+        } else {
+            u16::from_le_bytes([data[self.byte], data[self.byte + 1]])
+        }
+    }
+
+    /// Read the encoder position and its wrap range from a report
+    fn read_encoder(&self, data: &[u8]) -> (i32, i32) {
+        let raw = data[self.byte];
+        match self.nibble {
+            Some(Nibble::Low) => ((raw & 0x0F) as i32, 16),
+            Some(Nibble::High) => ((raw >> 4) as i32, 16),
+            None => (raw as i32, 256),
+        }
+    }
+}
+
+impl DeviceDescription {
+    /// Parse and validate a description from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self, DescriptionError> {
+        let description: Self = serde_yaml::from_str(yaml)?;
+        description.validate()?;
+        Ok(description)
+    }
+
+    /// Check that every offset fits its report and every reference resolves
+    pub fn validate(&self) -> Result<(), DescriptionError> {
+        let invalid = |reason: String| DescriptionError::Invalid {
+            device: self.name.clone(),
+            reason,
+        };
+
+        if self.input.size < 2 {
+            return Err(invalid(format!("input size {} is too small", self.input.size)));
+        }
+        if self.output.size < 1 {
+            return Err(invalid("output size must include the report ID byte".to_string()));
+        }
+        for (name, entries) in &self.palettes {
+            if entries.is_empty() {
+                return Err(invalid(format!("palette '{}' is empty", name)));
+            }
+        }
+
+        let mut names = HashSet::new();
+        for control in &self.controls {
+            let ctrl = &control.name;
+            if !names.insert(ctrl.as_str()) {
+                return Err(invalid(format!("duplicate control '{}'", ctrl)));
+            }
+
+            let input_len = match control.kind {
+                InputKind::Knob | InputKind::Fader => {
+                    if control.width != 1 && control.width != 2 {
+                        return Err(invalid(format!("'{}': width must be 1 or 2", ctrl)));
+                    }
+                    if control.analog_max() == 0 {
+                        return Err(invalid(format!("'{}': max must be non-zero", ctrl)));
+                    }
+                    control.width as usize
+                }
+                InputKind::Button => {
+                    if control.bit > 7 {
+                        return Err(invalid(format!("'{}': bit {} is out of range", ctrl, control.bit)));
+                    }
+                    1
+                }
+                InputKind::Encoder => 1,
+            };
+            if control.byte == 0 || control.byte + input_len > self.input.size {
+                return Err(invalid(format!(
+                    "'{}': input byte {} is outside the {}-byte report",
+                    ctrl, control.byte, self.input.size
+                )));
+            }
+
+            for &offset in &control.led {
+                if offset == 0 || offset >= self.output.size {
+                    return Err(invalid(format!("'{}': LED byte {} is outside the output report", ctrl, offset)));
+                }
+            }
+            if let Some(rgb) = &control.rgb {
+                let len = match &rgb.palette {
+                    Some(palette) if !self.palettes.contains_key(palette) => {
+                        return Err(invalid(format!("'{}': unknown palette '{}'", ctrl, palette)));
+                    }
+                    Some(_) => 1,
+                    None => 3,
+                };
+                if rgb.byte == 0 || rgb.byte + len > self.output.size {
+                    return Err(invalid(format!("'{}': RGB byte {} is outside the output report", ctrl, rgb.byte)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find a control by name
+    fn control(&self, name: &str) -> Option<&ControlSpec> {
+        self.controls.iter().find(|c| c.name == name)
+    }
+
+    /// Scale a 0-127 feedback value to the device's brightness range
+    fn scale(&self, value: u8) -> u8 {
+        let value = (value as u16).min(FEEDBACK_MAX);
+        (value * self.output.brightness_max as u16 / FEEDBACK_MAX) as u8
+    }
+
+    /// Pick the palette entry closest to an RGB color
+    fn nearest_palette_value(&self, palette: &str, r: u8, g: u8, b: u8) -> Option<u8> {
+        let target = [r.min(0x7F) as i32, g.min(0x7F) as i32, b.min(0x7F) as i32];
+        self.palettes.get(palette)?.iter().min_by_key(|entry| {
+            entry
+                .color
+                .iter()
+                .zip(target)
+                .map(|(&c, t)| (c as i32 - t).pow(2))
+                .sum::<i32>()
+        }).map(|entry| entry.value)
+    }
+}
+
+/// Load every `*.yaml` description in a directory
+///
+/// Invalid files are logged and skipped. A missing directory yields no descriptions.
+pub fn load_descriptions_from_dir(dir: &Path) -> Vec<DeviceDescription> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "yaml" || ext == "yml"))
+        .collect();
+    paths.sort();
+
+    let mut descriptions = Vec::new();
+    for path in paths {
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|yaml| DeviceDescription::from_yaml(&yaml).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(description) => {
+                log::info!(
+                    "HID: Loaded description '{}' (VID={:#06x} PID={:#06x}) from {:?}",
+                    description.name, description.vendor_id, description.product_id, path
+                );
+                descriptions.push(description);
+            }
+            Err(e) => log::warn!("HID: Skipping description {:?}: {}", path, e),
+        }
+    }
+    descriptions
+}
+
+/// HID driver driven by a [`DeviceDescription`]
+pub struct DeclarativeDriver {
+    /// Report layout
+    description: Arc<DeviceDescription>,
+    /// Unique device identifier (USB serial number or VID/PID fallback)
+    device_id: String,
+    /// Previous input report for delta detection
+    prev_input: Vec<u8>,
+    /// Whether we've received at least one report (for initialization)
+    has_prev: bool,
+    /// Control descriptors (built once)
+    descriptors: Vec<ControlDescriptor>,
+}
+
+impl DeclarativeDriver {
+    pub fn new(description: Arc<DeviceDescription>, device_id: String) -> Self {
+        let descriptors = description
+            .controls
+            .iter()
+            .map(|c| ControlDescriptor {
+                address: hid_addr(&device_id, &c.name),
+                name: c.label.clone().unwrap_or_else(|| c.name.clone()),
+                control_type: c.kind.hardware_type(),
+                has_led: !c.led.is_empty(),
+                has_rgb: c.rgb.is_some(),
+            })
+            .collect();
+        let prev_input = vec![0; description.input.size];
+
+        Self {
+            description,
+            device_id,
+            prev_input,
+            has_prev: false,
+            descriptors,
+        }
+    }
+
+    /// Compare one control between the current and previous report
+    fn parse_control(&self, control: &ControlSpec, data: &[u8]) -> Option<ControlValue> {
+        let prev = &self.prev_input;
+        match control.kind {
+            InputKind::Button => {
+                let mask = 1u8 << control.bit;
+                let pressed = data[control.byte] & mask != 0;
+                (pressed != (prev[control.byte] & mask != 0)).then_some(ControlValue::Button(pressed))
+            }
+            InputKind::Knob | InputKind::Fader => {
+                let current = control.read_analog(data);
+                if current.abs_diff(control.read_analog(prev)) < control.deadzone.max(1) {
+                    return None;
+                }
+                let normalized = (current as f64 / control.analog_max() as f64).clamp(0.0, 1.0);
+                Some(ControlValue::Absolute(if control.invert { 1.0 - normalized } else { normalized }))
+            }
+            InputKind::Encoder => {
+                let (current, range) = control.read_encoder(data);
+                let (previous, _) = control.read_encoder(prev);
+                // Shortest way around the wrapping counter
+                let half = range / 2;
+                let delta = (current - previous + half + range) % range - half;
+                (delta != 0).then_some(ControlValue::Relative(delta))
+            }
+        }
+    }
+}
+
+/// Build an HID control address with device identity
+fn hid_addr(device_id: &str, name: &str) -> ControlAddress {
+    ControlAddress::Hid {
+        device_id: device_id.to_string(),
+        name: name.to_string(),
+    }
+}
+
+impl HidDeviceDriver for DeclarativeDriver {
+    fn parse_input(&mut self, data: &[u8]) -> Vec<ControlEvent> {
+        let size = self.description.input.size;
+        if data.len() < size {
+            return Vec::new();
+        }
+        if let Some(id) = self.description.input.report_id {
+            if data[0] != id {
+                return Vec::new();
+            }
+        }
+
+        // First report: store as baseline, emit no events
+        if !self.has_prev {
+            self.prev_input.copy_from_slice(&data[..size]);
+            self.has_prev = true;
+            return Vec::new();
+        }
+
+        let events = self
+            .description
+            .controls
+            .iter()
+            .filter_map(|control| {
+                self.parse_control(control, data).map(|value| ControlEvent {
+                    address: hid_addr(&self.device_id, &control.name),
+                    value,
+                })
+            })
+            .collect();
+
+        self.prev_input.copy_from_slice(&data[..size]);
+        events
+    }
+
+    fn apply_feedback(&mut self, output: &mut [u8], cmd: FeedbackCommand) {
+        let desc = &self.description;
+        if output.len() < desc.output.size {
+            return;
+        }
+
+        match cmd {
+            FeedbackCommand::SetLed { ref control, brightness } => {
+                match desc.control(control) {
+                    Some(spec) if !spec.led.is_empty() => {
+                        let val = desc.scale(brightness);
+                        for &offset in &spec.led {
+                            output[offset] = val;
+                        }
+                    }
+                    _ => log::debug!("[{} LED] SetLed '{}' → NO MATCH (dropped)", desc.name, control),
+                }
+            }
+            FeedbackCommand::SetRgb { ref control, r, g, b } => {
+                let Some(spec) = desc.control(control) else {
+                    log::debug!("[{} LED] SetRgb '{}' → NO MATCH (dropped)", desc.name, control);
+                    return;
+                };
+                match &spec.rgb {
+                    Some(RgbSpec { byte, palette: Some(palette), .. }) => {
+                        if let Some(value) = desc.nearest_palette_value(palette, r, g, b) {
+                            output[*byte] = value;
+                        }
+                    }
+                    Some(RgbSpec { byte, order, palette: None }) => {
+                        let bytes = order.arrange(desc.scale(r), desc.scale(g), desc.scale(b));
+                        output[*byte..*byte + 3].copy_from_slice(&bytes);
+                    }
+                    // Fallback: single-color LED, convert RGB to brightness
+                    None => {
+                        let val = desc.scale(r.max(g).max(b));
+                        for &offset in &spec.led {
+                            output[offset] = val;
+                        }
+                    }
+                }
+            }
+            FeedbackCommand::SetDisplay { .. } => {
+                // Displays are not part of the description format yet
+            }
+        }
+    }
+
+    fn output_report_size(&self) -> usize {
+        self.description.output.size
+    }
+
+    fn output_report_id(&self) -> u8 {
+        self.description.output.report_id
+    }
+
+    fn post_write_read(&self) -> bool {
+        self.description.output.post_write_read
+    }
+
+    fn controls(&self) -> &[ControlDescriptor] {
+        &self.descriptors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DEVICE: &str = r#"
+name: "Test Device"
+vendor_id: 0x1234
+product_id: 0x5678
+input: { report_id: 0x01, size: 8 }
+output: { report_id: 0x80, size: 12 }
+palettes:
+  pads:
+    - { value: 0x00, color: [0, 0, 0] }
+    - { value: 0x04, color: [127, 0, 0] }
+    - { value: 0x10, color: [0, 0, 127] }
+controls:
+  - { name: play, label: "Play", type: button, byte: 1, bit: 0, led: [1, 2] }
+  - { name: pad_1, type: button, byte: 1, bit: 7, rgb: { byte: 3, order: brg } }
+  - { name: pad_2, type: button, byte: 2, bit: 0, rgb: { byte: 6, palette: pads } }
+  - { name: browse, type: encoder, byte: 3, nibble: high }
+  - { name: jog, type: encoder, byte: 4 }
+  - { name: volume, type: fader, byte: 5, max: 4095 }
+  - { name: filter, type: knob, byte: 7, width: 1, invert: true }
+"#;
+
+    fn driver() -> DeclarativeDriver {
+        let description = DeviceDescription::from_yaml(TEST_DEVICE).unwrap();
+        DeclarativeDriver::new(Arc::new(description), "test".to_string())
+    }
+
+    /// Feed a baseline then a second report, returning the events of the second
+    fn events_between(driver: &mut DeclarativeDriver, baseline: &[u8], report: &[u8]) -> Vec<ControlEvent> {
+        assert!(driver.parse_input(baseline).is_empty(), "First report should produce no events");
+        driver.parse_input(report)
+    }
+
+    fn name_of(event: &ControlEvent) -> &str {
+        match &event.address {
+            ControlAddress::Hid { name, .. } => name,
+            other => panic!("Expected HID address, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_buttons_from_report_bytes() {
+        let mut driver = driver();
+        let idle = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let pressed = [0x01, 0x81, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let events = events_between(&mut driver, &idle, &pressed);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(name_of(&events[0]), "play");
+        assert_eq!(name_of(&events[1]), "pad_1");
+        assert!(events.iter().all(|e| matches!(e.value, ControlValue::Button(true))));
+
+        let events = driver.parse_input(&idle);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| matches!(e.value, ControlValue::Button(false))));
+    }
+
+    #[test]
+    fn test_encoder_nibble_wraps() {
+        let mut driver = driver();
+        // High nibble 15 → 1 is +2 clockwise; low nibble changes must be ignored
+        let before = [0x01, 0x00, 0x00, 0xF3, 0x00, 0x00, 0x00, 0x00];
+        let after = [0x01, 0x00, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x00];
+        let events = events_between(&mut driver, &before, &after);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(name_of(&events[0]), "browse");
+        assert!(matches!(events[0].value, ControlValue::Relative(2)));
+    }
+
+    #[test]
+    fn test_full_byte_encoder_counter_clockwise() {
+        let mut driver = driver();
+        let before = [0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+        let after = [0x01, 0x00, 0x00, 0x00, 0xFD, 0x00, 0x00, 0x00];
+        let events = events_between(&mut driver, &before, &after);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(name_of(&events[0]), "jog");
+        assert!(matches!(events[0].value, ControlValue::Relative(-5)));
+    }
+
+    #[test]
+    fn test_analog_range_deadzone_and_invert() {
+        let mut driver = driver();
+        // Fader at 4095 (0x0FFF LE), filter knob at 0x40
+        let baseline = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let moved = [0x01, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x0F, 0x40];
+        let events = events_between(&mut driver, &baseline, &moved);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(name_of(&events[0]), "volume");
+        assert!(matches!(events[0].value, ControlValue::Absolute(v) if (v - 1.0).abs() < 1e-9));
+        assert_eq!(name_of(&events[1]), "filter");
+        assert!(matches!(events[1].value, ControlValue::Absolute(v) if (v - (1.0 - 64.0 / 255.0)).abs() < 1e-9));
+
+        // One step of jitter on the fader stays inside the default deadzone
+        let jitter = [0x01, 0x00, 0x00, 0x00, 0x00, 0xFE, 0x0F, 0x40];
+        assert!(driver.parse_input(&jitter).is_empty());
+    }
+
+    #[test]
+    fn test_foreign_report_id_and_short_reports_ignored() {
+        let mut driver = driver();
+        driver.parse_input(&[0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        assert!(driver.parse_input(&[0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).is_empty());
+        assert!(driver.parse_input(&[0x01, 0xFF]).is_empty());
+    }
+
+    #[test]
+    fn test_led_outputs() {
+        let mut driver = driver();
+        let mut output = [0u8; 12];
+        output[0] = 0x80;
+
+        driver.apply_feedback(&mut output, FeedbackCommand::SetLed { control: "play".to_string(), brightness: 100 });
+        assert_eq!(&output[1..3], &[100, 100], "Every LED byte receives the brightness");
+
+        driver.apply_feedback(&mut output, FeedbackCommand::SetRgb { control: "pad_1".to_string(), r: 10, g: 20, b: 30 });
+        assert_eq!(&output[3..6], &[30, 10, 20], "BRG channel order");
+
+        driver.apply_feedback(&mut output, FeedbackCommand::SetRgb { control: "pad_2".to_string(), r: 0, g: 10, b: 110 });
+        assert_eq!(output[6], 0x10, "Nearest palette entry is blue");
+
+        // RGB on a single-color LED falls back to brightness
+        driver.apply_feedback(&mut output, FeedbackCommand::SetRgb { control: "play".to_string(), r: 0, g: 64, b: 0 });
+        assert_eq!(&output[1..3], &[64, 64]);
+
+        let before = output;
+        driver.apply_feedback(&mut output, FeedbackCommand::SetLed { control: "volume".to_string(), brightness: 127 });
+        assert_eq!(output, before, "Controls without LEDs leave the report untouched");
+    }
+
+    #[test]
+    fn test_brightness_scaled_to_device_range() {
+        let yaml = TEST_DEVICE.replace("size: 12 }", "size: 12, brightness_max: 0xFF }");
+        let description = DeviceDescription::from_yaml(&yaml).unwrap();
+        let mut driver = DeclarativeDriver::new(Arc::new(description), "test".to_string());
+        let mut output = [0u8; 12];
+
+        driver.apply_feedback(&mut output, FeedbackCommand::SetLed { control: "play".to_string(), brightness: 127 });
+        assert_eq!(output[1], 0xFF);
+    }
+
+    #[test]
+    fn test_descriptors() {
+        let driver = driver();
+        let descs = driver.controls();
+        assert_eq!(descs.len(), 7);
+        assert_eq!(descs[0].name, "Play");
+        assert!(descs[0].has_led && !descs[0].has_rgb);
+        assert_eq!(descs[1].name, "pad_1", "Label defaults to the control name");
+        assert!(descs[1].has_rgb);
+        assert_eq!(descs[5].control_type, HardwareType::Fader);
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_layouts() {
+        let cases = [
+            ("byte: 5, max: 4095", "byte: 7, max: 4095"), // 2-byte fader overruns the report
+            ("led: [1, 2]", "led: [12]"),                  // LED past the output report
+            ("palette: pads", "palette: missing"),         // unknown palette
+            ("bit: 7", "bit: 8"),                          // bit out of range
+            ("name: jog", "name: browse"),                 // duplicate control
+        ];
+        for (from, to) in cases {
+            let yaml = TEST_DEVICE.replace(from, to);
+            assert!(
+                matches!(DeviceDescription::from_yaml(&yaml), Err(DescriptionError::Invalid { .. })),
+                "'{}' should be rejected",
+                to
+            );
+        }
+        assert!(matches!(DeviceDescription::from_yaml("name: ["), Err(DescriptionError::Parse(_))));
+    }
+}
@@ -12,6 +12,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
 
+/// How long to wait for the acknowledgement of devices that need a read
+/// after every output write
+const HANDSHAKE_TIMEOUT_MS: i32 = 10;
+
 /// HID I/O thread handle
 ///
 /// Owns the thread join handle and a shutdown flag.
@@ -97,15 +101,7 @@ impl HidIoThread {
 
             // ─── Input: non-blocking read with 1ms timeout ───
             match device.read_timeout(&mut input_buf, 1) {
-                Ok(n) if n > 0 => {
-                    let events = driver.parse_input(&input_buf[..n]);
-                    for event in events {
-                        log::debug!("[HID {}] {:?}", name, event);
-                        if event_tx.try_send(event).is_err() {
-                            log::warn!("[HID {}] Event channel full, dropping event", name);
-                        }
-                    }
-                }
+                Ok(n) if n > 0 => Self::handle_input(driver, &input_buf[..n], &event_tx, name),
                 Ok(_) => {} // Timeout, no data (expected)
                 Err(e) => {
                     log::error!("[HID {}] Read error: {}", name, e);
@@ -140,11 +136,43 @@ impl HidIoThread {
                         break; // Device disconnected
                     }
                 }
+
+                // Acknowledge the write; what comes back is a regular input
+                // report when the device has one pending
+                if driver.post_write_read() {
+                    match device.read_timeout(&mut input_buf, HANDSHAKE_TIMEOUT_MS) {
+                        Ok(n) if n > 1 => Self::handle_input(driver, &input_buf[..n], &event_tx, name),
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::error!("[HID {}] Handshake read error: {}", name, e);
+                            break; // Device disconnected
+                        }
+                    }
+                }
             }
         }
 
         log::info!("[HID {}] I/O thread stopped", name);
     }
+
+    /// Parse one input report and forward its events
+    fn handle_input(
+        driver: &mut dyn HidDeviceDriver,
+        report: &[u8],
+        event_tx: &Sender<ControlEvent>,
+        name: &str,
+    ) {
+        if log::log_enabled!(log::Level::Trace) {
+            let hex: Vec<String> = report.iter().map(|b| format!("{:02x}", b)).collect();
+            log::trace!("[HID {}] Input: {}", name, hex.join(" "));
+        }
+        for event in driver.parse_input(report) {
+            log::debug!("[HID {}] {:?}", name, event);
+            if event_tx.try_send(event).is_err() {
+                log::warn!("[HID {}] Event channel full, dropping event", name);
+            }
+        }
+    }
 }
 
 impl Drop for HidIoThread {
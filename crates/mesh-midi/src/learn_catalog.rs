@@ -265,6 +265,12 @@ static TRANSPORT_MAPPINGS: &[MappingDef] = &[
     button("transport.beat_jump_fwd", "Beat Jump Forward",
         "Jump forward by the current beat jump size.",
         "deck.beat_jump_forward", None),
+    button("transport.phrase_jump_back", "Phrase Jump Back",
+        "Jump to the start of the current phrase (or the previous one).",
+        "deck.phrase_jump_backward", None),
+    button("transport.phrase_jump_fwd", "Phrase Jump Forward",
+        "Jump to the start of the next phrase.",
+        "deck.phrase_jump_forward", None),
     button("transport.slip", "Slip Mode",
         "Enable slip: playback continues underneath loops and scratches.",
         "deck.slip", Some("deck.slip_active")),
@@ -280,6 +286,48 @@ static TRANSPORT_MAPPINGS: &[MappingDef] = &[
     button("transport.deck_load", "Deck Load",
         "Load the selected browser track into this deck.",
         "deck.load_selected", None),
+    button("transport.rating_up", "Rating Up",
+        "Add a star to the rating of the track on this deck.",
+        "deck.rating_up", None),
+    button("transport.rating_down", "Rating Down",
+        "Remove a star from the rating of the track on this deck.",
+        "deck.rating_down", None),
+    button("transport.color_label", "Colour Label",
+        "Cycle the colour label of the track on this deck.",
+        "deck.color_label", None),
+    fader("transport.pitch", "Pitch Fader",
+        "Tempo offset from the synced BPM, scaled by the pitch range.",
+        "deck.pitch"),
+    button("transport.pitch_range", "Pitch Range",
+        "Cycle the pitch fader range: ±6, ±10, ±16, ±50%.",
+        "deck.pitch_range", None),
+    button("transport.keylock", "Keylock",
+        "Keep the key when the tempo changes (off = vinyl mode).",
+        "deck.keylock", Some("deck.keylock")),
+    button("transport.key_shift_up", "Key Up",
+        "Shift this deck's key up a semitone.",
+        "deck.key_shift_up", None),
+    button("transport.key_shift_down", "Key Down",
+        "Shift this deck's key down a semitone.",
+        "deck.key_shift_down", None),
+    button("transport.key_fine_up", "Key Fine Up",
+        "Shift this deck's key up 10 cents.",
+        "deck.key_fine_up", None),
+    button("transport.key_fine_down", "Key Fine Down",
+        "Shift this deck's key down 10 cents.",
+        "deck.key_fine_down", None),
+    button("transport.key_shift_reset", "Key Reset",
+        "Clear the manual key shift.",
+        "deck.key_shift_reset", None),
+    encoder("transport.jog", "Jog Wheel",
+        "Turn to bend the pitch; touch and turn in vinyl mode to scratch.",
+        "deck.jog"),
+    button("transport.jog_touch", "Jog Touch",
+        "Platter touch sensor: grabs the track in vinyl mode.",
+        "deck.jog_touch", None),
+    button("transport.vinyl_mode", "Vinyl Mode",
+        "Toggle whether touching the jog wheel scratches or only nudges.",
+        "deck.vinyl_mode", Some("deck.vinyl_mode")),
 ];
 
 static TRANSPORT: SectionDef = SectionDef {
@@ -405,7 +453,8 @@ static MIXER_MAPPINGS: &[MappingDef] = &[
     fader("mixer.volume", "Volume", "Channel volume fader.", "mixer.volume"),
     knob("mixer.filter", "Filter", "Bipolar filter: left = low-pass, center = off, right = high-pass.", "mixer.filter"),
     knob("mixer.eq_hi", "EQ High", "3-band equalizer high frequency.", "mixer.eq_hi"),
-    knob("mixer.eq_mid", "EQ Mid", "3-band equalizer mid frequency.", "mixer.eq_mid"),
+    knob("mixer.eq_mid", "EQ Mid", "3-band equalizer mid frequency (low-mid on the 4-band EQ).", "mixer.eq_mid"),
+    knob("mixer.eq_hi_mid", "EQ High-Mid", "High-mid band, used when the deck's EQ model is 4-band.", "mixer.eq_hi_mid"),
     knob("mixer.eq_lo", "EQ Low", "3-band equalizer low frequency.", "mixer.eq_lo"),
     MappingDef {
         uses_physical_deck: false,
@@ -413,12 +462,22 @@ static MIXER_MAPPINGS: &[MappingDef] = &[
             "Send this channel to the headphone cue bus for previewing.",
             "mixer.cue", Some("mixer.cue_enabled"))
     },
+    MappingDef {
+        uses_physical_deck: false,
+        ..button("mixer.crossfader_assign", "Crossfader Assign",
+            "Cycle this channel's crossfader side: A, THRU, B.",
+            "mixer.crossfader_assign", None)
+    },
+    MappingDef { param_key: Some("send"), param_value: Some(0),
+        ..knob("mixer.fx_send.0", "FX 1 Send", "Post-fader send level from this channel to FX bus 1.", "mixer.fx_send") },
+    MappingDef { param_key: Some("send"), param_value: Some(1),
+        ..knob("mixer.fx_send.1", "FX 2 Send", "Post-fader send level from this channel to FX bus 2.", "mixer.fx_send") },
 ];
 
 static MIXER_CHANNELS: SectionDef = SectionDef {
     id: "mixer",
     label: "Mixer",
-    description: "Volume faders, EQ knobs, filter, and headphone cue per channel.",
+    description: "Volume faders, EQ knobs, filter, headphone cue, crossfader assign and FX sends per channel.",
     repeat_mode: RepeatMode::PerVirtualDeck,
     visibility: Visibility::Always,
     mappings: MIXER_MAPPINGS,
@@ -494,6 +553,141 @@ static GLOBAL: SectionDef = SectionDef {
     mappings: GLOBAL_MAPPINGS,
 };
 
+// ---------------------------------------------------------------------------
+// Section: Sampler (once)
+// ---------------------------------------------------------------------------
+
+static SAMPLER_MAPPINGS: &[MappingDef] = &[
+    MappingDef { param_key: Some("slot"), param_value: Some(0),
+        ..button("sampler.pad.0", "Sampler 1", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(1),
+        ..button("sampler.pad.1", "Sampler 2", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(2),
+        ..button("sampler.pad.2", "Sampler 3", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(3),
+        ..button("sampler.pad.3", "Sampler 4", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(4),
+        ..button("sampler.pad.4", "Sampler 5", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(5),
+        ..button("sampler.pad.5", "Sampler 6", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(6),
+        ..button("sampler.pad.6", "Sampler 7", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    MappingDef { param_key: Some("slot"), param_value: Some(7),
+        ..button("sampler.pad.7", "Sampler 8", "Play this slot: restart a one-shot, start or stop a loop.", "sampler.trigger", Some("sampler.slot")) },
+    button("sampler.stop_all", "Stop All Samples",
+        "Stop every playing sampler slot.",
+        "sampler.stop_all", None),
+    MappingDef {
+        uses_physical_deck: true,
+        ..button("sampler.capture", "Capture Loop",
+            "Capture the deck's current loop (audible stems only) into the next empty sampler slot.",
+            "deck.sampler_capture", None)
+    },
+];
+
+static SAMPLER: SectionDef = SectionDef {
+    id: "sampler",
+    label: "Sampler",
+    description: "Sampler pads, stop all, and loop capture from a deck.",
+    repeat_mode: RepeatMode::Once,
+    visibility: Visibility::Always,
+    mappings: SAMPLER_MAPPINGS,
+};
+
+// ---------------------------------------------------------------------------
+// Section: Line inputs (once)
+// ---------------------------------------------------------------------------
+
+static LINE_INPUT_MAPPINGS: &[MappingDef] = &[
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..fader("line.mic.volume", "Mic Volume", "Mic channel volume fader.", "line.volume") },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("line.mic.trim", "Mic Trim", "Mic input gain: left = -24 dB, center = unity, right = +12 dB.", "line.trim") },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("line.mic.eq_hi", "Mic EQ High", "Mic 3-band equalizer high frequency.", "line.eq_hi") },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("line.mic.eq_mid", "Mic EQ Mid", "Mic 3-band equalizer mid frequency.", "line.eq_mid") },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("line.mic.eq_lo", "Mic EQ Low", "Mic 3-band equalizer low frequency.", "line.eq_lo") },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..button("line.mic.cue", "Mic Cue / PFL", "Send the mic input to the headphone cue bus.", "line.cue", Some("line.cue_enabled")) },
+    MappingDef { param_key: Some("input"), param_value: Some(0), uses_physical_deck: false,
+        ..button("line.mic.talkover", "Mic Talkover", "Duck the decks while there is signal on the mic input.", "line.talkover", Some("line.talkover")) },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..fader("line.aux.volume", "Aux Volume", "Aux channel volume fader.", "line.volume") },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("line.aux.trim", "Aux Trim", "Aux input gain: left = -24 dB, center = unity, right = +12 dB.", "line.trim") },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("line.aux.eq_hi", "Aux EQ High", "Aux 3-band equalizer high frequency.", "line.eq_hi") },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("line.aux.eq_mid", "Aux EQ Mid", "Aux 3-band equalizer mid frequency.", "line.eq_mid") },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("line.aux.eq_lo", "Aux EQ Low", "Aux 3-band equalizer low frequency.", "line.eq_lo") },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..button("line.aux.cue", "Aux Cue / PFL", "Send the aux input to the headphone cue bus.", "line.cue", Some("line.cue_enabled")) },
+    MappingDef { param_key: Some("input"), param_value: Some(1), uses_physical_deck: false,
+        ..button("line.aux.talkover", "Aux Talkover", "Duck the decks while there is signal on the aux input.", "line.talkover", Some("line.talkover")) },
+];
+
+static LINE_INPUTS: SectionDef = SectionDef {
+    id: "line_inputs",
+    label: "Mic & Aux",
+    description: "Mic and aux line input channels: fader, trim, EQ, cue and talkover.",
+    repeat_mode: RepeatMode::Once,
+    visibility: Visibility::Always,
+    mappings: LINE_INPUT_MAPPINGS,
+};
+
+// ---------------------------------------------------------------------------
+// Section: FX buses (once)
+// ---------------------------------------------------------------------------
+
+static FX_BUS_MAPPINGS: &[MappingDef] = &[
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("fx_bus.fx1.return", "FX 1 Return", "Level of the FX 1 bus coming back into master.", "fx_bus.return") },
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..button("fx_bus.fx1.enable", "FX 1 On", "Turn the FX 1 effect chain on or off.", "fx_bus.enable", Some("fx_bus.enabled")) },
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("fx_bus.fx1.knob_1", "FX 1 Knob 1", "Parameter 1 of the FX 1 effect chain, counting through its effects in order.", "fx_bus.knob_1") },
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("fx_bus.fx1.knob_2", "FX 1 Knob 2", "Parameter 2 of the FX 1 effect chain, counting through its effects in order.", "fx_bus.knob_2") },
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("fx_bus.fx1.knob_3", "FX 1 Knob 3", "Parameter 3 of the FX 1 effect chain, counting through its effects in order.", "fx_bus.knob_3") },
+    MappingDef { param_key: Some("bus"), param_value: Some(0), uses_physical_deck: false,
+        ..knob("fx_bus.fx1.knob_4", "FX 1 Knob 4", "Parameter 4 of the FX 1 effect chain, counting through its effects in order.", "fx_bus.knob_4") },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("fx_bus.fx2.return", "FX 2 Return", "Level of the FX 2 bus coming back into master.", "fx_bus.return") },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..button("fx_bus.fx2.enable", "FX 2 On", "Turn the FX 2 effect chain on or off.", "fx_bus.enable", Some("fx_bus.enabled")) },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("fx_bus.fx2.knob_1", "FX 2 Knob 1", "Parameter 1 of the FX 2 effect chain, counting through its effects in order.", "fx_bus.knob_1") },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("fx_bus.fx2.knob_2", "FX 2 Knob 2", "Parameter 2 of the FX 2 effect chain, counting through its effects in order.", "fx_bus.knob_2") },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("fx_bus.fx2.knob_3", "FX 2 Knob 3", "Parameter 3 of the FX 2 effect chain, counting through its effects in order.", "fx_bus.knob_3") },
+    MappingDef { param_key: Some("bus"), param_value: Some(1), uses_physical_deck: false,
+        ..knob("fx_bus.fx2.knob_4", "FX 2 Knob 4", "Parameter 4 of the FX 2 effect chain, counting through its effects in order.", "fx_bus.knob_4") },
+    MappingDef { param_key: Some("bus"), param_value: Some(2), uses_physical_deck: false,
+        ..button("fx_bus.master.enable", "Master FX On", "Turn the master insert chain on or off.", "fx_bus.enable", Some("fx_bus.enabled")) },
+    MappingDef { param_key: Some("bus"), param_value: Some(2), uses_physical_deck: false,
+        ..knob("fx_bus.master.knob_1", "Master FX Knob 1", "Parameter 1 of the master insert chain, counting through its effects in order.", "fx_bus.knob_1") },
+    MappingDef { param_key: Some("bus"), param_value: Some(2), uses_physical_deck: false,
+        ..knob("fx_bus.master.knob_2", "Master FX Knob 2", "Parameter 2 of the master insert chain, counting through its effects in order.", "fx_bus.knob_2") },
+    MappingDef { param_key: Some("bus"), param_value: Some(2), uses_physical_deck: false,
+        ..knob("fx_bus.master.knob_3", "Master FX Knob 3", "Parameter 3 of the master insert chain, counting through its effects in order.", "fx_bus.knob_3") },
+    MappingDef { param_key: Some("bus"), param_value: Some(2), uses_physical_deck: false,
+        ..knob("fx_bus.master.knob_4", "Master FX Knob 4", "Parameter 4 of the master insert chain, counting through its effects in order.", "fx_bus.knob_4") },
+];
+
+static FX_BUSES: SectionDef = SectionDef {
+    id: "fx_buses",
+    label: "FX Buses",
+    description: "Send/return FX buses and the master insert chain: return level, on/off and parameter knobs.",
+    repeat_mode: RepeatMode::Once,
+    visibility: Visibility::Always,
+    mappings: FX_BUS_MAPPINGS,
+};
+
 // ---------------------------------------------------------------------------
 // Public API: section catalog
 // ---------------------------------------------------------------------------
@@ -512,6 +706,9 @@ pub fn section_catalog() -> &'static [SectionDef] {
         MIXER_CHANNELS,
         EFFECTS,
         GLOBAL,
+        SAMPLER,
+        LINE_INPUTS,
+        FX_BUSES,
     ];
     CATALOG
 }
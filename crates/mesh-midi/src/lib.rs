@@ -7,6 +7,7 @@
 //! - Configurable control-to-action mapping
 //! - Deck layer targeting (for 2-deck controllers accessing 4 virtual decks)
 //! - LED/RGB feedback output
+//! - MIDI clock output (from the master deck) and input (tempo following)
 //! - Async channel bridge for iced subscriptions
 //!
 //! # Architecture
@@ -40,11 +41,12 @@ pub mod types;
 pub use config::{
     default_midi_config_path, detect_color_note_offsets, load_midi_config, normalize_port_name,
     port_matches, save_midi_config, ColorNoteOffsets, ControlBehavior, ControlMapping,
-    DeckTargetConfig, DeviceProfile, EncoderMode, FeedbackMapping, HardwareType, MidiConfig,
+    DeckTargetConfig, DeviceProfile, EncoderMode, DEFAULT_JOG_TICKS_PER_REVOLUTION, FeedbackMapping, HardwareType, MidiClockConfig, MidiConfig,
     MidiControlConfig, PadModeSource, ShiftButtonConfig,
 };
 
 // Re-export MIDI backend types
+pub use midi::clock::{ClockSnapshot, ClockSource, MidiClockInput, MidiClockOutput};
 pub use midi::connection::{MidiConnection, MidiConnectionError};
 pub use midi::input::{MidiInputEvent, MidiInputHandler};
 pub use midi::output::MidiOutputHandler;
@@ -54,7 +56,7 @@ pub use detection::{MidiSample, MidiSampleBuffer};
 pub use deck_target::{DeckTargetMode, DeckTargetState, LayerSelection};
 pub use mapping::{ActionRegistry, MappingEngine, learn_mode_dispatch};
 pub use direct_dispatch::DirectDispatch;
-pub use messages::{DeckAction, GlobalAction, MidiMessage, MidiEvent, MixerAction, BrowserAction};
+pub use messages::{DeckAction, GlobalAction, JogRelease, FxBusAction, LineInputAction, MidiMessage, MidiEvent, MixerAction, BrowserAction};
 pub use normalize::{normalize_cc_value, ControlRange};
 pub use shared_state::{SharedState, SharedMidiState};
 
@@ -173,6 +175,10 @@ pub struct ControllerManager {
     /// so both the callback thread and `drain()` route input to learn instead of
     /// the performance app. See `set_learn_mode`.
     learn_mode: Arc<AtomicBool>,
+    /// MIDI clock output (started by `set_clock_source` when configured)
+    clock_output: Option<MidiClockOutput>,
+    /// MIDI clock input feeding `GlobalAction::ClockBpm`
+    clock_input: Option<MidiClockInput>,
 }
 
 /// Backwards-compatible type alias
@@ -264,11 +270,14 @@ impl ControllerManager {
             capture_raw,
             direct_dispatch: None,
             learn_mode: Arc::new(AtomicBool::new(false)),
+            clock_output: None,
+            clock_input: None,
         };
 
         // Try to connect to all matching devices
         controller.try_connect_all_midi(capture_raw)?;
         controller.try_connect_all_hid();
+        controller.try_connect_clock_input();
         controller.start_feedback_worker();
 
         Ok(controller)
@@ -301,6 +310,8 @@ impl ControllerManager {
             // Profiles are empty in this mode, so nothing can map anyway — the flag
             // stays false and `drain()` behaves normally.
             learn_mode: Arc::new(AtomicBool::new(false)),
+            clock_output: None,
+            clock_input: None,
         };
 
         // Connect to ALL available MIDI ports
@@ -706,6 +717,34 @@ impl ControllerManager {
         self.direct_dispatch = Some(dispatch);
     }
 
+    /// Set the master transport for MIDI clock output
+    ///
+    /// Starts sending clock to `clock.output` from the config, if set. The
+    /// source should be fed by the audio thread so ticks follow the audio
+    /// callback rather than the UI tick.
+    pub fn set_clock_source(&mut self, source: Arc<dyn ClockSource>) {
+        let Some(port) = self.config.clock.output.clone() else {
+            return;
+        };
+        // Close the previous connection before opening the port again
+        self.clock_output = None;
+        match MidiClockOutput::connect(&port, source) {
+            Ok(output) => self.clock_output = Some(output),
+            Err(e) => log::warn!("MIDI clock: Output '{}' unavailable: {}", port, e),
+        }
+    }
+
+    /// Follow the tempo of `clock.input` from the config, if set
+    fn try_connect_clock_input(&mut self) {
+        let Some(port) = self.config.clock.input.clone() else {
+            return;
+        };
+        match MidiClockInput::connect(&port, self.message_tx.clone()) {
+            Ok(input) => self.clock_input = Some(input),
+            Err(e) => log::warn!("MIDI clock: Input '{}' unavailable: {}", port, e),
+        }
+    }
+
     /// Hand input over to the MIDI learn UI (or hand it back).
     ///
     /// While set, mapped actions stop reaching the performance app: the MIDI
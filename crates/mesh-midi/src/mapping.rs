@@ -3,8 +3,8 @@
 //! Maps abstract control events to application actions based on device configuration.
 //! Works identically for MIDI and HID input.
 
-use crate::config::{ControlBehavior, ControlMapping, DeviceProfile, EncoderMode};
-use crate::messages::{BrowserAction, DeckAction, GlobalAction, MidiMessage, MixerAction};
+use crate::config::{ControlBehavior, ControlMapping, DeviceProfile, EncoderMode, DEFAULT_JOG_TICKS_PER_REVOLUTION};
+use crate::messages::{BrowserAction, DeckAction, FxBusAction, GlobalAction, JogRelease, LineInputAction, MidiMessage, MixerAction};
 use crate::normalize::{encoder_to_delta, normalize_cc_value, range_for_action, ControlRange};
 use crate::shared_state::SharedState;
 use crate::types::{ControlAddress, ControlEvent, ControlValue};
@@ -54,6 +54,8 @@ impl ActionRegistry {
             "deck.loop_out",
             "deck.beat_jump_forward",
             "deck.beat_jump_backward",
+            "deck.phrase_jump_forward",
+            "deck.phrase_jump_backward",
             "deck.slicer_trigger",
             "deck.slicer_assign",
             "deck.slicer_mode",
@@ -82,11 +84,13 @@ impl ActionRegistry {
         // Mixer actions
         actions.insert("mixer.volume".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
         actions.insert("mixer.filter".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Bipolar });
-        for action in ["mixer.eq_hi", "mixer.eq_mid", "mixer.eq_lo"] {
+        for action in ["mixer.eq_hi", "mixer.eq_hi_mid", "mixer.eq_mid", "mixer.eq_lo"] {
             actions.insert(action.to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Eq });
         }
         actions.insert("mixer.cue".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
         actions.insert("mixer.crossfader".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("mixer.crossfader_assign".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("mixer.fx_send".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
 
         // Browser actions
         actions.insert("browser.scroll".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
@@ -102,6 +106,30 @@ impl ActionRegistry {
         actions.insert("global.fx_select".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
         actions.insert("global.settings_toggle".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
 
+        // Sampler
+        actions.insert("sampler.trigger".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("sampler.stop_all".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("deck.sampler_capture".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+
+        // Line inputs (mic/aux, "input" param)
+        actions.insert("line.volume".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("line.trim".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Eq });
+        for action in ["line.eq_hi", "line.eq_mid", "line.eq_lo"] {
+            actions.insert(action.to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Eq });
+        }
+        actions.insert("line.cue".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        actions.insert("line.talkover".to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+
+        // Mixer FX buses ("bus" param: 0 = FX 1, 1 = FX 2, 2 = master)
+        for action in ["fx_bus.return", "fx_bus.enable", "fx_bus.knob_1", "fx_bus.knob_2", "fx_bus.knob_3", "fx_bus.knob_4"] {
+            actions.insert(action.to_string(), ActionInfo { deck_targetable: false, value_range: ControlRange::Unit });
+        }
+
+        // Track rating
+        actions.insert("deck.rating_up".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+        actions.insert("deck.rating_down".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+        actions.insert("deck.color_label".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+
         // FX macro knobs (per-deck)
         actions.insert("deck.fx_macro".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
 
@@ -111,6 +139,21 @@ impl ActionRegistry {
 
         // Suggestion energy direction (per-deck routed, controls global slider)
         actions.insert("deck.suggestion_energy".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+        actions.insert("deck.pitch".to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Bipolar });
+        for action in [
+            "deck.pitch_range",
+            "deck.keylock",
+            "deck.key_shift_up",
+            "deck.key_shift_down",
+            "deck.key_fine_up",
+            "deck.key_fine_down",
+            "deck.key_shift_reset",
+            "deck.jog",
+            "deck.jog_touch",
+            "deck.vinyl_mode",
+        ] {
+            actions.insert(action.to_string(), ActionInfo { deck_targetable: true, value_range: ControlRange::Unit });
+        }
 
         Self { actions }
     }
@@ -709,6 +752,12 @@ impl MappingEngine {
             "deck.beat_jump_backward" => {
                 if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::BeatJumpBackward }) } else { None }
             }
+            "deck.phrase_jump_forward" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::PhraseJumpForward }) } else { None }
+            }
+            "deck.phrase_jump_backward" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::PhraseJumpBackward }) } else { None }
+            }
 
             // Slicer
             "deck.slicer_trigger" => {
@@ -801,6 +850,10 @@ impl MappingEngine {
                 let normalized = self.extract_continuous_value(event, action, mapping, None);
                 normalized.map(|v| MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqLo(v) })
             }
+            "mixer.eq_hi_mid" => {
+                let normalized = self.extract_continuous_value(event, action, mapping, None);
+                normalized.map(|v| MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqHiMid(v) })
+            }
             "mixer.cue" => {
                 if event.value.is_press() { Some(MidiMessage::Mixer { channel: deck, action: MixerAction::ToggleCue }) } else { None }
             }
@@ -808,6 +861,14 @@ impl MappingEngine {
                 let normalized = self.extract_continuous_value(event, "mixer.crossfader", mapping, None);
                 normalized.map(|v| MidiMessage::Mixer { channel: 0, action: MixerAction::SetCrossfader(v) })
             }
+            "mixer.crossfader_assign" => {
+                if event.value.is_press() { Some(MidiMessage::Mixer { channel: deck, action: MixerAction::CycleCrossfaderAssign }) } else { None }
+            }
+            "mixer.fx_send" => {
+                let send = get_param("send").unwrap_or(0);
+                let normalized = self.extract_continuous_value(event, action, mapping, None);
+                normalized.map(|value| MidiMessage::Mixer { channel: deck, action: MixerAction::SetFxSend { send, value } })
+            }
 
             // Browser
             "browser.scroll" => {
@@ -885,6 +946,99 @@ impl MappingEngine {
                 if event.value.is_press() { Some(MidiMessage::Global(GlobalAction::SettingsToggle)) } else { None }
             }
 
+            // Sampler
+            "sampler.trigger" => {
+                let slot = get_param("slot").unwrap_or(0);
+                if event.value.is_press() { Some(MidiMessage::Global(GlobalAction::SamplerTrigger { slot })) } else { None }
+            }
+            "sampler.stop_all" => {
+                if event.value.is_press() { Some(MidiMessage::Global(GlobalAction::SamplerStopAll)) } else { None }
+            }
+            "deck.sampler_capture" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::SamplerCapture }) } else { None }
+            }
+
+            // Line inputs (mic/aux)
+            "line.volume" | "line.trim" | "line.eq_hi" | "line.eq_mid" | "line.eq_lo" => {
+                let input = get_param("input").unwrap_or(0);
+                let normalized = self.extract_continuous_value(event, action, mapping, None);
+                normalized.and_then(|v| line_input_message(action, input, v, true))
+            }
+            "line.cue" | "line.talkover" => {
+                let input = get_param("input").unwrap_or(0);
+                line_input_message(action, input, 0.0, event.value.is_press())
+            }
+
+            // Mixer FX buses
+            "fx_bus.enable" => {
+                let bus = get_param("bus").unwrap_or(0);
+                fx_bus_message(action, bus, 0.0, event.value.is_press())
+            }
+            _ if action.starts_with("fx_bus.") => {
+                let bus = get_param("bus").unwrap_or(0);
+                let normalized = self.extract_continuous_value(event, action, mapping, None);
+                normalized.and_then(|v| fx_bus_message(action, bus, v, true))
+            }
+
+            // Track rating
+            "deck.rating_up" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::StepRating(1) }) } else { None }
+            }
+            "deck.rating_down" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::StepRating(-1) }) } else { None }
+            }
+            "deck.color_label" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::CycleColorLabel }) } else { None }
+            }
+
+            // Pitch / keylock
+            "deck.pitch" => {
+                let normalized = self.extract_continuous_value(event, action, mapping, Some(1));
+                normalized.map(|v| MidiMessage::Deck { deck, action: DeckAction::SetPitch(v) })
+            }
+            "deck.pitch_range" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::CyclePitchRange }) } else { None }
+            }
+            "deck.keylock" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::ToggleKeylock }) } else { None }
+            }
+            "deck.key_shift_up" | "deck.key_shift_down" | "deck.key_fine_up" | "deck.key_fine_down" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: key_shift_step(action) }) } else { None }
+            }
+            "deck.key_shift_reset" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::ResetKeyShift }) } else { None }
+            }
+
+            // Jog wheel
+            "deck.jog" => {
+                // Relative ticks, same decoding as loop_size
+                let delta = match &event.value {
+                    ControlValue::Relative(d) => *d,
+                    ControlValue::Absolute(v) => {
+                        let mode = mapping.encoder_mode.unwrap_or(EncoderMode::Relative);
+                        encoder_to_delta((*v * 127.0).round() as u8, mode)
+                    }
+                    _ => 0,
+                };
+                if delta != 0 {
+                    let ticks = mapping.ticks_per_revolution.unwrap_or(DEFAULT_JOG_TICKS_PER_REVOLUTION).max(1);
+                    let revolutions = delta as f32 / ticks as f32;
+                    Some(MidiMessage::Deck { deck, action: DeckAction::JogTurn { revolutions } })
+                } else {
+                    None
+                }
+            }
+            "deck.jog_touch" => {
+                let release = mapping.params.get("release")
+                    .and_then(|v| v.as_str())
+                    .map(JogRelease::from_param)
+                    .unwrap_or_default();
+                Some(MidiMessage::Deck { deck, action: DeckAction::JogTouch { touched: event.value.is_press(), release } })
+            }
+            "deck.vinyl_mode" => {
+                if event.value.is_press() { Some(MidiMessage::Deck { deck, action: DeckAction::ToggleVinylMode }) } else { None }
+            }
+
             _ => {
                 log::debug!("Mapping: Unknown action '{}'", action);
                 None
@@ -948,6 +1102,52 @@ impl MappingEngine {
     }
 }
 
+/// Key shift step for a `deck.key_shift_*` / `deck.key_fine_*` button
+///
+/// Coarse buttons move a semitone, fine buttons ten cents.
+fn key_shift_step(action: &str) -> DeckAction {
+    let (semitones, cents) = match action {
+        "deck.key_shift_up" => (1, 0),
+        "deck.key_shift_down" => (-1, 0),
+        "deck.key_fine_up" => (0, 10),
+        _ => (0, -10),
+    };
+    DeckAction::ShiftKey { semitones, cents }
+}
+
+/// Message for a `line.*` action on a mic/aux input
+///
+/// Continuous actions carry `value`; cue and talkover toggle on press only.
+fn line_input_message(action: &str, input: usize, value: f32, is_press: bool) -> Option<MidiMessage> {
+    let action = match action {
+        "line.volume" => LineInputAction::SetVolume(value),
+        "line.trim" => LineInputAction::SetTrim(value),
+        "line.eq_hi" => LineInputAction::SetEqHi(value),
+        "line.eq_mid" => LineInputAction::SetEqMid(value),
+        "line.eq_lo" => LineInputAction::SetEqLo(value),
+        "line.cue" if is_press => LineInputAction::ToggleCue,
+        "line.talkover" if is_press => LineInputAction::ToggleTalkover,
+        _ => return None,
+    };
+    Some(MidiMessage::Global(GlobalAction::LineInput { input, action }))
+}
+
+/// Message for an `fx_bus.*` action on a mixer FX bus
+///
+/// Return level and knobs carry `value`; enable toggles on press only.
+fn fx_bus_message(action: &str, bus: usize, value: f32, is_press: bool) -> Option<MidiMessage> {
+    let action = match action {
+        "fx_bus.return" => FxBusAction::SetReturn(value),
+        "fx_bus.enable" if is_press => FxBusAction::ToggleEnabled,
+        "fx_bus.knob_1" => FxBusAction::SetKnob { knob: 0, value },
+        "fx_bus.knob_2" => FxBusAction::SetKnob { knob: 1, value },
+        "fx_bus.knob_3" => FxBusAction::SetKnob { knob: 2, value },
+        "fx_bus.knob_4" => FxBusAction::SetKnob { knob: 3, value },
+        _ => return None,
+    };
+    Some(MidiMessage::Global(GlobalAction::FxBus { bus, action }))
+}
+
 // ============================================================================
 // Standalone action dispatch for learn mode (no MappingEngine needed)
 // ============================================================================
@@ -976,7 +1176,7 @@ pub fn learn_mode_dispatch(
     let get_param = || param_value.unwrap_or(0);
 
     // Encoder delta extraction (relative mode: 1-63 positive, 65-127 negative)
-    let delta = if hardware_type == HardwareType::Encoder {
+    let delta = if matches!(hardware_type, HardwareType::Encoder | HardwareType::JogWheel) {
         encoder_to_delta(value, EncoderMode::Relative)
     } else {
         0
@@ -1016,6 +1216,12 @@ pub fn learn_mode_dispatch(
         "deck.beat_jump_backward" => {
             if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::BeatJumpBackward }) } else { None }
         }
+        "deck.phrase_jump_forward" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::PhraseJumpForward }) } else { None }
+        }
+        "deck.phrase_jump_backward" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::PhraseJumpBackward }) } else { None }
+        }
         "deck.slip" => {
             if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::ToggleSlip }) } else { None }
         }
@@ -1074,10 +1280,17 @@ pub fn learn_mode_dispatch(
         "mixer.eq_hi" => Some(MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqHi(normalized) }),
         "mixer.eq_mid" => Some(MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqMid(normalized) }),
         "mixer.eq_lo" => Some(MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqLo(normalized) }),
+        "mixer.eq_hi_mid" => Some(MidiMessage::Mixer { channel: deck, action: MixerAction::SetEqHiMid(normalized) }),
         "mixer.cue" => {
             if is_press { Some(MidiMessage::Mixer { channel: deck, action: MixerAction::ToggleCue }) } else { None }
         }
         "mixer.crossfader" => Some(MidiMessage::Mixer { channel: 0, action: MixerAction::SetCrossfader(normalized) }),
+        "mixer.crossfader_assign" => {
+            if is_press { Some(MidiMessage::Mixer { channel: deck, action: MixerAction::CycleCrossfaderAssign }) } else { None }
+        }
+        "mixer.fx_send" => {
+            Some(MidiMessage::Mixer { channel: deck, action: MixerAction::SetFxSend { send: get_param(), value: normalized } })
+        }
 
         // Browser
         "browser.scroll" => {
@@ -1111,6 +1324,68 @@ pub fn learn_mode_dispatch(
             Some(MidiMessage::Deck { deck, action: DeckAction::SetSuggestionEnergy(normalized) })
         }
 
+        // Sampler
+        "sampler.trigger" => {
+            if is_press { Some(MidiMessage::Global(GlobalAction::SamplerTrigger { slot: get_param() })) } else { None }
+        }
+        "sampler.stop_all" => {
+            if is_press { Some(MidiMessage::Global(GlobalAction::SamplerStopAll)) } else { None }
+        }
+        "deck.sampler_capture" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::SamplerCapture }) } else { None }
+        }
+
+        // Line inputs (mic/aux)
+        "line.volume" | "line.trim" | "line.eq_hi" | "line.eq_mid" | "line.eq_lo" | "line.cue" | "line.talkover" => {
+            line_input_message(action, get_param(), normalized, is_press)
+        }
+
+        // Mixer FX buses
+        _ if action.starts_with("fx_bus.") => fx_bus_message(action, get_param(), normalized, is_press),
+
+        // Track rating
+        "deck.rating_up" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::StepRating(1) }) } else { None }
+        }
+        "deck.rating_down" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::StepRating(-1) }) } else { None }
+        }
+        "deck.color_label" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::CycleColorLabel }) } else { None }
+        }
+
+        // Pitch / keylock
+        "deck.pitch" => {
+            // Bipolar: center = 0.5 = synced tempo
+            Some(MidiMessage::Deck { deck, action: DeckAction::SetPitch(normalized * 2.0 - 1.0) })
+        }
+        "deck.pitch_range" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::CyclePitchRange }) } else { None }
+        }
+        "deck.keylock" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::ToggleKeylock }) } else { None }
+        }
+        "deck.key_shift_up" | "deck.key_shift_down" | "deck.key_fine_up" | "deck.key_fine_down" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: key_shift_step(action) }) } else { None }
+        }
+        "deck.key_shift_reset" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::ResetKeyShift }) } else { None }
+        }
+        "deck.jog" => {
+            if delta != 0 {
+                let revolutions = delta as f32 / DEFAULT_JOG_TICKS_PER_REVOLUTION as f32;
+                Some(MidiMessage::Deck { deck, action: DeckAction::JogTurn { revolutions } })
+            } else {
+                None
+            }
+        }
+        "deck.jog_touch" => {
+            Some(MidiMessage::Deck { deck, action: DeckAction::JogTouch { touched: is_press, release: JogRelease::Resume } })
+        }
+        "deck.vinyl_mode" => {
+            if is_press { Some(MidiMessage::Deck { deck, action: DeckAction::ToggleVinylMode }) } else { None }
+        }
+
         _ => None,
     }
 }
@@ -104,6 +104,10 @@ pub enum DeckAction {
     BeatJumpForward,
     /// Beat jump backward
     BeatJumpBackward,
+    /// Jump to the next phrase start
+    PhraseJumpForward,
+    /// Jump to the current / previous phrase start
+    PhraseJumpBackward,
 
     // Slicer
     /// Slicer pad trigger
@@ -179,6 +183,20 @@ pub enum DeckAction {
         /// Nudge direction and amount (-1.0 to 1.0)
         amount: f32,
     },
+    /// Jog wheel turned (scratch when touched in vinyl mode, otherwise pitch bend)
+    JogTurn {
+        /// Platter revolutions since the last event (negative = backwards)
+        revolutions: f32,
+    },
+    /// Jog wheel touch sensor pressed or released
+    JogTouch {
+        /// Whether the platter is touched
+        touched: bool,
+        /// What the platter does when let go
+        release: JogRelease,
+    },
+    /// Toggle vinyl mode (jog touch scratches)
+    ToggleVinylMode,
 
     /// Set FX macro value (macro_index 0-3, value 0.0-1.0)
     SetFxMacro {
@@ -190,6 +208,60 @@ pub enum DeckAction {
 
     /// Set suggestion energy direction (0.0-1.0)
     SetSuggestionEnergy(f32),
+
+    /// Capture this deck's current loop (audible stems) into the first empty sampler slot
+    SamplerCapture,
+
+    /// Add (positive) or remove (negative) stars on the loaded track
+    StepRating(i8),
+
+    /// Move the loaded track to the next colour label
+    CycleColorLabel,
+
+    /// Set pitch fader position (-1.0 to 1.0, 0.0 = synced tempo)
+    SetPitch(f32),
+
+    /// Switch to the next pitch fader range (±6/10/16/50%)
+    CyclePitchRange,
+
+    /// Toggle keylock (master tempo)
+    ToggleKeylock,
+
+    /// Shift the key by whole semitones and fine cents
+    ShiftKey {
+        /// Semitone step (positive = up)
+        semitones: i8,
+        /// Cent step (positive = up)
+        cents: i8,
+    },
+
+    /// Clear the manual key shift
+    ResetKeyShift,
+}
+
+/// What the platter does when a touch-sensitive jog wheel is let go
+///
+/// Set per mapping with the `release` param on `deck.jog_touch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JogRelease {
+    /// Spin back up to speed and carry on
+    #[default]
+    Resume,
+    /// Fast backwards spin, then carry on
+    SpinBack,
+    /// Slow to a halt and stop the deck
+    Brake,
+}
+
+impl JogRelease {
+    /// Parse the `release` mapping param ("resume", "spinback", "brake")
+    pub fn from_param(value: &str) -> Self {
+        match value {
+            "spinback" | "spin_back" => Self::SpinBack,
+            "brake" => Self::Brake,
+            _ => Self::Resume,
+        }
+    }
 }
 
 /// Mixer actions
@@ -205,10 +277,16 @@ pub enum MixerAction {
     SetEqMid(f32),
     /// Set EQ low
     SetEqLo(f32),
+    /// Set EQ high-mid (4-band EQ model only)
+    SetEqHiMid(f32),
     /// Toggle headphone cue (PFL)
     ToggleCue,
-    /// Set crossfader position
+    /// Set crossfader position (0.0 = full A, 1.0 = full B)
     SetCrossfader(f32),
+    /// Cycle channel crossfader assignment (A → THRU → B)
+    CycleCrossfaderAssign,
+    /// Set the channel's send level to an FX send bus (0 = FX 1, 1 = FX 2)
+    SetFxSend { send: usize, value: f32 },
 }
 
 /// Browser/navigation actions
@@ -235,6 +313,8 @@ pub enum GlobalAction {
     SetBpm(f64),
     /// Adjust BPM by delta
     AdjustBpm(f64),
+    /// Follow the tempo of an incoming MIDI clock (already jitter-filtered)
+    ClockBpm(f64),
     /// Set master volume
     SetMasterVolume(f32),
     /// Set cue/headphone volume
@@ -254,6 +334,55 @@ pub enum GlobalAction {
     },
     /// Toggle settings modal open/close
     SettingsToggle,
+    /// Sampler pad pressed (one-shot retrigger / loop toggle)
+    SamplerTrigger {
+        /// Slot index (0-7)
+        slot: usize,
+    },
+    /// Stop every sampler slot
+    SamplerStopAll,
+    /// Mic/aux line input channel control
+    LineInput {
+        /// Line input (0 = mic, 1 = aux)
+        input: usize,
+        action: LineInputAction,
+    },
+    /// Mixer FX bus control
+    FxBus {
+        /// FX bus (0 = FX 1, 1 = FX 2, 2 = master insert)
+        bus: usize,
+        action: FxBusAction,
+    },
+}
+
+/// Line input (mic/aux) channel strip actions
+#[derive(Debug, Clone)]
+pub enum LineInputAction {
+    /// Set channel volume (0.0 - 1.0)
+    SetVolume(f32),
+    /// Set trim (0.0 = -24 dB, 0.5 = unity, 1.0 = +12 dB)
+    SetTrim(f32),
+    /// Set EQ high (0.0 - 1.0)
+    SetEqHi(f32),
+    /// Set EQ mid (0.0 - 1.0)
+    SetEqMid(f32),
+    /// Set EQ low (0.0 - 1.0)
+    SetEqLo(f32),
+    /// Toggle cue (PFL)
+    ToggleCue,
+    /// Toggle talkover (duck the decks while the input has signal)
+    ToggleTalkover,
+}
+
+/// Mixer FX bus (send/return or master insert) actions
+#[derive(Debug, Clone)]
+pub enum FxBusAction {
+    /// Set the bus return level (0.0 - 1.0, send buses only)
+    SetReturn(f32),
+    /// Toggle the bus effect chain on/off
+    ToggleEnabled,
+    /// Set one of the bus parameter knobs (0.0 - 1.0)
+    SetKnob { knob: usize, value: f32 },
 }
 
 impl MidiMessage {
@@ -0,0 +1,636 @@
+//! MIDI clock output and input
+//!
+//! **Output**: a dedicated thread sends 24 PPQN clock with Start/Continue/Stop
+//! and Song Position Pointer, phase-locked to the master deck. The timing comes
+//! from a [`ClockSource`] that the audio thread updates once per buffer; between
+//! buffers the thread extrapolates at the master tempo, so ticks follow what is
+//! heard at the speakers rather than the ~16ms UI tick.
+//!
+//! **Input**: clock ticks from another device are timed with the MIDI driver's
+//! timestamps and fitted over the last few beats, which filters out per-tick
+//! jitter before the tempo reaches the app as [`GlobalAction::ClockBpm`].
+
+use super::connection::{MidiConnection, MidiConnectionError};
+use crate::messages::{GlobalAction, MidiEvent, MidiMessage};
+use flume::Sender;
+use midir::{Ignore, MidiInputConnection, MidiOutputConnection};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// MIDI clock resolution (pulses per quarter note)
+pub const PPQN: u32 = 24;
+
+/// Clock ticks per Song Position Pointer step (one 16th note)
+const TICKS_PER_SIXTEENTH: i64 = PPQN as i64 / 4;
+
+/// Song Position Pointer range (14 bits of 16th notes, a multiple of a bar)
+const SONG_POSITION_RANGE: i64 = 1 << 14;
+
+/// Longest sleep between polls, so play/stop is picked up promptly
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Missed ticks sent as a burst before the generator re-locates instead
+const MAX_CATCH_UP_TICKS: i64 = 3;
+
+/// Phase error (in beats) below which the output is smoothed rather than snapped
+const PHASE_SNAP_THRESHOLD: f64 = 2.0 / PPQN as f64;
+
+/// Share of the phase error corrected per poll (absorbs audio callback jitter)
+const PHASE_CORRECTION_GAIN: f64 = 0.05;
+
+/// Clock ticks kept for the input tempo fit (4 beats)
+const FOLLOW_WINDOW: usize = 4 * PPQN as usize;
+
+/// Ticks needed before an input tempo is reported (1 beat)
+const FOLLOW_MIN_TICKS: usize = PPQN as usize + 1;
+
+/// Gap between input ticks that means the sender stopped clocking
+const FOLLOW_MAX_GAP_US: u64 = 500_000;
+
+/// Ticks between input tempo reports
+const FOLLOW_REPORT_INTERVAL: usize = PPQN as usize / 4;
+
+/// Input tempo change ignored as jitter
+const FOLLOW_HYSTERESIS_BPM: f64 = 0.05;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Clock source
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Where the master transport is at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockSnapshot {
+    /// Master deck position in beats, None when nothing is playing in sync
+    pub beat: Option<f64>,
+    /// Master tempo in BPM
+    pub bpm: f64,
+    /// When `beat` is heard at the output
+    pub at: Instant,
+}
+
+impl ClockSnapshot {
+    /// Beat position extrapolated to `now` (None while stopped)
+    fn beat_at(&self, now: Instant) -> Option<f64> {
+        Some(self.beat? + signed_secs(self.at, now) * self.bpm / 60.0)
+    }
+
+    /// When the transport reaches `beat` (`at` while stopped)
+    fn time_of(&self, beat: f64) -> Instant {
+        match self.beat {
+            Some(anchor) => offset(self.at, (beat - anchor) * 60.0 / self.bpm),
+            None => self.at,
+        }
+    }
+}
+
+/// Provides the master transport to the MIDI clock output
+///
+/// Implemented by the application, typically over atomics the audio thread
+/// publishes at the start of every buffer. Must be cheap and lock-free — it
+/// is polled once per clock tick.
+pub trait ClockSource: Send + Sync {
+    /// Latest transport snapshot, None before audio has started
+    fn snapshot(&self) -> Option<ClockSnapshot>;
+}
+
+/// Seconds from `from` to `to` (negative if `to` is earlier)
+fn signed_secs(from: Instant, to: Instant) -> f64 {
+    match to.checked_duration_since(from) {
+        Some(d) => d.as_secs_f64(),
+        None => -from.duration_since(to).as_secs_f64(),
+    }
+}
+
+/// `at` shifted by `secs` (which may be negative)
+fn offset(at: Instant, secs: f64) -> Instant {
+    if !secs.is_finite() {
+        return at;
+    }
+    if secs >= 0.0 {
+        at + Duration::from_secs_f64(secs)
+    } else {
+        at.checked_sub(Duration::from_secs_f64(-secs)).unwrap_or(at)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Clock output
+// ═══════════════════════════════════════════════════════════════════════
+
+/// MIDI system real-time and common messages used for clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockMessage {
+    /// Timing clock (0xF8), 24 per quarter note
+    Clock,
+    /// Start from the top (0xFA)
+    Start,
+    /// Continue from the current song position (0xFB)
+    Continue,
+    /// Stop (0xFC)
+    Stop,
+    /// Song Position Pointer in 16th notes (0xF2)
+    SongPosition(u16),
+}
+
+impl ClockMessage {
+    /// Encode to MIDI bytes (returns buffer and used length)
+    pub fn encode(self) -> ([u8; 3], usize) {
+        match self {
+            Self::Clock => ([0xF8, 0, 0], 1),
+            Self::Start => ([0xFA, 0, 0], 1),
+            Self::Continue => ([0xFB, 0, 0], 1),
+            Self::Stop => ([0xFC, 0, 0], 1),
+            Self::SongPosition(pos) => ([0xF2, (pos & 0x7F) as u8, ((pos >> 7) & 0x7F) as u8], 3),
+        }
+    }
+}
+
+/// Turns transport snapshots into a clock message stream
+///
+/// Pure state machine — the caller supplies the time, sends the messages and
+/// sleeps until the returned deadline.
+///
+/// - While the master deck plays, tick N is sent when the deck reaches beat
+///   N/24. Starting (or any jump) sends a Song Position Pointer to the next
+///   16th note plus Continue (Start at position 0), so slaves land on the
+///   same bar position.
+/// - With no master deck, Stop is sent and the clock keeps running at the
+///   global tempo so slaves hold their tempo.
+#[derive(Debug, Default)]
+pub struct ClockGenerator {
+    /// Smoothed transport used for tick timing
+    phase: Option<ClockSnapshot>,
+    /// Last tick sent (in 1/24 beats) while the transport runs
+    last_tick: Option<i64>,
+    /// Next free-running tick while the transport is stopped
+    free_next: Option<Instant>,
+}
+
+impl ClockGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance to `now`, appending due messages to `out`
+    ///
+    /// Returns when to poll next.
+    pub fn poll(
+        &mut self,
+        snapshot: Option<ClockSnapshot>,
+        now: Instant,
+        out: &mut Vec<ClockMessage>,
+    ) -> Instant {
+        let snapshot = snapshot.filter(|s| s.bpm.is_finite() && s.bpm > 0.0);
+        let wake = match snapshot {
+            Some(snapshot) if snapshot.beat.is_some() => self.poll_running(snapshot, now, out),
+            Some(snapshot) => self.poll_stopped(snapshot.bpm, now, out),
+            None => {
+                self.stop(out);
+                self.free_next = None;
+                now + MAX_POLL_INTERVAL
+            }
+        };
+        wake.clamp(now, now + MAX_POLL_INTERVAL)
+    }
+
+    fn poll_running(&mut self, snapshot: ClockSnapshot, now: Instant, out: &mut Vec<ClockMessage>) -> Instant {
+        self.free_next = None;
+        let phase = self.track_phase(snapshot, now);
+        let beat = phase.beat_at(now).unwrap_or_default();
+        let current = (beat * PPQN as f64).floor() as i64;
+
+        let last = match self.last_tick {
+            Some(last) if (-TICKS_PER_SIXTEENTH..=MAX_CATCH_UP_TICKS).contains(&(current - last)) => last,
+            Some(_) => {
+                // Beat jump, hot cue or a new master deck: re-locate slaves
+                out.push(ClockMessage::Stop);
+                self.locate(beat, out)
+            }
+            None => self.locate(beat, out),
+        };
+
+        if current > last {
+            out.extend(std::iter::repeat_n(ClockMessage::Clock, (current - last) as usize));
+            self.last_tick = Some(current);
+        }
+
+        let next = current.max(last) + 1;
+        phase.time_of(next as f64 / PPQN as f64)
+    }
+
+    fn poll_stopped(&mut self, bpm: f64, now: Instant, out: &mut Vec<ClockMessage>) -> Instant {
+        self.stop(out);
+
+        let tick = Duration::from_secs_f64(60.0 / (bpm * PPQN as f64));
+        let next = self.free_next.get_or_insert(now);
+        if *next <= now {
+            out.push(ClockMessage::Clock);
+            *next += tick;
+            if *next <= now {
+                // Fell behind by more than a tick — restart the grid
+                *next = now + tick;
+            }
+        }
+        *next
+    }
+
+    /// Follow the published transport, low-pass filtering small phase errors
+    ///
+    /// Each snapshot is stamped by the audio callback, whose wake-up time
+    /// jitters by a fraction of a buffer. Correcting a share of the error per
+    /// poll keeps tick spacing even; large errors (jumps) are taken at once.
+    fn track_phase(&mut self, snapshot: ClockSnapshot, now: Instant) -> ClockSnapshot {
+        let measured = snapshot.beat_at(now).unwrap_or_default();
+        let beat = match self.phase.and_then(|p| p.beat_at(now)) {
+            Some(predicted) if (measured - predicted).abs() < PHASE_SNAP_THRESHOLD => {
+                predicted + (measured - predicted) * PHASE_CORRECTION_GAIN
+            }
+            _ => measured,
+        };
+        let phase = ClockSnapshot { beat: Some(beat), bpm: snapshot.bpm, at: now };
+        self.phase = Some(phase);
+        phase
+    }
+
+    /// Position slaves at the next 16th note and arm the clock for it
+    ///
+    /// Returns the new last tick (the one just before the located position).
+    fn locate(&mut self, beat: f64, out: &mut Vec<ClockMessage>) -> i64 {
+        let sixteenth = ((beat * 4.0).ceil() as i64).max(0);
+        if sixteenth == 0 {
+            out.push(ClockMessage::Start);
+        } else {
+            let position = sixteenth.rem_euclid(SONG_POSITION_RANGE) as u16;
+            out.push(ClockMessage::SongPosition(position));
+            out.push(ClockMessage::Continue);
+        }
+        // The first clock after Start/Continue marks the located position
+        let last = sixteenth * TICKS_PER_SIXTEENTH - 1;
+        self.last_tick = Some(last);
+        last
+    }
+
+    /// Send Stop if the transport was running
+    fn stop(&mut self, out: &mut Vec<ClockMessage>) {
+        self.phase = None;
+        if self.last_tick.take().is_some() {
+            out.push(ClockMessage::Stop);
+        }
+    }
+}
+
+/// MIDI clock output to one port
+///
+/// Runs a dedicated thread that polls the [`ClockSource`] and sends clock
+/// messages at their due time. Dropping the output sends Stop (if running)
+/// and closes the port.
+pub struct MidiClockOutput {
+    /// Dropped to stop the thread
+    stop_tx: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MidiClockOutput {
+    /// Connect to an output port (case-insensitive substring) and start clocking
+    pub fn connect(port_match: &str, source: Arc<dyn ClockSource>) -> Result<Self, MidiConnectionError> {
+        let connection = MidiConnection::connect_output(port_match)
+            .ok_or_else(|| MidiConnectionError::PortNotFound(port_match.to_string()))?;
+        let (stop_tx, stop_rx) = flume::bounded::<()>(1);
+
+        let thread = std::thread::Builder::new()
+            .name("midi-clock-out".into())
+            .spawn(move || Self::run(connection, source, stop_rx))
+            .map_err(|e| MidiConnectionError::ConnectionError(e.to_string()))?;
+
+        log::info!("MIDI clock: Sending clock to '{}'", port_match);
+
+        Ok(Self {
+            stop_tx: Some(stop_tx),
+            thread: Some(thread),
+        })
+    }
+
+    fn run(mut connection: MidiOutputConnection, source: Arc<dyn ClockSource>, stop_rx: flume::Receiver<()>) {
+        crate::rt::pin_to_big_cores();
+
+        let mut generator = ClockGenerator::new();
+        let mut messages = Vec::with_capacity(8);
+
+        loop {
+            messages.clear();
+            let wake = generator.poll(source.snapshot(), Instant::now(), &mut messages);
+            for message in &messages {
+                let (bytes, len) = message.encode();
+                if let Err(e) = connection.send(&bytes[..len]) {
+                    log::warn!("MIDI clock: Failed to send {:?}: {}", message, e);
+                }
+            }
+
+            match stop_rx.recv_deadline(wake) {
+                Err(flume::RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        }
+
+        // Leave slaves stopped rather than waiting on a clock that never comes
+        messages.clear();
+        generator.stop(&mut messages);
+        for message in &messages {
+            let (bytes, len) = message.encode();
+            let _ = connection.send(&bytes[..len]);
+        }
+        log::info!("MIDI clock: Output stopped");
+    }
+}
+
+impl Drop for MidiClockOutput {
+    fn drop(&mut self) {
+        self.stop_tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Clock input
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Derives a steady tempo from incoming clock ticks
+///
+/// Pure state machine fed with message bytes and their driver timestamps.
+/// The tempo is a least-squares fit over the last 4 beats of ticks, so a
+/// single late or early tick barely moves it; reports are throttled and
+/// only sent when the tempo moves by more than the jitter hysteresis.
+#[derive(Debug, Default)]
+pub struct ClockFollower {
+    /// Timestamps of recent ticks (µs, driver clock)
+    ticks: VecDeque<u64>,
+    /// Last tempo reported
+    reported: Option<f64>,
+    /// Ticks since the last report
+    since_report: usize,
+}
+
+impl ClockFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle one MIDI message, returning a new tempo to apply
+    pub fn on_message(&mut self, data: &[u8], timestamp_us: u64) -> Option<f64> {
+        match data.first()? {
+            0xF8 => self.on_tick(timestamp_us),
+            // Transport restart: tick spacing across it is meaningless
+            0xFA..=0xFC => {
+                self.ticks.clear();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn on_tick(&mut self, timestamp_us: u64) -> Option<f64> {
+        if let Some(&last) = self.ticks.back() {
+            if timestamp_us <= last || timestamp_us - last > FOLLOW_MAX_GAP_US {
+                self.ticks.clear();
+            }
+        }
+        self.ticks.push_back(timestamp_us);
+        if self.ticks.len() > FOLLOW_WINDOW {
+            self.ticks.pop_front();
+        }
+        self.since_report += 1;
+
+        if self.ticks.len() < FOLLOW_MIN_TICKS || self.since_report < FOLLOW_REPORT_INTERVAL {
+            return None;
+        }
+
+        let bpm = (self.fit_bpm()? * 100.0).round() / 100.0;
+        if self.reported.is_some_and(|r| (bpm - r).abs() < FOLLOW_HYSTERESIS_BPM) {
+            return None;
+        }
+        self.reported = Some(bpm);
+        self.since_report = 0;
+        Some(bpm)
+    }
+
+    /// Tempo from the least-squares slope of tick time over tick index
+    fn fit_bpm(&self) -> Option<f64> {
+        let first = *self.ticks.front()?;
+        let n = self.ticks.len() as f64;
+        let mean_index = (n - 1.0) / 2.0;
+        let mean_time = self.ticks.iter().map(|&t| (t - first) as f64).sum::<f64>() / n;
+
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (i, &t) in self.ticks.iter().enumerate() {
+            let di = i as f64 - mean_index;
+            covariance += di * ((t - first) as f64 - mean_time);
+            variance += di * di;
+        }
+        let us_per_tick = covariance / variance;
+        (us_per_tick > 0.0).then(|| 60_000_000.0 / (us_per_tick * PPQN as f64))
+    }
+}
+
+/// Callback data passed to midir
+struct ClockInputData {
+    follower: ClockFollower,
+    message_tx: Sender<MidiEvent>,
+}
+
+/// MIDI clock input from one port
+///
+/// Owns its own midir connection (clock bytes are dropped by the controller
+/// input parser) and sends [`GlobalAction::ClockBpm`] on tempo changes.
+pub struct MidiClockInput {
+    _connection: MidiInputConnection<ClockInputData>,
+}
+
+impl MidiClockInput {
+    /// Connect to an input port (case-insensitive substring) and follow its clock
+    pub fn connect(port_match: &str, message_tx: Sender<MidiEvent>) -> Result<Self, MidiConnectionError> {
+        let (mut midi_in, port) = MidiConnection::find_input_port(port_match)?;
+        midi_in.ignore(Ignore::SysexAndActiveSense);
+
+        let data = ClockInputData {
+            follower: ClockFollower::new(),
+            message_tx,
+        };
+        let connection = midi_in
+            .connect(&port, "mesh-midi-clock-in", Self::midi_callback, data)
+            .map_err(|e| MidiConnectionError::ConnectionError(e.to_string()))?;
+
+        log::info!("MIDI clock: Following clock from '{}'", port_match);
+
+        Ok(Self { _connection: connection })
+    }
+
+    fn midi_callback(timestamp_us: u64, data: &[u8], callback_data: &mut ClockInputData) {
+        if let Some(bpm) = callback_data.follower.on_message(data, timestamp_us) {
+            log::debug!("MIDI clock: Input tempo {:.2} BPM", bpm);
+            let _ = callback_data.message_tx.try_send(MidiEvent {
+                message: MidiMessage::Global(GlobalAction::ClockBpm(bpm)),
+                engine_dispatched: false,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the generator against an ideal transport with jittered snapshots,
+    /// returning (time in seconds, message) pairs
+    fn simulate(
+        bpm: f64,
+        start_beat: f64,
+        duration: f64,
+        jitter: impl Fn(usize) -> f64,
+    ) -> Vec<(f64, ClockMessage)> {
+        let t0 = Instant::now();
+        let buffer = 0.005;
+        let mut generator = ClockGenerator::new();
+        let mut messages = Vec::new();
+        let mut sent = Vec::new();
+        let mut now = t0;
+
+        while signed_secs(t0, now) < duration {
+            let elapsed = signed_secs(t0, now);
+            let buffer_index = (elapsed / buffer) as usize;
+            let buffer_start = buffer_index as f64 * buffer;
+            let snapshot = ClockSnapshot {
+                beat: Some(start_beat + buffer_start * bpm / 60.0),
+                bpm,
+                at: offset(t0, buffer_start + jitter(buffer_index)),
+            };
+
+            messages.clear();
+            let wake = generator.poll(Some(snapshot), now, &mut messages);
+            sent.extend(messages.iter().map(|m| (elapsed, *m)));
+            now = wake.max(now + Duration::from_micros(50));
+        }
+        sent
+    }
+
+    fn tick_times(sent: &[(f64, ClockMessage)]) -> Vec<f64> {
+        sent.iter()
+            .filter(|(_, m)| *m == ClockMessage::Clock)
+            .map(|(t, _)| *t)
+            .collect()
+    }
+
+    #[test]
+    fn test_song_position_encoding() {
+        assert_eq!(ClockMessage::SongPosition(0x1234).encode(), ([0xF2, 0x34, 0x24], 3));
+        assert_eq!(ClockMessage::Clock.encode().0[0], 0xF8);
+    }
+
+    #[test]
+    fn test_start_from_top() {
+        let sent = simulate(120.0, 0.0, 1.0, |_| 0.0);
+        assert_eq!(sent[0].1, ClockMessage::Start);
+        // 2 beats per second at 120 BPM
+        let ticks = tick_times(&sent).len() as i64;
+        assert!((ticks - 48).abs() <= 1, "got {} ticks", ticks);
+    }
+
+    #[test]
+    fn test_continue_locates_next_sixteenth() {
+        // 33.1 beats → next 16th note is 133, first tick lands on beat 33.25
+        let sent = simulate(120.0, 33.1, 0.5, |_| 0.0);
+        assert_eq!(sent[0].1, ClockMessage::SongPosition(133));
+        assert_eq!(sent[1].1, ClockMessage::Continue);
+        let first_tick = tick_times(&sent)[0];
+        let expected = 0.15 * 60.0 / 120.0;
+        assert!((first_tick - expected).abs() < 0.001, "first tick at {}", first_tick);
+    }
+
+    #[test]
+    fn test_callback_jitter_is_smoothed() {
+        // ±1.5ms jitter on every audio buffer timestamp
+        let sent = simulate(174.0, 0.0, 4.0, |i| if i % 2 == 0 { 0.0015 } else { -0.0015 });
+        let ticks = tick_times(&sent);
+        let expected = 60.0 / (174.0 * PPQN as f64);
+        let worst = ticks[24..]
+            .windows(2)
+            .map(|w| ((w[1] - w[0]) - expected).abs())
+            .fold(0.0, f64::max);
+        assert!(worst < 0.0006, "worst spacing error {}s", worst);
+    }
+
+    #[test]
+    fn test_jump_relocates() {
+        let t0 = Instant::now();
+        let mut generator = ClockGenerator::new();
+        let mut out = Vec::new();
+        let snapshot = |beat| Some(ClockSnapshot { beat: Some(beat), bpm: 120.0, at: t0 });
+
+        generator.poll(snapshot(16.0), t0, &mut out);
+        assert_eq!(out, vec![ClockMessage::SongPosition(64), ClockMessage::Continue, ClockMessage::Clock]);
+
+        // Hot cue back to beat 4
+        out.clear();
+        generator.poll(snapshot(4.0), t0, &mut out);
+        assert_eq!(
+            out,
+            vec![ClockMessage::Stop, ClockMessage::SongPosition(16), ClockMessage::Continue, ClockMessage::Clock]
+        );
+    }
+
+    #[test]
+    fn test_stop_keeps_clocking_at_tempo() {
+        let t0 = Instant::now();
+        let mut generator = ClockGenerator::new();
+        let mut out = Vec::new();
+        generator.poll(Some(ClockSnapshot { beat: Some(8.0), bpm: 120.0, at: t0 }), t0, &mut out);
+
+        out.clear();
+        let stopped = Some(ClockSnapshot { beat: None, bpm: 120.0, at: t0 });
+        let wake = generator.poll(stopped, t0, &mut out);
+        assert_eq!(out, vec![ClockMessage::Stop, ClockMessage::Clock]);
+        assert!(wake > t0);
+
+        // No master tempo at all: silence
+        out.clear();
+        generator.poll(None, t0 + Duration::from_secs(1), &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_follower_filters_jitter() {
+        let mut follower = ClockFollower::new();
+        let us_per_tick = 60_000_000.0 / (128.0 * PPQN as f64);
+        let mut last = None;
+        for i in 0..(8 * PPQN as u64) {
+            // ±2ms of pseudo-random jitter per tick
+            let jitter = ((i * 7919) % 9) as f64 * 500.0 - 2000.0;
+            let timestamp = 1_000_000 + (i as f64 * us_per_tick + jitter) as u64;
+            if let Some(bpm) = follower.on_message(&[0xF8], timestamp) {
+                last = Some(bpm);
+            }
+        }
+        let bpm = last.expect("tempo reported");
+        assert!((bpm - 128.0).abs() < 0.1, "got {}", bpm);
+    }
+
+    #[test]
+    fn test_follower_ignores_steady_tempo_and_gaps() {
+        let mut follower = ClockFollower::new();
+        let us_per_tick = 60_000_000 / (140 * PPQN as u64);
+        let reports = (0..(8 * PPQN as u64))
+            .filter_map(|i| follower.on_message(&[0xF8], i * us_per_tick))
+            .count();
+        assert_eq!(reports, 1);
+
+        // A long gap restarts the fit instead of averaging across it
+        let resumed = 60_000_000;
+        let reports: Vec<_> = (0..(2 * PPQN as u64))
+            .filter_map(|i| follower.on_message(&[0xF8], resumed + i * us_per_tick * 2))
+            .collect();
+        assert_eq!(reports, vec![70.0]);
+    }
+}
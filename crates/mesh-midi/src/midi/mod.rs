@@ -1,12 +1,15 @@
 //! MIDI protocol backend
 //!
-//! Handles MIDI device connection, input parsing, and LED output via midir.
+//! Handles MIDI device connection, input parsing, LED output and MIDI clock
+//! via midir.
 //! This is one of potentially several protocol backends (alongside HID).
 
+pub mod clock;
 pub mod connection;
 pub mod input;
 pub mod output;
 
+pub use clock::{ClockSnapshot, ClockSource, MidiClockInput, MidiClockOutput};
 pub use connection::{MidiConnection, MidiConnectionError};
 pub use input::{MidiInputEvent, MidiInputHandler};
 pub use output::MidiOutputHandler;
@@ -148,12 +148,15 @@ pub fn denormalize_to_midi(value: f32, range: ControlRange) -> u8 {
 pub fn range_for_action(action: &str) -> ControlRange {
     match action {
         // Mixer controls
-        "mixer.volume" | "mixer.eq_hi" | "mixer.eq_mid" | "mixer.eq_lo" => ControlRange::Eq,
+        "mixer.volume" | "mixer.eq_hi" | "mixer.eq_hi_mid" | "mixer.eq_mid" | "mixer.eq_lo" => ControlRange::Eq,
         "mixer.filter" => ControlRange::Bipolar,
         "mixer.crossfader" => ControlRange::Unit,
+        "line.volume" | "line.trim" | "line.eq_hi" | "line.eq_mid" | "line.eq_lo" => ControlRange::Eq,
+        "mixer.fx_send" | "fx_bus.return" => ControlRange::Unit,
 
         // Deck controls
         "deck.effect_param" | "deck.fx_macro" => ControlRange::Unit,
+        "deck.pitch" => ControlRange::Bipolar,
 
         // Global controls
         "global.master_volume" | "global.cue_volume" => ControlRange::Unit,
@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use mesh_core::audio::{self, AudioConfig, AudioHandle, AudioResult, DeviceId};
 use mesh_core::db::DatabaseService;
-use mesh_core::engine::{DeckAtomics, LevelAtomics, LinkedStemAtomics, SlicerAtomics};
+use mesh_core::engine::{DeckAtomics, LevelAtomics, LinkedStemAtomics, MasterClockAtomics, SamplerAtomics, SlicerAtomics};
 use mesh_core::loader::LinkedStemResultReceiver;
 use mesh_core::types::NUM_DECKS;
 
@@ -35,10 +35,12 @@ pub type AudioSystemResult = (
     LinkedStemResultReceiver,
     Arc<AtomicBool>,  // clip_indicator
     Arc<LevelAtomics>, // level_atomics for peak metering
+    Arc<SamplerAtomics>, // sampler slot state
     u32,              // sample_rate
     String,           // actual JACK client name
     Arc<AtomicU64>,   // output_latency_samples
     Arc<AtomicU32>,   // internal_latency_samples
+    Arc<MasterClockAtomics>, // master transport for MIDI clock
     rtrb::Producer<mesh_core::engine::EngineCommand>,  // direct_command_producer
 );
 
@@ -50,15 +52,22 @@ pub type AudioSystemResult = (
 /// # Arguments
 /// * `_client_name` - Client name (kept for API compatibility, not used with CPAL)
 /// * `db_service` - Database service for the audio engine
+/// * `input_count` - Stereo inputs to open for DVS (0 = none)
+/// * `mic_input`, `aux_input` - Open stereo inputs for the mixer's line channels
 ///
 /// # Returns
 /// Tuple of (handle, command_sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, sample_rate)
 pub fn start_audio_system(
     _client_name: &str,
     db_service: Arc<DatabaseService>,
+    input_count: usize,
+    mic_input: bool,
+    aux_input: bool,
 ) -> AudioResult<AudioSystemResult> {
     // Use master+cue mode for DJ player
-    let config = AudioConfig::master_and_cue();
+    let config = AudioConfig::master_and_cue()
+        .with_inputs(input_count)
+        .with_line_inputs(mic_input, aux_input);
 
     let result = audio::start_audio_system(&config, db_service)?;
 
@@ -71,10 +80,12 @@ pub fn start_audio_system(
         result.linked_stem_receiver,
         result.clip_indicator,
         result.level_atomics,
+        result.sampler_atomics,
         result.sample_rate,
         result.client_name,
         result.output_latency_samples,
         result.internal_latency_samples,
+        result.master_clock,
         result.direct_command_producer,
     ))
 }
@@ -86,8 +97,13 @@ pub fn start_audio_system_with_devices(
     db_service: Arc<DatabaseService>,
     master_device: Option<DeviceId>,
     cue_device: Option<DeviceId>,
+    input_count: usize,
+    mic_input: bool,
+    aux_input: bool,
 ) -> AudioResult<AudioSystemResult> {
-    let mut config = AudioConfig::master_and_cue();
+    let mut config = AudioConfig::master_and_cue()
+        .with_inputs(input_count)
+        .with_line_inputs(mic_input, aux_input);
     config.master_device = master_device;
     config.cue_device = cue_device;
 
@@ -102,10 +118,12 @@ pub fn start_audio_system_with_devices(
         result.linked_stem_receiver,
         result.clip_indicator,
         result.level_atomics,
+        result.sampler_atomics,
         result.sample_rate,
         result.client_name,
         result.output_latency_samples,
         result.internal_latency_samples,
+        result.master_clock,
         result.direct_command_producer,
     ))
 }
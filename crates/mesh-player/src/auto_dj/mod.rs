@@ -0,0 +1,367 @@
+//! Auto DJ — unattended mixing driven by the suggestion engine
+//!
+//! Auto DJ keeps one deck playing and prepares the next track on an idle deck:
+//!
+//! 1. **Picking** — take the next track from the playlist queue, or ask the
+//!    suggestion engine for the best follow-up to the live track.
+//! 2. **Loading** — load it on the idle deck (logged to history as `auto_dj`).
+//! 3. **Cued** — park the incoming deck on its mix-in downbeat and wait for
+//!    the live deck to reach its mix-out downbeat.
+//! 4. **Mixing** — start the incoming deck (phase sync locks it to the beat)
+//!    and drive volume, EQ, filter and stem mutes along the transition curve.
+//!
+//! When the transition completes the outgoing deck is stopped and returned to
+//! its cue point, the incoming deck becomes live and the cycle starts again.
+//!
+//! This module holds the state and the pure mix planning/transition math.
+//! The message handler in `ui/handlers/auto_dj.rs` applies it to the app.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::Instant;
+
+use mesh_core::audio_file::TrackMetadata;
+
+use crate::config::AutoDjTransition;
+use crate::history::SuggestionContext;
+
+/// Beats per bar (mesh grids are 4/4)
+const BEATS_PER_BAR: usize = 4;
+
+/// Cue labels that mark where a track's outro (mix-out) begins
+const OUTRO_LABELS: [&str; 3] = ["outro", "mix out", "mixout"];
+
+// ============================================================================
+// Mix Planning
+// ============================================================================
+
+/// Where a transition starts and ends on both decks (sample positions)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MixPlan {
+    /// Outgoing deck position where the incoming deck starts
+    pub out_start: u64,
+    /// Outgoing deck position where the transition is complete
+    pub out_end: u64,
+    /// Incoming deck position to cue before starting it
+    pub in_start: u64,
+}
+
+impl MixPlan {
+    /// Fallback plan for tracks without a usable beat grid:
+    /// mix over the last `length` samples, incoming from its start
+    pub fn at_end(duration: u64, length: u64) -> Self {
+        Self {
+            out_start: duration.saturating_sub(length),
+            out_end: duration,
+            in_start: 0,
+        }
+    }
+
+    /// Transition progress (0.0–1.0) for an outgoing deck position
+    pub fn progress(&self, out_position: u64) -> f32 {
+        if self.out_end <= self.out_start {
+            return if out_position >= self.out_end { 1.0 } else { 0.0 };
+        }
+        let elapsed = out_position.saturating_sub(self.out_start) as f64;
+        (elapsed / (self.out_end - self.out_start) as f64).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Plan a transition of `bars` bars from `outgoing` into `incoming`.
+///
+/// The mix-out starts at the outgoing track's outro cue (a cue labelled
+/// "Outro"/"Mix Out"), or `bars` bars before the end of its grid, snapped to
+/// a bar line. The incoming track is cued `bars` bars before its drop marker
+/// so the drop lands as the outgoing track leaves; without a drop marker it
+/// starts on its first downbeat. Returns `None` when either grid is too short.
+pub fn plan_mix(outgoing: &TrackMetadata, incoming: &TrackMetadata, bars: u32) -> Option<MixPlan> {
+    let length = bars.max(1) as usize * BEATS_PER_BAR;
+
+    let out_beats = &outgoing.beat_grid.beats;
+    if out_beats.len() <= length {
+        return None;
+    }
+    // Latest bar line that still leaves room for the whole transition
+    let last_bar = (out_beats.len() - 1) / BEATS_PER_BAR * BEATS_PER_BAR;
+    let latest_start = last_bar.checked_sub(length)?;
+    let start = outro_cue(outgoing)
+        .map(|pos| nearest_bar(out_beats, pos))
+        .map_or(latest_start, |idx| idx.min(latest_start));
+
+    let in_beats = &incoming.beat_grid.beats;
+    let in_first = *in_beats.first()?;
+    let in_start = incoming.drop_marker
+        .map(|drop| nearest_bar(in_beats, drop))
+        .and_then(|drop_idx| drop_idx.checked_sub(length))
+        .map_or(in_first, |idx| in_beats[idx]);
+
+    Some(MixPlan {
+        out_start: out_beats[start],
+        out_end: out_beats[start + length],
+        in_start,
+    })
+}
+
+/// Position of the first cue labelled as an outro
+fn outro_cue(track: &TrackMetadata) -> Option<u64> {
+    track.cue_points.iter()
+        .filter(|cue| {
+            let label = cue.label.to_lowercase();
+            OUTRO_LABELS.iter().any(|l| label.contains(l))
+        })
+        .map(|cue| cue.sample_position)
+        .min()
+}
+
+/// Index of the bar line (every 4th beat from the first) nearest to `position`
+fn nearest_bar(beats: &[u64], position: u64) -> usize {
+    let beat = match beats.binary_search(&position) {
+        Ok(idx) => idx,
+        Err(0) => 0,
+        Err(idx) if idx >= beats.len() => beats.len() - 1,
+        Err(idx) => {
+            if position - beats[idx - 1] <= beats[idx] - position { idx - 1 } else { idx }
+        }
+    };
+    let bar = (beat + BEATS_PER_BAR / 2) / BEATS_PER_BAR * BEATS_PER_BAR;
+    if bar < beats.len() { bar } else { bar - BEATS_PER_BAR }
+}
+
+// ============================================================================
+// Transition Curves
+// ============================================================================
+
+/// Mixer state Auto DJ drives on one channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelLevels {
+    /// Channel fader (0–1)
+    pub volume: f32,
+    /// EQ knobs (0 = kill, 0.5 = flat)
+    pub eq_hi: f32,
+    pub eq_mid: f32,
+    pub eq_lo: f32,
+    /// Filter (-1 = low-pass, 0 = off, 1 = high-pass)
+    pub filter: f32,
+    /// Stem mutes (vocals, drums, bass, other)
+    pub stems_muted: [bool; 4],
+}
+
+impl ChannelLevels {
+    /// Full volume, flat EQ, no filter, all stems playing
+    pub const FLAT: Self = Self {
+        volume: 1.0,
+        eq_hi: 0.5,
+        eq_mid: 0.5,
+        eq_lo: 0.5,
+        filter: 0.0,
+        stems_muted: [false; 4],
+    };
+}
+
+const VOCALS: usize = 0;
+const DRUMS: usize = 1;
+const BASS: usize = 2;
+const OTHER: usize = 3;
+
+/// Maximum filter sweep (keeps the filtered track faintly audible)
+const FILTER_DEPTH: f32 = 0.7;
+
+/// Channel levels for the (outgoing, incoming) decks at `progress` (0.0–1.0)
+///
+/// Every style starts with the outgoing deck flat and ends with the incoming
+/// deck flat, so consecutive transitions hand over cleanly.
+pub fn transition_levels(style: AutoDjTransition, progress: f32) -> (ChannelLevels, ChannelLevels) {
+    let p = progress.clamp(0.0, 1.0);
+    let mut out = ChannelLevels::FLAT;
+    let mut inc = ChannelLevels::FLAT;
+
+    match style {
+        AutoDjTransition::EqSwap => {
+            inc.volume = ramp(p, 0.0, 0.5);
+            out.volume = 1.0 - ramp(p, 0.5, 1.0);
+            // Swap the lows on the halfway downbeat
+            let swapped = p >= 0.5;
+            inc.eq_lo = if swapped { 0.5 } else { 0.0 };
+            out.eq_lo = if swapped { 0.0 } else { 0.5 };
+        }
+        AutoDjTransition::Filter => {
+            inc.volume = ramp(p, 0.0, 0.25);
+            inc.filter = FILTER_DEPTH * (1.0 - ramp(p, 0.0, 0.75));
+            out.filter = -FILTER_DEPTH * ramp(p, 0.25, 1.0);
+            out.volume = 1.0 - ramp(p, 0.75, 1.0);
+        }
+        AutoDjTransition::BassSwap => {
+            inc.volume = ramp(p, 0.0, 0.5);
+            out.volume = 1.0 - ramp(p, 0.5, 1.0);
+            let swapped = p >= 0.5;
+            inc.stems_muted[BASS] = !swapped;
+            out.stems_muted[BASS] = swapped;
+        }
+        AutoDjTransition::StemHandover => {
+            // Incoming: drums, then other, bass and vocals on quarter marks.
+            // Outgoing: vocals leave first so the two never overlap.
+            inc.volume = ramp(p, 0.0, 0.25);
+            out.volume = 1.0 - ramp(p, 0.75, 1.0);
+            inc.stems_muted[OTHER] = p < 0.25;
+            inc.stems_muted[BASS] = p < 0.5;
+            inc.stems_muted[VOCALS] = p < 0.75;
+            out.stems_muted[VOCALS] = p >= 0.25;
+            out.stems_muted[BASS] = p >= 0.5;
+            out.stems_muted[OTHER] = p >= 0.75;
+            out.stems_muted[DRUMS] = p >= 1.0;
+        }
+    }
+
+    (out, inc)
+}
+
+/// Linear 0→1 ramp between `from` and `to`
+fn ramp(p: f32, from: f32, to: f32) -> f32 {
+    ((p - from) / (to - from)).clamp(0.0, 1.0)
+}
+
+// ============================================================================
+// State
+// ============================================================================
+
+/// A track chosen to play next
+#[derive(Debug, Clone)]
+pub struct AutoDjPick {
+    pub path: String,
+    /// Suggestion score/tags (None for playlist picks)
+    pub context: Option<SuggestionContext>,
+}
+
+/// Where Auto DJ is in its cycle
+#[derive(Debug, Clone)]
+pub enum AutoDjPhase {
+    Off,
+    /// Waiting for the next pick; `live` is the playing deck (None = nothing yet)
+    Picking { live: Option<usize> },
+    /// The pick is loading on `incoming`
+    Loading { live: Option<usize>, incoming: usize, path: String, since: Instant },
+    /// Incoming is parked on its mix-in point, waiting for the live deck's mix-out
+    Cued { live: usize, incoming: usize, path: String, plan: MixPlan },
+    /// Transition in progress
+    Mixing { live: usize, incoming: usize, plan: MixPlan },
+}
+
+/// Auto DJ runtime state
+pub struct AutoDjState {
+    pub phase: AutoDjPhase,
+    /// Remaining playlist tracks (Playlist source only)
+    pub queue: VecDeque<String>,
+    /// Tracks Auto DJ tried to load this run (never picked twice)
+    pub attempted: HashSet<String>,
+    /// Levels last sent during the current transition (out, in)
+    pub applied: Option<(ChannelLevels, ChannelLevels)>,
+}
+
+impl AutoDjState {
+    pub fn new() -> Self {
+        Self {
+            phase: AutoDjPhase::Off,
+            queue: VecDeque::new(),
+            attempted: HashSet::new(),
+            applied: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !matches!(self.phase, AutoDjPhase::Off)
+    }
+
+    /// Whether Auto DJ issued the pending load of `path` on `deck`
+    /// (used to attribute the load in history)
+    pub fn owns_load(&self, deck: usize, path: &str) -> bool {
+        matches!(&self.phase, AutoDjPhase::Loading { incoming, path: p, .. }
+            if *incoming == deck && p == path)
+    }
+
+    /// Stop and forget the queue
+    pub fn stop(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for AutoDjState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mesh_core::audio_file::{BeatGrid, CuePoint};
+
+    /// Track with `beats` beats every 24000 samples (120 BPM at 48 kHz)
+    fn track(beats: u64, drop_beat: Option<u64>) -> TrackMetadata {
+        TrackMetadata {
+            beat_grid: BeatGrid {
+                beats: (0..beats).map(|b| 1000 + b * 24_000).collect(),
+                first_beat_sample: Some(1000),
+                anchors: Vec::new(),
+            },
+            drop_marker: drop_beat.map(|b| 1000 + b * 24_000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plans_mix_out_at_grid_end_and_mix_in_before_drop() {
+        let outgoing = track(257, None);
+        let incoming = track(300, Some(129)); // drop slightly off a bar line (128)
+        let plan = plan_mix(&outgoing, &incoming, 16).unwrap();
+
+        // Last bar line is beat 256; a 64-beat mix starts at beat 192
+        assert_eq!(plan.out_start, 1000 + 192 * 24_000);
+        assert_eq!(plan.out_end, 1000 + 256 * 24_000);
+        // Drop snaps to beat 128 → cue 64 beats earlier
+        assert_eq!(plan.in_start, 1000 + 64 * 24_000);
+    }
+
+    #[test]
+    fn outro_cue_and_missing_drop() {
+        let mut outgoing = track(400, None);
+        outgoing.cue_points.push(CuePoint {
+            index: 7,
+            sample_position: 1000 + 161 * 24_000,
+            label: "Outro".to_string(),
+            color: None,
+        });
+        let incoming = track(300, Some(20)); // drop too early to fit a full mix
+        let plan = plan_mix(&outgoing, &incoming, 8).unwrap();
+        assert_eq!(plan.out_start, 1000 + 160 * 24_000);
+        assert_eq!(plan.in_start, 1000);
+
+        // Grid shorter than the transition
+        assert!(plan_mix(&track(20, None), &incoming, 8).is_none());
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        let plan = MixPlan { out_start: 100, out_end: 200, in_start: 0 };
+        assert_eq!(plan.progress(50), 0.0);
+        assert_eq!(plan.progress(150), 0.5);
+        assert_eq!(plan.progress(500), 1.0);
+    }
+
+    #[test]
+    fn every_style_hands_over_cleanly() {
+        for style in AutoDjTransition::ALL {
+            let (out, _) = transition_levels(style, 0.0);
+            assert_eq!(out, ChannelLevels::FLAT, "{:?} start", style);
+            let (out, inc) = transition_levels(style, 1.0);
+            assert_eq!(inc, ChannelLevels::FLAT, "{:?} end", style);
+            assert_eq!(out.volume, 0.0, "{:?} end", style);
+
+            // Never two bass lines at once
+            for step in 0..=100 {
+                let (out, inc) = transition_levels(style, step as f32 / 100.0);
+                let bass = |l: &ChannelLevels| l.volume > 0.0 && l.eq_lo > 0.0 && !l.stems_muted[BASS];
+                assert!(!(bass(&out) && bass(&inc)) || style == AutoDjTransition::Filter,
+                    "{:?} at {}", style, step);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Engine → MIDI clock bridge
+//!
+//! Implements the `ClockSource` trait from mesh-midi over the master clock the
+//! audio thread publishes every buffer, so MIDI clock output is timed by the
+//! audio callback rather than the iced tick.
+
+use std::sync::Arc;
+
+use mesh_core::engine::MasterClockAtomics;
+use mesh_midi::{ClockSnapshot, ClockSource};
+
+/// Reads the audio engine's master transport for MIDI clock output
+pub struct EngineClockSource {
+    clock: Arc<MasterClockAtomics>,
+}
+
+impl EngineClockSource {
+    pub fn new(clock: Arc<MasterClockAtomics>) -> Self {
+        Self { clock }
+    }
+}
+
+impl ClockSource for EngineClockSource {
+    fn snapshot(&self) -> Option<ClockSnapshot> {
+        let clock = self.clock.read()?;
+        Some(ClockSnapshot {
+            beat: clock.beat,
+            bpm: clock.bpm,
+            at: clock.at,
+        })
+    }
+}
@@ -7,7 +7,11 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 // Re-export shared config utilities from mesh-core
-pub use mesh_core::config::{load_config, save_config, LoudnessConfig};
+pub use mesh_core::config::{
+    load_config, save_config, CrossfaderAssign, CrossfaderConfig, CrossfaderCurve, EqConfig,
+    EqModel, LoudnessConfig, RecordingConfig, RecordingFormat, RecordingSplit, RecordingStems,
+};
+pub use mesh_core::dvs::TimecodeFormat;
 pub use mesh_widgets::{AppFont, FontSize};
 
 // Re-export suggestion config enums from mesh-core (shared with mesh-cue)
@@ -100,6 +104,105 @@ impl WaveformAbstraction {
     }
 }
 
+/// Where Auto DJ takes its next track from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoDjSource {
+    /// Top suggestion for the playing track (energy fader applies)
+    #[default]
+    Suggestions,
+    /// The playlist open in the browser, in order, then suggestions
+    Playlist,
+}
+
+impl AutoDjSource {
+    pub const ALL: [AutoDjSource; 2] = [AutoDjSource::Suggestions, AutoDjSource::Playlist];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AutoDjSource::Suggestions => "Suggestions",
+            AutoDjSource::Playlist => "Playlist",
+        }
+    }
+}
+
+/// How Auto DJ blends the outgoing track into the incoming one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoDjTransition {
+    /// Fade in with the lows killed, swap the low EQs halfway, fade out
+    #[default]
+    EqSwap,
+    /// High-pass the incoming track open while low-passing the outgoing one
+    Filter,
+    /// Like EQ swap, but hands over the bass stem instead of the low EQ
+    BassSwap,
+    /// Bring the incoming stems in one by one while the outgoing ones drop out
+    StemHandover,
+}
+
+impl AutoDjTransition {
+    pub const ALL: [AutoDjTransition; 4] = [
+        AutoDjTransition::EqSwap,
+        AutoDjTransition::Filter,
+        AutoDjTransition::BassSwap,
+        AutoDjTransition::StemHandover,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AutoDjTransition::EqSwap => "EQ Swap",
+            AutoDjTransition::Filter => "Filter",
+            AutoDjTransition::BassSwap => "Bass Swap",
+            AutoDjTransition::StemHandover => "Stems",
+        }
+    }
+}
+
+/// Transition lengths offered in settings (bars)
+pub const AUTO_DJ_TRANSITION_BARS: [u32; 4] = [4, 8, 16, 32];
+
+/// Auto DJ configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoDjConfig {
+    /// Where the next track comes from
+    pub source: AutoDjSource,
+    /// Transition style
+    pub transition: AutoDjTransition,
+    /// Transition length in bars
+    pub transition_bars: u32,
+    /// Start Auto DJ shortly after launch (unattended venue playback)
+    pub start_on_launch: bool,
+}
+
+impl Default for AutoDjConfig {
+    fn default() -> Self {
+        Self {
+            source: AutoDjSource::Suggestions,
+            transition: AutoDjTransition::EqSwap,
+            transition_bars: 16,
+            start_on_launch: false,
+        }
+    }
+}
+
+impl AutoDjConfig {
+    /// Transition length clamped to the offered options' range
+    pub fn validated_transition_bars(&self) -> u32 {
+        self.transition_bars.clamp(AUTO_DJ_TRANSITION_BARS[0], AUTO_DJ_TRANSITION_BARS[3])
+    }
+}
+
+/// Sampler configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplerConfig {
+    /// Sample pack loaded into the sampler at startup
+    /// (folder name under `<collection>/samples/`)
+    pub pack: Option<String>,
+}
+
 /// Root configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -112,6 +215,12 @@ pub struct PlayerConfig {
     pub slicer: SlicerConfig,
     /// OTA update settings
     pub updates: UpdateConfig,
+    /// Set recording settings (file format, splitting)
+    pub recording: RecordingConfig,
+    /// Auto DJ settings (track source, transition style and length)
+    pub auto_dj: AutoDjConfig,
+    /// Sampler settings (active sample pack)
+    pub sampler: SamplerConfig,
     /// Path to the mesh collection folder (shared with mesh-cue)
     /// Default: ~/Music/mesh-collection
     pub collection_path: PathBuf,
@@ -130,6 +239,9 @@ impl Default for PlayerConfig {
             display: DisplayConfig::default(),
             slicer: SlicerConfig::default(),
             updates: UpdateConfig::default(),
+            recording: RecordingConfig::default(),
+            auto_dj: AutoDjConfig::default(),
+            sampler: SamplerConfig::default(),
             collection_path,
         }
     }
@@ -167,8 +279,18 @@ pub struct AudioConfig {
     pub auto_cue: bool,
     /// Loudness normalization settings
     pub loudness: LoudnessConfig,
+    /// Crossfader curve, hamster mode and per-deck A/B/thru assignment
+    pub crossfader: CrossfaderConfig,
+    /// Channel EQ model (classic, isolator, 4-band) and isolator crossovers
+    pub eq: EqConfig,
     /// Audio output device configuration
     pub outputs: AudioOutputConfig,
+    /// Timecode vinyl control
+    pub dvs: DvsConfig,
+    /// Mic and aux line inputs on the mixer
+    pub line_inputs: LineInputConfig,
+    /// Effects loaded onto the mixer send/return buses and master insert
+    pub fx_buses: FxBusesConfig,
 }
 
 impl Default for AudioConfig {
@@ -178,7 +300,12 @@ impl Default for AudioConfig {
             phase_sync: true,  // Automatic beat sync enabled by default
             auto_cue: true,    // Auto-cue enabled by default
             loudness: LoudnessConfig::default(),
+            crossfader: CrossfaderConfig::default(), // All decks on THRU
+            eq: EqConfig::default(),                 // Classic EQ on every deck
             outputs: AudioOutputConfig::default(),
+            dvs: DvsConfig::default(),
+            line_inputs: LineInputConfig::default(),
+            fx_buses: FxBusesConfig::default(),
         }
     }
 }
@@ -202,6 +329,124 @@ pub struct AudioOutputConfig {
     pub buffer_size: Option<u32>,
 }
 
+/// Timecode vinyl (DVS) configuration
+///
+/// When enabled, one stereo input per deck is opened at startup and each
+/// deck's DVS button cycles INT → REL → ABS.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DvsConfig {
+    /// Open the timecode inputs (takes effect on restart)
+    pub enabled: bool,
+    /// Timecode record or CD on the turntables
+    pub format: TimecodeFormat,
+}
+
+/// Mic and aux line input configuration
+///
+/// Each enabled input opens a stereo input at startup and adds a channel
+/// strip (trim, EQ, cue, talkover) to the mixer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineInputConfig {
+    /// Open the MC mic input (takes effect on restart)
+    pub mic: bool,
+    /// Open the aux line input (takes effect on restart)
+    pub aux: bool,
+    /// How far talkover ducks the decks in dB (-40 to 0)
+    pub talkover_depth_db: f32,
+}
+
+impl Default for LineInputConfig {
+    fn default() -> Self {
+        Self {
+            mic: false,
+            aux: false,
+            talkover_depth_db: mesh_core::engine::DEFAULT_TALKOVER_DEPTH_DB,
+        }
+    }
+}
+
+impl LineInputConfig {
+    /// Whether a line input is enabled
+    pub fn enabled(&self, input: mesh_core::engine::LineInput) -> bool {
+        match input {
+            mesh_core::engine::LineInput::Mic => self.mic,
+            mesh_core::engine::LineInput::Aux => self.aux,
+        }
+    }
+}
+
+/// Mixer FX bus configuration
+///
+/// Effects listed here are loaded onto the buses at startup. Send levels
+/// are not stored; every deck starts with its sends closed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FxBusesConfig {
+    /// Send/return bus FX 1
+    pub send1: FxBusConfig,
+    /// Send/return bus FX 2
+    pub send2: FxBusConfig,
+    /// Insert chain on the master bus
+    pub master: FxBusConfig,
+}
+
+impl FxBusesConfig {
+    /// Configuration of one bus
+    pub fn bus(&self, bus: mesh_core::engine::FxBus) -> &FxBusConfig {
+        match bus {
+            mesh_core::engine::FxBus::Send1 => &self.send1,
+            mesh_core::engine::FxBus::Send2 => &self.send2,
+            mesh_core::engine::FxBus::Master => &self.master,
+        }
+    }
+}
+
+/// Effects and return level of one mixer FX bus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FxBusConfig {
+    /// Effects in processing order
+    pub effects: Vec<BusEffectConfig>,
+    /// Return level into master (0.0 - 1.0, ignored for the master insert)
+    pub return_level: f32,
+}
+
+impl Default for FxBusConfig {
+    fn default() -> Self {
+        Self {
+            effects: Vec::new(),
+            return_level: 1.0,
+        }
+    }
+}
+
+/// Where a bus effect comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BusEffectSource {
+    /// Built-in effect (see `mesh_core::effect::native::NATIVE_EFFECT_IDS`)
+    #[default]
+    Native,
+    /// Pure Data patch from the effects folder
+    Pd,
+    /// CLAP plugin
+    Clap,
+}
+
+/// One effect on a mixer FX bus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct BusEffectConfig {
+    /// Effect type
+    pub source: BusEffectSource,
+    /// Native effect id, PD effect folder name or CLAP plugin id
+    pub id: String,
+    /// Initial parameter values (normalized 0.0 - 1.0, in parameter order)
+    pub params: Vec<f32>,
+}
+
 // LoudnessConfig is re-exported from mesh_core::config
 
 /// Display configuration section
@@ -358,6 +603,7 @@ mod tests {
         assert_eq!(config.audio.global_bpm, 128.0);
         assert!(config.audio.phase_sync);
         assert_eq!(config.display.default_loop_length_index, 7);
+        assert_eq!(config.audio.crossfader.assignments, [CrossfaderAssign::Thru; 4]);
     }
 
     #[test]
@@ -372,6 +618,21 @@ mod tests {
             audio: AudioConfig {
                 global_bpm: 140.0,
                 phase_sync: false,
+                crossfader: CrossfaderConfig {
+                    curve: CrossfaderCurve::Scratch,
+                    reverse: true,
+                    assignments: [
+                        CrossfaderAssign::A,
+                        CrossfaderAssign::B,
+                        CrossfaderAssign::A,
+                        CrossfaderAssign::B,
+                    ],
+                },
+                eq: EqConfig {
+                    model: EqModel::Isolator,
+                    decks: [None, Some(EqModel::FourBand), None, None],
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             display: DisplayConfig {
@@ -385,6 +646,15 @@ mod tests {
                 ..Default::default()
             },
             updates: UpdateConfig::default(),
+            recording: RecordingConfig::default(),
+            auto_dj: AutoDjConfig {
+                transition: AutoDjTransition::BassSwap,
+                transition_bars: 32,
+                ..Default::default()
+            },
+            sampler: SamplerConfig {
+                pack: Some("Warehouse Tools".to_string()),
+            },
             collection_path: PathBuf::from("/tmp/test-collection"),
         };
 
@@ -393,8 +663,12 @@ mod tests {
 
         assert_eq!(parsed.audio.global_bpm, 140.0);
         assert!(!parsed.audio.phase_sync);
+        assert_eq!(parsed.audio.crossfader, config.audio.crossfader);
+        assert_eq!(parsed.audio.eq, config.audio.eq);
         assert_eq!(parsed.display.default_loop_length_index, 5);
         assert_eq!(parsed.display.default_zoom_bars, 4);
+        assert_eq!(parsed.auto_dj, config.auto_dj);
+        assert_eq!(parsed.sampler, config.sampler);
     }
 
     #[test]
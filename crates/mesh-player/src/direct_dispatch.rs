@@ -3,12 +3,21 @@
 //! Implements the `DirectDispatch` trait from mesh-midi, pushing timing-critical
 //! EngineCommands directly into the audio thread's ringbuffer from the MIDI
 //! callback thread. This bypasses the ~16ms iced tick loop for actions where
-//! latency matters (play, cue, hot cue, beat jump).
+//! latency matters (play, cue, hot cue, beat jump, jog wheel).
 
 use std::sync::Mutex;
 
-use mesh_core::engine::EngineCommand;
-use mesh_midi::{DeckAction, DirectDispatch};
+use mesh_core::engine::{self, EngineCommand, MAX_PITCH_BEND};
+use mesh_midi::{DeckAction, DirectDispatch, JogRelease};
+
+/// Convert the mapping-level jog release to the engine's
+pub fn jog_release(release: JogRelease) -> engine::JogRelease {
+    match release {
+        JogRelease::Resume => engine::JogRelease::Resume,
+        JogRelease::SpinBack => engine::JogRelease::SpinBack,
+        JogRelease::Brake => engine::JogRelease::Brake,
+    }
+}
 
 /// Routes timing-critical MIDI deck actions directly to the audio engine
 /// via a lock-free SPSC ringbuffer, bypassing the iced tick loop.
@@ -39,6 +48,15 @@ impl DirectDispatch for EngineDirectDispatch {
             DeckAction::HotCueRelease { .. } => Some(EngineCommand::HotCueRelease { deck }),
             DeckAction::BeatJumpForward => Some(EngineCommand::BeatJumpForward { deck }),
             DeckAction::BeatJumpBackward => Some(EngineCommand::BeatJumpBackward { deck }),
+            DeckAction::PhraseJumpForward => Some(EngineCommand::PhraseJumpForward { deck }),
+            DeckAction::PhraseJumpBackward => Some(EngineCommand::PhraseJumpBackward { deck }),
+            DeckAction::JogTurn { revolutions } => Some(EngineCommand::JogTurn { deck, revolutions: *revolutions }),
+            DeckAction::JogTouch { touched, release } => {
+                Some(EngineCommand::JogTouch { deck, touched: *touched, release: jog_release(*release) })
+            }
+            DeckAction::Nudge { amount } => {
+                Some(EngineCommand::Nudge { deck, amount: *amount * MAX_PITCH_BEND as f32 })
+            }
             _ => None,
         };
 
@@ -29,15 +29,17 @@ use std::sync::mpsc::Receiver;
 
 use basedrop::Shared;
 use mesh_core::audio_file::{LoadedTrack, StemBuffers, TrackMetadata};
-use mesh_core::config::LoudnessConfig;
+use mesh_core::config::{CrossfaderAssign, CrossfaderConfig, EqConfig, LoudnessConfig};
 use mesh_core::db::DatabaseService;
+use mesh_core::dvs::{DvsMode, TimecodeFormat};
 use mesh_core::effect::{Effect, EffectInfo};
-use mesh_core::engine::{EngineCommand, LinkedStemData, PreparedTrack, SlicerPreset};
+use mesh_core::engine::{EngineCommand, FxBus, JogRelease, LineInput, LinkedStemData, PitchRange, PreparedTrack, SlicerPreset, MAX_BUS_EFFECTS, NUM_SAMPLER_SLOTS};
 use mesh_core::loader::LinkedStemResultReceiver;
 use mesh_core::clap::{ClapManager, ClapPluginCategory, DiscoveredClapPlugin, ClapGuiHandle};
 use std::collections::HashMap;
 use mesh_core::pd::{DiscoveredEffect, PdManager};
 use mesh_core::preset_loader::{PresetLoader, PresetLoadResultReceiver, MultibandBuildSpec};
+use mesh_core::sampler::{ClipMode, SampleClip};
 use mesh_core::types::{Stem, StereoBuffer};
 use mesh_core::usb::{get_or_open_usb_database, UsbCommand, UsbManager, UsbMessage};
 use mesh_widgets::{CueMarker, OverviewState, ZoomedState, CUE_COLORS};
@@ -141,6 +143,13 @@ pub struct MeshDomain {
     /// These are stored when effects are created via `add_clap_effect_*` methods.
     clap_gui_handles: HashMap<String, ClapGuiHandle>,
 
+    /// Effects on each mixer bus in chain order, mirroring the engine's chains.
+    /// Holds the CLAP GUI handle ID of each effect (`None` if it has no window).
+    bus_effects: [Vec<Option<String>>; 3],
+
+    /// Counter that keeps CLAP bus effect instance IDs unique
+    next_bus_effect_id: u64,
+
     // =========================================================================
     // Domain State (caches and computed values)
     // =========================================================================
@@ -158,6 +167,9 @@ pub struct MeshDomain {
 
     /// Global BPM (cached for UI display; authoritative value is in audio engine)
     global_bpm: f64,
+
+    /// Clips loaded into the sampler slots (kept for pack saving and display)
+    sampler_clips: [Option<Shared<SampleClip>>; NUM_SAMPLER_SLOTS],
 }
 
 impl MeshDomain {
@@ -235,11 +247,14 @@ impl MeshDomain {
             clap_manager,
             preset_loader,
             clap_gui_handles: HashMap::new(),
+            bus_effects: std::array::from_fn(|_| Vec::new()),
+            next_bus_effect_id: 0,
             // Domain state
             deck_stems: [None, None, None, None],
             deck_linked_stems: std::array::from_fn(|_| [None, None, None, None]),
             track_lufs_per_deck: [None, None, None, None],
             global_bpm: initial_global_bpm,
+            sampler_clips: std::array::from_fn(|_| None),
         }
     }
 
@@ -739,6 +754,16 @@ impl MeshDomain {
         self.send_command(EngineCommand::BeatJumpBackward { deck });
     }
 
+    /// Jump to the start of the next phrase
+    pub fn phrase_jump_forward(&mut self, deck: usize) {
+        self.send_command(EngineCommand::PhraseJumpForward { deck });
+    }
+
+    /// Jump to the start of the current (or previous) phrase
+    pub fn phrase_jump_backward(&mut self, deck: usize) {
+        self.send_command(EngineCommand::PhraseJumpBackward { deck });
+    }
+
     // =========================================================================
     // Deck Control - Stem Control
     // =========================================================================
@@ -762,6 +787,64 @@ impl MeshDomain {
         self.send_command(EngineCommand::SetKeyMatchEnabled { deck, enabled });
     }
 
+    // =========================================================================
+    // Deck Control - Pitch / Keylock
+    // =========================================================================
+
+    /// Set the pitch fader position (-1.0 to 1.0)
+    pub fn set_pitch_fader(&mut self, deck: usize, value: f32) {
+        self.send_command(EngineCommand::SetPitchFader { deck, value });
+    }
+
+    /// Set the pitch fader range
+    pub fn set_pitch_range(&mut self, deck: usize, range: PitchRange) {
+        self.send_command(EngineCommand::SetPitchRange { deck, range });
+    }
+
+    /// Enable/disable keylock (master tempo)
+    pub fn set_keylock(&mut self, deck: usize, enabled: bool) {
+        self.send_command(EngineCommand::SetKeylock { deck, enabled });
+    }
+
+    /// Set the manual key shift in semitones and cents
+    pub fn set_key_shift(&mut self, deck: usize, semitones: i8, cents: i8) {
+        self.send_command(EngineCommand::SetKeyShift { deck, semitones, cents });
+    }
+
+    /// Jog wheel touch sensor pressed or released
+    pub fn jog_touch(&mut self, deck: usize, touched: bool, release: JogRelease) {
+        self.send_command(EngineCommand::JogTouch { deck, touched, release });
+    }
+
+    /// Jog wheel turned by a number of platter revolutions
+    pub fn jog_turn(&mut self, deck: usize, revolutions: f32) {
+        self.send_command(EngineCommand::JogTurn { deck, revolutions });
+    }
+
+    /// Temporary pitch bend (fraction of playback speed)
+    pub fn nudge(&mut self, deck: usize, amount: f32) {
+        self.send_command(EngineCommand::Nudge { deck, amount });
+    }
+
+    /// Enable/disable vinyl mode (jog touch scratches)
+    pub fn set_vinyl_mode(&mut self, deck: usize, enabled: bool) {
+        self.send_command(EngineCommand::SetVinylMode { deck, enabled });
+    }
+
+    /// Switch a deck between internal playback and timecode control
+    pub fn set_dvs_mode(&mut self, deck: usize, mode: DvsMode) {
+        self.send_command(EngineCommand::SetDvsMode { deck, mode });
+    }
+
+    /// Select the timecode format for all decks
+    ///
+    /// The format's position table is built on a background thread: decks
+    /// follow speed right away and needle drops once it is ready.
+    pub fn set_timecode_format(&mut self, format: TimecodeFormat) {
+        std::thread::spawn(move || mesh_core::dvs::prepare_format(format));
+        self.send_command(EngineCommand::SetTimecodeFormat { format });
+    }
+
     // =========================================================================
     // Deck Control - Slicer
     // =========================================================================
@@ -824,6 +907,21 @@ impl MeshDomain {
         self.send_command(EngineCommand::SetEqLo { deck, value });
     }
 
+    /// Set high-mid EQ, used by the 4-band model (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    pub fn set_eq_hi_mid(&mut self, deck: usize, value: f32) {
+        self.send_command(EngineCommand::SetEqHiMid { deck, value });
+    }
+
+    /// Send the EQ configuration (per-deck model and isolator crossovers)
+    ///
+    /// Model changes crossfade in the engine, so this is safe mid-set.
+    pub fn set_eq_config(&mut self, config: &EqConfig) {
+        self.send_command(EngineCommand::SetEqCrossovers { crossovers: config.crossovers });
+        for deck in 0..4 {
+            self.send_command(EngineCommand::SetEqModel { deck, model: config.model_for(deck) });
+        }
+    }
+
     /// Set filter position (-1.0 to 1.0, 0.0 = bypass)
     pub fn set_filter(&mut self, deck: usize, value: f32) {
         self.send_command(EngineCommand::SetFilter { deck, value });
@@ -849,6 +947,163 @@ impl MeshDomain {
         self.send_command(EngineCommand::SetAutoCue { enabled });
     }
 
+    /// Set crossfader position (-1.0 = A, 0.0 = center, 1.0 = B)
+    pub fn set_crossfader(&mut self, position: f32) {
+        self.send_command(EngineCommand::SetCrossfader { position });
+    }
+
+    /// Assign a deck to crossfader side A, B, or thru
+    pub fn set_crossfader_assign(&mut self, deck: usize, assign: CrossfaderAssign) {
+        self.send_command(EngineCommand::SetCrossfaderAssign { deck, assign });
+    }
+
+    // =========================================================================
+    // Line Inputs (mic / aux)
+    // =========================================================================
+
+    /// Set a line input's channel volume (0.0 - 1.0)
+    pub fn set_line_volume(&mut self, input: LineInput, volume: f32) {
+        self.send_command(EngineCommand::SetLineVolume { input, volume });
+    }
+
+    /// Set a line input's trim in dB (-24 to +12)
+    pub fn set_line_trim(&mut self, input: LineInput, db: f32) {
+        self.send_command(EngineCommand::SetLineTrim { input, db });
+    }
+
+    /// Set a line input's EQ high (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    pub fn set_line_eq_hi(&mut self, input: LineInput, value: f32) {
+        self.send_command(EngineCommand::SetLineEqHi { input, value });
+    }
+
+    /// Set a line input's EQ mid (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    pub fn set_line_eq_mid(&mut self, input: LineInput, value: f32) {
+        self.send_command(EngineCommand::SetLineEqMid { input, value });
+    }
+
+    /// Set a line input's EQ low (0.0 = kill, 0.5 = flat, 1.0 = boost)
+    pub fn set_line_eq_lo(&mut self, input: LineInput, value: f32) {
+        self.send_command(EngineCommand::SetLineEqLo { input, value });
+    }
+
+    /// Route a line input to the cue bus
+    pub fn set_line_cue(&mut self, input: LineInput, enabled: bool) {
+        self.send_command(EngineCommand::SetLineCue { input, enabled });
+    }
+
+    /// Duck the decks while there is signal on a line input
+    pub fn set_talkover(&mut self, input: LineInput, enabled: bool) {
+        self.send_command(EngineCommand::SetTalkover { input, enabled });
+    }
+
+    /// Set how far talkover ducks the decks in dB (-40 to 0)
+    pub fn set_talkover_depth(&mut self, db: f32) {
+        self.send_command(EngineCommand::SetTalkoverDepth { db });
+    }
+
+    /// Send the full crossfader configuration (curve, reverse, assignments)
+    pub fn set_crossfader_config(&mut self, config: &CrossfaderConfig) {
+        self.send_command(EngineCommand::SetCrossfaderCurve { curve: config.curve });
+        self.send_command(EngineCommand::SetCrossfaderReverse { enabled: config.reverse });
+        for (deck, assign) in config.assignments.iter().enumerate() {
+            self.set_crossfader_assign(deck, *assign);
+        }
+    }
+
+    // =========================================================================
+    // Mixer FX Buses
+    // =========================================================================
+
+    /// Add a built-in effect (echo, reverb, phaser, ...) to a mixer bus
+    pub fn add_native_bus_effect(&mut self, bus: FxBus, effect_id: &str) -> Result<EffectInfo, String> {
+        let effect = mesh_core::effect::native::create_native_effect(effect_id)
+            .ok_or_else(|| format!("Unknown native effect '{}'", effect_id))?;
+        self.push_bus_effect(bus, effect_id, None, effect)
+    }
+
+    /// Add a PD effect to a mixer bus
+    pub fn add_pd_bus_effect(&mut self, bus: FxBus, effect_id: &str) -> Result<EffectInfo, String> {
+        let effect = self
+            .pd_manager
+            .create_effect(effect_id)
+            .map_err(|e| format!("Failed to create PD effect '{}': {}", effect_id, e))?;
+        self.push_bus_effect(bus, effect_id, None, effect)
+    }
+
+    /// Add a CLAP effect to a mixer bus
+    ///
+    /// The GUI handle is stored as `{plugin_id}_bus{index}_{n}`, unique per
+    /// instance, so the plugin window can be opened like any other CLAP effect.
+    pub fn add_clap_bus_effect(&mut self, bus: FxBus, plugin_id: &str) -> Result<EffectInfo, String> {
+        let (effect, gui_handle) = self
+            .clap_manager
+            .create_effect_with_gui_handle(plugin_id)
+            .map_err(|e| format!("Failed to create CLAP effect '{}': {}", plugin_id, e))?;
+        self.next_bus_effect_id += 1;
+        let effect_instance_id = format!("{}_bus{}_{}", plugin_id, bus.index(), self.next_bus_effect_id);
+        let effect_info = self.push_bus_effect(bus, plugin_id, Some(effect_instance_id.clone()), effect)?;
+        self.clap_gui_handles.insert(effect_instance_id, gui_handle);
+        Ok(effect_info)
+    }
+
+    /// Send a created effect to the engine's bus chain
+    ///
+    /// Fails without sending when the chain already holds `MAX_BUS_EFFECTS`
+    /// (the engine would drop the effect while callers count it in the chain).
+    fn push_bus_effect(
+        &mut self,
+        bus: FxBus,
+        effect_id: &str,
+        gui_handle_id: Option<String>,
+        effect: Box<dyn Effect>,
+    ) -> Result<EffectInfo, String> {
+        if self.bus_effects[bus.index()].len() >= MAX_BUS_EFFECTS {
+            return Err(format!("Chain is full ({} effects), skipping '{}'", MAX_BUS_EFFECTS, effect_id));
+        }
+        let effect_info = effect.info().clone();
+        if let Some(ref mut sender) = self.command_sender {
+            Self::send_with_retry(sender, EngineCommand::AddBusEffect { bus, effect });
+            self.bus_effects[bus.index()].push(gui_handle_id);
+            log::info!("Added effect '{}' to {} ({} params)",
+                effect_id, bus.label(), effect_info.params.len());
+            Ok(effect_info)
+        } else {
+            Err("Audio engine not connected".to_string())
+        }
+    }
+
+    /// Remove an effect from a mixer bus (and its CLAP GUI handle, if any)
+    pub fn remove_bus_effect(&mut self, bus: FxBus, effect_index: usize) {
+        let chain = &mut self.bus_effects[bus.index()];
+        if effect_index >= chain.len() {
+            return;
+        }
+        if let Some(id) = chain.remove(effect_index) {
+            self.clap_gui_handles.remove(&id);
+        }
+        self.send_command(EngineCommand::RemoveBusEffect { bus, effect_index });
+    }
+
+    /// Set a bus effect parameter (normalized 0.0 - 1.0)
+    pub fn set_bus_effect_param(&mut self, bus: FxBus, effect_index: usize, param_index: usize, value: f32) {
+        self.send_command(EngineCommand::SetBusEffectParam { bus, effect_index, param_index, value });
+    }
+
+    /// Turn a mixer bus on or off
+    pub fn set_bus_enabled(&mut self, bus: FxBus, enabled: bool) {
+        self.send_command(EngineCommand::SetBusEnabled { bus, enabled });
+    }
+
+    /// Set a send bus return level into master (0.0 - 1.0)
+    pub fn set_bus_return(&mut self, bus: FxBus, level: f32) {
+        self.send_command(EngineCommand::SetBusReturn { bus, level });
+    }
+
+    /// Set how much of a deck goes to a send bus (0.0 - 1.0)
+    pub fn set_fx_send(&mut self, deck: usize, send: usize, level: f32) {
+        self.send_command(EngineCommand::SetFxSend { deck, send, level });
+    }
+
     // =========================================================================
     // Global Controls
     // =========================================================================
@@ -859,6 +1114,51 @@ impl MeshDomain {
         self.send_command(EngineCommand::SetGlobalBpm(bpm));
     }
 
+    // =========================================================================
+    // Sampler
+    // =========================================================================
+
+    /// Put a clip into a sampler slot (None empties it)
+    pub fn load_sampler_slot(&mut self, slot: usize, clip: Option<SampleClip>) {
+        if slot >= NUM_SAMPLER_SLOTS {
+            return;
+        }
+        let clip = clip.map(|c| Shared::new(&mesh_core::engine::gc::gc_handle(), c));
+        self.sampler_clips[slot] = clip.clone();
+        self.send_command(EngineCommand::LoadSamplerSlot { slot, clip });
+    }
+
+    /// Clip loaded in a sampler slot
+    pub fn sampler_clip(&self, slot: usize) -> Option<&SampleClip> {
+        self.sampler_clips.get(slot)?.as_deref()
+    }
+
+    /// First sampler slot without a clip
+    pub fn first_empty_sampler_slot(&self) -> Option<usize> {
+        self.sampler_clips.iter().position(|c| c.is_none())
+    }
+
+    /// Switch a slot's clip between one-shot and loop (reloads the slot)
+    pub fn toggle_sampler_mode(&mut self, slot: usize) {
+        let Some(clip) = self.sampler_clip(slot) else { return };
+        let mode = match clip.mode {
+            ClipMode::OneShot => ClipMode::Loop,
+            ClipMode::Loop => ClipMode::OneShot,
+        };
+        let clip = SampleClip { mode, ..clip.clone() };
+        self.load_sampler_slot(slot, Some(clip));
+    }
+
+    /// Trigger a sampler pad
+    pub fn sampler_trigger(&mut self, slot: usize) {
+        self.send_command(EngineCommand::SamplerTrigger { slot });
+    }
+
+    /// Stop every sampler slot
+    pub fn sampler_stop_all(&mut self) {
+        self.send_command(EngineCommand::SamplerStopAll);
+    }
+
     // =========================================================================
     // Track Loading (to Audio Engine)
     // =========================================================================
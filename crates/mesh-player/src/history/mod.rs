@@ -35,6 +35,8 @@ use mesh_core::db::{DatabaseService, TrackPlayRecord, TrackPlayUpdate};
 pub enum LoadSource {
     Browser,
     Suggestions,
+    /// Picked and loaded by Auto DJ
+    AutoDj,
 }
 
 impl LoadSource {
@@ -42,6 +44,7 @@ impl LoadSource {
         match self {
             Self::Browser => "browser",
             Self::Suggestions => "suggestions",
+            Self::AutoDj => "auto_dj",
         }
     }
 }
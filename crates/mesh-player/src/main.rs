@@ -18,6 +18,8 @@
 //! - `--midi-learn`: Start in MIDI learn mode for creating controller profiles
 
 mod audio;
+mod auto_dj;
+mod clock_source;
 mod config;
 mod direct_dispatch;
 mod domain;
@@ -31,7 +33,7 @@ use iced::{Size, Task};
 
 use audio::{start_audio_system, start_audio_system_with_devices};
 use mesh_core::db::DatabaseService;
-use ui::{MeshApp, app::Message, midi_learn::MidiLearnMessage};
+use ui::{MeshApp, app::{AutoDjMessage, Message, SamplerMessage}, midi_learn::MidiLearnMessage};
 
 const CLIENT_NAME: &str = "mesh-player";
 
@@ -261,16 +263,20 @@ fn main() -> iced::Result {
     // Try to start audio system
     // Returns AudioHandle, CommandSender (lock-free queue), DeckAtomics, SlicerAtomics,
     // LinkedStemAtomics, LinkedStemResultReceiver, and sample rate
+    // DVS: one stereo timecode input per deck
+    let input_count = if config.audio.dvs.enabled { mesh_core::types::NUM_DECKS } else { 0 };
+    // Mixer line inputs: MC mic and aux
+    let line_inputs = config.audio.line_inputs;
     let audio_start_result = if master_device_id.is_some() || cue_device_id.is_some() {
-        start_audio_system_with_devices(db_service.clone(), master_device_id, cue_device_id)
+        start_audio_system_with_devices(db_service.clone(), master_device_id, cue_device_id, input_count, line_inputs.mic, line_inputs.aux)
     } else {
-        start_audio_system(CLIENT_NAME, db_service.clone())
+        start_audio_system(CLIENT_NAME, db_service.clone(), input_count, line_inputs.mic, line_inputs.aux)
     };
-    let (audio_handle, command_sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, audio_sample_rate, audio_client_name, output_latency_samples, internal_latency_samples, direct_command_producer) =
+    let (audio_handle, command_sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, sampler_atomics, audio_sample_rate, audio_client_name, output_latency_samples, internal_latency_samples, master_clock, direct_command_producer) =
         match audio_start_result {
-            Ok((handle, sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, sample_rate, client_name, output_lat, internal_lat, direct_producer)) => {
+            Ok((handle, sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, sampler_atomics, sample_rate, client_name, output_lat, internal_lat, master_clock, direct_producer)) => {
                 println!("Audio system started successfully ({} Hz, client: {})", sample_rate, client_name);
-                (Some(handle), Some(sender), Some(deck_atomics), Some(slicer_atomics), Some(linked_stem_atomics), Some(linked_stem_receiver), Some(clip_indicator), Some(level_atomics), sample_rate, client_name, Some(output_lat), Some(internal_lat), Some(direct_producer))
+                (Some(handle), Some(sender), Some(deck_atomics), Some(slicer_atomics), Some(linked_stem_atomics), Some(linked_stem_receiver), Some(clip_indicator), Some(level_atomics), Some(sampler_atomics), sample_rate, client_name, Some(output_lat), Some(internal_lat), Some(master_clock), Some(direct_producer))
             }
             Err(e) => {
                 eprintln!("Warning: Could not start audio system: {}", e);
@@ -278,7 +284,7 @@ fn main() -> iced::Result {
                 eprintln!();
                 eprintln!("Check that audio devices are available and not in use by other applications.");
                 // Default to 44100 Hz when audio is not available
-                (None, None, None, None, None, None, None, None, 44100, "mesh-player".to_string(), None, None, None)
+                (None, None, None, None, None, None, None, None, None, 44100, "mesh-player".to_string(), None, None, None, None)
             }
         };
 
@@ -295,8 +301,10 @@ fn main() -> iced::Result {
     let linked_stem_receiver_cell = std::cell::RefCell::new(linked_stem_receiver);
     let clip_indicator_cell = std::cell::RefCell::new(clip_indicator);
     let level_atomics_cell = std::cell::RefCell::new(level_atomics);
+    let sampler_atomics_cell = std::cell::RefCell::new(sampler_atomics);
     let output_latency_cell = std::cell::RefCell::new(output_latency_samples);
     let internal_latency_cell = std::cell::RefCell::new(internal_latency_samples);
+    let master_clock_cell = std::cell::RefCell::new(master_clock);
     let direct_producer_cell = std::cell::RefCell::new(direct_command_producer);
 
     // Pre-allocate StemBuffer pool for zero-allocation track loading.
@@ -326,8 +334,10 @@ fn main() -> iced::Result {
             let linked_stem_receiver = linked_stem_receiver_cell.borrow_mut().take();
             let clip_indicator = clip_indicator_cell.borrow_mut().take();
             let level_atomics = level_atomics_cell.borrow_mut().take();
+            let sampler_atomics = sampler_atomics_cell.borrow_mut().take();
             let output_latency = output_latency_cell.borrow_mut().take();
             let internal_latency = internal_latency_cell.borrow_mut().take();
+            let master_clock = master_clock_cell.borrow_mut().take();
             let direct_producer = direct_producer_cell.borrow_mut().take();
 
             // Create direct dispatch for timing-critical MIDI→engine path
@@ -336,6 +346,12 @@ fn main() -> iced::Result {
                     as std::sync::Arc<dyn mesh_midi::DirectDispatch>
             });
 
+            // MIDI clock output follows the master deck as published by the audio thread
+            let clock_source = master_clock.map(|clock| {
+                std::sync::Arc::new(clock_source::EngineClockSource::new(clock))
+                    as std::sync::Arc<dyn mesh_midi::ClockSource>
+            });
+
             // Auto-start MIDI learn if no midi.yaml exists
             let auto_learn = !mesh_midi::default_midi_config_path().exists();
             if auto_learn {
@@ -346,7 +362,7 @@ fn main() -> iced::Result {
             let show_mapping_ui = start_midi_learn;
             let start_learn = start_midi_learn || auto_learn;
 
-            let mut app = MeshApp::new(db_service, sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, audio_sample_rate, audio_client_name.clone(), show_mapping_ui, start_learn, output_latency, internal_latency, buffer_pool.clone());
+            let mut app = MeshApp::new(db_service, sender, deck_atomics, slicer_atomics, linked_stem_atomics, linked_stem_receiver, clip_indicator, level_atomics, sampler_atomics, audio_sample_rate, audio_client_name.clone(), show_mapping_ui, start_learn, output_latency, internal_latency, buffer_pool.clone());
 
             // Wire direct dispatch to controller for bypassing iced tick on timing-critical commands.
             // Retained on the app so a later controller rebuild (MIDI learn save) can re-apply it.
@@ -357,6 +373,12 @@ fn main() -> iced::Result {
                 controller.set_direct_dispatch(dispatch);
                 log::info!("Direct MIDI→engine dispatch enabled");
             }
+            app.clock_source = clock_source;
+            if let (Some(ref mut controller), Some(source)) =
+                (&mut app.controller, app.clock_source.clone())
+            {
+                controller.set_clock_source(source);
+            }
 
             // Query monitor size for auto-resolution
             // Use oldest() to get the main window Id, then chain monitor_size query
@@ -374,15 +396,33 @@ fn main() -> iced::Result {
             app.collection_browser.graph_building = true;
             let graph_task = ui::app::build_graph_task(graph_sources);
 
+            // Unattended playback: start Auto DJ once USB collections had time to mount
+            let auto_dj_task = if app.config.auto_dj.start_on_launch {
+                Task::perform(
+                    async { tokio::time::sleep(std::time::Duration::from_secs(10)).await },
+                    |_| Message::AutoDj(AutoDjMessage::Start),
+                )
+            } else {
+                Task::none()
+            };
+
+            // Restore the sample pack from the last session
+            let sampler_task = match app.config.sampler.pack.clone() {
+                Some(pack) => Task::done(Message::Sampler(SamplerMessage::LoadPack(Some(pack)))),
+                None => Task::none(),
+            };
+
             // If --midi-learn flag was passed or no midi.yaml exists, start MIDI learn mode
             let startup_task = if start_learn {
                 Task::batch([
                     monitor_task,
                     graph_task,
+                    auto_dj_task,
+                    sampler_task,
                     Task::done(Message::MidiLearn(MidiLearnMessage::Start)),
                 ])
             } else {
-                Task::batch([monitor_task, graph_task])
+                Task::batch([monitor_task, graph_task, auto_dj_task, sampler_task])
             };
 
             (app, startup_task)
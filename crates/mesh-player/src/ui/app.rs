@@ -15,7 +15,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 
-use mesh_core::db::DatabaseService;
+use mesh_core::db::{DatabaseService, TrackRating};
 use iced::widget::{button, center, column, container, mouse_area, opaque, row, slider, stack, text, Space};
 use iced::{Center as CenterAlign, Color, Element, Fill, Length, Subscription, Task, Theme};
 use iced::{event, mouse, Event};
@@ -27,7 +27,7 @@ use crate::domain::MeshDomain;
 use crate::plugin_gui::PluginGuiManager;
 
 use mesh_midi::{ControllerManager, MidiMessage as MidiMsg, MidiEvent, MidiInputEvent, DeckAction as MidiDeckAction, MixerAction as MidiMixerAction, BrowserAction as MidiBrowserAction};
-use mesh_core::engine::{DeckAtomics, LevelAtomics, LinkedStemAtomics, SlicerAtomics};
+use mesh_core::engine::{DeckAtomics, FxBus, LevelAtomics, LineInput, LinkedStemAtomics, SamplerAtomics, SlicerAtomics, NUM_SAMPLER_SLOTS};
 use mesh_core::types::NUM_DECKS;
 use mesh_widgets::{mpsc_subscription, multiband_editor, MultibandEditorState, SliceEditorState, sz};
 use mesh_widgets::keyboard::{KeyboardState, KeyboardEvent, keyboard_view, keyboard_handle};
@@ -40,7 +40,7 @@ use super::player_canvas::{view_player_canvas, PlayerCanvasState};
 use super::settings::SettingsState;
 
 // Re-export extracted modules for use by other UI modules
-pub use super::message::{Message, RecordingMessage, SettingsMessage};
+pub use super::message::{AutoDjMessage, Message, RecordingMessage, SamplerMessage, SettingsMessage};
 pub use super::state::{AppMode, LinkedStemLoadedMsg, PresetLoadedMsg, StemLinkState, TrackLoadedMsg};
 
 /// UI-side state for an active set recording
@@ -100,6 +100,8 @@ pub struct MeshApp {
     pub(crate) clip_hold_frames: u8,
     /// Lock-free peak level atomics from the audio engine (None in UI-only mode)
     pub(crate) level_atomics: Option<Arc<LevelAtomics>>,
+    /// Lock-free sampler slot state (loaded/playing) from the audio engine
+    pub(crate) sampler_atomics: Option<Arc<SamplerAtomics>>,
     /// Per-deck PPM meter ballistics state (UI-thread owned)
     pub(crate) deck_meter: [DeckMeterState; NUM_DECKS],
     /// Master output PPM meter ballistics state
@@ -130,6 +132,9 @@ pub struct MeshApp {
     /// controller is rebuilt (e.g. after saving a MIDI learn config). Without this
     /// the timing-critical path silently degrades to the ~16ms tick until restart.
     pub(crate) direct_dispatch: Option<std::sync::Arc<dyn mesh_midi::DirectDispatch>>,
+    /// Master transport for MIDI clock output, retained for controller rebuilds
+    /// like `direct_dispatch`
+    pub(crate) clock_source: Option<std::sync::Arc<dyn mesh_midi::ClockSource>>,
     /// MIDI learn mode state
     pub(crate) midi_learn: MidiLearnState,
     /// UI display mode (performance vs mapping)
@@ -178,6 +183,8 @@ pub struct MeshApp {
     pub(crate) history: crate::history::HistoryManager,
     /// Active set recording state (None when not recording)
     pub(crate) recording_state: Option<RecordingState>,
+    /// Auto DJ state (phase, playlist queue)
+    pub(crate) auto_dj: crate::auto_dj::AutoDjState,
     /// System resource monitor (CPU%, GPU%, RAM)
     pub(crate) resource_monitor: mesh_core::resource_monitor::ResourceMonitor,
     /// FPS frame counter (incremented each tick, reset every second)
@@ -212,6 +219,7 @@ impl MeshApp {
         linked_stem_receiver: Option<mesh_core::loader::LinkedStemResultReceiver>,
         clip_indicator: Option<Arc<AtomicBool>>,
         level_atomics: Option<Arc<LevelAtomics>>,
+        sampler_atomics: Option<Arc<SamplerAtomics>>,
         sample_rate: u32,
         audio_client_name: String,
         mapping_mode: bool,
@@ -298,6 +306,46 @@ impl MeshApp {
             && config.audio.outputs.master_device != config.audio.outputs.cue_device;
         domain.set_auto_cue(effective_auto_cue);
 
+        // Apply persisted crossfader curve/assignments (fader position starts centered)
+        domain.set_crossfader_config(&config.audio.crossfader);
+
+        // Per-deck EQ model and isolator crossovers
+        domain.set_eq_config(&config.audio.eq);
+
+        // Timecode format for DVS decks (every deck starts in internal mode)
+        if config.audio.dvs.enabled {
+            domain.set_timecode_format(config.audio.dvs.format);
+        }
+
+        // Talkover depth for the mic/aux line inputs
+        domain.set_talkover_depth(config.audio.line_inputs.talkover_depth_db);
+
+        // Effects on the mixer FX buses (send levels start closed)
+        let mut bus_effects = Vec::new();
+        for bus in FxBus::ALL {
+            let bus_config = config.audio.fx_buses.bus(bus);
+            for effect in &bus_config.effects {
+                let result = match effect.source {
+                    config::BusEffectSource::Native => domain.add_native_bus_effect(bus, &effect.id),
+                    config::BusEffectSource::Pd => domain.add_pd_bus_effect(bus, &effect.id),
+                    config::BusEffectSource::Clap => domain.add_clap_bus_effect(bus, &effect.id),
+                };
+                match result {
+                    Ok(info) => {
+                        let index = bus_effects.iter().filter(|(b, _, _)| *b == bus).count();
+                        for (param, value) in effect.params.iter().enumerate().take(info.param_count()) {
+                            domain.set_bus_effect_param(bus, index, param, *value);
+                        }
+                        bus_effects.push((bus, info, effect.params.clone()));
+                    }
+                    Err(e) => log::warn!("{}: {}", bus.label(), e),
+                }
+            }
+            if bus.send_index().is_some() {
+                domain.set_bus_return(bus, bus_config.return_level);
+            }
+        }
+
         // mesh-player always needs USB hotplug detection (performance mode)
         domain.set_usb_monitor_paused(false);
 
@@ -324,6 +372,7 @@ impl MeshApp {
         ];
         for dv in &mut deck_views {
             dv.sync_loop_length_index(default_loop_idx as u8);
+            dv.set_dvs_available(config.audio.dvs.enabled);
         }
 
         let history = crate::history::HistoryManager::new(
@@ -339,6 +388,7 @@ impl MeshApp {
             clip_indicator,
             clip_hold_frames: 0,
             level_atomics,
+            sampler_atomics,
             deck_meter: std::array::from_fn(|_| DeckMeterState::default()),
             master_meter: DeckMeterState::default(),
             player_canvas_state: {
@@ -351,7 +401,23 @@ impl MeshApp {
                 state
             },
             deck_views,
-            mixer_view: MixerView::new(),
+            mixer_view: {
+                let mut view = MixerView::new();
+                view.set_crossfader_assignments(config.audio.crossfader.assignments);
+                for deck in 0..4 {
+                    view.set_eq_model(deck, config.audio.eq.model_for(deck));
+                }
+                for input in LineInput::ALL {
+                    view.set_line_available(input, config.audio.line_inputs.enabled(input));
+                }
+                for bus in FxBus::ALL {
+                    view.set_fx_return(bus, config.audio.fx_buses.bus(bus).return_level);
+                }
+                for (bus, info, params) in &bus_effects {
+                    view.add_fx_bus_effect(*bus, info, params);
+                }
+                view
+            },
             collection_browser: {
                 let mut cb = CollectionBrowserState::new(
                     config.collection_path.clone(),
@@ -381,6 +447,7 @@ impl MeshApp {
             settings,
             controller,
             direct_dispatch: None,
+            clock_source: None,
             midi_learn: MidiLearnState::new(),
             app_mode: if mapping_mode { AppMode::Mapping } else { AppMode::Performance },
             stem_link_state: StemLinkState::Idle,
@@ -402,6 +469,7 @@ impl MeshApp {
             audio_sample_rate: sample_rate,
             history,
             recording_state: None,
+            auto_dj: crate::auto_dj::AutoDjState::new(),
             keyboard: KeyboardState::new(),
             keyboard_for_search: false,
             resource_monitor: mesh_core::resource_monitor::ResourceMonitor::new(),
@@ -494,7 +562,9 @@ impl MeshApp {
 
                             // Record in session history
                             let track_path = track.path.to_string_lossy().to_string();
-                            let load_source = if suggestion_ctx.is_some() {
+                            let load_source = if self.auto_dj.owns_load(deck_idx, &track_path) {
+                                crate::history::LoadSource::AutoDj
+                            } else if suggestion_ctx.is_some() {
                                 crate::history::LoadSource::Suggestions
                             } else {
                                 crate::history::LoadSource::Browser
@@ -636,7 +706,7 @@ impl MeshApp {
                             if self.keyboard_for_search {
                                 // Route to browser search
                                 self.keyboard_for_search = false;
-                                self.collection_browser.browser.table_state.set_search(text);
+                                self.collection_browser.set_search(text);
                             } else {
                                 // Route to WiFi connect if a network was selected
                                 if let Some(ref net_state) = self.settings.network {
@@ -667,31 +737,47 @@ impl MeshApp {
                 super::handlers::network::handle(self, net_msg)
             }
 
+            Message::AutoDj(msg) => super::handlers::auto_dj::handle(self, msg),
+
+            Message::Sampler(msg) => super::handlers::sampler::handle(self, msg),
+
             Message::Recording(RecordingMessage::Event(event)) => {
                 use mesh_core::recording::RecordingEvent;
                 match event {
                     RecordingEvent::Started { path } => {
                         log::info!("[UI] Recording started: {}", path.display());
                     }
-                    RecordingEvent::Stopped { path, duration_secs, .. } => {
-                        log::info!("[UI] Recording stopped: {} ({:.1}s)", path.display(), duration_secs);
+                    RecordingEvent::FirstPartRenamed { from, to } => {
+                        log::info!("[UI] Recording split: {} is now {}", from.display(), to.display());
+                    }
+                    RecordingEvent::PartStarted { path, part } => {
+                        log::info!("[UI] Recording continued in part {}: {}", part, path.display());
+                    }
+                    RecordingEvent::Stopped { path, duration_secs, parts, .. } => {
+                        log::info!(
+                            "[UI] Recording stopped: {} ({:.1}s, {} part(s))",
+                            path.display(), duration_secs, parts.len()
+                        );
                         // Generate tracklist in background
                         if let Some(ref rec_state) = self.recording_state {
                             let db = self.domain.local_db_arc();
                             let session_id = self.history.session_id();
                             let start_ms = rec_state.started_at_ms;
-                            let wav_path = path.clone();
+                            let embed_chapters = self.config.recording.embed_chapters;
                             let now_ms = std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap_or_default()
                                 .as_millis() as i64;
                             std::thread::spawn(move || {
                                 mesh_core::recording::generate_tracklist(
-                                    &wav_path, start_ms, now_ms, session_id, &db,
+                                    &parts, start_ms, now_ms, session_id, embed_chapters, &db,
                                 );
                             });
                         }
                     }
+                    RecordingEvent::StemsStopped { parts } => {
+                        log::info!("[UI] Stem recording stopped ({} file(s))", parts.len());
+                    }
                     RecordingEvent::Error { path, message } => {
                         log::error!("[UI] Recording error on {}: {}", path.display(), message);
                         self.status = format!("Recording error: {message}");
@@ -1057,6 +1143,8 @@ impl MeshApp {
                     MidiDeckAction::LoopIn | MidiDeckAction::LoopOut => None, // TODO
                     MidiDeckAction::BeatJumpForward => Some(DeckMessage::BeatJumpForward),
                     MidiDeckAction::BeatJumpBackward => Some(DeckMessage::BeatJumpBack),
+                    MidiDeckAction::PhraseJumpForward => Some(DeckMessage::PhraseJumpForward),
+                    MidiDeckAction::PhraseJumpBackward => Some(DeckMessage::PhraseJumpBack),
                     MidiDeckAction::SlicerTrigger { pad } => Some(DeckMessage::SlicerTrigger(pad)),
                     MidiDeckAction::SlicerAssign { .. } => None, // TODO
                     MidiDeckAction::SetSlicerMode { enabled } => {
@@ -1090,8 +1178,28 @@ impl MeshApp {
                             CollectionBrowserMessage::SetEnergyDirection(value),
                         ));
                     }
+                    MidiDeckAction::SamplerCapture => {
+                        return self.update(Message::Sampler(SamplerMessage::CaptureFromDeck(deck)));
+                    }
+                    MidiDeckAction::StepRating(delta) => {
+                        if let Some(path) = self.deck_views.get(deck).and_then(|v| v.loaded_track_path()).map(String::from) {
+                            self.collection_browser.update_track_rating(&path, |r| r.step_rating(delta));
+                        }
+                        None
+                    }
+                    MidiDeckAction::CycleColorLabel => {
+                        if let Some(path) = self.deck_views.get(deck).and_then(|v| v.loaded_track_path()).map(String::from) {
+                            self.collection_browser.update_track_rating(&path, TrackRating::cycle_color);
+                        }
+                        None
+                    }
                     MidiDeckAction::ToggleSlip => Some(DeckMessage::ToggleSlip),
                     MidiDeckAction::ToggleKeyMatch => Some(DeckMessage::ToggleKeyMatch),
+                    MidiDeckAction::SetPitch(value) => Some(DeckMessage::SetPitchFader(value)),
+                    MidiDeckAction::CyclePitchRange => Some(DeckMessage::CyclePitchRange),
+                    MidiDeckAction::ToggleKeylock => Some(DeckMessage::ToggleKeylock),
+                    MidiDeckAction::ShiftKey { semitones, cents } => Some(DeckMessage::ShiftKey(semitones, cents)),
+                    MidiDeckAction::ResetKeyShift => Some(DeckMessage::ResetKeyShift),
                     MidiDeckAction::LoadSelected => {
                         // If a track is selected, load it to this deck
                         if let Some(track_path) = self.collection_browser.get_selected_track_path() {
@@ -1111,7 +1219,20 @@ impl MeshApp {
                         ));
                         None
                     }
-                    MidiDeckAction::Seek { .. } | MidiDeckAction::Nudge { .. } => None, // TODO
+                    MidiDeckAction::JogTurn { revolutions } => {
+                        self.domain.jog_turn(deck, revolutions);
+                        None
+                    }
+                    MidiDeckAction::JogTouch { touched, release } => {
+                        self.domain.jog_touch(deck, touched, crate::direct_dispatch::jog_release(release));
+                        None
+                    }
+                    MidiDeckAction::Nudge { amount } => {
+                        self.domain.nudge(deck, amount * mesh_core::engine::MAX_PITCH_BEND as f32);
+                        None
+                    }
+                    MidiDeckAction::ToggleVinylMode => Some(DeckMessage::ToggleVinylMode),
+                    MidiDeckAction::Seek { .. } => None, // TODO
                 };
 
                 if let Some(dm) = deck_msg {
@@ -1126,8 +1247,13 @@ impl MeshApp {
                     MidiMixerAction::SetEqHi(v) => Some(MixerMessage::SetChannelEqHi(channel, v)),
                     MidiMixerAction::SetEqMid(v) => Some(MixerMessage::SetChannelEqMid(channel, v)),
                     MidiMixerAction::SetEqLo(v) => Some(MixerMessage::SetChannelEqLo(channel, v)),
+                    MidiMixerAction::SetEqHiMid(v) => Some(MixerMessage::SetChannelEqHiMid(channel, v)),
                     MidiMixerAction::ToggleCue => Some(MixerMessage::ToggleChannelCue(channel)),
-                    MidiMixerAction::SetCrossfader(_) => None, // No crossfader in mesh-player
+                    // MIDI delivers 0.0 (left) .. 1.0 (right); engine expects -1.0 .. 1.0
+                    MidiMixerAction::SetCrossfader(v) => Some(MixerMessage::SetCrossfader(v * 2.0 - 1.0)),
+                    MidiMixerAction::CycleCrossfaderAssign => Some(MixerMessage::CycleCrossfaderAssign(channel)),
+                    MidiMixerAction::SetFxSend { send, value } => (send < mesh_core::engine::NUM_FX_SENDS)
+                        .then_some(MixerMessage::SetFxSend(channel, send, value)),
                 };
 
                 if let Some(mm) = mixer_msg {
@@ -1198,6 +1324,10 @@ impl MeshApp {
                         let bpm_rounded = bpm.round();
                         self.domain.set_global_bpm_with_engine(bpm_rounded);
                     }
+                    MidiGlobalAction::ClockBpm(bpm) => {
+                        // Already filtered to 0.01 BPM — rounding would make decks drift
+                        self.domain.set_global_bpm_with_engine(bpm);
+                    }
                     MidiGlobalAction::AdjustBpm(_delta) => {
                         // Relative BPM adjustment — not needed yet
                     }
@@ -1246,6 +1376,40 @@ impl MeshApp {
                             );
                         }
                     }
+                    MidiGlobalAction::SamplerTrigger { slot } => {
+                        return self.update(Message::Sampler(SamplerMessage::Trigger(slot)));
+                    }
+                    MidiGlobalAction::SamplerStopAll => {
+                        return self.update(Message::Sampler(SamplerMessage::StopAll));
+                    }
+                    MidiGlobalAction::LineInput { input, action } => {
+                        use mesh_midi::LineInputAction;
+                        let Some(input) = LineInput::from_index(input) else {
+                            return Task::none();
+                        };
+                        let msg = match action {
+                            LineInputAction::SetVolume(v) => MixerMessage::SetLineVolume(input, v),
+                            LineInputAction::SetTrim(v) => MixerMessage::SetLineTrim(input, v),
+                            LineInputAction::SetEqHi(v) => MixerMessage::SetLineEqHi(input, v),
+                            LineInputAction::SetEqMid(v) => MixerMessage::SetLineEqMid(input, v),
+                            LineInputAction::SetEqLo(v) => MixerMessage::SetLineEqLo(input, v),
+                            LineInputAction::ToggleCue => MixerMessage::ToggleLineCue(input),
+                            LineInputAction::ToggleTalkover => MixerMessage::ToggleTalkover(input),
+                        };
+                        let _ = self.update(Message::Mixer(msg));
+                    }
+                    MidiGlobalAction::FxBus { bus, action } => {
+                        use mesh_midi::FxBusAction;
+                        let Some(bus) = FxBus::from_index(bus) else {
+                            return Task::none();
+                        };
+                        let msg = match action {
+                            FxBusAction::SetReturn(v) => MixerMessage::SetFxReturn(bus, v),
+                            FxBusAction::ToggleEnabled => MixerMessage::ToggleFxBus(bus),
+                            FxBusAction::SetKnob { knob, value } => MixerMessage::SetFxKnob(bus, knob, value),
+                        };
+                        let _ = self.update(Message::Mixer(msg));
+                    }
                     MidiGlobalAction::BrowseModeChanged { side, active } => {
                         if side < 2 {
                             self.browse_mode_active[side] = active;
@@ -1630,6 +1794,14 @@ impl MeshApp {
             Subscription::none()
         };
 
+        // Auto DJ step timer — 20Hz, only while Auto DJ is running
+        let auto_dj_sub = if self.auto_dj.is_active() {
+            time::every(std::time::Duration::from_millis(50))
+                .map(|_| Message::AutoDj(AutoDjMessage::Step))
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch([
             // Update UI capped at 120Hz. Was iced::window::frames() (vsync-locked),
             // but on >120Hz displays in fullscreen the resulting Tick rate could
@@ -1661,6 +1833,8 @@ impl MeshApp {
             resource_sub,
             // Recording thread events (started, stopped, error)
             recording_sub,
+            // Auto DJ loading/cueing/transition steps (20Hz, only while running)
+            auto_dj_sub,
         ])
     }
 
@@ -2030,8 +2204,38 @@ impl MeshApp {
             Space::new().into()
         };
 
-        // Right group: recording, stats, master meter, latency, settings
+        // Auto DJ indicator (click to stop)
+        let auto_dj_indicator: Element<'_, Message> = if self.auto_dj.is_active() {
+            button(text("AUTO DJ").size(sz(12.0)))
+                .on_press(Message::AutoDj(AutoDjMessage::Toggle))
+                .style(button::success)
+                .into()
+        } else {
+            Space::new().into()
+        };
+
+        // Sampler pads (only once something is loaded; lit while playing)
+        let sampler_pads: Element<'_, Message> = match self.sampler_atomics {
+            Some(ref atomics) if (0..NUM_SAMPLER_SLOTS).any(|s| atomics.is_loaded(s)) => {
+                let pads = (0..NUM_SAMPLER_SLOTS).map(|slot| -> Element<'_, Message> {
+                    let pad = button(text(format!("{}", slot + 1)).size(sz(10.0)))
+                        .padding([2, 6])
+                        .style(if atomics.is_playing(slot) { button::success } else { button::secondary });
+                    if atomics.is_loaded(slot) {
+                        pad.on_press(Message::Sampler(SamplerMessage::Trigger(slot))).into()
+                    } else {
+                        pad.into()
+                    }
+                });
+                row(pads).spacing(2).align_y(CenterAlign).into()
+            }
+            _ => Space::new().into(),
+        };
+
+        // Right group: sampler, Auto DJ, recording, stats, master meter, latency, settings
         let right_group: Element<'_, Message> = row![
+            sampler_pads,
+            auto_dj_indicator,
             recording_indicator,
             stats_label,
             master_meter,
@@ -13,7 +13,7 @@
 
 use iced::widget::{button, column, container, row, slider, text, Space};
 use iced::{Alignment, Background, Color, Element, Length};
-use mesh_core::db::DatabaseService;
+use mesh_core::db::{DatabaseService, TrackRating};
 use mesh_core::playlist::{DatabaseStorage, NodeId, NodeKind, PlaylistNode, PlaylistStorage};
 use mesh_core::usb::{UsbDevice, UsbStorage};
 use mesh_core::music::MusicalKey;
@@ -411,8 +411,8 @@ impl CollectionBrowserState {
                     PlaylistBrowserMessage::Table(ref table_msg) => {
                         // Only handle read-only table operations
                         match table_msg {
-                            TrackTableMessage::SearchChanged(_) => {
-                                let _ = self.browser.handle_table_message(table_msg);
+                            TrackTableMessage::SearchChanged(query) => {
+                                self.set_search(query.clone());
                             }
                             TrackTableMessage::SortBy(_) => {
                                 let _ = self.browser.handle_table_message(table_msg);
@@ -424,8 +424,7 @@ impl CollectionBrowserState {
                                 // mesh-player uses simple single-selection (no Shift/Ctrl)
                                 self.browser.table_state.select(track_id.clone());
                                 // Sync scroll index from clicked position
-                                let active = self.active_track_list();
-                                self.scroll_index = active.iter().position(|t| &t.id == track_id);
+                                self.scroll_index = self.visible_tracks().iter().position(|t| &t.id == track_id);
                                 // Update selected track path for load buttons
                                 self.selected_track_path = self.get_track_path(track_id);
                                 // Highlight selected track in graph
@@ -442,8 +441,7 @@ impl CollectionBrowserState {
                             TrackTableMessage::CellClicked(track_id, _) => {
                                 // Treat same as Select in mesh-player (no cell editing)
                                 self.browser.table_state.select(track_id.clone());
-                                let active = self.active_track_list();
-                                self.scroll_index = active.iter().position(|t| &t.id == track_id);
+                                self.scroll_index = self.visible_tracks().iter().position(|t| &t.id == track_id);
                                 self.update_graph_hover(track_id);
                                 self.rebuild_energy_arc();
                                 self.selected_track_path = self.get_track_path(track_id);
@@ -478,22 +476,23 @@ impl CollectionBrowserState {
                         self.scroll_index = None;
                     }
                 }
+                self.refresh_search_vocabulary();
+                self.update_search_matches();
                 None
             }
             CollectionBrowserMessage::ScrollBy(delta) => {
-                // Pick the active track list (suggestions or normal)
-                let active_tracks = if self.suggestions_enabled && !self.suggestion_tracks.is_empty() {
-                    &self.suggestion_tracks
-                } else {
-                    &self.tracks
-                };
-
                 // If no tracks loaded, scroll through folders (tree view)
-                if active_tracks.is_empty() {
+                if self.active_track_list().is_empty() {
                     self.scroll_tree(delta);
                     return None;
                 }
 
+                // Only the rows passing the search are on screen
+                let active_tracks = self.visible_tracks();
+                if active_tracks.is_empty() {
+                    return None;
+                }
+
                 // Use stored scroll index to avoid ambiguous ID→index lookup
                 // (duplicate track IDs would cause .position() to find the wrong one)
                 let current_idx = self.scroll_index.unwrap_or(0);
@@ -506,11 +505,11 @@ impl CollectionBrowserState {
                 };
 
                 // Select the new track and remember the index
-                if let Some(track) = active_tracks.get(new_idx) {
+                if let Some(track_id) = active_tracks.get(new_idx).map(|t| t.id.clone()) {
                     self.scroll_index = Some(new_idx);
-                    self.browser.table_state.select(track.id.clone());
-                    self.selected_track_path = self.get_track_path(&track.id);
-                    self.update_graph_hover(&track.id.clone());
+                    self.browser.table_state.select(track_id.clone());
+                    self.selected_track_path = self.get_track_path(&track_id);
+                    self.update_graph_hover(&track_id);
                     self.rebuild_energy_arc();
                 }
                 None
@@ -544,7 +543,7 @@ impl CollectionBrowserState {
                 None
             }
             CollectionBrowserMessage::ClearSearch => {
-                self.browser.table_state.set_search(String::new());
+                self.set_search(String::new());
                 None
             }
             CollectionBrowserMessage::Back => {
@@ -633,6 +632,14 @@ impl CollectionBrowserState {
     /// Returns `None` when at a root node, when no folder is selected, or when the folder
     /// has no tracks (e.g. an empty playlist or the "tracks"/"playlists" root pseudo-nodes).
     pub fn playlist_track_paths(&self) -> Option<(HashSet<String>, String)> {
+        let (paths, name) = self.playlist_queue()?;
+        Some((paths.into_iter().collect(), name))
+    }
+
+    /// Return the selected playlist's track paths in playlist order, with its display name.
+    ///
+    /// Used by Auto DJ's playlist source. Same `None` cases as `playlist_track_paths`.
+    pub fn playlist_queue(&self) -> Option<(Vec<String>, String)> {
         let folder = self.browser.current_folder.as_ref()?;
 
         // Skip root-level pseudo-nodes that don't represent real playlists
@@ -650,7 +657,7 @@ impl CollectionBrowserState {
             return None;
         };
 
-        let paths: HashSet<String> = tracks
+        let paths: Vec<String> = tracks
             .into_iter()
             .filter_map(|r| r.track_path)
             .collect();
@@ -678,6 +685,7 @@ impl CollectionBrowserState {
             log::debug!("load_tracks_for_folder: no storage available");
         }
         self.enrich_and_rebuild();
+        self.refresh_search_vocabulary();
 
         // Select first track if any
         self.scroll_index = None;
@@ -737,13 +745,13 @@ impl CollectionBrowserState {
     /// Returns None if no track is selected or tracks list is empty
     pub fn get_selected_index(&self) -> Option<usize> {
         // Prefer stored scroll index (avoids duplicate ID ambiguity)
+        let active_tracks = self.visible_tracks();
         if let Some(idx) = self.scroll_index {
-            if idx < self.active_track_list().len() {
+            if idx < active_tracks.len() {
                 return Some(idx);
             }
         }
         // Fallback: search by ID (for mouse-click selections)
-        let active_tracks = self.active_track_list();
         self.browser
             .table_state
             .selected
@@ -754,7 +762,47 @@ impl CollectionBrowserState {
 
     /// Get total track count (for scroll calculations)
     pub fn track_count(&self) -> usize {
-        self.active_track_list().len()
+        self.visible_tracks().len()
+    }
+
+    /// The active track list filtered by the search, as shown in the table
+    fn visible_tracks(&self) -> Vec<&TrackRow<NodeId>> {
+        let state = &self.browser.table_state;
+        self.active_track_list()
+            .iter()
+            .filter(|t| state.search_query.is_empty() || state.matches_search(t))
+            .collect()
+    }
+
+    /// Set the search query
+    ///
+    /// Structured filters (`bpm:124-128 tag:peak`) are evaluated against the
+    /// database being browsed, local or USB.
+    pub fn set_search(&mut self, query: String) {
+        self.browser.table_state.set_search(query);
+        self.update_search_matches();
+        self.scroll_index = None;
+    }
+
+    /// Re-run a structured search, e.g. after tracks were played
+    fn update_search_matches(&mut self) {
+        let query = self.browser.table_state.structured_search();
+        let matches = query.and_then(|query| match self.active_db().search_track_paths(&query) {
+            Ok(paths) => Some(paths),
+            Err(e) => {
+                log::warn!("Search failed: {}", e);
+                None
+            }
+        });
+        self.browser.table_state.search_matches = matches;
+    }
+
+    /// Reload the tags and genres offered by search autocomplete
+    fn refresh_search_vocabulary(&mut self) {
+        match self.active_db().get_search_vocabulary() {
+            Ok(vocab) => self.browser.table_state.search_vocabulary = vocab,
+            Err(e) => log::warn!("Failed to load search vocabulary: {}", e),
+        }
     }
 
     /// Get the active track list (suggestions when enabled, otherwise normal tracks)
@@ -1064,6 +1112,7 @@ impl CollectionBrowserState {
                         if info.cue_count > 0 {
                             row = row.with_cue_count(info.cue_count);
                         }
+                        row = row.with_rating(info.rating, info.color_label.as_deref().and_then(parse_hex_color));
                         if !info.tags.is_empty() {
                             let mut sorted_tags = info.tags.clone();
                             sorted_tags.sort_by_key(|(_, color)| {
@@ -1130,6 +1179,49 @@ impl CollectionBrowserState {
         sources
     }
 
+    /// Change the rating / colour label of a track and refresh its rows.
+    ///
+    /// The new rating is written to every database holding the file (local
+    /// and each USB stick) so it survives the next export from mesh-cue.
+    pub fn update_track_rating(
+        &mut self,
+        track_path: &str,
+        update: impl FnOnce(&mut TrackRating),
+    ) -> Option<TrackRating> {
+        let filename = std::path::Path::new(track_path).file_name()?.to_string_lossy().into_owned();
+        let targets: Vec<(Arc<DatabaseService>, i64)> = self.all_graph_sources()
+            .into_iter()
+            .filter_map(|(db, _)| {
+                let id = db.find_track_by_filename(&filename).ok()??.id?;
+                Some((db, id))
+            })
+            .collect();
+
+        // Local database first, so its rating is the one being changed
+        let (first_db, first_id) = targets.first()?;
+        let mut rating = first_db.get_track_rating(*first_id).ok().flatten()
+            .unwrap_or(TrackRating { track_id: *first_id, ..Default::default() });
+        update(&mut rating);
+        for (db, track_id) in &targets {
+            if let Err(e) = db.set_track_rating(&TrackRating { track_id: *track_id, ..rating.clone() }) {
+                log::warn!("Failed to save rating for {}: {}", filename, e);
+            }
+        }
+        log::info!("Rated {}: {} stars, colour {:?}", filename, rating.rating, rating.color);
+
+        let color = rating.color.as_deref().and_then(parse_hex_color);
+        for row in self.tracks.iter_mut().chain(self.suggestion_tracks.iter_mut()) {
+            let same_file = row.track_path.as_deref()
+                .and_then(|p| std::path::Path::new(p).file_name())
+                .is_some_and(|f| f.to_string_lossy() == filename);
+            if same_file {
+                row.rating = rating.rating;
+                row.color_label = color;
+            }
+        }
+        Some(rating)
+    }
+
     /// Get the active database (USB if browsing USB, local otherwise).
     fn active_db(&self) -> Arc<DatabaseService> {
         if let Some(idx) = self.active_usb_idx {
@@ -1191,6 +1283,7 @@ impl CollectionBrowserState {
         self.suggestion_paths = paths;
         self.suggestion_context_cache = contexts;
         self.suggestion_loading = false;
+        self.apply_play_stats();
 
         // Auto-select first suggestion and highlight in graph
         self.scroll_index = None;
@@ -1217,6 +1310,16 @@ impl CollectionBrowserState {
             return;
         }
         self.played_this_session = played.clone();
+        // Smart playlists with play-count or session rules change as tracks are played
+        if let (Some(storage), Some(folder)) = (self.storage.as_ref(), self.browser.current_folder.as_ref()) {
+            if !self.suggestions_enabled && storage.is_smart_playlist(folder) {
+                self.tracks = get_tracks_for_folder(storage.as_ref(), folder);
+                self.enrich_and_rebuild();
+            }
+        }
+        // "played" and "plays:" filters change too
+        self.update_search_matches();
+        self.apply_play_stats();
         self.refresh_dimming();
     }
 
@@ -1340,10 +1443,25 @@ impl CollectionBrowserState {
             }
         }
 
+        self.apply_play_stats();
         self.compute_similarities();
         self.rebuild_energy_arc();
     }
 
+    /// Fill play counts and last-played times on the cached tracks, counted
+    /// across the local database and every mounted USB stick.
+    pub fn apply_play_stats(&mut self) {
+        let sources = self.all_graph_sources();
+        let stats = DatabaseService::aggregate_play_stats(sources.iter().map(|(db, _)| db.as_ref()));
+        for row in self.tracks.iter_mut().chain(self.suggestion_tracks.iter_mut()) {
+            let Some(path) = &row.track_path else { continue };
+            if let Some(s) = stats.get(&DatabaseService::play_stats_key(path)) {
+                row.play_count = s.play_count;
+                row.last_played = Some(s.last_played);
+            }
+        }
+    }
+
     /// Rebuild energy arc from the active track list.
     /// Update graph hover to highlight the selected track node.
     /// Parses the DB track ID from suggestion NodeIds ("suggestion:{id}").
@@ -1431,6 +1549,7 @@ fn build_node_children(storage: &dyn PlaylistStorage, parent: &PlaylistNode) ->
                 NodeKind::CollectionFolder => TreeIcon::Folder,
                 NodeKind::PlaylistsRoot => TreeIcon::Folder,
                 NodeKind::Playlist => TreeIcon::Playlist,
+                NodeKind::SmartPlaylist => TreeIcon::SmartPlaylist,
                 _ => TreeIcon::Folder,
             };
 
@@ -1473,6 +1592,7 @@ fn get_tracks_for_folder(storage: &dyn PlaylistStorage, folder_id: &NodeId) -> V
             if info.cue_count > 0 {
                 row = row.with_cue_count(info.cue_count);
             }
+            row = row.with_rating(info.rating, info.color_label.as_deref().and_then(parse_hex_color));
             if !info.tags.is_empty() {
                 let mut sorted_tags = info.tags.clone();
                 sorted_tags.sort_by_key(|(_, color)| {
@@ -1519,6 +1639,7 @@ fn build_usb_node_children(
                 NodeKind::CollectionFolder => TreeIcon::Folder,
                 NodeKind::PlaylistsRoot => TreeIcon::Folder,
                 NodeKind::Playlist => TreeIcon::Playlist,
+                NodeKind::SmartPlaylist => TreeIcon::SmartPlaylist,
                 _ => TreeIcon::Folder,
             };
 
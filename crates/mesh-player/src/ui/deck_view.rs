@@ -12,7 +12,8 @@
 use iced::widget::{button, column, container, mouse_area, row, scrollable, slider, text, Row, Space};
 use iced::{Background, Center, Color, Element, Fill, Length};
 
-use mesh_core::engine::Deck;
+use mesh_core::dvs::DvsMode;
+use mesh_core::engine::{Deck, DeckAtomics, PitchRange};
 use mesh_core::types::PlayState;
 use mesh_widgets::{sz, CUE_COLORS, DeckPresetState, DeckPresetMessage, DECK_PRESET_NUM_MACROS};
 
@@ -68,6 +69,24 @@ pub struct DeckView {
     slip_enabled: bool,
     /// Key matching enabled
     key_match_enabled: bool,
+    /// Pitch fader position (-1.0 to 1.0, synced from atomics)
+    pitch_fader: f32,
+    /// Pitch fader range
+    pitch_range: PitchRange,
+    /// Keylock (master tempo) on
+    keylock: bool,
+    /// Manual key shift (semitones, cents)
+    key_shift: (i8, i8),
+    /// Jog touch scratches (vinyl mode) rather than only nudging
+    vinyl_mode: bool,
+    /// Timecode inputs are open (DVS enabled in config)
+    dvs_available: bool,
+    /// How the deck follows its timecode input (synced from atomics)
+    dvs_mode: DvsMode,
+    /// Timecode signal present
+    dvs_signal: bool,
+    /// Platter speed read from the timecode
+    dvs_speed: f32,
     /// Currently selected stem for effect chain view (0-3)
     selected_stem: usize,
     /// Deck preset state (shared preset + macros across all stems)
@@ -121,6 +140,20 @@ pub enum DeckMessage {
     ToggleSlip,
     /// Toggle key matching
     ToggleKeyMatch,
+    /// Set pitch fader position (-1.0 to 1.0)
+    SetPitchFader(f32),
+    /// Cycle pitch fader range (±6/10/16/50%)
+    CyclePitchRange,
+    /// Toggle keylock (master tempo)
+    ToggleKeylock,
+    /// Shift the key by (semitones, cents)
+    ShiftKey(i8, i8),
+    /// Clear the manual key shift
+    ResetKeyShift,
+    /// Toggle jog wheel vinyl mode (touch to scratch)
+    ToggleVinylMode,
+    /// Cycle DVS mode (internal → relative → absolute)
+    CycleDvsMode,
     /// Set loop length (beats)
     SetLoopLength(u32),
     /// Halve loop length
@@ -131,6 +164,10 @@ pub enum DeckMessage {
     BeatJumpBack,
     /// Beat jump forward (uses loop length)
     BeatJumpForward,
+    /// Jump to the current / previous phrase start
+    PhraseJumpBack,
+    /// Jump to the next phrase start
+    PhraseJumpForward,
     /// Toggle stem mute
     ToggleStemMute(usize),
     /// Toggle stem solo
@@ -177,6 +214,15 @@ impl DeckView {
             loop_length_beats: 4.0, // Default 4 beats
             slip_enabled: false,
             key_match_enabled: false,
+            pitch_fader: 0.0,
+            pitch_range: PitchRange::default(),
+            keylock: true,
+            key_shift: (0, 0),
+            vinyl_mode: true,
+            dvs_available: false,
+            dvs_mode: DvsMode::Off,
+            dvs_signal: false,
+            dvs_speed: 0.0,
             selected_stem: 0,       // Start with Vocals selected
             deck_preset: DeckPresetState::new(),
             action_mode: ActionButtonMode::default(),
@@ -219,6 +265,12 @@ impl DeckView {
         self.loop_active = deck.loop_state().active;
         self.slip_enabled = deck.slip_enabled();
         self.key_match_enabled = deck.key_match_enabled();
+        self.pitch_fader = deck.pitch_fader();
+        self.pitch_range = deck.pitch_range();
+        self.keylock = deck.keylock();
+        self.key_shift = deck.key_shift();
+        self.vinyl_mode = deck.vinyl_mode();
+        self.dvs_mode = deck.dvs_mode();
 
         if let Some(track) = deck.track() {
             self.track_bpm = track.bpm();
@@ -267,6 +319,24 @@ impl DeckView {
         self.state = state;
     }
 
+    /// Sync pitch fader, keylock, key shift, vinyl and DVS mode from atomics (lock-free UI update)
+    pub fn sync_pitch_state(&mut self, atomics: &DeckAtomics) {
+        use std::sync::atomic::Ordering::Relaxed;
+        self.pitch_fader = atomics.pitch_fader();
+        self.pitch_range = atomics.pitch_range();
+        self.keylock = atomics.keylock.load(Relaxed);
+        self.key_shift = (atomics.key_shift_semitones.load(Relaxed), atomics.key_shift_cents.load(Relaxed));
+        self.vinyl_mode = atomics.vinyl_mode.load(Relaxed);
+        self.dvs_mode = atomics.dvs_mode();
+        self.dvs_signal = atomics.dvs_signal.load(Relaxed);
+        self.dvs_speed = atomics.dvs_speed();
+    }
+
+    /// Show the DVS mode button (timecode inputs are open)
+    pub fn set_dvs_available(&mut self, available: bool) {
+        self.dvs_available = available;
+    }
+
     /// Sync loop length from atomics (lock-free UI update)
     ///
     /// This is called every frame to update the loop length display.
@@ -344,6 +414,31 @@ impl DeckView {
         self.key_match_enabled
     }
 
+    /// Get the pitch fader range
+    pub fn pitch_range(&self) -> PitchRange {
+        self.pitch_range
+    }
+
+    /// Check if keylock is on
+    pub fn keylock(&self) -> bool {
+        self.keylock
+    }
+
+    /// Check if jog touch scratches (vinyl mode)
+    pub fn vinyl_mode(&self) -> bool {
+        self.vinyl_mode
+    }
+
+    /// Get the DVS mode
+    pub fn dvs_mode(&self) -> DvsMode {
+        self.dvs_mode
+    }
+
+    /// Get the manual key shift as (semitones, cents)
+    pub fn key_shift(&self) -> (i8, i8) {
+        self.key_shift
+    }
+
     /// Set the action button mode
     pub fn set_action_mode(&mut self, mode: ActionButtonMode) {
         self.action_mode = mode;
@@ -493,6 +588,14 @@ impl DeckView {
             DeckMessage::ToggleKeyMatch => {
                 // Handled at engine level via command
             }
+            DeckMessage::SetPitchFader(_)
+            | DeckMessage::CyclePitchRange
+            | DeckMessage::ToggleKeylock
+            | DeckMessage::ShiftKey(..)
+            | DeckMessage::ResetKeyShift
+            | DeckMessage::ToggleVinylMode => {
+                // Handled at engine level via command
+            }
             DeckMessage::SetLoopLength(beats) => {
                 // Find index for this beat length and set it
                 if let Some(idx) = LOOP_LENGTHS.iter().position(|&b| b == beats as f32) {
@@ -520,6 +623,12 @@ impl DeckView {
             DeckMessage::BeatJumpForward => {
                 deck.beat_jump_forward();
             }
+            DeckMessage::PhraseJumpBack => {
+                deck.phrase_jump_backward();
+            }
+            DeckMessage::PhraseJumpForward => {
+                deck.phrase_jump_forward();
+            }
             DeckMessage::ToggleStemMute(stem_idx) => {
                 // Mute/solo is now on StemState directly
                 if stem_idx < 4 {
@@ -1366,7 +1475,10 @@ impl DeckView {
             .spacing(12)
             .align_y(Center);
 
-        column![stem_section, control_row, mode_row, bottom_section]
+        // Pitch fader, range, keylock and key shift
+        let pitch_row = self.view_pitch_row_compact();
+
+        column![stem_section, control_row, pitch_row, mode_row, bottom_section]
             .spacing(8)
             .padding(8)
             .into()
@@ -1652,6 +1764,79 @@ impl DeckView {
         .into()
     }
 
+    /// Pitch row: fader, tempo offset, range, keylock, vinyl/DVS mode and key shift
+    ///
+    /// ```text
+    /// [━━━━●━━━━] +2.4%  [±10%] [KEYLOCK ●] [VINYL ●] [DVS REL ● 1.02x]  [♭] -1st +10¢ [♯]
+    /// ```
+    ///
+    /// The DVS button only shows when timecode inputs are open. Outside INT
+    /// it shows whether a signal is present (●/○) and the platter speed.
+    fn view_pitch_row_compact(&self) -> Element<'_, DeckMessage> {
+        let percent = self.pitch_fader * self.pitch_range.fraction() as f32 * 100.0;
+        let fader = slider(-1.0..=1.0, self.pitch_fader, DeckMessage::SetPitchFader)
+            .step(0.001)
+            .width(Length::Fill);
+        // Click the readout to snap back to the synced tempo
+        let percent_btn = button(text(format!("{:+.1}%", percent)).size(sz(10.0)))
+            .on_press(DeckMessage::SetPitchFader(0.0))
+            .padding([4, 6])
+            .width(Length::Fixed(56.0));
+
+        let range_btn = button(text(format!("±{}%", self.pitch_range.percent())).size(sz(10.0)))
+            .on_press(DeckMessage::CyclePitchRange)
+            .padding([4, 6]);
+
+        let keylock_text = if self.keylock { "KEYLOCK ●" } else { "KEYLOCK" };
+        let keylock_btn = button(text(keylock_text).size(sz(10.0)))
+            .on_press(DeckMessage::ToggleKeylock)
+            .padding([4, 8]);
+
+        let vinyl_text = if self.vinyl_mode { "VINYL ●" } else { "VINYL" };
+        let vinyl_btn = button(text(vinyl_text).size(sz(10.0)))
+            .on_press(DeckMessage::ToggleVinylMode)
+            .padding([4, 8]);
+
+        let dvs_btn = self.dvs_available.then(|| {
+            let dvs_text = match self.dvs_mode {
+                DvsMode::Off => "DVS INT".to_string(),
+                mode => format!(
+                    "DVS {} {} {:.2}x",
+                    mode.label(),
+                    if self.dvs_signal { "●" } else { "○" },
+                    self.dvs_speed,
+                ),
+            };
+            button(text(dvs_text).size(sz(10.0)))
+                .on_press(DeckMessage::CycleDvsMode)
+                .padding([4, 8])
+        });
+
+        let key_down = button(text("♭").size(sz(11.0)))
+            .on_press(DeckMessage::ShiftKey(-1, 0))
+            .padding([4, 6]);
+        let key_up = button(text("♯").size(sz(11.0)))
+            .on_press(DeckMessage::ShiftKey(1, 0))
+            .padding([4, 6]);
+        let key_label = button(text(format_key_shift(self.key_shift)).size(sz(10.0)))
+            .on_press(DeckMessage::ResetKeyShift)
+            .padding([4, 6])
+            .style(button::text);
+
+        let mut pitch_row = row![fader, percent_btn, range_btn, keylock_btn, vinyl_btn];
+        if let Some(dvs_btn) = dvs_btn {
+            pitch_row = pitch_row.push(dvs_btn);
+        }
+        pitch_row
+            .push(Space::new().width(8))
+            .push(key_down)
+            .push(key_label)
+            .push(key_up)
+            .spacing(4)
+            .align_y(Center)
+            .into()
+    }
+
     /// Mode selection row: [SHIFT] [HOTCUE] [SLICER]
     ///
     /// Determines the behavior of the 8 action buttons below
@@ -1907,6 +2092,16 @@ fn format_loop_length(beats: f32) -> String {
     }
 }
 
+/// Format a manual key shift for display ("0st", "+2st", "-1st +10¢")
+fn format_key_shift((semitones, cents): (i8, i8)) -> String {
+    match (semitones, cents) {
+        (0, 0) => "0st".to_string(),
+        (s, 0) => format!("{:+}st", s),
+        (0, c) => format!("{:+}¢", c),
+        (s, c) => format!("{:+}st {:+}¢", s, c),
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Preset picker styles
 // ─────────────────────────────────────────────────────────────────────────────
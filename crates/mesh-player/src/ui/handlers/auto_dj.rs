@@ -0,0 +1,381 @@
+//! Auto DJ message handler
+//!
+//! Drives the Auto DJ cycle described in [`crate::auto_dj`]: picks the next
+//! track, loads and cues it on the idle deck and applies the transition curve.
+//! Fader, EQ and play changes go through the regular mixer and deck handlers
+//! so the mixer view, session history and suggestion seeds stay in sync.
+
+use std::time::{Duration, Instant};
+
+use iced::Task;
+use mesh_core::types::{Stem, SAMPLE_RATE};
+
+use crate::auto_dj::{plan_mix, transition_levels, AutoDjPhase, AutoDjPick, ChannelLevels, MixPlan};
+use crate::config::AutoDjSource;
+use crate::history::SuggestionContext;
+use crate::suggestions::query_suggestions;
+use crate::ui::app::MeshApp;
+use crate::ui::deck_view::DeckMessage;
+use crate::ui::handlers::browser::{suggestion_config, suggestion_sources};
+use crate::ui::handlers::{deck_controls, mixer};
+use crate::ui::message::{AutoDjMessage, Message};
+use crate::ui::mixer_view::MixerMessage;
+
+/// How long a load may take before Auto DJ gives up on the track
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Suggestions fetched per pick (the first one not yet played wins)
+const PICK_POOL: usize = 10;
+
+/// Handle Auto DJ messages
+pub fn handle(app: &mut MeshApp, msg: AutoDjMessage) -> Task<Message> {
+    match msg {
+        AutoDjMessage::Toggle => {
+            if app.auto_dj.is_active() {
+                stop(app, "Auto DJ stopped")
+            } else {
+                start(app)
+            }
+        }
+        AutoDjMessage::Start => {
+            if app.auto_dj.is_active() { Task::none() } else { start(app) }
+        }
+        AutoDjMessage::Step => step(app),
+        AutoDjMessage::Picked(result) => handle_picked(app, result),
+    }
+}
+
+fn start(app: &mut MeshApp) -> Task<Message> {
+    app.auto_dj.stop();
+    app.status = "Auto DJ started".to_string();
+
+    if app.config.auto_dj.source == AutoDjSource::Playlist {
+        match app.collection_browser.playlist_queue() {
+            Some((paths, name)) => {
+                log::info!("[AUTO_DJ] Queued {} tracks from '{}'", paths.len(), name);
+                app.auto_dj.queue = paths.into();
+            }
+            None => {
+                app.status = "Auto DJ started (no playlist open, using suggestions)".to_string();
+            }
+        }
+    }
+
+    let live = live_deck(app);
+    log::info!("[AUTO_DJ] Started (live deck: {:?})", live.map(|d| d + 1));
+    app.auto_dj.phase = AutoDjPhase::Picking { live };
+    app.settings.auto_dj_active = true;
+
+    // Both channels must be audible whatever the crossfader assignment. Goes
+    // through the mixer handler so the mixer view's crossfader moves too.
+    let crossfader_task = mixer::handle(app, MixerMessage::SetCrossfader(0.0));
+    Task::batch([crossfader_task, pick_next(app)])
+}
+
+fn stop(app: &mut MeshApp, status: &str) -> Task<Message> {
+    // Don't leave a cued or half-finished transition's EQ, filter and stem
+    // mutes behind; faders stay where they are
+    let decks = match app.auto_dj.phase {
+        AutoDjPhase::Cued { incoming, .. } => vec![incoming],
+        AutoDjPhase::Mixing { live, incoming, .. } => vec![live, incoming],
+        _ => Vec::new(),
+    };
+    let tasks: Vec<_> = decks.into_iter()
+        .map(|deck| {
+            let volume = app.mixer_view.channel_volume(deck);
+            apply_levels(app, deck, ChannelLevels { volume, ..ChannelLevels::FLAT }, None)
+        })
+        .collect();
+
+    log::info!("[AUTO_DJ] {}", status);
+    app.auto_dj.stop();
+    app.settings.auto_dj_active = false;
+    app.status = status.to_string();
+    Task::batch(tasks)
+}
+
+// ─────────────────────────────────────────────────
+// Picking
+// ─────────────────────────────────────────────────
+
+/// Choose the next track: playlist queue first, then the suggestion engine
+fn pick_next(app: &mut MeshApp) -> Task<Message> {
+    let mut exclude = app.history.played_paths().clone();
+    exclude.extend(app.auto_dj.attempted.iter().cloned());
+    exclude.extend((0..4).filter_map(|i| app.deck_views[i].loaded_track_path().map(String::from)));
+
+    while let Some(path) = app.auto_dj.queue.pop_front() {
+        if !exclude.contains(&path) {
+            let pick = AutoDjPick { path, context: None };
+            return Task::done(Message::AutoDj(AutoDjMessage::Picked(Ok(Some(pick)))));
+        }
+    }
+
+    // Seed from the live track (none = opener mode)
+    let seed_paths: Vec<String> = match app.auto_dj.phase {
+        AutoDjPhase::Picking { live: Some(deck) } => {
+            app.deck_views[deck].loaded_track_path().map(String::from).into_iter().collect()
+        }
+        _ => Vec::new(),
+    };
+    let sources = suggestion_sources(app);
+    let config = suggestion_config(app);
+    let energy_direction = app.collection_browser.energy_direction();
+    let key_model = app.config.display.key_scoring_model;
+
+    Task::perform(
+        async move {
+            let suggestions = query_suggestions(
+                &sources, seed_paths, energy_direction, key_model,
+                config, 10_000, PICK_POOL, &exclude,
+                None, None, None,
+                false,
+            )?;
+            Ok(suggestions.into_iter()
+                .find(|s| !exclude.contains(&*s.track.path.to_string_lossy()))
+                .map(|s| AutoDjPick {
+                    path: s.track.path.to_string_lossy().into_owned(),
+                    context: Some(SuggestionContext {
+                        score: s.score,
+                        reason_tags: s.reason_tags,
+                        energy_direction,
+                    }),
+                }))
+        },
+        |result| Message::AutoDj(AutoDjMessage::Picked(result)),
+    )
+}
+
+fn handle_picked(app: &mut MeshApp, result: Result<Option<AutoDjPick>, String>) -> Task<Message> {
+    // Stopped (or restarted) while the query was running
+    let AutoDjPhase::Picking { live } = app.auto_dj.phase else {
+        return Task::none();
+    };
+
+    match result {
+        Ok(Some(pick)) => {
+            let Some(incoming) = idle_deck(app, live) else {
+                return stop(app, "Auto DJ stopped: no idle deck");
+            };
+            log::info!("[AUTO_DJ] Loading '{}' on deck {}", pick.path, incoming + 1);
+            app.auto_dj.attempted.insert(pick.path.clone());
+            app.auto_dj.phase = AutoDjPhase::Loading {
+                live,
+                incoming,
+                path: pick.path.clone(),
+                since: Instant::now(),
+            };
+            Task::done(Message::LoadTrack(incoming, pick.path, pick.context))
+        }
+        Ok(None) => stop(app, "Auto DJ stopped: nothing left to play"),
+        Err(e) => {
+            log::warn!("[AUTO_DJ] Suggestion query failed: {}", e);
+            stop(app, "Auto DJ stopped: suggestion query failed")
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────
+// Stepping
+// ─────────────────────────────────────────────────
+
+fn step(app: &mut MeshApp) -> Task<Message> {
+    match app.auto_dj.phase.clone() {
+        AutoDjPhase::Off | AutoDjPhase::Picking { .. } => Task::none(),
+        AutoDjPhase::Loading { live, incoming, path, since } => {
+            step_loading(app, live, incoming, path, since)
+        }
+        AutoDjPhase::Cued { live, incoming, path, plan } => step_cued(app, live, incoming, &path, plan),
+        AutoDjPhase::Mixing { live, incoming, plan } => step_mixing(app, live, incoming, plan),
+    }
+}
+
+/// Wait for the audio, then cue it (or start it right away if nothing plays)
+fn step_loading(
+    app: &mut MeshApp,
+    live: Option<usize>,
+    incoming: usize,
+    path: String,
+    since: Instant,
+) -> Task<Message> {
+    let deck = &app.deck_views[incoming];
+    if deck.loaded_track_path() != Some(path.as_str()) || deck.audio_loading() {
+        if since.elapsed() > LOAD_TIMEOUT {
+            log::warn!("[AUTO_DJ] Timed out loading '{}', picking another track", path);
+            app.auto_dj.phase = AutoDjPhase::Picking { live };
+            return pick_next(app);
+        }
+        return Task::none();
+    }
+
+    let Some(live) = live else {
+        // Nothing playing yet: open with this track from its first downbeat
+        let first_beat = app.domain.load_track_metadata(&path)
+            .and_then(|m| m.beat_grid.beats.first().copied())
+            .unwrap_or(0);
+        app.domain.seek(incoming, first_beat as usize);
+        let levels_task = apply_levels(app, incoming, ChannelLevels::FLAT, None);
+        let play_task = deck_controls::handle(app, incoming, DeckMessage::TogglePlayPause);
+        app.auto_dj.phase = AutoDjPhase::Picking { live: Some(incoming) };
+        return Task::batch([levels_task, play_task, pick_next(app)]);
+    };
+
+    let plan = mix_plan(app, live, &path);
+    app.domain.seek(incoming, plan.in_start as usize);
+    let (_, levels) = transition_levels(app.config.auto_dj.transition, 0.0);
+    let task = apply_levels(app, incoming, levels, None);
+    app.auto_dj.phase = AutoDjPhase::Cued { live, incoming, path, plan };
+    task
+}
+
+/// Start the incoming deck once the live deck reaches the mix-out downbeat
+fn step_cued(app: &mut MeshApp, live: usize, incoming: usize, path: &str, plan: MixPlan) -> Task<Message> {
+    // The DJ loaded something else over the cued track: pick again
+    if app.deck_views[incoming].loaded_track_path() != Some(path) {
+        app.auto_dj.phase = AutoDjPhase::Picking { live: Some(live) };
+        return pick_next(app);
+    }
+    if position(app, live) < plan.out_start {
+        return Task::none();
+    }
+
+    log::info!("[AUTO_DJ] Mixing deck {} into deck {}", live + 1, incoming + 1);
+    let play_task = if is_playing(app, incoming) {
+        Task::none()
+    } else {
+        // Phase sync locks the incoming downbeat to the live deck's beat
+        deck_controls::handle(app, incoming, DeckMessage::TogglePlayPause)
+    };
+    app.auto_dj.applied = None;
+    app.auto_dj.phase = AutoDjPhase::Mixing { live, incoming, plan };
+    Task::batch([play_task, step_mixing(app, live, incoming, plan)])
+}
+
+/// Follow the transition curve; hand over to the incoming deck at the end
+fn step_mixing(app: &mut MeshApp, live: usize, incoming: usize, plan: MixPlan) -> Task<Message> {
+    // A stopped outgoing deck (track ran out, or the DJ paused it) ends the mix
+    let progress = if is_playing(app, live) { plan.progress(position(app, live)) } else { 1.0 };
+    let (out, inc) = transition_levels(app.config.auto_dj.transition, progress);
+
+    let prev = app.auto_dj.applied;
+    let mut tasks = vec![
+        apply_levels(app, live, out, prev.map(|(o, _)| o)),
+        apply_levels(app, incoming, inc, prev.map(|(_, i)| i)),
+    ];
+    app.auto_dj.applied = Some((out, inc));
+
+    if progress >= 1.0 {
+        log::info!("[AUTO_DJ] Deck {} is live", incoming + 1);
+        // Stop the outgoing deck and return it to its cue point
+        app.domain.pause(live);
+        app.domain.seek(live, cue_point(app, live) as usize);
+        tasks.push(apply_levels(app, live, ChannelLevels { volume: 0.0, ..ChannelLevels::FLAT }, Some(out)));
+        app.auto_dj.applied = None;
+        app.auto_dj.phase = AutoDjPhase::Picking { live: Some(incoming) };
+        tasks.push(pick_next(app));
+    }
+    Task::batch(tasks)
+}
+
+// ─────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────
+
+/// Plan the mix from the live track into `incoming_path`.
+///
+/// Falls back to mixing over the live track's last bars (at its BPM) when
+/// either track lacks a usable beat grid.
+fn mix_plan(app: &MeshApp, live: usize, incoming_path: &str) -> MixPlan {
+    let bars = app.config.auto_dj.validated_transition_bars();
+    let outgoing = app.deck_views[live].loaded_track_path()
+        .and_then(|p| app.domain.load_track_metadata(p));
+    let incoming = app.domain.load_track_metadata(incoming_path);
+
+    if let (Some(out), Some(inc)) = (&outgoing, &incoming) {
+        if let Some(plan) = plan_mix(out, inc, bars) {
+            return plan;
+        }
+    }
+
+    let bpm = outgoing.as_ref().and_then(|m| m.bpm).unwrap_or(120.0);
+    let length = (bars as f64 * 4.0 * 60.0 / bpm * SAMPLE_RATE as f64) as u64;
+    log::debug!("[AUTO_DJ] No usable beat grid, mixing over the last {} samples", length);
+    MixPlan::at_end(app.player_canvas_state.decks[live].overview.duration_samples, length)
+}
+
+/// Send the channel levels that differ from `prev` (all of them when `None`)
+fn apply_levels(
+    app: &mut MeshApp,
+    deck: usize,
+    levels: ChannelLevels,
+    prev: Option<ChannelLevels>,
+) -> Task<Message> {
+    let changed = |field: fn(&ChannelLevels) -> f32| match prev {
+        Some(p) => field(&p) != field(&levels),
+        None => true,
+    };
+
+    let mut messages = Vec::new();
+    if changed(|l| l.volume) {
+        messages.push(MixerMessage::SetChannelVolume(deck, levels.volume));
+    }
+    if changed(|l| l.eq_hi) {
+        messages.push(MixerMessage::SetChannelEqHi(deck, levels.eq_hi));
+    }
+    if changed(|l| l.eq_mid) {
+        messages.push(MixerMessage::SetChannelEqMid(deck, levels.eq_mid));
+    }
+    if changed(|l| l.eq_lo) {
+        messages.push(MixerMessage::SetChannelEqLo(deck, levels.eq_lo));
+    }
+    if changed(|l| l.filter) {
+        messages.push(MixerMessage::SetChannelFilter(deck, levels.filter));
+    }
+    let tasks: Vec<_> = messages.into_iter().map(|msg| mixer::handle(app, msg)).collect();
+
+    for (stem_idx, &muted) in levels.stems_muted.iter().enumerate() {
+        set_stem_muted(app, deck, stem_idx, muted);
+    }
+    Task::batch(tasks)
+}
+
+/// Mute or unmute a stem (no-op when already in that state)
+fn set_stem_muted(app: &mut MeshApp, deck: usize, stem_idx: usize, muted: bool) {
+    if app.deck_views[deck].is_stem_muted(stem_idx) == muted {
+        return;
+    }
+    if let Some(stem) = Stem::from_index(stem_idx) {
+        app.domain.toggle_stem_mute(deck, stem);
+    }
+    app.deck_views[deck].set_stem_muted(stem_idx, muted);
+    app.player_canvas_state.set_stem_active(deck, stem_idx, !muted);
+}
+
+/// The loudest playing deck with a track loaded
+fn live_deck(app: &MeshApp) -> Option<usize> {
+    (0..4)
+        .filter(|&i| is_playing(app, i) && app.deck_views[i].loaded_track_path().is_some())
+        .max_by(|&a, &b| {
+            app.mixer_view.channel_volume(a).total_cmp(&app.mixer_view.channel_volume(b))
+        })
+}
+
+/// The live deck's partner (1↔2, 3↔4) if free, else any stopped deck
+fn idle_deck(app: &MeshApp, live: Option<usize>) -> Option<usize> {
+    let partner = live.map_or(0, |deck| deck ^ 1);
+    std::iter::once(partner)
+        .chain(0..4)
+        .find(|&i| Some(i) != live && !is_playing(app, i))
+}
+
+fn is_playing(app: &MeshApp, deck: usize) -> bool {
+    app.deck_atomics.as_ref().is_some_and(|a| a[deck].is_playing())
+}
+
+fn position(app: &MeshApp, deck: usize) -> u64 {
+    app.deck_atomics.as_ref().map_or(0, |a| a[deck].position())
+}
+
+fn cue_point(app: &MeshApp, deck: usize) -> u64 {
+    app.deck_atomics.as_ref().map_or(0, |a| a[deck].cue_point())
+}
@@ -70,7 +70,7 @@ pub fn handle_browser(app: &mut MeshApp, browser_msg: CollectionBrowserMessage)
         }
         CollectionBrowserMessage::OpenSearch => {
             let current = app.collection_browser.browser.table_state.search_query.clone();
-            app.keyboard.open("Search tracks...", false);
+            app.keyboard.open("Search (e.g. bpm:124-128 key:8A tag:peak -played)", false);
             app.keyboard.text = current; // pre-fill with existing query
             app.keyboard_for_search = true;
             return Task::none();
@@ -431,6 +431,36 @@ fn compute_blend_vec(app: &MeshApp, seed_paths: &[String], bias_abs: f32) -> Opt
     Some(blend)
 }
 
+/// Collect all available database sources: local collection + all mounted USBs
+pub(crate) fn suggestion_sources(app: &MeshApp) -> Vec<DbSource> {
+    let mut sources = vec![DbSource {
+        db: app.domain.local_db_arc(),
+        collection_root: app.domain.local_collection_path().to_path_buf(),
+        name: "Local".to_string(),
+    }];
+    for (_, usb_storage) in &app.collection_browser.usb_storages {
+        if let Some(db) = usb_storage.db() {
+            sources.push(DbSource {
+                db: db.clone(),
+                collection_root: usb_storage.collection_root().clone(),
+                name: usb_storage.device().label.clone(),
+            });
+        }
+    }
+    sources
+}
+
+/// Build suggestion algorithm config from display settings + dynamic community thresholds
+pub(crate) fn suggestion_config(app: &MeshApp) -> crate::suggestions::SuggestionConfig {
+    crate::suggestions::SuggestionConfig::from_display(
+        app.config.display.suggestion_blend_mode,
+        app.config.display.suggestion_key_filter,
+        app.config.display.suggestion_stem_complement,
+        app.config.display.suggestion_transition_reach,
+        app.collection_browser.community_thresholds.as_ref(),
+    )
+}
+
 /// Build and dispatch a background suggestion query from current deck seeds.
 ///
 /// When no decks are playing, delegates to opener mode which scores candidates
@@ -457,34 +487,10 @@ pub fn trigger_suggestion_query(app: &mut MeshApp) -> Task<Message> {
 
     // Opener mode (no playing decks) is handled inside query_suggestions — don't bail here
 
-    // Collect all available database sources: local collection + all mounted USBs
-    let mut sources = vec![DbSource {
-        db: app.domain.local_db_arc(),
-        collection_root: app.domain.local_collection_path().to_path_buf(),
-        name: "Local".to_string(),
-    }];
-    for (_, usb_storage) in &app.collection_browser.usb_storages {
-        if let Some(db) = usb_storage.db() {
-            sources.push(DbSource {
-                db: db.clone(),
-                collection_root: usb_storage.collection_root().clone(),
-                name: usb_storage.device().label.clone(),
-            });
-        }
-    }
-
+    let sources = suggestion_sources(app);
     let key_model = app.config.display.key_scoring_model;
     let played = app.history.played_paths().clone();
-
-    // Build suggestion algorithm config from display settings + dynamic community thresholds
-    let community_thresholds = app.collection_browser.community_thresholds.clone();
-    let mut suggestion_config = crate::suggestions::SuggestionConfig::from_display(
-        app.config.display.suggestion_blend_mode,
-        app.config.display.suggestion_key_filter,
-        app.config.display.suggestion_stem_complement,
-        app.config.display.suggestion_transition_reach,
-        community_thresholds.as_ref(),
-    );
+    let mut suggestion_config = suggestion_config(app);
     // Snapshot current playlist context for the split logic
     let playlist_paths = app.collection_browser.playlist_track_paths().map(|(p, _)| p);
     let playlist_split = app.config.display.suggestion_playlist_split;
@@ -7,6 +7,7 @@ use iced::Task;
 use crate::ui::app::MeshApp;
 use crate::ui::deck_view::{DeckMessage, ActionButtonMode};
 use crate::ui::message::Message;
+use mesh_core::engine::step_key_shift;
 use mesh_core::types::{PlayState, Stem};
 use mesh_widgets::multiband::{
     list_deck_presets, list_stem_presets,
@@ -92,6 +93,37 @@ pub fn handle(app: &mut MeshApp, deck_idx: usize, deck_msg: DeckMessage) -> Task
             let current = app.deck_views[deck_idx].key_match_enabled();
             app.domain.set_key_match_enabled(deck_idx, !current);
         }
+
+        // ─────────────────────────────────────────────────
+        // Pitch / Keylock
+        // ─────────────────────────────────────────────────
+        SetPitchFader(value) => {
+            app.domain.set_pitch_fader(deck_idx, value);
+        }
+        CyclePitchRange => {
+            let next = app.deck_views[deck_idx].pitch_range().next();
+            app.domain.set_pitch_range(deck_idx, next);
+        }
+        ToggleKeylock => {
+            let current = app.deck_views[deck_idx].keylock();
+            app.domain.set_keylock(deck_idx, !current);
+        }
+        ToggleVinylMode => {
+            let current = app.deck_views[deck_idx].vinyl_mode();
+            app.domain.set_vinyl_mode(deck_idx, !current);
+        }
+        CycleDvsMode => {
+            let next = app.deck_views[deck_idx].dvs_mode().next();
+            app.domain.set_dvs_mode(deck_idx, next);
+        }
+        ShiftKey(semitones, cents) => {
+            let current = app.deck_views[deck_idx].key_shift();
+            let (semitones, cents) = step_key_shift(current, semitones, cents);
+            app.domain.set_key_shift(deck_idx, semitones, cents);
+        }
+        ResetKeyShift => {
+            app.domain.set_key_shift(deck_idx, 0, 0);
+        }
         SetLoopLength(_beats) => {
             // Loop length is handled via adjust commands
         }
@@ -111,6 +143,12 @@ pub fn handle(app: &mut MeshApp, deck_idx: usize, deck_msg: DeckMessage) -> Task
         BeatJumpForward => {
             app.domain.beat_jump_forward(deck_idx);
         }
+        PhraseJumpBack => {
+            app.domain.phrase_jump_backward(deck_idx);
+        }
+        PhraseJumpForward => {
+            app.domain.phrase_jump_forward(deck_idx);
+        }
 
         // ─────────────────────────────────────────────────
         // Stem Control
@@ -37,6 +37,29 @@ pub fn handle(app: &mut MeshApp) -> Task<Message> {
         }
     }
 
+    // Sampler pad bitmaps (loaded / playing)
+    if let Some(ref sampler) = app.sampler_atomics {
+        for slot in 0..mesh_core::engine::NUM_SAMPLER_SLOTS {
+            if sampler.is_loaded(slot) {
+                feedback.sampler_loaded |= 1 << slot;
+            }
+            if sampler.is_playing(slot) {
+                feedback.sampler_playing |= 1 << slot;
+            }
+        }
+    }
+
+    // Mic/aux line input cue and talkover buttons
+    for input in mesh_core::engine::LineInput::ALL {
+        feedback.line_cue[input.index()] = app.mixer_view.line_cue_enabled(input);
+        feedback.talkover[input.index()] = app.mixer_view.talkover(input);
+    }
+
+    // Mixer FX bus on/off buttons
+    for bus in mesh_core::engine::FxBus::ALL {
+        feedback.fx_bus_enabled[bus.index()] = app.mixer_view.fx_bus_enabled(bus);
+    }
+
     // Compute slicer preset assignment bitmap once (doesn't vary per deck)
     let slicer_presets_assigned: u8 = app.slice_editor.presets
         .iter()
@@ -53,6 +76,10 @@ pub fn handle(app: &mut MeshApp) -> Task<Message> {
             feedback.decks[deck_idx].loop_active = atomics[deck_idx].loop_active();
             feedback.decks[deck_idx].key_match_enabled =
                 atomics[deck_idx].key_match_enabled.load(std::sync::atomic::Ordering::Relaxed);
+            feedback.decks[deck_idx].keylock =
+                atomics[deck_idx].keylock.load(std::sync::atomic::Ordering::Relaxed);
+            feedback.decks[deck_idx].vinyl_mode =
+                atomics[deck_idx].vinyl_mode.load(std::sync::atomic::Ordering::Relaxed);
         }
 
         // Get slicer state
@@ -174,8 +174,10 @@ pub fn handle(app: &mut MeshApp, learn_msg: MidiLearnMessage) -> Task<Message> {
         Save => {
             app.status = "Saving MIDI config...".to_string();
 
-            let config = app.midi_learn.generate_config();
+            let mut config = app.midi_learn.generate_config();
             let config_path = mesh_midi::default_midi_config_path();
+            // Clock ports aren't part of learn — keep the hand-written section
+            config.clock = mesh_midi::load_midi_config(&config_path).clock;
 
             let mapping_count = config.devices.first()
                 .map(|d| d.mappings.len())
@@ -207,6 +209,9 @@ pub fn handle(app: &mut MeshApp, learn_msg: MidiLearnMessage) -> Task<Message> {
                             if let Some(dispatch) = app.direct_dispatch.clone() {
                                 controller.set_direct_dispatch(dispatch);
                             }
+                            if let Some(source) = app.clock_source.clone() {
+                                controller.set_clock_source(source);
+                            }
                             if controller.is_connected() {
                                 log::info!("MIDI: Reloaded controller with new config");
                                 app.status = "MIDI config saved and loaded!".to_string();
@@ -1,14 +1,18 @@
 //! Mixer message handler
 //!
-//! Handles volume, EQ, filter, and cue controls for all deck channels.
+//! Handles volume, EQ, filter, cue and crossfader controls for all deck channels,
+//! the mic/aux line input strips and the FX send/return buses.
+
+use std::sync::Arc;
 
 use iced::Task;
 
+use crate::config;
 use crate::ui::app::MeshApp;
 use crate::ui::message::Message;
 use crate::ui::mixer_view::MixerMessage;
 
-/// Handle mixer messages (volume, EQ, filter, cue)
+/// Handle mixer messages (volume, EQ, filter, cue, crossfader)
 pub fn handle(app: &mut MeshApp, mixer_msg: MixerMessage) -> Task<Message> {
     use MixerMessage::*;
 
@@ -35,6 +39,9 @@ pub fn handle(app: &mut MeshApp, mixer_msg: MixerMessage) -> Task<Message> {
         SetChannelEqLo(deck, value) => {
             app.domain.set_eq_lo(*deck, *value);
         }
+        SetChannelEqHiMid(deck, value) => {
+            app.domain.set_eq_hi_mid(*deck, *value);
+        }
         SetChannelFilter(deck, value) => {
             app.domain.set_filter(*deck, *value);
         }
@@ -47,6 +54,68 @@ pub fn handle(app: &mut MeshApp, mixer_msg: MixerMessage) -> Task<Message> {
         SetCueVolume(volume) => {
             app.domain.set_cue_volume(*volume);
         }
+        SetCrossfader(position) => {
+            app.domain.set_crossfader(*position);
+        }
+        CycleCrossfaderAssign(deck) => {
+            let assign = app.mixer_view.crossfader_assign(*deck).cycle();
+            app.domain.set_crossfader_assign(*deck, assign);
+        }
+        SetLineVolume(input, volume) => {
+            app.domain.set_line_volume(*input, *volume);
+        }
+        SetLineTrim(input, value) => {
+            app.domain.set_line_trim(*input, mesh_core::engine::trim_db_from_knob(*value));
+        }
+        SetLineEqHi(input, value) => {
+            app.domain.set_line_eq_hi(*input, *value);
+        }
+        SetLineEqMid(input, value) => {
+            app.domain.set_line_eq_mid(*input, *value);
+        }
+        SetLineEqLo(input, value) => {
+            app.domain.set_line_eq_lo(*input, *value);
+        }
+        ToggleLineCue(input) => {
+            let enabled = !app.mixer_view.line_cue_enabled(*input);
+            app.domain.set_line_cue(*input, enabled);
+        }
+        ToggleTalkover(input) => {
+            let enabled = !app.mixer_view.talkover(*input);
+            app.domain.set_talkover(*input, enabled);
+        }
+        SetFxSend(deck, send, level) => {
+            app.domain.set_fx_send(*deck, *send, *level);
+        }
+        SetFxReturn(bus, level) => {
+            app.domain.set_bus_return(*bus, *level);
+        }
+        ToggleFxBus(bus) => {
+            let enabled = !app.mixer_view.fx_bus_enabled(*bus);
+            app.domain.set_bus_enabled(*bus, enabled);
+        }
+        SetFxKnob(bus, knob, value) => {
+            if let Some((effect, param)) = app.mixer_view.fx_knob_target(*bus, *knob) {
+                app.domain.set_bus_effect_param(*bus, effect, param, *value);
+            }
+        }
+    }
+
+    // Crossfader assignments are persisted immediately (no settings Save needed).
+    // Saved on a thread rather than a Task: MIDI-originated updates discard the Task.
+    // Out-of-range decks (from a bad controller mapping) are ignored.
+    if let CycleCrossfaderAssign(deck) = &mixer_msg {
+        let mut new_config = (*app.config).clone();
+        if let Some(slot) = new_config.audio.crossfader.assignments.get_mut(*deck) {
+            *slot = app.mixer_view.crossfader_assign(*deck).cycle();
+            app.config = Arc::new(new_config.clone());
+            let config_path = app.config_path.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = config::save_config(&new_config, &config_path) {
+                    log::warn!("Failed to save crossfader assignment: {}", e);
+                }
+            });
+        }
     }
 
     // Always update local UI state
@@ -14,3 +14,5 @@ pub mod led_feedback;
 pub mod multiband;
 pub mod network;
 pub mod system_update;
+pub mod auto_dj;
+pub mod sampler;
@@ -0,0 +1,176 @@
+//! Sampler message handler
+//!
+//! Loads clips into the engine's sampler bank: sample packs decoded in the
+//! background, and loops captured from a deck's stems. Pads, stop-all and
+//! pack saving go through here too.
+
+use std::path::Path;
+
+use iced::Task;
+use mesh_core::sampler::{self, capture_region, ClipMode, SampleClip};
+use mesh_core::types::NUM_STEMS;
+
+use crate::ui::app::MeshApp;
+use crate::ui::message::{Message, SamplerMessage};
+
+/// Beats captured when the deck has no active loop
+const DEFAULT_CAPTURE_BEATS: f64 = 4.0;
+
+/// Handle sampler messages
+pub fn handle(app: &mut MeshApp, msg: SamplerMessage) -> Task<Message> {
+    match msg {
+        SamplerMessage::Trigger(slot) => {
+            app.domain.sampler_trigger(slot);
+            Task::none()
+        }
+        SamplerMessage::StopAll => {
+            app.domain.sampler_stop_all();
+            Task::none()
+        }
+        SamplerMessage::Clear(slot) => {
+            app.domain.load_sampler_slot(slot, None);
+            Task::none()
+        }
+        SamplerMessage::ToggleMode(slot) => {
+            app.domain.toggle_sampler_mode(slot);
+            Task::none()
+        }
+        SamplerMessage::CaptureFromDeck(deck) => {
+            capture_from_deck(app, deck);
+            Task::none()
+        }
+        SamplerMessage::LoadPack(None) => {
+            for slot in 0..mesh_core::engine::NUM_SAMPLER_SLOTS {
+                app.domain.load_sampler_slot(slot, None);
+            }
+            app.status = "Sampler cleared".to_string();
+            Task::none()
+        }
+        SamplerMessage::LoadPack(Some(name)) => {
+            app.status = format!("Loading sample pack '{}'...", name);
+            let root = app.config.collection_path.clone();
+            let sample_rate = app.audio_sample_rate;
+            Task::perform(
+                async move {
+                    let clips = sampler::load_pack(&root, &name, sample_rate);
+                    (name, clips)
+                },
+                |(name, clips)| Message::Sampler(SamplerMessage::PackLoaded(name, clips)),
+            )
+        }
+        SamplerMessage::PackLoaded(name, Ok(clips)) => {
+            let loaded = clips.iter().filter(|c| c.is_some()).count();
+            for (slot, clip) in clips.into_iter().enumerate() {
+                app.domain.load_sampler_slot(slot, clip);
+            }
+            log::info!("[SAMPLER] Loaded pack '{}' ({} clips)", name, loaded);
+            app.status = format!("Sample pack '{}' loaded ({} clips)", name, loaded);
+            Task::none()
+        }
+        SamplerMessage::PackLoaded(name, Err(e)) => {
+            log::warn!("[SAMPLER] Failed to load pack '{}': {}", name, e);
+            app.status = format!("Sample pack '{}' failed: {}", name, e);
+            Task::none()
+        }
+        SamplerMessage::SavePack => save_pack(app),
+        SamplerMessage::PackSaved(Ok(name)) => {
+            app.settings.available_sampler_packs = sampler::list_packs(&app.config.collection_path);
+            app.settings.draft_sampler_pack = Some(name.clone());
+            app.status = format!("Sample pack '{}' saved", name);
+            Task::none()
+        }
+        SamplerMessage::PackSaved(Err(e)) => {
+            log::warn!("[SAMPLER] Failed to save pack: {}", e);
+            app.status = format!("Saving sample pack failed: {}", e);
+            Task::none()
+        }
+    }
+}
+
+/// Capture the deck's active loop (or the next few beats) into a free slot
+///
+/// Takes the stems that are audible on the deck: the soloed ones if any
+/// stem is soloed, otherwise every stem that isn't muted.
+fn capture_from_deck(app: &mut MeshApp, deck: usize) {
+    let Some(slot) = app.domain.first_empty_sampler_slot() else {
+        app.status = "Sampler full: clear a slot before capturing".to_string();
+        return;
+    };
+    let Some(stems) = app.domain.deck_stems().get(deck).cloned().flatten() else {
+        app.status = format!("Deck {} has no track to capture", deck + 1);
+        return;
+    };
+    let Some(atomics) = app.deck_atomics.as_ref().map(|a| a[deck].clone()) else {
+        return;
+    };
+
+    let view = &app.deck_views[deck];
+    let path = view.loaded_track_path().map(String::from);
+    let bpm = path.as_deref()
+        .and_then(|p| app.domain.load_track_metadata(p))
+        .and_then(|m| m.bpm);
+
+    let (start, end) = if atomics.loop_active() {
+        (atomics.loop_start() as usize, atomics.loop_end() as usize)
+    } else {
+        let Some(bpm) = bpm else {
+            app.status = "Set a loop first (track has no BPM)".to_string();
+            return;
+        };
+        // Start on the beat the playhead is in
+        let beat_len = app.audio_sample_rate as f64 * 60.0 / bpm;
+        let first_beat = view.first_beat_sample() as f64;
+        let offset = (atomics.position() as f64 - first_beat).max(0.0);
+        let start = first_beat + (offset / beat_len).floor() * beat_len;
+        (start as usize, (start + DEFAULT_CAPTURE_BEATS * beat_len) as usize)
+    };
+
+    let any_solo = (0..NUM_STEMS).any(|i| view.is_stem_soloed(i));
+    let stem_mask: [bool; NUM_STEMS] = std::array::from_fn(|i| {
+        if any_solo { view.is_stem_soloed(i) } else { !view.is_stem_muted(i) }
+    });
+
+    let audio = capture_region(&stems, start, end, stem_mask);
+    if audio.is_empty() {
+        app.status = "Nothing to capture at this position".to_string();
+        return;
+    }
+
+    let track_name = path.as_deref()
+        .and_then(|p| Path::new(p).file_stem())
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("Deck {}", deck + 1));
+    let clip = SampleClip {
+        name: format!("{} loop", track_name),
+        audio,
+        sample_rate: app.audio_sample_rate,
+        bpm,
+        mode: ClipMode::Loop,
+    };
+    log::info!("[SAMPLER] Captured {} samples from deck {} into slot {}", clip.len(), deck + 1, slot + 1);
+    app.status = format!("Captured loop into sampler slot {}", slot + 1);
+    app.domain.load_sampler_slot(slot, Some(clip));
+}
+
+/// Write the loaded slots to the active pack, or a new pack named by date
+fn save_pack(app: &mut MeshApp) -> Task<Message> {
+    let clips: Vec<Option<SampleClip>> = (0..mesh_core::engine::NUM_SAMPLER_SLOTS)
+        .map(|slot| app.domain.sampler_clip(slot).cloned())
+        .collect();
+    if clips.iter().all(|c| c.is_none()) {
+        app.status = "Sampler is empty, nothing to save".to_string();
+        return Task::none();
+    }
+
+    let name = app.settings.draft_sampler_pack.clone().unwrap_or_else(sampler::dated_pack_name);
+    let root = app.config.collection_path.clone();
+    app.status = format!("Saving sample pack '{}'...", name);
+
+    Task::perform(
+        async move {
+            let slots: Vec<Option<&SampleClip>> = clips.iter().map(|c| c.as_ref()).collect();
+            sampler::save_pack(&root, &name, &slots).map(|_| name)
+        },
+        |result| Message::Sampler(SamplerMessage::PackSaved(result)),
+    )
+}
@@ -8,7 +8,7 @@ use iced::Task;
 use crate::config;
 use crate::ui::app::MeshApp;
 use crate::ui::handlers::browser::trigger_suggestion_query;
-use crate::ui::message::{Message, SettingsMessage};
+use crate::ui::message::{Message, SamplerMessage, SettingsMessage};
 use crate::ui::settings::SettingsState;
 
 /// Handle settings messages
@@ -21,12 +21,14 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
             let network = app.settings.network.take();
             let update = app.settings.update.take();
             let recording_active = app.settings.recording_active;
+            let auto_dj_active = app.settings.auto_dj_active;
             app.settings = SettingsState::from_config(&app.config);
             app.settings.available_theme_names = app.themes.iter().map(|t| t.name.clone()).collect();
             // Preserve stateful sections across reopen (avoid re-detecting nmcli/NixOS)
             app.settings.network = network;
             app.settings.update = update;
             app.settings.recording_active = recording_active;
+            app.settings.auto_dj_active = auto_dj_active;
             app.settings.is_open = true;
             app.settings.settings_midi_nav = midi_nav;
             app.settings.take_snapshot();
@@ -89,6 +91,56 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
             app.settings.draft_auto_cue = enabled;
             Task::none()
         }
+        UpdateCrossfaderCurve(curve) => {
+            app.settings.draft_crossfader_curve = curve;
+            Task::none()
+        }
+        UpdateCrossfaderReverse(enabled) => {
+            app.settings.draft_crossfader_reverse = enabled;
+            Task::none()
+        }
+        UpdateEqModel(model) => {
+            app.settings.draft_eq_model = model;
+            Task::none()
+        }
+        UpdateRecordingFormat(format) => {
+            app.settings.draft_recording_format = format;
+            Task::none()
+        }
+        UpdateRecordingSplit(split) => {
+            app.settings.draft_recording_split = split;
+            Task::none()
+        }
+        UpdateRecordingChapters(enabled) => {
+            app.settings.draft_recording_chapters = enabled;
+            Task::none()
+        }
+        UpdateRecordingStems(stems) => {
+            app.settings.draft_recording_stems = stems;
+            Task::none()
+        }
+        UpdateAutoDjSource(source) => {
+            app.settings.draft_auto_dj_source = source;
+            Task::none()
+        }
+        UpdateAutoDjTransition(transition) => {
+            app.settings.draft_auto_dj_transition = transition;
+            Task::none()
+        }
+        UpdateAutoDjBars(bars) => {
+            app.settings.draft_auto_dj_bars = bars;
+            Task::none()
+        }
+        UpdateAutoDjStartOnLaunch(enabled) => {
+            app.settings.draft_auto_dj_start_on_launch = enabled;
+            Task::none()
+        }
+        UpdateSamplerPack(idx) => {
+            let pack = idx.checked_sub(1)
+                .and_then(|i| app.settings.available_sampler_packs.get(i).cloned());
+            app.settings.draft_sampler_pack = pack.clone();
+            Task::done(Message::Sampler(SamplerMessage::LoadPack(pack)))
+        }
         UpdateSlicerBufferBars(bars) => {
             app.settings.draft_slicer_buffer_bars = bars;
             Task::none()
@@ -239,15 +291,18 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
                     usb_mounts
                 };
 
+                let recording_config = app.config.recording;
+                let mut stem_producers = Vec::new();
                 for (mount, available_bytes) in &recording_targets {
-                    match mesh_core::recording::start_recording(mount, sample_rate, *available_bytes, event_tx.clone()) {
-                        Ok((producer, handle)) => {
+                    match mesh_core::recording::start_recording(mount, sample_rate, *available_bytes, recording_config, event_tx.clone()) {
+                        Ok((producer, stem_producer, handle)) => {
                             // Send producer to audio thread (boxed for EngineCommand size)
                             app.domain.send_command(
                                 mesh_core::engine::EngineCommand::StartRecording {
                                     producer: Box::new(producer),
                                 }
                             );
+                            stem_producers.extend(stem_producer);
                             handles.push(handle);
                         }
                         Err(e) => {
@@ -256,6 +311,17 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
                     }
                 }
 
+                // One stem capture feeds the stem threads of all sticks
+                // (built here: its time stretchers allocate)
+                if !stem_producers.is_empty() {
+                    let capture = mesh_core::engine::StemCapture::new(sample_rate, stem_producers);
+                    app.domain.send_command(
+                        mesh_core::engine::EngineCommand::StartStemRecording {
+                            capture: basedrop::Owned::new(&mesh_core::engine::gc::gc_handle(), capture),
+                        }
+                    );
+                }
+
                 if handles.is_empty() {
                     app.status = "Failed to start recording".to_string();
                     app.settings.recording_active = false;
@@ -311,6 +377,23 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
             new_config.audio.phase_sync = app.settings.draft_phase_sync;
             // Save auto-cue intent (effective value accounts for same-device constraint)
             new_config.audio.auto_cue = app.settings.draft_auto_cue;
+            // Save crossfader curve/hamster (assignments are persisted from the mixer view)
+            new_config.audio.crossfader.curve = app.settings.draft_crossfader_curve;
+            new_config.audio.crossfader.reverse = app.settings.draft_crossfader_reverse;
+            // Save EQ model (per-deck overrides and crossovers are edited in the config file)
+            new_config.audio.eq.model = app.settings.draft_eq_model;
+            // Save set recording format (applies to the next recording)
+            new_config.recording.format = app.settings.draft_recording_format;
+            new_config.recording.split = app.settings.draft_recording_split;
+            new_config.recording.embed_chapters = app.settings.draft_recording_chapters;
+            new_config.recording.stems = app.settings.draft_recording_stems;
+            // Save Auto DJ settings (picked up by the next transition)
+            new_config.auto_dj.source = app.settings.draft_auto_dj_source;
+            new_config.auto_dj.transition = app.settings.draft_auto_dj_transition;
+            new_config.auto_dj.transition_bars = app.settings.draft_auto_dj_bars;
+            new_config.auto_dj.start_on_launch = app.settings.draft_auto_dj_start_on_launch;
+            // Save the sample pack to load on the next launch
+            new_config.sampler.pack = app.settings.draft_sampler_pack.clone();
             // Save only buffer_bars (presets are read-only from shared file)
             new_config.slicer.buffer_bars = app.settings.draft_slicer_buffer_bars;
             // Save loudness settings
@@ -380,6 +463,12 @@ pub fn handle(app: &mut MeshApp, msg: SettingsMessage) -> Task<Message> {
             let effective_auto_cue = app.settings.draft_auto_cue
                 && app.settings.draft_master_device != app.settings.draft_cue_device;
             app.domain.set_auto_cue(effective_auto_cue);
+            app.domain.set_crossfader_config(&app.config.audio.crossfader);
+            // EQ model switches crossfade in the engine, so this is safe mid-set
+            app.domain.set_eq_config(&app.config.audio.eq);
+            for deck in 0..4 {
+                app.mixer_view.set_eq_model(deck, app.config.audio.eq.model_for(deck));
+            }
             // Send loudness config to engine (triggers recalculation for all loaded decks)
             app.domain.set_loudness_config(app.config.audio.loudness.clone());
             // Send slicer buffer bars to audio engine for all decks and stems
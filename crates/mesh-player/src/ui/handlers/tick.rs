@@ -181,6 +181,7 @@ pub fn handle(app: &mut MeshApp) -> Task<Message> {
             // Update deck view state from atomics
             app.deck_views[i].sync_play_state(atomics[i].play_state());
             app.deck_views[i].sync_loop_length_index(atomics[i].loop_length_index());
+            app.deck_views[i].sync_pitch_state(&atomics[i]);
 
             // Sync loop length and active state to canvas
             let has_track = app.player_canvas_state.decks[i].overview.has_track;
@@ -5,8 +5,10 @@
 use std::sync::Arc;
 use mesh_widgets::MultibandEditorMessage;
 
-use crate::config::{AppFont, FontSize, KeyScoringModel, SuggestionBlendMode, SuggestionKeyFilter, SuggestionTransitionReach, WaveformAbstraction, WaveformLayout};
+use crate::auto_dj::AutoDjPick;
+use crate::config::{AppFont, AutoDjSource, AutoDjTransition, CrossfaderCurve, EqModel, FontSize, RecordingFormat, RecordingSplit, RecordingStems, KeyScoringModel, SuggestionBlendMode, SuggestionKeyFilter, SuggestionTransitionReach, WaveformAbstraction, WaveformLayout};
 use crate::history::SuggestionContext;
+use mesh_core::sampler::SampleClip;
 use crate::suggestions::SplitSuggestions;
 use super::collection_browser::CollectionBrowserMessage;
 use super::deck_view::DeckMessage;
@@ -25,6 +27,42 @@ pub enum RecordingMessage {
     Event(mesh_core::recording::RecordingEvent),
 }
 
+/// Messages for Auto DJ
+#[derive(Debug, Clone)]
+pub enum AutoDjMessage {
+    /// Start or stop Auto DJ
+    Toggle,
+    /// Start Auto DJ if it isn't running (start-on-launch)
+    Start,
+    /// Periodic step: advance loading, cueing and transition ramps
+    Step,
+    /// Next track chosen (None = nothing left to play)
+    Picked(Result<Option<AutoDjPick>, String>),
+}
+
+/// Messages for the sampler bank
+#[derive(Debug, Clone)]
+pub enum SamplerMessage {
+    /// Pad pressed: restart a one-shot, start/stop a loop
+    Trigger(usize),
+    /// Stop every slot
+    StopAll,
+    /// Empty a slot
+    Clear(usize),
+    /// Switch a slot between one-shot and loop
+    ToggleMode(usize),
+    /// Capture a deck's loop (audible stems) into the first empty slot
+    CaptureFromDeck(usize),
+    /// Load a sample pack from the collection (None = empty all slots)
+    LoadPack(Option<String>),
+    /// Pack decoded in the background (pack name, one clip per slot)
+    PackLoaded(String, Result<Vec<Option<SampleClip>>, String>),
+    /// Save the loaded slots as a pack (the active one, or a new dated pack)
+    SavePack,
+    /// Pack written to disk (pack name)
+    PackSaved(Result<String, String>),
+}
+
 /// Settings-related messages
 #[derive(Debug, Clone)]
 pub enum SettingsMessage {
@@ -46,6 +84,30 @@ pub enum SettingsMessage {
     UpdatePhaseSync(bool),
     /// Update draft auto-cue setting
     UpdateAutoCue(bool),
+    /// Update draft crossfader curve
+    UpdateCrossfaderCurve(CrossfaderCurve),
+    /// Update draft crossfader reverse (hamster mode)
+    UpdateCrossfaderReverse(bool),
+    /// Update draft channel EQ model (all decks without an override)
+    UpdateEqModel(EqModel),
+    /// Update draft set recording file format
+    UpdateRecordingFormat(RecordingFormat),
+    /// Update draft set recording split policy
+    UpdateRecordingSplit(RecordingSplit),
+    /// Update draft chapter-marker embedding for set recordings
+    UpdateRecordingChapters(bool),
+    /// Update draft stem track recording mode
+    UpdateRecordingStems(RecordingStems),
+    /// Update draft Auto DJ track source
+    UpdateAutoDjSource(AutoDjSource),
+    /// Update draft Auto DJ transition style
+    UpdateAutoDjTransition(AutoDjTransition),
+    /// Update draft Auto DJ transition length (bars)
+    UpdateAutoDjBars(u32),
+    /// Update draft Auto DJ start-on-launch
+    UpdateAutoDjStartOnLaunch(bool),
+    /// Select the sample pack (index into "None" + available packs); loads it immediately
+    UpdateSamplerPack(usize),
     /// Update draft slicer buffer bars
     UpdateSlicerBufferBars(u32),
     /// Update draft auto-gain enabled
@@ -180,6 +242,12 @@ pub enum Message {
     /// Set recording message
     Recording(RecordingMessage),
 
+    /// Auto DJ message
+    AutoDj(AutoDjMessage),
+
+    /// Sampler message
+    Sampler(SamplerMessage),
+
     /// Monitor size detected at startup (for auto-sizing)
     GotMonitorSize(Option<iced::Size>),
 
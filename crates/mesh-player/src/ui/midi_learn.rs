@@ -149,6 +149,8 @@ pub enum HighlightTarget {
     DeckLoopOut(usize),
     DeckBeatJumpBack(usize),
     DeckBeatJumpForward(usize),
+    DeckPhraseJumpBack(usize),
+    DeckPhraseJumpForward(usize),
     DeckSlip(usize),
     DeckKeyMatch(usize),
 
@@ -175,7 +177,9 @@ pub enum HighlightTarget {
     MixerEqHi(usize),
     MixerEqMid(usize),
     MixerEqLo(usize),
+    MixerEqHiMid(usize),
     MixerCue(usize),
+    MixerCrossfaderAssign(usize),
     MixerCrossfader,
 
     // Master section
@@ -225,6 +229,12 @@ impl HighlightTarget {
             HighlightTarget::DeckBeatJumpForward(d) => {
                 format!("Press BEAT JUMP FORWARD on deck {}", d + 1)
             }
+            HighlightTarget::DeckPhraseJumpBack(d) => {
+                format!("Press PHRASE JUMP BACK on deck {}", d + 1)
+            }
+            HighlightTarget::DeckPhraseJumpForward(d) => {
+                format!("Press PHRASE JUMP FORWARD on deck {}", d + 1)
+            }
             HighlightTarget::DeckSlip(d) => format!("Press SLIP button on deck {}", d + 1),
             HighlightTarget::DeckKeyMatch(d) => {
                 format!("Press KEY MATCH button on deck {}", d + 1)
@@ -279,9 +289,15 @@ impl HighlightTarget {
             HighlightTarget::MixerEqLo(ch) => {
                 format!("Turn EQ LOW knob on channel {}", ch + 1)
             }
+            HighlightTarget::MixerEqHiMid(ch) => {
+                format!("Turn EQ HIGH-MID knob on channel {}", ch + 1)
+            }
             HighlightTarget::MixerCue(ch) => {
                 format!("Press CUE (headphone) button on channel {}", ch + 1)
             }
+            HighlightTarget::MixerCrossfaderAssign(ch) => {
+                format!("Press CROSSFADER ASSIGN button on channel {}", ch + 1)
+            }
             HighlightTarget::MixerCrossfader => "Move the CROSSFADER".to_string(),
             HighlightTarget::MasterVolume => "Move the MASTER volume fader".to_string(),
             HighlightTarget::CueVolume => "Move the CUE/HEADPHONE volume knob".to_string(),
@@ -335,6 +351,8 @@ pub fn highlight_for_mapping(
         ("deck.loop_out", _, _) => Some(HighlightTarget::DeckLoopOut(d)),
         ("deck.beat_jump_backward", _, _) => Some(HighlightTarget::DeckBeatJumpBack(d)),
         ("deck.beat_jump_forward", _, _) => Some(HighlightTarget::DeckBeatJumpForward(d)),
+        ("deck.phrase_jump_backward", _, _) => Some(HighlightTarget::DeckPhraseJumpBack(d)),
+        ("deck.phrase_jump_forward", _, _) => Some(HighlightTarget::DeckPhraseJumpForward(d)),
         ("deck.slip", _, _) => Some(HighlightTarget::DeckSlip(d)),
         ("deck.key_match", _, _) => Some(HighlightTarget::DeckKeyMatch(d)),
         ("deck.hot_cue_mode", _, _) => Some(HighlightTarget::DeckHotCueMode(d)),
@@ -360,7 +378,9 @@ pub fn highlight_for_mapping(
         ("mixer.eq_hi", _, _) => Some(HighlightTarget::MixerEqHi(d)),
         ("mixer.eq_mid", _, _) => Some(HighlightTarget::MixerEqMid(d)),
         ("mixer.eq_lo", _, _) => Some(HighlightTarget::MixerEqLo(d)),
+        ("mixer.eq_hi_mid", _, _) => Some(HighlightTarget::MixerEqHiMid(d)),
         ("mixer.cue", _, _) => Some(HighlightTarget::MixerCue(d)),
+        ("mixer.crossfader_assign", _, _) => Some(HighlightTarget::MixerCrossfaderAssign(d)),
         ("mixer.crossfader", _, _) => Some(HighlightTarget::MixerCrossfader),
         ("mixer.cue_mix", _, _) => Some(HighlightTarget::CueMix),
         ("global.master_volume", _, _) => Some(HighlightTarget::MasterVolume),
@@ -885,7 +905,7 @@ impl MidiLearnState {
     pub fn generate_config(&self) -> MidiConfig {
         let tree = match &self.tree {
             Some(t) => t,
-            None => return MidiConfig { devices: vec![] },
+            None => return MidiConfig::default(),
         };
 
         let topology = &tree.topology;
@@ -935,7 +955,7 @@ impl MidiLearnState {
         }
 
         if device_groups.is_empty() {
-            return MidiConfig { devices: vec![] };
+            return MidiConfig::default();
         }
 
         // --- Build one DeviceProfile per device ---
@@ -1010,6 +1030,7 @@ impl MidiLearnState {
                     encoder_mode,
                     hardware_type: Some(ctrl.hardware_type),
                     mode: def.mode_condition.map(|s| s.to_string()),
+                    ticks_per_revolution: None,
                 };
 
                 let idx = mappings.len();
@@ -1166,6 +1187,7 @@ impl MidiLearnState {
                     encoder_mode,
                     hardware_type: hw_type,
                     mode: None,
+                    ticks_per_revolution: None,
                 });
             }
 
@@ -1289,6 +1311,7 @@ impl MidiLearnState {
                     encoder_mode,
                     hardware_type: hw_type,
                     mode: Some("browse".to_string()),
+                    ticks_per_revolution: None,
                 });
             }
 
@@ -1313,6 +1336,7 @@ impl MidiLearnState {
                     encoder_mode: None,
                     hardware_type: hw_type,
                     mode: Some("browse".to_string()),
+                    ticks_per_revolution: None,
                 });
             }
 
@@ -1342,6 +1366,7 @@ impl MidiLearnState {
                     encoder_mode: None,
                     hardware_type: *hw_type,
                     mode: Some("slicer".to_string()),
+                    ticks_per_revolution: None,
                 });
                 feedback.push(FeedbackMapping {
                     state: "deck.slicer_slice_active".to_string(),
@@ -1404,6 +1429,7 @@ impl MidiLearnState {
                         encoder_mode: None,
                         hardware_type: hw_type,
                         mode: None, // default/performance mode
+                        ticks_per_revolution: None,
                     });
                 }
             }
@@ -1557,7 +1583,7 @@ impl MidiLearnState {
             }
         }
 
-        MidiConfig { devices: profiles }
+        MidiConfig { devices: profiles, ..Default::default() }
     }
 
     /// Derive a device grouping key from a mapped control.
@@ -2750,6 +2776,7 @@ mod tests {
             encoder_mode: None,
             hardware_type: Some(HardwareType::Button),
             mode: None,
+            ticks_per_revolution: None,
         }
     }
 
@@ -2773,6 +2800,7 @@ mod tests {
                 momentary_mode_buttons: false,
                 color_note_offsets: None,
             }],
+            ..Default::default()
         }
     }
 
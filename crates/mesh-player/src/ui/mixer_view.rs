@@ -2,20 +2,54 @@
 //!
 //! Displays the 4-channel mixer with:
 //! - Per-channel volume faders
-//! - Per-channel EQ (hi/mid/lo)
+//! - Per-channel EQ (hi/mid/lo, plus hi-mid on decks using the 4-band model)
 //! - Per-channel filter
 //! - Master/cue volume
 //! - Cue select buttons
-//!
-//! Note: No crossfader - Mesh uses stem-based mixing with global BPM sync
+//! - Crossfader with per-channel A/THRU/B assign buttons
+//! - Mic/aux line input strips (trim, EQ, volume, cue, talkover) when enabled
+//! - FX 1/FX 2 send levels per channel and an FX bus row (on/off, return,
+//!   4 macro knobs) when effects are loaded on the buses
 
 use iced::widget::{button, column, container, row, slider, text, Row};
 use iced::{Center, Color, Element};
 
-use mesh_core::engine::Mixer;
+use mesh_core::config::{CrossfaderAssign, EqModel};
+use mesh_core::effect::EffectInfo;
+use mesh_core::engine::{FxBus, LineInput, Mixer, NUM_FX_SENDS, NUM_LINE_INPUTS};
 use mesh_widgets::sz;
 use super::midi_learn::HighlightTarget;
 
+/// Knobs shown per FX bus (mapped in order onto the chain's parameters)
+pub const FX_BUS_KNOBS: usize = 4;
+
+/// UI state of one mixer FX bus
+#[derive(Debug, Clone)]
+struct FxBusView {
+    /// Parameter count of each loaded effect, in chain order
+    effect_params: Vec<usize>,
+    /// Name of each loaded effect
+    effect_names: Vec<String>,
+    /// Chain on/off
+    enabled: bool,
+    /// Return level into master (send buses only)
+    return_level: f32,
+    /// Macro knob values (0-1)
+    knobs: [f32; FX_BUS_KNOBS],
+}
+
+impl Default for FxBusView {
+    fn default() -> Self {
+        Self {
+            effect_params: Vec::new(),
+            effect_names: Vec::new(),
+            enabled: true,
+            return_level: 1.0,
+            knobs: [0.0; FX_BUS_KNOBS],
+        }
+    }
+}
+
 /// State for the mixer view
 pub struct MixerView {
     /// Channel volumes (0-1)
@@ -28,6 +62,10 @@ pub struct MixerView {
     channel_eq_mid: [f32; 4],
     /// Channel EQ lo (0-1)
     channel_eq_lo: [f32; 4],
+    /// Channel EQ hi-mid (0-1, 4-band model only)
+    channel_eq_hi_mid: [f32; 4],
+    /// EQ model per channel (decides which EQ knobs are shown)
+    channel_eq_model: [EqModel; 4],
     /// Cue enabled per channel
     channel_cue: [bool; 4],
     /// Master volume
@@ -36,6 +74,30 @@ pub struct MixerView {
     cue_volume: f32,
     /// Cue/master mix for headphones
     cue_mix: f32,
+    /// Crossfader position (-1 = A, 0 = center, 1 = B)
+    crossfader: f32,
+    /// Crossfader side assignment per channel
+    crossfader_assign: [CrossfaderAssign; 4],
+    /// Line inputs opened at startup (strips are hidden otherwise)
+    line_available: [bool; NUM_LINE_INPUTS],
+    /// Line input volumes (0-1)
+    line_volumes: [f32; NUM_LINE_INPUTS],
+    /// Line input trim knobs (0-1, 0.5 = unity)
+    line_trim: [f32; NUM_LINE_INPUTS],
+    /// Line input EQ hi (0-1)
+    line_eq_hi: [f32; NUM_LINE_INPUTS],
+    /// Line input EQ mid (0-1)
+    line_eq_mid: [f32; NUM_LINE_INPUTS],
+    /// Line input EQ lo (0-1)
+    line_eq_lo: [f32; NUM_LINE_INPUTS],
+    /// Cue enabled per line input
+    line_cue: [bool; NUM_LINE_INPUTS],
+    /// Talkover enabled per line input
+    talkover: [bool; NUM_LINE_INPUTS],
+    /// FX send levels per channel (0-1)
+    fx_send: [[f32; NUM_FX_SENDS]; 4],
+    /// FX 1, FX 2 and master insert bus state
+    fx_buses: [FxBusView; 3],
     /// Current highlight target for MIDI learn mode
     highlight_target: Option<HighlightTarget>,
 }
@@ -53,6 +115,8 @@ pub enum MixerMessage {
     SetChannelEqMid(usize, f32),
     /// Set channel EQ lo
     SetChannelEqLo(usize, f32),
+    /// Set channel EQ hi-mid (4-band model)
+    SetChannelEqHiMid(usize, f32),
     /// Toggle channel cue
     ToggleChannelCue(usize),
     /// Set master volume
@@ -61,6 +125,32 @@ pub enum MixerMessage {
     SetCueVolume(f32),
     /// Set cue/master mix
     SetCueMix(f32),
+    /// Set crossfader position (-1 = A, 0 = center, 1 = B)
+    SetCrossfader(f32),
+    /// Cycle a channel's crossfader assignment (A → THRU → B)
+    CycleCrossfaderAssign(usize),
+    /// Set line input volume
+    SetLineVolume(LineInput, f32),
+    /// Set line input trim knob (0 = -24 dB, 0.5 = unity, 1 = +12 dB)
+    SetLineTrim(LineInput, f32),
+    /// Set line input EQ hi
+    SetLineEqHi(LineInput, f32),
+    /// Set line input EQ mid
+    SetLineEqMid(LineInput, f32),
+    /// Set line input EQ lo
+    SetLineEqLo(LineInput, f32),
+    /// Toggle line input cue
+    ToggleLineCue(LineInput),
+    /// Toggle line input talkover
+    ToggleTalkover(LineInput),
+    /// Set a channel's send level to FX 1 or FX 2 (channel, send, level)
+    SetFxSend(usize, usize, f32),
+    /// Set an FX bus return level into master
+    SetFxReturn(FxBus, f32),
+    /// Turn an FX bus on or off
+    ToggleFxBus(FxBus),
+    /// Set one of an FX bus's macro knobs (bus, knob, value)
+    SetFxKnob(FxBus, usize, f32),
 }
 
 impl MixerView {
@@ -72,10 +162,24 @@ impl MixerView {
             channel_eq_hi: [0.5; 4],
             channel_eq_mid: [0.5; 4],
             channel_eq_lo: [0.5; 4],
+            channel_eq_hi_mid: [0.5; 4],
+            channel_eq_model: [EqModel::default(); 4],
             channel_cue: [false; 4],
             master_volume: 0.8,
             cue_volume: 0.8,
             cue_mix: 0.0,
+            crossfader: 0.0,
+            crossfader_assign: [CrossfaderAssign::Thru; 4],
+            line_available: [false; NUM_LINE_INPUTS],
+            line_volumes: [0.0; NUM_LINE_INPUTS],
+            line_trim: [0.5; NUM_LINE_INPUTS],
+            line_eq_hi: [0.5; NUM_LINE_INPUTS],
+            line_eq_mid: [0.5; NUM_LINE_INPUTS],
+            line_eq_lo: [0.5; NUM_LINE_INPUTS],
+            line_cue: [false; NUM_LINE_INPUTS],
+            talkover: [false; NUM_LINE_INPUTS],
+            fx_send: [[0.0; NUM_FX_SENDS]; 4],
+            fx_buses: Default::default(),
             highlight_target: None,
         }
     }
@@ -106,8 +210,10 @@ impl MixerView {
                 self.channel_volumes[i] = ch.volume;
                 self.channel_filters[i] = ch.filter;
                 self.channel_cue[i] = ch.cue_enabled;
+                self.crossfader_assign[i] = ch.crossfader_assign;
             }
         }
+        self.crossfader = mixer.crossfader();
         self.master_volume = mixer.master_volume();
         // cue_volume not separate in mixer yet - use cue_mix
         self.cue_mix = mixer.cue_mix();
@@ -147,6 +253,12 @@ impl MixerView {
                     channel.set_eq_lo(val);
                 }
             }
+            MixerMessage::SetChannelEqHiMid(ch, val) => {
+                self.channel_eq_hi_mid[ch] = val;
+                if let Some(channel) = mixer.channel_mut(ch) {
+                    channel.set_eq_hi_mid(val);
+                }
+            }
             MixerMessage::ToggleChannelCue(ch) => {
                 self.channel_cue[ch] = !self.channel_cue[ch];
                 if let Some(channel) = mixer.channel_mut(ch) {
@@ -165,6 +277,65 @@ impl MixerView {
                 self.cue_mix = mix;
                 mixer.set_cue_mix(mix);
             }
+            MixerMessage::SetCrossfader(pos) => {
+                self.crossfader = pos;
+                mixer.set_crossfader(pos);
+            }
+            MixerMessage::CycleCrossfaderAssign(ch) => {
+                if let Some(assign) = self.crossfader_assign.get_mut(ch) {
+                    *assign = assign.cycle();
+                    mixer.set_crossfader_assign(ch, *assign);
+                }
+            }
+            MixerMessage::SetLineVolume(input, vol) => {
+                self.line_volumes[input.index()] = vol;
+                mixer.line_channel_mut(input).volume = vol;
+            }
+            MixerMessage::SetLineTrim(input, val) => {
+                self.line_trim[input.index()] = val;
+                mixer.line_channel_mut(input).set_trim_db(mesh_core::engine::trim_db_from_knob(val));
+            }
+            MixerMessage::SetLineEqHi(input, val) => {
+                self.line_eq_hi[input.index()] = val;
+                mixer.line_channel_mut(input).set_eq_hi(val);
+            }
+            MixerMessage::SetLineEqMid(input, val) => {
+                self.line_eq_mid[input.index()] = val;
+                mixer.line_channel_mut(input).set_eq_mid(val);
+            }
+            MixerMessage::SetLineEqLo(input, val) => {
+                self.line_eq_lo[input.index()] = val;
+                mixer.line_channel_mut(input).set_eq_lo(val);
+            }
+            MixerMessage::ToggleLineCue(input) => {
+                self.line_cue[input.index()] = !self.line_cue[input.index()];
+                mixer.line_channel_mut(input).cue_enabled = self.line_cue[input.index()];
+            }
+            MixerMessage::ToggleTalkover(input) => {
+                self.talkover[input.index()] = !self.talkover[input.index()];
+                mixer.set_talkover(input, self.talkover[input.index()]);
+            }
+            MixerMessage::SetFxSend(ch, send, level) => {
+                self.fx_send[ch][send] = level;
+                mixer.set_send_level(ch, send, level);
+            }
+            MixerMessage::SetFxReturn(bus, level) => {
+                self.fx_buses[bus.index()].return_level = level;
+                mixer.fx_chain_mut(bus).set_return_level(level);
+            }
+            MixerMessage::ToggleFxBus(bus) => {
+                let state = &mut self.fx_buses[bus.index()];
+                state.enabled = !state.enabled;
+                mixer.fx_chain_mut(bus).set_enabled(state.enabled);
+            }
+            MixerMessage::SetFxKnob(bus, knob, value) => {
+                if let Some(slot) = self.fx_buses[bus.index()].knobs.get_mut(knob) {
+                    *slot = value;
+                }
+                if let Some((effect, param)) = self.fx_knob_target(bus, knob) {
+                    mixer.fx_chain_mut(bus).set_effect_param(effect, param, value);
+                }
+            }
         }
     }
 
@@ -186,6 +357,9 @@ impl MixerView {
             MixerMessage::SetChannelEqLo(ch, val) => {
                 self.channel_eq_lo[ch] = val;
             }
+            MixerMessage::SetChannelEqHiMid(ch, val) => {
+                self.channel_eq_hi_mid[ch] = val;
+            }
             MixerMessage::ToggleChannelCue(ch) => {
                 self.channel_cue[ch] = !self.channel_cue[ch];
             }
@@ -198,9 +372,116 @@ impl MixerView {
             MixerMessage::SetCueMix(mix) => {
                 self.cue_mix = mix;
             }
+            MixerMessage::SetCrossfader(pos) => {
+                self.crossfader = pos;
+            }
+            MixerMessage::CycleCrossfaderAssign(ch) => {
+                if let Some(assign) = self.crossfader_assign.get_mut(ch) {
+                    *assign = assign.cycle();
+                }
+            }
+            MixerMessage::SetLineVolume(input, vol) => {
+                self.line_volumes[input.index()] = vol;
+            }
+            MixerMessage::SetLineTrim(input, val) => {
+                self.line_trim[input.index()] = val;
+            }
+            MixerMessage::SetLineEqHi(input, val) => {
+                self.line_eq_hi[input.index()] = val;
+            }
+            MixerMessage::SetLineEqMid(input, val) => {
+                self.line_eq_mid[input.index()] = val;
+            }
+            MixerMessage::SetLineEqLo(input, val) => {
+                self.line_eq_lo[input.index()] = val;
+            }
+            MixerMessage::ToggleLineCue(input) => {
+                self.line_cue[input.index()] = !self.line_cue[input.index()];
+            }
+            MixerMessage::ToggleTalkover(input) => {
+                self.talkover[input.index()] = !self.talkover[input.index()];
+            }
+            MixerMessage::SetFxSend(ch, send, level) => {
+                self.fx_send[ch][send] = level;
+            }
+            MixerMessage::SetFxReturn(bus, level) => {
+                self.fx_buses[bus.index()].return_level = level;
+            }
+            MixerMessage::ToggleFxBus(bus) => {
+                let state = &mut self.fx_buses[bus.index()];
+                state.enabled = !state.enabled;
+            }
+            MixerMessage::SetFxKnob(bus, knob, value) => {
+                if let Some(slot) = self.fx_buses[bus.index()].knobs.get_mut(knob) {
+                    *slot = value;
+                }
+            }
+        }
+    }
+
+    /// Show or hide the strip of a line input (local UI state, e.g. from config)
+    pub fn set_line_available(&mut self, input: LineInput, available: bool) {
+        self.line_available[input.index()] = available;
+    }
+
+    /// Get cue enabled state for a line input
+    pub fn line_cue_enabled(&self, input: LineInput) -> bool {
+        self.line_cue[input.index()]
+    }
+
+    /// Get talkover state for a line input
+    pub fn talkover(&self, input: LineInput) -> bool {
+        self.talkover[input.index()]
+    }
+
+    /// Register an effect loaded onto an FX bus (local UI state, e.g. from config)
+    ///
+    /// Knob values start at the effect's current parameter values.
+    pub fn add_fx_bus_effect(&mut self, bus: FxBus, info: &EffectInfo, params: &[f32]) {
+        let first_param: usize = self.fx_buses[bus.index()].effect_params.iter().sum();
+        let state = &mut self.fx_buses[bus.index()];
+        state.effect_names.push(info.name.clone());
+        state.effect_params.push(info.param_count());
+        for (i, param) in info.params.iter().enumerate() {
+            if let Some(knob) = state.knobs.get_mut(first_param + i) {
+                *knob = params.get(i).copied().unwrap_or(param.default);
+            }
         }
     }
 
+    /// Set a channel's EQ model (local UI state only, from config)
+    pub fn set_eq_model(&mut self, ch: usize, model: EqModel) {
+        if let Some(slot) = self.channel_eq_model.get_mut(ch) {
+            *slot = model;
+        }
+    }
+
+    /// Set an FX bus return level (local UI state only, e.g. from config)
+    pub fn set_fx_return(&mut self, bus: FxBus, level: f32) {
+        self.fx_buses[bus.index()].return_level = level;
+    }
+
+    /// Whether an FX bus is on
+    pub fn fx_bus_enabled(&self, bus: FxBus) -> bool {
+        self.fx_buses[bus.index()].enabled
+    }
+
+    /// Effect and parameter index a bus macro knob controls
+    ///
+    /// Knobs walk the chain's parameters in order: with a 4-parameter delay
+    /// followed by a reverb, knobs 1-4 drive the delay. `None` when the
+    /// chain has fewer parameters than the knob index.
+    pub fn fx_knob_target(&self, bus: FxBus, knob: usize) -> Option<(usize, usize)> {
+        let mut remaining = knob;
+        for (effect, &count) in self.fx_buses[bus.index()].effect_params.iter().enumerate() {
+            if remaining < count {
+                return Some((effect, remaining));
+            }
+            remaining -= count;
+        }
+        None
+    }
+
     /// Get cue enabled state for a channel
     pub fn cue_enabled(&self, ch: usize) -> bool {
         self.channel_cue.get(ch).copied().unwrap_or(false)
@@ -211,6 +492,21 @@ impl MixerView {
         self.channel_volumes.get(ch).copied().unwrap_or(1.0)
     }
 
+    /// Get the crossfader assignment for a channel
+    pub fn crossfader_assign(&self, ch: usize) -> CrossfaderAssign {
+        self.crossfader_assign.get(ch).copied().unwrap_or_default()
+    }
+
+    /// Get all crossfader assignments (for persisting to config)
+    pub fn crossfader_assignments(&self) -> [CrossfaderAssign; 4] {
+        self.crossfader_assign
+    }
+
+    /// Set all crossfader assignments (local UI state only, e.g. from config)
+    pub fn set_crossfader_assignments(&mut self, assignments: [CrossfaderAssign; 4]) {
+        self.crossfader_assign = assignments;
+    }
+
     /// Set cue enabled state for a channel (local UI state only)
     pub fn set_cue_enabled(&mut self, ch: usize, enabled: bool) {
         if ch < 4 {
@@ -226,14 +522,22 @@ impl MixerView {
     /// │ CH1   CH2   CH3   CH4          │ MASTER    CUE   │
     /// │ (4 channel strips)             │ MIX             │
     /// │ ~75%                           │ ~25%            │
-    /// └────────────────────────────────┴─────────────────┘
+    /// ├────────────────────────────────┴─────────────────┤
+    /// │ A ──────────── XFADER ──────────── B             │
+    /// └──────────────────────────────────────────────────┘
     /// ```
     pub fn view(&self) -> Element<'_, MixerMessage> {
         use iced::Length;
 
-        // Channel strips column (~75%)
+        // Channel strips column (~75%), line inputs after the decks
         let channels: Vec<Element<MixerMessage>> = (0..4)
             .map(|i| self.view_channel(i))
+            .chain(
+                LineInput::ALL
+                    .into_iter()
+                    .filter(|input| self.line_available[input.index()])
+                    .map(|input| self.view_line_input(input)),
+            )
             .collect();
 
         let channels_section = container(
@@ -309,12 +613,48 @@ impl MixerView {
         )
         .width(Length::FillPortion(25));
 
-        let content = row![
-            channels_section,
-            master_cue_section,
+        // Crossfader (full width below channels)
+        let xfader_slider = slider(-1.0..=1.0, self.crossfader, MixerMessage::SetCrossfader)
+            .step(0.01)
+            .width(Length::Fill);
+        let xfader_elem: Element<_> = if self.is_highlighted(HighlightTarget::MixerCrossfader) {
+            container(xfader_slider)
+                .style(|_| container::Style {
+                    border: Self::highlight_border(),
+                    ..Default::default()
+                })
+                .into()
+        } else {
+            xfader_slider.into()
+        };
+        let xfader = row![
+            text("A").size(sz(10.0)),
+            xfader_elem,
+            text("B").size(sz(10.0)),
         ]
+        .spacing(8)
         .align_y(Center);
 
+        let mut content = column![
+            row![
+                channels_section,
+                master_cue_section,
+            ]
+            .align_y(Center),
+            xfader,
+        ]
+        .spacing(6);
+
+        // FX bus row (only buses with effects loaded)
+        let buses: Vec<Element<MixerMessage>> = FxBus::ALL
+            .into_iter()
+            .filter(|bus| !self.fx_buses[bus.index()].effect_names.is_empty())
+            .map(|bus| self.view_fx_bus(bus))
+            .collect();
+        if !buses.is_empty() {
+            content = content.push(Row::with_children(buses).spacing(12));
+        }
+
         container(content)
             .padding(8)
             .width(Length::Fill)
@@ -352,12 +692,41 @@ impl MixerView {
                 .into()
         };
 
+        // High-mid band, only on the 4-band model (where MID becomes low-mid)
+        let four_band = self.channel_eq_model[ch] == EqModel::FourBand;
+        let eq_hi_mid: Option<Element<'_, MixerMessage>> = four_band.then(|| {
+            let eq_hi_mid_slider = slider(0.0..=1.0, self.channel_eq_hi_mid[ch], move |v| MixerMessage::SetChannelEqHiMid(ch, v))
+                .step(0.01)
+                .width(Length::Fill);
+            if self.is_highlighted(HighlightTarget::MixerEqHiMid(ch)) {
+                column![
+                    text("HI-MID").size(sz(9.0)),
+                    container(eq_hi_mid_slider)
+                        .style(|_| container::Style {
+                            border: Self::highlight_border(),
+                            ..Default::default()
+                        }),
+                ]
+                .spacing(2)
+                .align_x(Center)
+                .width(Length::Fill)
+                .into()
+            } else {
+                column![text("HI-MID").size(sz(9.0)), eq_hi_mid_slider]
+                    .spacing(2)
+                    .align_x(Center)
+                    .width(Length::Fill)
+                    .into()
+            }
+        });
+        let mid_label = if four_band { "LO-MID" } else { "MID" };
+
         let eq_mid_slider = slider(0.0..=1.0, self.channel_eq_mid[ch], move |v| MixerMessage::SetChannelEqMid(ch, v))
             .step(0.01)
             .width(Length::Fill);
         let eq_mid: Element<'_, MixerMessage> = if self.is_highlighted(HighlightTarget::MixerEqMid(ch)) {
             column![
-                text("MID").size(sz(9.0)),
+                text(mid_label).size(sz(9.0)),
                 container(eq_mid_slider)
                     .style(|_| container::Style {
                         border: Self::highlight_border(),
@@ -369,7 +738,7 @@ impl MixerView {
             .width(Length::Fill)
             .into()
         } else {
-            column![text("MID").size(sz(9.0)), eq_mid_slider]
+            column![text(mid_label).size(sz(9.0)), eq_mid_slider]
                 .spacing(2)
                 .align_x(Center)
                 .width(Length::Fill)
@@ -467,20 +836,154 @@ impl MixerView {
             cue_btn.into()
         };
 
+        // Crossfader assign button (cycles A → THRU → B)
+        let assign_btn = button(text(self.crossfader_assign(ch).display_name()).size(sz(10.0)))
+            .on_press(MixerMessage::CycleCrossfaderAssign(ch))
+            .padding([4, 8])
+            .width(Length::Fill);
+        let assign: Element<'_, MixerMessage> = if self.is_highlighted(HighlightTarget::MixerCrossfaderAssign(ch)) {
+            container(assign_btn)
+                .style(|_| container::Style {
+                    border: Self::highlight_border(),
+                    ..Default::default()
+                })
+                .into()
+        } else {
+            assign_btn.into()
+        };
+
+        // FX sends (post-fader)
+        let sends = Row::with_children((0..NUM_FX_SENDS).map(|send| {
+            column![
+                text(format!("FX{}", send + 1)).size(sz(9.0)),
+                slider(0.0..=1.0, self.fx_send[ch][send], move |v| MixerMessage::SetFxSend(ch, send, v))
+                    .step(0.01)
+                    .width(Length::Fill),
+            ]
+            .spacing(2)
+            .align_x(Center)
+            .width(Length::Fill)
+            .into()
+        }))
+        .spacing(4);
+
+        let mut strip = column![ch_label, eq_hi];
+        if let Some(eq_hi_mid) = eq_hi_mid {
+            strip = strip.push(eq_hi_mid);
+        }
+        strip
+            .push(eq_mid)
+            .push(eq_lo)
+            .push(filter)
+            .push(sends)
+            .push(volume)
+            .push(cue)
+            .push(assign)
+            .spacing(4)
+            .align_x(Center)
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// View for a mic/aux line input strip
+    ///
+    /// Same layout as a deck channel, with trim in place of the filter and
+    /// talkover in place of the crossfader assign.
+    fn view_line_input(&self, input: LineInput) -> Element<'_, MixerMessage> {
+        use iced::Length;
+
+        let i = input.index();
+        let labeled = |label: &'static str, value: f32, on_change: fn(LineInput, f32) -> MixerMessage| {
+            column![
+                text(label).size(sz(9.0)),
+                slider(0.0..=1.0, value, move |v| on_change(input, v))
+                    .step(0.01)
+                    .width(Length::Fill),
+            ]
+            .spacing(2)
+            .align_x(Center)
+            .width(Length::Fill)
+        };
+
+        let cue_label = if self.line_cue[i] { "CUE ●" } else { "CUE" };
+        let cue = button(text(cue_label).size(sz(10.0)))
+            .on_press(MixerMessage::ToggleLineCue(input))
+            .padding([4, 8])
+            .width(Length::Fill);
+        let talk_label = if self.talkover[i] { "TALK ●" } else { "TALK" };
+        let talkover = button(text(talk_label).size(sz(10.0)))
+            .on_press(MixerMessage::ToggleTalkover(input))
+            .padding([4, 8])
+            .width(Length::Fill);
+
         column![
-            ch_label,
-            eq_hi,
-            eq_mid,
-            eq_lo,
-            filter,
-            volume,
+            text(input.label()).size(sz(11.0)),
+            labeled("HI", self.line_eq_hi[i], MixerMessage::SetLineEqHi),
+            labeled("MID", self.line_eq_mid[i], MixerMessage::SetLineEqMid),
+            labeled("LO", self.line_eq_lo[i], MixerMessage::SetLineEqLo),
+            labeled("TRIM", self.line_trim[i], MixerMessage::SetLineTrim),
+            labeled("VOL", self.line_volumes[i], MixerMessage::SetLineVolume),
             cue,
+            talkover,
         ]
         .spacing(4)
         .align_x(Center)
         .width(Length::Fill)
         .into()
     }
+
+    /// View for one FX bus: name, on/off, return (send buses) and macro knobs
+    fn view_fx_bus(&self, bus: FxBus) -> Element<'_, MixerMessage> {
+        use iced::Length;
+
+        let state = &self.fx_buses[bus.index()];
+        let on_label = if state.enabled { "ON ●" } else { "ON" };
+        let header = row![
+            text(bus.label()).size(sz(10.0)),
+            text(state.effect_names.join(" › ")).size(sz(9.0)),
+            button(text(on_label).size(sz(9.0)))
+                .on_press(MixerMessage::ToggleFxBus(bus))
+                .padding([2, 6]),
+        ]
+        .spacing(6)
+        .align_y(Center);
+
+        let mut knobs = Row::new().spacing(4);
+        if bus.send_index().is_some() {
+            knobs = knobs.push(
+                column![
+                    text("RET").size(sz(9.0)),
+                    slider(0.0..=1.0, state.return_level, move |v| MixerMessage::SetFxReturn(bus, v))
+                        .step(0.01)
+                        .width(Length::Fill),
+                ]
+                .spacing(2)
+                .align_x(Center)
+                .width(Length::Fill),
+            );
+        }
+        for knob in 0..FX_BUS_KNOBS {
+            if self.fx_knob_target(bus, knob).is_none() {
+                break;
+            }
+            knobs = knobs.push(
+                column![
+                    text(format!("{}", knob + 1)).size(sz(9.0)),
+                    slider(0.0..=1.0, state.knobs[knob], move |v| MixerMessage::SetFxKnob(bus, knob, v))
+                        .step(0.01)
+                        .width(Length::Fill),
+                ]
+                .spacing(2)
+                .align_x(Center)
+                .width(Length::Fill),
+            );
+        }
+
+        column![header, knobs]
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 }
 
 impl Default for MixerView {
@@ -6,12 +6,12 @@
 //! Both the view rendering and MIDI navigation derive from this list — no hardcoded
 //! indices, no manual counts. Adding a setting = adding one item to the vec.
 
-use super::message::{Message, SettingsMessage};
+use super::message::{AutoDjMessage, Message, SamplerMessage, SettingsMessage};
 use super::midi_learn::MidiLearnMessage;
 use super::network::NetworkState;
 use super::system_update::UpdateState;
 use crate::audio::{get_available_stereo_pairs, StereoPair};
-use crate::config::{AppFont, FontSize, LOOP_LENGTH_OPTIONS, KeyScoringModel, SuggestionBlendMode, SuggestionKeyFilter, SuggestionTransitionReach, WaveformAbstraction, WaveformLayout};
+use crate::config::{AppFont, AutoDjSource, AutoDjTransition, AUTO_DJ_TRANSITION_BARS, CrossfaderCurve, EqModel, FontSize, RecordingFormat, RecordingSplit, RecordingStems, LOOP_LENGTH_OPTIONS, KeyScoringModel, SuggestionBlendMode, SuggestionKeyFilter, SuggestionTransitionReach, WaveformAbstraction, WaveformLayout};
 use iced::widget::{button, column, container, pick_list, row, scrollable, text, toggler, Id, Space};
 use iced::{Alignment, Color, Element, Length};
 use mesh_widgets::sz;
@@ -189,12 +189,123 @@ pub fn build_settings_items(state: &SettingsState) -> Vec<SettingsItem> {
         Message::Settings(SettingsMessage::RecordingConfirm),
     ))
         .section("Recording")
-        .hint("Record master output to WAV on all connected USB sticks");
+        .hint("Record master output on all connected USB sticks");
     if state.recording_active {
         rec_item = rec_item.danger();
     }
     items.push(rec_item);
 
+    items.extend([
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: RecordingFormat::ALL.iter().map(|f| f.display_name().to_string()).collect(),
+            selected: RecordingFormat::ALL.iter().position(|&f| f == state.draft_recording_format).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateRecordingFormat(RecordingFormat::ALL[idx.min(RecordingFormat::ALL.len() - 1)]),
+        })
+            .subsection("File Format")
+            .subsection_hint("FLAC is lossless at about half the size; 32f keeps peaks above 0 dB")
+            .button_width(ButtonWidth::Fixed(72.0)),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: RecordingSplit::ALL.iter().map(|s| s.display_name().to_string()).collect(),
+            selected: RecordingSplit::ALL.iter().position(|&s| s == state.draft_recording_split).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateRecordingSplit(RecordingSplit::ALL[idx.min(RecordingSplit::ALL.len() - 1)]),
+        })
+            .subsection("Split Files")
+            .subsection_hint("Continue long sets in numbered parts (4 GB keeps files FAT32-safe)")
+            .button_width(ButtonWidth::Fixed(64.0)),
+
+        SettingsItem::new("Embed Chapter Markers", SettingsBehavior::Toggle {
+            value: state.draft_recording_chapters,
+            on_toggle: |v| SettingsMessage::UpdateRecordingChapters(v),
+        })
+            .hint("Write the tracklist into the recording as chapters (CUE and JSON tracklists are always saved)"),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: RecordingStems::ALL.iter().map(|s| s.display_name().to_string()).collect(),
+            selected: RecordingStems::ALL.iter().position(|&s| s == state.draft_recording_stems).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateRecordingStems(RecordingStems::ALL[idx.min(RecordingStems::ALL.len() - 1)]),
+        })
+            .subsection("Stem Tracks")
+            .subsection_hint("Also record all vocals/drums/bass/other for remixing (uses more CPU)")
+            .button_width(ButtonWidth::Fixed(80.0)),
+    ]);
+
+    // ── Auto DJ ──
+    let auto_dj_label = if state.auto_dj_active { "Stop Auto DJ" } else { "Start Auto DJ" };
+    let mut auto_dj_item = SettingsItem::new(auto_dj_label, SettingsBehavior::Action(
+        Message::AutoDj(AutoDjMessage::Toggle),
+    ))
+        .section("Auto DJ")
+        .hint("Pick, load and mix the next track automatically");
+    if state.auto_dj_active {
+        auto_dj_item = auto_dj_item.danger();
+    }
+    items.push(auto_dj_item);
+
+    items.extend([
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: AutoDjSource::ALL.iter().map(|s| s.display_name().to_string()).collect(),
+            selected: AutoDjSource::ALL.iter().position(|&s| s == state.draft_auto_dj_source).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateAutoDjSource(AutoDjSource::ALL[idx.min(AutoDjSource::ALL.len() - 1)]),
+        })
+            .subsection("Next Track")
+            .subsection_hint("Playlist plays the open browser playlist in order, then continues with suggestions")
+            .button_width(ButtonWidth::Fixed(100.0)),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: AutoDjTransition::ALL.iter().map(|t| t.display_name().to_string()).collect(),
+            selected: AutoDjTransition::ALL.iter().position(|&t| t == state.draft_auto_dj_transition).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateAutoDjTransition(AutoDjTransition::ALL[idx.min(AutoDjTransition::ALL.len() - 1)]),
+        })
+            .subsection("Transition")
+            .subsection_hint("Stems brings the next track in stem by stem while the current one drops out")
+            .button_width(ButtonWidth::Fixed(80.0)),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: AUTO_DJ_TRANSITION_BARS.iter().map(|b| b.to_string()).collect(),
+            selected: AUTO_DJ_TRANSITION_BARS.iter().position(|&b| b == state.draft_auto_dj_bars).unwrap_or(2),
+            on_select: |idx| SettingsMessage::UpdateAutoDjBars(AUTO_DJ_TRANSITION_BARS[idx.min(AUTO_DJ_TRANSITION_BARS.len() - 1)]),
+        })
+            .subsection("Transition Length")
+            .prefix("Bars:")
+            .button_width(ButtonWidth::Fixed(50.0)),
+
+        SettingsItem::new("Start on Launch", SettingsBehavior::Toggle {
+            value: state.draft_auto_dj_start_on_launch,
+            on_toggle: |v| SettingsMessage::UpdateAutoDjStartOnLaunch(v),
+        })
+            .hint("Begin playing automatically after startup (unattended venue playback)"),
+    ]);
+
+    // ── Sampler ──
+    let pack_options: Vec<String> = std::iter::once("None".to_string())
+        .chain(state.available_sampler_packs.iter().cloned())
+        .collect();
+    let selected_pack = state.draft_sampler_pack.as_ref()
+        .and_then(|p| state.available_sampler_packs.iter().position(|a| a == p))
+        .map_or(0, |i| i + 1);
+    items.extend([
+        SettingsItem::new("Stop All Samples", SettingsBehavior::Action(
+            Message::Sampler(SamplerMessage::StopAll),
+        ))
+            .section("Sampler")
+            .hint("Silence every one-shot and loop slot"),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: pack_options,
+            selected: selected_pack,
+            on_select: |idx| SettingsMessage::UpdateSamplerPack(idx),
+        })
+            .subsection("Sample Pack")
+            .subsection_hint("Folders in samples/ of the collection; loose audio files fill the slots in name order")
+            .button_width(ButtonWidth::Shrink),
+
+        SettingsItem::new("Save Pack", SettingsBehavior::Action(
+            Message::Sampler(SamplerMessage::SavePack),
+        ))
+            .hint("Write the loaded slots, including deck captures, to the selected pack (or a new dated one)"),
+    ]);
+
     // ── Power Off (embedded only, first item for quick access) ──
     #[cfg(feature = "embedded-rt")]
     items.push(
@@ -237,6 +348,30 @@ pub fn build_settings_items(state: &SettingsState) -> Vec<SettingsItem> {
         })
             .hint("Route low-volume decks to headphones automatically (requires separate cue output)"),
 
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: CrossfaderCurve::ALL.iter().map(|c| c.display_name().to_string()).collect(),
+            selected: CrossfaderCurve::ALL.iter().position(|&c| c == state.draft_crossfader_curve).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateCrossfaderCurve(CrossfaderCurve::ALL[idx.min(CrossfaderCurve::ALL.len() - 1)]),
+        })
+            .subsection("Crossfader Curve")
+            .subsection_hint("Smooth for long blends, Scratch for fast cuts at the fader edges")
+            .button_width(ButtonWidth::Fixed(80.0)),
+
+        SettingsItem::new("Hamster (Reverse Crossfader)", SettingsBehavior::Toggle {
+            value: state.draft_crossfader_reverse,
+            on_toggle: |v| SettingsMessage::UpdateCrossfaderReverse(v),
+        })
+            .hint("Swap crossfader direction (left = B, right = A)"),
+
+        SettingsItem::new("", SettingsBehavior::ButtonGroup {
+            options: EqModel::ALL.iter().map(|m| m.display_name().to_string()).collect(),
+            selected: EqModel::ALL.iter().position(|&m| m == state.draft_eq_model).unwrap_or(0),
+            on_select: |idx| SettingsMessage::UpdateEqModel(EqModel::ALL[idx.min(EqModel::ALL.len() - 1)]),
+        })
+            .subsection("Channel EQ")
+            .subsection_hint("Classic shelving EQ, or isolators that fully kill each band (4-Band adds a HI-MID knob)")
+            .button_width(ButtonWidth::Fixed(80.0)),
+
         SettingsItem::new("", SettingsBehavior::ButtonGroup {
             options: LOOP_LENGTH_OPTIONS.iter().map(|&b| format_beats(b)).collect(),
             selected: state.draft_loop_length_index,
@@ -465,6 +600,32 @@ pub struct SettingsState {
     pub draft_phase_sync: bool,
     /// Draft auto-cue enabled (routes low-volume decks to headphone output)
     pub draft_auto_cue: bool,
+    /// Draft crossfader curve
+    pub draft_crossfader_curve: CrossfaderCurve,
+    /// Draft crossfader reverse (hamster mode)
+    pub draft_crossfader_reverse: bool,
+    /// Draft channel EQ model
+    pub draft_eq_model: EqModel,
+    /// Draft set recording file format
+    pub draft_recording_format: RecordingFormat,
+    /// Draft set recording split policy
+    pub draft_recording_split: RecordingSplit,
+    /// Draft chapter-marker embedding for set recordings
+    pub draft_recording_chapters: bool,
+    /// Draft stem track recording mode
+    pub draft_recording_stems: RecordingStems,
+    /// Draft Auto DJ track source
+    pub draft_auto_dj_source: AutoDjSource,
+    /// Draft Auto DJ transition style
+    pub draft_auto_dj_transition: AutoDjTransition,
+    /// Draft Auto DJ transition length (bars)
+    pub draft_auto_dj_bars: u32,
+    /// Draft Auto DJ start-on-launch
+    pub draft_auto_dj_start_on_launch: bool,
+    /// Draft sample pack loaded at startup
+    pub draft_sampler_pack: Option<String>,
+    /// Sample packs found in the collection
+    pub available_sampler_packs: Vec<String>,
     /// Draft slicer buffer bars (1, 4, 8, or 16)
     pub draft_slicer_buffer_bars: u32,
     /// Draft auto-gain enabled
@@ -501,6 +662,8 @@ pub struct SettingsState {
     pub draft_prerelease_channel: bool,
     /// Whether set recording is currently active
     pub recording_active: bool,
+    /// Whether Auto DJ is currently running
+    pub auto_dj_active: bool,
     /// Whether the set recording confirmation dialog is showing
     pub recording_confirm: bool,
     /// Whether the power off confirmation dialog is showing
@@ -533,6 +696,19 @@ impl SettingsState {
             available_theme_names: Vec::new(),
             draft_phase_sync: config.audio.phase_sync,
             draft_auto_cue: config.audio.auto_cue,
+            draft_crossfader_curve: config.audio.crossfader.curve,
+            draft_crossfader_reverse: config.audio.crossfader.reverse,
+            draft_eq_model: config.audio.eq.model,
+            draft_recording_format: config.recording.format,
+            draft_recording_split: config.recording.split,
+            draft_recording_chapters: config.recording.embed_chapters,
+            draft_recording_stems: config.recording.stems,
+            draft_auto_dj_source: config.auto_dj.source,
+            draft_auto_dj_transition: config.auto_dj.transition,
+            draft_auto_dj_bars: config.auto_dj.validated_transition_bars(),
+            draft_auto_dj_start_on_launch: config.auto_dj.start_on_launch,
+            draft_sampler_pack: config.sampler.pack.clone(),
+            available_sampler_packs: mesh_core::sampler::list_packs(&config.collection_path),
             draft_slicer_buffer_bars: config.slicer.validated_buffer_bars(),
             draft_auto_gain_enabled: config.audio.loudness.auto_gain_enabled,
             draft_target_lufs_index: lufs_to_index(config.audio.loudness.target_lufs),
@@ -554,6 +730,7 @@ impl SettingsState {
             available_devices,
             draft_prerelease_channel: config.updates.prerelease_channel,
             recording_active: false,
+            auto_dj_active: false,
             recording_confirm: false,
             power_off_confirm: false,
             status: String::new(),
@@ -573,6 +750,18 @@ impl SettingsState {
             theme: self.draft_theme.clone(),
             phase_sync: self.draft_phase_sync,
             auto_cue: self.draft_auto_cue,
+            crossfader_curve: self.draft_crossfader_curve,
+            crossfader_reverse: self.draft_crossfader_reverse,
+            eq_model: self.draft_eq_model,
+            recording_format: self.draft_recording_format,
+            recording_split: self.draft_recording_split,
+            recording_chapters: self.draft_recording_chapters,
+            recording_stems: self.draft_recording_stems,
+            auto_dj_source: self.draft_auto_dj_source,
+            auto_dj_transition: self.draft_auto_dj_transition,
+            auto_dj_bars: self.draft_auto_dj_bars,
+            auto_dj_start_on_launch: self.draft_auto_dj_start_on_launch,
+            sampler_pack: self.draft_sampler_pack.clone(),
             slicer_buffer_bars: self.draft_slicer_buffer_bars,
             auto_gain_enabled: self.draft_auto_gain_enabled,
             target_lufs_index: self.draft_target_lufs_index,
@@ -604,6 +793,18 @@ impl SettingsState {
             || self.draft_theme != snap.theme
             || self.draft_phase_sync != snap.phase_sync
             || self.draft_auto_cue != snap.auto_cue
+            || self.draft_crossfader_curve != snap.crossfader_curve
+            || self.draft_crossfader_reverse != snap.crossfader_reverse
+            || self.draft_eq_model != snap.eq_model
+            || self.draft_recording_format != snap.recording_format
+            || self.draft_recording_split != snap.recording_split
+            || self.draft_recording_chapters != snap.recording_chapters
+            || self.draft_recording_stems != snap.recording_stems
+            || self.draft_auto_dj_source != snap.auto_dj_source
+            || self.draft_auto_dj_transition != snap.auto_dj_transition
+            || self.draft_auto_dj_bars != snap.auto_dj_bars
+            || self.draft_auto_dj_start_on_launch != snap.auto_dj_start_on_launch
+            || self.draft_sampler_pack != snap.sampler_pack
             || self.draft_slicer_buffer_bars != snap.slicer_buffer_bars
             || self.draft_auto_gain_enabled != snap.auto_gain_enabled
             || self.draft_target_lufs_index != snap.target_lufs_index
@@ -643,6 +844,18 @@ struct SettingsSnapshot {
     theme: String,
     phase_sync: bool,
     auto_cue: bool,
+    crossfader_curve: CrossfaderCurve,
+    crossfader_reverse: bool,
+    eq_model: EqModel,
+    recording_format: RecordingFormat,
+    recording_split: RecordingSplit,
+    recording_chapters: bool,
+    recording_stems: RecordingStems,
+    auto_dj_source: AutoDjSource,
+    auto_dj_transition: AutoDjTransition,
+    auto_dj_bars: u32,
+    auto_dj_start_on_launch: bool,
+    sampler_pack: Option<String>,
     slicer_buffer_bars: u32,
     auto_gain_enabled: bool,
     target_lufs_index: usize,
@@ -1087,7 +1300,7 @@ pub fn view(state: &SettingsState) -> Element<'_, Message> {
         let (title, description, confirm_label, confirm_style) = if state.recording_active {
             ("Stop Recording?", "The current recording will be finalized and saved.", "Stop", button::danger as fn(&iced::Theme, button::Status) -> button::Style)
         } else {
-            ("Start Recording?", "Master output will be recorded to WAV on all connected USB sticks.", "Record", button::primary as fn(&iced::Theme, button::Status) -> button::Style)
+            ("Start Recording?", "Master output will be recorded on all connected USB sticks.", "Record", button::primary as fn(&iced::Theme, button::Status) -> button::Style)
         };
 
         let confirm_btn: Element<Message> = button(text(confirm_label).size(sz(16.0)))
@@ -132,11 +132,12 @@ pub const ROW_1: &[KeyDef] = &[
     KeyDef::normal('p'),
 ];
 
-/// Row 2: home row
+/// Row 2: home row, plus `:` and `"` for search filters (`bpm:124`, `tag:"peak time"`)
 pub const ROW_2: &[KeyDef] = &[
     KeyDef::normal('a'), KeyDef::normal('s'), KeyDef::normal('d'),
     KeyDef::normal('f'), KeyDef::normal('g'), KeyDef::normal('h'),
     KeyDef::normal('j'), KeyDef::normal('k'), KeyDef::normal('l'),
+    KeyDef::symbol(':', '"'),
 ];
 
 /// Row 3: bottom row with shift and backspace
@@ -166,6 +166,7 @@ impl StemPresetConfig {
             let source = match effect.source.as_str() {
                 "pd" => EffectSourceType::Pd,
                 "clap" => EffectSourceType::Clap,
+                "native" => EffectSourceType::Native,
                 _ => EffectSourceType::Pd, // fallback
             };
 
@@ -68,6 +68,7 @@ where
         // Clear table selection when folder changes
         self.table_state.clear_selection();
         self.table_state.search_query.clear();
+        self.table_state.search_matches = None;
     }
 
     /// Handle a tree message, returning true if the folder changed
@@ -17,6 +17,7 @@ use iced::widget::{button, column, container, mouse_area, row, scrollable, text,
 use iced::{Background, Border, Color, Element, Length, Padding, Point, Theme};
 use std::collections::HashSet;
 use crate::font::sz;
+use mesh_core::search::{self, SearchFilter, SearchQuery, SearchVocabulary};
 use std::hash::Hash;
 use std::sync::LazyLock;
 
@@ -244,6 +245,22 @@ pub enum TrackColumn {
     Duration,
     /// LUFS loudness
     Lufs,
+    // Embedded tag columns (mesh-cue library view)
+    /// Album title
+    Album,
+    /// Release year
+    Year,
+    /// Record label
+    Label,
+    /// Tag comment
+    Comment,
+    // Rating and play history
+    /// Star rating with colour label swatch
+    Rating,
+    /// Times played across all sessions
+    Plays,
+    /// Time since the most recent play
+    LastPlayed,
     // Graph view analysis columns
     /// Raw PCA-128 cosine distance
     HnswDist,
@@ -255,7 +272,7 @@ pub enum TrackColumn {
     CoplayCount,
     /// ML aggression score [0-1]
     Aggression,
-    /// Primary genre string
+    /// Primary genre string (embedded tag in the library, ML genre in graph view)
     Genre,
     /// Stem balance mini-bar (vocal/drums/bass/other)
     StemBalance,
@@ -276,6 +293,13 @@ impl TrackColumn {
             Self::Key => "Key",
             Self::Duration => "Duration",
             Self::Lufs => "LUFS",
+            Self::Album => "Album",
+            Self::Year => "Year",
+            Self::Label => "Label",
+            Self::Comment => "Comment",
+            Self::Rating => "Rating",
+            Self::Plays => "Plays",
+            Self::LastPlayed => "Last",
             Self::HnswDist => "Vector",
             Self::KeyScore => "Key%",
             Self::EnergyMatch => "Intens",
@@ -304,6 +328,13 @@ impl TrackColumn {
             Self::Key => Length::Fixed(50.0),
             Self::Duration => Length::Fixed(70.0),
             Self::Lufs => Length::Fixed(55.0),
+            Self::Album => Length::Fixed(120.0),
+            Self::Year => Length::Fixed(45.0),
+            Self::Label => Length::Fixed(100.0),
+            Self::Comment => Length::Fixed(140.0),
+            Self::Rating => Length::Fixed(70.0),
+            Self::Plays => Length::Fixed(40.0),
+            Self::LastPlayed => Length::Fixed(45.0),
             Self::HnswDist => Length::Fixed(55.0),
             Self::KeyScore => Length::Fixed(55.0),
             Self::EnergyMatch => Length::Fixed(55.0),
@@ -323,10 +354,37 @@ impl TrackColumn {
             TrackColumn::Name,
             TrackColumn::Artist,
             TrackColumn::Tags,
+            TrackColumn::Rating,
             TrackColumn::Bpm,
             TrackColumn::Key,
             TrackColumn::Lufs,
             TrackColumn::Duration,
+            TrackColumn::Plays,
+        ]
+    }
+
+    /// Get columns for the mesh-cue library view: the default columns plus
+    /// the embedded tag metadata (album, genre, year, label, comment) and
+    /// play history
+    pub fn library() -> &'static [TrackColumn] {
+        &[
+            TrackColumn::Order,
+            TrackColumn::Cues,
+            TrackColumn::Name,
+            TrackColumn::Artist,
+            TrackColumn::Album,
+            TrackColumn::Tags,
+            TrackColumn::Genre,
+            TrackColumn::Year,
+            TrackColumn::Label,
+            TrackColumn::Comment,
+            TrackColumn::Rating,
+            TrackColumn::Bpm,
+            TrackColumn::Key,
+            TrackColumn::Lufs,
+            TrackColumn::Duration,
+            TrackColumn::Plays,
+            TrackColumn::LastPlayed,
         ]
     }
 
@@ -368,8 +426,24 @@ pub struct TrackRow<Id: Clone> {
     pub lufs: Option<f32>,
     /// Tags to display (user-defined + suggestion reason)
     pub tags: Vec<TrackTag>,
+    /// Album from embedded tags
+    pub album: Option<String>,
+    /// Release year from embedded tags
+    pub year: Option<i32>,
+    /// Record label from embedded tags
+    pub label: Option<String>,
+    /// Comment from embedded tags
+    pub comment: Option<String>,
     /// Number of hot cue points set (0 = hide in browser)
     pub cue_count: u8,
+    /// Star rating 0-5 (0 = unrated)
+    pub rating: u8,
+    /// Colour label shown next to the stars
+    pub color_label: Option<Color>,
+    /// Times played across all sessions and databases
+    pub play_count: u32,
+    /// Unix ms timestamp of the most recent play
+    pub last_played: Option<i64>,
     /// Whether this track should appear dimmed (e.g. already played this session)
     pub dimmed: bool,
     /// Absolute file path (cached for fast dimming lookups without DB queries)
@@ -404,7 +478,15 @@ impl<Id: Clone> TrackRow<Id> {
             duration: None,
             lufs: None,
             tags: Vec::new(),
+            album: None,
+            year: None,
+            label: None,
+            comment: None,
             cue_count: 0,
+            rating: 0,
+            color_label: None,
+            play_count: 0,
+            last_played: None,
             dimmed: false,
             track_path: None,
             is_global_suggestion: false,
@@ -457,6 +539,37 @@ impl<Id: Clone> TrackRow<Id> {
         self
     }
 
+    /// Set the embedded tag metadata shown in the library columns
+    pub fn with_tag_metadata(
+        mut self,
+        album: Option<String>,
+        genre: Option<String>,
+        year: Option<i32>,
+        label: Option<String>,
+        comment: Option<String>,
+    ) -> Self {
+        self.album = album;
+        self.genre = genre;
+        self.year = year;
+        self.label = label;
+        self.comment = comment;
+        self
+    }
+
+    /// Set the star rating (0-5) and colour label
+    pub fn with_rating(mut self, rating: u8, color_label: Option<Color>) -> Self {
+        self.rating = rating.min(5);
+        self.color_label = color_label;
+        self
+    }
+
+    /// Set the play count and last-played timestamp (Unix ms)
+    pub fn with_play_stats(mut self, play_count: u32, last_played: Option<i64>) -> Self {
+        self.play_count = play_count;
+        self.last_played = last_played;
+        self
+    }
+
     /// Set tags for this row
     pub fn with_tags(mut self, tags: Vec<TrackTag>) -> Self {
         self.tags = tags;
@@ -505,6 +618,25 @@ impl<Id: Clone> TrackRow<Id> {
             .map(|l| format!("{:.1}", l))
             .unwrap_or_else(|| "-".to_string())
     }
+
+    /// Format the rating as filled stars (empty when unrated)
+    pub fn format_rating(&self) -> String {
+        "★".repeat(self.rating as usize)
+    }
+
+    /// Format the last play relative to `now_ms`: "today", "3d", "2w", "5mo", "1y"
+    pub fn format_last_played(&self, now_ms: i64) -> String {
+        const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+        let Some(last) = self.last_played else { return "-".to_string() };
+        let days = (now_ms - last).max(0) / DAY_MS;
+        match days {
+            0 => "today".to_string(),
+            1..=6 => format!("{}d", days),
+            7..=59 => format!("{}w", days / 7),
+            60..=729 => format!("{}mo", days / 30),
+            _ => format!("{}y", days / 365),
+        }
+    }
 }
 
 /// Sort a slice of tracks by a specific column and direction
@@ -585,11 +717,47 @@ pub fn compare_tracks_by_column<Id: Clone>(
             let b_genre = b.genre.as_deref().unwrap_or("");
             a_genre.cmp(b_genre)
         }
+        TrackColumn::Album => compare_opt_str(a.album.as_deref(), b.album.as_deref()),
+        TrackColumn::Label => compare_opt_str(a.label.as_deref(), b.label.as_deref()),
+        TrackColumn::Comment => compare_opt_str(a.comment.as_deref(), b.comment.as_deref()),
+        TrackColumn::Year => match (a.year, b.year) {
+            (Some(a_year), Some(b_year)) => a_year.cmp(&b_year),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        TrackColumn::Rating => a.rating.cmp(&b.rating),
+        TrackColumn::Plays => a.play_count.cmp(&b.play_count),
+        TrackColumn::LastPlayed => match (a.last_played, b.last_played) {
+            (Some(a_last), Some(b_last)) => a_last.cmp(&b_last),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
         TrackColumn::StemBalance => Ordering::Equal, // No meaningful sort for stem balance
         TrackColumn::FinalScore => compare_opt_f32(a.final_score, b.final_score),
     }
 }
 
+/// Current time in Unix ms (for relative "last played" labels)
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Compare two optional strings case-insensitively (None sorts to end)
+fn compare_opt_str(a: Option<&str>, b: Option<&str>) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a, b) {
+        (Some(a_val), Some(b_val)) => a_val.to_lowercase().cmp(&b_val.to_lowercase()),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 /// Compare two optional f32 values (None sorts to end)
 fn compare_opt_f32(a: Option<f32>, b: Option<f32>) -> std::cmp::Ordering {
     use std::cmp::Ordering;
@@ -606,6 +774,11 @@ fn compare_opt_f32(a: Option<f32>, b: Option<f32>) -> std::cmp::Ordering {
 pub struct TrackTableState<Id: Clone + Eq + Hash> {
     /// Current search query
     pub search_query: String,
+    /// Paths matching a structured search, evaluated by the app against its
+    /// database (None = filter plain words locally)
+    pub search_matches: Option<HashSet<String>>,
+    /// Tags and genres offered by search autocomplete
+    pub search_vocabulary: SearchVocabulary,
     /// Currently selected track IDs (supports multi-selection)
     pub selected: HashSet<Id>,
     /// Anchor point for Shift+click range selection
@@ -651,6 +824,8 @@ impl<Id: Clone + Eq + Hash> TrackTableState<Id> {
     pub fn new() -> Self {
         Self {
             search_query: String::new(),
+            search_matches: None,
+            search_vocabulary: SearchVocabulary::default(),
             selected: HashSet::new(),
             anchor: None,
             last_selected: None,
@@ -693,6 +868,32 @@ impl<Id: Clone + Eq + Hash> TrackTableState<Id> {
         self.search_query = query;
     }
 
+    /// The parsed search query, if it needs the database to evaluate
+    ///
+    /// The app runs it and stores the result in `search_matches`.
+    pub fn structured_search(&self) -> Option<SearchQuery> {
+        let query = SearchQuery::parse(&self.search_query);
+        query.is_structured().then_some(query)
+    }
+
+    /// Whether a track passes the current search
+    pub fn matches_search(&self, track: &TrackRow<Id>) -> bool {
+        if let Some(matches) = &self.search_matches {
+            return track.track_path.as_ref().is_some_and(|p| matches.contains(p));
+        }
+        // Plain words: every one must appear in "title artist"
+        let haystack = format!(
+            "{} {}",
+            track.title.to_lowercase(),
+            track.artist.as_deref().unwrap_or("").to_lowercase()
+        );
+        SearchQuery::parse(&self.search_query).terms.iter().all(|term| match &term.filter {
+            SearchFilter::Text(word) if !term.negated => haystack.contains(word.as_str()),
+            // Structured terms wait for `search_matches`
+            _ => true,
+        })
+    }
+
     /// Select a single track (clears other selections)
     /// Used for normal clicks without modifiers
     pub fn select(&mut self, id: Id) {
@@ -920,20 +1121,32 @@ where
     // Column headers
     let headers = build_headers(state, on_message.clone());
 
-    // Filter tracks by search query: split by whitespace, all tokens must appear
-    // anywhere in the combined "title artist" text (case-insensitive).
+    // Autocomplete for the term being typed
+    let completions = search::complete(&state.search_query, &state.search_vocabulary);
+    let suggestions: Option<Element<'a, Message>> = (!completions.is_empty()).then(|| {
+        let pills: Vec<Element<'a, Message>> = completions
+            .into_iter()
+            .map(|c| {
+                let label = match c.detail {
+                    Some(detail) => format!("{}  {}", c.label, detail),
+                    None => c.label,
+                };
+                let on_msg = on_message.clone();
+                button(text(label).size(sz(11.0)))
+                    .padding([2, 8])
+                    .style(button::secondary)
+                    .on_press(on_msg(TrackTableMessage::SearchChanged(c.query)))
+                    .into()
+            })
+            .collect();
+        row(pills).spacing(4).padding([0, 4]).into()
+    });
+
+    // Filter tracks by search query: plain words locally, structured
+    // queries via the matches the app evaluated
     let mut filtered: Vec<_> = tracks
         .iter()
-        .filter(|t| {
-            if state.search_query.is_empty() { return true; }
-            let title_lc = t.title.to_lowercase();
-            let artist_lc = t.artist.as_deref().unwrap_or("").to_lowercase();
-            let haystack = format!("{} {}", title_lc, artist_lc);
-            state.search_query
-                .to_lowercase()
-                .split_whitespace()
-                .all(|token| haystack.contains(token))
-        })
+        .filter(|t| state.search_query.is_empty() || state.matches_search(t))
         .collect();
 
     // Sort tracks by selected column
@@ -985,20 +1198,21 @@ where
     .on_release(on_msg_table_drop(TrackTableMessage::DropReceivedOnTable))
     .into();
 
-    column![
-        search,
-        container(headers).style(|theme: &Theme| {
+    let mut layout = column![search].spacing(2);
+    if let Some(suggestions) = suggestions {
+        layout = layout.push(suggestions);
+    }
+    layout
+        .push(container(headers).style(|theme: &Theme| {
             container::Style {
                 background: Some(Background::Color(
                     theme.extended_palette().background.weak.color,
                 )),
                 ..Default::default()
             }
-        }),
-        track_list,
-    ]
-    .spacing(2)
-    .into()
+        }))
+        .push(track_list)
+        .into()
 }
 
 /// Build column headers row
@@ -1123,6 +1337,29 @@ where
             .into();
     }
 
+    // Rating renders as the colour label swatch followed by stars
+    if column == TrackColumn::Rating {
+        let swatch: Element<'a, Message> = match track.color_label {
+            Some(color) => container(text(""))
+                .width(Length::Fixed(8.0))
+                .height(Length::Fixed(8.0))
+                .style(move |_theme: &Theme| container::Style {
+                    background: Some(Background::Color(color)),
+                    border: Border { radius: 2.0.into(), ..Default::default() },
+                    ..Default::default()
+                })
+                .into(),
+            None => container(text("")).width(Length::Fixed(8.0)).into(),
+        };
+        let stars = text(track.format_rating())
+            .size(sz(11.0))
+            .wrapping(iced::widget::text::Wrapping::None);
+        return container(row![swatch, stars].spacing(4).align_y(iced::Alignment::Center))
+            .width(state.column_width(column))
+            .clip(true)
+            .into();
+    }
+
     let is_editing = state.is_editing(&track.id, column);
 
     // StemBalance renders as four small colored bars — handle before text path
@@ -1181,6 +1418,14 @@ where
         TrackColumn::CoplayCount => track.coplay_count.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
         TrackColumn::Aggression => track.aggression.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
         TrackColumn::Genre => track.genre.clone().unwrap_or_else(|| "-".to_string()),
+        TrackColumn::Album => track.album.clone().unwrap_or_else(|| "-".to_string()),
+        TrackColumn::Year => track.year.map(|y| y.to_string()).unwrap_or_else(|| "-".to_string()),
+        TrackColumn::Label => track.label.clone().unwrap_or_else(|| "-".to_string()),
+        TrackColumn::Comment => track.comment.clone().unwrap_or_else(|| "-".to_string()),
+        // Normally drawn with its colour swatch above; plain stars otherwise
+        TrackColumn::Rating => track.format_rating(),
+        TrackColumn::Plays => if track.play_count > 0 { track.play_count.to_string() } else { "-".to_string() },
+        TrackColumn::LastPlayed => track.format_last_played(now_ms()),
         TrackColumn::FinalScore => track.final_score.map(|v| format!("{:.0}%", v * 100.0)).unwrap_or_else(|| "-".to_string()),
     };
 
@@ -1370,6 +1615,24 @@ mod tests {
         assert_eq!(row.format_duration(), "3:05");
     }
 
+    #[test]
+    fn test_rating_and_play_stats_formatting() {
+        const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+        let now = 1_000 * DAY_MS;
+        let row = TrackRow::new("1", "Test Track", 1).with_rating(7, None);
+        assert_eq!(row.format_rating(), "★★★★★");
+        assert_eq!(row.format_last_played(now), "-");
+
+        let at = |days_ago: i64| TrackRow::new("1", "Test Track", 1)
+            .with_play_stats(3, Some(now - days_ago * DAY_MS))
+            .format_last_played(now);
+        assert_eq!(at(0), "today");
+        assert_eq!(at(3), "3d");
+        assert_eq!(at(15), "2w");
+        assert_eq!(at(100), "3mo");
+        assert_eq!(at(800), "2y");
+    }
+
     #[test]
     fn test_table_state() {
         let mut state: TrackTableState<String> = TrackTableState::new();
@@ -1394,4 +1657,26 @@ mod tests {
         assert_eq!(state.sort_column, TrackColumn::Bpm);
         assert!(state.sort_ascending); // Reset for new column
     }
+
+    #[test]
+    fn test_search_filtering() {
+        let mut state: TrackTableState<String> = TrackTableState::new();
+        let mut daft = TrackRow::new("1".to_string(), "Around the World", 1).with_artist("Daft Punk");
+        daft.track_path = Some("/music/daft.flac".to_string());
+        let other = TrackRow::new("2".to_string(), "World Hold On", 2);
+
+        // Plain words are matched locally against title and artist
+        state.set_search("world daft".to_string());
+        assert!(state.structured_search().is_none());
+        assert!(state.matches_search(&daft));
+        assert!(!state.matches_search(&other));
+
+        // Structured terms are left to the app until it sets the matches
+        state.set_search("world bpm:120-130".to_string());
+        assert!(state.structured_search().is_some());
+        assert!(state.matches_search(&other));
+        state.search_matches = Some(HashSet::from(["/music/daft.flac".to_string()]));
+        assert!(state.matches_search(&daft));
+        assert!(!state.matches_search(&other));
+    }
 }
@@ -69,6 +69,8 @@ pub enum TreeIcon {
     Track,
     /// Collection (special folder)
     Collection,
+    /// Rule-based (smart) playlist
+    SmartPlaylist,
 }
 
 impl TreeIcon {
@@ -79,6 +81,7 @@ impl TreeIcon {
             TreeIcon::Playlist | TreeIcon::PlaylistOpen => "\u{1F4CB}",  // 📋
             TreeIcon::Track => "\u{1F3B5}",  // 🎵
             TreeIcon::Collection => "\u{1F4BF}",  // 💿
+            TreeIcon::SmartPlaylist => "\u{26A1}",  // ⚡
         }
     }
 }
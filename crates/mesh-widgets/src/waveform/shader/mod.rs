@@ -24,7 +24,7 @@ use iced::widget::shader;
 use iced::{Color, Element, Length, Rectangle, Theme};
 
 use super::state::{
-    CombinedState, OverviewPeakCache, PlayerCanvasState, SharedPeakBuffer, ZoomedViewMode,
+    CombinedState, OverviewPeakCache, OverviewState, PlayerCanvasState, SharedPeakBuffer, ZoomedViewMode,
     COMBINED_WAVEFORM_GAP, WAVEFORM_HEIGHT, ZOOMED_WAVEFORM_HEIGHT,
     MIN_ZOOM_BARS, MAX_ZOOM_BARS, ZOOM_PIXELS_PER_LEVEL,
     next_peak_generation,
@@ -1762,7 +1762,7 @@ pub fn waveform_shader_overview<'a, Message: Clone + 'a>(
     deck_idx: usize,
     on_action: impl Fn(WaveformAction) -> Message + 'a,
 ) -> Element<'a, Message> {
-    shader(WaveformProgram {
+    let waveform = shader(WaveformProgram {
         state,
         deck_idx,
         is_overview: true,
@@ -1771,7 +1771,8 @@ pub fn waveform_shader_overview<'a, Message: Clone + 'a>(
     })
     .width(Length::Fill)
     .height(Length::Fixed(WAVEFORM_HEIGHT))
-    .into()
+    .into();
+    with_overview_strips(waveform, &state.deck(deck_idx).overview)
 }
 
 /// Create the full 4-deck waveform display using pure GPU shader rendering.
@@ -1963,6 +1964,152 @@ fn view_stem_indicators<'a, Message: Clone + 'a>(
     }
 }
 
+// =============================================================================
+// Song-structure and key strips — zero-GPU overlays on the overview
+// =============================================================================
+
+/// Height of the song-structure strip along the top edge of the overview
+const PHRASE_STRIP_HEIGHT: f32 = 4.0;
+/// Height of the key strip along the bottom edge of the overview
+const KEY_STRIP_HEIGHT: f32 = 12.0;
+
+/// One coloured span of an overview strip (normalized track positions)
+struct StripSpan {
+    start: f64,
+    end: f64,
+    color: Color,
+    label: Option<String>,
+}
+
+/// Overlay the detected song sections and key changes on an overview waveform.
+///
+/// Sections are drawn in their kind's colour as a thin strip along the top
+/// edge, so phrase boundaries line up with the waveform below. When the
+/// track changes key, a labelled key strip runs along the bottom edge
+/// (a single-key track already shows its key in the header). Implemented
+/// with `container` + `FillPortion` segments like the peak meter (no Canvas).
+fn with_overview_strips<'a, Message: 'a>(
+    waveform: Element<'a, Message>,
+    overview: &OverviewState,
+) -> Element<'a, Message> {
+    use iced::widget::{column, stack, Space};
+
+    let show_phrases = !overview.phrase_sections.is_empty();
+    let show_keys = overview.key_segments.len() > 1;
+    if !(show_phrases || show_keys) || overview.duration_samples == 0 {
+        return waveform;
+    }
+    let duration = overview.duration_samples as f64;
+
+    let mut strips = column![].width(Length::Fill).height(Length::Fill);
+    if show_phrases {
+        let spans = overview
+            .phrase_sections
+            .iter()
+            .map(|section| StripSpan {
+                start: section.start_sample as f64 / duration,
+                end: section.end_sample as f64 / duration,
+                color: crate::parse_hex_color(section.kind.color())
+                    .map(|c| Color { a: 0.85, ..c })
+                    .unwrap_or(Color::WHITE),
+                label: None,
+            })
+            .collect();
+        strips = strips.push(strip_row(spans, PHRASE_STRIP_HEIGHT));
+    }
+    if show_keys {
+        let spans = overview
+            .key_segments
+            .iter()
+            .map(|segment| StripSpan {
+                start: segment.start_sample as f64 / duration,
+                end: segment.end_sample as f64 / duration,
+                color: key_color(&segment.key),
+                label: Some(segment.key.clone()),
+            })
+            .collect();
+        strips = strips
+            .push(Space::new().height(Length::Fill))
+            .push(strip_row(spans, KEY_STRIP_HEIGHT));
+    }
+
+    stack![waveform, strips].into()
+}
+
+/// Lay out spans as a full-width row, with transparent gaps between them
+fn strip_row<'a, Message: 'a>(spans: Vec<StripSpan>, height: f32) -> Element<'a, Message> {
+    use iced::widget::{container, row, text, Space};
+
+    // FillPortion scale: 10 000 units ≈ full width
+    const SCALE: f64 = 10_000.0;
+
+    let seg = |color: Color, portion: f64, label: Option<String>| -> Element<'a, Message> {
+        let content: Element<'a, Message> = match label {
+            Some(label) => text(label)
+                .size(height - 3.0)
+                .color(Color::from_rgba(1.0, 1.0, 1.0, 0.9))
+                .into(),
+            None => Space::new().into(),
+        };
+        container(content)
+            .width(Length::FillPortion(((portion * SCALE) as u16).max(1)))
+            .height(height)
+            .padding([0, 2])
+            .clip(true)
+            .style(move |_: &Theme| container::Style {
+                background: Some(color.into()),
+                ..container::Style::default()
+            })
+            .into()
+    };
+
+    let mut segs: Vec<Element<'a, Message>> = Vec::with_capacity(spans.len() * 2 + 1);
+    let mut cursor = 0.0;
+    for span in spans {
+        let start = span.start.clamp(cursor, 1.0);
+        let end = span.end.clamp(start, 1.0);
+        if start > cursor {
+            segs.push(seg(Color::TRANSPARENT, start - cursor, None));
+        }
+        if end > start {
+            segs.push(seg(span.color, end - start, span.label));
+        }
+        cursor = end;
+    }
+    if cursor < 1.0 {
+        segs.push(seg(Color::TRANSPARENT, 1.0 - cursor, None));
+    }
+
+    row(segs).width(Length::Fill).height(height).into()
+}
+
+/// Colour for a key, following the Camelot wheel: the hue walks around the
+/// wheel, minor keys (A) are darker than their relative majors (B)
+fn key_color(key: &str) -> Color {
+    let Some(parsed) = mesh_core::music::MusicalKey::parse(key) else {
+        return Color::from_rgba(0.5, 0.5, 0.5, 0.75);
+    };
+    let (number, letter) = parsed.camelot();
+    let hue = (number.saturating_sub(1) as f32) / 12.0;
+    let value = if letter == 'A' { 0.55 } else { 0.75 };
+
+    // HSV → RGB with fixed saturation
+    let saturation = 0.6;
+    let h6 = hue * 6.0;
+    let c = value * saturation;
+    let x = c * (1.0 - (h6 % 2.0 - 1.0).abs());
+    let (r, g, b) = match h6 as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    Color::from_rgba(r + m, g + m, b + m, 0.75)
+}
+
 // =============================================================================
 // Peak meter widget (PPM-style, vertical, Canvas-based)
 // =============================================================================
@@ -2183,7 +2330,7 @@ pub fn waveform_shader_single_overview<'a, Message: Clone + 'a>(
     stem_colors: [Color; 4],
     on_action: impl Fn(SingleDeckAction) -> Message + 'a,
 ) -> Element<'a, Message> {
-    shader(SingleDeckProgram {
+    let waveform = shader(SingleDeckProgram {
         state,
         playhead,
         stem_colors,
@@ -2193,7 +2340,8 @@ pub fn waveform_shader_single_overview<'a, Message: Clone + 'a>(
     })
     .width(Length::Fill)
     .height(Length::Fixed(WAVEFORM_HEIGHT))
-    .into()
+    .into();
+    with_overview_strips(waveform, &state.overview)
 }
 
 /// Create a combined single-deck shader waveform (zoomed + overview in a column).
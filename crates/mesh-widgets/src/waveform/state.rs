@@ -8,7 +8,7 @@ use super::CueMarker;
 use super::shader::PeakBuffer;
 use crate::{CUE_COLORS, STEM_COLORS};
 use iced::Color;
-use mesh_core::audio_file::{CuePoint, LoadedTrack, StemBuffers};
+use mesh_core::audio_file::{CuePoint, KeySegment, LoadedTrack, PhraseSection, StemBuffers};
 use std::cell::RefCell;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -434,6 +434,11 @@ pub struct OverviewState {
     pub slicer_current_slice: Option<u8>,
     /// Drop marker position in samples (for linked stem alignment visualization)
     pub drop_marker: Option<u64>,
+    /// Detected song sections (drawn as a coloured strip along the top edge)
+    pub phrase_sections: Vec<PhraseSection>,
+    /// Key timeline (drawn as a labelled strip along the bottom edge when the
+    /// track changes key)
+    pub key_segments: Vec<KeySegment>,
     /// Linked stem waveform peaks [stem_idx] - None if no linked stem for that slot
     /// When a stem has a linked stem and is active, this provides the peaks to display
     pub linked_stem_waveforms: [Option<Vec<(f32, f32)>>; 4],
@@ -498,6 +503,8 @@ impl OverviewState {
             slicer_region: None,
             slicer_current_slice: None,
             drop_marker: None,
+            phrase_sections: Vec::new(),
+            key_segments: Vec::new(),
             linked_stem_waveforms: [None, None, None, None],
             linked_drop_markers: [None, None, None, None],
             linked_durations: [None, None, None, None],
@@ -552,6 +559,16 @@ impl OverviewState {
         self.drop_marker = position;
     }
 
+    /// Set the detected song sections (sample positions, in track order)
+    pub fn set_phrase_sections(&mut self, sections: Vec<PhraseSection>) {
+        self.phrase_sections = sections;
+    }
+
+    /// Set the key timeline (sample positions, in track order)
+    pub fn set_key_segments(&mut self, segments: Vec<KeySegment>) {
+        self.key_segments = segments;
+    }
+
     /// Create an empty state with a message explaining why data is missing
     ///
     /// Used when a track doesn't have a cached waveform preview (needs re-analysis).
@@ -575,6 +592,8 @@ impl OverviewState {
             slicer_region: None,
             slicer_current_slice: None,
             drop_marker: None,
+            phrase_sections: Vec::new(),
+            key_segments: Vec::new(),
             linked_stem_waveforms: [None, None, None, None],
             linked_drop_markers: [None, None, None, None],
             linked_durations: [None, None, None, None],
@@ -621,6 +640,8 @@ impl OverviewState {
             slicer_region: None,
             slicer_current_slice: None,
             drop_marker: metadata.drop_marker,
+            phrase_sections: metadata.phrases.clone(),
+            key_segments: metadata.key_timeline.clone(),
             linked_stem_waveforms: [None, None, None, None],
             linked_drop_markers: [None, None, None, None],
             linked_durations: [None, None, None, None],
@@ -751,6 +772,8 @@ impl OverviewState {
             slicer_region: None,
             slicer_current_slice: None,
             drop_marker: track.metadata.drop_marker,
+            phrase_sections: track.metadata.phrases.clone(),
+            key_segments: track.metadata.key_timeline.clone(),
             linked_stem_waveforms: [None, None, None, None],
             linked_drop_markers: [None, None, None, None],
             linked_durations: [None, None, None, None],